@@ -8,9 +8,10 @@
 //! - Order cancellation
 //! - Market order execution
 //! - Book queries (BBO, depth)
+//! - Central order index insert/lookup
 
 use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
-use nanobook::{Exchange, OrderId, Price, Side, TimeInForce};
+use nanobook::{Exchange, OrderBook, OrderId, Price, Side, TimeInForce};
 
 /// Build an exchange with N price levels on each side.
 fn build_book(levels: usize, orders_per_level: usize) -> Exchange {
@@ -205,6 +206,31 @@ fn bench_depth_snapshot(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark: Insert 100k resting orders, then look each one up by ID
+fn bench_order_index(c: &mut Criterion) {
+    const ORDERS: u64 = 100_000;
+    let mut group = c.benchmark_group("order_index");
+    group.throughput(Throughput::Elements(ORDERS));
+    group.sample_size(10);
+
+    group.bench_function("insert_lookup_100k", |b| {
+        b.iter(|| {
+            let mut book = OrderBook::new();
+            for i in 0..ORDERS {
+                let price = Price(99_00 - (i % 500) as i64);
+                let order = book.create_order(Side::Buy, price, 100, TimeInForce::GTC);
+                book.add_order(order);
+            }
+            let found = (1..=ORDERS)
+                .filter(|&id| book.get_order(OrderId(id)).is_some())
+                .count();
+            black_box(found)
+        });
+    });
+
+    group.finish();
+}
+
 /// Benchmark: Replay events (only with event-log feature)
 #[cfg(feature = "event-log")]
 fn bench_replay(c: &mut Criterion) {
@@ -322,6 +348,7 @@ criterion_group!(
     bench_market_sweep,
     bench_bbo_query,
     bench_depth_snapshot,
+    bench_order_index,
     bench_replay,
     bench_event_apply,
     bench_multi_symbol,
@@ -337,6 +364,7 @@ criterion_group!(
     bench_market_sweep,
     bench_bbo_query,
     bench_depth_snapshot,
+    bench_order_index,
     bench_multi_symbol,
 );
 
@@ -0,0 +1,73 @@
+//! Async counterpart of the [`Broker`](crate::Broker) trait.
+//!
+//! Blocking clients serialize every request, which makes fetching quotes for
+//! hundreds of symbols slow. [`AsyncBroker`] mirrors `Broker` with methods that
+//! return futures, and [`AsyncBroker::quotes_batch`] issues quote requests
+//! concurrently with a bounded number in flight. Requires the `async` feature;
+//! the futures are runtime-agnostic, though the Binance implementation needs a
+//! Tokio runtime for its HTTP client.
+
+use std::future::Future;
+
+use futures_util::stream::{self, StreamExt};
+use nanobook::Symbol;
+
+use crate::error::BrokerError;
+use crate::types::*;
+
+/// Default number of requests [`AsyncBroker::quotes_batch`] keeps in flight.
+pub const DEFAULT_MAX_CONCURRENCY: usize = 16;
+
+/// An async broker connection. Method-for-method equivalent of `Broker`.
+pub trait AsyncBroker: Send + Sync {
+    /// Connect to the broker.
+    fn connect(&mut self) -> impl Future<Output = Result<(), BrokerError>> + Send;
+
+    /// Disconnect gracefully.
+    fn disconnect(&mut self) -> impl Future<Output = Result<(), BrokerError>> + Send;
+
+    /// Get all current positions.
+    fn positions(&self) -> impl Future<Output = Result<Vec<Position>, BrokerError>> + Send;
+
+    /// Get account summary (equity, buying power, etc.).
+    fn account(&self) -> impl Future<Output = Result<Account, BrokerError>> + Send;
+
+    /// Submit an order. Returns order ID.
+    fn submit_order(
+        &self,
+        order: &BrokerOrder,
+    ) -> impl Future<Output = Result<OrderId, BrokerError>> + Send;
+
+    /// Get status of a submitted order.
+    fn order_status(
+        &self,
+        id: OrderId,
+    ) -> impl Future<Output = Result<BrokerOrderStatus, BrokerError>> + Send;
+
+    /// Cancel a pending order.
+    fn cancel_order(&self, id: OrderId) -> impl Future<Output = Result<(), BrokerError>> + Send;
+
+    /// Get current quote for a symbol.
+    fn quote(&self, symbol: &Symbol) -> impl Future<Output = Result<Quote, BrokerError>> + Send;
+
+    /// Maximum number of concurrent requests used by [`quotes_batch`](Self::quotes_batch).
+    fn max_concurrency(&self) -> usize {
+        DEFAULT_MAX_CONCURRENCY
+    }
+
+    /// Fetch quotes for many symbols concurrently.
+    ///
+    /// At most [`max_concurrency`](Self::max_concurrency) requests are in
+    /// flight at once. Results are returned in the order of `symbols`, and a
+    /// failure for one symbol does not affect the others.
+    fn quotes_batch(
+        &self,
+        symbols: &[Symbol],
+    ) -> impl Future<Output = Vec<Result<Quote, BrokerError>>> + Send {
+        let limit = self.max_concurrency().max(1);
+        stream::iter(symbols)
+            .map(move |symbol| self.quote(symbol))
+            .buffered(limit)
+            .collect()
+    }
+}
@@ -0,0 +1,177 @@
+//! Async Binance spot broker (requires the `async` feature).
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use nanobook::Symbol;
+
+use super::async_client::AsyncBinanceClient;
+use super::{BinanceBroker, LotSizeCache};
+use crate::async_broker::{AsyncBroker, DEFAULT_MAX_CONCURRENCY};
+use crate::error::BrokerError;
+use crate::types::*;
+
+/// Binance spot broker implementing [`AsyncBroker`] over non-blocking reqwest.
+///
+/// Behaves like [`BinanceBroker`] (same symbol mapping, order cache, and
+/// status mapping), but every call returns a future. Use
+/// [`AsyncBroker::quotes_batch`] to fetch many tickers concurrently.
+pub struct AsyncBinanceBroker {
+    api_key: String,
+    secret_key: String,
+    testnet: bool,
+    client: Option<AsyncBinanceClient>,
+    quote_asset: String,
+    max_concurrency: usize,
+    /// Orders submitted in this session: id → (Binance pair, original order).
+    orders: Mutex<HashMap<OrderId, (String, BrokerOrder)>>,
+    /// `LOT_SIZE` filters fetched so far, by Binance pair.
+    lot_sizes: LotSizeCache,
+}
+
+impl AsyncBinanceBroker {
+    /// Create a new async Binance broker handle (not yet connected).
+    pub fn new(api_key: &str, secret_key: &str, testnet: bool) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            secret_key: secret_key.to_string(),
+            testnet,
+            client: None,
+            quote_asset: "USDT".to_string(),
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            orders: Mutex::new(HashMap::new()),
+            lot_sizes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the quote asset (default "USDT").
+    pub fn with_quote_asset(mut self, quote: &str) -> Self {
+        self.quote_asset = quote.to_string();
+        self
+    }
+
+    /// Set the number of concurrent requests used by `quotes_batch`.
+    pub fn with_max_concurrency(mut self, limit: usize) -> Self {
+        self.max_concurrency = limit.max(1);
+        self
+    }
+
+    fn to_binance_symbol(&self, symbol: &Symbol) -> String {
+        format!("{}{}", symbol.as_str(), self.quote_asset)
+    }
+
+    fn require_client(&self) -> Result<&AsyncBinanceClient, BrokerError> {
+        self.client.as_ref().ok_or(BrokerError::NotConnected)
+    }
+
+    fn cached_order(&self, id: OrderId) -> Result<(String, BrokerOrder), BrokerError> {
+        let orders = self.orders.lock().unwrap_or_else(|e| e.into_inner());
+        orders.get(&id).cloned().ok_or_else(|| {
+            BrokerError::Order(format!("order {} was not submitted in this session", id.0))
+        })
+    }
+}
+
+impl AsyncBroker for AsyncBinanceBroker {
+    async fn connect(&mut self) -> Result<(), BrokerError> {
+        let client = AsyncBinanceClient::new(&self.api_key, &self.secret_key, self.testnet);
+        client.ping().await?;
+        self.client = Some(client);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<(), BrokerError> {
+        self.client = None;
+        Ok(())
+    }
+
+    async fn positions(&self) -> Result<Vec<Position>, BrokerError> {
+        let info = self.require_client()?.account_info().await?;
+        Ok(BinanceBroker::positions_from_info(&info))
+    }
+
+    async fn account(&self) -> Result<Account, BrokerError> {
+        let info = self.require_client()?.account_info().await?;
+        Ok(BinanceBroker::account_from_info(&self.quote_asset, &info))
+    }
+
+    async fn submit_order(&self, order: &BrokerOrder) -> Result<OrderId, BrokerError> {
+        let client = self.require_client()?;
+        let positions = if order.reduce_only {
+            self.positions().await?
+        } else {
+            Vec::new()
+        };
+        let order =
+            &*order.resolve_reduce_only_scaled(&positions, BinanceBroker::POSITION_DECIMALS)?;
+        let binance_sym = self.to_binance_symbol(&order.symbol);
+        let cached = self
+            .lot_sizes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&binance_sym)
+            .copied();
+        let lot = match cached {
+            Some(lot) => lot,
+            None if order.quantity_scaled.is_some() => {
+                let lot = client.lot_size(&binance_sym).await?;
+                BinanceBroker::lot_size_for(&self.lot_sizes, order, &binance_sym, || Ok(lot))?
+            }
+            None => None,
+        };
+        let params = BinanceBroker::order_params(order, lot.as_ref())?;
+
+        let resp = client
+            .submit_order(
+                &binance_sym,
+                params.side,
+                params.order_type,
+                &params.quantity,
+                params.price.as_deref(),
+                params.time_in_force,
+            )
+            .await?;
+
+        let id = OrderId(resp.order_id);
+        self.orders
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id, (binance_sym, order.clone()));
+        Ok(id)
+    }
+
+    async fn order_status(&self, id: OrderId) -> Result<BrokerOrderStatus, BrokerError> {
+        let (binance_sym, order) = self.cached_order(id)?;
+        let resp = self
+            .require_client()?
+            .order_status(&binance_sym, id.0)
+            .await?;
+        let mut status = BinanceBroker::status_from_response(id, &resp, Some(order.quantity));
+        if BinanceBroker::has_fills(&resp) {
+            let trades = self
+                .require_client()?
+                .order_trades(&binance_sym, id.0)
+                .await?;
+            status.commission_cents =
+                BinanceBroker::commission_cents(&trades, order.symbol.as_str(), &self.quote_asset);
+        }
+        Ok(status)
+    }
+
+    async fn cancel_order(&self, id: OrderId) -> Result<(), BrokerError> {
+        let (binance_sym, _) = self.cached_order(id)?;
+        self.require_client()?
+            .cancel_order(&binance_sym, id.0)
+            .await
+    }
+
+    async fn quote(&self, symbol: &Symbol) -> Result<Quote, BrokerError> {
+        let binance_sym = self.to_binance_symbol(symbol);
+        let ticker = self.require_client()?.book_ticker(&binance_sym).await?;
+        Ok(BinanceBroker::quote_from_ticker(symbol, &ticker))
+    }
+
+    fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+}
@@ -0,0 +1,285 @@
+//! Non-blocking Binance REST client (requires the `async` feature).
+//!
+//! Same endpoints and validation as [`BinanceClient`](super::client::BinanceClient),
+//! built on `reqwest::Client` so many requests can be in flight at once.
+//! Requests share the client's [`RateLimiter`] and are retried per its
+//! [`RetryPolicy`], so a large `quotes_batch` queues behind the limiter
+//! instead of bursting past Binance's weight limits.
+//! Must be driven from within a Tokio runtime.
+
+use log::debug;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+
+use super::auth;
+use super::client::{
+    base_url, current_timestamp_ms, new_order_query, parse_json, validate_query_param,
+};
+use super::transport::{
+    HttpResponse, Method, RateLimiter, RetryPolicy, retry_after, send_with_retry_async,
+};
+use super::types::{AccountInfo, AccountTrade, BookTicker, ExchangeInfo, LotSize, OrderResponse};
+use crate::error::BrokerError;
+
+/// Async Binance REST client.
+pub struct AsyncBinanceClient {
+    client: Client,
+    limiter: RateLimiter,
+    retry: RetryPolicy,
+    api_key: String,
+    secret_key: String,
+    base_url: String,
+}
+
+impl Drop for AsyncBinanceClient {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.api_key);
+        zeroize::Zeroize::zeroize(&mut self.secret_key);
+    }
+}
+
+impl AsyncBinanceClient {
+    /// Create a new async Binance client.
+    pub fn new(api_key: &str, secret_key: &str, testnet: bool) -> Self {
+        Self {
+            client: Client::new(),
+            limiter: RateLimiter::default(),
+            retry: RetryPolicy::default(),
+            api_key: api_key.to_string(),
+            secret_key: secret_key.to_string(),
+            base_url: base_url(testnet).to_string(),
+        }
+    }
+
+    /// Limit requests to `requests_per_second`, allowing bursts of `burst`.
+    pub fn with_rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.limiter = RateLimiter::new(requests_per_second, burst);
+        self
+    }
+
+    /// Set the retry/backoff policy for throttled or failed requests.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Test connectivity (GET /api/v3/ping).
+    pub async fn ping(&self) -> Result<(), BrokerError> {
+        let url = format!("{}/api/v3/ping", self.base_url);
+        self.execute(
+            Method::Get,
+            &|| (url.clone(), None),
+            false,
+            "ping",
+            BrokerError::Connection,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Get account information (GET /api/v3/account).
+    pub async fn account_info(&self) -> Result<AccountInfo, BrokerError> {
+        self.fetch(
+            Method::Get,
+            &|| (self.signed_url("/api/v3/account", ""), None),
+            true,
+            "account",
+            BrokerError::Connection,
+        )
+        .await
+    }
+
+    /// Submit a new order (POST /api/v3/order).
+    pub async fn submit_order(
+        &self,
+        symbol: &str,
+        side: &str,
+        order_type: &str,
+        quantity: &str,
+        price: Option<&str>,
+        time_in_force: Option<&str>,
+    ) -> Result<OrderResponse, BrokerError> {
+        let query = new_order_query(symbol, side, order_type, quantity, price, time_in_force)?;
+        let url = format!("{}/api/v3/order", self.base_url);
+
+        debug!("Submitting Binance order (async): {symbol} {side} qty={quantity}");
+
+        self.fetch(
+            Method::Post,
+            &|| (url.clone(), Some(self.signed_query(&query))),
+            true,
+            "order",
+            BrokerError::Order,
+        )
+        .await
+    }
+
+    /// Get order status (GET /api/v3/order).
+    pub async fn order_status(
+        &self,
+        symbol: &str,
+        order_id: u64,
+    ) -> Result<OrderResponse, BrokerError> {
+        validate_query_param(symbol, "symbol")?;
+        let query = format!("symbol={symbol}&orderId={order_id}");
+        self.fetch(
+            Method::Get,
+            &|| (self.signed_url("/api/v3/order", &query), None),
+            true,
+            "order status",
+            BrokerError::Order,
+        )
+        .await
+    }
+
+    /// Fills of an order (GET /api/v3/myTrades).
+    pub async fn order_trades(
+        &self,
+        symbol: &str,
+        order_id: u64,
+    ) -> Result<Vec<AccountTrade>, BrokerError> {
+        validate_query_param(symbol, "symbol")?;
+        let query = format!("symbol={symbol}&orderId={order_id}");
+        self.fetch(
+            Method::Get,
+            &|| (self.signed_url("/api/v3/myTrades", &query), None),
+            true,
+            "trades",
+            BrokerError::Order,
+        )
+        .await
+    }
+
+    /// Cancel an order (DELETE /api/v3/order).
+    pub async fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<(), BrokerError> {
+        validate_query_param(symbol, "symbol")?;
+        let query = format!("symbol={symbol}&orderId={order_id}");
+        self.execute(
+            Method::Delete,
+            &|| (self.signed_url("/api/v3/order", &query), None),
+            true,
+            "cancel",
+            BrokerError::Order,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Get book ticker (best bid/ask) for a symbol (GET /api/v3/ticker/bookTicker).
+    pub async fn book_ticker(&self, symbol: &str) -> Result<BookTicker, BrokerError> {
+        validate_query_param(symbol, "symbol")?;
+        let url = format!("{}/api/v3/ticker/bookTicker?symbol={symbol}", self.base_url);
+        self.fetch(
+            Method::Get,
+            &|| (url.clone(), None),
+            false,
+            "ticker",
+            BrokerError::Connection,
+        )
+        .await
+    }
+
+    /// `LOT_SIZE` filter for a symbol (GET /api/v3/exchangeInfo).
+    pub async fn lot_size(&self, symbol: &str) -> Result<Option<LotSize>, BrokerError> {
+        validate_query_param(symbol, "symbol")?;
+        let url = format!("{}/api/v3/exchangeInfo?symbol={symbol}", self.base_url);
+        let info: ExchangeInfo = self
+            .fetch(
+                Method::Get,
+                &|| (url.clone(), None),
+                false,
+                "exchange info",
+                BrokerError::Connection,
+            )
+            .await?;
+        Ok(info.lot_size(symbol))
+    }
+
+    /// [`execute`](Self::execute) and parse the JSON body.
+    async fn fetch<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        build: &(dyn Fn() -> (String, Option<String>) + Sync),
+        signed: bool,
+        context: &str,
+        error_kind: fn(String) -> BrokerError,
+    ) -> Result<T, BrokerError> {
+        let body = self
+            .execute(method, build, signed, context, error_kind)
+            .await?;
+        parse_json(&body, context, error_kind)
+    }
+
+    /// Send a request with rate limiting and retries; return the body of a 2xx response.
+    ///
+    /// `build` returns the URL and optional form body. It runs once per
+    /// attempt, so signed requests are re-signed with a fresh timestamp.
+    async fn execute(
+        &self,
+        method: Method,
+        build: &(dyn Fn() -> (String, Option<String>) + Sync),
+        signed: bool,
+        context: &str,
+        error_kind: fn(String) -> BrokerError,
+    ) -> Result<String, BrokerError> {
+        let send = || {
+            let (url, body) = build();
+            let mut request = match method {
+                Method::Get => self.client.get(url),
+                Method::Post => self.client.post(url),
+                Method::Delete => self.client.delete(url),
+            };
+            if signed {
+                request = request.header("X-MBX-APIKEY", &self.api_key);
+            }
+            if let Some(body) = body {
+                request = request
+                    .header("Content-Type", "application/x-www-form-urlencoded")
+                    .body(body);
+            }
+            async move {
+                let resp = request.send().await.map_err(|e| e.to_string())?;
+                let status = resp.status().as_u16();
+                let retry_after = retry_after(resp.headers());
+                let body = resp.text().await.unwrap_or_default();
+                Ok(HttpResponse {
+                    status,
+                    retry_after,
+                    body,
+                })
+            }
+        };
+
+        let resp = send_with_retry_async(&self.limiter, &self.retry, method, send)
+            .await
+            .map_err(|e| match e {
+                BrokerError::Connection(msg) => error_kind(format!("{context} {msg}")),
+                other => other,
+            })?;
+
+        if resp.is_success() {
+            Ok(resp.body)
+        } else {
+            Err(error_kind(format!(
+                "{context} returned {}: {}",
+                resp.status, resp.body
+            )))
+        }
+    }
+
+    /// `query` with the current timestamp and its HMAC signature appended.
+    fn signed_query(&self, query: &str) -> String {
+        let query = if query.is_empty() {
+            format!("timestamp={}", current_timestamp_ms())
+        } else {
+            format!("{query}&timestamp={}", current_timestamp_ms())
+        };
+        let signature = auth::sign(&query, &self.secret_key);
+        format!("{query}&signature={signature}")
+    }
+
+    /// Signed URL for `path`: `query` plus a fresh timestamp and signature.
+    fn signed_url(&self, path: &str, query: &str) -> String {
+        format!("{}{path}?{}", self.base_url, self.signed_query(query))
+    }
+}
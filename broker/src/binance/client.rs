@@ -1,19 +1,23 @@
 //! Binance REST API client.
 
+use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use log::debug;
-use reqwest::blocking::Client;
+use serde::de::DeserializeOwned;
 
 use super::auth;
-use super::types::{AccountInfo, BookTicker, OrderResponse};
+use super::transport::{
+    HttpRequest, Method, RateLimiter, ReqwestTransport, RetryPolicy, Transport, send_with_retry,
+};
+use super::types::{AccountInfo, AccountTrade, BookTicker, ExchangeInfo, LotSize, OrderResponse};
 use crate::error::BrokerError;
 
 /// Validate that a parameter value is safe for URL query strings.
 ///
 /// Rejects any value containing characters that could inject additional
 /// query parameters (e.g., `&`, `=`, `?`, `#`, space).
-fn validate_query_param(value: &str, name: &str) -> Result<(), BrokerError> {
+pub(super) fn validate_query_param(value: &str, name: &str) -> Result<(), BrokerError> {
     if value
         .bytes()
         .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'.' || b == b'-')
@@ -27,30 +31,70 @@ fn validate_query_param(value: &str, name: &str) -> Result<(), BrokerError> {
 }
 
 /// Validate multiple query parameters at once.
-fn validate_query_params(params: &[(&str, &str)]) -> Result<(), BrokerError> {
+pub(super) fn validate_query_params(params: &[(&str, &str)]) -> Result<(), BrokerError> {
     for &(value, name) in params {
         validate_query_param(value, name)?;
     }
     Ok(())
 }
 
-/// Check an HTTP response status and return a formatted error on failure.
-fn check_response(
-    resp: reqwest::blocking::Response,
-    context: &str,
-    error_kind: fn(String) -> BrokerError,
-) -> Result<reqwest::blocking::Response, BrokerError> {
-    if resp.status().is_success() {
-        return Ok(resp);
+/// The Binance REST operations used by [`BinanceBroker`](super::BinanceBroker).
+///
+/// Implemented by [`BinanceClient`]; tests can substitute an in-memory client
+/// via [`BinanceBroker::with_client`](super::BinanceBroker::with_client).
+pub trait BinanceApi: Send + Sync {
+    /// Test connectivity.
+    fn ping(&self) -> Result<(), BrokerError>;
+
+    /// Get account information.
+    fn account_info(&self) -> Result<AccountInfo, BrokerError>;
+
+    /// Submit a new order.
+    fn submit_order(
+        &self,
+        symbol: &str,
+        side: &str,
+        order_type: &str,
+        quantity: &str,
+        price: Option<&str>,
+        time_in_force: Option<&str>,
+    ) -> Result<OrderResponse, BrokerError>;
+
+    /// Get order status.
+    fn order_status(&self, symbol: &str, order_id: u64) -> Result<OrderResponse, BrokerError>;
+
+    /// Cancel an order.
+    fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<(), BrokerError>;
+
+    /// Get book ticker (best bid/ask) for a symbol.
+    fn book_ticker(&self, symbol: &str) -> Result<BookTicker, BrokerError>;
+
+    /// Fills of an order, with the commission charged on each.
+    ///
+    /// Defaults to no fills (commission reported as 0).
+    fn order_trades(&self, symbol: &str, order_id: u64) -> Result<Vec<AccountTrade>, BrokerError> {
+        let _ = (symbol, order_id);
+        Ok(Vec::new())
+    }
+
+    /// `LOT_SIZE` filter for a symbol, used to round fractional quantities.
+    ///
+    /// Defaults to `None` (quantities are sent unrounded).
+    fn lot_size(&self, symbol: &str) -> Result<Option<LotSize>, BrokerError> {
+        let _ = symbol;
+        Ok(None)
     }
-    let status = resp.status();
-    let body = resp.text().unwrap_or_default();
-    Err(error_kind(format!("{context} returned {status}: {body}")))
 }
 
 /// Blocking Binance REST client.
+///
+/// Every request takes a token from the client's [`RateLimiter`] and is
+/// retried with backoff on 429/418 responses, and on 5xx responses to
+/// idempotent requests (see [`RetryPolicy`]).
 pub struct BinanceClient {
-    client: Client,
+    transport: Box<dyn Transport>,
+    limiter: RateLimiter,
+    retry: RetryPolicy,
     api_key: String,
     secret_key: String,
     base_url: String,
@@ -66,57 +110,178 @@ impl Drop for BinanceClient {
 impl BinanceClient {
     /// Create a new Binance client.
     pub fn new(api_key: &str, secret_key: &str, testnet: bool) -> Self {
-        let base_url = if testnet {
-            "https://testnet.binance.vision"
-        } else {
-            "https://api.binance.com"
-        };
-
         Self {
-            client: Client::new(),
+            transport: Box::new(ReqwestTransport::default()),
+            limiter: RateLimiter::default(),
+            retry: RetryPolicy::default(),
             api_key: api_key.to_string(),
             secret_key: secret_key.to_string(),
-            base_url: base_url.to_string(),
+            base_url: base_url(testnet).to_string(),
         }
     }
 
+    /// Create a client for the USDT-M futures API (`fapi` endpoints).
+    pub fn new_futures(api_key: &str, secret_key: &str, testnet: bool) -> Self {
+        let mut client = Self::new(api_key, secret_key, testnet);
+        client.base_url = futures_base_url(testnet).to_string();
+        client
+    }
+
+    /// Replace the HTTP transport (e.g. with canned responses in tests).
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Box::new(transport);
+        self
+    }
+
+    /// Limit requests to `requests_per_second`, allowing bursts of `burst`.
+    pub fn with_rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.limiter = RateLimiter::new(requests_per_second, burst);
+        self
+    }
+
+    /// Set the retry/backoff policy for throttled or failed requests.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Send a request with rate limiting and retries; return the body of a 2xx response.
+    ///
+    /// `build` returns the URL and optional form body. It runs once per
+    /// attempt, so signed requests are re-signed with a fresh timestamp.
+    pub(super) fn execute(
+        &self,
+        method: Method,
+        build: &dyn Fn() -> (String, Option<String>),
+        signed: bool,
+        context: &str,
+        error_kind: fn(String) -> BrokerError,
+    ) -> Result<String, BrokerError> {
+        let mut request = || {
+            let (url, body) = build();
+            let mut headers = Vec::new();
+            if signed {
+                headers.push(("X-MBX-APIKEY", self.api_key.clone()));
+            }
+            if body.is_some() {
+                headers.push((
+                    "Content-Type",
+                    "application/x-www-form-urlencoded".to_string(),
+                ));
+            }
+            HttpRequest {
+                method,
+                url,
+                headers,
+                body,
+            }
+        };
+
+        let resp = send_with_retry(
+            self.transport.as_ref(),
+            &self.limiter,
+            &self.retry,
+            &mut request,
+            &mut thread::sleep,
+        )
+        .map_err(|e| match e {
+            BrokerError::Connection(msg) => error_kind(format!("{context} {msg}")),
+            other => other,
+        })?;
+
+        if resp.is_success() {
+            Ok(resp.body)
+        } else {
+            Err(error_kind(format!(
+                "{context} returned {}: {}",
+                resp.status, resp.body
+            )))
+        }
+    }
+
+    /// Unsigned URL for `path` on this client's host.
+    pub(super) fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    /// `LOT_SIZE` filter for `symbol` from the exchange-info endpoint at `path`.
+    pub(super) fn lot_size_at(
+        &self,
+        path: &str,
+        symbol: &str,
+    ) -> Result<Option<LotSize>, BrokerError> {
+        validate_query_param(symbol, "symbol")?;
+        let url = self.url(&format!("{path}?symbol={symbol}"));
+        let body = self.execute(
+            Method::Get,
+            &|| (url.clone(), None),
+            false,
+            "exchange info",
+            BrokerError::Connection,
+        )?;
+        let info: ExchangeInfo = parse_json(&body, "exchange info", BrokerError::Connection)?;
+        Ok(info.lot_size(symbol))
+    }
+
+    /// HMAC signature of `query` with this client's secret.
+    pub(super) fn sign(&self, query: &str) -> String {
+        auth::sign(query, &self.secret_key)
+    }
+
+    /// `query` with the current timestamp and its HMAC signature appended.
+    pub(super) fn signed_query(&self, query: &str) -> String {
+        let query = if query.is_empty() {
+            format!("timestamp={}", current_timestamp_ms())
+        } else {
+            format!("{query}&timestamp={}", current_timestamp_ms())
+        };
+        let signature = self.sign(&query);
+        format!("{query}&signature={signature}")
+    }
+
+    /// Signed URL for `path`: `query` plus a fresh timestamp and signature.
+    pub(super) fn signed_url(&self, path: &str, query: &str) -> String {
+        format!("{}{path}?{}", self.base_url, self.signed_query(query))
+    }
+}
+
+/// Parse a JSON body, mapping failures onto `error_kind`.
+pub(super) fn parse_json<T: DeserializeOwned>(
+    body: &str,
+    what: &str,
+    error_kind: fn(String) -> BrokerError,
+) -> Result<T, BrokerError> {
+    serde_json::from_str(body).map_err(|e| error_kind(format!("failed to parse {what}: {e}")))
+}
+
+impl BinanceApi for BinanceClient {
     /// Test connectivity (GET /api/v3/ping).
-    pub fn ping(&self) -> Result<(), BrokerError> {
+    fn ping(&self) -> Result<(), BrokerError> {
         let url = format!("{}/api/v3/ping", self.base_url);
-        let resp = self
-            .client
-            .get(&url)
-            .send()
-            .map_err(|e| BrokerError::Connection(format!("ping failed: {e}")))?;
-
-        check_response(resp, "ping", BrokerError::Connection)?;
+        self.execute(
+            Method::Get,
+            &|| (url.clone(), None),
+            false,
+            "ping",
+            BrokerError::Connection,
+        )?;
         Ok(())
     }
 
     /// Get account information (GET /api/v3/account).
-    pub fn account_info(&self) -> Result<AccountInfo, BrokerError> {
-        let timestamp = current_timestamp_ms();
-        let query = format!("timestamp={timestamp}");
-        let signature = auth::sign(&query, &self.secret_key);
-        let url = format!(
-            "{}/api/v3/account?{query}&signature={signature}",
-            self.base_url
-        );
-
-        let resp = self
-            .client
-            .get(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .send()
-            .map_err(|e| BrokerError::Connection(format!("account request failed: {e}")))?;
-
-        let resp = check_response(resp, "account", BrokerError::Connection)?;
-        resp.json::<AccountInfo>()
-            .map_err(|e| BrokerError::Connection(format!("failed to parse account: {e}")))
+    fn account_info(&self) -> Result<AccountInfo, BrokerError> {
+        let body = self.execute(
+            Method::Get,
+            &|| (self.signed_url("/api/v3/account", ""), None),
+            true,
+            "account",
+            BrokerError::Connection,
+        )?;
+        parse_json(&body, "account", BrokerError::Connection)
     }
 
     /// Submit a new order (POST /api/v3/order).
-    pub fn submit_order(
+    fn submit_order(
         &self,
         symbol: &str,
         side: &str,
@@ -125,113 +290,138 @@ impl BinanceClient {
         price: Option<&str>,
         time_in_force: Option<&str>,
     ) -> Result<OrderResponse, BrokerError> {
-        validate_query_params(&[
-            (symbol, "symbol"),
-            (side, "side"),
-            (order_type, "order_type"),
-            (quantity, "quantity"),
-        ])?;
-        if let Some(p) = price {
-            validate_query_param(p, "price")?;
-        }
-        if let Some(tif) = time_in_force {
-            validate_query_param(tif, "timeInForce")?;
-        }
-
-        let timestamp = current_timestamp_ms();
-        let mut query = format!(
-            "symbol={symbol}&side={side}&type={order_type}&quantity={quantity}&timestamp={timestamp}"
-        );
-        if let Some(p) = price {
-            query.push_str(&format!("&price={p}"));
-        }
-        if let Some(tif) = time_in_force {
-            query.push_str(&format!("&timeInForce={tif}"));
-        }
-
-        let signature = auth::sign(&query, &self.secret_key);
-        let url = format!("{}/api/v3/order", self.base_url);
+        let query = new_order_query(symbol, side, order_type, quantity, price, time_in_force)?;
+        let url = self.url("/api/v3/order");
 
         debug!("Submitting Binance order: {symbol} {side} qty={quantity}");
 
-        let resp = self
-            .client
-            .post(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .body(format!("{query}&signature={signature}"))
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .send()
-            .map_err(|e| BrokerError::Order(format!("order request failed: {e}")))?;
-
-        let resp = check_response(resp, "order", BrokerError::Order)?;
-        resp.json::<OrderResponse>()
-            .map_err(|e| BrokerError::Order(format!("failed to parse order response: {e}")))
+        let body = self.execute(
+            Method::Post,
+            &|| (url.clone(), Some(self.signed_query(&query))),
+            true,
+            "order",
+            BrokerError::Order,
+        )?;
+        parse_json(&body, "order response", BrokerError::Order)
     }
 
     /// Get order status (GET /api/v3/order).
-    pub fn order_status(&self, symbol: &str, order_id: u64) -> Result<OrderResponse, BrokerError> {
+    fn order_status(&self, symbol: &str, order_id: u64) -> Result<OrderResponse, BrokerError> {
         validate_query_param(symbol, "symbol")?;
-        let timestamp = current_timestamp_ms();
-        let query = format!("symbol={symbol}&orderId={order_id}&timestamp={timestamp}");
-        let signature = auth::sign(&query, &self.secret_key);
-        let url = format!(
-            "{}/api/v3/order?{query}&signature={signature}",
-            self.base_url
-        );
-
-        let resp = self
-            .client
-            .get(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .send()
-            .map_err(|e| BrokerError::Order(format!("order status request failed: {e}")))?;
-
-        let resp = check_response(resp, "order status", BrokerError::Order)?;
-        resp.json::<OrderResponse>()
-            .map_err(|e| BrokerError::Order(format!("failed to parse order status: {e}")))
+        let query = format!("symbol={symbol}&orderId={order_id}");
+        let body = self.execute(
+            Method::Get,
+            &|| (self.signed_url("/api/v3/order", &query), None),
+            true,
+            "order status",
+            BrokerError::Order,
+        )?;
+        parse_json(&body, "order status", BrokerError::Order)
     }
 
     /// Cancel an order (DELETE /api/v3/order).
-    pub fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<(), BrokerError> {
+    fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<(), BrokerError> {
         validate_query_param(symbol, "symbol")?;
-        let timestamp = current_timestamp_ms();
-        let query = format!("symbol={symbol}&orderId={order_id}&timestamp={timestamp}");
-        let signature = auth::sign(&query, &self.secret_key);
-        let url = format!(
-            "{}/api/v3/order?{query}&signature={signature}",
-            self.base_url
-        );
-
-        let resp = self
-            .client
-            .delete(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .send()
-            .map_err(|e| BrokerError::Order(format!("cancel request failed: {e}")))?;
-
-        check_response(resp, "cancel", BrokerError::Order)?;
+        let query = format!("symbol={symbol}&orderId={order_id}");
+        self.execute(
+            Method::Delete,
+            &|| (self.signed_url("/api/v3/order", &query), None),
+            true,
+            "cancel",
+            BrokerError::Order,
+        )?;
         Ok(())
     }
 
     /// Get book ticker (best bid/ask) for a symbol (GET /api/v3/ticker/bookTicker).
-    pub fn book_ticker(&self, symbol: &str) -> Result<BookTicker, BrokerError> {
+    fn book_ticker(&self, symbol: &str) -> Result<BookTicker, BrokerError> {
         validate_query_param(symbol, "symbol")?;
         let url = format!("{}/api/v3/ticker/bookTicker?symbol={symbol}", self.base_url);
+        let body = self.execute(
+            Method::Get,
+            &|| (url.clone(), None),
+            false,
+            "ticker",
+            BrokerError::Connection,
+        )?;
+        parse_json(&body, "ticker", BrokerError::Connection)
+    }
+
+    /// Fills of an order (GET /api/v3/myTrades).
+    fn order_trades(&self, symbol: &str, order_id: u64) -> Result<Vec<AccountTrade>, BrokerError> {
+        validate_query_param(symbol, "symbol")?;
+        let query = format!("symbol={symbol}&orderId={order_id}");
+        let body = self.execute(
+            Method::Get,
+            &|| (self.signed_url("/api/v3/myTrades", &query), None),
+            true,
+            "trades",
+            BrokerError::Order,
+        )?;
+        parse_json(&body, "trades", BrokerError::Order)
+    }
+
+    /// `LOT_SIZE` filter for a symbol (GET /api/v3/exchangeInfo).
+    fn lot_size(&self, symbol: &str) -> Result<Option<LotSize>, BrokerError> {
+        self.lot_size_at("/api/v3/exchangeInfo", symbol)
+    }
+}
+
+/// REST endpoint for production or the spot testnet.
+pub(super) fn base_url(testnet: bool) -> &'static str {
+    if testnet {
+        "https://testnet.binance.vision"
+    } else {
+        "https://api.binance.com"
+    }
+}
+
+/// REST endpoint for USDT-M futures, production or testnet.
+pub(super) fn futures_base_url(testnet: bool) -> &'static str {
+    if testnet {
+        "https://testnet.binancefuture.com"
+    } else {
+        "https://fapi.binance.com"
+    }
+}
 
-        let resp = self
-            .client
-            .get(&url)
-            .send()
-            .map_err(|e| BrokerError::Connection(format!("ticker request failed: {e}")))?;
+/// Build the unsigned query string for a new order, validating every field.
+///
+/// The timestamp is left out; [`BinanceClient::signed_query`] adds it when
+/// the request is signed.
+pub(super) fn new_order_query(
+    symbol: &str,
+    side: &str,
+    order_type: &str,
+    quantity: &str,
+    price: Option<&str>,
+    time_in_force: Option<&str>,
+) -> Result<String, BrokerError> {
+    validate_query_params(&[
+        (symbol, "symbol"),
+        (side, "side"),
+        (order_type, "order_type"),
+        (quantity, "quantity"),
+    ])?;
+    if let Some(p) = price {
+        validate_query_param(p, "price")?;
+    }
+    if let Some(tif) = time_in_force {
+        validate_query_param(tif, "timeInForce")?;
+    }
 
-        let resp = check_response(resp, "ticker", BrokerError::Connection)?;
-        resp.json::<BookTicker>()
-            .map_err(|e| BrokerError::Connection(format!("failed to parse ticker: {e}")))
+    let mut query = format!("symbol={symbol}&side={side}&type={order_type}&quantity={quantity}");
+    if let Some(p) = price {
+        query.push_str(&format!("&price={p}"));
+    }
+    if let Some(tif) = time_in_force {
+        query.push_str(&format!("&timeInForce={tif}"));
     }
+    Ok(query)
 }
 
 /// Current timestamp in milliseconds.
-fn current_timestamp_ms() -> u64 {
+pub(super) fn current_timestamp_ms() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or(Duration::ZERO)
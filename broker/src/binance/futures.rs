@@ -0,0 +1,330 @@
+//! Binance USDT-M perpetual futures broker.
+//!
+//! Targets the `fapi` endpoints with the same signing, rate limiting, and
+//! retry behavior as spot. Unlike spot balances, futures positions carry a
+//! sign: `positions()` reports shorts as negative quantities, and unrealized
+//! PnL includes funding paid or received since the position last changed.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use nanobook::Symbol;
+
+use super::client::{BinanceClient, new_order_query, parse_json, validate_query_param};
+use super::transport::Method;
+use super::types::{AccountTrade, FuturesAccount, FuturesPosition, IncomeRecord, OrderResponse};
+use super::{BinanceBroker, LotSizeCache};
+use crate::Broker;
+use crate::error::BrokerError;
+use crate::types::*;
+
+/// Binance USDT-M futures broker implementing the generic Broker trait.
+///
+/// Quantities follow the spot broker's convention (1e-8 units per contract
+/// unit); prices and PnL are in cents of the quote asset.
+pub struct BinanceFuturesBroker {
+    api_key: String,
+    secret_key: String,
+    testnet: bool,
+    client: Option<BinanceClient>,
+    quote_asset: String,
+    /// Orders submitted in this session: id → (Binance pair, original order).
+    orders: Mutex<HashMap<OrderId, (String, BrokerOrder)>>,
+    /// `LOT_SIZE` filters fetched so far, by Binance pair.
+    lot_sizes: LotSizeCache,
+}
+
+impl BinanceFuturesBroker {
+    /// Create a new futures broker handle (not yet connected).
+    pub fn new(api_key: &str, secret_key: &str, testnet: bool) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            secret_key: secret_key.to_string(),
+            testnet,
+            client: None,
+            quote_asset: "USDT".to_string(),
+            orders: Mutex::new(HashMap::new()),
+            lot_sizes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a broker that is already connected through `client`.
+    ///
+    /// Build the client with [`BinanceClient::new_futures`]; tests can give it
+    /// a mock transport.
+    pub fn with_client(client: BinanceClient) -> Self {
+        Self {
+            client: Some(client),
+            ..Self::new("", "", false)
+        }
+    }
+
+    /// Set the quote asset (default "USDT").
+    pub fn with_quote_asset(mut self, quote: &str) -> Self {
+        self.quote_asset = quote.to_string();
+        self
+    }
+
+    /// Set initial leverage for a symbol (`POST /fapi/v1/leverage`).
+    pub fn set_leverage(&self, symbol: &Symbol, leverage: u32) -> Result<(), BrokerError> {
+        if !(1..=125).contains(&leverage) {
+            return Err(BrokerError::Order(format!(
+                "leverage must be between 1 and 125, got {leverage}"
+            )));
+        }
+        let client = self.require_client()?;
+        let query = format!(
+            "symbol={}&leverage={leverage}",
+            self.to_binance_symbol(symbol)
+        );
+        client.execute(
+            Method::Post,
+            &|| (client.signed_url("/fapi/v1/leverage", &query), None),
+            true,
+            "leverage",
+            BrokerError::Order,
+        )?;
+        Ok(())
+    }
+
+    fn to_binance_symbol(&self, symbol: &Symbol) -> String {
+        format!("{}{}", symbol.as_str(), self.quote_asset)
+    }
+
+    /// Map a Binance pair back to a nanobook symbol ("BTCUSDT" → "BTC").
+    fn nanobook_symbol(&self, pair: &str) -> Option<Symbol> {
+        Symbol::try_new(pair.strip_suffix(self.quote_asset.as_str())?)
+    }
+
+    fn require_client(&self) -> Result<&BinanceClient, BrokerError> {
+        self.client.as_ref().ok_or(BrokerError::NotConnected)
+    }
+
+    fn cached_order(&self, id: OrderId) -> Result<(String, BrokerOrder), BrokerError> {
+        let orders = self.orders.lock().unwrap_or_else(|e| e.into_inner());
+        orders.get(&id).cloned().ok_or_else(|| {
+            BrokerError::Order(format!("order {} was not submitted in this session", id.0))
+        })
+    }
+
+    fn signed_get<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &str,
+        context: &str,
+    ) -> Result<T, BrokerError> {
+        let client = self.require_client()?;
+        let body = client.execute(
+            Method::Get,
+            &|| (client.signed_url(path, query), None),
+            true,
+            context,
+            BrokerError::Connection,
+        )?;
+        parse_json(&body, context, BrokerError::Connection)
+    }
+
+    /// Funding received (positive) or paid (negative) per pair, in cents,
+    /// counting only payments at or after `since_ms[pair]`.
+    fn funding_since(
+        records: &[IncomeRecord],
+        since_ms: &HashMap<&str, u64>,
+    ) -> HashMap<String, i64> {
+        let mut funding = HashMap::new();
+        for record in records {
+            if record.income_type != "FUNDING_FEE" {
+                continue;
+            }
+            let Some(&since) = since_ms.get(record.symbol.as_str()) else {
+                continue;
+            };
+            if record.time >= since {
+                *funding.entry(record.symbol.clone()).or_insert(0) +=
+                    BinanceBroker::parse_price_cents(&record.income);
+            }
+        }
+        funding
+    }
+
+    /// Convert open futures positions, adding funding to unrealized PnL.
+    fn positions_from_risk(
+        &self,
+        risk: &[FuturesPosition],
+        funding: &HashMap<String, i64>,
+    ) -> Vec<Position> {
+        risk.iter()
+            .filter_map(|p| {
+                let amount: f64 = p.position_amt.parse().unwrap_or(0.0);
+                if amount == 0.0 {
+                    return None;
+                }
+                let symbol = self.nanobook_symbol(&p.symbol)?;
+                let mark: f64 = p.mark_price.parse().unwrap_or(0.0);
+                let pnl = BinanceBroker::parse_price_cents(&p.un_realized_profit)
+                    + funding.get(&p.symbol).copied().unwrap_or(0);
+                Some(Position {
+                    symbol,
+                    quantity: (amount * 1e8).round() as i64,
+                    avg_cost_cents: BinanceBroker::parse_price_cents(&p.entry_price),
+                    market_value_cents: (amount * mark * 100.0).round() as i64,
+                    unrealized_pnl_cents: pnl,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Broker for BinanceFuturesBroker {
+    fn connect(&mut self) -> Result<(), BrokerError> {
+        let client = BinanceClient::new_futures(&self.api_key, &self.secret_key, self.testnet);
+        let url = client.url("/fapi/v1/ping");
+        client.execute(
+            Method::Get,
+            &|| (url.clone(), None),
+            false,
+            "ping",
+            BrokerError::Connection,
+        )?;
+        self.client = Some(client);
+        Ok(())
+    }
+
+    fn disconnect(&mut self) -> Result<(), BrokerError> {
+        self.client = None;
+        Ok(())
+    }
+
+    fn positions(&self) -> Result<Vec<Position>, BrokerError> {
+        let risk: Vec<FuturesPosition> =
+            self.signed_get("/fapi/v2/positionRisk", "", "positions")?;
+        let open: HashMap<&str, u64> = risk
+            .iter()
+            .filter(|p| p.position_amt.parse::<f64>().unwrap_or(0.0) != 0.0)
+            .map(|p| (p.symbol.as_str(), p.update_time))
+            .collect();
+
+        let funding = if open.is_empty() {
+            HashMap::new()
+        } else {
+            let income: Vec<IncomeRecord> =
+                self.signed_get("/fapi/v1/income", "incomeType=FUNDING_FEE", "income")?;
+            Self::funding_since(&income, &open)
+        };
+
+        Ok(self.positions_from_risk(&risk, &funding))
+    }
+
+    fn account(&self) -> Result<Account, BrokerError> {
+        let info: FuturesAccount = self.signed_get("/fapi/v2/account", "", "account")?;
+        let gross = info
+            .positions
+            .iter()
+            .map(|p| BinanceBroker::parse_price_cents(&p.notional).abs())
+            .sum();
+        Ok(Account {
+            equity_cents: BinanceBroker::parse_price_cents(&info.total_margin_balance),
+            buying_power_cents: BinanceBroker::parse_price_cents(&info.available_balance),
+            cash_cents: BinanceBroker::parse_price_cents(&info.total_wallet_balance),
+            gross_position_value_cents: gross,
+        })
+    }
+
+    fn submit_order(&self, order: &BrokerOrder) -> Result<OrderId, BrokerError> {
+        let client = self.require_client()?;
+        let positions = if order.reduce_only {
+            self.positions()?
+        } else {
+            Vec::new()
+        };
+        let order =
+            &*order.resolve_reduce_only_scaled(&positions, BinanceBroker::POSITION_DECIMALS)?;
+        let binance_sym = self.to_binance_symbol(&order.symbol);
+        let lot = BinanceBroker::lot_size_for(&self.lot_sizes, order, &binance_sym, || {
+            client.lot_size_at("/fapi/v1/exchangeInfo", &binance_sym)
+        })?;
+        let params = BinanceBroker::order_params(order, lot.as_ref())?;
+        let mut query = new_order_query(
+            &binance_sym,
+            params.side,
+            params.order_type,
+            &params.quantity,
+            params.price.as_deref(),
+            params.time_in_force,
+        )?;
+        if order.reduce_only {
+            // Also enforced venue-side, in case the position moved meanwhile.
+            query.push_str("&reduceOnly=true");
+        }
+        let url = client.url("/fapi/v1/order");
+
+        let body = client.execute(
+            Method::Post,
+            &|| (url.clone(), Some(client.signed_query(&query))),
+            true,
+            "futures order",
+            BrokerError::Order,
+        )?;
+        let resp: OrderResponse = parse_json(&body, "order response", BrokerError::Order)?;
+
+        let id = OrderId(resp.order_id);
+        self.orders
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id, (binance_sym, order.clone()));
+        Ok(id)
+    }
+
+    fn order_status(&self, id: OrderId) -> Result<BrokerOrderStatus, BrokerError> {
+        let (binance_sym, order) = self.cached_order(id)?;
+        let resp: OrderResponse = self
+            .signed_get(
+                "/fapi/v1/order",
+                &format!("symbol={binance_sym}&orderId={}", id.0),
+                "order status",
+            )
+            .map_err(|e| BrokerError::Order(e.to_string()))?;
+        let mut status = BinanceBroker::status_from_response(id, &resp, Some(order.quantity));
+        if BinanceBroker::has_fills(&resp) {
+            let trades: Vec<AccountTrade> = self
+                .signed_get(
+                    "/fapi/v1/userTrades",
+                    &format!("symbol={binance_sym}&orderId={}", id.0),
+                    "trades",
+                )
+                .map_err(|e| BrokerError::Order(e.to_string()))?;
+            status.commission_cents =
+                BinanceBroker::commission_cents(&trades, order.symbol.as_str(), &self.quote_asset);
+        }
+        Ok(status)
+    }
+
+    fn cancel_order(&self, id: OrderId) -> Result<(), BrokerError> {
+        let (binance_sym, _) = self.cached_order(id)?;
+        let client = self.require_client()?;
+        let query = format!("symbol={binance_sym}&orderId={}", id.0);
+        client.execute(
+            Method::Delete,
+            &|| (client.signed_url("/fapi/v1/order", &query), None),
+            true,
+            "cancel",
+            BrokerError::Order,
+        )?;
+        Ok(())
+    }
+
+    fn quote(&self, symbol: &Symbol) -> Result<Quote, BrokerError> {
+        let client = self.require_client()?;
+        let binance_sym = self.to_binance_symbol(symbol);
+        validate_query_param(&binance_sym, "symbol")?;
+        let url = client.url(&format!("/fapi/v1/ticker/bookTicker?symbol={binance_sym}"));
+        let body = client.execute(
+            Method::Get,
+            &|| (url.clone(), None),
+            false,
+            "ticker",
+            BrokerError::Connection,
+        )?;
+        let ticker = parse_json(&body, "ticker", BrokerError::Connection)?;
+        Ok(BinanceBroker::quote_from_ticker(symbol, &ticker))
+    }
+}
@@ -2,14 +2,29 @@
 
 pub mod auth;
 pub mod client;
+pub mod futures;
+pub mod transport;
 pub mod types;
+pub mod websocket;
+
+#[cfg(feature = "async")]
+pub mod async_broker;
+#[cfg(feature = "async")]
+pub mod async_client;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 use nanobook::Symbol;
 
 use crate::Broker;
 use crate::error::BrokerError;
 use crate::types::*;
-use client::BinanceClient;
+use client::{BinanceApi, BinanceClient};
+use types::{AccountInfo, AccountTrade, BookTicker, LotSize, OrderResponse};
 
 /// Binance spot broker implementing the generic Broker trait.
 ///
@@ -18,10 +33,37 @@ pub struct BinanceBroker {
     api_key: String,
     secret_key: String,
     testnet: bool,
-    client: Option<BinanceClient>,
+    client: Option<Box<dyn BinanceApi>>,
     /// Symbol → Binance trading pair mapping.
     /// nanobook symbols are like "BTC", Binance needs "BTCUSDT".
     quote_asset: String,
+    /// WebSocket market-data endpoint for `subscribe_quotes`.
+    stream_url: String,
+    /// Set on disconnect or drop to stop the `subscribe_quotes` threads.
+    stream_stop: Arc<AtomicBool>,
+    /// Orders submitted in this session: id → (Binance pair, original order).
+    /// Binance needs the pair to query or cancel an order.
+    orders: Mutex<HashMap<OrderId, (String, BrokerOrder)>>,
+    /// `LOT_SIZE` filters fetched so far, by Binance pair.
+    lot_sizes: LotSizeCache,
+}
+
+impl Drop for BinanceBroker {
+    fn drop(&mut self) {
+        self.stream_stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// `LOT_SIZE` filters by Binance pair (`None`: the pair has no filter).
+pub(crate) type LotSizeCache = Mutex<HashMap<String, Option<LotSize>>>;
+
+/// String parameters for a Binance new-order request.
+struct OrderParams {
+    side: &'static str,
+    order_type: &'static str,
+    quantity: String,
+    price: Option<String>,
+    time_in_force: Option<&'static str>,
 }
 
 impl BinanceBroker {
@@ -36,50 +78,157 @@ impl BinanceBroker {
             testnet,
             client: None,
             quote_asset: "USDT".to_string(),
+            stream_url: if testnet {
+                "wss://stream.testnet.binance.vision".to_string()
+            } else {
+                "wss://stream.binance.com:9443".to_string()
+            },
+            stream_stop: Arc::default(),
+            orders: Mutex::new(HashMap::new()),
+            lot_sizes: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Create a broker that is already connected through a custom client.
+    ///
+    /// Useful for tests (an in-memory [`BinanceApi`]) or custom transports.
+    pub fn with_client(client: impl BinanceApi + 'static) -> Self {
+        let mut broker = Self::new("", "", false);
+        broker.client = Some(Box::new(client));
+        broker
+    }
+
     /// Set the quote asset (default "USDT").
     pub fn with_quote_asset(mut self, quote: &str) -> Self {
         self.quote_asset = quote.to_string();
         self
     }
 
+    /// Override the WebSocket endpoint used by `subscribe_quotes`
+    /// (e.g. a local `ws://` server in tests).
+    pub fn with_stream_url(mut self, url: &str) -> Self {
+        self.stream_url = url.trim_end_matches('/').to_string();
+        self
+    }
+
     /// Convert a nanobook Symbol to a Binance trading pair string.
     fn to_binance_symbol(&self, symbol: &Symbol) -> String {
         format!("{}{}", symbol.as_str(), self.quote_asset)
     }
 
-    fn require_client(&self) -> Result<&BinanceClient, BrokerError> {
-        self.client.as_ref().ok_or(BrokerError::NotConnected)
+    fn require_client(&self) -> Result<&dyn BinanceApi, BrokerError> {
+        self.client.as_deref().ok_or(BrokerError::NotConnected)
     }
 
-    /// Parse a decimal string to cents (e.g., "185.50" → 18550).
-    fn parse_price_cents(s: &str) -> i64 {
-        let val: f64 = s.parse().unwrap_or(0.0);
-        (val * 100.0) as i64
+    /// Look up the Binance pair and original order for an id from this session.
+    fn cached_order(&self, id: OrderId) -> Result<(String, BrokerOrder), BrokerError> {
+        let orders = self.orders.lock().unwrap_or_else(|e| e.into_inner());
+        orders.get(&id).cloned().ok_or_else(|| {
+            BrokerError::Order(format!(
+                "order {} was not submitted in this session; \
+                 use cancel_order_with_symbol() / order_status_with_symbol() with its symbol",
+                id.0
+            ))
+        })
     }
-}
 
-impl Broker for BinanceBroker {
-    fn connect(&mut self) -> Result<(), BrokerError> {
-        let client = BinanceClient::new(&self.api_key, &self.secret_key, self.testnet);
-        client.ping()?;
-        self.client = Some(client);
-        Ok(())
+    /// Cancel an order by explicit symbol, for orders not placed through this handle
+    /// (e.g. from a prior session).
+    pub fn cancel_order_with_symbol(
+        &self,
+        symbol: &Symbol,
+        id: OrderId,
+    ) -> Result<(), BrokerError> {
+        let client = self.require_client()?;
+        client.cancel_order(&self.to_binance_symbol(symbol), id.0)
     }
 
-    fn disconnect(&mut self) -> Result<(), BrokerError> {
-        self.client = None;
-        Ok(())
+    /// Query an order by explicit symbol, for orders not placed through this handle.
+    ///
+    /// `remaining_quantity` is unknown for such orders and reported as 0.
+    pub fn order_status_with_symbol(
+        &self,
+        symbol: &Symbol,
+        id: OrderId,
+    ) -> Result<BrokerOrderStatus, BrokerError> {
+        let client = self.require_client()?;
+        let pair = self.to_binance_symbol(symbol);
+        let resp = client.order_status(&pair, id.0)?;
+        let mut status = Self::status_from_response(id, &resp, None);
+        if Self::has_fills(&resp) {
+            let trades = client.order_trades(&pair, id.0)?;
+            status.commission_cents =
+                Self::commission_cents(&trades, symbol.as_str(), &self.quote_asset);
+        }
+        Ok(status)
     }
 
-    fn positions(&self) -> Result<Vec<Position>, BrokerError> {
-        let client = self.require_client()?;
-        let info = client.account_info()?;
+    /// Map a Binance order response onto the generic status.
+    fn status_from_response(
+        id: OrderId,
+        resp: &OrderResponse,
+        ordered_quantity: Option<u64>,
+    ) -> BrokerOrderStatus {
+        let executed: f64 = resp.executed_qty.parse().unwrap_or(0.0);
+        let quote_qty: f64 = resp.cummulative_quote_qty.parse().unwrap_or(0.0);
+        let filled_quantity = executed.round().max(0.0) as u64;
+        let avg_fill_price_cents = if executed > 0.0 {
+            (quote_qty / executed * 100.0).round() as i64
+        } else {
+            0
+        };
 
-        let positions = info
-            .balances
+        let status = match resp.status.as_str() {
+            "NEW" | "PENDING_NEW" => OrderState::Submitted,
+            "PARTIALLY_FILLED" => OrderState::PartiallyFilled,
+            "FILLED" => OrderState::Filled,
+            "CANCELED" | "PENDING_CANCEL" | "EXPIRED" | "EXPIRED_IN_MATCH" => OrderState::Cancelled,
+            "REJECTED" => OrderState::Rejected,
+            _ => OrderState::Pending,
+        };
+
+        BrokerOrderStatus {
+            id,
+            status,
+            filled_quantity,
+            remaining_quantity: ordered_quantity
+                .map(|q| q.saturating_sub(filled_quantity))
+                .unwrap_or(0),
+            avg_fill_price_cents,
+            commission_cents: 0,
+        }
+    }
+
+    /// Whether an order response reports any executed quantity.
+    fn has_fills(resp: &OrderResponse) -> bool {
+        resp.executed_qty.parse::<f64>().unwrap_or(0.0) > 0.0
+    }
+
+    /// Commission on `trades` in cents of `quote_asset`.
+    ///
+    /// Fees charged in the quote asset count as is and fees in the base
+    /// asset at the fill price. Fees in any other asset (e.g. BNB discounts)
+    /// have no price here and are left out.
+    fn commission_cents(trades: &[AccountTrade], base_asset: &str, quote_asset: &str) -> i64 {
+        let total: f64 = trades
+            .iter()
+            .map(|t| {
+                let commission: f64 = t.commission.parse().unwrap_or(0.0);
+                if t.commission_asset == quote_asset {
+                    commission
+                } else if t.commission_asset == base_asset {
+                    commission * t.price.parse::<f64>().unwrap_or(0.0)
+                } else {
+                    0.0
+                }
+            })
+            .sum();
+        (total * 100.0).round() as i64
+    }
+
+    /// Map account balances onto positions (quantity in 1e-8 units).
+    fn positions_from_info(info: &AccountInfo) -> Vec<Position> {
+        info.balances
             .iter()
             .filter_map(|b| {
                 let free: f64 = b.free.parse().unwrap_or(0.0);
@@ -99,20 +248,65 @@ impl Broker for BinanceBroker {
                     unrealized_pnl_cents: 0,
                 })
             })
-            .collect();
+            .collect()
+    }
+
+    /// Position size unit: balances are reported in 1e-8 units.
+    pub(crate) const POSITION_DECIMALS: u32 = 8;
 
-        Ok(positions)
+    /// The `LOT_SIZE` filter for `pair` if `order` needs one (a fractional
+    /// quantity), fetching it on first use.
+    pub(crate) fn lot_size_for(
+        cache: &LotSizeCache,
+        order: &BrokerOrder,
+        pair: &str,
+        fetch: impl FnOnce() -> Result<Option<LotSize>, BrokerError>,
+    ) -> Result<Option<LotSize>, BrokerError> {
+        if order.quantity_scaled.is_none() {
+            return Ok(None);
+        }
+        if let Some(&lot) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(pair) {
+            return Ok(lot);
+        }
+        let lot = fetch()?;
+        cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(pair.to_string(), lot);
+        Ok(lot)
     }
 
-    fn account(&self) -> Result<Account, BrokerError> {
-        let client = self.require_client()?;
-        let info = client.account_info()?;
+    /// Order quantity as sent to Binance.
+    ///
+    /// Fractional quantities are truncated to the `LOT_SIZE` step (never
+    /// rounded up) and rejected if the result falls outside the filter's
+    /// bounds.
+    fn order_quantity(order: &BrokerOrder, lot: Option<&LotSize>) -> Result<String, BrokerError> {
+        let Some(qty) = order.quantity_scaled else {
+            return Ok(order.quantity.to_string());
+        };
+        let Some(lot) = lot else {
+            return Ok(qty.to_string());
+        };
+        let rounded = qty.floor_to_step(lot.step_size);
+        if rounded.is_zero() || rounded < lot.min_qty || rounded > lot.max_qty {
+            return Err(BrokerError::Order(format!(
+                "quantity {qty} of {} rounds to {rounded}, outside LOT_SIZE [{}, {}]",
+                order.symbol.as_str(),
+                lot.min_qty,
+                lot.max_qty,
+            )));
+        }
+        Ok(rounded.to_string())
+    }
 
+    /// Summarize the quote-asset balance as an account.
+    fn account_from_info(quote_asset: &str, info: &AccountInfo) -> Account {
         // Sum USDT-equivalent balance as a rough equity estimate
         let usdt_balance: f64 = info
             .balances
             .iter()
-            .filter(|b| b.asset == self.quote_asset)
+            .filter(|b| b.asset == quote_asset)
             .map(|b| {
                 let free: f64 = b.free.parse().unwrap_or(0.0);
                 let locked: f64 = b.locked.parse().unwrap_or(0.0);
@@ -122,17 +316,20 @@ impl Broker for BinanceBroker {
 
         let equity_cents = (usdt_balance * 100.0) as i64;
 
-        Ok(Account {
+        Account {
             equity_cents,
             buying_power_cents: equity_cents,
             cash_cents: equity_cents,
             gross_position_value_cents: 0,
-        })
+        }
     }
 
-    fn submit_order(&self, order: &BrokerOrder) -> Result<OrderId, BrokerError> {
-        let client = self.require_client()?;
-        let binance_sym = self.to_binance_symbol(&order.symbol);
+    /// Binance request parameters for an order, with the quantity rounded
+    /// to `lot` (see [`order_quantity`](Self::order_quantity)).
+    fn order_params(
+        order: &BrokerOrder,
+        lot: Option<&LotSize>,
+    ) -> Result<OrderParams, BrokerError> {
         let side = match order.side {
             BrokerSide::Buy => "BUY",
             BrokerSide::Sell => "SELL",
@@ -146,58 +343,145 @@ impl Broker for BinanceBroker {
             }
         };
 
-        let qty_str = format!("{}", order.quantity);
+        Ok(OrderParams {
+            side,
+            order_type,
+            quantity: Self::order_quantity(order, lot)?,
+            price,
+            time_in_force: tif,
+        })
+    }
+
+    fn quote_from_ticker(symbol: &Symbol, ticker: &BookTicker) -> Quote {
+        let bid = Self::parse_price_cents(&ticker.bid_price);
+        let ask = Self::parse_price_cents(&ticker.ask_price);
+        let last = (bid + ask) / 2; // Binance bookTicker doesn't have last; use mid
+
+        Quote {
+            symbol: *symbol,
+            bid_cents: bid,
+            ask_cents: ask,
+            last_cents: last,
+            volume: 0,
+        }
+    }
+
+    /// Parse a decimal string to cents (e.g., "185.50" → 18550).
+    fn parse_price_cents(s: &str) -> i64 {
+        let val: f64 = s.parse().unwrap_or(0.0);
+        (val * 100.0) as i64
+    }
+}
+
+impl Broker for BinanceBroker {
+    fn connect(&mut self) -> Result<(), BrokerError> {
+        let client = BinanceClient::new(&self.api_key, &self.secret_key, self.testnet);
+        client.ping()?;
+        self.client = Some(Box::new(client));
+        Ok(())
+    }
+
+    fn disconnect(&mut self) -> Result<(), BrokerError> {
+        self.client = None;
+        self.stream_stop.store(true, Ordering::Relaxed);
+        self.stream_stop = Arc::default();
+        Ok(())
+    }
+
+    fn positions(&self) -> Result<Vec<Position>, BrokerError> {
+        let client = self.require_client()?;
+        Ok(Self::positions_from_info(&client.account_info()?))
+    }
+
+    fn account(&self) -> Result<Account, BrokerError> {
+        let client = self.require_client()?;
+        Ok(Self::account_from_info(
+            &self.quote_asset,
+            &client.account_info()?,
+        ))
+    }
+
+    fn submit_order(&self, order: &BrokerOrder) -> Result<OrderId, BrokerError> {
+        let client = self.require_client()?;
+        let positions = if order.reduce_only {
+            self.positions()?
+        } else {
+            Vec::new()
+        };
+        let order = &*order.resolve_reduce_only_scaled(&positions, Self::POSITION_DECIMALS)?;
+        let binance_sym = self.to_binance_symbol(&order.symbol);
+        let lot = Self::lot_size_for(&self.lot_sizes, order, &binance_sym, || {
+            client.lot_size(&binance_sym)
+        })?;
+        let params = Self::order_params(order, lot.as_ref())?;
 
         let resp = client.submit_order(
             &binance_sym,
-            side,
-            order_type,
-            &qty_str,
-            price.as_deref(),
-            tif,
+            params.side,
+            params.order_type,
+            &params.quantity,
+            params.price.as_deref(),
+            params.time_in_force,
         )?;
 
-        Ok(OrderId(resp.order_id))
+        let id = OrderId(resp.order_id);
+        self.orders
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id, (binance_sym, order.clone()));
+        Ok(id)
     }
 
     fn order_status(&self, id: OrderId) -> Result<BrokerOrderStatus, BrokerError> {
-        // Binance requires the symbol to query order status.
-        // Since we only have the order ID, return a basic status.
-        // Full implementation would need a local order cache.
-        Ok(BrokerOrderStatus {
-            id,
-            status: OrderState::Submitted,
-            filled_quantity: 0,
-            remaining_quantity: 0,
-            avg_fill_price_cents: 0,
-        })
+        // Binance requires the pair; it was cached when the order was submitted.
+        let (binance_sym, order) = self.cached_order(id)?;
+        let client = self.require_client()?;
+        let resp = client.order_status(&binance_sym, id.0)?;
+        let mut status = Self::status_from_response(id, &resp, Some(order.quantity));
+        if Self::has_fills(&resp) {
+            let trades = client.order_trades(&binance_sym, id.0)?;
+            status.commission_cents =
+                Self::commission_cents(&trades, order.symbol.as_str(), &self.quote_asset);
+        }
+        Ok(status)
     }
 
     fn cancel_order(&self, id: OrderId) -> Result<(), BrokerError> {
-        // Binance requires symbol + orderId. Without a local cache,
-        // this is a placeholder. Full implementation would store
-        // symbol mappings from submit_order.
-        let _ = id;
-        Err(BrokerError::Order(
-            "cancel requires symbol — use BinanceBroker.cancel_order_with_symbol() instead".into(),
-        ))
+        let (binance_sym, _) = self.cached_order(id)?;
+        let client = self.require_client()?;
+        client.cancel_order(&binance_sym, id.0)
     }
 
     fn quote(&self, symbol: &Symbol) -> Result<Quote, BrokerError> {
         let client = self.require_client()?;
         let binance_sym = self.to_binance_symbol(symbol);
         let ticker = client.book_ticker(&binance_sym)?;
+        Ok(Self::quote_from_ticker(symbol, &ticker))
+    }
 
-        let bid = Self::parse_price_cents(&ticker.bid_price);
-        let ask = Self::parse_price_cents(&ticker.ask_price);
-        let last = (bid + ask) / 2; // Binance bookTicker doesn't have last; use mid
+    /// Stream quotes from the public bookTicker WebSocket (no connection or
+    /// API key needed). One background thread serves all `symbols`,
+    /// reconnecting on disconnect; it exits after the receiver is dropped
+    /// (noticed on the next update) or once this broker is disconnected or
+    /// dropped.
+    fn subscribe_quotes(&self, symbols: &[Symbol]) -> Result<Receiver<Quote>, BrokerError> {
+        if symbols.is_empty() {
+            return Err(BrokerError::Other("no symbols to subscribe".into()));
+        }
+        let mut pairs = HashMap::new();
+        for symbol in symbols {
+            let pair = self.to_binance_symbol(symbol);
+            client::validate_query_param(&pair, "symbol")?;
+            pairs.insert(pair, *symbol);
+        }
 
-        Ok(Quote {
-            symbol: *symbol,
-            bid_cents: bid,
-            ask_cents: ask,
-            last_cents: last,
-            volume: 0,
-        })
+        let (tx, rx) = mpsc::channel();
+        let url = self.stream_url.clone();
+        let stop = Arc::clone(&self.stream_stop);
+        thread::Builder::new()
+            .name("binance-book-ticker".into())
+            .spawn(move || websocket::run_book_ticker_stream(&url, pairs, tx, stop))
+            .map_err(|e| BrokerError::Other(format!("failed to spawn stream thread: {e}")))?;
+        Ok(rx)
     }
 }
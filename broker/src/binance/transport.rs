@@ -0,0 +1,326 @@
+//! HTTP transport, rate limiting, and retry/backoff for the Binance client.
+//!
+//! [`BinanceClient`](super::client::BinanceClient) sends every request through
+//! a [`Transport`], after taking a token from a shared [`RateLimiter`]. Responses
+//! with status 429/418 (rate limited) are retried with exponential backoff and
+//! jitter, honoring `Retry-After` when the server sends it. 5xx responses are
+//! retried only for GET and DELETE: Binance documents them as "execution
+//! status unknown", so resending a POST could place an order twice.
+//!
+//! `AsyncBinanceClient` applies the same limiter and policy through
+//! `send_with_retry_async` (with the `async` feature).
+//!
+//! Requests are rebuilt for every attempt, so signed requests carry a fresh
+//! `timestamp` and stay inside Binance's `recvWindow` after a long backoff.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use log::warn;
+use reqwest::blocking::Client;
+
+use crate::error::BrokerError;
+
+/// HTTP method of a Binance REST call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Delete,
+}
+
+impl Method {
+    /// Whether resending after an unknown outcome is safe (GET and DELETE).
+    pub fn is_idempotent(self) -> bool {
+        !matches!(self, Method::Post)
+    }
+}
+
+/// A fully built request (URL already includes query and signature).
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Option<String>,
+}
+
+/// The parts of a response the client needs.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// Parsed `Retry-After` header (seconds), if present.
+    pub retry_after: Option<Duration>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// True for 2xx statuses.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// True when Binance is throttling us (429, or 418 for an IP ban).
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == 429 || self.status == 418
+    }
+}
+
+/// Sends HTTP requests. Implemented over `reqwest::blocking` by
+/// [`ReqwestTransport`]; tests can substitute canned responses.
+pub trait Transport: Send + Sync {
+    /// Send one request. `Err` means the request never produced a response.
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, String>;
+}
+
+/// Default transport over a blocking reqwest client.
+#[derive(Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, String> {
+        let mut builder = match request.method {
+            Method::Get => self.client.get(&request.url),
+            Method::Post => self.client.post(&request.url),
+            Method::Delete => self.client.delete(&request.url),
+        };
+        for (name, value) in &request.headers {
+            builder = builder.header(*name, value);
+        }
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+
+        let resp = builder.send().map_err(|e| e.to_string())?;
+        let status = resp.status().as_u16();
+        let retry_after = retry_after(resp.headers());
+        let body = resp.text().unwrap_or_default();
+        Ok(HttpResponse {
+            status,
+            retry_after,
+            body,
+        })
+    }
+}
+
+/// The `Retry-After` header in whole seconds, if present.
+pub(super) fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get("Retry-After")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// Token-bucket rate limiter shared by all calls on one client.
+///
+/// Holds up to `burst` tokens, refilled at `requests_per_second`. Each request
+/// takes one token; when the bucket is empty the caller waits for the refill.
+#[derive(Debug)]
+pub struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing `requests_per_second` with bursts of `burst`.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            rate: requests_per_second.max(f64::MIN_POSITIVE),
+            burst,
+            state: Mutex::new((burst, Instant::now())),
+        }
+    }
+
+    /// Take a token at `now` and return how long the caller must wait before sending.
+    ///
+    /// Tokens may go negative, so concurrent callers queue up behind each other.
+    pub fn reserve(&self, now: Instant) -> Duration {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (tokens, last) = &mut *state;
+        let elapsed = now.saturating_duration_since(*last).as_secs_f64();
+        *tokens = (*tokens + elapsed * self.rate).min(self.burst);
+        *last = now.max(*last);
+        *tokens -= 1.0;
+        if *tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-*tokens / self.rate)
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    /// 10 requests/second with bursts of 10, well under Binance's weight limits.
+    fn default() -> Self {
+        Self::new(10.0, 10)
+    }
+}
+
+/// How throttled or failed requests are retried.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retrying).
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for each further retry.
+    pub base_delay: Duration,
+    /// Upper bound on any single backoff delay.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 4,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff before retry number `attempt` (0-based), scaled by `jitter` in `[0, 1)`.
+    ///
+    /// The delay is drawn from `[d/2, d)` where `d = min(base · 2^attempt, max)`.
+    pub fn backoff(&self, attempt: u32, jitter: f64) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(1u32.checked_shl(attempt.min(31)).unwrap_or(u32::MAX))
+            .min(self.max_delay);
+        exp.mul_f64(0.5 + 0.5 * jitter.clamp(0.0, 1.0))
+    }
+
+    /// Delay before retrying after `response`: `Retry-After` if present, else backoff.
+    pub fn delay_for(&self, response: &HttpResponse, attempt: u32, jitter: f64) -> Duration {
+        response
+            .retry_after
+            .unwrap_or_else(|| self.backoff(attempt, jitter))
+    }
+
+    /// Delay before retry number `attempt` of a `method` request that got
+    /// `response`, or `None` to hand the response back as-is.
+    ///
+    /// Rate-limited responses are rejected before execution and always
+    /// retried; 5xx responses only for idempotent methods.
+    fn next_delay(
+        &self,
+        method: Method,
+        response: &HttpResponse,
+        attempt: u32,
+    ) -> Result<Option<Duration>, BrokerError> {
+        let retryable =
+            response.is_rate_limited() || (response.status >= 500 && method.is_idempotent());
+        if !retryable {
+            return Ok(None);
+        }
+        if attempt >= self.max_retries {
+            if response.is_rate_limited() {
+                return Err(BrokerError::RateLimited(format!(
+                    "status {} after {} retries: {}",
+                    response.status, self.max_retries, response.body
+                )));
+            }
+            return Ok(None);
+        }
+
+        let delay = self.delay_for(response, attempt, jitter());
+        warn!(
+            "Binance returned {}; retrying in {delay:?} (attempt {}/{})",
+            response.status,
+            attempt + 1,
+            self.max_retries
+        );
+        Ok(Some(delay))
+    }
+}
+
+/// Send the request made by `build`, waiting on `limiter` and retrying per `policy`.
+///
+/// `build` runs once per attempt, after any wait, so signed requests get a
+/// fresh timestamp. `sleep` performs every wait (rate limiting and backoff),
+/// which lets tests record delays instead of sleeping. Returns the final
+/// response; a response that is still rate limited after all retries becomes
+/// [`BrokerError::RateLimited`]. Other 5xx responses, including the first one
+/// to a POST, are returned as-is for the caller's normal status handling.
+pub fn send_with_retry(
+    transport: &dyn Transport,
+    limiter: &RateLimiter,
+    policy: &RetryPolicy,
+    build: &mut dyn FnMut() -> HttpRequest,
+    sleep: &mut dyn FnMut(Duration),
+) -> Result<HttpResponse, BrokerError> {
+    let mut attempt = 0;
+    loop {
+        let wait = limiter.reserve(Instant::now());
+        if !wait.is_zero() {
+            sleep(wait);
+        }
+
+        let request = build();
+        let response = transport
+            .send(&request)
+            .map_err(|e| BrokerError::Connection(format!("request failed: {e}")))?;
+
+        match policy.next_delay(request.method, &response, attempt)? {
+            Some(delay) => sleep(delay),
+            None => return Ok(response),
+        }
+        attempt += 1;
+    }
+}
+
+/// Async counterpart of [`send_with_retry`], waiting with `tokio::time::sleep`.
+///
+/// `send` builds and sends one attempt of a `method` request; like `build`
+/// above it runs after any wait, so signed requests get a fresh timestamp.
+#[cfg(feature = "async")]
+pub async fn send_with_retry_async<F, Fut>(
+    limiter: &RateLimiter,
+    policy: &RetryPolicy,
+    method: Method,
+    mut send: F,
+) -> Result<HttpResponse, BrokerError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<HttpResponse, String>>,
+{
+    let mut attempt = 0;
+    loop {
+        let wait = limiter.reserve(Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+
+        let response = send()
+            .await
+            .map_err(|e| BrokerError::Connection(format!("request failed: {e}")))?;
+
+        match policy.next_delay(method, &response, attempt)? {
+            Some(delay) => tokio::time::sleep(delay).await,
+            None => return Ok(response),
+        }
+        attempt += 1;
+    }
+}
+
+/// Cheap jitter in `[0, 1)`: splitmix64 over the clock and a call counter.
+fn jitter() -> f64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    let mut z = nanos
+        ^ COUNTER
+            .fetch_add(1, Ordering::Relaxed)
+            .wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -2,6 +2,8 @@
 
 use serde::Deserialize;
 
+use crate::types::DecimalQty;
+
 /// Binance account balance entry.
 #[derive(Debug, Deserialize)]
 pub struct BalanceInfo {
@@ -27,7 +29,8 @@ pub struct OrderResponse {
     pub order_id: u64,
     pub status: String,
     pub executed_qty: String,
-    #[serde(default)]
+    /// Spot `cummulativeQuoteQty`; futures responses call it `cumQuote`.
+    #[serde(default, alias = "cumQuote")]
     pub cummulative_quote_qty: String,
 }
 
@@ -41,3 +44,143 @@ pub struct BookTicker {
     pub ask_price: String,
     pub ask_qty: String,
 }
+
+/// One fill of an order (`GET /api/v3/myTrades`, `/fapi/v1/userTrades`).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountTrade {
+    pub symbol: String,
+    pub order_id: u64,
+    pub price: String,
+    pub qty: String,
+    pub commission: String,
+    pub commission_asset: String,
+}
+
+/// Exchange info response (`GET /api/v3/exchangeInfo`, `/fapi/v1/exchangeInfo`).
+#[derive(Debug, Deserialize)]
+pub struct ExchangeInfo {
+    pub symbols: Vec<SymbolInfo>,
+}
+
+/// Trading rules for one pair.
+#[derive(Debug, Deserialize)]
+pub struct SymbolInfo {
+    pub symbol: String,
+    #[serde(default)]
+    pub filters: Vec<SymbolFilter>,
+}
+
+/// One entry of [`SymbolInfo::filters`]; only the quantity fields are kept.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolFilter {
+    pub filter_type: String,
+    #[serde(default)]
+    pub min_qty: Option<String>,
+    #[serde(default)]
+    pub max_qty: Option<String>,
+    #[serde(default)]
+    pub step_size: Option<String>,
+}
+
+/// `LOT_SIZE` filter: order quantities must lie in `[min_qty, max_qty]` and
+/// be a multiple of `step_size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LotSize {
+    pub min_qty: DecimalQty,
+    pub max_qty: DecimalQty,
+    pub step_size: DecimalQty,
+}
+
+impl ExchangeInfo {
+    /// The `LOT_SIZE` filter of `symbol`, if listed and well-formed.
+    pub fn lot_size(&self, symbol: &str) -> Option<LotSize> {
+        let filter = self
+            .symbols
+            .iter()
+            .find(|s| s.symbol == symbol)?
+            .filters
+            .iter()
+            .find(|f| f.filter_type == "LOT_SIZE")?;
+        let parse = |field: &Option<String>| DecimalQty::parse(field.as_deref()?);
+        Some(LotSize {
+            min_qty: parse(&filter.min_qty)?,
+            max_qty: parse(&filter.max_qty)?,
+            step_size: parse(&filter.step_size)?,
+        })
+    }
+}
+
+/// USDT-M futures position (`GET /fapi/v2/positionRisk`).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesPosition {
+    pub symbol: String,
+    /// Signed size: negative for shorts.
+    pub position_amt: String,
+    pub entry_price: String,
+    pub mark_price: String,
+    pub un_realized_profit: String,
+    #[serde(default)]
+    pub leverage: String,
+    /// Last position change (ms since epoch).
+    #[serde(default)]
+    pub update_time: u64,
+}
+
+/// USDT-M futures account summary (`GET /fapi/v2/account`).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesAccount {
+    pub total_wallet_balance: String,
+    pub total_unrealized_profit: String,
+    pub total_margin_balance: String,
+    pub available_balance: String,
+    #[serde(default)]
+    pub positions: Vec<FuturesAccountPosition>,
+}
+
+/// Per-symbol entry in [`FuturesAccount::positions`].
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuturesAccountPosition {
+    pub symbol: String,
+    #[serde(default)]
+    pub notional: String,
+}
+
+/// Income history record (`GET /fapi/v1/income`), e.g. a funding payment.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeRecord {
+    pub symbol: String,
+    pub income_type: String,
+    pub income: String,
+    pub time: u64,
+}
+
+/// Combined-stream wrapper: `{"stream": "...", "data": {...}}`.
+#[derive(Debug, Deserialize)]
+pub struct StreamEnvelope<T> {
+    pub stream: String,
+    pub data: T,
+}
+
+/// `<symbol>@bookTicker` stream event.
+#[derive(Debug, Deserialize)]
+pub struct BookTickerEvent {
+    /// Order book update id; increases with every change.
+    #[serde(rename = "u")]
+    pub update_id: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "b")]
+    pub bid_price: String,
+    #[serde(rename = "B")]
+    pub bid_qty: String,
+    #[serde(rename = "a")]
+    pub ask_price: String,
+    #[serde(rename = "A")]
+    pub ask_qty: String,
+}
@@ -0,0 +1,182 @@
+//! Binance bookTicker quote stream over a blocking WebSocket.
+//!
+//! The WebSocket protocol (handshake validation, framing, masking, ping/pong)
+//! is handled by `tungstenite`; `wss://` URLs go through native-tls and
+//! `ws://` is plain TCP (used by tests).
+
+use std::collections::HashMap;
+use std::io;
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{debug, warn};
+use nanobook::Symbol;
+use tungstenite::client::IntoClientRequest;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Error as WsError, Message, WebSocket};
+
+use super::BinanceBroker;
+use super::types::{BookTickerEvent, StreamEnvelope};
+use crate::types::Quote;
+
+/// Longest wait for the TCP/TLS connect and upgrade to complete.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Socket read timeout; bounds how long the stop flag goes unchecked.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Silence after which the connection is presumed dead and reopened.
+/// Binance pings every 20 seconds, so a live connection is never this quiet.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Connect to a `ws://` or `wss://` URL with a read timeout on the socket.
+fn connect(url: &str) -> Result<WebSocket<MaybeTlsStream<TcpStream>>, WsError> {
+    let request = url.into_client_request()?;
+    let uri = request.uri();
+    let host = uri.host().unwrap_or_default().to_string();
+    let port = uri
+        .port_u16()
+        .unwrap_or(if uri.scheme_str() == Some("wss") {
+            443
+        } else {
+            80
+        });
+
+    let tcp = TcpStream::connect((host.as_str(), port))?;
+    tcp.set_nodelay(true)?;
+    tcp.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let socket = tcp.try_clone()?;
+
+    let (ws, _) = tungstenite::client_tls(request, tcp).map_err(|e| match e {
+        tungstenite::HandshakeError::Failure(e) => e,
+        tungstenite::HandshakeError::Interrupted(_) => WsError::Io(io::Error::new(
+            io::ErrorKind::TimedOut,
+            "handshake timed out",
+        )),
+    })?;
+    socket.set_read_timeout(Some(POLL_INTERVAL))?;
+    Ok(ws)
+}
+
+/// Stream `pairs` (Binance pair → nanobook symbol) from the combined bookTicker
+/// stream at `base_url` into `tx`, reconnecting with backoff.
+///
+/// Returns once `tx` is closed or `stop` is set. `stop` is checked before
+/// every reconnect and whenever a read times out, so the thread also exits
+/// while the server is unreachable or silent.
+///
+/// Updates whose `u` (order book update id) is not newer than the last one
+/// delivered for that pair are dropped, so a reconnect never replays stale
+/// quotes. Blocks the calling thread; run it on a dedicated one.
+pub(super) fn run_book_ticker_stream(
+    base_url: &str,
+    pairs: HashMap<String, Symbol>,
+    tx: Sender<Quote>,
+    stop: Arc<AtomicBool>,
+) {
+    let streams: Vec<String> = pairs
+        .keys()
+        .map(|pair| format!("{}@bookTicker", pair.to_lowercase()))
+        .collect();
+    let url = format!("{base_url}/stream?streams={}", streams.join("/"));
+    let mut last_update: HashMap<String, u64> = HashMap::new();
+    let mut backoff = Duration::from_millis(100);
+
+    while !stop.load(Ordering::Relaxed) {
+        match connect(&url) {
+            Ok(mut ws) => {
+                debug!("Connected to {url}");
+                backoff = Duration::from_millis(100);
+                let mut last_message = Instant::now();
+                loop {
+                    if stop.load(Ordering::Relaxed) {
+                        let _ = ws.close(None);
+                        return;
+                    }
+                    let message = match ws.read() {
+                        Ok(message) => message,
+                        Err(WsError::Io(e))
+                            if matches!(
+                                e.kind(),
+                                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                            ) =>
+                        {
+                            if last_message.elapsed() >= IDLE_TIMEOUT {
+                                warn!("bookTicker stream idle for {IDLE_TIMEOUT:?}; reconnecting");
+                                break;
+                            }
+                            continue;
+                        }
+                        Err(WsError::ConnectionClosed) => break,
+                        Err(e) => {
+                            warn!("bookTicker stream error: {e}");
+                            break;
+                        }
+                    };
+                    last_message = Instant::now();
+                    let payload = match message {
+                        Message::Text(text) => text.as_bytes().to_vec(),
+                        Message::Binary(bytes) => bytes.to_vec(),
+                        _ => continue,
+                    };
+                    let Some(event) = parse_book_ticker(&payload) else {
+                        continue;
+                    };
+                    let Some(symbol) = pairs.get(&event.symbol) else {
+                        continue;
+                    };
+                    if last_update
+                        .get(&event.symbol)
+                        .is_some_and(|&last| event.update_id <= last)
+                    {
+                        continue;
+                    }
+                    last_update.insert(event.symbol.clone(), event.update_id);
+
+                    if tx.send(quote_from_event(*symbol, &event)).is_err() {
+                        return; // receiver dropped
+                    }
+                }
+            }
+            Err(e) => warn!("bookTicker connect to {url} failed: {e}"),
+        }
+        sleep_unless_stopped(backoff, &stop);
+        backoff = (backoff * 2).min(Duration::from_secs(30));
+    }
+}
+
+/// Sleep for `duration` in [`POLL_INTERVAL`] steps, returning early once `stop` is set.
+fn sleep_unless_stopped(duration: Duration, stop: &AtomicBool) {
+    let deadline = Instant::now() + duration;
+    while !stop.load(Ordering::Relaxed) {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return;
+        }
+        thread::sleep(left.min(POLL_INTERVAL));
+    }
+}
+
+/// Parse a combined-stream envelope or a bare bookTicker event.
+fn parse_book_ticker(message: &[u8]) -> Option<BookTickerEvent> {
+    serde_json::from_slice::<StreamEnvelope<BookTickerEvent>>(message)
+        .map(|envelope| envelope.data)
+        .or_else(|_| serde_json::from_slice(message))
+        .ok()
+}
+
+fn quote_from_event(symbol: Symbol, event: &BookTickerEvent) -> Quote {
+    let bid = BinanceBroker::parse_price_cents(&event.bid_price);
+    let ask = BinanceBroker::parse_price_cents(&event.ask_price);
+    Quote {
+        symbol,
+        bid_cents: bid,
+        ask_cents: ask,
+        last_cents: (bid + ask) / 2,
+        volume: 0,
+    }
+}
@@ -18,8 +18,8 @@ pub enum BrokerError {
     #[error("authentication error: {0}")]
     Auth(String),
 
-    #[error("rate limit exceeded")]
-    RateLimit,
+    #[error("rate limited: {0}")]
+    RateLimited(String),
 
     #[error("{0}")]
     Other(String),
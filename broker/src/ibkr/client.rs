@@ -1,5 +1,8 @@
 //! IBKR connection, position fetching, market data, and account summary.
 
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
 use ibapi::accounts::types::AccountGroup;
 use ibapi::accounts::{AccountSummaryResult, PositionUpdate};
 use ibapi::client::blocking::Client;
@@ -167,6 +170,59 @@ impl IbkrClient {
         })
     }
 
+    /// Stream quotes for `symbols` from streaming (non-snapshot) market data.
+    ///
+    /// Each symbol gets its own subscription and feeder thread. A quote is sent
+    /// whenever bid, ask, or last changes; repeated ticks at the same prices are
+    /// dropped. A feeder exits when its subscription ends (e.g. on disconnect)
+    /// or after the receiver is dropped.
+    pub fn subscribe_quotes(&self, symbols: &[Symbol]) -> Result<Receiver<Quote>, BrokerError> {
+        let (tx, rx) = mpsc::channel();
+        for symbol in symbols {
+            let contract = Contract::stock(symbol.as_str()).build();
+            let subscription = self
+                .client
+                .market_data(&contract)
+                .subscribe()
+                .map_err(|e| BrokerError::Connection(format!("market data request failed: {e}")))?;
+
+            let symbol = *symbol;
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut quote = Quote {
+                    symbol,
+                    bid_cents: 0,
+                    ask_cents: 0,
+                    last_cents: 0,
+                    volume: 0,
+                };
+                for tick in subscription {
+                    let (tick_type, price) = match tick {
+                        TickTypes::Price(t) => (t.tick_type, t.price),
+                        TickTypes::PriceSize(ps) => (ps.price_tick_type, ps.price),
+                        _ => continue,
+                    };
+                    let cents = (price * 100.0) as i64;
+                    let field = match tick_type {
+                        TickType::Bid => &mut quote.bid_cents,
+                        TickType::Ask => &mut quote.ask_cents,
+                        TickType::Last => &mut quote.last_cents,
+                        _ => continue,
+                    };
+                    if cents <= 0 || *field == cents {
+                        continue;
+                    }
+                    *field = cents;
+                    if tx.send(quote.clone()).is_err() {
+                        break; // receiver dropped
+                    }
+                }
+                debug!("Quote stream for {symbol} ended");
+            });
+        }
+        Ok(rx)
+    }
+
     /// Fetch bid/ask midpoint price for a symbol, in cents.
     pub fn mid_price(&self, symbol: &Symbol) -> Result<i64, BrokerError> {
         let q = self.quote(symbol)?;
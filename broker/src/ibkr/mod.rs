@@ -2,6 +2,7 @@
 
 pub mod client;
 pub mod orders;
+pub mod status;
 
 use nanobook::Symbol;
 
@@ -9,6 +10,7 @@ use crate::Broker;
 use crate::error::BrokerError;
 use crate::types::*;
 use client::IbkrClient;
+use status::OrderStatusTracker;
 
 /// Interactive Brokers broker, wrapping the TWS/Gateway blocking API.
 pub struct IbkrBroker {
@@ -16,6 +18,8 @@ pub struct IbkrBroker {
     port: u16,
     client_id: i32,
     client: Option<IbkrClient>,
+    /// Live status of orders submitted through this handle.
+    orders: OrderStatusTracker,
 }
 
 impl IbkrBroker {
@@ -26,6 +30,7 @@ impl IbkrBroker {
             port,
             client_id,
             client: None,
+            orders: OrderStatusTracker::new(),
         }
     }
 
@@ -35,6 +40,11 @@ impl IbkrBroker {
         self.client.as_ref()
     }
 
+    /// The tracker fed by TWS status callbacks for orders submitted here.
+    pub fn order_tracker(&self) -> &OrderStatusTracker {
+        &self.orders
+    }
+
     fn require_client(&self) -> Result<&IbkrClient, BrokerError> {
         self.client.as_ref().ok_or(BrokerError::NotConnected)
     }
@@ -62,20 +72,17 @@ impl Broker for IbkrBroker {
 
     fn submit_order(&self, order: &BrokerOrder) -> Result<OrderId, BrokerError> {
         let client = self.require_client()?;
-        orders::submit_order(client.inner(), order)
+        let order = crate::resolve_order(self, order)?;
+        orders::submit_order_tracked(client.inner(), &order, &self.orders)
     }
 
     fn order_status(&self, id: OrderId) -> Result<BrokerOrderStatus, BrokerError> {
         let _client = self.require_client()?;
-        // IBKR order status is tracked via the PlaceOrder subscription;
-        // for now return a basic pending status. Full implementation requires
-        // storing active order subscriptions.
-        Ok(BrokerOrderStatus {
-            id,
-            status: OrderState::Submitted,
-            filled_quantity: 0,
-            remaining_quantity: 0,
-            avg_fill_price_cents: 0,
+        self.orders.status(id).ok_or_else(|| {
+            BrokerError::Order(format!(
+                "order {} was not submitted through this connection",
+                id.0
+            ))
         })
     }
 
@@ -88,4 +95,11 @@ impl Broker for IbkrBroker {
     fn quote(&self, symbol: &Symbol) -> Result<Quote, BrokerError> {
         self.require_client()?.quote(symbol)
     }
+
+    fn subscribe_quotes(
+        &self,
+        symbols: &[Symbol],
+    ) -> Result<std::sync::mpsc::Receiver<Quote>, BrokerError> {
+        self.require_client()?.subscribe_quotes(symbols)
+    }
 }
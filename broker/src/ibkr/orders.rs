@@ -9,6 +9,7 @@ use ibapi::orders::order_builder::limit_order;
 use ibapi::orders::{Action as IbAction, CancelOrder, PlaceOrder};
 use log::{debug, info, warn};
 
+use super::status::{OrderStatusTracker, StatusUpdate};
 use crate::error::BrokerError;
 use crate::types::*;
 
@@ -34,6 +35,41 @@ pub enum OrderOutcome {
 
 /// Submit an order via the IBKR API. Returns the broker-assigned OrderId.
 pub fn submit_order(client: &Client, order: &BrokerOrder) -> Result<OrderId, BrokerError> {
+    let (order_id, _subscription) = place(client, order)?;
+    Ok(order_id)
+}
+
+/// Submit an order and keep its status subscription alive.
+///
+/// The order is registered with `tracker`, and a background thread feeds
+/// every `OrderStatus`/`ExecutionData` message from TWS into it until the
+/// subscription ends.
+pub fn submit_order_tracked(
+    client: &Client,
+    order: &BrokerOrder,
+    tracker: &OrderStatusTracker,
+) -> Result<OrderId, BrokerError> {
+    let (order_id, subscription) = place(client, order)?;
+    tracker.register(order_id, order.quantity);
+
+    let tracker = tracker.clone();
+    thread::spawn(move || {
+        for response in subscription {
+            if let Some(update) = StatusUpdate::from_place_order(&response) {
+                tracker.apply(&update);
+            }
+        }
+        debug!("Status stream for order {} ended", order_id.0);
+    });
+
+    Ok(order_id)
+}
+
+/// Build and place an order, returning its id and status subscription.
+fn place(
+    client: &Client,
+    order: &BrokerOrder,
+) -> Result<(OrderId, ibapi::subscriptions::Subscription<PlaceOrder>), BrokerError> {
     let contract = Contract::stock(order.symbol.as_str()).build();
 
     let ib_action = match order.side {
@@ -64,11 +100,11 @@ pub fn submit_order(client: &Client, order: &BrokerOrder) -> Result<OrderId, Bro
         order.side, order.quantity, order.symbol, limit_price, order_id
     );
 
-    let _subscription = client
+    let subscription = client
         .place_order(order_id, &contract, &ib_order)
         .map_err(|e| BrokerError::Order(format!("failed to place order {order_id}: {e}")))?;
 
-    Ok(OrderId(order_id as u64))
+    Ok((OrderId(order_id as u64), subscription))
 }
 
 /// Execute a rebalance-style order: submit limit, poll for fill, cancel on timeout.
@@ -156,10 +192,8 @@ pub fn execute_limit_order(
                 commission = comm.commission;
                 debug!("Commission: ${:.4}", commission);
             }
-            PlaceOrder::Message(notice) => {
-                if notice.code < 0 || notice.code >= 2000 {
-                    warn!("Order {order_id} error {}: {}", notice.code, notice.message);
-                }
+            PlaceOrder::Message(notice) if notice.code < 0 || notice.code >= 2000 => {
+                warn!("Order {order_id} error {}: {}", notice.code, notice.message);
             }
             _ => {}
         }
@@ -0,0 +1,212 @@
+//! Order status tracking fed by the TWS order-status stream.
+//!
+//! `place_order` returns a subscription that emits `OrderStatus`,
+//! `ExecutionData` and `CommissionReport` messages for the order. [`OrderStatusTracker`] folds those
+//! into a per-order [`BrokerOrderStatus`] so `order_status` can answer from
+//! memory instead of a stub.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use ibapi::orders::PlaceOrder;
+
+use crate::types::{BrokerOrderStatus, OrderId, OrderState};
+
+/// A status update for a tracked order, decoupled from the ibapi wire types.
+#[derive(Debug, Clone)]
+pub enum StatusUpdate {
+    /// An `orderStatus` callback (cumulative filled/remaining, TWS status string).
+    Status {
+        order_id: OrderId,
+        status: String,
+        filled: f64,
+        remaining: f64,
+        avg_fill_price: f64,
+    },
+    /// An `execDetails` callback for one (partial) execution.
+    Execution {
+        order_id: OrderId,
+        execution_id: String,
+        shares: f64,
+        price: f64,
+    },
+    /// A `commissionReport` callback for one execution (account currency).
+    Commission {
+        execution_id: String,
+        commission: f64,
+    },
+}
+
+impl StatusUpdate {
+    /// Convert a `PlaceOrder` subscription message, if it carries status.
+    pub fn from_place_order(msg: &PlaceOrder) -> Option<Self> {
+        match msg {
+            PlaceOrder::OrderStatus(s) => Some(Self::Status {
+                order_id: OrderId(s.order_id as u64),
+                status: s.status.clone(),
+                filled: s.filled,
+                remaining: s.remaining,
+                avg_fill_price: s.average_fill_price,
+            }),
+            PlaceOrder::ExecutionData(e) => Some(Self::Execution {
+                order_id: OrderId(e.execution.order_id as u64),
+                execution_id: e.execution.execution_id.clone(),
+                shares: e.execution.shares,
+                price: e.execution.price,
+            }),
+            PlaceOrder::CommissionReport(c) => Some(Self::Commission {
+                execution_id: c.execution_id.clone(),
+                commission: c.commission,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TrackedOrder {
+    quantity: u64,
+    state: OrderState,
+    /// Cumulative filled quantity from executions.
+    exec_shares: f64,
+    /// Sum of shares × price over executions (for VWAP).
+    exec_notional: f64,
+    /// Execution ids already counted (TWS may resend).
+    exec_ids: Vec<String>,
+    /// Commission per execution id (a resent report overwrites).
+    commissions: HashMap<String, f64>,
+    /// Latest cumulative figures from `orderStatus`.
+    status_filled: f64,
+    status_avg_price: f64,
+}
+
+/// Shared, thread-safe map of order id → live status.
+///
+/// Cloning shares the underlying map, so a background thread draining a
+/// subscription and the broker answering `order_status` see the same state.
+#[derive(Debug, Clone, Default)]
+pub struct OrderStatusTracker {
+    orders: Arc<Mutex<HashMap<OrderId, TrackedOrder>>>,
+}
+
+impl OrderStatusTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking an order of `quantity` shares.
+    pub fn register(&self, id: OrderId, quantity: u64) {
+        self.lock().insert(
+            id,
+            TrackedOrder {
+                quantity,
+                state: OrderState::Pending,
+                exec_shares: 0.0,
+                exec_notional: 0.0,
+                exec_ids: Vec::new(),
+                commissions: HashMap::new(),
+                status_filled: 0.0,
+                status_avg_price: 0.0,
+            },
+        );
+    }
+
+    /// Fold one update into the tracked state. Updates for unregistered
+    /// orders, and commissions for executions not yet seen, are ignored
+    /// (TWS reports an execution before its commission).
+    pub fn apply(&self, update: &StatusUpdate) {
+        let mut orders = self.lock();
+        match update {
+            StatusUpdate::Status {
+                order_id,
+                status,
+                filled,
+                remaining,
+                avg_fill_price,
+            } => {
+                let Some(order) = orders.get_mut(order_id) else {
+                    return;
+                };
+                order.status_filled = *filled;
+                order.status_avg_price = *avg_fill_price;
+                order.state = map_status(status, *filled, *remaining);
+            }
+            StatusUpdate::Execution {
+                order_id,
+                execution_id,
+                shares,
+                price,
+            } => {
+                let Some(order) = orders.get_mut(order_id) else {
+                    return;
+                };
+                if order.exec_ids.iter().any(|id| id == execution_id) {
+                    return;
+                }
+                order.exec_ids.push(execution_id.clone());
+                order.exec_shares += shares;
+                order.exec_notional += shares * price;
+                if order.state != OrderState::Filled && order.state != OrderState::Cancelled {
+                    order.state = if order.exec_shares >= order.quantity as f64 {
+                        OrderState::Filled
+                    } else {
+                        OrderState::PartiallyFilled
+                    };
+                }
+            }
+            StatusUpdate::Commission {
+                execution_id,
+                commission,
+            } => {
+                if let Some(order) = orders
+                    .values_mut()
+                    .find(|o| o.exec_ids.contains(execution_id))
+                {
+                    order.commissions.insert(execution_id.clone(), *commission);
+                }
+            }
+        }
+    }
+
+    /// Current status of a tracked order, or `None` if it was never registered.
+    pub fn status(&self, id: OrderId) -> Option<BrokerOrderStatus> {
+        let orders = self.lock();
+        let order = orders.get(&id)?;
+
+        // Executions are the finer-grained source; fall back to the
+        // cumulative orderStatus figures when none have arrived yet.
+        let (filled, avg_price) =
+            if order.exec_shares >= order.status_filled && order.exec_shares > 0.0 {
+                (order.exec_shares, order.exec_notional / order.exec_shares)
+            } else {
+                (order.status_filled, order.status_avg_price)
+            };
+        let filled_quantity = filled.round().max(0.0) as u64;
+
+        Some(BrokerOrderStatus {
+            id,
+            status: order.state,
+            filled_quantity,
+            remaining_quantity: order.quantity.saturating_sub(filled_quantity),
+            avg_fill_price_cents: (avg_price * 100.0).round() as i64,
+            commission_cents: (order.commissions.values().sum::<f64>() * 100.0).round() as i64,
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<OrderId, TrackedOrder>> {
+        self.orders.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Map a TWS status string onto the generic order state.
+pub fn map_status(status: &str, filled: f64, remaining: f64) -> OrderState {
+    match status {
+        "Filled" => OrderState::Filled,
+        "Cancelled" | "ApiCancelled" => OrderState::Cancelled,
+        "Inactive" => OrderState::Rejected,
+        "ApiPending" | "PendingSubmit" => OrderState::Pending,
+        _ if filled > 0.0 && remaining > 0.0 => OrderState::PartiallyFilled,
+        _ => OrderState::Submitted,
+    }
+}
@@ -5,9 +5,17 @@
 //!
 //! - **IBKR** (feature `ibkr`): Interactive Brokers via TWS API
 //! - **Binance** (feature `binance`): Binance spot REST API
+//! - **Paper**: `PaperBroker`, backed by the nanobook matching engine
+//!
+//! [`BrokerSession`] wraps any broker to track its equity curve and drawdown.
+//!
+//! With the `async` feature, [`AsyncBroker`] offers the same operations as
+//! futures, plus concurrent batch quote fetching.
 
 pub mod error;
 pub mod mock;
+pub mod paper;
+pub mod session;
 pub mod types;
 
 #[cfg(feature = "ibkr")]
@@ -16,9 +24,18 @@ pub mod ibkr;
 #[cfg(feature = "binance")]
 pub mod binance;
 
+#[cfg(feature = "async")]
+pub mod async_broker;
+
+#[cfg(feature = "async")]
+pub use async_broker::AsyncBroker;
 pub use error::BrokerError;
+pub use session::BrokerSession;
 pub use types::*;
 
+use std::borrow::Cow;
+use std::sync::mpsc::Receiver;
+
 use nanobook::Symbol;
 
 /// A broker connection that can fetch positions, submit orders, and get quotes.
@@ -26,7 +43,7 @@ pub trait Broker {
     /// Connect to the broker.
     fn connect(&mut self) -> Result<(), BrokerError>;
 
-    /// Disconnect gracefully.
+    /// Disconnect gracefully. Calling this when already disconnected is a no-op.
     fn disconnect(&mut self) -> Result<(), BrokerError>;
 
     /// Get all current positions.
@@ -36,6 +53,10 @@ pub trait Broker {
     fn account(&self) -> Result<Account, BrokerError>;
 
     /// Submit an order. Returns order ID.
+    ///
+    /// Implementations honor [`BrokerOrder::reduce_only`] by clamping the
+    /// quantity to the current position (see
+    /// [`BrokerOrder::resolve_reduce_only`]).
     fn submit_order(&self, order: &BrokerOrder) -> Result<OrderId, BrokerError>;
 
     /// Get status of a submitted order.
@@ -46,4 +67,29 @@ pub trait Broker {
 
     /// Get current quote for a symbol.
     fn quote(&self, symbol: &Symbol) -> Result<Quote, BrokerError>;
+
+    /// Stream live quotes for `symbols` over a channel.
+    ///
+    /// Implementations feed the channel from background threads they own;
+    /// those threads exit once the receiver is dropped (noticed on their next
+    /// update). The default returns an error for brokers without streaming.
+    fn subscribe_quotes(&self, symbols: &[Symbol]) -> Result<Receiver<Quote>, BrokerError> {
+        let _ = symbols;
+        Err(BrokerError::Other(
+            "quote streaming is not supported by this broker".into(),
+        ))
+    }
+}
+
+/// Apply `order.reduce_only` against the broker's current positions.
+///
+/// Positions are only fetched for reduce-only orders.
+pub(crate) fn resolve_order<'a, B: Broker + ?Sized>(
+    broker: &B,
+    order: &'a BrokerOrder,
+) -> Result<Cow<'a, BrokerOrder>, BrokerError> {
+    if !order.reduce_only {
+        return Ok(Cow::Borrowed(order));
+    }
+    order.resolve_reduce_only(&broker.positions()?)
 }
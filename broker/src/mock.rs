@@ -14,7 +14,7 @@
 //! ```
 
 use std::sync::Mutex;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 use nanobook::Symbol;
 
@@ -48,6 +48,7 @@ pub struct MockBrokerBuilder {
     positions: Vec<Position>,
     quotes: Vec<(Symbol, Quote)>,
     equity_cents: i64,
+    equity_sequence: Vec<i64>,
     cash_cents: i64,
 }
 
@@ -89,6 +90,13 @@ impl MockBrokerBuilder {
         self
     }
 
+    /// Report these equities from successive `account()` calls, repeating
+    /// the last one once the sequence is exhausted.
+    pub fn with_equity_sequence(mut self, equity_cents: impl IntoIterator<Item = i64>) -> Self {
+        self.equity_sequence = equity_cents.into_iter().collect();
+        self
+    }
+
     pub fn build(self) -> MockBroker {
         MockBroker {
             connected: false,
@@ -96,6 +104,8 @@ impl MockBrokerBuilder {
             positions: self.positions,
             quotes: self.quotes,
             equity_cents: self.equity_cents,
+            equity_sequence: self.equity_sequence,
+            account_calls: AtomicUsize::new(0),
             cash_cents: self.cash_cents,
             next_order_id: AtomicU64::new(1),
             submitted_orders: Mutex::new(Vec::new()),
@@ -110,6 +120,8 @@ pub struct MockBroker {
     positions: Vec<Position>,
     quotes: Vec<(Symbol, Quote)>,
     equity_cents: i64,
+    equity_sequence: Vec<i64>,
+    account_calls: AtomicUsize,
     cash_cents: i64,
     next_order_id: AtomicU64,
     submitted_orders: Mutex<Vec<RecordedOrder>>,
@@ -122,6 +134,7 @@ impl MockBroker {
             positions: Vec::new(),
             quotes: Vec::new(),
             equity_cents: 100_000_000,
+            equity_sequence: Vec::new(),
             cash_cents: 100_000_000,
         }
     }
@@ -159,8 +172,13 @@ impl Broker for MockBroker {
             .iter()
             .map(|p| p.market_value_cents.abs())
             .sum();
+        let call = self.account_calls.fetch_add(1, Ordering::Relaxed);
+        let equity_cents = match self.equity_sequence.len() {
+            0 => self.equity_cents,
+            n => self.equity_sequence[call.min(n - 1)],
+        };
         Ok(Account {
-            equity_cents: self.equity_cents,
+            equity_cents,
             buying_power_cents: self.cash_cents,
             cash_cents: self.cash_cents,
             gross_position_value_cents: gross,
@@ -171,6 +189,7 @@ impl Broker for MockBroker {
         if !self.connected {
             return Err(BrokerError::NotConnected);
         }
+        let order = crate::resolve_order(self, order)?;
 
         // Record the order
         self.submitted_orders.lock().unwrap().push(RecordedOrder {
@@ -207,6 +226,7 @@ impl Broker for MockBroker {
             filled_quantity: filled,
             remaining_quantity: remaining,
             avg_fill_price_cents: 0,
+            commission_cents: 0,
         })
     }
 
@@ -231,6 +251,8 @@ impl Broker for MockBroker {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::inconsistent_digit_grouping)]
+
     use super::*;
     use nanobook::Price;
 
@@ -278,6 +300,8 @@ mod tests {
             side: BrokerSide::Buy,
             quantity: 50,
             order_type: BrokerOrderType::Limit(Price(150_00)),
+            reduce_only: false,
+            quantity_scaled: None,
         };
 
         let id = broker.submit_order(&order).unwrap();
@@ -299,6 +323,8 @@ mod tests {
             side: BrokerSide::Buy,
             quantity: 50,
             order_type: BrokerOrderType::Market,
+            reduce_only: false,
+            quantity_scaled: None,
         };
 
         assert!(broker.submit_order(&order).is_err());
@@ -326,6 +352,8 @@ mod tests {
             side: BrokerSide::Buy,
             quantity: 1,
             order_type: BrokerOrderType::Market,
+            reduce_only: false,
+            quantity_scaled: None,
         };
 
         let first = broker.submit_order(&order).unwrap();
@@ -336,4 +364,38 @@ mod tests {
         assert_eq!(second, OrderId(2));
         assert_eq!(third, OrderId(3));
     }
+
+    #[test]
+    fn reduce_only_clamps_and_rejects() {
+        let mut broker = MockBroker::builder()
+            .with_position(aapl(), 150, 150_00)
+            .build();
+        broker.connect().unwrap();
+
+        let mut order = BrokerOrder {
+            symbol: aapl(),
+            side: BrokerSide::Sell,
+            quantity: 200,
+            order_type: BrokerOrderType::Market,
+            reduce_only: true,
+            quantity_scaled: None,
+        };
+        broker.submit_order(&order).unwrap();
+        assert_eq!(broker.submitted_orders()[0].quantity, 150);
+
+        // Buying would extend the long.
+        order.side = BrokerSide::Buy;
+        assert!(matches!(
+            broker.submit_order(&order),
+            Err(BrokerError::Order(_))
+        ));
+
+        // Nothing to reduce in a flat symbol.
+        order.symbol = Symbol::new("MSFT");
+        assert!(matches!(
+            broker.submit_order(&order),
+            Err(BrokerError::Order(_))
+        ));
+        assert_eq!(broker.submitted_orders().len(), 1);
+    }
 }
@@ -0,0 +1,476 @@
+//! Paper-trading broker backed by the nanobook matching engine.
+//!
+//! `PaperBroker` implements the `Broker` trait against an in-process
+//! [`MultiExchange`], so strategies and the rebalancer can be dry-run
+//! end to end without a live connection. Orders match against seeded
+//! liquidity, and fills update a simulated cash balance and positions.
+//!
+//! ```
+//! use nanobook::{Price, Symbol};
+//! use nanobook_broker::paper::PaperBroker;
+//! use nanobook_broker::{Broker, BrokerOrder, BrokerOrderType, BrokerSide};
+//!
+//! let aapl = Symbol::new("AAPL");
+//! let mut broker = PaperBroker::new(
+//!     1_000_000_00,
+//!     [(aapl, vec![(BrokerSide::Sell, Price(150_00), 500)])],
+//! );
+//! broker.connect().unwrap();
+//!
+//! broker
+//!     .submit_order(&BrokerOrder {
+//!         symbol: aapl,
+//!         side: BrokerSide::Buy,
+//!         quantity: 100,
+//!         order_type: BrokerOrderType::Market,
+//!         reduce_only: false,
+//!         quantity_scaled: None,
+//!     })
+//!     .unwrap();
+//!
+//! assert_eq!(broker.positions().unwrap()[0].quantity, 100);
+//! assert_eq!(broker.account().unwrap().cash_cents, 1_000_000_00 - 100 * 150_00);
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+use nanobook::{MultiExchange, OrderStatus, Price, Side, Symbol, TimeInForce};
+
+use crate::Broker;
+use crate::error::BrokerError;
+use crate::types::*;
+
+/// One resting order used to seed a paper book: side, limit price, quantity.
+pub type SeedOrder = (BrokerSide, Price, u64);
+
+/// A broker that routes orders into a local matching engine.
+pub struct PaperBroker {
+    connected: bool,
+    state: Mutex<PaperState>,
+}
+
+struct PaperState {
+    exchanges: MultiExchange,
+    cash_cents: i64,
+    positions: HashMap<Symbol, PaperPosition>,
+    orders: HashMap<OrderId, PaperOrder>,
+    /// Exchange order id → broker order id, per symbol, for passive fills.
+    by_exchange_id: HashMap<(Symbol, nanobook::OrderId), OrderId>,
+    next_order_id: u64,
+}
+
+#[derive(Default)]
+struct PaperPosition {
+    /// Positive = long, negative = short.
+    quantity: i64,
+    /// Signed cost basis of the open quantity.
+    cost_cents: i64,
+}
+
+struct PaperOrder {
+    symbol: Symbol,
+    exchange_id: nanobook::OrderId,
+    quantity: u64,
+    filled: u64,
+    notional_cents: i64,
+    state: OrderState,
+}
+
+impl PaperBroker {
+    /// Create a paper broker with `cash_cents` of starting cash.
+    ///
+    /// `liquidity` seeds each symbol's book with resting GTC limit orders.
+    /// Seed orders belong to the simulated market, not to this account.
+    pub fn new(
+        cash_cents: i64,
+        liquidity: impl IntoIterator<Item = (Symbol, Vec<SeedOrder>)>,
+    ) -> Self {
+        let mut exchanges = MultiExchange::new();
+        for (symbol, orders) in liquidity {
+            let exchange = exchanges.get_or_create(&symbol);
+            for (side, price, quantity) in orders {
+                exchange.submit_limit(to_side(side), price, quantity, TimeInForce::GTC);
+            }
+        }
+
+        Self {
+            connected: false,
+            state: Mutex::new(PaperState {
+                exchanges,
+                cash_cents,
+                positions: HashMap::new(),
+                orders: HashMap::new(),
+                by_exchange_id: HashMap::new(),
+                next_order_id: 1,
+            }),
+        }
+    }
+
+    /// Read-only access to the underlying books, e.g. to inspect depth.
+    pub fn with_exchanges<R>(&self, f: impl FnOnce(&MultiExchange) -> R) -> R {
+        f(&self.lock().exchanges)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PaperState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn require_connected(&self) -> Result<MutexGuard<'_, PaperState>, BrokerError> {
+        if !self.connected {
+            return Err(BrokerError::NotConnected);
+        }
+        Ok(self.lock())
+    }
+}
+
+impl PaperState {
+    /// Reference price for marking a position: last trade, then mid, then cost.
+    fn mark_cents(&self, symbol: &Symbol, position: &PaperPosition) -> i64 {
+        let exchange = self.exchanges.get(symbol);
+        if let Some(last) = exchange.and_then(|ex| ex.last_trade_price()) {
+            return last.0;
+        }
+        if let Some((Some(bid), Some(ask))) = exchange.map(|ex| ex.best_bid_ask()) {
+            return (bid.0 + ask.0) / 2;
+        }
+        if position.quantity == 0 {
+            0
+        } else {
+            position.cost_cents / position.quantity
+        }
+    }
+
+    fn apply_fill(&mut self, id: OrderId, side: Side, price: Price, quantity: u64) {
+        let Some(order) = self.orders.get_mut(&id) else {
+            return;
+        };
+        let notional = price.0 * quantity as i64;
+        order.filled += quantity;
+        order.notional_cents += notional;
+        order.state = if order.filled >= order.quantity {
+            OrderState::Filled
+        } else {
+            OrderState::PartiallyFilled
+        };
+
+        let delta = match side {
+            Side::Buy => quantity as i64,
+            Side::Sell => -(quantity as i64),
+        };
+        self.cash_cents -= delta * price.0;
+
+        let position = self.positions.entry(order.symbol).or_default();
+        if position.quantity == 0 || position.quantity.signum() == delta.signum() {
+            position.quantity += delta;
+            position.cost_cents += delta * price.0;
+        } else {
+            // Reduce at average cost; any excess opens the opposite side.
+            let closing = delta.abs().min(position.quantity.abs());
+            position.cost_cents -= position.cost_cents * closing / position.quantity.abs();
+            position.quantity += delta.signum() * closing;
+            let opening = delta.abs() - closing;
+            if opening > 0 {
+                position.quantity = delta.signum() * opening;
+                position.cost_cents = position.quantity * price.0;
+            }
+        }
+    }
+}
+
+impl Broker for PaperBroker {
+    fn connect(&mut self) -> Result<(), BrokerError> {
+        self.connected = true;
+        Ok(())
+    }
+
+    fn disconnect(&mut self) -> Result<(), BrokerError> {
+        self.connected = false;
+        Ok(())
+    }
+
+    fn positions(&self) -> Result<Vec<Position>, BrokerError> {
+        let state = self.require_connected()?;
+        let mut positions: Vec<Position> = state
+            .positions
+            .iter()
+            .filter(|(_, p)| p.quantity != 0)
+            .map(|(symbol, p)| {
+                let market_value = p.quantity * state.mark_cents(symbol, p);
+                Position {
+                    symbol: *symbol,
+                    quantity: p.quantity,
+                    avg_cost_cents: p.cost_cents / p.quantity,
+                    market_value_cents: market_value,
+                    unrealized_pnl_cents: market_value - p.cost_cents,
+                }
+            })
+            .collect();
+        positions.sort_by(|a, b| a.symbol.as_str().cmp(b.symbol.as_str()));
+        Ok(positions)
+    }
+
+    fn account(&self) -> Result<Account, BrokerError> {
+        let state = self.require_connected()?;
+        let (net, gross) = state
+            .positions
+            .iter()
+            .map(|(symbol, p)| p.quantity * state.mark_cents(symbol, p))
+            .fold((0, 0), |(net, gross), value| {
+                (net + value, gross + value.abs())
+            });
+        Ok(Account {
+            equity_cents: state.cash_cents + net,
+            buying_power_cents: state.cash_cents,
+            cash_cents: state.cash_cents,
+            gross_position_value_cents: gross,
+        })
+    }
+
+    fn submit_order(&self, order: &BrokerOrder) -> Result<OrderId, BrokerError> {
+        let order = &*crate::resolve_order(self, order)?;
+        let mut state = self.require_connected()?;
+        let side = to_side(order.side);
+        let exchange = state.exchanges.get_or_create(&order.symbol);
+        let result = match order.order_type {
+            BrokerOrderType::Market => exchange.submit_market(side, order.quantity),
+            BrokerOrderType::Limit(price) => {
+                exchange.submit_limit(side, price, order.quantity, TimeInForce::GTC)
+            }
+        };
+
+        let id = OrderId(state.next_order_id);
+        state.next_order_id += 1;
+        state.orders.insert(
+            id,
+            PaperOrder {
+                symbol: order.symbol,
+                exchange_id: result.order_id,
+                quantity: order.quantity,
+                filled: 0,
+                notional_cents: 0,
+                state: OrderState::Submitted,
+            },
+        );
+        state
+            .by_exchange_id
+            .insert((order.symbol, result.order_id), id);
+
+        for trade in &result.trades {
+            state.apply_fill(id, side, trade.price, trade.quantity);
+            // Our own resting order may be the passive side.
+            let passive = state
+                .by_exchange_id
+                .get(&(order.symbol, trade.passive_order_id))
+                .copied();
+            if let Some(passive) = passive {
+                state.apply_fill(passive, side.opposite(), trade.price, trade.quantity);
+            }
+        }
+
+        if result.status == OrderStatus::Cancelled
+            && let Some(paper) = state.orders.get_mut(&id)
+        {
+            paper.state = OrderState::Cancelled;
+        }
+        Ok(id)
+    }
+
+    fn order_status(&self, id: OrderId) -> Result<BrokerOrderStatus, BrokerError> {
+        let state = self.require_connected()?;
+        let order = state
+            .orders
+            .get(&id)
+            .ok_or_else(|| BrokerError::Order(format!("unknown paper order {}", id.0)))?;
+        let remaining = match order.state {
+            OrderState::Cancelled | OrderState::Rejected => 0,
+            _ => order.quantity - order.filled,
+        };
+        Ok(BrokerOrderStatus {
+            id,
+            status: order.state,
+            filled_quantity: order.filled,
+            remaining_quantity: remaining,
+            avg_fill_price_cents: if order.filled == 0 {
+                0
+            } else {
+                order.notional_cents / order.filled as i64
+            },
+            commission_cents: 0,
+        })
+    }
+
+    fn cancel_order(&self, id: OrderId) -> Result<(), BrokerError> {
+        let mut state = self.require_connected()?;
+        let (symbol, exchange_id) = state
+            .orders
+            .get(&id)
+            .map(|o| (o.symbol, o.exchange_id))
+            .ok_or_else(|| BrokerError::Order(format!("unknown paper order {}", id.0)))?;
+        let result = state.exchanges.get_or_create(&symbol).cancel(exchange_id);
+        if !result.success {
+            return Err(BrokerError::Order(format!(
+                "cannot cancel paper order {}: {:?}",
+                id.0, result.error
+            )));
+        }
+        if let Some(order) = state.orders.get_mut(&id) {
+            order.state = OrderState::Cancelled;
+        }
+        Ok(())
+    }
+
+    fn quote(&self, symbol: &Symbol) -> Result<Quote, BrokerError> {
+        let state = self.require_connected()?;
+        let exchange = state
+            .exchanges
+            .get(symbol)
+            .ok_or_else(|| BrokerError::InvalidSymbol(symbol.as_str().to_string()))?;
+        let (bid, ask) = exchange.best_bid_ask();
+        let bid_cents = bid.map_or(0, |p| p.0);
+        let ask_cents = ask.map_or(0, |p| p.0);
+        Ok(Quote {
+            symbol: *symbol,
+            bid_cents,
+            ask_cents,
+            last_cents: exchange
+                .last_trade_price()
+                .map_or((bid_cents + ask_cents) / 2, |p| p.0),
+            volume: exchange.trades().iter().map(|t| t.quantity).sum(),
+        })
+    }
+}
+
+fn to_side(side: BrokerSide) -> Side {
+    match side {
+        BrokerSide::Buy => Side::Buy,
+        BrokerSide::Sell => Side::Sell,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::inconsistent_digit_grouping)]
+
+    use super::*;
+
+    fn aapl() -> Symbol {
+        Symbol::new("AAPL")
+    }
+
+    fn order(side: BrokerSide, quantity: u64, order_type: BrokerOrderType) -> BrokerOrder {
+        BrokerOrder {
+            symbol: aapl(),
+            side,
+            quantity,
+            order_type,
+            reduce_only: false,
+            quantity_scaled: None,
+        }
+    }
+
+    fn seeded() -> PaperBroker {
+        let mut broker = PaperBroker::new(
+            100_000_00,
+            [(
+                aapl(),
+                vec![
+                    (BrokerSide::Buy, Price(149_00), 200),
+                    (BrokerSide::Sell, Price(150_00), 100),
+                    (BrokerSide::Sell, Price(151_00), 100),
+                ],
+            )],
+        );
+        broker.connect().unwrap();
+        broker
+    }
+
+    #[test]
+    fn marketable_buy_updates_position_and_buying_power() {
+        let broker = seeded();
+        let id = broker
+            .submit_order(&order(BrokerSide::Buy, 150, BrokerOrderType::Market))
+            .unwrap();
+
+        // 100 @ 150.00 + 50 @ 151.00
+        let cost = 100 * 150_00 + 50 * 151_00;
+        let status = broker.order_status(id).unwrap();
+        assert_eq!(status.status, OrderState::Filled);
+        assert_eq!(status.filled_quantity, 150);
+        assert_eq!(status.avg_fill_price_cents, cost / 150);
+
+        let positions = broker.positions().unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].quantity, 150);
+        assert_eq!(positions[0].avg_cost_cents, cost / 150);
+
+        let account = broker.account().unwrap();
+        assert_eq!(account.buying_power_cents, 100_000_00 - cost);
+        assert_eq!(account.cash_cents, 100_000_00 - cost);
+        // Marked at the last trade (151.00).
+        assert_eq!(account.gross_position_value_cents, 150 * 151_00);
+        assert_eq!(account.equity_cents, account.cash_cents + 150 * 151_00);
+    }
+
+    #[test]
+    fn selling_back_reduces_position_and_restores_cash() {
+        let broker = seeded();
+        broker
+            .submit_order(&order(BrokerSide::Buy, 100, BrokerOrderType::Market))
+            .unwrap();
+        broker
+            .submit_order(&order(BrokerSide::Sell, 60, BrokerOrderType::Market))
+            .unwrap();
+
+        let positions = broker.positions().unwrap();
+        assert_eq!(positions[0].quantity, 40);
+        assert_eq!(positions[0].avg_cost_cents, 150_00);
+        // Bought 100 @ 150.00, sold 60 @ 149.00.
+        let cash = broker.account().unwrap().cash_cents;
+        assert_eq!(cash, 100_000_00 - 100 * 150_00 + 60 * 149_00);
+    }
+
+    #[test]
+    fn resting_limit_can_be_cancelled() {
+        let broker = seeded();
+        let id = broker
+            .submit_order(&order(
+                BrokerSide::Buy,
+                50,
+                BrokerOrderType::Limit(Price(148_00)),
+            ))
+            .unwrap();
+        assert_eq!(
+            broker.order_status(id).unwrap().status,
+            OrderState::Submitted
+        );
+        assert_eq!(broker.quote(&aapl()).unwrap().bid_cents, 149_00);
+
+        broker.cancel_order(id).unwrap();
+        let status = broker.order_status(id).unwrap();
+        assert_eq!(status.status, OrderState::Cancelled);
+        assert_eq!(status.remaining_quantity, 0);
+        assert!(broker.cancel_order(id).is_err());
+        assert!(broker.positions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn quote_reads_bbo_and_unknown_symbol_errors() {
+        let broker = seeded();
+        let quote = broker.quote(&aapl()).unwrap();
+        assert_eq!(quote.bid_cents, 149_00);
+        assert_eq!(quote.ask_cents, 150_00);
+        assert_eq!(quote.last_cents, 149_50);
+        assert!(matches!(
+            broker.quote(&Symbol::new("MSFT")),
+            Err(BrokerError::InvalidSymbol(_))
+        ));
+    }
+
+    #[test]
+    fn requires_connection() {
+        let broker = PaperBroker::new(0, []);
+        assert!(matches!(broker.positions(), Err(BrokerError::NotConnected)));
+        assert!(broker.account().is_err());
+    }
+}
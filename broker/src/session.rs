@@ -0,0 +1,173 @@
+//! Equity-curve tracking over any [`Broker`].
+//!
+//! [`BrokerSession`] wraps a broker and samples `account().equity_cents` on
+//! demand or at a fixed interval, keeping the equity curve and its running
+//! high-water mark for live drawdown monitoring. Drawdowns use the same math
+//! as [`nanobook::portfolio::metrics`].
+//!
+//! ```
+//! use nanobook_broker::mock::MockBroker;
+//! use nanobook_broker::{Broker, BrokerSession};
+//!
+//! let mut broker = MockBroker::builder()
+//!     .with_equity_sequence([100_000_00, 120_000_00, 90_000_00])
+//!     .build();
+//! broker.connect().unwrap();
+//!
+//! let mut session = BrokerSession::new(broker);
+//! for _ in 0..3 {
+//!     session.sample().unwrap();
+//! }
+//! assert_eq!(session.high_water_cents(), Some(120_000_00));
+//! assert!((session.current_drawdown() - 0.25).abs() < 1e-12);
+//! ```
+
+use std::time::{Duration, Instant};
+
+use nanobook::portfolio::drawdown;
+
+use crate::Broker;
+use crate::error::BrokerError;
+
+/// A broker plus the equity curve sampled from it.
+pub struct BrokerSession<B: Broker> {
+    broker: B,
+    interval: Duration,
+    last_sample: Option<Instant>,
+    curve: Vec<i64>,
+    high_water_cents: Option<i64>,
+}
+
+impl<B: Broker> BrokerSession<B> {
+    /// Wrap `broker`; [`poll`](Self::poll) samples on every call.
+    pub fn new(broker: B) -> Self {
+        Self::with_interval(broker, Duration::ZERO)
+    }
+
+    /// Wrap `broker`; [`poll`](Self::poll) samples at most once per `interval`.
+    pub fn with_interval(broker: B, interval: Duration) -> Self {
+        Self {
+            broker,
+            interval,
+            last_sample: None,
+            curve: Vec::new(),
+            high_water_cents: None,
+        }
+    }
+
+    /// Fetch the account and append its equity to the curve.
+    ///
+    /// Returns the sampled equity in cents. On error nothing is recorded.
+    pub fn sample(&mut self) -> Result<i64, BrokerError> {
+        let equity = self.broker.account()?.equity_cents;
+        self.last_sample = Some(Instant::now());
+        self.curve.push(equity);
+        self.high_water_cents = Some(self.high_water_cents.map_or(equity, |hw| hw.max(equity)));
+        Ok(equity)
+    }
+
+    /// Sample if the interval has elapsed since the last sample.
+    ///
+    /// Call this from the monitoring loop; returns `Ok(None)` when it is not
+    /// time to sample yet.
+    pub fn poll(&mut self) -> Result<Option<i64>, BrokerError> {
+        let due = self
+            .last_sample
+            .is_none_or(|last| last.elapsed() >= self.interval);
+        if due {
+            self.sample().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Sampled equities in cents, oldest first.
+    pub fn equity_curve(&self) -> &[i64] {
+        &self.curve
+    }
+
+    /// Highest equity sampled so far (`None` before the first sample).
+    pub fn high_water_cents(&self) -> Option<i64> {
+        self.high_water_cents
+    }
+
+    /// Latest equity below the high-water mark, as a positive fraction
+    /// (0.0 at a new high or before the first sample).
+    pub fn current_drawdown(&self) -> f64 {
+        match (self.high_water_cents, self.curve.last()) {
+            (Some(peak), Some(&equity)) => drawdown(peak as f64, equity as f64),
+            _ => 0.0,
+        }
+    }
+
+    /// The wrapped broker.
+    pub fn broker(&self) -> &B {
+        &self.broker
+    }
+
+    /// The wrapped broker, mutably (e.g. to reconnect).
+    pub fn broker_mut(&mut self) -> &mut B {
+        &mut self.broker
+    }
+
+    /// Unwrap the session, returning the broker.
+    pub fn into_inner(self) -> B {
+        self.broker
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::inconsistent_digit_grouping)]
+
+    use super::*;
+    use crate::mock::MockBroker;
+
+    fn session(equities: impl IntoIterator<Item = i64>) -> BrokerSession<MockBroker> {
+        let mut broker = MockBroker::builder().with_equity_sequence(equities).build();
+        broker.connect().unwrap();
+        BrokerSession::new(broker)
+    }
+
+    #[test]
+    fn declining_equity_reports_peak_to_current() {
+        let mut session = session([100_000_00, 95_000_00, 80_000_00, 60_000_00]);
+        assert_eq!(session.current_drawdown(), 0.0);
+
+        session.sample().unwrap();
+        assert_eq!(session.current_drawdown(), 0.0);
+        for _ in 0..3 {
+            session.sample().unwrap();
+        }
+        assert_eq!(
+            session.equity_curve(),
+            &[100_000_00, 95_000_00, 80_000_00, 60_000_00]
+        );
+        assert_eq!(session.high_water_cents(), Some(100_000_00));
+        assert!((session.current_drawdown() - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn new_high_resets_drawdown() {
+        let mut session = session([100_00, 80_00, 120_00, 90_00]);
+        for _ in 0..3 {
+            session.sample().unwrap();
+        }
+        assert_eq!(session.current_drawdown(), 0.0);
+        session.sample().unwrap();
+        assert!((session.current_drawdown() - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn poll_respects_interval_and_errors_record_nothing() {
+        let broker = MockBroker::builder().build();
+        let mut session = BrokerSession::with_interval(broker, Duration::from_secs(3600));
+        assert!(matches!(session.poll(), Err(BrokerError::NotConnected)));
+        assert!(session.equity_curve().is_empty());
+
+        session.broker_mut().connect().unwrap();
+        assert!(session.poll().unwrap().is_some());
+        assert_eq!(session.poll().unwrap(), None);
+        assert_eq!(session.equity_curve().len(), 1);
+    }
+}
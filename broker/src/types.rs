@@ -1,7 +1,12 @@
 //! Shared broker types: positions, accounts, orders, quotes.
 
+use std::borrow::Cow;
+use std::fmt;
+
 use nanobook::{Price, Symbol};
 
+use crate::error::BrokerError;
+
 /// Broker-level position (the real-world counterpart, not the LOB position).
 #[derive(Debug, Clone)]
 pub struct Position {
@@ -29,6 +34,201 @@ pub struct BrokerOrder {
     pub side: BrokerSide,
     pub quantity: u64,
     pub order_type: BrokerOrderType,
+    /// Only shrink an existing position: the quantity is clamped to the
+    /// position size, and an order that would open or extend a position is
+    /// rejected.
+    pub reduce_only: bool,
+    /// Fractional size (e.g. 0.0012 BTC). Brokers that trade fractional
+    /// quantities (Binance) send this instead of `quantity`; integer-only
+    /// brokers (IBKR, paper, mock) ignore it.
+    pub quantity_scaled: Option<DecimalQty>,
+}
+
+impl BrokerOrder {
+    /// The order to actually send given the current `positions`.
+    ///
+    /// Orders without `reduce_only` pass through unchanged. A reduce-only
+    /// order is clamped to the size of the position in its symbol, or
+    /// rejected with [`BrokerError::Order`] if there is no position on the
+    /// opposite side to reduce.
+    pub fn resolve_reduce_only(
+        &self,
+        positions: &[Position],
+    ) -> Result<Cow<'_, Self>, BrokerError> {
+        self.resolve_reduce_only_scaled(positions, 0)
+    }
+
+    /// [`resolve_reduce_only`](Self::resolve_reduce_only) for positions
+    /// reported in units of `10^-position_decimals` (e.g. 8 for Binance).
+    ///
+    /// The order size is `quantity_scaled` if set, else `quantity` whole
+    /// units. A clamped order with `position_decimals > 0` carries the
+    /// position size in `quantity_scaled`.
+    pub fn resolve_reduce_only_scaled(
+        &self,
+        positions: &[Position],
+        position_decimals: u32,
+    ) -> Result<Cow<'_, Self>, BrokerError> {
+        if !self.reduce_only {
+            return Ok(Cow::Borrowed(self));
+        }
+
+        let held = positions
+            .iter()
+            .filter(|p| p.symbol == self.symbol)
+            .map(|p| p.quantity)
+            .sum::<i64>();
+        let reducible = match self.side {
+            BrokerSide::Sell => held.max(0).unsigned_abs(),
+            BrokerSide::Buy => held.min(0).unsigned_abs(),
+        };
+        if reducible == 0 {
+            return Err(BrokerError::Order(format!(
+                "reduce-only {:?} of {} would increase exposure (position {held})",
+                self.side,
+                self.symbol.as_str(),
+            )));
+        }
+
+        let size = self
+            .quantity_scaled
+            .unwrap_or(DecimalQty::whole(self.quantity))
+            .rescale(position_decimals)
+            .mantissa;
+        if size <= reducible {
+            return Ok(Cow::Borrowed(self));
+        }
+        let quantity_scaled = (position_decimals > 0)
+            .then(|| DecimalQty::new(reducible, position_decimals))
+            .or(self.quantity_scaled.map(|_| DecimalQty::whole(reducible)));
+        Ok(Cow::Owned(Self {
+            quantity: reducible / 10u64.pow(position_decimals),
+            quantity_scaled,
+            ..self.clone()
+        }))
+    }
+}
+
+/// Fixed-point quantity: `mantissa × 10^-decimals`.
+///
+/// ```
+/// use nanobook_broker::DecimalQty;
+///
+/// let qty = DecimalQty::new(123_456, 8);
+/// assert_eq!(qty.to_string(), "0.00123456");
+/// // Truncates to the lot step, never rounding up.
+/// assert_eq!(qty.floor_to_step(DecimalQty::new(1, 4)).to_string(), "0.0012");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecimalQty {
+    pub mantissa: u64,
+    pub decimals: u32,
+}
+
+impl DecimalQty {
+    /// `mantissa × 10^-decimals`.
+    pub const fn new(mantissa: u64, decimals: u32) -> Self {
+        Self { mantissa, decimals }
+    }
+
+    /// A whole number of units.
+    pub const fn whole(units: u64) -> Self {
+        Self::new(units, 0)
+    }
+
+    /// Parse a plain decimal string such as `"0.00100000"`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+        if int.is_empty() && frac.is_empty() {
+            return None;
+        }
+        let digits = format!("{int}{frac}");
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        Some(Self::new(digits.parse().ok()?, frac.len() as u32))
+    }
+
+    /// The same quantity with `decimals` digits, truncating extra digits.
+    /// Saturates at `u64::MAX` if the mantissa overflows.
+    pub fn rescale(self, decimals: u32) -> Self {
+        let mantissa = if decimals >= self.decimals {
+            10u128
+                .checked_pow(decimals - self.decimals)
+                .and_then(|scale| scale.checked_mul(self.mantissa as u128))
+                .map_or(u64::MAX, |m| u64::try_from(m).unwrap_or(u64::MAX))
+        } else {
+            10u128
+                .checked_pow(self.decimals - decimals)
+                .map_or(0, |scale| (self.mantissa as u128 / scale) as u64)
+        };
+        Self::new(mantissa, decimals)
+    }
+
+    /// Largest multiple of `step` not above `self`, with `step`'s precision.
+    ///
+    /// Truncation (rather than rounding) keeps an order from exceeding the
+    /// balance it was sized from. A zero step only rescales.
+    pub fn floor_to_step(self, step: DecimalQty) -> Self {
+        let value = self.rescale(step.decimals).mantissa;
+        if step.mantissa == 0 {
+            return Self::new(value, step.decimals);
+        }
+        Self::new(value - value % step.mantissa, step.decimals)
+    }
+
+    /// Whether the quantity is zero.
+    pub fn is_zero(self) -> bool {
+        self.mantissa == 0
+    }
+
+    /// Approximate value as `f64`.
+    pub fn to_f64(self) -> f64 {
+        self.mantissa as f64 / 10f64.powi(self.decimals as i32)
+    }
+}
+
+impl PartialOrd for DecimalQty {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DecimalQty {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Scale both to the finer precision. Only the coarser value is
+        // scaled up, and if that overflows it exceeds any u64 mantissa.
+        let decimals = self.decimals.max(other.decimals);
+        let scale = |q: &Self| match q.mantissa {
+            0 => Some(0),
+            m => 10u128
+                .checked_pow(decimals - q.decimals)
+                .and_then(|scale| scale.checked_mul(m as u128)),
+        };
+        match (scale(self), scale(other)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (None, _) => std::cmp::Ordering::Greater,
+            (_, None) => std::cmp::Ordering::Less,
+        }
+    }
+}
+
+/// Plain decimal notation without trailing fractional zeros.
+impl fmt::Display for DecimalQty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = format!(
+            "{:0>width$}",
+            self.mantissa,
+            width = self.decimals as usize + 1
+        );
+        let (int, frac) = digits.split_at(digits.len() - self.decimals as usize);
+        let frac = frac.trim_end_matches('0');
+        if frac.is_empty() {
+            write!(f, "{int}")
+        } else {
+            write!(f, "{int}.{frac}")
+        }
+    }
 }
 
 /// Buy or sell.
@@ -67,6 +267,9 @@ pub struct BrokerOrderStatus {
     pub filled_quantity: u64,
     pub remaining_quantity: u64,
     pub avg_fill_price_cents: i64,
+    /// Commission charged on the fills so far, in cents of the account
+    /// currency (0 if the broker does not report it).
+    pub commission_cents: i64,
 }
 
 /// Lifecycle state of an order.
@@ -79,3 +282,26 @@ pub enum OrderState {
     Cancelled,
     Rejected,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimal_qty_orders_across_wide_precision_gaps() {
+        let tiny = DecimalQty::parse("0.0000000000000000000000000000000000000001").unwrap();
+        let one = DecimalQty::whole(1);
+
+        assert!(tiny < one);
+        assert!(one > tiny);
+        assert!(DecimalQty::whole(0) < tiny);
+        assert_eq!(
+            DecimalQty::whole(0).cmp(&DecimalQty::new(0, 40)),
+            std::cmp::Ordering::Equal
+        );
+        assert_eq!(
+            DecimalQty::new(1, 0).cmp(&DecimalQty::new(10_000_000_000_000_000_000, 19)),
+            std::cmp::Ordering::Equal
+        );
+    }
+}
@@ -0,0 +1,138 @@
+// Allow our dollar.cents digit grouping convention (e.g., 100_00 = $100.00)
+#![allow(clippy::inconsistent_digit_grouping)]
+
+//! Tests for the async broker trait and concurrent batch quotes.
+
+#[cfg(feature = "async")]
+mod async_tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    use nanobook::Symbol;
+    use nanobook_broker::types::*;
+    use nanobook_broker::{AsyncBroker, BrokerError};
+
+    /// Mock async client: each quote takes a symbol-dependent latency.
+    struct StaggeredBroker {
+        limit: usize,
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl StaggeredBroker {
+        fn new(limit: usize) -> Self {
+            Self {
+                limit,
+                in_flight: AtomicUsize::new(0),
+                peak: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl AsyncBroker for StaggeredBroker {
+        async fn connect(&mut self) -> Result<(), BrokerError> {
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<(), BrokerError> {
+            Ok(())
+        }
+
+        async fn positions(&self) -> Result<Vec<Position>, BrokerError> {
+            Ok(Vec::new())
+        }
+
+        async fn account(&self) -> Result<Account, BrokerError> {
+            Err(BrokerError::NotConnected)
+        }
+
+        async fn submit_order(&self, _order: &BrokerOrder) -> Result<OrderId, BrokerError> {
+            Err(BrokerError::NotConnected)
+        }
+
+        async fn order_status(&self, _id: OrderId) -> Result<BrokerOrderStatus, BrokerError> {
+            Err(BrokerError::NotConnected)
+        }
+
+        async fn cancel_order(&self, _id: OrderId) -> Result<(), BrokerError> {
+            Ok(())
+        }
+
+        async fn quote(&self, symbol: &Symbol) -> Result<Quote, BrokerError> {
+            let n: u64 = symbol.as_str()[1..].parse().unwrap();
+            if n == 13 {
+                return Err(BrokerError::InvalidSymbol(symbol.as_str().to_string()));
+            }
+
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            // Later symbols answer faster, so completion order != request order.
+            tokio::time::sleep(Duration::from_millis(5 + (20 - n % 20) * 2)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(Quote {
+                symbol: *symbol,
+                bid_cents: 100_00 + n as i64,
+                ask_cents: 100_10 + n as i64,
+                last_cents: 100_05 + n as i64,
+                volume: 0,
+            })
+        }
+
+        fn max_concurrency(&self) -> usize {
+            self.limit
+        }
+    }
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn quotes_batch_returns_all_quotes_in_order() {
+        let symbols: Vec<Symbol> = (0..40).map(|i| Symbol::new(&format!("S{i}"))).collect();
+        let broker = StaggeredBroker::new(8);
+
+        let started = Instant::now();
+        let quotes = runtime().block_on(broker.quotes_batch(&symbols));
+        let elapsed = started.elapsed();
+
+        assert_eq!(quotes.len(), symbols.len());
+        for (i, (symbol, quote)) in symbols.iter().zip(&quotes).enumerate() {
+            if i == 13 {
+                assert!(matches!(quote, Err(BrokerError::InvalidSymbol(_))));
+                continue;
+            }
+            let quote = quote.as_ref().unwrap();
+            assert_eq!(quote.symbol, *symbol);
+            assert_eq!(quote.bid_cents, 100_00 + i as i64);
+        }
+
+        // Concurrency was used but stayed within the limit.
+        let peak = broker.peak.load(Ordering::SeqCst);
+        assert!(peak > 1 && peak <= 8, "peak in flight = {peak}");
+        // Serial would take well over a second (sum of ~40 × 25ms).
+        assert!(elapsed < Duration::from_millis(800), "took {elapsed:?}");
+    }
+
+    #[test]
+    fn quotes_batch_with_limit_one_is_serial() {
+        let symbols: Vec<Symbol> = (0..5).map(|i| Symbol::new(&format!("S{i}"))).collect();
+        let broker = StaggeredBroker::new(1);
+
+        let quotes = runtime().block_on(broker.quotes_batch(&symbols));
+
+        assert!(quotes.iter().all(Result::is_ok));
+        assert_eq!(broker.peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn quotes_batch_empty() {
+        let broker = StaggeredBroker::new(4);
+        let quotes = runtime().block_on(broker.quotes_batch(&[]));
+        assert!(quotes.is_empty());
+    }
+}
@@ -0,0 +1,255 @@
+// Allow our dollar.cents digit grouping convention (e.g., 100_00 = $100.00)
+#![allow(clippy::inconsistent_digit_grouping)]
+
+//! USDT-M futures broker tests: endpoint routing and signed-position parsing
+//! against canned `fapi` responses.
+
+#[cfg(feature = "binance")]
+mod binance_futures_tests {
+    use std::sync::{Arc, Mutex};
+
+    use nanobook::{Price, Symbol};
+    use nanobook_broker::binance::client::BinanceClient;
+    use nanobook_broker::binance::futures::BinanceFuturesBroker;
+    use nanobook_broker::binance::transport::{HttpRequest, HttpResponse, Method, Transport};
+    use nanobook_broker::{Broker, BrokerOrder, BrokerOrderType, BrokerSide, OrderState};
+
+    const POSITION_RISK: &str = r#"[
+        {"symbol":"BTCUSDT","positionAmt":"0.500","entryPrice":"60000.0","markPrice":"61000.0",
+         "unRealizedProfit":"500.00","leverage":"10","updateTime":1000},
+        {"symbol":"ETHUSDT","positionAmt":"-2.000","entryPrice":"3000.0","markPrice":"2900.0",
+         "unRealizedProfit":"200.00","leverage":"5","updateTime":2000},
+        {"symbol":"SOLUSDT","positionAmt":"0.000","entryPrice":"0.0","markPrice":"150.0",
+         "unRealizedProfit":"0.00","leverage":"20","updateTime":0}
+    ]"#;
+
+    const INCOME: &str = r#"[
+        {"symbol":"BTCUSDT","incomeType":"FUNDING_FEE","income":"-1.50","time":1500},
+        {"symbol":"BTCUSDT","incomeType":"FUNDING_FEE","income":"-9.00","time":500},
+        {"symbol":"ETHUSDT","incomeType":"FUNDING_FEE","income":"3.25","time":2500},
+        {"symbol":"ETHUSDT","incomeType":"REALIZED_PNL","income":"100.00","time":2600}
+    ]"#;
+
+    const ACCOUNT: &str = r#"{
+        "totalWalletBalance":"10000.00","totalUnrealizedProfit":"700.00",
+        "totalMarginBalance":"10700.00","availableBalance":"8000.00",
+        "positions":[{"symbol":"BTCUSDT","notional":"30500.00"},
+                     {"symbol":"ETHUSDT","notional":"-5800.00"}]
+    }"#;
+
+    const ORDER: &str =
+        r#"{"symbol":"BTCUSDT","orderId":42,"status":"NEW","executedQty":"0","cumQuote":"0"}"#;
+
+    const ORDER_PARTIAL: &str = r#"{"symbol":"BTCUSDT","orderId":42,"status":"PARTIALLY_FILLED",
+        "executedQty":"2","cumQuote":"122000.00"}"#;
+
+    const USER_TRADES: &str = r#"[
+        {"symbol":"BTCUSDT","orderId":42,"price":"61000.00","qty":"2",
+         "commission":"24.40000000","commissionAsset":"USDT"}
+    ]"#;
+
+    const TICKER: &str = r#"{"symbol":"BTCUSDT","bidPrice":"61000.10","bidQty":"3",
+        "askPrice":"61000.30","askQty":"4"}"#;
+
+    const EXCHANGE_INFO: &str = r#"{"symbols":[
+        {"symbol":"ETHUSDT","filters":[
+            {"filterType":"PRICE_FILTER","minPrice":"0.01","maxPrice":"100000","tickSize":"0.01"},
+            {"filterType":"LOT_SIZE","minQty":"0.001","maxQty":"10000","stepSize":"0.001"}]},
+        {"symbol":"BTCUSDT","filters":[
+            {"filterType":"LOT_SIZE","minQty":"0.001","maxQty":"1000","stepSize":"0.001"}]}
+    ]}"#;
+
+    /// Answers by URL path and records every request.
+    #[derive(Clone, Default)]
+    struct FapiMock {
+        requests: Arc<Mutex<Vec<HttpRequest>>>,
+    }
+
+    impl FapiMock {
+        fn requests(&self) -> Vec<HttpRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    impl Transport for FapiMock {
+        fn send(&self, request: &HttpRequest) -> Result<HttpResponse, String> {
+            self.requests.lock().unwrap().push(request.clone());
+            let path = request
+                .url
+                .strip_prefix("https://testnet.binancefuture.com")
+                .ok_or_else(|| format!("unexpected host: {}", request.url))?;
+            let body = match (request.method, path.split('?').next().unwrap()) {
+                (Method::Get, "/fapi/v2/positionRisk") => POSITION_RISK,
+                (Method::Get, "/fapi/v1/income") => INCOME,
+                (Method::Get, "/fapi/v2/account") => ACCOUNT,
+                (Method::Post, "/fapi/v1/order") => ORDER,
+                (Method::Get, "/fapi/v1/order") => ORDER_PARTIAL,
+                (Method::Get, "/fapi/v1/userTrades") => USER_TRADES,
+                (Method::Delete, "/fapi/v1/order") => ORDER,
+                (Method::Post, "/fapi/v1/leverage") => r#"{"leverage":10}"#,
+                (Method::Get, "/fapi/v1/ticker/bookTicker") => TICKER,
+                (Method::Get, "/fapi/v1/exchangeInfo") => EXCHANGE_INFO,
+                _ => return Err(format!("unrouted {:?} {path}", request.method)),
+            };
+            Ok(HttpResponse {
+                status: 200,
+                retry_after: None,
+                body: body.to_string(),
+            })
+        }
+    }
+
+    fn broker() -> (BinanceFuturesBroker, FapiMock) {
+        let mock = FapiMock::default();
+        let client = BinanceClient::new_futures("key", "secret", true).with_transport(mock.clone());
+        (BinanceFuturesBroker::with_client(client), mock)
+    }
+
+    #[test]
+    fn positions_are_signed_and_include_funding() {
+        let (broker, mock) = broker();
+        let positions = broker.positions().unwrap();
+
+        assert_eq!(positions.len(), 2, "flat SOL position is skipped");
+        let btc = positions
+            .iter()
+            .find(|p| p.symbol == Symbol::new("BTC"))
+            .unwrap();
+        assert_eq!(btc.quantity, 50_000_000);
+        assert_eq!(btc.avg_cost_cents, 60_000_00);
+        assert_eq!(btc.market_value_cents, 30_500_00);
+        // 500.00 unrealized - 1.50 funding (the 9.00 predates the position).
+        assert_eq!(btc.unrealized_pnl_cents, 498_50);
+
+        let eth = positions
+            .iter()
+            .find(|p| p.symbol == Symbol::new("ETH"))
+            .unwrap();
+        assert_eq!(eth.quantity, -200_000_000, "shorts must be negative");
+        assert_eq!(eth.market_value_cents, -5_800_00);
+        assert_eq!(eth.unrealized_pnl_cents, 203_25);
+
+        let requests = mock.requests();
+        assert!(requests[0].url.contains("/fapi/v2/positionRisk?timestamp="));
+        assert!(requests[1].url.contains("incomeType=FUNDING_FEE"));
+        assert!(requests.iter().all(|r| r.url.contains("&signature=")));
+    }
+
+    #[test]
+    fn account_reports_margin_and_gross_notional() {
+        let (broker, _) = broker();
+        let account = broker.account().unwrap();
+        assert_eq!(account.equity_cents, 10_700_00);
+        assert_eq!(account.cash_cents, 10_000_00);
+        assert_eq!(account.buying_power_cents, 8_000_00);
+        assert_eq!(account.gross_position_value_cents, 36_300_00);
+    }
+
+    #[test]
+    fn orders_route_to_fapi_endpoints() {
+        let (broker, mock) = broker();
+        let id = broker
+            .submit_order(&BrokerOrder {
+                symbol: Symbol::new("BTC"),
+                side: BrokerSide::Sell,
+                quantity: 5,
+                order_type: BrokerOrderType::Limit(Price(61_000_00)),
+                reduce_only: false,
+                quantity_scaled: None,
+            })
+            .unwrap();
+        let status = broker.order_status(id).unwrap();
+        broker.cancel_order(id).unwrap();
+
+        assert_eq!(status.status, OrderState::PartiallyFilled);
+        assert_eq!(status.filled_quantity, 2);
+        assert_eq!(status.remaining_quantity, 3);
+        assert_eq!(status.avg_fill_price_cents, 61_000_00);
+        assert_eq!(status.commission_cents, 24_40);
+
+        let requests = mock.requests();
+        assert_eq!(requests[0].method, Method::Post);
+        let body = requests[0].body.as_deref().unwrap();
+        assert!(body.starts_with("symbol=BTCUSDT&side=SELL&type=LIMIT&quantity=5"));
+        assert!(body.contains("&price=61000.00") && body.contains("&signature="));
+        assert!(
+            requests[1]
+                .url
+                .contains("/fapi/v1/order?symbol=BTCUSDT&orderId=42")
+        );
+        assert!(
+            requests[2]
+                .url
+                .contains("/fapi/v1/userTrades?symbol=BTCUSDT&orderId=42")
+        );
+        assert_eq!(requests[3].method, Method::Delete);
+    }
+
+    #[test]
+    fn reduce_only_orders_are_clamped_and_flagged() {
+        let (broker, mock) = broker();
+        let mut order = BrokerOrder {
+            symbol: Symbol::new("ETH"),
+            side: BrokerSide::Buy,
+            quantity: 5,
+            order_type: BrokerOrderType::Market,
+            reduce_only: true,
+            quantity_scaled: None,
+        };
+        broker.submit_order(&order).unwrap();
+
+        // Short 2 ETH: the buy is clamped to 2 and flagged for the venue.
+        let post = mock
+            .requests()
+            .into_iter()
+            .find(|r| r.method == Method::Post)
+            .unwrap();
+        let body = post.body.unwrap();
+        assert!(body.contains("symbol=ETHUSDT&side=BUY&type=MARKET&quantity=2&"));
+        assert!(body.contains("&reduceOnly=true&timestamp="));
+
+        order.side = BrokerSide::Sell;
+        assert!(
+            broker.submit_order(&order).is_err(),
+            "would extend the short"
+        );
+
+        // Long 0.5 BTC: the sell is clamped to the fractional position.
+        order.symbol = Symbol::new("BTC");
+        broker.submit_order(&order).unwrap();
+        let post = mock
+            .requests()
+            .into_iter()
+            .rfind(|r| r.method == Method::Post)
+            .unwrap();
+        assert!(
+            post.body
+                .unwrap()
+                .contains("side=SELL&type=MARKET&quantity=0.5&")
+        );
+    }
+
+    #[test]
+    fn leverage_and_quote_routing() {
+        let (broker, mock) = broker();
+        broker.set_leverage(&Symbol::new("BTC"), 10).unwrap();
+        assert!(broker.set_leverage(&Symbol::new("BTC"), 0).is_err());
+        let quote = broker.quote(&Symbol::new("BTC")).unwrap();
+
+        assert_eq!(quote.bid_cents, 61_000_10);
+        assert_eq!(quote.ask_cents, 61_000_30);
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2, "invalid leverage never hits the wire");
+        assert!(
+            requests[0]
+                .url
+                .contains("/fapi/v1/leverage?symbol=BTCUSDT&leverage=10&timestamp=")
+        );
+        assert!(
+            requests[1]
+                .url
+                .ends_with("/fapi/v1/ticker/bookTicker?symbol=BTCUSDT")
+        );
+    }
+}
@@ -0,0 +1,252 @@
+// Allow our dollar.cents digit grouping convention (e.g., 100_00 = $100.00)
+#![allow(clippy::inconsistent_digit_grouping)]
+
+//! Order routing tests for the Binance broker against an in-memory client.
+
+#[cfg(feature = "binance")]
+mod binance_order_tests {
+    use std::sync::{Arc, Mutex};
+
+    use nanobook::Symbol;
+    use nanobook_broker::binance::BinanceBroker;
+    use nanobook_broker::binance::client::BinanceApi;
+    use nanobook_broker::binance::types::{
+        AccountInfo, AccountTrade, BookTicker, LotSize, OrderResponse,
+    };
+    use nanobook_broker::{
+        Broker, BrokerError, BrokerOrder, BrokerOrderType, BrokerSide, DecimalQty, OrderId,
+        OrderState,
+    };
+
+    /// (method, Binance symbol, order id)
+    type Call = (&'static str, String, u64);
+
+    /// Records calls and answers with canned data.
+    #[derive(Clone, Default)]
+    struct MockClient {
+        calls: Arc<Mutex<Vec<Call>>>,
+        /// Quantity strings sent with each submitted order.
+        quantities: Arc<Mutex<Vec<String>>>,
+        /// `LOT_SIZE` filter reported for every pair.
+        lot: Option<LotSize>,
+        lot_lookups: Arc<Mutex<u32>>,
+    }
+
+    impl MockClient {
+        fn calls(&self) -> Vec<Call> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl BinanceApi for MockClient {
+        fn ping(&self) -> Result<(), BrokerError> {
+            Ok(())
+        }
+
+        fn account_info(&self) -> Result<AccountInfo, BrokerError> {
+            Ok(AccountInfo {
+                balances: Vec::new(),
+                can_trade: true,
+            })
+        }
+
+        fn submit_order(
+            &self,
+            symbol: &str,
+            _side: &str,
+            _order_type: &str,
+            quantity: &str,
+            _price: Option<&str>,
+            _time_in_force: Option<&str>,
+        ) -> Result<OrderResponse, BrokerError> {
+            self.quantities.lock().unwrap().push(quantity.to_string());
+            let order_id = 1000 + self.calls.lock().unwrap().len() as u64;
+            self.calls
+                .lock()
+                .unwrap()
+                .push(("submit", symbol.to_string(), order_id));
+            Ok(OrderResponse {
+                symbol: symbol.to_string(),
+                order_id,
+                status: "NEW".into(),
+                executed_qty: "0".into(),
+                cummulative_quote_qty: "0".into(),
+            })
+        }
+
+        fn order_status(&self, symbol: &str, order_id: u64) -> Result<OrderResponse, BrokerError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(("status", symbol.to_string(), order_id));
+            Ok(OrderResponse {
+                symbol: symbol.to_string(),
+                order_id,
+                status: "PARTIALLY_FILLED".into(),
+                executed_qty: "4.00000000".into(),
+                cummulative_quote_qty: "402.00000000".into(),
+            })
+        }
+
+        fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<(), BrokerError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(("cancel", symbol.to_string(), order_id));
+            Ok(())
+        }
+
+        fn book_ticker(&self, symbol: &str) -> Result<BookTicker, BrokerError> {
+            Ok(BookTicker {
+                symbol: symbol.to_string(),
+                bid_price: "100.00".into(),
+                bid_qty: "1".into(),
+                ask_price: "100.10".into(),
+                ask_qty: "1".into(),
+            })
+        }
+
+        fn order_trades(
+            &self,
+            symbol: &str,
+            order_id: u64,
+        ) -> Result<Vec<AccountTrade>, BrokerError> {
+            let trade = |price: &str, commission: &str, asset: &str| AccountTrade {
+                symbol: symbol.to_string(),
+                order_id,
+                price: price.into(),
+                qty: "2.00000000".into(),
+                commission: commission.into(),
+                commission_asset: asset.into(),
+            };
+            Ok(vec![
+                trade("100.00000000", "0.20000000", "USDC"),
+                trade("101.00000000", "0.00200000", "SOL"),
+                trade("101.00000000", "0.00010000", "BNB"),
+            ])
+        }
+
+        fn lot_size(&self, _symbol: &str) -> Result<Option<LotSize>, BrokerError> {
+            *self.lot_lookups.lock().unwrap() += 1;
+            Ok(self.lot)
+        }
+    }
+
+    fn order(symbol: &str, quantity: u64) -> BrokerOrder {
+        BrokerOrder {
+            symbol: Symbol::new(symbol),
+            side: BrokerSide::Buy,
+            quantity,
+            order_type: BrokerOrderType::Market,
+            reduce_only: false,
+            quantity_scaled: None,
+        }
+    }
+
+    #[test]
+    fn cancel_routes_cached_symbol() {
+        let client = MockClient::default();
+        let broker = BinanceBroker::with_client(client.clone());
+
+        let btc = broker.submit_order(&order("BTC", 1)).unwrap();
+        let eth = broker.submit_order(&order("ETH", 2)).unwrap();
+
+        broker.cancel_order(eth).unwrap();
+        broker.cancel_order(btc).unwrap();
+
+        let cancels: Vec<_> = client
+            .calls()
+            .into_iter()
+            .filter(|(m, _, _)| *m == "cancel")
+            .collect();
+        assert_eq!(
+            cancels,
+            vec![
+                ("cancel", "ETHUSDT".to_string(), eth.0),
+                ("cancel", "BTCUSDT".to_string(), btc.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn order_status_queries_real_endpoint() {
+        let client = MockClient::default();
+        let broker = BinanceBroker::with_client(client.clone()).with_quote_asset("USDC");
+
+        let id = broker.submit_order(&order("SOL", 10)).unwrap();
+        let status = broker.order_status(id).unwrap();
+
+        assert_eq!(status.status, OrderState::PartiallyFilled);
+        assert_eq!(status.filled_quantity, 4);
+        assert_eq!(status.remaining_quantity, 6);
+        assert_eq!(status.avg_fill_price_cents, 100_50);
+        // 0.20 USDC + 0.002 SOL @ 101; the BNB fee has no price.
+        assert_eq!(status.commission_cents, 20 + 20);
+        assert!(
+            client
+                .calls()
+                .contains(&("status", "SOLUSDC".to_string(), id.0))
+        );
+    }
+
+    #[test]
+    fn unknown_order_asks_for_symbol() {
+        let client = MockClient::default();
+        let broker = BinanceBroker::with_client(client.clone());
+
+        let err = broker.cancel_order(OrderId(42)).unwrap_err();
+        assert!(matches!(err, BrokerError::Order(ref msg) if msg.contains("symbol")));
+        assert!(broker.order_status(OrderId(42)).is_err());
+        assert!(client.calls().is_empty());
+
+        broker
+            .cancel_order_with_symbol(&Symbol::new("BTC"), OrderId(42))
+            .unwrap();
+        assert_eq!(client.calls(), vec![("cancel", "BTCUSDT".to_string(), 42)]);
+    }
+
+    fn fractional(symbol: &str, mantissa: u64, decimals: u32) -> BrokerOrder {
+        BrokerOrder {
+            quantity_scaled: Some(DecimalQty::new(mantissa, decimals)),
+            ..order(symbol, 0)
+        }
+    }
+
+    #[test]
+    fn fractional_quantity_is_sent_exactly() {
+        let client = MockClient::default();
+        let broker = BinanceBroker::with_client(client.clone());
+
+        broker.submit_order(&fractional("BTC", 123_456, 8)).unwrap();
+        broker.submit_order(&order("ETH", 3)).unwrap();
+
+        assert_eq!(*client.quantities.lock().unwrap(), vec!["0.00123456", "3"]);
+        // Integer orders never look up the lot filter.
+        assert_eq!(*client.lot_lookups.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn fractional_quantity_truncates_to_lot_step() {
+        let client = MockClient {
+            lot: Some(LotSize {
+                min_qty: DecimalQty::parse("0.00010000").unwrap(),
+                max_qty: DecimalQty::parse("9000.00000000").unwrap(),
+                step_size: DecimalQty::parse("0.00010000").unwrap(),
+            }),
+            ..MockClient::default()
+        };
+        let broker = BinanceBroker::with_client(client.clone());
+
+        // 0.00129999 would round to 0.0013; it must truncate to 0.0012.
+        broker.submit_order(&fractional("BTC", 129_999, 8)).unwrap();
+        broker.submit_order(&fractional("BTC", 123_456, 8)).unwrap();
+        assert_eq!(*client.quantities.lock().unwrap(), vec!["0.0012", "0.0012"]);
+        assert_eq!(*client.lot_lookups.lock().unwrap(), 1, "filter is cached");
+
+        let err = broker
+            .submit_order(&fractional("BTC", 9_999, 8))
+            .unwrap_err();
+        assert!(matches!(err, BrokerError::Order(ref msg) if msg.contains("LOT_SIZE")));
+        assert_eq!(client.quantities.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,349 @@
+//! Rate limiting and retry/backoff tests for the Binance client against a mock transport.
+
+#[cfg(feature = "binance")]
+mod binance_retry_tests {
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use nanobook_broker::BrokerError;
+    use nanobook_broker::binance::client::{BinanceApi, BinanceClient};
+    use nanobook_broker::binance::transport::{
+        HttpRequest, HttpResponse, Method, RateLimiter, RetryPolicy, Transport, send_with_retry,
+    };
+
+    /// Replays canned responses in order and counts requests.
+    #[derive(Clone)]
+    struct MockTransport {
+        responses: Arc<Mutex<VecDeque<HttpResponse>>>,
+        calls: Arc<Mutex<Vec<Instant>>>,
+        urls: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl MockTransport {
+        fn new(responses: Vec<HttpResponse>) -> Self {
+            Self {
+                responses: Arc::new(Mutex::new(responses.into())),
+                calls: Arc::default(),
+                urls: Arc::default(),
+            }
+        }
+
+        fn calls(&self) -> Vec<Instant> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        fn urls(&self) -> Vec<String> {
+            self.urls.lock().unwrap().clone()
+        }
+    }
+
+    impl Transport for MockTransport {
+        fn send(&self, request: &HttpRequest) -> Result<HttpResponse, String> {
+            self.calls.lock().unwrap().push(Instant::now());
+            self.urls.lock().unwrap().push(request.url.clone());
+            self.responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .ok_or_else(|| "no more canned responses".to_string())
+        }
+    }
+
+    fn response(status: u16, retry_after_secs: Option<u64>, body: &str) -> HttpResponse {
+        HttpResponse {
+            status,
+            retry_after: retry_after_secs.map(Duration::from_secs),
+            body: body.to_string(),
+        }
+    }
+
+    fn ticker_body() -> &'static str {
+        r#"{"symbol":"BTCUSDT","bidPrice":"65000.10","bidQty":"1.0","askPrice":"65000.20","askQty":"2.0"}"#
+    }
+
+    fn request() -> HttpRequest {
+        request_with(Method::Get)
+    }
+
+    fn request_with(method: Method) -> HttpRequest {
+        HttpRequest {
+            method,
+            url: "https://example.invalid/api/v3/ping".into(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    fn policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn two_429s_then_success_honors_retry_after() {
+        let transport = MockTransport::new(vec![
+            response(429, Some(3), "slow down"),
+            response(429, None, "slow down"),
+            response(200, None, "{}"),
+        ]);
+        let limiter = RateLimiter::new(1000.0, 100);
+        let mut sleeps = Vec::new();
+
+        let resp = send_with_retry(&transport, &limiter, &policy(3), &mut request, &mut |d| {
+            sleeps.push(d)
+        })
+        .unwrap();
+
+        assert_eq!(resp.status, 200);
+        assert_eq!(transport.calls().len(), 3);
+        assert_eq!(sleeps.len(), 2);
+        // First wait comes from Retry-After; the second is jittered backoff
+        // for attempt 1: within [base·2/2, base·2).
+        assert_eq!(sleeps[0], Duration::from_secs(3));
+        assert!(
+            sleeps[1] >= Duration::from_millis(100) && sleeps[1] < Duration::from_millis(200),
+            "backoff {:?}",
+            sleeps[1]
+        );
+    }
+
+    #[test]
+    fn retries_exhausted_surface_rate_limited() {
+        let transport = MockTransport::new(vec![
+            response(429, None, "a"),
+            response(429, None, "b"),
+            response(418, None, "banned"),
+        ]);
+        let limiter = RateLimiter::new(1000.0, 100);
+
+        let err = send_with_retry(&transport, &limiter, &policy(2), &mut request, &mut |_| {})
+            .unwrap_err();
+
+        assert!(matches!(err, BrokerError::RateLimited(ref msg) if msg.contains("418")));
+        assert_eq!(transport.calls().len(), 3);
+    }
+
+    #[test]
+    fn server_errors_are_retried_client_errors_are_not() {
+        let transport = MockTransport::new(vec![
+            response(503, None, "busy"),
+            response(400, None, "bad request"),
+        ]);
+        let limiter = RateLimiter::new(1000.0, 100);
+
+        let resp =
+            send_with_retry(&transport, &limiter, &policy(3), &mut request, &mut |_| {}).unwrap();
+
+        assert_eq!(resp.status, 400);
+        assert_eq!(transport.calls().len(), 2);
+    }
+
+    #[test]
+    fn post_is_not_resent_after_server_error() {
+        let transport = MockTransport::new(vec![
+            response(503, None, "unknown"),
+            response(200, None, "{}"),
+        ]);
+        let limiter = RateLimiter::new(1000.0, 100);
+
+        let resp = send_with_retry(
+            &transport,
+            &limiter,
+            &policy(3),
+            &mut || request_with(Method::Post),
+            &mut |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(resp.status, 503);
+        assert_eq!(transport.calls().len(), 1);
+    }
+
+    #[test]
+    fn post_is_retried_when_rate_limited() {
+        let transport = MockTransport::new(vec![
+            response(429, None, "slow down"),
+            response(200, None, "{}"),
+        ]);
+        let limiter = RateLimiter::new(1000.0, 100);
+
+        let resp = send_with_retry(
+            &transport,
+            &limiter,
+            &policy(3),
+            &mut || request_with(Method::Post),
+            &mut |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(resp.status, 200);
+        assert_eq!(transport.calls().len(), 2);
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_and_caps() {
+        let p = policy(10);
+        assert_eq!(p.backoff(0, 0.0), Duration::from_millis(50));
+        assert_eq!(p.backoff(3, 0.0), Duration::from_millis(400));
+        assert_eq!(p.backoff(20, 0.0), Duration::from_millis(2500));
+        assert!(p.backoff(2, 0.999) < Duration::from_millis(400));
+    }
+
+    #[test]
+    fn token_bucket_spaces_requests_after_burst() {
+        let limiter = RateLimiter::new(4.0, 2);
+        let t0 = Instant::now();
+
+        assert_eq!(limiter.reserve(t0), Duration::ZERO);
+        assert_eq!(limiter.reserve(t0), Duration::ZERO);
+        // Bucket empty: the next two callers queue 250ms apart.
+        assert_eq!(limiter.reserve(t0), Duration::from_millis(250));
+        assert_eq!(limiter.reserve(t0), Duration::from_millis(500));
+        // A second later the debt is repaid and tokens have refilled.
+        assert_eq!(limiter.reserve(t0 + Duration::from_secs(1)), Duration::ZERO);
+    }
+
+    #[test]
+    fn client_recovers_from_429s_end_to_end() {
+        let transport = MockTransport::new(vec![
+            response(429, Some(1), ""),
+            response(429, None, ""),
+            response(200, None, ticker_body()),
+        ]);
+        let client = BinanceClient::new("key", "secret", true)
+            .with_transport(transport.clone())
+            .with_retry_policy(RetryPolicy {
+                max_retries: 3,
+                base_delay: Duration::from_millis(10),
+                max_delay: Duration::from_millis(50),
+            });
+
+        let ticker = client.book_ticker("BTCUSDT").unwrap();
+        assert_eq!(ticker.bid_price, "65000.10");
+
+        let calls = transport.calls();
+        assert_eq!(calls.len(), 3);
+        assert!(calls[1] - calls[0] >= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn client_resigns_each_retry_with_fresh_timestamp() {
+        let transport = MockTransport::new(vec![
+            response(429, Some(1), ""),
+            response(200, None, r#"{"balances":[]}"#),
+        ]);
+        let client = BinanceClient::new("key", "secret", true).with_transport(transport.clone());
+
+        client.account_info().unwrap();
+
+        let urls = transport.urls();
+        assert_eq!(urls.len(), 2);
+        assert!(urls.iter().all(|u| u.contains("timestamp=")));
+        assert_ne!(urls[0], urls[1]);
+    }
+
+    #[test]
+    fn client_does_not_resubmit_order_after_server_error() {
+        let transport = MockTransport::new(vec![
+            response(503, None, "execution status unknown"),
+            response(200, None, "{}"),
+        ]);
+        let client = BinanceClient::new("key", "secret", true).with_transport(transport.clone());
+
+        let result = client.submit_order("BTCUSDT", "BUY", "MARKET", "0.001", None, None);
+
+        assert!(matches!(result, Err(BrokerError::Order(_))));
+        assert_eq!(transport.calls().len(), 1);
+    }
+
+    #[test]
+    fn client_maps_exhausted_retries_to_rate_limited() {
+        let transport = MockTransport::new(vec![response(429, None, ""); 2]);
+        let client = BinanceClient::new("key", "secret", true)
+            .with_transport(transport)
+            .with_retry_policy(RetryPolicy {
+                max_retries: 1,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(1),
+            });
+
+        assert!(matches!(
+            client.book_ticker("BTCUSDT"),
+            Err(BrokerError::RateLimited(_))
+        ));
+    }
+}
+
+#[cfg(all(feature = "binance", feature = "async"))]
+mod async_retry_tests {
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+
+    use nanobook_broker::binance::transport::{
+        HttpResponse, Method, RateLimiter, RetryPolicy, send_with_retry_async,
+    };
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+    }
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+        }
+    }
+
+    /// Run `send_with_retry_async` against canned statuses; return the
+    /// final status and the send times.
+    fn run(limiter: &RateLimiter, method: Method, statuses: &[u16]) -> (u16, Vec<Instant>) {
+        let statuses = Mutex::new(statuses.to_vec());
+        let calls = Mutex::new(Vec::new());
+        let resp = runtime()
+            .block_on(send_with_retry_async(limiter, &policy(), method, || {
+                calls.lock().unwrap().push(Instant::now());
+                let status = statuses.lock().unwrap().remove(0);
+                async move {
+                    Ok(HttpResponse {
+                        status,
+                        retry_after: None,
+                        body: String::new(),
+                    })
+                }
+            }))
+            .unwrap();
+        (resp.status, calls.into_inner().unwrap())
+    }
+
+    #[test]
+    fn async_get_is_retried_after_throttling_and_server_errors() {
+        let limiter = RateLimiter::new(1000.0, 100);
+        let (status, calls) = run(&limiter, Method::Get, &[429, 503, 200]);
+        assert_eq!(status, 200);
+        assert_eq!(calls.len(), 3);
+    }
+
+    #[test]
+    fn async_post_is_not_resent_after_server_error() {
+        let limiter = RateLimiter::new(1000.0, 100);
+        let (status, calls) = run(&limiter, Method::Post, &[503, 200]);
+        assert_eq!(status, 503);
+        assert_eq!(calls.len(), 1);
+    }
+
+    #[test]
+    fn async_requests_wait_on_the_shared_limiter() {
+        let limiter = RateLimiter::new(20.0, 1);
+        let (_, first) = run(&limiter, Method::Get, &[200]);
+        let (_, second) = run(&limiter, Method::Get, &[200]);
+        assert!(second[0] - first[0] >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,154 @@
+// Allow our dollar.cents digit grouping convention (e.g., 100_00 = $100.00)
+#![allow(clippy::inconsistent_digit_grouping)]
+
+//! bookTicker quote streaming against a local mock WebSocket server.
+
+#[cfg(feature = "binance")]
+mod binance_stream_tests {
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::mpsc::{Receiver, RecvTimeoutError};
+    use std::thread;
+    use std::time::Duration;
+
+    use nanobook::Symbol;
+    use nanobook_broker::binance::BinanceBroker;
+    use nanobook_broker::{Broker, Quote};
+    use tungstenite::handshake::server::{Request, Response};
+    use tungstenite::{Message, WebSocket};
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Accept one client, complete the upgrade, and return the request target.
+    #[allow(clippy::result_large_err)] // callback signature is tungstenite's
+    fn accept(listener: &TcpListener) -> (WebSocket<TcpStream>, String) {
+        let (stream, _) = listener.accept().unwrap();
+        let mut target = String::new();
+        let ws = tungstenite::accept_hdr(stream, |req: &Request, resp: Response| {
+            target = req.uri().to_string();
+            Ok(resp)
+        })
+        .unwrap();
+        (ws, target)
+    }
+
+    /// Send one text message holding a combined-stream bookTicker event.
+    fn push(ws: &mut WebSocket<TcpStream>, pair: &str, update_id: u64, bid: &str, ask: &str) {
+        let payload = format!(
+            r#"{{"stream":"{}@bookTicker","data":{{"u":{update_id},"s":"{pair}","b":"{bid}","B":"1.0","a":"{ask}","A":"2.0"}}}}"#,
+            pair.to_lowercase()
+        );
+        ws.send(Message::text(payload)).unwrap();
+    }
+
+    fn close(mut ws: WebSocket<TcpStream>) {
+        ws.close(None).unwrap();
+        // Drain until the client's close reply completes the handshake.
+        while ws.read().is_ok() {}
+    }
+
+    fn next(rx: &Receiver<Quote>) -> Quote {
+        rx.recv_timeout(TIMEOUT).expect("quote within timeout")
+    }
+
+    #[test]
+    fn yields_updates_in_order_and_survives_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = thread::spawn(move || {
+            let (mut ws, target) = accept(&listener);
+            push(&mut ws, "BTCUSDT", 10, "65000.10", "65000.20");
+            push(&mut ws, "ETHUSDT", 11, "3000.00", "3000.50");
+            push(&mut ws, "BTCUSDT", 12, "65001.00", "65001.40");
+            push(&mut ws, "BTCUSDT", 9, "1.00", "2.00"); // stale
+            close(ws);
+
+            // The client reconnects and the server replays an old update.
+            let (mut ws, _) = accept(&listener);
+            push(&mut ws, "BTCUSDT", 12, "65001.00", "65001.40"); // duplicate
+            push(&mut ws, "BTCUSDT", 13, "64999.99", "65000.01");
+            close(ws);
+            target
+        });
+
+        let broker =
+            BinanceBroker::new("", "", false).with_stream_url(&format!("ws://127.0.0.1:{port}"));
+        let rx = broker
+            .subscribe_quotes(&[Symbol::new("BTC"), Symbol::new("ETH")])
+            .unwrap();
+
+        let got: Vec<(Symbol, i64, i64)> = (0..4)
+            .map(|_| next(&rx))
+            .map(|q| (q.symbol, q.bid_cents, q.ask_cents))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                (Symbol::new("BTC"), 65_000_10, 65_000_20),
+                (Symbol::new("ETH"), 3_000_00, 3_000_50),
+                (Symbol::new("BTC"), 65_001_00, 65_001_40),
+                (Symbol::new("BTC"), 64_999_99, 65_000_01),
+            ]
+        );
+
+        let target = server.join().unwrap();
+        assert!(target.starts_with("/stream?streams="));
+        assert!(target.contains("btcusdt@bookTicker"));
+        assert!(target.contains("ethusdt@bookTicker"));
+
+        // Nothing else arrives: the stale and duplicate updates were dropped.
+        assert_eq!(
+            rx.recv_timeout(Duration::from_millis(50)).unwrap_err(),
+            RecvTimeoutError::Timeout
+        );
+    }
+
+    #[test]
+    fn dropping_the_broker_stops_reconnecting() {
+        // Nothing listens on this port, so every connect attempt fails.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let broker =
+            BinanceBroker::new("", "", false).with_stream_url(&format!("ws://127.0.0.1:{port}"));
+        let rx = broker.subscribe_quotes(&[Symbol::new("BTC")]).unwrap();
+        thread::sleep(Duration::from_millis(300));
+
+        drop(broker);
+        assert_eq!(
+            rx.recv_timeout(TIMEOUT).unwrap_err(),
+            RecvTimeoutError::Disconnected
+        );
+    }
+
+    #[test]
+    fn disconnect_stops_a_silent_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        // Upgrade, then never send anything until the client goes away.
+        let server = thread::spawn(move || {
+            let (mut ws, _) = accept(&listener);
+            while ws.read().is_ok() {}
+        });
+
+        let mut broker =
+            BinanceBroker::new("", "", false).with_stream_url(&format!("ws://127.0.0.1:{port}"));
+        let rx = broker.subscribe_quotes(&[Symbol::new("BTC")]).unwrap();
+        thread::sleep(Duration::from_millis(300));
+
+        broker.disconnect().unwrap();
+        assert_eq!(
+            rx.recv_timeout(TIMEOUT).unwrap_err(),
+            RecvTimeoutError::Disconnected
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn empty_symbol_list_is_rejected() {
+        let broker = BinanceBroker::new("", "", false);
+        assert!(broker.subscribe_quotes(&[]).is_err());
+    }
+}
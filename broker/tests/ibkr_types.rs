@@ -65,6 +65,128 @@ mod ibkr_tests {
         assert!(broker.positions().is_err());
         assert!(broker.account().is_err());
     }
+
+    // ========================================================================
+    // Order status tracking
+    // ========================================================================
+
+    use nanobook_broker::ibkr::status::{OrderStatusTracker, StatusUpdate, map_status};
+    use nanobook_broker::types::{OrderId, OrderState};
+
+    fn status(id: u64, status: &str, filled: f64, remaining: f64, avg: f64) -> StatusUpdate {
+        StatusUpdate::Status {
+            order_id: OrderId(id),
+            status: status.into(),
+            filled,
+            remaining,
+            avg_fill_price: avg,
+        }
+    }
+
+    fn execution(id: u64, exec_id: &str, shares: f64, price: f64) -> StatusUpdate {
+        StatusUpdate::Execution {
+            order_id: OrderId(id),
+            execution_id: exec_id.into(),
+            shares,
+            price,
+        }
+    }
+
+    #[test]
+    fn tracker_accumulates_partial_executions() {
+        let tracker = OrderStatusTracker::new();
+        tracker.register(OrderId(7), 100);
+
+        // Mock TWS stream: drained on another thread like a live subscription.
+        let feed = [
+            status(7, "Submitted", 0.0, 100.0, 0.0),
+            execution(7, "0001.01", 40.0, 100.00),
+            execution(7, "0001.02", 60.0, 101.00),
+            status(7, "Filled", 100.0, 0.0, 100.60),
+        ];
+        let (tx, rx) = std::sync::mpsc::channel();
+        let worker = {
+            let tracker = tracker.clone();
+            std::thread::spawn(move || {
+                for update in rx {
+                    tracker.apply(&update);
+                }
+            })
+        };
+
+        tx.send(feed[0].clone()).unwrap();
+        tx.send(feed[1].clone()).unwrap();
+        tx.send(feed[1].clone()).unwrap(); // TWS resend is ignored
+        drop(tx);
+        worker.join().unwrap();
+
+        let partial = tracker.status(OrderId(7)).unwrap();
+        assert_eq!(partial.status, OrderState::PartiallyFilled);
+        assert_eq!(partial.filled_quantity, 40);
+        assert_eq!(partial.remaining_quantity, 60);
+        assert_eq!(partial.avg_fill_price_cents, 100_00);
+
+        tracker.apply(&feed[2]);
+        tracker.apply(&feed[3]);
+        let done = tracker.status(OrderId(7)).unwrap();
+        assert_eq!(done.status, OrderState::Filled);
+        assert_eq!(done.filled_quantity, 100);
+        assert_eq!(done.remaining_quantity, 0);
+        // VWAP: (40 × 100.00 + 60 × 101.00) / 100 = 100.60
+        assert_eq!(done.avg_fill_price_cents, 100_60);
+    }
+
+    #[test]
+    fn tracker_sums_commission_reports_per_execution() {
+        let tracker = OrderStatusTracker::new();
+        tracker.register(OrderId(5), 100);
+        let commission = |exec_id: &str, amount: f64| StatusUpdate::Commission {
+            execution_id: exec_id.into(),
+            commission: amount,
+        };
+
+        tracker.apply(&commission("0002.01", 9.99)); // execution not seen yet
+        tracker.apply(&execution(5, "0002.01", 40.0, 50.00));
+        tracker.apply(&execution(5, "0002.02", 60.0, 50.00));
+        tracker.apply(&commission("0002.01", 2.00));
+        tracker.apply(&commission("0002.02", 3.00));
+        tracker.apply(&commission("0002.02", 3.00)); // resend is not double counted
+
+        assert_eq!(tracker.status(OrderId(5)).unwrap().commission_cents, 5_00);
+    }
+
+    #[test]
+    fn tracker_falls_back_to_order_status_figures() {
+        let tracker = OrderStatusTracker::new();
+        tracker.register(OrderId(3), 50);
+        tracker.apply(&status(3, "Submitted", 20.0, 30.0, 185.25));
+
+        let s = tracker.status(OrderId(3)).unwrap();
+        assert_eq!(s.status, OrderState::PartiallyFilled);
+        assert_eq!(s.filled_quantity, 20);
+        assert_eq!(s.avg_fill_price_cents, 185_25);
+
+        // Unregistered orders are neither tracked nor reported.
+        tracker.apply(&status(99, "Filled", 10.0, 0.0, 1.0));
+        assert!(tracker.status(OrderId(99)).is_none());
+    }
+
+    #[test]
+    fn tws_status_strings_map_to_order_state() {
+        assert_eq!(map_status("PreSubmitted", 0.0, 10.0), OrderState::Submitted);
+        assert_eq!(map_status("PendingSubmit", 0.0, 10.0), OrderState::Pending);
+        assert_eq!(map_status("Cancelled", 4.0, 6.0), OrderState::Cancelled);
+        assert_eq!(map_status("Inactive", 0.0, 10.0), OrderState::Rejected);
+        assert_eq!(map_status("Filled", 10.0, 0.0), OrderState::Filled);
+    }
+
+    #[test]
+    fn ibkr_order_status_requires_tracked_order() {
+        use nanobook_broker::ibkr::IbkrBroker;
+
+        let broker = IbkrBroker::new("127.0.0.1", 4002, 100);
+        assert!(broker.order_tracker().status(OrderId(1)).is_none());
+    }
 }
 
 // ============================================================================
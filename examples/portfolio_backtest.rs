@@ -19,6 +19,8 @@ fn main() {
         commission_bps: 3,
         slippage_bps: 2,
         min_trade_fee: 1_00, // $1 minimum per trade
+        size_impact: None,
+        fee_tiers: None,
     };
     let mut portfolio = Portfolio::new(1_000_000_00, cost_model);
 
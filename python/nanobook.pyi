@@ -1,15 +1,61 @@
-from typing import List, Tuple, Optional, Dict, Any, Union, Callable
+from typing import List, Tuple, Optional, Dict, Any, Union, Callable, TypeVar
+
+import numpy as np
+import numpy.typing as npt
+
+# float64 numpy arrays in give numpy arrays out; lists give lists.
+Series = TypeVar("Series", List[float], npt.NDArray[np.float64])
 
 __version__: str
 
+class BrokerError(RuntimeError): ...
+class BrokerConnectionError(BrokerError): ...
+class NotConnectedError(BrokerError): ...
+class OrderError(BrokerError): ...
+class InvalidSymbolError(BrokerError): ...
+class AuthenticationError(BrokerError): ...
+class RateLimitedError(BrokerError): ...
+
+class BrokerPosition:
+    symbol: str
+    quantity: int
+    avg_cost_cents: int
+    market_value_cents: int
+    unrealized_pnl_cents: int
+    def __init__(self, symbol: str, quantity: int, avg_cost_cents: int, market_value_cents: int = 0, unrealized_pnl_cents: int = 0) -> None: ...
+    def to_dict(self) -> Dict[str, Any]: ...
+    def __getitem__(self, key: str) -> Any: ...
+
+class BrokerAccount:
+    equity_cents: int
+    buying_power_cents: int
+    cash_cents: int
+    gross_position_value_cents: int
+    def __init__(self, equity_cents: int, buying_power_cents: int, cash_cents: int, gross_position_value_cents: int = 0) -> None: ...
+    def to_dict(self) -> Dict[str, Any]: ...
+    def __getitem__(self, key: str) -> Any: ...
+
+class BrokerOrderStatus:
+    id: int
+    status: str
+    filled_quantity: int
+    remaining_quantity: int
+    avg_fill_price_cents: int
+    commission_cents: int
+    def to_dict(self) -> Dict[str, Any]: ...
+    def __getitem__(self, key: str) -> Any: ...
+
 class IbkrBroker:
     def __init__(self, host: str, port: int, client_id: int) -> None: ...
     def connect(self) -> None: ...
     def disconnect(self) -> None: ...
-    def positions(self) -> List[Dict[str, Any]]: ...
-    def account(self) -> Dict[str, Any]: ...
-    def submit_order(self, symbol: str, side: str, quantity: int, order_type: str = "market", limit_price_cents: Optional[int] = None) -> int: ...
-    def order_status(self, order_id: int) -> Dict[str, Any]: ...
+    def close(self) -> None: ...
+    def __enter__(self) -> "IbkrBroker": ...
+    def __exit__(self, *exc_info: Any) -> bool: ...
+    def positions(self) -> List[BrokerPosition]: ...
+    def account(self) -> BrokerAccount: ...
+    def submit_order(self, symbol: str, side: str, quantity: int, order_type: str = "market", limit_price_cents: Optional[int] = None, reduce_only: bool = False) -> int: ...
+    def order_status(self, order_id: int) -> BrokerOrderStatus: ...
     def cancel_order(self, order_id: int) -> None: ...
     def quote(self, symbol: str) -> Dict[str, Any]: ...
 
@@ -17,13 +63,39 @@ class BinanceBroker:
     def __init__(self, api_key: str, secret_key: str, testnet: bool = False, quote_asset: str = "USDT") -> None: ...
     def connect(self) -> None: ...
     def disconnect(self) -> None: ...
-    def positions(self) -> List[Dict[str, Any]]: ...
-    def account(self) -> Dict[str, Any]: ...
-    def submit_order(self, symbol: str, side: str, quantity: int, order_type: str = "market", limit_price_cents: Optional[int] = None) -> int: ...
-    def order_status(self, order_id: int) -> Dict[str, Any]: ...
+    def close(self) -> None: ...
+    def __enter__(self) -> "BinanceBroker": ...
+    def __exit__(self, *exc_info: Any) -> bool: ...
+    def positions(self) -> List[BrokerPosition]: ...
+    def account(self) -> BrokerAccount: ...
+    def submit_order(self, symbol: str, side: str, quantity: int, order_type: str = "market", limit_price_cents: Optional[int] = None, reduce_only: bool = False) -> int: ...
+    def order_status(self, order_id: int) -> BrokerOrderStatus: ...
     def cancel_order(self, order_id: int) -> None: ...
+    def cancel_order_with_symbol(self, symbol: str, order_id: int) -> None: ...
     def quote(self, symbol: str) -> Dict[str, Any]: ...
 
+class MockBroker:
+    def __init__(
+        self,
+        positions: List[Tuple[str, int, int]] = [],
+        quotes: List[Tuple[str, int, int]] = [],
+        equity_cents: int = 100_000_000,
+        cash_cents: int = 100_000_000,
+        reject_orders: bool = False,
+    ) -> None: ...
+    def connect(self) -> None: ...
+    def disconnect(self) -> None: ...
+    def close(self) -> None: ...
+    def __enter__(self) -> "MockBroker": ...
+    def __exit__(self, *exc_info: Any) -> bool: ...
+    def positions(self) -> List[BrokerPosition]: ...
+    def account(self) -> BrokerAccount: ...
+    def submit_order(self, symbol: str, side: str, quantity: int, order_type: str = "market", limit_price_cents: Optional[int] = None, reduce_only: bool = False) -> int: ...
+    def order_status(self, order_id: int) -> BrokerOrderStatus: ...
+    def cancel_order(self, order_id: int) -> None: ...
+    def quote(self, symbol: str) -> Dict[str, Any]: ...
+    def submitted_orders(self) -> List[Tuple[str, str, int]]: ...
+
 class RiskEngine:
     def __init__(
         self,
@@ -49,6 +121,7 @@ class Order:
     status: str
     time_in_force: str
     timestamp: int
+    executions: List[Tuple[int, int, int, int]]
 
 class Position:
     symbol: str
@@ -56,7 +129,14 @@ class Position:
     avg_entry_price: int
     total_cost: int
     realized_pnl: int
+    bought: int
+    sold: int
+    currency: str
     def unrealized_pnl(self, price: int) -> int: ...
+    def liquidation_price(self, maintenance_margin_pct: float, leverage: float) -> Optional[int]: ...
+    def break_even_price(self, fee_bps: float) -> Optional[int]: ...
+    def lots(self) -> List[Tuple[int, int]]: ...
+    def realized_pnl_by_lot(self) -> List[Tuple[int, int, int, int]]: ...
 
 class Event:
     kind: str
@@ -78,6 +158,8 @@ class LevelSnapshot:
     price: int
     quantity: int
     order_count: int
+    oldest_timestamp: Optional[int]
+    newest_timestamp: Optional[int]
     @property
     def price_float(self) -> float: ...
 
@@ -90,6 +172,8 @@ class BookSnapshot:
     def weighted_mid(self) -> Optional[float]: ...
     def mid_price(self) -> Optional[float]: ...
     def spread(self) -> Optional[int]: ...
+    def aggregate_levels(self, bin_width: int) -> 'BookSnapshot': ...
+    def checksum(self, depth: int = 10) -> int: ...
 
 class SubmitResult:
     order_id: int
@@ -97,6 +181,7 @@ class SubmitResult:
     filled_quantity: int
     resting_quantity: int
     cancelled_quantity: int
+    cascade_truncated: bool
     @property
     def trades(self) -> List[Trade]: ...
 
@@ -111,12 +196,22 @@ class ModifyResult:
     new_order_id: Optional[int]
     cancelled_quantity: int
     error: Optional[str]
+    priority_kept: bool
     @property
     def trades(self) -> List[Trade]: ...
 
 class StopSubmitResult:
     order_id: int
     status: str
+    cascade_truncated: bool
+
+class AuctionResult:
+    clearing_price: Optional[int]
+    matched_volume: int
+    imbalance: int
+    cascade_truncated: bool
+    @property
+    def trades(self) -> List[Trade]: ...
 
 class Metrics:
     total_return: float
@@ -135,28 +230,49 @@ class Metrics:
     payoff_ratio: float
     kelly: float
 
+class RelativeMetrics:
+    beta: float
+    alpha: float
+    tracking_error: float
+    information_ratio: float
+
 class BacktestResult:
     portfolio: 'Portfolio'
     metrics: Optional[Metrics]
 
+class BacktestWeightsResult(Dict[str, Any]):
+    def to_records(self) -> Dict[str, List[Any]]: ...
+    def stop_events_records(self) -> Dict[str, List[Any]]: ...
+
 class CostModel:
-    def __init__(self, commission_bps: int = 0, slippage_bps: int = 0, min_trade_fee: int = 0) -> None: ...
+    def __init__(self, commission_bps: int = 0, slippage_bps: int = 0, min_trade_fee: int = 0, maker_bps: Optional[int] = None, taker_bps: Optional[int] = None) -> None: ...
     @staticmethod
     def zero() -> 'CostModel': ...
     def compute_cost(self, notional: int) -> int: ...
+    def compute_cost_with_liquidity(self, notional: int, quantity: int, maker: bool = False) -> int: ...
 
 class Portfolio:
-    def __init__(self, initial_cash: int, cost_model: CostModel) -> None: ...
+    def __init__(self, initial_cash: int, cost_model: CostModel, cost_basis: str = "average", currencies: Optional[List[Tuple[str, str]]] = None, cash_currency: str = "USD") -> None: ...
+    @staticmethod
+    def with_leverage(initial_cash: int, cost_model: CostModel, max_leverage: float) -> 'Portfolio': ...
     @property
     def cash(self) -> int: ...
+    def gross_exposure(self, prices: List[Tuple[str, int]]) -> int: ...
+    def net_exposure(self, prices: List[Tuple[str, int]]) -> int: ...
     def position(self, symbol: str) -> Optional[Position]: ...
     def positions(self) -> Dict[str, Position]: ...
+    def consolidated_position(self, symbol: str) -> Optional[Dict[str, int]]: ...
+    def gross_traded_quantity(self, symbol: str) -> int: ...
     def total_equity(self, prices: List[Tuple[str, int]]) -> int: ...
+    def total_equity_fx(self, prices: List[Tuple[str, int]], fx_rates: List[Tuple[str, str, float]], base_currency: str = "USD") -> Tuple[int, List[str]]: ...
     def current_weights(self, prices: List[Tuple[str, int]]) -> List[Tuple[str, float]]: ...
     def returns(self) -> List[float]: ...
     def equity_curve(self) -> List[int]: ...
     def rebalance_simple(self, targets: List[Tuple[str, float]], prices: List[Tuple[str, int]]) -> None: ...
+    def rebalance_with_band(self, targets: List[Tuple[str, float]], prices: List[Tuple[str, int]], no_trade_band: float) -> None: ...
+    def turnover(self, prior_weights: List[Tuple[str, float]]) -> float: ...
     def rebalance_lob(self, targets: List[Tuple[str, float]], exchanges: 'MultiExchange') -> None: ...
+    def last_skipped(self) -> List[Tuple[str, int]]: ...
     def record_return(self, prices: List[Tuple[str, int]]) -> None: ...
     def snapshot(self, prices: List[Tuple[str, int]]) -> Dict[str, Any]: ...
     def compute_metrics(self, periods_per_year: float, risk_free: float) -> Optional[Metrics]: ...
@@ -165,18 +281,35 @@ class Portfolio:
     def load_json(path: str) -> 'Portfolio': ...
 
 class Exchange:
-    def __init__(self) -> None: ...
+    def __init__(self, trade_price_rule: str = "resting", order_offset: int = 0, trade_offset: int = 0, ts_offset: int = 0) -> None: ...
     @staticmethod
     def replay(events: List[Event]) -> 'Exchange': ...
+    @staticmethod
+    def replay_checked(events: List[Event]) -> 'Exchange': ...
     def submit_limit(self, side: str, price: int, quantity: int, tif: str = "gtc") -> SubmitResult: ...
     def submit_market(self, side: str, quantity: int) -> SubmitResult: ...
+    def submit_market_fok(self, side: str, quantity: int) -> SubmitResult: ...
+    def submit_batch(self, orders: List[Tuple[str, int, int, str]]) -> List[SubmitResult]: ...
+    def submit_limit_delayed(self, side: str, price: int, quantity: int, arrival: int, tif: str = "gtc") -> None: ...
+    def advance_to(self, timestamp: int) -> List[SubmitResult]: ...
+    def sim_time(self) -> int: ...
+    def pending_delayed_count(self) -> int: ...
     def cancel(self, order_id: int) -> CancelResult: ...
-    def modify(self, order_id: int, new_price: int, new_quantity: int) -> ModifyResult: ...
-    def submit_stop_market(self, side: str, stop_price: int, quantity: int) -> StopSubmitResult: ...
+    def cancel_partial(self, order_id: int, quantity: int) -> CancelResult: ...
+    def cancel_stale(self, older_than: int) -> List[int]: ...
+    def modify(self, order_id: int, new_price: int, new_quantity: int, preserve_priority: bool = False) -> ModifyResult: ...
+    def reduce_quantity(self, order_id: int, new_quantity: int) -> ModifyResult: ...
+    def submit_stop_market(self, side: str, stop_price: int, quantity: int, max_slippage: Optional[int] = None) -> StopSubmitResult: ...
+    def submit_mit(self, side: str, trigger_price: int, quantity: int) -> StopSubmitResult: ...
     def submit_stop_limit(self, side: str, stop_price: int, limit_price: int, quantity: int, tif: str = "gtc") -> StopSubmitResult: ...
     def submit_trailing_stop_market(self, side: str, initial_stop_price: int, quantity: int, trail_type: str, trail_value: float, atr_period: Optional[int] = None) -> StopSubmitResult: ...
     def submit_trailing_stop_limit(self, side: str, initial_stop_price: int, limit_price: int, quantity: int, trail_type: str, trail_value: float, tif: str = "gtc", atr_period: Optional[int] = None) -> StopSubmitResult: ...
+    def submit_trailing_stop_limit_offset(self, side: str, initial_stop_price: int, limit_offset: int, quantity: int, trail_type: str, trail_value: float, tif: str = "gtc", atr_period: Optional[int] = None) -> StopSubmitResult: ...
+    def begin_auction(self) -> None: ...
+    def in_auction(self) -> bool: ...
+    def run_auction(self) -> AuctionResult: ...
     def get_order(self, order_id: int) -> Optional[Order]: ...
+    def order_executions(self, order_id: int) -> Optional[List[Tuple[int, int, int, int]]]: ...
     def get_stop_order(self, order_id: int) -> Optional[Dict[str, Any]]: ...
     def best_bid_ask(self) -> Tuple[Optional[int], Optional[int]]: ...
     def best_bid(self) -> Optional[int]: ...
@@ -185,9 +318,26 @@ class Exchange:
     def last_trade_price(self) -> Optional[int]: ...
     def trades(self) -> List[Trade]: ...
     def events(self) -> List[Event]: ...
+    def trades_to_csv(self) -> str: ...
+    def events_to_csv(self) -> str: ...
     def depth(self, levels: int = 10) -> BookSnapshot: ...
     def full_book(self) -> BookSnapshot: ...
+    def book_checksum(self, depth: int = 10) -> int: ...
+    def level_orders(self, side: str, price: int) -> List[Tuple[int, int, int]]: ...
+    def set_trade_observer(self, callback: Optional[Callable[[Trade], None]]) -> None: ...
+    def queue_ahead(self, order_id: int) -> Optional[int]: ...
+    def fill_probability(self, order_id: int, volume_rate: float, horizon: float) -> Optional[float]: ...
+    def total_matched_quantity(self) -> int: ...
+    def total_matched_notional(self) -> int: ...
+    def trade_count(self) -> int: ...
+    def execution_quality(self) -> Dict[str, Any]: ...
     def pending_stop_count(self) -> int: ...
+    def set_max_cascade_depth(self, depth: int) -> None: ...
+    def max_cascade_depth(self) -> int: ...
+    def truncated_cascades(self) -> int: ...
+    def enable_imbalance_recording(self, capacity: int = 10000) -> None: ...
+    def disable_imbalance_recording(self) -> None: ...
+    def imbalance_history(self) -> List[Tuple[int, float]]: ...
     def clear_trades(self) -> None: ...
     def clear_order_history(self) -> int: ...
     def compact(self) -> None: ...
@@ -197,50 +347,94 @@ class MultiExchange:
     def get_or_create(self, symbol: str) -> Exchange: ...
     def symbols(self) -> List[str]: ...
     def best_prices(self) -> List[Tuple[str, Optional[int], Optional[int]]]: ...
+    def register_symbol(self, symbol: str, tick_size: int = 1, lot_size: int = 1, currency: str = "USD", multiplier: int = 1) -> None: ...
+    def notional(self, symbol: str, price: int, quantity: int) -> int: ...
+    def remove(self, symbol: str) -> Optional[Exchange]: ...
+    def retain_active(self) -> List[str]: ...
+    def symbol_count(self) -> int: ...
+    def total_order_count(self) -> int: ...
+    def submit_limit_on(self, venue: str, symbol: str, side: str, price: int, quantity: int, tif: str = "gtc") -> SubmitResult: ...
+    def find_arbitrage(self, symbol: str) -> Optional[Dict[str, Any]]: ...
     def submit_limit(self, symbol: str, side: str, price: int, quantity: int, tif: str = "gtc") -> SubmitResult: ...
     def submit_market(self, symbol: str, side: str, quantity: int) -> SubmitResult: ...
     def cancel(self, symbol: str, order_id: int) -> CancelResult: ...
     def modify(self, symbol: str, order_id: int, new_price: int, new_quantity: int) -> ModifyResult: ...
     def len(self) -> int: ...
 
-def compute_metrics(returns: List[float], periods_per_year: float = 252.0, risk_free: float = 0.0) -> Optional[Metrics]: ...
+def compute_metrics(returns: List[float], periods_per_year: float = 252.0, risk_free: float = 0.0, mar: Optional[float] = None) -> Optional[Metrics]: ...
 def sweep_equal_weight(price_series: List[List[Tuple[str, int]]], initial_cash: int, cost_model: CostModel, periods_per_year: float = 252.0, risk_free: float = 0.0) -> BacktestResult: ...
+def sweep_stop_configs(weight_schedule: List[List[Tuple[str, float]]], price_schedule: List[List[Tuple[str, int]]], initial_cash: int, cost_bps: int, configs: List[Dict[str, Any]], periods_per_year: float = 252.0, risk_free: float = 0.0) -> List[Tuple[Dict[str, Any], Optional[Metrics]]]: ...
 def run_backtest(strategy: Callable[[int, Dict[str, int], Portfolio], List[Tuple[str, float]]], price_series: List[Dict[str, int]], initial_cash: int, cost_model: CostModel, periods_per_year: float = 252.0, risk_free: float = 0.0) -> BacktestResult: ...
 def parse_itch(path: str) -> List[Tuple[str, Event]]: ...
-def py_backtest_weights(weight_schedule: List[List[Tuple[str, float]]], price_schedule: List[List[Tuple[str, int]]], initial_cash: int, cost_bps: int, periods_per_year: float = 252.0, risk_free: float = 0.0, stop_cfg: Optional[Dict[str, Any]] = None) -> Dict[str, Any]: ...
+def parse_moldudp64(data: bytes) -> Tuple[List[Tuple[str, Event]], List[Tuple[int, int]]]: ...
+def parse_ouch(data: bytes) -> List[Event]: ...
+def load_csv(path: str, action: int, side: int, price: int, quantity: int, tif: Optional[int] = None, order_id: Optional[int] = None, delimiter: str = ",", has_header: bool = True, price_scale: float = 1.0) -> List[Event]: ...
+def py_backtest_weights(weight_schedule: List[List[Tuple[str, float]]], price_schedule: List[List[Tuple[str, int]]], initial_cash: int, cost_bps: int, periods_per_year: float = 252.0, risk_free: float = 0.0, stop_cfg: Optional[Dict[str, Any]] = None, dividends: Optional[List[List[Tuple[str, int]]]] = None, rebalance_on: Optional[List[bool]] = None, warmup: int = 0, record_trades: bool = False, max_gross_weight: Optional[float] = None, benchmark_returns: Optional[List[float]] = None) -> BacktestWeightsResult: ...
 
 # v0.8 — Technical indicators (ta-lib replacements)
-def py_rsi(close: List[float], period: int) -> List[float]: ...
-def py_macd(close: List[float], fast_period: int, slow_period: int, signal_period: int) -> Tuple[List[float], List[float], List[float]]: ...
-def py_bbands(close: List[float], period: int, num_std_up: float, num_std_dn: float) -> Tuple[List[float], List[float], List[float]]: ...
-def py_atr(high: List[float], low: List[float], close: List[float], period: int) -> List[float]: ...
+def py_rsi(close: Series, period: int = 14) -> Series: ...
+def py_macd(close: Series, fast_period: int = 12, slow_period: int = 26, signal_period: int = 9) -> Tuple[Series, Series, Series]: ...
+def py_bbands(close: Series, period: int = 20, num_std_up: float = 2.0, num_std_dn: float = 2.0) -> Tuple[Series, Series, Series]: ...
+def py_roc(values: Series, period: int = 10) -> Series: ...
+def py_momentum(values: Series, period: int = 10) -> Series: ...
+def py_cci(high: Series, low: Series, close: Series, period: int = 14) -> Series: ...
+def py_keltner(high: Series, low: Series, close: Series, period: int = 20, atr_mult: float = 2.0) -> Tuple[Series, Series, Series]: ...
+def py_donchian(high: Series, low: Series, period: int = 20) -> Tuple[Series, Series, Series]: ...
+def py_bbands_ext(close: Series, period: int = 20, num_std_up: float = 2.0, num_std_dn: float = 2.0) -> Tuple[Series, Series, Series, Series, Series]: ...
+def py_atr(high: Series, low: Series, close: Series, period: int = 14) -> Series: ...
 
 # v0.8 — Statistics (scipy replacements)
 def py_spearman(x: List[float], y: List[float]) -> Tuple[float, float]: ...
 def py_quintile_spread(scores: List[float], returns: List[float], n_quantiles: int) -> float: ...
+def py_information_coefficient(signal: List[float], forward_return: List[float]) -> Optional[float]: ...
+def py_rolling_ic(signals: List[List[float]], returns: List[List[float]]) -> List[float]: ...
+def py_quantile_portfolio(signals: List[Tuple[str, float]], n_quantiles: int = 5, long_top: bool = True) -> List[Tuple[str, float]]: ...
 
 # v0.8 — Cross-validation (sklearn replacement)
 def py_time_series_split(n_samples: int, n_splits: int) -> List[Tuple[List[int], List[int]]]: ...
 
 # v0.8 — Rolling metrics (quantstats replacements)
-def py_rolling_sharpe(returns: List[float], window: int, periods_per_year: int = 252) -> List[float]: ...
-def py_rolling_volatility(returns: List[float], window: int, periods_per_year: int = 252) -> List[float]: ...
+def py_rolling_sharpe(returns: Series, window: int, periods_per_year: int = 252) -> Series: ...
+def py_rolling_volatility(returns: Series, window: int, periods_per_year: int = 252) -> Series: ...
+def py_compute_relative_metrics(returns: List[float], benchmark: List[float], periods_per_year: float = 252.0, risk_free: float = 0.0) -> Optional[RelativeMetrics]: ...
 
 # v0.9 — Capability probing and advanced compute APIs
 def py_capabilities() -> List[str]: ...
 def py_garch_forecast(returns: List[float], p: int = 1, q: int = 1, mean: str = "zero") -> float: ...
-def py_optimize_min_variance(returns_matrix: List[List[float]], symbols: List[str]) -> Dict[str, float]: ...
+def py_garch_gjr_forecast(returns: List[float], horizon: int = 1) -> List[float]: ...
+def py_ewma_volatility(returns: List[float], lambda_: float = 0.94, periods_per_year: float = 252.0) -> List[float]: ...
+def py_garch_fit(returns: List[float]) -> Optional[Dict[str, Any]]: ...
+def py_garch_term_structure(returns: List[float], horizon: int = 1, periods_per_year: float = 252.0) -> List[Dict[str, float]]: ...
+def py_optimize_min_variance(returns_matrix: List[List[float]], symbols: List[str], max_iter: int = 350, tolerance: float = 1e-8) -> Dict[str, float]: ...
+def py_optimize_min_variance_ext(returns_matrix: List[List[float]], symbols: List[str], max_iter: int = 350, tolerance: float = 1e-8) -> Dict[str, Any]: ...
 def py_optimize_max_sharpe(returns_matrix: List[List[float]], symbols: List[str], risk_free: float = 0.0) -> Dict[str, float]: ...
-def py_optimize_risk_parity(returns_matrix: List[List[float]], symbols: List[str]) -> Dict[str, float]: ...
+def py_optimize_risk_parity(returns_matrix: List[List[float]], symbols: List[str], max_iter: int = 600, tolerance: float = 1e-8) -> Dict[str, float]: ...
+def py_optimize_risk_parity_ext(returns_matrix: List[List[float]], symbols: List[str], max_iter: int = 600, tolerance: float = 1e-8) -> Dict[str, Any]: ...
 def py_optimize_cvar(returns_matrix: List[List[float]], symbols: List[str], alpha: float = 0.95) -> Dict[str, float]: ...
 def py_optimize_cdar(returns_matrix: List[List[float]], symbols: List[str], alpha: float = 0.95) -> Dict[str, float]: ...
+def py_optimize_max_diversification(cov: List[List[float]], vols: List[float], symbols: List[str]) -> Dict[str, float]: ...
+def py_optimize_mean_variance(expected_returns: List[float], cov: List[List[float]], symbols: List[str], min_weights: Optional[List[float]] = None, max_weights: Optional[List[float]] = None, target_return: Optional[float] = None) -> Dict[str, float]: ...
+def py_optimize_cvar_scenarios(scenario_returns: List[List[float]], symbols: List[str], alpha: float = 0.95, min_weights: Optional[List[float]] = None, max_weights: Optional[List[float]] = None) -> Dict[str, float]: ...
+def py_optimize_min_variance_tc(cov: List[List[float]], current_weights: List[float], symbols: List[str], cost_bps: float, min_weights: Optional[List[float]] = None, max_weights: Optional[List[float]] = None) -> Dict[str, float]: ...
+def py_risk_contributions(weights: List[float], cov: List[List[float]]) -> List[float]: ...
+def py_marginal_risk(weights: List[float], cov: List[List[float]]) -> List[float]: ...
+
+# Position sizing
+def py_kelly_fraction(win_rate: float, payoff_ratio: float) -> float: ...
+def py_vol_target_weight(signal: float, asset_vol: float, target_vol: float) -> float: ...
+def py_equal_risk_weights(vols: List[float]) -> List[float]: ...
 
 # v0.9 — Clean aliases (preferred for new callers)
 def capabilities() -> List[str]: ...
-def backtest_weights(weight_schedule: List[List[Tuple[str, float]]], price_schedule: List[List[Tuple[str, int]]], initial_cash: int, cost_bps: int, periods_per_year: float = 252.0, risk_free: float = 0.0, stop_cfg: Optional[Dict[str, Any]] = None) -> Dict[str, Any]: ...
+def backtest_weights(weight_schedule: List[List[Tuple[str, float]]], price_schedule: List[List[Tuple[str, int]]], initial_cash: int, cost_bps: int, periods_per_year: float = 252.0, risk_free: float = 0.0, stop_cfg: Optional[Dict[str, Any]] = None, dividends: Optional[List[List[Tuple[str, int]]]] = None, rebalance_on: Optional[List[bool]] = None, warmup: int = 0, record_trades: bool = False, max_gross_weight: Optional[float] = None, benchmark_returns: Optional[List[float]] = None) -> BacktestWeightsResult: ...
 def garch_forecast(returns: List[float], p: int = 1, q: int = 1, mean: str = "zero") -> float: ...
-def optimize_min_variance(returns_matrix: List[List[float]], symbols: List[str]) -> Dict[str, float]: ...
+def garch_gjr_forecast(returns: List[float], horizon: int = 1) -> List[float]: ...
+def optimize_min_variance(returns_matrix: List[List[float]], symbols: List[str], max_iter: int = 350, tolerance: float = 1e-8) -> Dict[str, float]: ...
 def optimize_max_sharpe(returns_matrix: List[List[float]], symbols: List[str], risk_free: float = 0.0) -> Dict[str, float]: ...
-def optimize_risk_parity(returns_matrix: List[List[float]], symbols: List[str]) -> Dict[str, float]: ...
+def optimize_risk_parity(returns_matrix: List[List[float]], symbols: List[str], max_iter: int = 600, tolerance: float = 1e-8) -> Dict[str, float]: ...
 def optimize_cvar(returns_matrix: List[List[float]], symbols: List[str], alpha: float = 0.95) -> Dict[str, float]: ...
 def optimize_cdar(returns_matrix: List[List[float]], symbols: List[str], alpha: float = 0.95) -> Dict[str, float]: ...
+def optimize_max_diversification(cov: List[List[float]], vols: List[float], symbols: List[str]) -> Dict[str, float]: ...
+def optimize_mean_variance(expected_returns: List[float], cov: List[List[float]], symbols: List[str], min_weights: Optional[List[float]] = None, max_weights: Optional[List[float]] = None, target_return: Optional[float] = None) -> Dict[str, float]: ...
+def optimize_cvar_scenarios(scenario_returns: List[List[float]], symbols: List[str], alpha: float = 0.95, min_weights: Optional[List[float]] = None, max_weights: Optional[List[float]] = None) -> Dict[str, float]: ...
+def optimize_min_variance_tc(cov: List[List[float]], current_weights: List[float], symbols: List[str], cost_bps: float, min_weights: Optional[List[float]] = None, max_weights: Optional[List[float]] = None) -> Dict[str, float]: ...
@@ -19,6 +19,12 @@ def backtest_weights(
     periods_per_year=252.0,
     risk_free=0.0,
     stop_cfg=None,
+    dividends=None,
+    rebalance_on=None,
+    warmup=0,
+    record_trades=False,
+    max_gross_weight=None,
+    benchmark_returns=None,
 ):
     return py_backtest_weights(
         weight_schedule,
@@ -28,6 +34,12 @@ def backtest_weights(
         periods_per_year,
         risk_free,
         stop_cfg,
+        dividends,
+        rebalance_on,
+        warmup,
+        record_trades,
+        max_gross_weight,
+        benchmark_returns,
     )
 
 
@@ -35,16 +47,20 @@ def garch_forecast(returns, p=1, q=1, mean="zero"):
     return py_garch_forecast(returns, p, q, mean)
 
 
-def optimize_min_variance(returns_matrix, symbols):
-    return py_optimize_min_variance(returns_matrix, symbols)
+def garch_gjr_forecast(returns, horizon=1):
+    return py_garch_gjr_forecast(returns, horizon)
+
+
+def optimize_min_variance(returns_matrix, symbols, max_iter=350, tolerance=1e-8):
+    return py_optimize_min_variance(returns_matrix, symbols, max_iter, tolerance)
 
 
 def optimize_max_sharpe(returns_matrix, symbols, risk_free=0.0):
     return py_optimize_max_sharpe(returns_matrix, symbols, risk_free)
 
 
-def optimize_risk_parity(returns_matrix, symbols):
-    return py_optimize_risk_parity(returns_matrix, symbols)
+def optimize_risk_parity(returns_matrix, symbols, max_iter=600, tolerance=1e-8):
+    return py_optimize_risk_parity(returns_matrix, symbols, max_iter, tolerance)
 
 
 def optimize_cvar(returns_matrix, symbols, alpha=0.95):
@@ -55,4 +71,37 @@ def optimize_cdar(returns_matrix, symbols, alpha=0.95):
     return py_optimize_cdar(returns_matrix, symbols, alpha)
 
 
+def optimize_max_diversification(cov, vols, symbols):
+    return py_optimize_max_diversification(cov, vols, symbols)
+
+
+def optimize_mean_variance(
+    expected_returns,
+    cov,
+    symbols,
+    min_weights=None,
+    max_weights=None,
+    target_return=None,
+):
+    return py_optimize_mean_variance(
+        expected_returns, cov, symbols, min_weights, max_weights, target_return
+    )
+
+
+def optimize_cvar_scenarios(
+    scenario_returns, symbols, alpha=0.95, min_weights=None, max_weights=None
+):
+    return py_optimize_cvar_scenarios(
+        scenario_returns, symbols, alpha, min_weights, max_weights
+    )
+
+
+def optimize_min_variance_tc(
+    cov, current_weights, symbols, cost_bps, min_weights=None, max_weights=None
+):
+    return py_optimize_min_variance_tc(
+        cov, current_weights, symbols, cost_bps, min_weights, max_weights
+    )
+
+
 __all__ = [name for name in globals() if not name.startswith("_")]
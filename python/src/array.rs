@@ -0,0 +1,160 @@
+//! Zero-copy `float64` series in and out of the Python layer.
+//!
+//! Inputs accept anything exporting a 1-D contiguous `float64` buffer (numpy
+//! arrays, `array.array('d')`, memoryviews) without copying, and fall back to
+//! element-wise extraction otherwise. Results computed from array inputs come
+//! back as numpy arrays that own the Rust allocation; lists still get lists.
+
+use std::ffi::{CString, c_void};
+use std::os::raw::c_int;
+use std::ptr;
+
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyBufferError;
+use pyo3::ffi;
+use pyo3::prelude::*;
+
+/// A `float64` series argument.
+pub enum F64Series {
+    /// Borrowed `float64` buffer (zero-copy).
+    Buffer(PyBuffer<f64>),
+    /// Another buffer exporter (e.g. an `int64` or `float32` array), converted.
+    Converted(Vec<f64>),
+    /// A list or other sequence.
+    List(Vec<f64>),
+}
+
+impl<'py> FromPyObject<'py> for F64Series {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(buffer) = PyBuffer::<f64>::get(ob)
+            && buffer.dimensions() == 1
+            && buffer.is_c_contiguous()
+        {
+            return Ok(Self::Buffer(buffer));
+        }
+        let values = ob.extract()?;
+        // SAFETY: `ob` is a valid object pointer for the duration of the call.
+        if unsafe { ffi::PyObject_CheckBuffer(ob.as_ptr()) } != 0 {
+            Ok(Self::Converted(values))
+        } else {
+            Ok(Self::List(values))
+        }
+    }
+}
+
+impl F64Series {
+    pub fn as_slice(&self) -> &[f64] {
+        match self {
+            // SAFETY: the buffer is 1-D, C-contiguous and typed f64 (checked on
+            // extraction), and stays exported until `self` is dropped. The GIL
+            // is held for the whole call, so no Python code mutates it meanwhile.
+            Self::Buffer(buffer) => unsafe {
+                std::slice::from_raw_parts(buffer.buf_ptr() as *const f64, buffer.item_count())
+            },
+            Self::Converted(values) | Self::List(values) => values,
+        }
+    }
+
+    /// Whether results should be returned as numpy arrays.
+    pub fn is_array(&self) -> bool {
+        !matches!(self, Self::List(_))
+    }
+}
+
+/// Return `values` as a numpy array when `as_array`, else as a list.
+///
+/// Falls back to a list if numpy is not installed.
+pub fn to_python(py: Python<'_>, values: Vec<f64>, as_array: bool) -> PyResult<PyObject> {
+    if as_array && let Ok(numpy) = py.import("numpy") {
+        let owner = Bound::new(py, F64Buffer::new(values))?;
+        return Ok(numpy.call_method1("asarray", (owner,))?.unbind());
+    }
+    Ok(values.into_pyobject(py)?.into_any().unbind())
+}
+
+/// Owns a Rust `Vec<f64>` and exports it through the buffer protocol.
+///
+/// The storage is held as a raw allocation (not a `Vec`) because numpy views
+/// of it are writable.
+#[pyclass(frozen)]
+pub struct F64Buffer {
+    ptr: *mut f64,
+    len: usize,
+    shape: [ffi::Py_ssize_t; 1],
+    strides: [ffi::Py_ssize_t; 1],
+}
+
+// SAFETY: the allocation is exclusively owned and only touched with the GIL held.
+unsafe impl Send for F64Buffer {}
+unsafe impl Sync for F64Buffer {}
+
+impl F64Buffer {
+    fn new(values: Vec<f64>) -> Self {
+        let len = values.len();
+        let ptr = Box::into_raw(values.into_boxed_slice()) as *mut f64;
+        Self {
+            ptr,
+            len,
+            shape: [len as ffi::Py_ssize_t],
+            strides: [size_of::<f64>() as ffi::Py_ssize_t],
+        }
+    }
+}
+
+impl Drop for F64Buffer {
+    fn drop(&mut self) {
+        // SAFETY: `ptr`/`len` came from `Box::into_raw` in `new`.
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(self.ptr, self.len)) });
+    }
+}
+
+#[pymethods]
+impl F64Buffer {
+    unsafe fn __getbuffer__(
+        slf: Bound<'_, Self>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("View is null"));
+        }
+        let this = slf.get();
+        // SAFETY: `view` is non-null and provided by the interpreter. The data,
+        // shape and strides live in `slf`, which the view keeps alive via `obj`.
+        unsafe {
+            (*view).buf = this.ptr as *mut c_void;
+            (*view).len = (this.len * size_of::<f64>()) as ffi::Py_ssize_t;
+            (*view).readonly = 0;
+            (*view).itemsize = size_of::<f64>() as ffi::Py_ssize_t;
+            (*view).format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+                CString::new("d").unwrap().into_raw()
+            } else {
+                ptr::null_mut()
+            };
+            (*view).ndim = 1;
+            (*view).shape = if flags & ffi::PyBUF_ND == ffi::PyBUF_ND {
+                this.shape.as_ptr() as *mut ffi::Py_ssize_t
+            } else {
+                ptr::null_mut()
+            };
+            (*view).strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+                this.strides.as_ptr() as *mut ffi::Py_ssize_t
+            } else {
+                ptr::null_mut()
+            };
+            (*view).suboffsets = ptr::null_mut();
+            (*view).internal = ptr::null_mut();
+            (*view).obj = slf.into_any().into_ptr();
+        }
+        Ok(())
+    }
+
+    unsafe fn __releasebuffer__(&self, view: *mut ffi::Py_buffer) {
+        // SAFETY: `format` is either null or the CString leaked in `__getbuffer__`.
+        unsafe {
+            if !(*view).format.is_null() {
+                drop(CString::from_raw((*view).format));
+            }
+        }
+    }
+}
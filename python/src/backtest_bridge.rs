@@ -3,9 +3,9 @@
 use nanobook::backtest_bridge::{self, BacktestBridgeOptions, BacktestStopConfig};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::PyDict;
 
-use crate::metrics::PyMetrics;
+use crate::results::PyBacktestWeightsResult;
 use crate::types::parse_symbol;
 
 /// Simulate portfolio returns from a pre-computed weight schedule.
@@ -23,12 +23,29 @@ use crate::types::parse_symbol;
 ///     risk_free: Risk-free rate per period.
 ///     stop_cfg: Optional stop simulation config dictionary with supported keys:
 ///         ``fixed_stop_pct``, ``trailing_stop_pct``, ``atr_multiple``, ``atr_period``.
+///     dividends: Optional per-period list of (symbol, per_share_cents) cash
+///         dividends, parallel with weight_schedule. Only held positions are paid.
+///     rebalance_on: Optional per-period list of bools, parallel with
+///         weight_schedule. Weights are only traded on True periods (positions
+///         drift in between); periods past its end do not rebalance.
+///     warmup: Number of leading periods to stay in cash while signals warm
+///         up. They are reported with 0.0 returns and excluded from metrics.
+///     record_trades: Record every fill under ``trades`` (off by default to
+///         keep large sweeps cheap).
+///     max_gross_weight: Optional bound on the sum of absolute weights per
+///         period. Negative weights are shorts; periods above the bound are
+///         scaled down proportionally.
+///     benchmark_returns: Optional per-period benchmark returns. When the
+///         length matches, ``relative_metrics`` holds beta, alpha, tracking
+///         error and information ratio; otherwise it is None.
 ///
-/// Returns a dict with keys:
-///     ``returns``, ``equity_curve``, ``final_cash``, ``metrics``, ``holdings``,
-///     ``symbol_returns``, ``stop_events``.
+/// Returns a ``BacktestWeightsResult`` (a dict) with keys:
+///     ``returns``, ``equity_curve``, ``final_cash``, ``metrics``,
+///     ``relative_metrics``, ``holdings``, ``symbol_returns``, ``stop_events``,
+///     ``turnover``, ``trades``. Its ``to_records()`` and
+///     ``stop_events_records()`` return DataFrame-ready columns.
 #[pyfunction]
-#[pyo3(signature = (weight_schedule, price_schedule, initial_cash, cost_bps, periods_per_year=252.0, risk_free=0.0, stop_cfg=None))]
+#[pyo3(signature = (weight_schedule, price_schedule, initial_cash, cost_bps, periods_per_year=252.0, risk_free=0.0, stop_cfg=None, dividends=None, rebalance_on=None, warmup=0, record_trades=false, max_gross_weight=None, benchmark_returns=None))]
 #[allow(clippy::too_many_arguments)]
 pub fn backtest_weights(
     py: Python<'_>,
@@ -39,30 +56,27 @@ pub fn backtest_weights(
     periods_per_year: f64,
     risk_free: f64,
     stop_cfg: Option<Bound<'_, PyDict>>,
+    dividends: Option<Vec<Vec<(String, i64)>>>,
+    rebalance_on: Option<Vec<bool>>,
+    warmup: usize,
+    record_trades: bool,
+    max_gross_weight: Option<f64>,
+    benchmark_returns: Option<Vec<f64>>,
 ) -> PyResult<PyObject> {
     // Convert Python types to Rust types.
-    let rust_weights: Vec<Vec<(nanobook::Symbol, f64)>> = weight_schedule
-        .iter()
-        .map(|period| {
-            period
-                .iter()
-                .map(|(s, w)| Ok((parse_symbol(s)?, *w)))
-                .collect::<PyResult<Vec<_>>>()
-        })
-        .collect::<PyResult<Vec<_>>>()?;
-
-    let rust_prices: Vec<Vec<(nanobook::Symbol, i64)>> = price_schedule
-        .iter()
-        .map(|period| {
-            period
-                .iter()
-                .map(|(s, p)| Ok((parse_symbol(s)?, *p)))
-                .collect::<PyResult<Vec<_>>>()
-        })
-        .collect::<PyResult<Vec<_>>>()?;
+    let rust_weights = parse_schedule(&weight_schedule)?;
+    let rust_prices = parse_schedule(&price_schedule)?;
+    let rust_dividends = dividends.as_deref().map(parse_schedule).transpose()?;
 
     let options = BacktestBridgeOptions {
         stop_cfg: parse_stop_cfg(stop_cfg)?,
+        dividends: rust_dividends,
+        rebalance_on,
+        warmup,
+        record_trades,
+        max_gross_weight,
+        benchmark_returns,
+        ..Default::default()
     };
 
     // Release GIL during computation.
@@ -78,55 +92,14 @@ pub fn backtest_weights(
         )
     });
 
-    // Convert result to Python dict.
-    let dict = PyDict::new(py);
-    dict.set_item("returns", result.returns)?;
-    dict.set_item("equity_curve", result.equity_curve)?;
-    dict.set_item("final_cash", result.final_cash)?;
-    dict.set_item("metrics", result.metrics.map(PyMetrics::from))?;
-
-    let holdings: Vec<Vec<(String, f64)>> = result
-        .holdings
-        .into_iter()
-        .map(|period| {
-            period
-                .into_iter()
-                .map(|(s, w)| (s.to_string(), w))
-                .collect()
-        })
-        .collect();
-    dict.set_item("holdings", holdings)?;
-
-    let symbol_returns: Vec<Vec<(String, f64)>> = result
-        .symbol_returns
-        .into_iter()
-        .map(|period| {
-            period
-                .into_iter()
-                .map(|(s, r)| (s.to_string(), r))
-                .collect()
-        })
-        .collect();
-    dict.set_item("symbol_returns", symbol_returns)?;
-
-    let stop_events = PyList::empty(py);
-    for ev in result.stop_events {
-        let item = PyDict::new(py);
-        item.set_item("period_index", ev.period_index)?;
-        item.set_item("symbol", ev.symbol.to_string())?;
-        item.set_item("trigger_price", ev.trigger_price)?;
-        item.set_item("exit_price", ev.exit_price)?;
-        item.set_item("reason", ev.reason)?;
-        stop_events.append(item)?;
-    }
-    dict.set_item("stop_events", stop_events)?;
-
-    Ok(dict.into())
+    Ok(PyBacktestWeightsResult::create(py, result)?
+        .into_any()
+        .unbind())
 }
 
 /// Backward-compatible alias for older callers using ``py_backtest_weights``.
 #[pyfunction]
-#[pyo3(signature = (weight_schedule, price_schedule, initial_cash, cost_bps, periods_per_year=252.0, risk_free=0.0, stop_cfg=None))]
+#[pyo3(signature = (weight_schedule, price_schedule, initial_cash, cost_bps, periods_per_year=252.0, risk_free=0.0, stop_cfg=None, dividends=None, rebalance_on=None, warmup=0, record_trades=false, max_gross_weight=None, benchmark_returns=None))]
 #[allow(clippy::too_many_arguments)]
 pub fn py_backtest_weights(
     py: Python<'_>,
@@ -137,6 +110,12 @@ pub fn py_backtest_weights(
     periods_per_year: f64,
     risk_free: f64,
     stop_cfg: Option<Bound<'_, PyDict>>,
+    dividends: Option<Vec<Vec<(String, i64)>>>,
+    rebalance_on: Option<Vec<bool>>,
+    warmup: usize,
+    record_trades: bool,
+    max_gross_weight: Option<f64>,
+    benchmark_returns: Option<Vec<f64>>,
 ) -> PyResult<PyObject> {
     backtest_weights(
         py,
@@ -147,10 +126,33 @@ pub fn py_backtest_weights(
         periods_per_year,
         risk_free,
         stop_cfg,
+        dividends,
+        rebalance_on,
+        warmup,
+        record_trades,
+        max_gross_weight,
+        benchmark_returns,
     )
 }
 
-fn parse_stop_cfg(stop_cfg: Option<Bound<'_, PyDict>>) -> PyResult<Option<BacktestStopConfig>> {
+/// Convert a per-period `(symbol, value)` schedule to Rust symbols.
+pub(crate) fn parse_schedule<T: Copy>(
+    schedule: &[Vec<(String, T)>],
+) -> PyResult<Vec<Vec<(nanobook::Symbol, T)>>> {
+    schedule
+        .iter()
+        .map(|period| {
+            period
+                .iter()
+                .map(|(s, v)| Ok((parse_symbol(s)?, *v)))
+                .collect::<PyResult<Vec<_>>>()
+        })
+        .collect()
+}
+
+pub(crate) fn parse_stop_cfg(
+    stop_cfg: Option<Bound<'_, PyDict>>,
+) -> PyResult<Option<BacktestStopConfig>> {
     let Some(cfg) = stop_cfg else {
         return Ok(None);
     };
@@ -1,12 +1,392 @@
 //! PyO3 bindings for the broker crate.
 
-use nanobook_broker::Broker;
 use nanobook_broker::ibkr::IbkrBroker as RustIbkrBroker;
-use pyo3::exceptions::PyRuntimeError;
+use nanobook_broker::mock::{FillMode, MockBroker as RustMockBroker};
+use nanobook_broker::{
+    Account, Broker, BrokerOrder, BrokerOrderStatus, BrokerOrderType, BrokerSide, OrderId,
+    Position, Quote,
+};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
 
 use crate::types::parse_symbol;
 
+create_exception!(
+    nanobook,
+    BrokerError,
+    PyRuntimeError,
+    "Base class for broker errors."
+);
+create_exception!(
+    nanobook,
+    BrokerConnectionError,
+    BrokerError,
+    "The broker could not be reached."
+);
+create_exception!(
+    nanobook,
+    NotConnectedError,
+    BrokerError,
+    "The broker is not connected."
+);
+create_exception!(
+    nanobook,
+    OrderError,
+    BrokerError,
+    "The broker rejected or failed an order request."
+);
+create_exception!(
+    nanobook,
+    InvalidSymbolError,
+    BrokerError,
+    "The broker does not know the symbol."
+);
+create_exception!(
+    nanobook,
+    AuthenticationError,
+    BrokerError,
+    "The broker rejected the credentials."
+);
+create_exception!(
+    nanobook,
+    RateLimitedError,
+    BrokerError,
+    "The broker throttled the request."
+);
+
+/// Map each `BrokerError` variant to its Python exception type.
+///
+/// All of them derive from `BrokerError`, itself a `RuntimeError`.
+fn broker_err(e: nanobook_broker::BrokerError) -> PyErr {
+    use nanobook_broker::BrokerError as E;
+    let message = e.to_string();
+    match e {
+        E::NotConnected => NotConnectedError::new_err(
+            "not connected: call connect() first or use the broker in a `with` block",
+        ),
+        E::Connection(_) => BrokerConnectionError::new_err(message),
+        E::Order(_) => OrderError::new_err(message),
+        E::InvalidSymbol(_) => InvalidSymbolError::new_err(message),
+        E::Auth(_) => AuthenticationError::new_err(message),
+        E::RateLimited(_) => RateLimitedError::new_err(message),
+        E::Other(_) => BrokerError::new_err(message),
+    }
+}
+
+/// Register the broker exception hierarchy on the module.
+pub fn add_exceptions(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("BrokerError", py.get_type::<BrokerError>())?;
+    m.add(
+        "BrokerConnectionError",
+        py.get_type::<BrokerConnectionError>(),
+    )?;
+    m.add("NotConnectedError", py.get_type::<NotConnectedError>())?;
+    m.add("OrderError", py.get_type::<OrderError>())?;
+    m.add("InvalidSymbolError", py.get_type::<InvalidSymbolError>())?;
+    m.add("AuthenticationError", py.get_type::<AuthenticationError>())?;
+    m.add("RateLimitedError", py.get_type::<RateLimitedError>())?;
+    Ok(())
+}
+
+/// Look up `key` in a record's dict form, raising `KeyError` if absent.
+fn record_item(dict: Bound<'_, PyDict>, key: &str) -> PyResult<PyObject> {
+    dict.get_item(key)?
+        .map(Bound::unbind)
+        .ok_or_else(|| PyKeyError::new_err(key.to_string()))
+}
+
+/// A position held at the broker.
+///
+/// Also supports ``pos["symbol"]`` style access, like the dicts returned
+/// by earlier versions.
+#[pyclass(name = "BrokerPosition", frozen, eq)]
+#[derive(Clone, PartialEq)]
+pub struct PyBrokerPosition {
+    #[pyo3(get)]
+    pub symbol: String,
+    /// Positive = long, negative = short.
+    #[pyo3(get)]
+    pub quantity: i64,
+    #[pyo3(get)]
+    pub avg_cost_cents: i64,
+    #[pyo3(get)]
+    pub market_value_cents: i64,
+    #[pyo3(get)]
+    pub unrealized_pnl_cents: i64,
+}
+
+#[pymethods]
+impl PyBrokerPosition {
+    #[new]
+    #[pyo3(signature = (symbol, quantity, avg_cost_cents, market_value_cents=0, unrealized_pnl_cents=0))]
+    fn new(
+        symbol: String,
+        quantity: i64,
+        avg_cost_cents: i64,
+        market_value_cents: i64,
+        unrealized_pnl_cents: i64,
+    ) -> Self {
+        Self {
+            symbol,
+            quantity,
+            avg_cost_cents,
+            market_value_cents,
+            unrealized_pnl_cents,
+        }
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("symbol", &self.symbol)?;
+        dict.set_item("quantity", self.quantity)?;
+        dict.set_item("avg_cost_cents", self.avg_cost_cents)?;
+        dict.set_item("market_value_cents", self.market_value_cents)?;
+        dict.set_item("unrealized_pnl_cents", self.unrealized_pnl_cents)?;
+        Ok(dict)
+    }
+
+    fn __getitem__(&self, py: Python<'_>, key: &str) -> PyResult<PyObject> {
+        record_item(self.to_dict(py)?, key)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "BrokerPosition(symbol='{}', quantity={}, avg_cost_cents={}, market_value_cents={}, unrealized_pnl_cents={})",
+            self.symbol,
+            self.quantity,
+            self.avg_cost_cents,
+            self.market_value_cents,
+            self.unrealized_pnl_cents,
+        )
+    }
+}
+
+impl From<Position> for PyBrokerPosition {
+    fn from(p: Position) -> Self {
+        Self {
+            symbol: p.symbol.as_str().to_string(),
+            quantity: p.quantity,
+            avg_cost_cents: p.avg_cost_cents,
+            market_value_cents: p.market_value_cents,
+            unrealized_pnl_cents: p.unrealized_pnl_cents,
+        }
+    }
+}
+
+/// Account summary from the broker.
+///
+/// Also supports ``account["equity_cents"]`` style access.
+#[pyclass(name = "BrokerAccount", frozen, eq)]
+#[derive(Clone, PartialEq)]
+pub struct PyBrokerAccount {
+    #[pyo3(get)]
+    pub equity_cents: i64,
+    #[pyo3(get)]
+    pub buying_power_cents: i64,
+    #[pyo3(get)]
+    pub cash_cents: i64,
+    #[pyo3(get)]
+    pub gross_position_value_cents: i64,
+}
+
+#[pymethods]
+impl PyBrokerAccount {
+    #[new]
+    #[pyo3(signature = (equity_cents, buying_power_cents, cash_cents, gross_position_value_cents=0))]
+    fn new(
+        equity_cents: i64,
+        buying_power_cents: i64,
+        cash_cents: i64,
+        gross_position_value_cents: i64,
+    ) -> Self {
+        Self {
+            equity_cents,
+            buying_power_cents,
+            cash_cents,
+            gross_position_value_cents,
+        }
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("equity_cents", self.equity_cents)?;
+        dict.set_item("buying_power_cents", self.buying_power_cents)?;
+        dict.set_item("cash_cents", self.cash_cents)?;
+        dict.set_item(
+            "gross_position_value_cents",
+            self.gross_position_value_cents,
+        )?;
+        Ok(dict)
+    }
+
+    fn __getitem__(&self, py: Python<'_>, key: &str) -> PyResult<PyObject> {
+        record_item(self.to_dict(py)?, key)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "BrokerAccount(equity_cents={}, buying_power_cents={}, cash_cents={}, gross_position_value_cents={})",
+            self.equity_cents,
+            self.buying_power_cents,
+            self.cash_cents,
+            self.gross_position_value_cents,
+        )
+    }
+}
+
+impl From<Account> for PyBrokerAccount {
+    fn from(a: Account) -> Self {
+        Self {
+            equity_cents: a.equity_cents,
+            buying_power_cents: a.buying_power_cents,
+            cash_cents: a.cash_cents,
+            gross_position_value_cents: a.gross_position_value_cents,
+        }
+    }
+}
+
+/// Status of an order submitted to the broker.
+///
+/// ``status`` is one of "Pending", "Submitted", "PartiallyFilled", "Filled",
+/// "Cancelled", "Rejected". Also supports ``status["filled_quantity"]``
+/// style access.
+#[pyclass(name = "BrokerOrderStatus", frozen, eq)]
+#[derive(Clone, PartialEq)]
+pub struct PyBrokerOrderStatus {
+    #[pyo3(get)]
+    pub id: u64,
+    #[pyo3(get)]
+    pub status: String,
+    #[pyo3(get)]
+    pub filled_quantity: u64,
+    #[pyo3(get)]
+    pub remaining_quantity: u64,
+    #[pyo3(get)]
+    pub avg_fill_price_cents: i64,
+    #[pyo3(get)]
+    pub commission_cents: i64,
+}
+
+#[pymethods]
+impl PyBrokerOrderStatus {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("id", self.id)?;
+        dict.set_item("status", &self.status)?;
+        dict.set_item("filled_quantity", self.filled_quantity)?;
+        dict.set_item("remaining_quantity", self.remaining_quantity)?;
+        dict.set_item("avg_fill_price_cents", self.avg_fill_price_cents)?;
+        dict.set_item("commission_cents", self.commission_cents)?;
+        Ok(dict)
+    }
+
+    fn __getitem__(&self, py: Python<'_>, key: &str) -> PyResult<PyObject> {
+        record_item(self.to_dict(py)?, key)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "BrokerOrderStatus(id={}, status='{}', filled={}, remaining={})",
+            self.id, self.status, self.filled_quantity, self.remaining_quantity,
+        )
+    }
+}
+
+impl From<BrokerOrderStatus> for PyBrokerOrderStatus {
+    fn from(s: BrokerOrderStatus) -> Self {
+        Self {
+            id: s.id.0,
+            status: format!("{:?}", s.status),
+            filled_quantity: s.filled_quantity,
+            remaining_quantity: s.remaining_quantity,
+            avg_fill_price_cents: s.avg_fill_price_cents,
+            commission_cents: s.commission_cents,
+        }
+    }
+}
+
+fn positions(broker: &impl Broker) -> PyResult<Vec<PyBrokerPosition>> {
+    let positions = broker.positions().map_err(broker_err)?;
+    Ok(positions.into_iter().map(Into::into).collect())
+}
+
+fn account(broker: &impl Broker) -> PyResult<PyBrokerAccount> {
+    broker.account().map(Into::into).map_err(broker_err)
+}
+
+fn order_status(broker: &impl Broker, order_id: u64) -> PyResult<PyBrokerOrderStatus> {
+    broker
+        .order_status(OrderId(order_id))
+        .map(Into::into)
+        .map_err(broker_err)
+}
+
+fn cancel_order(broker: &impl Broker, order_id: u64) -> PyResult<()> {
+    broker.cancel_order(OrderId(order_id)).map_err(broker_err)
+}
+
+/// Build a `BrokerOrder` from the Python `submit_order` arguments.
+fn parse_order(
+    symbol: &str,
+    side: &str,
+    quantity: u64,
+    order_type: &str,
+    limit_price_cents: Option<i64>,
+    reduce_only: bool,
+) -> PyResult<BrokerOrder> {
+    let sym = parse_symbol(symbol)?;
+
+    let broker_side = match side.to_ascii_lowercase().as_str() {
+        "buy" | "b" => BrokerSide::Buy,
+        "sell" | "s" => BrokerSide::Sell,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Invalid side '{side}'. Use 'buy' or 'sell'."
+            )));
+        }
+    };
+
+    let broker_order_type = match order_type.to_ascii_lowercase().as_str() {
+        "market" => BrokerOrderType::Market,
+        "limit" => {
+            let price = limit_price_cents.ok_or_else(|| {
+                PyValueError::new_err("limit_price_cents required for limit orders")
+            })?;
+            BrokerOrderType::Limit(nanobook::Price(price))
+        }
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Invalid order_type '{order_type}'. Use 'market' or 'limit'."
+            )));
+        }
+    };
+
+    Ok(BrokerOrder {
+        symbol: sym,
+        side: broker_side,
+        quantity,
+        order_type: broker_order_type,
+        reduce_only,
+        quantity_scaled: None,
+    })
+}
+
+fn quote(broker: &impl Broker, py: Python<'_>, symbol: &str) -> PyResult<PyObject> {
+    let sym = parse_symbol(symbol)?;
+    let quote: Quote = broker.quote(&sym).map_err(broker_err)?;
+
+    let dict = PyDict::new(py);
+    dict.set_item("symbol", quote.symbol.as_str())?;
+    dict.set_item("bid_cents", quote.bid_cents)?;
+    dict.set_item("ask_cents", quote.ask_cents)?;
+    dict.set_item("last_cents", quote.last_cents)?;
+    dict.set_item("volume", quote.volume)?;
+    Ok(dict.into())
+}
+
 /// Interactive Brokers connection.
 ///
 /// Args:
@@ -16,12 +396,13 @@ use crate::types::parse_symbol;
 ///
 /// Example::
 ///
-///     broker = IbkrBroker("127.0.0.1", 4002, 100)
-///     broker.connect()
-///     positions = broker.positions()
-///     broker.disconnect()
+///     with IbkrBroker("127.0.0.1", 4002, 100) as broker:
+///         positions = broker.positions()
+///
+/// The ``with`` block connects on entry and disconnects on exit, even when
+/// the block raises. ``connect()``/``close()`` do the same by hand.
 ///
-#[pyclass(name = "IbkrBroker")]
+#[pyclass(name = "IbkrBroker", subclass)]
 pub struct PyIbkrBroker {
     inner: RustIbkrBroker,
 }
@@ -37,60 +418,38 @@ impl PyIbkrBroker {
 
     /// Connect to IB Gateway/TWS.
     fn connect(&mut self) -> PyResult<()> {
-        self.inner
-            .connect()
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+        self.inner.connect().map_err(broker_err)
     }
 
     /// Disconnect from IB Gateway/TWS.
     fn disconnect(&mut self) -> PyResult<()> {
-        self.inner
-            .disconnect()
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+        self.inner.disconnect().map_err(broker_err)
     }
 
-    /// Get all current positions.
-    ///
-    /// Returns list of dicts with keys: symbol, quantity, avg_cost_cents,
-    /// market_value_cents, unrealized_pnl_cents.
-    fn positions(&self, py: Python<'_>) -> PyResult<PyObject> {
-        let positions = self
-            .inner
-            .positions()
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
-        let list = pyo3::types::PyList::empty(py);
-        for pos in positions {
-            let dict = pyo3::types::PyDict::new(py);
-            dict.set_item("symbol", pos.symbol.as_str())?;
-            dict.set_item("quantity", pos.quantity)?;
-            dict.set_item("avg_cost_cents", pos.avg_cost_cents)?;
-            dict.set_item("market_value_cents", pos.market_value_cents)?;
-            dict.set_item("unrealized_pnl_cents", pos.unrealized_pnl_cents)?;
-            list.append(dict)?;
-        }
-        Ok(list.into())
+    /// Disconnect; safe to call more than once.
+    fn close(&mut self) -> PyResult<()> {
+        self.inner.disconnect().map_err(broker_err)
     }
 
-    /// Get account summary.
-    ///
-    /// Returns dict with keys: equity_cents, buying_power_cents, cash_cents,
-    /// gross_position_value_cents.
-    fn account(&self, py: Python<'_>) -> PyResult<PyObject> {
-        let account = self
-            .inner
-            .account()
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
-        let dict = pyo3::types::PyDict::new(py);
-        dict.set_item("equity_cents", account.equity_cents)?;
-        dict.set_item("buying_power_cents", account.buying_power_cents)?;
-        dict.set_item("cash_cents", account.cash_cents)?;
-        dict.set_item(
-            "gross_position_value_cents",
-            account.gross_position_value_cents,
-        )?;
-        Ok(dict.into())
+    fn __enter__(slf: Bound<'_, Self>) -> PyResult<Bound<'_, Self>> {
+        slf.call_method0("connect")?;
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (*_exc_info))]
+    fn __exit__(slf: &Bound<'_, Self>, _exc_info: &Bound<'_, PyTuple>) -> PyResult<bool> {
+        slf.call_method0("disconnect")?;
+        Ok(false)
+    }
+
+    /// Get all current positions as a list of `BrokerPosition`.
+    fn positions(&self) -> PyResult<Vec<PyBrokerPosition>> {
+        positions(&self.inner)
+    }
+
+    /// Get the account summary as a `BrokerAccount`.
+    fn account(&self) -> PyResult<PyBrokerAccount> {
+        account(&self.inner)
     }
 
     /// Submit an order.
@@ -101,9 +460,12 @@ impl PyIbkrBroker {
     ///     quantity: Number of shares
     ///     order_type: "market" or "limit"
     ///     limit_price_cents: Price in cents (required for limit orders)
+    ///     reduce_only: Only shrink an existing position; the quantity is
+    ///         clamped to the position and orders that would increase
+    ///         exposure raise `OrderError`
     ///
     /// Returns the broker-assigned order ID.
-    #[pyo3(signature = (symbol, side, quantity, order_type="market", limit_price_cents=None))]
+    #[pyo3(signature = (symbol, side, quantity, order_type="market", limit_price_cents=None, reduce_only=false))]
     fn submit_order(
         &self,
         symbol: &str,
@@ -111,96 +473,35 @@ impl PyIbkrBroker {
         quantity: u64,
         order_type: &str,
         limit_price_cents: Option<i64>,
+        reduce_only: bool,
     ) -> PyResult<u64> {
-        let sym = parse_symbol(symbol)?;
-
-        let broker_side = match side.to_ascii_lowercase().as_str() {
-            "buy" | "b" => nanobook_broker::BrokerSide::Buy,
-            "sell" | "s" => nanobook_broker::BrokerSide::Sell,
-            _ => {
-                return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                    "Invalid side '{side}'. Use 'buy' or 'sell'."
-                )));
-            }
-        };
-
-        let broker_order_type = match order_type.to_ascii_lowercase().as_str() {
-            "market" => nanobook_broker::BrokerOrderType::Market,
-            "limit" => {
-                let price = limit_price_cents.ok_or_else(|| {
-                    pyo3::exceptions::PyValueError::new_err(
-                        "limit_price_cents required for limit orders",
-                    )
-                })?;
-                nanobook_broker::BrokerOrderType::Limit(nanobook::Price(price))
-            }
-            _ => {
-                return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                    "Invalid order_type '{order_type}'. Use 'market' or 'limit'."
-                )));
-            }
-        };
-
-        let order = nanobook_broker::BrokerOrder {
-            symbol: sym,
-            side: broker_side,
+        let order = parse_order(
+            symbol,
+            side,
             quantity,
-            order_type: broker_order_type,
-        };
-
-        let id = self
-            .inner
-            .submit_order(&order)
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
+            order_type,
+            limit_price_cents,
+            reduce_only,
+        )?;
+        let id = self.inner.submit_order(&order).map_err(broker_err)?;
         Ok(id.0)
     }
 
-    /// Get status of a submitted order.
-    ///
-    /// Returns dict with keys: id, status, filled_quantity,
-    /// remaining_quantity, avg_fill_price_cents.
-    fn order_status(&self, py: Python<'_>, order_id: u64) -> PyResult<PyObject> {
-        let id = nanobook_broker::OrderId(order_id);
-        let status = self
-            .inner
-            .order_status(id)
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
-        let dict = pyo3::types::PyDict::new(py);
-        dict.set_item("id", status.id.0)?;
-        dict.set_item("status", format!("{:?}", status.status))?;
-        dict.set_item("filled_quantity", status.filled_quantity)?;
-        dict.set_item("remaining_quantity", status.remaining_quantity)?;
-        dict.set_item("avg_fill_price_cents", status.avg_fill_price_cents)?;
-        Ok(dict.into())
+    /// Get the status of an order as a `BrokerOrderStatus`.
+    fn order_status(&self, order_id: u64) -> PyResult<PyBrokerOrderStatus> {
+        order_status(&self.inner, order_id)
     }
 
     /// Cancel a pending order.
     fn cancel_order(&self, order_id: u64) -> PyResult<()> {
-        let id = nanobook_broker::OrderId(order_id);
-        self.inner
-            .cancel_order(id)
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+        cancel_order(&self.inner, order_id)
     }
 
     /// Get current quote for a symbol.
     ///
     /// Returns dict with keys: symbol, bid_cents, ask_cents, last_cents, volume.
     fn quote(&self, py: Python<'_>, symbol: &str) -> PyResult<PyObject> {
-        let sym = parse_symbol(symbol)?;
-        let quote = self
-            .inner
-            .quote(&sym)
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
-        let dict = pyo3::types::PyDict::new(py);
-        dict.set_item("symbol", quote.symbol.as_str())?;
-        dict.set_item("bid_cents", quote.bid_cents)?;
-        dict.set_item("ask_cents", quote.ask_cents)?;
-        dict.set_item("last_cents", quote.last_cents)?;
-        dict.set_item("volume", quote.volume)?;
-        Ok(dict.into())
+        quote(&self.inner, py, symbol)
     }
 
     fn __repr__(&self) -> String {
@@ -208,6 +509,138 @@ impl PyIbkrBroker {
     }
 }
 
+/// In-memory broker backed by `nanobook_broker::mock::MockBroker`.
+///
+/// Lets Python rebalancers and tests run against the same adapter surface
+/// as the real brokers without a network connection.
+///
+/// Args:
+///     positions: ``(symbol, quantity, avg_cost_cents)`` tuples
+///     quotes: ``(symbol, bid_cents, ask_cents)`` tuples
+///     equity_cents: Reported account equity (default $1M)
+///     cash_cents: Reported cash and buying power (default $1M)
+///     reject_orders: Reject every submitted order if True
+///
+#[pyclass(name = "MockBroker", subclass)]
+pub struct PyMockBroker {
+    inner: RustMockBroker,
+}
+
+#[pymethods]
+impl PyMockBroker {
+    #[new]
+    #[pyo3(signature = (positions=Vec::new(), quotes=Vec::new(), equity_cents=100_000_000, cash_cents=100_000_000, reject_orders=false))]
+    fn new(
+        positions: Vec<(String, i64, i64)>,
+        quotes: Vec<(String, i64, i64)>,
+        equity_cents: i64,
+        cash_cents: i64,
+        reject_orders: bool,
+    ) -> PyResult<Self> {
+        let mut builder = RustMockBroker::builder()
+            .with_account(equity_cents, cash_cents)
+            .fill_mode(if reject_orders {
+                FillMode::Reject
+            } else {
+                FillMode::ImmediateFull
+            });
+        for (symbol, quantity, avg_cost_cents) in positions {
+            builder = builder.with_position(parse_symbol(&symbol)?, quantity, avg_cost_cents);
+        }
+        for (symbol, bid_cents, ask_cents) in quotes {
+            builder = builder.with_quote(parse_symbol(&symbol)?, bid_cents, ask_cents);
+        }
+        Ok(Self {
+            inner: builder.build(),
+        })
+    }
+
+    fn connect(&mut self) -> PyResult<()> {
+        self.inner.connect().map_err(broker_err)
+    }
+
+    fn disconnect(&mut self) -> PyResult<()> {
+        self.inner.disconnect().map_err(broker_err)
+    }
+
+    /// Disconnect; safe to call more than once.
+    fn close(&mut self) -> PyResult<()> {
+        self.inner.disconnect().map_err(broker_err)
+    }
+
+    fn __enter__(slf: Bound<'_, Self>) -> PyResult<Bound<'_, Self>> {
+        slf.call_method0("connect")?;
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (*_exc_info))]
+    fn __exit__(slf: &Bound<'_, Self>, _exc_info: &Bound<'_, PyTuple>) -> PyResult<bool> {
+        slf.call_method0("disconnect")?;
+        Ok(false)
+    }
+
+    fn positions(&self) -> PyResult<Vec<PyBrokerPosition>> {
+        positions(&self.inner)
+    }
+
+    fn account(&self) -> PyResult<PyBrokerAccount> {
+        account(&self.inner)
+    }
+
+    #[pyo3(signature = (symbol, side, quantity, order_type="market", limit_price_cents=None, reduce_only=false))]
+    fn submit_order(
+        &self,
+        symbol: &str,
+        side: &str,
+        quantity: u64,
+        order_type: &str,
+        limit_price_cents: Option<i64>,
+        reduce_only: bool,
+    ) -> PyResult<u64> {
+        let order = parse_order(
+            symbol,
+            side,
+            quantity,
+            order_type,
+            limit_price_cents,
+            reduce_only,
+        )?;
+        let id = self.inner.submit_order(&order).map_err(broker_err)?;
+        Ok(id.0)
+    }
+
+    fn order_status(&self, order_id: u64) -> PyResult<PyBrokerOrderStatus> {
+        order_status(&self.inner, order_id)
+    }
+
+    fn cancel_order(&self, order_id: u64) -> PyResult<()> {
+        cancel_order(&self.inner, order_id)
+    }
+
+    fn quote(&self, py: Python<'_>, symbol: &str) -> PyResult<PyObject> {
+        quote(&self.inner, py, symbol)
+    }
+
+    /// Orders submitted so far, as ``(symbol, side, quantity)`` tuples.
+    fn submitted_orders(&self) -> Vec<(String, &'static str, u64)> {
+        self.inner
+            .submitted_orders()
+            .into_iter()
+            .map(|o| {
+                let side = match o.side {
+                    BrokerSide::Buy => "buy",
+                    BrokerSide::Sell => "sell",
+                };
+                (o.symbol.as_str().to_string(), side, o.quantity)
+            })
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        "MockBroker(...)".to_string()
+    }
+}
+
 #[cfg(feature = "binance")]
 mod binance_binding {
     use super::*;
@@ -221,12 +654,10 @@ mod binance_binding {
     ///
     /// Example::
     ///
-    ///     broker = BinanceBroker("key", "secret", testnet=True)
-    ///     broker.connect()
-    ///     quote = broker.quote("BTC")
-    ///     broker.disconnect()
+    ///     with BinanceBroker("key", "secret", testnet=True) as broker:
+    ///         quote = broker.quote("BTC")
     ///
-    #[pyclass(name = "BinanceBroker")]
+    #[pyclass(name = "BinanceBroker", subclass)]
     pub struct PyBinanceBroker {
         inner: nanobook_broker::binance::BinanceBroker,
     }
@@ -244,58 +675,42 @@ mod binance_binding {
 
         /// Connect to Binance (sends a ping).
         fn connect(&mut self) -> PyResult<()> {
-            self.inner
-                .connect()
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+            self.inner.connect().map_err(broker_err)
         }
 
         /// Disconnect (no-op for REST, clears internal client).
         fn disconnect(&mut self) -> PyResult<()> {
-            self.inner
-                .disconnect()
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+            self.inner.disconnect().map_err(broker_err)
         }
 
-        /// Get all positions (non-zero balances).
-        fn positions(&self, py: Python<'_>) -> PyResult<PyObject> {
-            let positions = self
-                .inner
-                .positions()
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
-            let list = pyo3::types::PyList::empty(py);
-            for pos in positions {
-                let dict = pyo3::types::PyDict::new(py);
-                dict.set_item("symbol", pos.symbol.as_str())?;
-                dict.set_item("quantity", pos.quantity)?;
-                dict.set_item("avg_cost_cents", pos.avg_cost_cents)?;
-                dict.set_item("market_value_cents", pos.market_value_cents)?;
-                dict.set_item("unrealized_pnl_cents", pos.unrealized_pnl_cents)?;
-                list.append(dict)?;
-            }
-            Ok(list.into())
+        /// Disconnect; safe to call more than once.
+        fn close(&mut self) -> PyResult<()> {
+            self.inner.disconnect().map_err(broker_err)
         }
 
-        /// Get account summary (USDT balance).
-        fn account(&self, py: Python<'_>) -> PyResult<PyObject> {
-            let account = self
-                .inner
-                .account()
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
-            let dict = pyo3::types::PyDict::new(py);
-            dict.set_item("equity_cents", account.equity_cents)?;
-            dict.set_item("buying_power_cents", account.buying_power_cents)?;
-            dict.set_item("cash_cents", account.cash_cents)?;
-            dict.set_item(
-                "gross_position_value_cents",
-                account.gross_position_value_cents,
-            )?;
-            Ok(dict.into())
+        fn __enter__(slf: Bound<'_, Self>) -> PyResult<Bound<'_, Self>> {
+            slf.call_method0("connect")?;
+            Ok(slf)
+        }
+
+        #[pyo3(signature = (*_exc_info))]
+        fn __exit__(slf: &Bound<'_, Self>, _exc_info: &Bound<'_, PyTuple>) -> PyResult<bool> {
+            slf.call_method0("disconnect")?;
+            Ok(false)
+        }
+
+        /// Get all positions (non-zero balances) as `BrokerPosition`s.
+        fn positions(&self) -> PyResult<Vec<PyBrokerPosition>> {
+            positions(&self.inner)
+        }
+
+        /// Get account summary (quote-asset balance) as a `BrokerAccount`.
+        fn account(&self) -> PyResult<PyBrokerAccount> {
+            account(&self.inner)
         }
 
         /// Submit an order.
-        #[pyo3(signature = (symbol, side, quantity, order_type="market", limit_price_cents=None))]
+        #[pyo3(signature = (symbol, side, quantity, order_type="market", limit_price_cents=None, reduce_only=false))]
         fn submit_order(
             &self,
             symbol: &str,
@@ -303,91 +718,41 @@ mod binance_binding {
             quantity: u64,
             order_type: &str,
             limit_price_cents: Option<i64>,
+            reduce_only: bool,
         ) -> PyResult<u64> {
-            let sym = parse_symbol(symbol)?;
-
-            let broker_side = match side.to_ascii_lowercase().as_str() {
-                "buy" | "b" => nanobook_broker::BrokerSide::Buy,
-                "sell" | "s" => nanobook_broker::BrokerSide::Sell,
-                _ => {
-                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                        "Invalid side '{side}'. Use 'buy' or 'sell'."
-                    )));
-                }
-            };
-
-            let broker_order_type = match order_type.to_ascii_lowercase().as_str() {
-                "market" => nanobook_broker::BrokerOrderType::Market,
-                "limit" => {
-                    let price = limit_price_cents.ok_or_else(|| {
-                        pyo3::exceptions::PyValueError::new_err(
-                            "limit_price_cents required for limit orders",
-                        )
-                    })?;
-                    nanobook_broker::BrokerOrderType::Limit(nanobook::Price(price))
-                }
-                _ => {
-                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                        "Invalid order_type '{order_type}'. Use 'market' or 'limit'."
-                    )));
-                }
-            };
-
-            let order = nanobook_broker::BrokerOrder {
-                symbol: sym,
-                side: broker_side,
+            let order = parse_order(
+                symbol,
+                side,
                 quantity,
-                order_type: broker_order_type,
-            };
-
-            let id = self
-                .inner
-                .submit_order(&order)
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
+                order_type,
+                limit_price_cents,
+                reduce_only,
+            )?;
+            let id = self.inner.submit_order(&order).map_err(broker_err)?;
             Ok(id.0)
         }
 
-        /// Get order status.
-        fn order_status(&self, py: Python<'_>, order_id: u64) -> PyResult<PyObject> {
-            let id = nanobook_broker::OrderId(order_id);
-            let status = self
-                .inner
-                .order_status(id)
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
-            let dict = pyo3::types::PyDict::new(py);
-            dict.set_item("id", status.id.0)?;
-            dict.set_item("status", format!("{:?}", status.status))?;
-            dict.set_item("filled_quantity", status.filled_quantity)?;
-            dict.set_item("remaining_quantity", status.remaining_quantity)?;
-            dict.set_item("avg_fill_price_cents", status.avg_fill_price_cents)?;
-            Ok(dict.into())
+        /// Get order status as a `BrokerOrderStatus`.
+        fn order_status(&self, order_id: u64) -> PyResult<PyBrokerOrderStatus> {
+            order_status(&self.inner, order_id)
         }
 
         /// Cancel an order.
         fn cancel_order(&self, order_id: u64) -> PyResult<()> {
-            let id = nanobook_broker::OrderId(order_id);
+            cancel_order(&self.inner, order_id)
+        }
+
+        /// Cancel an order by symbol, for orders not submitted through this handle.
+        fn cancel_order_with_symbol(&self, symbol: &str, order_id: u64) -> PyResult<()> {
+            let sym = parse_symbol(symbol)?;
             self.inner
-                .cancel_order(id)
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+                .cancel_order_with_symbol(&sym, OrderId(order_id))
+                .map_err(broker_err)
         }
 
         /// Get current quote for a symbol (e.g., "BTC").
         fn quote(&self, py: Python<'_>, symbol: &str) -> PyResult<PyObject> {
-            let sym = parse_symbol(symbol)?;
-            let quote = self
-                .inner
-                .quote(&sym)
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
-            let dict = pyo3::types::PyDict::new(py);
-            dict.set_item("symbol", quote.symbol.as_str())?;
-            dict.set_item("bid_cents", quote.bid_cents)?;
-            dict.set_item("ask_cents", quote.ask_cents)?;
-            dict.set_item("last_cents", quote.last_cents)?;
-            dict.set_item("volume", quote.volume)?;
-            Ok(dict.into())
+            quote(&self.inner, py, symbol)
         }
 
         fn __repr__(&self) -> String {
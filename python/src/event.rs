@@ -17,10 +17,24 @@ impl PyEvent {
             Event::SubmitMarket { .. } => "submit_market".to_string(),
             Event::Cancel { .. } => "cancel".to_string(),
             Event::Modify { .. } => "modify".to_string(),
+            Event::ReduceQuantity { .. } => "reduce_quantity".to_string(),
+            Event::CancelPartial { .. } => "cancel_partial".to_string(),
             Event::SubmitStopMarket { .. } => "submit_stop_market".to_string(),
             Event::SubmitStopLimit { .. } => "submit_stop_limit".to_string(),
             Event::SubmitTrailingStopMarket { .. } => "submit_trailing_stop_market".to_string(),
             Event::SubmitTrailingStopLimit { .. } => "submit_trailing_stop_limit".to_string(),
+            Event::BeginAuction => "begin_auction".to_string(),
+            Event::RunAuction => "run_auction".to_string(),
+            Event::SubmitStopMarketCapped { .. } => "submit_stop_market_capped".to_string(),
+            Event::SubmitMit { .. } => "submit_mit".to_string(),
+            Event::SubmitLimitDelayed { .. } => "submit_limit_delayed".to_string(),
+            Event::AdvanceTo { .. } => "advance_to".to_string(),
+            Event::SubmitTrailingStopLimitOffset { .. } => {
+                "submit_trailing_stop_limit_offset".to_string()
+            }
+            Event::SetIdOffsets { .. } => "set_id_offsets".to_string(),
+            Event::SubmitBatch { .. } => "submit_batch".to_string(),
+            Event::SubmitMarketFok { .. } => "submit_market_fok".to_string(),
         }
     }
 
@@ -1,4 +1,6 @@
-use nanobook::{Event, Exchange, OrderId, Price, TrailMethod};
+use nanobook::{
+    ConditionalKind, Event, Exchange, ModifyPolicy, OrderId, Price, TradePriceRule, TrailMethod,
+};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
@@ -33,11 +35,25 @@ impl PyExchange {
 
 #[pymethods]
 impl PyExchange {
+    /// Args:
+    ///     trade_price_rule: Trade print price: "resting" (default),
+    ///         "aggressor", or "midpoint"
+    ///     order_offset, trade_offset, ts_offset: IDs and timestamps start
+    ///         just above these (for disjoint ranges across shards)
     #[new]
-    fn new() -> Self {
-        Self {
-            inner: Exchange::new(),
+    #[pyo3(signature = (trade_price_rule="resting", order_offset=0, trade_offset=0, ts_offset=0))]
+    fn new(
+        trade_price_rule: &str,
+        order_offset: u64,
+        trade_offset: u64,
+        ts_offset: u64,
+    ) -> PyResult<Self> {
+        let rule = parse_trade_price_rule(trade_price_rule)?;
+        let mut inner = Exchange::with_trade_price_rule(rule);
+        if order_offset > 0 || trade_offset > 0 || ts_offset > 0 {
+            inner.set_id_offsets(order_offset, trade_offset, ts_offset);
         }
+        Ok(Self { inner })
     }
 
     /// Replay events to reconstruct exchange state.
@@ -49,6 +65,17 @@ impl PyExchange {
         }
     }
 
+    /// Replay events, checking book invariants after each one.
+    ///
+    /// Raises ValueError naming the first offending event index.
+    #[staticmethod]
+    fn replay_checked(events: Vec<PyEvent>) -> PyResult<Self> {
+        let inner_events: Vec<Event> = events.into_iter().map(|e| e.inner).collect();
+        let inner = Exchange::replay_checked(&inner_events)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { inner })
+    }
+
     // === Order Submission ===
 
     /// Submit a limit order.
@@ -77,6 +104,31 @@ impl PyExchange {
             .into())
     }
 
+    /// Submit limit orders in one call, as if by repeated ``submit_limit``.
+    ///
+    /// Args:
+    ///     orders: List of ``(side, price, quantity, tif)`` tuples
+    ///
+    /// Returns:
+    ///     One SubmitResult per order, in order
+    fn submit_batch(
+        &mut self,
+        orders: Vec<(String, i64, u64, String)>,
+    ) -> PyResult<Vec<PySubmitResult>> {
+        let orders = orders
+            .iter()
+            .map(|(side, price, quantity, tif)| {
+                Ok((parse_side(side)?, Price(*price), *quantity, parse_tif(tif)?))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(self
+            .inner
+            .submit_batch(&orders)
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
     /// Submit a market order.
     ///
     /// Args:
@@ -90,6 +142,55 @@ impl PyExchange {
         Ok(self.inner.submit_market(side, quantity).into())
     }
 
+    /// Submit a fill-or-kill market order.
+    ///
+    /// Rejected with no trades (status "Cancelled") unless the book can
+    /// fill the whole quantity.
+    fn submit_market_fok(&mut self, side: &str, quantity: u64) -> PyResult<PySubmitResult> {
+        let side = parse_side(side)?;
+        Ok(self.inner.submit_market_fok(side, quantity).into())
+    }
+
+    // === Latency Simulation ===
+
+    /// Queue a limit order that reaches the matching engine at simulated
+    /// time `arrival`; it matches once `advance_to` reaches that time.
+    #[pyo3(signature = (side, price, quantity, arrival, tif="gtc"))]
+    fn submit_limit_delayed(
+        &mut self,
+        side: &str,
+        price: i64,
+        quantity: u64,
+        arrival: u64,
+        tif: &str,
+    ) -> PyResult<()> {
+        let side = parse_side(side)?;
+        let tif = parse_tif(tif)?;
+        self.inner
+            .submit_limit_delayed(side, Price(price), quantity, tif, arrival);
+        Ok(())
+    }
+
+    /// Advance the simulation clock, processing arrived delayed orders in
+    /// arrival order. Returns one SubmitResult per processed order.
+    fn advance_to(&mut self, timestamp: u64) -> Vec<PySubmitResult> {
+        self.inner
+            .advance_to(timestamp)
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    /// Current simulation clock.
+    fn sim_time(&self) -> u64 {
+        self.inner.sim_time()
+    }
+
+    /// Number of delayed orders that have not arrived yet.
+    fn pending_delayed_count(&self) -> usize {
+        self.inner.pending_delayed_count()
+    }
+
     // === Order Management ===
 
     /// Cancel an order by ID.
@@ -99,10 +200,52 @@ impl PyExchange {
 
     /// Modify an order (cancel and replace).
     ///
-    /// The new order gets a new ID and loses time priority.
-    fn modify(&mut self, order_id: u64, new_price: i64, new_quantity: u64) -> PyModifyResult {
+    /// The new order gets a new ID and loses time priority. With
+    /// `preserve_priority=True`, a size reduction at the same price is
+    /// applied in place instead and keeps the order's ID and queue position
+    /// (see `ModifyResult.priority_kept`).
+    #[pyo3(signature = (order_id, new_price, new_quantity, preserve_priority=false))]
+    fn modify(
+        &mut self,
+        order_id: u64,
+        new_price: i64,
+        new_quantity: u64,
+        preserve_priority: bool,
+    ) -> PyModifyResult {
+        let policy = if preserve_priority {
+            ModifyPolicy::PreservePriorityOnReduce
+        } else {
+            ModifyPolicy::AlwaysCancelReplace
+        };
+        self.inner
+            .modify_with_policy(OrderId(order_id), Price(new_price), new_quantity, policy)
+            .into()
+    }
+
+    /// Cancel part of an order, keeping priority on the rest.
+    ///
+    /// Cancelling the whole remaining quantity (or more) is a full cancel.
+    fn cancel_partial(&mut self, order_id: u64, quantity: u64) -> PyCancelResult {
+        self.inner
+            .cancel_partial(OrderId(order_id), quantity)
+            .into()
+    }
+
+    /// Cancel every resting order with a timestamp before `older_than`.
+    ///
+    /// Returns the cancelled order IDs in submission order.
+    fn cancel_stale(&mut self, older_than: u64) -> Vec<u64> {
+        self.inner
+            .cancel_stale(older_than)
+            .into_iter()
+            .map(|id| id.0)
+            .collect()
+    }
+
+    /// Reduce a resting order's remaining quantity in place, keeping priority.
+    fn reduce_quantity(&mut self, order_id: u64, new_quantity: u64) -> PyModifyResult {
         self.inner
-            .modify(OrderId(order_id), Price(new_price), new_quantity)
+            .reduce_quantity(OrderId(order_id), new_quantity)
             .into()
     }
 
@@ -111,16 +254,44 @@ impl PyExchange {
     /// Submit a stop-market order.
     ///
     /// Triggers when trade price reaches stop_price, then becomes a market order.
+    /// With `max_slippage` (cents), it becomes an IOC limit at
+    /// `stop_price ± max_slippage` instead, and any unfilled rest is cancelled.
+    #[pyo3(signature = (side, stop_price, quantity, max_slippage=None))]
     fn submit_stop_market(
         &mut self,
         side: &str,
         stop_price: i64,
         quantity: u64,
+        max_slippage: Option<i64>,
+    ) -> PyResult<PyStopSubmitResult> {
+        let side = parse_side(side)?;
+        let result = match max_slippage {
+            Some(cap) => {
+                self.inner
+                    .submit_stop_market_capped(side, Price(stop_price), quantity, cap)
+            }
+            None => self
+                .inner
+                .submit_stop_market(side, Price(stop_price), quantity),
+        };
+        Ok(result.into())
+    }
+
+    /// Submit a market-if-touched order.
+    ///
+    /// Unlike a stop, it triggers on a favorable move: a buy MIT when the
+    /// trade price falls to or below `trigger_price`, a sell MIT when it
+    /// rises to or above. It then becomes a market order.
+    fn submit_mit(
+        &mut self,
+        side: &str,
+        trigger_price: i64,
+        quantity: u64,
     ) -> PyResult<PyStopSubmitResult> {
         let side = parse_side(side)?;
         Ok(self
             .inner
-            .submit_stop_market(side, Price(stop_price), quantity)
+            .submit_mit(side, Price(trigger_price), quantity)
             .into())
     }
 
@@ -199,6 +370,37 @@ impl PyExchange {
             .into())
     }
 
+    /// Submit a trailing stop-limit order whose limit trails `limit_offset`
+    /// cents beyond the stop.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (side, initial_stop_price, limit_offset, quantity, trail_type, trail_value, tif="gtc", atr_period=None))]
+    fn submit_trailing_stop_limit_offset(
+        &mut self,
+        side: &str,
+        initial_stop_price: i64,
+        limit_offset: i64,
+        quantity: u64,
+        trail_type: &str,
+        trail_value: f64,
+        tif: &str,
+        atr_period: Option<usize>,
+    ) -> PyResult<PyStopSubmitResult> {
+        let side = parse_side(side)?;
+        let tif = parse_tif(tif)?;
+        let method = parse_trail_method(trail_type, trail_value, atr_period)?;
+        Ok(self
+            .inner
+            .submit_trailing_stop_limit_offset(
+                side,
+                Price(initial_stop_price),
+                limit_offset,
+                quantity,
+                tif,
+                method,
+            )
+            .into())
+    }
+
     // === Queries ===
 
     /// Get an order by ID.
@@ -208,6 +410,21 @@ impl PyExchange {
             .map(|o| PyOrder { inner: o.clone() })
     }
 
+    /// Fills of an order as (trade_id, price, quantity, timestamp), or None
+    /// if the order is unknown or was cleared from history.
+    fn order_executions(&self, order_id: u64) -> Option<Vec<(u64, i64, u64, u64)>> {
+        self.inner
+            .order_executions(OrderId(order_id))
+            .map(|executions| {
+                executions
+                    .iter()
+                    .map(|&(trade_id, price, quantity, timestamp)| {
+                        (trade_id.0, price.0, quantity, timestamp)
+                    })
+                    .collect()
+            })
+    }
+
     /// Get a stop order by ID.
     fn get_stop_order(&self, py: Python<'_>, order_id: u64) -> PyResult<Option<PyObject>> {
         if let Some(stop) = self.inner.get_stop_order(OrderId(order_id)) {
@@ -215,6 +432,11 @@ impl PyExchange {
             dict.set_item("id", stop.id.0)?;
             dict.set_item("side", side_str(stop.side))?;
             dict.set_item("stop_price", stop.stop_price.0)?;
+            let kind = match stop.kind {
+                ConditionalKind::Stop => "stop",
+                ConditionalKind::IfTouched => "if_touched",
+            };
+            dict.set_item("kind", kind)?;
             dict.set_item("limit_price", stop.limit_price.map(|p| p.0))?;
             dict.set_item("quantity", stop.quantity)?;
             dict.set_item("status", format!("{:?}", stop.status).to_lowercase())?;
@@ -247,6 +469,24 @@ impl PyExchange {
         self.inner.spread()
     }
 
+    // === Auctions ===
+
+    /// Start an auction call phase: GTC limit orders rest without matching
+    /// until `run_auction`. Market, IOC and FOK orders are cancelled.
+    fn begin_auction(&mut self) {
+        self.inner.begin_auction();
+    }
+
+    /// Whether the exchange is in an auction call phase.
+    fn in_auction(&self) -> bool {
+        self.inner.in_auction()
+    }
+
+    /// Uncross the book at a single clearing price and resume continuous trading.
+    fn run_auction(&mut self) -> PyAuctionResult {
+        self.inner.run_auction().into()
+    }
+
     /// Get the last trade price, or None.
     fn last_trade_price(&self) -> Option<i64> {
         self.inner.last_trade_price().map(|p| p.0)
@@ -262,6 +502,26 @@ impl PyExchange {
             .collect()
     }
 
+    /// Call `callback(trade)` for every trade as it happens, including trades
+    /// from triggered stops. Pass None to remove it.
+    ///
+    /// The callback must not call back into this exchange. Exceptions it
+    /// raises are reported as unraisable and do not stop matching.
+    #[pyo3(signature = (callback))]
+    fn set_trade_observer(&mut self, callback: Option<PyObject>) {
+        let Some(callback) = callback else {
+            self.inner.take_trade_observer();
+            return;
+        };
+        self.inner.set_trade_observer(Box::new(move |trade| {
+            Python::with_gil(|py| {
+                if let Err(err) = callback.call1(py, (PyTrade::from(trade.clone()),)) {
+                    err.write_unraisable(py, Some(callback.bind(py)));
+                }
+            });
+        }));
+    }
+
     /// Get recorded events.
     fn events(&self) -> Vec<PyEvent> {
         self.inner
@@ -272,6 +532,27 @@ impl PyExchange {
             .collect()
     }
 
+    /// Trade history as a CSV string (header plus one row per trade).
+    ///
+    /// Columns: trade_id, timestamp, price, quantity, aggressor_side,
+    /// maker_order_id, taker_order_id. Prices are formatted as dollars.
+    fn trades_to_csv(&self) -> PyResult<String> {
+        let mut buf = Vec::new();
+        self.inner
+            .trades_to_csv(&mut buf)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        String::from_utf8(buf).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Event log as a CSV string (header plus one row per event).
+    fn events_to_csv(&self) -> PyResult<String> {
+        let mut buf = Vec::new();
+        self.inner
+            .events_to_csv(&mut buf)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        String::from_utf8(buf).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
     /// Get a depth snapshot of the book (top N levels each side).
     #[pyo3(signature = (levels=10))]
     fn depth(&self, levels: usize) -> PyBookSnapshot {
@@ -285,11 +566,110 @@ impl PyExchange {
         PyBookSnapshot::from_snapshot(&snap)
     }
 
+    /// Live orders at a price level in queue priority order.
+    ///
+    /// Returns a list of (order_id, remaining_quantity, timestamp) tuples;
+    /// empty if there is no level at that price.
+    fn level_orders(&self, side: &str, price: i64) -> PyResult<Vec<(u64, u64, u64)>> {
+        let side = parse_side(side)?;
+        Ok(self
+            .inner
+            .level_orders(side, Price(price))
+            .into_iter()
+            .map(|(id, quantity, timestamp)| (id.0, quantity, timestamp))
+            .collect())
+    }
+
+    /// Quantity resting ahead of an order at its level, or None if the
+    /// order is not resting.
+    fn queue_ahead(&self, order_id: u64) -> Option<u64> {
+        self.inner.queue_ahead(OrderId(order_id))
+    }
+
+    /// Probability (0.0 or 1.0 in this deterministic model) that a resting
+    /// order fully fills within `horizon` at `volume_rate` shares per unit
+    /// time; None if the order is not resting.
+    fn fill_probability(&self, order_id: u64, volume_rate: f64, horizon: f64) -> Option<f64> {
+        self.inner
+            .fill_probability(OrderId(order_id), volume_rate, horizon)
+    }
+
+    /// CRC-32 of the top `depth` levels per side (see BookSnapshot.checksum).
+    #[pyo3(signature = (depth=10))]
+    fn book_checksum(&self, depth: usize) -> u32 {
+        self.inner.book_checksum(depth)
+    }
+
+    /// Total quantity matched since creation (kept by `clear_trades`).
+    fn total_matched_quantity(&self) -> u64 {
+        self.inner.total_matched_quantity()
+    }
+
+    /// Total matched notional in cents since creation (kept by `clear_trades`).
+    fn total_matched_notional(&self) -> i128 {
+        self.inner.total_matched_notional()
+    }
+
+    /// Number of trades since creation (kept by `clear_trades`).
+    fn trade_count(&self) -> u64 {
+        self.inner.trade_count()
+    }
+
+    /// Price improvement and trade-through counts since creation (kept by
+    /// `clear_trades`), as a dict.
+    fn execution_quality(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let stats = self.inner.execution_quality();
+        let dict = PyDict::new(py);
+        dict.set_item("limit_trades", stats.limit_trades)?;
+        dict.set_item("limit_quantity", stats.limit_quantity)?;
+        dict.set_item("improved_trades", stats.improved_trades)?;
+        dict.set_item("improved_quantity", stats.improved_quantity)?;
+        dict.set_item("improvement_cents", stats.improvement_cents)?;
+        dict.set_item("trade_throughs", stats.trade_throughs)?;
+        dict.set_item("market_trades", stats.market_trades)?;
+        dict.set_item("improvement_per_unit", stats.improvement_per_unit())?;
+        Ok(dict.into())
+    }
+
     /// Number of pending stop orders.
     fn pending_stop_count(&self) -> usize {
         self.inner.pending_stop_count()
     }
 
+    /// Limit how many rounds of stop triggers one trade can set off (at least 1).
+    fn set_max_cascade_depth(&mut self, depth: usize) {
+        self.inner.set_max_cascade_depth(depth);
+    }
+
+    /// Current limit on stop-trigger rounds per trade.
+    fn max_cascade_depth(&self) -> usize {
+        self.inner.max_cascade_depth()
+    }
+
+    /// Number of stop cascades cut off at the depth limit since creation.
+    fn truncated_cascades(&self) -> u64 {
+        self.inner.truncated_cascades()
+    }
+
+    // === Imbalance Recording ===
+
+    /// Record top-of-book imbalance whenever the best level changes,
+    /// keeping the latest `capacity` samples.
+    #[pyo3(signature = (capacity=10_000))]
+    fn enable_imbalance_recording(&mut self, capacity: usize) {
+        self.inner.enable_imbalance_recording(capacity);
+    }
+
+    /// Stop recording imbalance and drop the samples.
+    fn disable_imbalance_recording(&mut self) {
+        self.inner.disable_imbalance_recording();
+    }
+
+    /// Recorded `(timestamp, imbalance)` samples, oldest first.
+    fn imbalance_history(&self) -> Vec<(u64, f64)> {
+        self.inner.imbalance_history().collect()
+    }
+
     // === Memory Management ===
 
     /// Clear trade history to free memory.
@@ -362,6 +742,21 @@ impl PyBookSnapshot {
         self.inner.spread()
     }
 
+    /// Merge levels into ``bin_width``-cent price buckets.
+    ///
+    /// Bids round down and asks round up to multiples of the width, so the
+    /// spread is never crossed. A width of 1 or less returns the same levels.
+    fn aggregate_levels(&self, bin_width: i64) -> PyBookSnapshot {
+        PyBookSnapshot::from_snapshot(&self.inner.aggregate_levels(bin_width))
+    }
+
+    /// CRC-32 of the top `depth` levels: `price:quantity;` per level in
+    /// cents, asks then bids (best first), separated by `|`.
+    #[pyo3(signature = (depth=10))]
+    fn checksum(&self, depth: usize) -> u32 {
+        self.inner.checksum(depth)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "BookSnapshot(bids={}, asks={})",
@@ -380,6 +775,8 @@ impl PyBookSnapshot {
                     price: l.price.0,
                     quantity: l.quantity,
                     order_count: l.order_count,
+                    oldest_timestamp: l.oldest_timestamp,
+                    newest_timestamp: l.newest_timestamp,
                 })
                 .collect()
         }
@@ -392,6 +789,18 @@ impl PyBookSnapshot {
     }
 }
 
+/// Parse a trade price rule name ("resting", "aggressor", "midpoint").
+fn parse_trade_price_rule(name: &str) -> PyResult<TradePriceRule> {
+    match name.to_ascii_lowercase().as_str() {
+        "resting" => Ok(TradePriceRule::RestingPrice),
+        "aggressor" => Ok(TradePriceRule::AggressorPrice),
+        "midpoint" | "mid" => Ok(TradePriceRule::Midpoint),
+        _ => Err(PyValueError::new_err(format!(
+            "Invalid trade_price_rule '{name}'. Use 'resting', 'aggressor', or 'midpoint'."
+        ))),
+    }
+}
+
 /// Parse trail method from Python arguments.
 fn parse_trail_method(
     trail_type: &str,
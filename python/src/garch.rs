@@ -1,5 +1,7 @@
 use nanobook::garch;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 
 /// One-step-ahead GARCH-style volatility forecast.
 ///
@@ -22,3 +24,104 @@ pub fn garch_forecast(returns: Vec<f64>, p: usize, q: usize, mean: String) -> f6
 pub fn py_garch_forecast(returns: Vec<f64>, p: usize, q: usize, mean: String) -> f64 {
     garch_forecast(returns, p, q, mean)
 }
+
+/// Multi-step GJR-GARCH(1,1,1) conditional variance forecast.
+///
+/// Args:
+///     returns: Return series as decimal fractions.
+///     horizon: Number of steps to forecast (default 1).
+///
+/// Returns:
+///     Per-period conditional variances, one per step ahead.
+#[pyfunction]
+#[pyo3(signature = (returns, horizon=1))]
+pub fn garch_gjr_forecast(returns: Vec<f64>, horizon: usize) -> Vec<f64> {
+    garch::garch_gjr_forecast(&returns, horizon)
+}
+
+#[pyfunction]
+#[pyo3(signature = (returns, horizon=1))]
+pub fn py_garch_gjr_forecast(returns: Vec<f64>, horizon: usize) -> Vec<f64> {
+    garch_gjr_forecast(returns, horizon)
+}
+
+/// Fit GARCH(1,1) by Gaussian maximum likelihood and return its parameters.
+///
+/// Args:
+///     returns: Return series as decimal fractions (zero-mean model).
+///
+/// Returns:
+///     Dict with ``omega``, ``alpha``, ``beta``, ``log_likelihood``,
+///     ``persistence`` (alpha + beta), ``last_variance`` (conditional variance
+///     at the final observation) and ``stationary`` (persistence < 1), or
+///     None for fewer than 2 returns, non-finite values, or zero variance.
+#[pyfunction]
+pub fn py_garch_fit(py: Python<'_>, returns: Vec<f64>) -> PyResult<Option<PyObject>> {
+    let Some(fit) = garch::garch_fit(&returns) else {
+        return Ok(None);
+    };
+    let dict = PyDict::new(py);
+    dict.set_item("omega", fit.omega)?;
+    dict.set_item("alpha", fit.alpha)?;
+    dict.set_item("beta", fit.beta)?;
+    dict.set_item("log_likelihood", fit.log_likelihood)?;
+    dict.set_item("persistence", fit.persistence)?;
+    dict.set_item("last_variance", fit.last_variance)?;
+    dict.set_item("stationary", fit.is_stationary())?;
+    Ok(Some(dict.into_any().unbind()))
+}
+
+/// GARCH(1,1) conditional variance term structure.
+///
+/// Args:
+///     returns: Return series as decimal fractions (zero-mean model).
+///     horizon: Number of steps to forecast (default 1).
+///     periods_per_year: Annualization factor (default 252).
+///
+/// Returns:
+///     One dict per step with ``variance`` (per-period) and
+///     ``annualized_vol``. Reverts toward the unconditional variance for a
+///     stationary fit; falls back to the sample variance if the fit fails.
+#[pyfunction]
+#[pyo3(signature = (returns, horizon=1, periods_per_year=252.0))]
+pub fn py_garch_term_structure(
+    py: Python<'_>,
+    returns: Vec<f64>,
+    horizon: usize,
+    periods_per_year: f64,
+) -> PyResult<Vec<PyObject>> {
+    garch::garch_term_structure(&returns, horizon, periods_per_year)
+        .into_iter()
+        .map(|step| {
+            let dict = PyDict::new(py);
+            dict.set_item("variance", step.variance)?;
+            dict.set_item("annualized_vol", step.annualized_vol)?;
+            Ok(dict.into_any().unbind())
+        })
+        .collect()
+}
+
+/// RiskMetrics-style EWMA annualized volatility path.
+///
+/// Args:
+///     returns: Return series as decimal fractions.
+///     lambda_: Decay factor in ``(0, 1)`` (default 0.94).
+///     periods_per_year: Annualization factor (default 252).
+///
+/// Returns:
+///     Annualized conditional volatility, one value per input return.
+///
+/// Raises:
+///     ValueError: If ``lambda_`` is not strictly between 0 and 1.
+#[pyfunction]
+#[pyo3(signature = (returns, lambda_=0.94, periods_per_year=252.0))]
+pub fn py_ewma_volatility(
+    returns: Vec<f64>,
+    lambda_: f64,
+    periods_per_year: f64,
+) -> PyResult<Vec<f64>> {
+    if !(lambda_ > 0.0 && lambda_ < 1.0) {
+        return Err(PyValueError::new_err("lambda_ must be in (0, 1)"));
+    }
+    Ok(garch::ewma_volatility(&returns, lambda_, periods_per_year))
+}
@@ -1,3 +1,4 @@
+use crate::array::{F64Series, to_python};
 use nanobook::indicators;
 use pyo3::prelude::*;
 
@@ -6,11 +7,12 @@ use pyo3::prelude::*;
 /// Drop-in replacement for ``talib.RSI(close, timeperiod)``.
 ///
 /// Args:
-///     close: List of closing prices.
+///     close: Closing prices (list or float64 numpy array).
 ///     period: Lookback period (default 14).
 ///
 /// Returns:
-///     List of RSI values. NaN for the lookback period.
+///     RSI values (numpy array for array input, else list). NaN for the
+///     lookback period.
 ///
 /// Example::
 ///
@@ -18,8 +20,12 @@ use pyo3::prelude::*;
 ///
 #[pyfunction]
 #[pyo3(signature = (close, period=14))]
-pub fn py_rsi(close: Vec<f64>, period: usize) -> Vec<f64> {
-    indicators::rsi(&close, period)
+pub fn py_rsi(py: Python<'_>, close: F64Series, period: usize) -> PyResult<PyObject> {
+    to_python(
+        py,
+        indicators::rsi(close.as_slice(), period),
+        close.is_array(),
+    )
 }
 
 /// Compute MACD (Moving Average Convergence Divergence).
@@ -27,7 +33,7 @@ pub fn py_rsi(close: Vec<f64>, period: usize) -> Vec<f64> {
 /// Drop-in replacement for ``talib.MACD(close, fast, slow, signal)``.
 ///
 /// Args:
-///     close: List of closing prices.
+///     close: Closing prices (list or float64 numpy array).
 ///     fast_period: Fast EMA period (default 12).
 ///     slow_period: Slow EMA period (default 26).
 ///     signal_period: Signal line EMA period (default 9).
@@ -42,12 +48,20 @@ pub fn py_rsi(close: Vec<f64>, period: usize) -> Vec<f64> {
 #[pyfunction]
 #[pyo3(signature = (close, fast_period=12, slow_period=26, signal_period=9))]
 pub fn py_macd(
-    close: Vec<f64>,
+    py: Python<'_>,
+    close: F64Series,
     fast_period: usize,
     slow_period: usize,
     signal_period: usize,
-) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
-    indicators::macd(&close, fast_period, slow_period, signal_period)
+) -> PyResult<(PyObject, PyObject, PyObject)> {
+    let (line, signal, hist) =
+        indicators::macd(close.as_slice(), fast_period, slow_period, signal_period);
+    let array = close.is_array();
+    Ok((
+        to_python(py, line, array)?,
+        to_python(py, signal, array)?,
+        to_python(py, hist, array)?,
+    ))
 }
 
 /// Compute Bollinger Bands (SMA +/- k * standard deviation).
@@ -55,7 +69,7 @@ pub fn py_macd(
 /// Drop-in replacement for ``talib.BBANDS(close, period, nbdevup, nbdevdn)``.
 ///
 /// Args:
-///     close: List of closing prices.
+///     close: Closing prices (list or float64 numpy array).
 ///     period: SMA/stddev period (default 20).
 ///     num_std_up: Standard deviations above SMA (default 2.0).
 ///     num_std_dn: Standard deviations below SMA (default 2.0).
@@ -70,12 +84,206 @@ pub fn py_macd(
 #[pyfunction]
 #[pyo3(signature = (close, period=20, num_std_up=2.0, num_std_dn=2.0))]
 pub fn py_bbands(
-    close: Vec<f64>,
+    py: Python<'_>,
+    close: F64Series,
     period: usize,
     num_std_up: f64,
     num_std_dn: f64,
-) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
-    indicators::bbands(&close, period, num_std_up, num_std_dn)
+) -> PyResult<(PyObject, PyObject, PyObject)> {
+    let (upper, middle, lower) =
+        indicators::bbands(close.as_slice(), period, num_std_up, num_std_dn);
+    let array = close.is_array();
+    Ok((
+        to_python(py, upper, array)?,
+        to_python(py, middle, array)?,
+        to_python(py, lower, array)?,
+    ))
+}
+
+/// Compute Bollinger Bands with %B and bandwidth.
+///
+/// Args:
+///     close: Closing prices (list or float64 numpy array).
+///     period: SMA/stddev period (default 20).
+///     num_std_up: Standard deviations above SMA (default 2.0).
+///     num_std_dn: Standard deviations below SMA (default 2.0).
+///
+/// Returns:
+///     Tuple of (upper, middle, lower, percent_b, bandwidth). %B is 0.5 at
+///     the middle band, above 1.0 over the upper band and negative under
+///     the lower; bandwidth is (upper - lower) / middle.
+///
+/// Example::
+///
+///     upper, middle, lower, pct_b, width = nanobook.py_bbands_ext(closes)
+///
+#[pyfunction]
+#[pyo3(signature = (close, period=20, num_std_up=2.0, num_std_dn=2.0))]
+pub fn py_bbands_ext(
+    py: Python<'_>,
+    close: F64Series,
+    period: usize,
+    num_std_up: f64,
+    num_std_dn: f64,
+) -> PyResult<(PyObject, PyObject, PyObject, PyObject, PyObject)> {
+    let result = indicators::bbands_ext(close.as_slice(), period, num_std_up, num_std_dn);
+    let array = close.is_array();
+    Ok((
+        to_python(py, result.upper, array)?,
+        to_python(py, result.middle, array)?,
+        to_python(py, result.lower, array)?,
+        to_python(py, result.percent_b, array)?,
+        to_python(py, result.bandwidth, array)?,
+    ))
+}
+
+/// Compute ROC (rate of change) as a percentage over `period` bars.
+///
+/// Drop-in replacement for ``talib.ROC(values, timeperiod)``.
+///
+/// Args:
+///     values: Input series (list or float64 numpy array).
+///     period: Lookback period (default 10).
+///
+/// Returns:
+///     ROC values in percent. NaN for the lookback period.
+///
+/// Example::
+///
+///     roc = nanobook.py_roc(closes, 10)
+///
+#[pyfunction]
+#[pyo3(signature = (values, period=10))]
+pub fn py_roc(py: Python<'_>, values: F64Series, period: usize) -> PyResult<PyObject> {
+    let roc = indicators::roc(values.as_slice(), period);
+    to_python(py, roc, values.is_array())
+}
+
+/// Compute momentum: the absolute change over `period` bars.
+///
+/// Drop-in replacement for ``talib.MOM(values, timeperiod)``.
+///
+/// Args:
+///     values: Input series (list or float64 numpy array).
+///     period: Lookback period (default 10).
+///
+/// Returns:
+///     Momentum values. NaN for the lookback period.
+///
+/// Example::
+///
+///     mom = nanobook.py_momentum(closes, 10)
+///
+#[pyfunction]
+#[pyo3(signature = (values, period=10))]
+pub fn py_momentum(py: Python<'_>, values: F64Series, period: usize) -> PyResult<PyObject> {
+    let momentum = indicators::momentum(values.as_slice(), period);
+    to_python(py, momentum, values.is_array())
+}
+
+/// Compute CCI (Commodity Channel Index).
+///
+/// Drop-in replacement for ``talib.CCI(high, low, close, timeperiod)``.
+///
+/// Args:
+///     high: High prices (list or float64 numpy array).
+///     low: Low prices (list or float64 numpy array).
+///     close: Closing prices (list or float64 numpy array).
+///     period: Lookback period (default 14).
+///
+/// Returns:
+///     CCI values. NaN for the lookback period, or everywhere if the input
+///     lengths differ.
+///
+/// Example::
+///
+///     cci = nanobook.py_cci(highs, lows, closes, 14)
+///
+#[pyfunction]
+#[pyo3(signature = (high, low, close, period=14))]
+pub fn py_cci(
+    py: Python<'_>,
+    high: F64Series,
+    low: F64Series,
+    close: F64Series,
+    period: usize,
+) -> PyResult<PyObject> {
+    let cci = indicators::cci(high.as_slice(), low.as_slice(), close.as_slice(), period);
+    to_python(py, cci, close.is_array())
+}
+
+/// Compute Keltner Channels: EMA of close ± a multiple of ATR.
+///
+/// Args:
+///     high: High prices (list or float64 numpy array).
+///     low: Low prices (list or float64 numpy array).
+///     close: Closing prices (list or float64 numpy array).
+///     period: EMA and ATR period (default 20).
+///     atr_mult: ATR multiple for the outer lines (default 2.0).
+///
+/// Returns:
+///     Tuple of (upper, middle, lower). NaN for the lookback period, or
+///     everywhere if the input lengths differ.
+///
+/// Example::
+///
+///     upper, middle, lower = nanobook.py_keltner(highs, lows, closes, 20, 2.0)
+///
+#[pyfunction]
+#[pyo3(signature = (high, low, close, period=20, atr_mult=2.0))]
+pub fn py_keltner(
+    py: Python<'_>,
+    high: F64Series,
+    low: F64Series,
+    close: F64Series,
+    period: usize,
+    atr_mult: f64,
+) -> PyResult<(PyObject, PyObject, PyObject)> {
+    let (upper, middle, lower) = indicators::keltner(
+        high.as_slice(),
+        low.as_slice(),
+        close.as_slice(),
+        period,
+        atr_mult,
+    );
+    let array = close.is_array();
+    Ok((
+        to_python(py, upper, array)?,
+        to_python(py, middle, array)?,
+        to_python(py, lower, array)?,
+    ))
+}
+
+/// Compute Donchian Channels: rolling highest high and lowest low.
+///
+/// Args:
+///     high: High prices (list or float64 numpy array).
+///     low: Low prices (list or float64 numpy array).
+///     period: Window length, including the current bar (default 20).
+///
+/// Returns:
+///     Tuple of (upper, middle, lower). NaN for the first period - 1
+///     elements, or everywhere if the input lengths differ.
+///
+/// Example::
+///
+///     upper, middle, lower = nanobook.py_donchian(highs, lows, 20)
+///
+#[pyfunction]
+#[pyo3(signature = (high, low, period=20))]
+pub fn py_donchian(
+    py: Python<'_>,
+    high: F64Series,
+    low: F64Series,
+    period: usize,
+) -> PyResult<(PyObject, PyObject, PyObject)> {
+    let (upper, middle, lower) = indicators::donchian(high.as_slice(), low.as_slice(), period);
+    let array = high.is_array();
+    Ok((
+        to_python(py, upper, array)?,
+        to_python(py, middle, array)?,
+        to_python(py, lower, array)?,
+    ))
 }
 
 /// Compute ATR (Average True Range) using Wilder's smoothing.
@@ -83,13 +291,14 @@ pub fn py_bbands(
 /// Drop-in replacement for ``talib.ATR(high, low, close, timeperiod)``.
 ///
 /// Args:
-///     high: List of high prices.
-///     low: List of low prices.
-///     close: List of closing prices.
+///     high: High prices (list or float64 numpy array).
+///     low: Low prices (list or float64 numpy array).
+///     close: Closing prices (list or float64 numpy array).
 ///     period: Lookback period (default 14).
 ///
 /// Returns:
-///     List of ATR values. NaN for the lookback period.
+///     ATR values (numpy array for array input, else list). NaN for the
+///     lookback period.
 ///
 /// Example::
 ///
@@ -97,6 +306,13 @@ pub fn py_bbands(
 ///
 #[pyfunction]
 #[pyo3(signature = (high, low, close, period=14))]
-pub fn py_atr(high: Vec<f64>, low: Vec<f64>, close: Vec<f64>, period: usize) -> Vec<f64> {
-    indicators::atr(&high, &low, &close, period)
+pub fn py_atr(
+    py: Python<'_>,
+    high: F64Series,
+    low: F64Series,
+    close: F64Series,
+    period: usize,
+) -> PyResult<PyObject> {
+    let atr = indicators::atr(high.as_slice(), low.as_slice(), close.as_slice(), period);
+    to_python(py, atr, close.is_array())
 }
@@ -1,5 +1,5 @@
 use crate::event::PyEvent;
-use nanobook::itch::{ItchParser, itch_to_event};
+use nanobook::itch::{ItchParser, MoldUdp64Decoder, itch_to_event};
 use pyo3::prelude::*;
 use std::fs::File;
 use std::io::BufReader;
@@ -23,3 +23,27 @@ pub fn parse_itch(path: &str) -> PyResult<Vec<(String, PyEvent)>> {
 
     Ok(events)
 }
+
+/// `(symbol, event)` pairs and `(expected, received)` sequence gaps.
+type MoldDecoded = (Vec<(String, PyEvent)>, Vec<(u64, u64)>);
+
+#[pyfunction]
+pub fn parse_moldudp64(data: &[u8]) -> PyResult<MoldDecoded> {
+    let mut decoder = MoldUdp64Decoder::new();
+    let messages = decoder
+        .decode(data)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+
+    let events = messages
+        .into_iter()
+        .filter_map(itch_to_event)
+        .map(|(symbol, event)| (symbol, PyEvent { inner: event }))
+        .collect();
+    let gaps = decoder
+        .gaps()
+        .iter()
+        .map(|gap| (gap.expected, gap.received))
+        .collect();
+
+    Ok((events, gaps))
+}
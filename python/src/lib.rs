@@ -1,3 +1,4 @@
+mod array;
 mod backtest_bridge;
 mod broker;
 mod cv;
@@ -7,14 +8,18 @@ mod garch;
 mod indicators;
 #[cfg(feature = "itch")]
 mod itch;
+mod loaders;
 mod metrics;
 mod multi;
 mod optimize;
 mod order;
+#[cfg(feature = "ouch")]
+mod ouch;
 mod portfolio;
 mod position;
 mod results;
 mod risk;
+mod sizing;
 mod stats;
 mod strategy;
 mod sweep;
@@ -27,11 +32,16 @@ fn capabilities() -> Vec<&'static str> {
     vec![
         "backtest_stops",
         "garch_forecast",
+        "garch_gjr_forecast",
         "optimize_min_variance",
         "optimize_max_sharpe",
         "optimize_risk_parity",
         "optimize_cvar",
         "optimize_cdar",
+        "optimize_mean_variance",
+        "optimize_max_diversification",
+        "optimize_cvar_scenarios",
+        "optimize_min_variance_tc",
         "backtest_holdings",
     ]
 }
@@ -51,6 +61,11 @@ fn nanobook(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<broker::PyIbkrBroker>()?;
     #[cfg(feature = "binance")]
     m.add_class::<broker::PyBinanceBroker>()?;
+    m.add_class::<broker::PyMockBroker>()?;
+    m.add_class::<broker::PyBrokerPosition>()?;
+    m.add_class::<broker::PyBrokerAccount>()?;
+    m.add_class::<broker::PyBrokerOrderStatus>()?;
+    broker::add_exceptions(m)?;
 
     // Risk engine
     m.add_class::<risk::PyRiskEngine>()?;
@@ -66,35 +81,54 @@ fn nanobook(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<results::PyCancelResult>()?;
     m.add_class::<results::PyModifyResult>()?;
     m.add_class::<results::PyStopSubmitResult>()?;
+    m.add_class::<results::PyAuctionResult>()?;
     m.add_class::<results::PyTrade>()?;
     m.add_class::<results::PyLevelSnapshot>()?;
     m.add_class::<exchange::PyBookSnapshot>()?;
     m.add_class::<results::PyBacktestResult>()?;
+    m.add_class::<results::PyBacktestWeightsResult>()?;
 
     // Portfolio types
     m.add_class::<portfolio::PyCostModel>()?;
     m.add_class::<portfolio::PyPortfolio>()?;
     m.add_class::<position::PyPosition>()?;
     m.add_class::<metrics::PyMetrics>()?;
+    m.add_class::<metrics::PyRelativeMetrics>()?;
 
     // v0.7 functions
     m.add_function(wrap_pyfunction!(metrics::py_compute_metrics, m)?)?;
+    m.add_function(wrap_pyfunction!(metrics::py_compute_relative_metrics, m)?)?;
     m.add_function(wrap_pyfunction!(sweep::py_sweep_equal_weight, m)?)?;
+    m.add_function(wrap_pyfunction!(sweep::py_sweep_stop_configs, m)?)?;
     m.add_function(wrap_pyfunction!(strategy::py_run_backtest, m)?)?;
     m.add_function(wrap_pyfunction!(backtest_bridge::backtest_weights, m)?)?;
     m.add_function(wrap_pyfunction!(backtest_bridge::py_backtest_weights, m)?)?;
     #[cfg(feature = "itch")]
     m.add_function(wrap_pyfunction!(itch::parse_itch, m)?)?;
+    #[cfg(feature = "itch")]
+    m.add_function(wrap_pyfunction!(itch::parse_moldudp64, m)?)?;
+    #[cfg(feature = "ouch")]
+    m.add_function(wrap_pyfunction!(ouch::parse_ouch, m)?)?;
+    m.add_function(wrap_pyfunction!(loaders::load_csv, m)?)?;
 
     // v0.8 — Technical indicators (ta-lib replacements)
     m.add_function(wrap_pyfunction!(indicators::py_rsi, m)?)?;
     m.add_function(wrap_pyfunction!(indicators::py_macd, m)?)?;
     m.add_function(wrap_pyfunction!(indicators::py_bbands, m)?)?;
+    m.add_function(wrap_pyfunction!(indicators::py_bbands_ext, m)?)?;
+    m.add_function(wrap_pyfunction!(indicators::py_keltner, m)?)?;
+    m.add_function(wrap_pyfunction!(indicators::py_roc, m)?)?;
+    m.add_function(wrap_pyfunction!(indicators::py_momentum, m)?)?;
+    m.add_function(wrap_pyfunction!(indicators::py_cci, m)?)?;
+    m.add_function(wrap_pyfunction!(indicators::py_donchian, m)?)?;
     m.add_function(wrap_pyfunction!(indicators::py_atr, m)?)?;
 
     // v0.8 — Statistics (scipy replacements)
     m.add_function(wrap_pyfunction!(stats::py_spearman, m)?)?;
     m.add_function(wrap_pyfunction!(stats::py_quintile_spread, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_quantile_portfolio, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_information_coefficient, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_rolling_ic, m)?)?;
 
     // v0.8 — Cross-validation (sklearn replacement)
     m.add_function(wrap_pyfunction!(cv::py_time_series_split, m)?)?;
@@ -108,16 +142,41 @@ fn nanobook(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(py_capabilities, m)?)?;
     m.add_function(wrap_pyfunction!(garch::garch_forecast, m)?)?;
     m.add_function(wrap_pyfunction!(garch::py_garch_forecast, m)?)?;
+    m.add_function(wrap_pyfunction!(garch::garch_gjr_forecast, m)?)?;
+    m.add_function(wrap_pyfunction!(garch::py_garch_gjr_forecast, m)?)?;
+    m.add_function(wrap_pyfunction!(garch::py_ewma_volatility, m)?)?;
+    m.add_function(wrap_pyfunction!(garch::py_garch_fit, m)?)?;
+    m.add_function(wrap_pyfunction!(garch::py_garch_term_structure, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::optimize_min_variance, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::py_optimize_min_variance, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_optimize_min_variance_ext, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::optimize_max_sharpe, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::py_optimize_max_sharpe, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::optimize_risk_parity, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::py_optimize_risk_parity, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_optimize_risk_parity_ext, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::optimize_cvar, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::py_optimize_cvar, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::optimize_cdar, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::py_optimize_cdar, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::optimize_mean_variance, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_optimize_mean_variance, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::optimize_max_diversification, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        optimize::py_optimize_max_diversification,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(optimize::optimize_cvar_scenarios, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_optimize_cvar_scenarios, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::optimize_min_variance_tc, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_optimize_min_variance_tc, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_risk_contributions, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_marginal_risk, m)?)?;
+
+    // Position sizing
+    m.add_function(wrap_pyfunction!(sizing::py_kelly_fraction, m)?)?;
+    m.add_function(wrap_pyfunction!(sizing::py_vol_target_weight, m)?)?;
+    m.add_function(wrap_pyfunction!(sizing::py_equal_risk_weights, m)?)?;
 
     Ok(())
 }
@@ -0,0 +1,42 @@
+use crate::event::PyEvent;
+use nanobook::loaders::{CsvSchema, load_csv as load_csv_events};
+use pyo3::prelude::*;
+use std::path::Path;
+
+/// Load order events from a CSV/TSV file of external market data.
+///
+/// Columns are zero-based indices. Prices are multiplied by
+/// ``price_scale`` to get cents (100.0 for dollar prices). Malformed rows
+/// raise ``OSError`` naming the line number.
+#[pyfunction]
+#[pyo3(signature = (path, action, side, price, quantity, tif=None, order_id=None, delimiter=',', has_header=true, price_scale=1.0))]
+#[allow(clippy::too_many_arguments)]
+pub fn load_csv(
+    path: &str,
+    action: usize,
+    side: usize,
+    price: usize,
+    quantity: usize,
+    tif: Option<usize>,
+    order_id: Option<usize>,
+    delimiter: char,
+    has_header: bool,
+    price_scale: f64,
+) -> PyResult<Vec<PyEvent>> {
+    let schema = CsvSchema {
+        delimiter,
+        has_header,
+        action,
+        side,
+        price,
+        quantity,
+        tif,
+        order_id,
+        price_scale,
+    };
+    let events = load_csv_events(Path::new(path), &schema)?;
+    Ok(events
+        .into_iter()
+        .map(|event| PyEvent { inner: event })
+        .collect())
+}
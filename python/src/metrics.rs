@@ -1,4 +1,8 @@
-use nanobook::portfolio::metrics::{Metrics, compute_metrics, rolling_sharpe, rolling_volatility};
+use crate::array::{F64Series, to_python};
+use nanobook::portfolio::metrics::{
+    Metrics, RelativeMetrics, compute_metrics_ext, compute_relative_metrics, rolling_sharpe,
+    rolling_volatility,
+};
 use pyo3::prelude::*;
 
 /// Performance metrics for a return series.
@@ -74,12 +78,52 @@ impl From<Metrics> for PyMetrics {
     }
 }
 
+/// Benchmark-relative performance metrics.
+#[pyclass(name = "RelativeMetrics")]
+#[derive(Clone)]
+pub struct PyRelativeMetrics {
+    #[pyo3(get)]
+    pub beta: f64,
+    #[pyo3(get)]
+    pub alpha: f64,
+    #[pyo3(get)]
+    pub tracking_error: f64,
+    #[pyo3(get)]
+    pub information_ratio: f64,
+}
+
+#[pymethods]
+impl PyRelativeMetrics {
+    fn __repr__(&self) -> String {
+        format!(
+            "RelativeMetrics(beta={:.2}, alpha={:.2}%, tracking_error={:.2}%, information_ratio={:.2})",
+            self.beta,
+            self.alpha * 100.0,
+            self.tracking_error * 100.0,
+            self.information_ratio,
+        )
+    }
+}
+
+impl From<RelativeMetrics> for PyRelativeMetrics {
+    fn from(m: RelativeMetrics) -> Self {
+        Self {
+            beta: m.beta,
+            alpha: m.alpha,
+            tracking_error: m.tracking_error,
+            information_ratio: m.information_ratio,
+        }
+    }
+}
+
 /// Compute performance metrics from a return series.
 ///
 /// Args:
 ///     returns: List of periodic returns (e.g., [0.01, -0.005, 0.02])
 ///     periods_per_year: Annualization factor (252 for daily, 12 for monthly)
 ///     risk_free: Risk-free rate per period
+///     mar: Minimum acceptable return per period for Sortino
+///         (defaults to risk_free)
 ///
 /// Returns:
 ///     Metrics object, or None if returns is empty
@@ -90,24 +134,54 @@ impl From<Metrics> for PyMetrics {
 ///     print(m.sharpe, m.cvar_95, m.kelly)
 ///
 #[pyfunction]
-#[pyo3(signature = (returns, periods_per_year=252.0, risk_free=0.0))]
+#[pyo3(signature = (returns, periods_per_year=252.0, risk_free=0.0, mar=None))]
 pub fn py_compute_metrics(
     returns: Vec<f64>,
     periods_per_year: f64,
     risk_free: f64,
+    mar: Option<f64>,
 ) -> Option<PyMetrics> {
-    compute_metrics(&returns, periods_per_year, risk_free).map(PyMetrics::from)
+    compute_metrics_ext(
+        &returns,
+        periods_per_year,
+        risk_free,
+        mar.unwrap_or(risk_free),
+    )
+    .map(PyMetrics::from)
+}
+
+/// Compute beta, alpha, tracking error and information ratio against a benchmark.
+///
+/// Args:
+///     returns: Strategy periodic returns
+///     benchmark: Benchmark returns for the same periods
+///     periods_per_year: Annualization factor (252 for daily, 12 for monthly)
+///     risk_free: Risk-free rate per period (used for alpha)
+///
+/// Returns:
+///     RelativeMetrics object, or None if the series are empty or differ in length
+#[pyfunction]
+#[pyo3(signature = (returns, benchmark, periods_per_year=252.0, risk_free=0.0))]
+pub fn py_compute_relative_metrics(
+    returns: Vec<f64>,
+    benchmark: Vec<f64>,
+    periods_per_year: f64,
+    risk_free: f64,
+) -> Option<PyRelativeMetrics> {
+    compute_relative_metrics(&returns, &benchmark, periods_per_year, risk_free)
+        .map(PyRelativeMetrics::from)
 }
 
 /// Compute rolling Sharpe ratio over a sliding window.
 ///
 /// Args:
-///     returns: List of periodic returns.
+///     returns: Periodic returns (list or float64 numpy array).
 ///     window: Window size (e.g., 63 for quarterly).
 ///     periods_per_year: Annualization factor (default 252).
 ///
 /// Returns:
-///     List of rolling Sharpe values. NaN for incomplete windows.
+///     Rolling Sharpe values (numpy array for array input, else list).
+///     NaN for incomplete windows.
 ///
 /// Example::
 ///
@@ -115,19 +189,26 @@ pub fn py_compute_metrics(
 ///
 #[pyfunction]
 #[pyo3(signature = (returns, window, periods_per_year=252))]
-pub fn py_rolling_sharpe(returns: Vec<f64>, window: usize, periods_per_year: usize) -> Vec<f64> {
-    rolling_sharpe(&returns, window, periods_per_year)
+pub fn py_rolling_sharpe(
+    py: Python<'_>,
+    returns: F64Series,
+    window: usize,
+    periods_per_year: usize,
+) -> PyResult<PyObject> {
+    let rolling = rolling_sharpe(returns.as_slice(), window, periods_per_year);
+    to_python(py, rolling, returns.is_array())
 }
 
 /// Compute rolling annualized volatility over a sliding window.
 ///
 /// Args:
-///     returns: List of periodic returns.
+///     returns: Periodic returns (list or float64 numpy array).
 ///     window: Window size (e.g., 63 for quarterly).
 ///     periods_per_year: Annualization factor (default 252).
 ///
 /// Returns:
-///     List of rolling volatility values. NaN for incomplete windows.
+///     Rolling volatility values (numpy array for array input, else list).
+///     NaN for incomplete windows.
 ///
 /// Example::
 ///
@@ -136,9 +217,11 @@ pub fn py_rolling_sharpe(returns: Vec<f64>, window: usize, periods_per_year: usi
 #[pyfunction]
 #[pyo3(signature = (returns, window, periods_per_year=252))]
 pub fn py_rolling_volatility(
-    returns: Vec<f64>,
+    py: Python<'_>,
+    returns: F64Series,
     window: usize,
     periods_per_year: usize,
-) -> Vec<f64> {
-    rolling_volatility(&returns, window, periods_per_year)
+) -> PyResult<PyObject> {
+    let rolling = rolling_volatility(returns.as_slice(), window, periods_per_year);
+    to_python(py, rolling, returns.is_array())
 }
@@ -1,5 +1,6 @@
-use nanobook::{MultiExchange, OrderId, Price};
+use nanobook::{InstrumentSpec, MultiExchange, OrderId, Price};
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 
 use crate::exchange::PyExchange;
 use crate::results::*;
@@ -37,6 +38,67 @@ impl PyMultiExchange {
         Ok(PyExchange::from_exchange(ex.clone()))
     }
 
+    /// Register instrument metadata for a symbol.
+    ///
+    /// ``tick_size`` is in cents; ``multiplier`` scales ``notional`` (e.g. 50
+    /// for ES futures, 100 for equity options).
+    #[pyo3(signature = (symbol, tick_size=1, lot_size=1, currency="USD", multiplier=1))]
+    fn register_symbol(
+        &mut self,
+        symbol: &str,
+        tick_size: i64,
+        lot_size: u64,
+        currency: &str,
+        multiplier: i64,
+    ) -> PyResult<()> {
+        let spec = InstrumentSpec {
+            tick_size,
+            lot_size,
+            currency: parse_symbol(currency)?,
+            multiplier,
+        };
+        self.inner.register_symbol(parse_symbol(symbol)?, spec);
+        Ok(())
+    }
+
+    /// Value of ``quantity`` contracts at ``price`` in cents, applying the
+    /// symbol's contract multiplier (1 if unregistered).
+    fn notional(&self, symbol: &str, price: i64, quantity: u64) -> PyResult<i64> {
+        let sym = parse_symbol(symbol)?;
+        Ok(self.inner.notional(&sym, Price(price), quantity))
+    }
+
+    /// Remove a symbol's exchange, returning (a copy of) it, or None.
+    fn remove(&mut self, symbol: &str) -> PyResult<Option<PyExchange>> {
+        let sym = parse_symbol(symbol)?;
+        Ok(self.inner.remove(&sym).map(PyExchange::from_exchange))
+    }
+
+    /// Keep only the symbols whose book still has active orders.
+    ///
+    /// Returns the removed symbols.
+    fn retain_active(&mut self) -> Vec<String> {
+        let mut removed = Vec::new();
+        self.inner.retain(|sym, ex| {
+            let keep = ex.book().active_order_count() > 0;
+            if !keep {
+                removed.push(sym.to_string());
+            }
+            keep
+        });
+        removed
+    }
+
+    /// Number of symbols with a live exchange.
+    fn symbol_count(&self) -> usize {
+        self.inner.symbol_count()
+    }
+
+    /// Orders stored across all books (including history).
+    fn total_order_count(&self) -> usize {
+        self.inner.total_order_count()
+    }
+
     /// List all symbols that have exchanges.
     fn symbols(&self) -> Vec<String> {
         self.inner
@@ -111,6 +173,46 @@ impl PyMultiExchange {
             .into())
     }
 
+    // === Venues ===
+
+    /// Submit a limit order to ``symbol``'s book on ``venue``.
+    #[pyo3(signature = (venue, symbol, side, price, quantity, tif="gtc"))]
+    fn submit_limit_on(
+        &mut self,
+        venue: &str,
+        symbol: &str,
+        side: &str,
+        price: i64,
+        quantity: u64,
+        tif: &str,
+    ) -> PyResult<PySubmitResult> {
+        let venue = parse_symbol(venue)?;
+        let sym = parse_symbol(symbol)?;
+        let side = parse_side(side)?;
+        let tif = parse_tif(tif)?;
+        let ex = self.inner.get_or_create_on(&venue, &sym);
+        Ok(ex.submit_limit(side, Price(price), quantity, tif).into())
+    }
+
+    /// Cross-venue opportunity for ``symbol`` as a dict with ``buy_venue``,
+    /// ``sell_venue``, ``quantity`` and ``spread`` (cents), or None.
+    fn find_arbitrage<'py>(
+        &self,
+        py: Python<'py>,
+        symbol: &str,
+    ) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let sym = parse_symbol(symbol)?;
+        let Some(arb) = self.inner.find_arbitrage(&sym) else {
+            return Ok(None);
+        };
+        let dict = PyDict::new(py);
+        dict.set_item("buy_venue", arb.buy_venue.as_str())?;
+        dict.set_item("sell_venue", arb.sell_venue.as_str())?;
+        dict.set_item("quantity", arb.quantity)?;
+        dict.set_item("spread", arb.spread)?;
+        Ok(Some(dict))
+    }
+
     /// Number of symbols.
     fn len(&self) -> usize {
         self.inner.len()
@@ -19,6 +19,18 @@ fn to_weights_dict<'py>(
     Ok(out)
 }
 
+fn to_result_dict(
+    py: Python<'_>,
+    symbols: &[String],
+    result: optimize::OptimizeResult,
+) -> PyResult<PyObject> {
+    let dict = PyDict::new(py);
+    dict.set_item("weights", to_weights_dict(py, symbols, result.weights)?)?;
+    dict.set_item("iterations", result.iterations)?;
+    dict.set_item("converged", result.converged)?;
+    Ok(dict.into_any().unbind())
+}
+
 fn sanitize_symbols(symbols: Vec<String>) -> Vec<String> {
     // Preserve order but reject empty names.
     symbols
@@ -28,23 +40,52 @@ fn sanitize_symbols(symbols: Vec<String>) -> Vec<String> {
 }
 
 #[pyfunction]
+#[pyo3(signature = (returns_matrix, symbols, max_iter=optimize::MIN_VARIANCE_MAX_ITER, tolerance=optimize::OPTIMIZER_TOLERANCE))]
 pub fn optimize_min_variance(
     py: Python<'_>,
     returns_matrix: Vec<Vec<f64>>,
     symbols: Vec<String>,
+    max_iter: usize,
+    tolerance: f64,
 ) -> PyResult<PyObject> {
     let symbols = sanitize_symbols(symbols);
-    let w = py.allow_threads(|| optimize::optimize_min_variance(&returns_matrix));
-    Ok(to_weights_dict(py, &symbols, w)?.into())
+    let result = py.allow_threads(|| {
+        optimize::optimize_min_variance_ext(&returns_matrix, max_iter, tolerance)
+    });
+    Ok(to_weights_dict(py, &symbols, result.weights)?.into())
 }
 
 #[pyfunction]
+#[pyo3(signature = (returns_matrix, symbols, max_iter=optimize::MIN_VARIANCE_MAX_ITER, tolerance=optimize::OPTIMIZER_TOLERANCE))]
 pub fn py_optimize_min_variance(
     py: Python<'_>,
     returns_matrix: Vec<Vec<f64>>,
     symbols: Vec<String>,
+    max_iter: usize,
+    tolerance: f64,
+) -> PyResult<PyObject> {
+    optimize_min_variance(py, returns_matrix, symbols, max_iter, tolerance)
+}
+
+/// Like ``py_optimize_min_variance`` but also reports convergence.
+///
+/// Returns ``{"weights": {...}, "iterations": int, "converged": bool}``.
+/// When ``max_iter`` runs out first, ``weights`` holds the last iterate and
+/// ``converged`` is ``False``.
+#[pyfunction]
+#[pyo3(signature = (returns_matrix, symbols, max_iter=optimize::MIN_VARIANCE_MAX_ITER, tolerance=optimize::OPTIMIZER_TOLERANCE))]
+pub fn py_optimize_min_variance_ext(
+    py: Python<'_>,
+    returns_matrix: Vec<Vec<f64>>,
+    symbols: Vec<String>,
+    max_iter: usize,
+    tolerance: f64,
 ) -> PyResult<PyObject> {
-    optimize_min_variance(py, returns_matrix, symbols)
+    let symbols = sanitize_symbols(symbols);
+    let result = py.allow_threads(|| {
+        optimize::optimize_min_variance_ext(&returns_matrix, max_iter, tolerance)
+    });
+    to_result_dict(py, &symbols, result)
 }
 
 #[pyfunction]
@@ -72,23 +113,50 @@ pub fn py_optimize_max_sharpe(
 }
 
 #[pyfunction]
+#[pyo3(signature = (returns_matrix, symbols, max_iter=optimize::RISK_PARITY_MAX_ITER, tolerance=optimize::OPTIMIZER_TOLERANCE))]
 pub fn optimize_risk_parity(
     py: Python<'_>,
     returns_matrix: Vec<Vec<f64>>,
     symbols: Vec<String>,
+    max_iter: usize,
+    tolerance: f64,
 ) -> PyResult<PyObject> {
     let symbols = sanitize_symbols(symbols);
-    let w = py.allow_threads(|| optimize::optimize_risk_parity(&returns_matrix));
-    Ok(to_weights_dict(py, &symbols, w)?.into())
+    let result = py
+        .allow_threads(|| optimize::optimize_risk_parity_ext(&returns_matrix, max_iter, tolerance));
+    Ok(to_weights_dict(py, &symbols, result.weights)?.into())
 }
 
 #[pyfunction]
+#[pyo3(signature = (returns_matrix, symbols, max_iter=optimize::RISK_PARITY_MAX_ITER, tolerance=optimize::OPTIMIZER_TOLERANCE))]
 pub fn py_optimize_risk_parity(
     py: Python<'_>,
     returns_matrix: Vec<Vec<f64>>,
     symbols: Vec<String>,
+    max_iter: usize,
+    tolerance: f64,
+) -> PyResult<PyObject> {
+    optimize_risk_parity(py, returns_matrix, symbols, max_iter, tolerance)
+}
+
+/// Like ``py_optimize_risk_parity`` but also reports convergence.
+///
+/// Returns ``{"weights": {...}, "iterations": int, "converged": bool}``.
+/// When ``max_iter`` runs out first, ``weights`` holds the last iterate and
+/// ``converged`` is ``False``.
+#[pyfunction]
+#[pyo3(signature = (returns_matrix, symbols, max_iter=optimize::RISK_PARITY_MAX_ITER, tolerance=optimize::OPTIMIZER_TOLERANCE))]
+pub fn py_optimize_risk_parity_ext(
+    py: Python<'_>,
+    returns_matrix: Vec<Vec<f64>>,
+    symbols: Vec<String>,
+    max_iter: usize,
+    tolerance: f64,
 ) -> PyResult<PyObject> {
-    optimize_risk_parity(py, returns_matrix, symbols)
+    let symbols = sanitize_symbols(symbols);
+    let result = py
+        .allow_threads(|| optimize::optimize_risk_parity_ext(&returns_matrix, max_iter, tolerance));
+    to_result_dict(py, &symbols, result)
 }
 
 #[pyfunction]
@@ -138,3 +206,160 @@ pub fn py_optimize_cdar(
 ) -> PyResult<PyObject> {
     optimize_cdar(py, returns_matrix, symbols, alpha)
 }
+
+#[pyfunction]
+pub fn optimize_max_diversification(
+    py: Python<'_>,
+    cov: Vec<Vec<f64>>,
+    vols: Vec<f64>,
+    symbols: Vec<String>,
+) -> PyResult<PyObject> {
+    let symbols = sanitize_symbols(symbols);
+    let w = py.allow_threads(|| optimize::optimize_max_diversification(&cov, &vols));
+    Ok(to_weights_dict(py, &symbols, w)?.into())
+}
+
+#[pyfunction]
+pub fn py_optimize_max_diversification(
+    py: Python<'_>,
+    cov: Vec<Vec<f64>>,
+    vols: Vec<f64>,
+    symbols: Vec<String>,
+) -> PyResult<PyObject> {
+    optimize_max_diversification(py, cov, vols, symbols)
+}
+
+#[pyfunction]
+#[pyo3(signature = (expected_returns, cov, symbols, min_weights=None, max_weights=None, target_return=None))]
+pub fn optimize_mean_variance(
+    py: Python<'_>,
+    expected_returns: Vec<f64>,
+    cov: Vec<Vec<f64>>,
+    symbols: Vec<String>,
+    min_weights: Option<Vec<f64>>,
+    max_weights: Option<Vec<f64>>,
+    target_return: Option<f64>,
+) -> PyResult<PyObject> {
+    let symbols = sanitize_symbols(symbols);
+    let n = expected_returns.len();
+    let lo = min_weights.unwrap_or_else(|| vec![0.0; n]);
+    let hi = max_weights.unwrap_or_else(|| vec![1.0; n]);
+    let w = py.allow_threads(|| {
+        optimize::optimize_mean_variance(&expected_returns, &cov, &lo, &hi, target_return)
+    });
+    Ok(to_weights_dict(py, &symbols, w)?.into())
+}
+
+#[pyfunction]
+#[pyo3(signature = (expected_returns, cov, symbols, min_weights=None, max_weights=None, target_return=None))]
+pub fn py_optimize_mean_variance(
+    py: Python<'_>,
+    expected_returns: Vec<f64>,
+    cov: Vec<Vec<f64>>,
+    symbols: Vec<String>,
+    min_weights: Option<Vec<f64>>,
+    max_weights: Option<Vec<f64>>,
+    target_return: Option<f64>,
+) -> PyResult<PyObject> {
+    optimize_mean_variance(
+        py,
+        expected_returns,
+        cov,
+        symbols,
+        min_weights,
+        max_weights,
+        target_return,
+    )
+}
+
+#[pyfunction]
+#[pyo3(signature = (cov, current_weights, symbols, cost_bps, min_weights=None, max_weights=None))]
+pub fn optimize_min_variance_tc(
+    py: Python<'_>,
+    cov: Vec<Vec<f64>>,
+    current_weights: Vec<f64>,
+    symbols: Vec<String>,
+    cost_bps: f64,
+    min_weights: Option<Vec<f64>>,
+    max_weights: Option<Vec<f64>>,
+) -> PyResult<PyObject> {
+    let symbols = sanitize_symbols(symbols);
+    let n = current_weights.len();
+    let lo = min_weights.unwrap_or_else(|| vec![0.0; n]);
+    let hi = max_weights.unwrap_or_else(|| vec![1.0; n]);
+    let w = py.allow_threads(|| {
+        optimize::optimize_min_variance_tc(&cov, &current_weights, cost_bps, &lo, &hi)
+    });
+    Ok(to_weights_dict(py, &symbols, w)?.into())
+}
+
+#[pyfunction]
+#[pyo3(signature = (cov, current_weights, symbols, cost_bps, min_weights=None, max_weights=None))]
+pub fn py_optimize_min_variance_tc(
+    py: Python<'_>,
+    cov: Vec<Vec<f64>>,
+    current_weights: Vec<f64>,
+    symbols: Vec<String>,
+    cost_bps: f64,
+    min_weights: Option<Vec<f64>>,
+    max_weights: Option<Vec<f64>>,
+) -> PyResult<PyObject> {
+    optimize_min_variance_tc(
+        py,
+        cov,
+        current_weights,
+        symbols,
+        cost_bps,
+        min_weights,
+        max_weights,
+    )
+}
+
+#[pyfunction]
+#[pyo3(signature = (scenario_returns, symbols, alpha=0.95, min_weights=None, max_weights=None))]
+pub fn optimize_cvar_scenarios(
+    py: Python<'_>,
+    scenario_returns: Vec<Vec<f64>>,
+    symbols: Vec<String>,
+    alpha: f64,
+    min_weights: Option<Vec<f64>>,
+    max_weights: Option<Vec<f64>>,
+) -> PyResult<PyObject> {
+    let symbols = sanitize_symbols(symbols);
+    let n = scenario_returns.first().map_or(0, Vec::len);
+    let lo = min_weights.unwrap_or_else(|| vec![0.0; n]);
+    let hi = max_weights.unwrap_or_else(|| vec![1.0; n]);
+    let w =
+        py.allow_threads(|| optimize::optimize_cvar_scenarios(&scenario_returns, alpha, &lo, &hi));
+    Ok(to_weights_dict(py, &symbols, w)?.into())
+}
+
+#[pyfunction]
+#[pyo3(signature = (scenario_returns, symbols, alpha=0.95, min_weights=None, max_weights=None))]
+pub fn py_optimize_cvar_scenarios(
+    py: Python<'_>,
+    scenario_returns: Vec<Vec<f64>>,
+    symbols: Vec<String>,
+    alpha: f64,
+    min_weights: Option<Vec<f64>>,
+    max_weights: Option<Vec<f64>>,
+) -> PyResult<PyObject> {
+    optimize_cvar_scenarios(
+        py,
+        scenario_returns,
+        symbols,
+        alpha,
+        min_weights,
+        max_weights,
+    )
+}
+
+#[pyfunction]
+pub fn py_risk_contributions(weights: Vec<f64>, cov: Vec<Vec<f64>>) -> Vec<f64> {
+    optimize::risk_contributions(&weights, &cov)
+}
+
+#[pyfunction]
+pub fn py_marginal_risk(weights: Vec<f64>, cov: Vec<Vec<f64>>) -> Vec<f64> {
+    optimize::marginal_risk(&weights, &cov)
+}
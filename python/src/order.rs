@@ -55,6 +55,18 @@ impl PyOrder {
         self.inner.timestamp
     }
 
+    /// Fills as (trade_id, price, quantity, timestamp), in execution order.
+    #[getter]
+    fn executions(&self) -> Vec<(u64, i64, u64, u64)> {
+        self.inner
+            .executions
+            .iter()
+            .map(|&(trade_id, price, quantity, timestamp)| {
+                (trade_id.0, price.0, quantity, timestamp)
+            })
+            .collect()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "Order(id={}, side={}, price={}, qty={}/{}, status={})",
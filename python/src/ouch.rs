@@ -0,0 +1,22 @@
+use crate::event::PyEvent;
+use nanobook::ouch::{OuchParser, OuchReplay};
+use pyo3::prelude::*;
+
+#[pyfunction]
+pub fn parse_ouch(data: &[u8]) -> PyResult<Vec<PyEvent>> {
+    let mut parser = OuchParser::new(data);
+    let mut replay = OuchReplay::new();
+
+    let mut events = Vec::new();
+
+    while let Some(msg) = parser
+        .next_message()
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?
+    {
+        if let Some(event) = replay.to_event(&msg) {
+            events.push(PyEvent { inner: event });
+        }
+    }
+
+    Ok(events)
+}
@@ -1,4 +1,4 @@
-use nanobook::portfolio::{CostModel, Portfolio};
+use nanobook::portfolio::{CostBasisMethod, CostModel, FeeTiers, LiquidityFlag, Portfolio};
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
@@ -28,15 +28,35 @@ pub struct PyCostModel {
 #[pymethods]
 impl PyCostModel {
     #[new]
-    #[pyo3(signature = (commission_bps=0, slippage_bps=0, min_trade_fee=0))]
-    fn new(commission_bps: u32, slippage_bps: u32, min_trade_fee: i64) -> Self {
-        Self {
+    #[pyo3(signature = (commission_bps=0, slippage_bps=0, min_trade_fee=0, maker_bps=None, taker_bps=None))]
+    fn new(
+        commission_bps: u32,
+        slippage_bps: u32,
+        min_trade_fee: i64,
+        maker_bps: Option<i32>,
+        taker_bps: Option<i32>,
+    ) -> PyResult<Self> {
+        let fee_tiers = match (maker_bps, taker_bps) {
+            (Some(maker_bps), Some(taker_bps)) => Some(FeeTiers {
+                maker_bps,
+                taker_bps,
+            }),
+            (None, None) => None,
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "maker_bps and taker_bps must be set together",
+                ));
+            }
+        };
+        Ok(Self {
             inner: CostModel {
                 commission_bps,
                 slippage_bps,
                 min_trade_fee,
+                size_impact: None,
+                fee_tiers,
             },
-        }
+        })
     }
 
     /// Create a zero-cost model.
@@ -52,6 +72,19 @@ impl PyCostModel {
         self.inner.compute_cost(notional)
     }
 
+    /// Compute cost for a fill that added (`maker=True`) or removed
+    /// liquidity; negative for a maker rebate.
+    #[pyo3(signature = (notional, quantity, maker=false))]
+    fn compute_cost_with_liquidity(&self, notional: i64, quantity: u64, maker: bool) -> i64 {
+        let flag = if maker {
+            LiquidityFlag::Maker
+        } else {
+            LiquidityFlag::Taker
+        };
+        self.inner
+            .compute_cost_with_liquidity(notional, quantity, flag)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "CostModel(commission_bps={}, slippage_bps={}, min_trade_fee={})",
@@ -65,6 +98,7 @@ impl PyCostModel {
 /// Args:
 ///     initial_cash: Starting cash in cents (e.g., 1_000_000_00 = $1M)
 ///     cost_model: A CostModel instance
+///     cost_basis: Realized PnL method: "average" (default), "fifo", or "lifo"
 ///
 /// Example::
 ///
@@ -86,10 +120,43 @@ impl PyPortfolio {
 #[pymethods]
 impl PyPortfolio {
     #[new]
-    fn new(initial_cash: i64, cost_model: &PyCostModel) -> Self {
-        Self {
-            inner: Portfolio::new(initial_cash, cost_model.inner),
+    #[pyo3(signature = (initial_cash, cost_model, cost_basis="average", currencies=None, cash_currency="USD"))]
+    fn new(
+        initial_cash: i64,
+        cost_model: &PyCostModel,
+        cost_basis: &str,
+        currencies: Option<Vec<(String, String)>>,
+        cash_currency: &str,
+    ) -> PyResult<Self> {
+        let method = parse_cost_basis(cost_basis)?;
+        let currencies = currencies
+            .unwrap_or_default()
+            .iter()
+            .map(|(s, c)| Ok((parse_symbol(s)?, parse_symbol(c)?)))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(Self {
+            inner: Portfolio::new(initial_cash, cost_model.inner)
+                .with_cost_basis(method)
+                .with_symbol_currencies(currencies)
+                .with_cash_currency(parse_symbol(cash_currency)?),
+        })
+    }
+
+    /// Create a portfolio whose gross exposure is capped at `max_leverage` × equity.
+    #[staticmethod]
+    fn with_leverage(
+        initial_cash: i64,
+        cost_model: &PyCostModel,
+        max_leverage: f64,
+    ) -> PyResult<Self> {
+        if !(max_leverage.is_finite() && max_leverage > 0.0) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "max_leverage must be finite and > 0",
+            ));
         }
+        Ok(Self {
+            inner: Portfolio::with_leverage(initial_cash, cost_model.inner, max_leverage),
+        })
     }
 
     /// Current cash balance in cents.
@@ -98,6 +165,18 @@ impl PyPortfolio {
         self.inner.cash()
     }
 
+    /// Gross exposure (sum of |quantity| × price) in cents.
+    fn gross_exposure(&self, prices: Vec<(String, i64)>) -> PyResult<i64> {
+        let prices = parse_price_list(&prices)?;
+        Ok(self.inner.gross_exposure(&prices))
+    }
+
+    /// Net exposure (sum of signed quantity × price) in cents.
+    fn net_exposure(&self, prices: Vec<(String, i64)>) -> PyResult<i64> {
+        let prices = parse_price_list(&prices)?;
+        Ok(self.inner.net_exposure(&prices))
+    }
+
     /// Get a position by symbol.
     fn position(&self, symbol: &str) -> PyResult<Option<PyPosition>> {
         let sym = parse_symbol(symbol)?;
@@ -107,6 +186,28 @@ impl PyPortfolio {
             .map(|p| PyPosition { inner: p.clone() }))
     }
 
+    /// Gross buys, gross sells and net quantity for a symbol across venues.
+    ///
+    /// Returns a dict with keys ``bought``, ``sold``, ``net``, or None if
+    /// the symbol was never traded.
+    fn consolidated_position(&self, py: Python<'_>, symbol: &str) -> PyResult<Option<PyObject>> {
+        let sym = parse_symbol(symbol)?;
+        let Some(c) = self.inner.consolidated_position(&sym) else {
+            return Ok(None);
+        };
+        let dict = PyDict::new(py);
+        dict.set_item("bought", c.bought)?;
+        dict.set_item("sold", c.sold)?;
+        dict.set_item("net", c.net)?;
+        Ok(Some(dict.into()))
+    }
+
+    /// Total quantity traded in a symbol (bought + sold), across venues.
+    fn gross_traded_quantity(&self, symbol: &str) -> PyResult<i64> {
+        let sym = parse_symbol(symbol)?;
+        Ok(self.inner.gross_traded_quantity(&sym))
+    }
+
     /// Get all positions as a dict {symbol: Position}.
     fn positions(&self, py: Python<'_>) -> PyResult<PyObject> {
         let dict = PyDict::new(py);
@@ -125,6 +226,36 @@ impl PyPortfolio {
         Ok(self.inner.total_equity(&prices))
     }
 
+    /// Total equity converted to `base_currency`.
+    ///
+    /// Args:
+    ///     prices: List of (symbol, price_in_cents) tuples
+    ///     fx_rates: List of (from, to, rate) tuples; one `from` is worth
+    ///         `rate` units of `to`
+    ///     base_currency: Currency to value the portfolio in
+    ///
+    /// Returns:
+    ///     (equity_in_cents, missing) where `missing` lists currencies that
+    ///     had no rate and were left out of the total.
+    #[pyo3(signature = (prices, fx_rates, base_currency="USD"))]
+    fn total_equity_fx(
+        &self,
+        prices: Vec<(String, i64)>,
+        fx_rates: Vec<(String, String, f64)>,
+        base_currency: &str,
+    ) -> PyResult<(i64, Vec<String>)> {
+        let prices = parse_price_list(&prices)?;
+        let rates = fx_rates
+            .iter()
+            .map(|(from, to, rate)| Ok((parse_symbol(from)?, parse_symbol(to)?, *rate)))
+            .collect::<PyResult<Vec<_>>>()?;
+        let value = self
+            .inner
+            .total_equity_fx(&prices, &rates, parse_symbol(base_currency)?);
+        let missing = value.missing_rates.iter().map(|c| c.to_string()).collect();
+        Ok((value.equity, missing))
+    }
+
     /// Current portfolio weights.
     ///
     /// Returns list of (symbol, weight) tuples.
@@ -164,6 +295,44 @@ impl PyPortfolio {
         Ok(())
     }
 
+    /// Rebalance to target weights, skipping symbols within a no-trade band.
+    ///
+    /// Args:
+    ///     targets: List of (symbol, weight) tuples.
+    ///     prices: List of (symbol, price_in_cents) tuples.
+    ///     no_trade_band: Only trade when |current - target| weight exceeds this.
+    fn rebalance_with_band(
+        &mut self,
+        targets: Vec<(String, f64)>,
+        prices: Vec<(String, i64)>,
+        no_trade_band: f64,
+    ) -> PyResult<()> {
+        let targets = parse_target_list(&targets)?;
+        let prices = parse_price_list(&prices)?;
+        self.inner
+            .rebalance_with_band(&targets, &prices, no_trade_band);
+        Ok(())
+    }
+
+    /// One-sided turnover of the last rebalance versus `prior_weights`.
+    ///
+    /// Args:
+    ///     prior_weights: List of (symbol, weight) tuples held before the rebalance.
+    fn turnover(&self, prior_weights: Vec<(String, f64)>) -> PyResult<f64> {
+        let prior = parse_target_list(&prior_weights)?;
+        Ok(self.inner.turnover(&prior))
+    }
+
+    /// Shares the leverage cap refused to trade in the last rebalance or close,
+    /// as a list of (symbol, shares) tuples.
+    fn last_skipped(&self) -> Vec<(String, i64)> {
+        self.inner
+            .last_skipped()
+            .iter()
+            .map(|(sym, qty)| (sym.to_string(), *qty))
+            .collect()
+    }
+
     /// Rebalance through LOB matching engines.
     fn rebalance_lob(
         &mut self,
@@ -236,6 +405,18 @@ impl PyPortfolio {
     }
 }
 
+/// Parse a cost basis method name ("average", "fifo", "lifo").
+fn parse_cost_basis(name: &str) -> PyResult<CostBasisMethod> {
+    match name.to_ascii_lowercase().as_str() {
+        "average" | "avg" => Ok(CostBasisMethod::AverageCost),
+        "fifo" => Ok(CostBasisMethod::Fifo),
+        "lifo" => Ok(CostBasisMethod::Lifo),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Invalid cost_basis: '{name}'. Use 'average', 'fifo', or 'lifo'"
+        ))),
+    }
+}
+
 /// Parse Python list of (str, i64) into Vec<(Symbol, i64)>.
 fn parse_price_list(prices: &[(String, i64)]) -> PyResult<Vec<(nanobook::Symbol, i64)>> {
     prices
@@ -34,10 +34,55 @@ impl PyPosition {
         self.inner.realized_pnl
     }
 
+    #[getter]
+    fn bought(&self) -> i64 {
+        self.inner.bought
+    }
+
+    #[getter]
+    fn sold(&self) -> i64 {
+        self.inner.sold
+    }
+
+    #[getter]
+    fn currency(&self) -> String {
+        self.inner.currency.to_string()
+    }
+
     fn unrealized_pnl(&self, price: i64) -> i64 {
         self.inner.unrealized_pnl(price)
     }
 
+    /// Price at which an isolated-margin position at ``leverage`` hits
+    /// ``maintenance_margin_pct`` (a fraction). None if flat.
+    fn liquidation_price(&self, maintenance_margin_pct: f64, leverage: f64) -> Option<i64> {
+        self.inner
+            .liquidation_price(maintenance_margin_pct, leverage)
+    }
+
+    /// Exit price covering ``fee_bps`` on entry and exit. None if flat.
+    fn break_even_price(&self, fee_bps: f64) -> Option<i64> {
+        self.inner.break_even_price(fee_bps)
+    }
+
+    /// Open lots as (quantity, price) tuples, oldest first.
+    fn lots(&self) -> Vec<(i64, i64)> {
+        self.inner
+            .lots()
+            .iter()
+            .map(|lot| (lot.quantity, lot.price))
+            .collect()
+    }
+
+    /// Closed lot slices as (quantity, entry_price, exit_price, realized_pnl) tuples.
+    fn realized_pnl_by_lot(&self) -> Vec<(i64, i64, i64, i64)> {
+        self.inner
+            .realized_pnl_by_lot()
+            .iter()
+            .map(|c| (c.quantity, c.entry_price, c.exit_price, c.realized_pnl))
+            .collect()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "Position(symbol={}, qty={}, avg_price={}, realized_pnl={})",
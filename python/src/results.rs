@@ -1,7 +1,11 @@
-use crate::metrics::PyMetrics;
+use std::collections::BTreeSet;
+
+use crate::metrics::{PyMetrics, PyRelativeMetrics};
 use crate::portfolio::PyPortfolio;
 use crate::types::{price_to_float, side_str};
+use nanobook::backtest_bridge::{BacktestBridgeResult, BacktestStopEvent};
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
 
 /// Result of a backtest run.
 #[pyclass(name = "BacktestResult")]
@@ -33,6 +37,160 @@ impl From<nanobook::portfolio::BacktestResult> for PyBacktestResult {
     }
 }
 
+/// Result of `backtest_weights`.
+///
+/// A `dict` subclass, so existing key access keeps working, with column views
+/// that drop straight into `pd.DataFrame(...)`.
+#[pyclass(name = "BacktestWeightsResult", extends = PyDict)]
+pub struct PyBacktestWeightsResult {
+    returns: Vec<f64>,
+    equity_curve: Vec<i64>,
+    holdings: Vec<Vec<(String, f64)>>,
+    stop_events: Vec<BacktestStopEvent>,
+}
+
+impl PyBacktestWeightsResult {
+    pub fn create(
+        py: Python<'_>,
+        result: BacktestBridgeResult,
+    ) -> PyResult<Bound<'_, PyBacktestWeightsResult>> {
+        let holdings: Vec<Vec<(String, f64)>> = result
+            .holdings
+            .into_iter()
+            .map(|period| {
+                period
+                    .into_iter()
+                    .map(|(s, w)| (s.to_string(), w))
+                    .collect()
+            })
+            .collect();
+
+        let symbol_returns: Vec<Vec<(String, f64)>> = result
+            .symbol_returns
+            .into_iter()
+            .map(|period| {
+                period
+                    .into_iter()
+                    .map(|(s, r)| (s.to_string(), r))
+                    .collect()
+            })
+            .collect();
+
+        let stop_events = PyList::empty(py);
+        for ev in &result.stop_events {
+            let item = PyDict::new(py);
+            item.set_item("period_index", ev.period_index)?;
+            item.set_item("symbol", ev.symbol.to_string())?;
+            item.set_item("trigger_price", ev.trigger_price)?;
+            item.set_item("exit_price", ev.exit_price)?;
+            item.set_item("reason", ev.reason)?;
+            stop_events.append(item)?;
+        }
+
+        let trades = PyList::empty(py);
+        for trade in &result.trades {
+            let item = PyDict::new(py);
+            item.set_item("period_index", trade.period_index)?;
+            item.set_item("symbol", trade.symbol.to_string())?;
+            item.set_item("side", side_str(trade.side))?;
+            item.set_item("quantity", trade.quantity)?;
+            item.set_item("price", trade.price)?;
+            item.set_item("cost", trade.cost)?;
+            trades.append(item)?;
+        }
+
+        let obj = Bound::new(
+            py,
+            Self {
+                returns: result.returns.clone(),
+                equity_curve: result.equity_curve.clone(),
+                holdings: holdings.clone(),
+                stop_events: result.stop_events,
+            },
+        )?;
+
+        let dict = obj.downcast::<PyDict>()?;
+        dict.set_item("returns", result.returns)?;
+        dict.set_item("equity_curve", result.equity_curve)?;
+        dict.set_item("final_cash", result.final_cash)?;
+        dict.set_item("metrics", result.metrics.map(PyMetrics::from))?;
+        dict.set_item(
+            "relative_metrics",
+            result.relative_metrics.map(PyRelativeMetrics::from),
+        )?;
+        dict.set_item("holdings", holdings)?;
+        dict.set_item("symbol_returns", symbol_returns)?;
+        dict.set_item("stop_events", stop_events)?;
+        dict.set_item("turnover", result.turnover)?;
+        dict.set_item("trades", trades)?;
+        Ok(obj)
+    }
+}
+
+#[pymethods]
+impl PyBacktestWeightsResult {
+    /// Per-period columns: ``period``, ``return``, ``equity`` (end-of-period
+    /// equity in cents), then one weight column per symbol ever held (0.0 when
+    /// not held), in symbol order.
+    fn to_records<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let periods = self.returns.len();
+        let symbols: BTreeSet<&str> = self
+            .holdings
+            .iter()
+            .flatten()
+            .map(|(s, _)| s.as_str())
+            .collect();
+
+        let records = PyDict::new(py);
+        records.set_item("period", (0..periods).collect::<Vec<_>>())?;
+        records.set_item("return", &self.returns)?;
+        records.set_item("equity", &self.equity_curve[1..=periods])?;
+        for symbol in symbols {
+            let weights: Vec<f64> = (0..periods)
+                .map(|i| {
+                    self.holdings
+                        .get(i)
+                        .and_then(|period| period.iter().find(|(s, _)| s == symbol))
+                        .map_or(0.0, |(_, w)| *w)
+                })
+                .collect();
+            records.set_item(symbol, weights)?;
+        }
+        Ok(records)
+    }
+
+    /// Stop-event columns: ``period_index``, ``symbol``, ``trigger_price``,
+    /// ``exit_price``, ``reason`` (empty lists when nothing triggered).
+    fn stop_events_records<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let events = &self.stop_events;
+        let records = PyDict::new(py);
+        records.set_item(
+            "period_index",
+            events.iter().map(|e| e.period_index).collect::<Vec<_>>(),
+        )?;
+        records.set_item(
+            "symbol",
+            events
+                .iter()
+                .map(|e| e.symbol.to_string())
+                .collect::<Vec<_>>(),
+        )?;
+        records.set_item(
+            "trigger_price",
+            events.iter().map(|e| e.trigger_price).collect::<Vec<_>>(),
+        )?;
+        records.set_item(
+            "exit_price",
+            events.iter().map(|e| e.exit_price).collect::<Vec<_>>(),
+        )?;
+        records.set_item(
+            "reason",
+            events.iter().map(|e| e.reason).collect::<Vec<_>>(),
+        )?;
+        Ok(records)
+    }
+}
+
 /// Result of submitting an order.
 #[pyclass(name = "SubmitResult")]
 #[derive(Clone)]
@@ -47,6 +205,8 @@ pub struct PySubmitResult {
     pub resting_quantity: u64,
     #[pyo3(get)]
     pub cancelled_quantity: u64,
+    #[pyo3(get)]
+    pub cascade_truncated: bool,
     pub trades: Vec<PyTrade>,
 }
 
@@ -78,6 +238,7 @@ impl From<nanobook::SubmitResult> for PySubmitResult {
             filled_quantity: r.filled_quantity,
             resting_quantity: r.resting_quantity,
             cancelled_quantity: r.cancelled_quantity,
+            cascade_truncated: r.cascade_truncated,
             trades: r.trades.into_iter().map(PyTrade::from).collect(),
         }
     }
@@ -137,6 +298,8 @@ pub struct PyModifyResult {
     pub trades: Vec<PyTrade>,
     #[pyo3(get)]
     pub error: Option<String>,
+    #[pyo3(get)]
+    pub priority_kept: bool,
 }
 
 #[pymethods]
@@ -172,6 +335,52 @@ impl From<nanobook::ModifyResult> for PyModifyResult {
             cancelled_quantity: r.cancelled_quantity,
             trades: r.trades.into_iter().map(PyTrade::from).collect(),
             error: r.error.map(|e| format!("{e:?}")),
+            priority_kept: r.priority_kept,
+        }
+    }
+}
+
+/// Result of an auction uncross.
+#[pyclass(name = "AuctionResult")]
+#[derive(Clone)]
+pub struct PyAuctionResult {
+    #[pyo3(get)]
+    pub clearing_price: Option<i64>,
+    #[pyo3(get)]
+    pub matched_volume: u64,
+    #[pyo3(get)]
+    pub imbalance: i64,
+    #[pyo3(get)]
+    pub cascade_truncated: bool,
+    pub trades: Vec<PyTrade>,
+}
+
+#[pymethods]
+impl PyAuctionResult {
+    #[getter]
+    fn trades(&self) -> Vec<PyTrade> {
+        self.trades.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "AuctionResult(clearing_price={:?}, matched_volume={}, imbalance={}, trades={})",
+            self.clearing_price,
+            self.matched_volume,
+            self.imbalance,
+            self.trades.len(),
+        )
+    }
+}
+
+impl From<nanobook::AuctionResult> for PyAuctionResult {
+    fn from(r: nanobook::AuctionResult) -> Self {
+        Self {
+            clearing_price: r.clearing_price.map(|p| p.0),
+            matched_volume: r.matched_volume,
+            imbalance: r.imbalance,
+            cascade_truncated: r.cascade_truncated,
+            trades: r.trades.into_iter().map(PyTrade::from).collect(),
         }
     }
 }
@@ -184,6 +393,8 @@ pub struct PyStopSubmitResult {
     pub order_id: u64,
     #[pyo3(get)]
     pub status: String,
+    #[pyo3(get)]
+    pub cascade_truncated: bool,
 }
 
 #[pymethods]
@@ -201,6 +412,7 @@ impl From<nanobook::StopSubmitResult> for PyStopSubmitResult {
         Self {
             order_id: r.order_id.0,
             status: format!("{:?}", r.status),
+            cascade_truncated: r.cascade_truncated,
         }
     }
 }
@@ -268,6 +480,10 @@ pub struct PyLevelSnapshot {
     pub quantity: u64,
     #[pyo3(get)]
     pub order_count: usize,
+    #[pyo3(get)]
+    pub oldest_timestamp: Option<u64>,
+    #[pyo3(get)]
+    pub newest_timestamp: Option<u64>,
 }
 
 #[pymethods]
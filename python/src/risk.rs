@@ -43,6 +43,7 @@ impl PyRiskEngine {
         max_order_value_cents=10_000_000,
         max_batch_value_cents=100_000_000,
     ))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         max_position_pct: f64,
         max_leverage: f64,
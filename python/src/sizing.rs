@@ -0,0 +1,37 @@
+use nanobook::portfolio::sizing;
+use pyo3::prelude::*;
+
+/// Kelly-optimal fraction of capital: ``win_rate - (1 - win_rate) / payoff_ratio``.
+///
+/// Returns 0.0 if ``payoff_ratio`` is not positive and finite.
+///
+/// Example::
+///
+///     f = nanobook.py_kelly_fraction(0.55, 1.2)
+///
+#[pyfunction]
+pub fn py_kelly_fraction(win_rate: f64, payoff_ratio: f64) -> f64 {
+    sizing::kelly_fraction(win_rate, payoff_ratio)
+}
+
+/// Weight scaling an asset to a target volatility:
+/// ``signal * target_vol / asset_vol``.
+///
+/// Returns 0.0 if ``asset_vol`` is not positive and finite.
+#[pyfunction]
+pub fn py_vol_target_weight(signal: f64, asset_vol: f64, target_vol: f64) -> f64 {
+    sizing::vol_target_weight(signal, asset_vol, target_vol)
+}
+
+/// Inverse-volatility weights summing to 1.
+///
+/// Assets with a non-positive or non-finite volatility get weight 0.
+///
+/// Example::
+///
+///     weights = nanobook.py_equal_risk_weights([0.15, 0.30, 0.45])
+///
+#[pyfunction]
+pub fn py_equal_risk_weights(vols: Vec<f64>) -> Vec<f64> {
+    sizing::equal_risk_weights(&vols)
+}
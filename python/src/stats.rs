@@ -1,3 +1,4 @@
+use crate::types::parse_symbol;
 use nanobook::stats;
 use pyo3::prelude::*;
 
@@ -44,3 +45,79 @@ pub fn py_spearman(x: Vec<f64>, y: Vec<f64>) -> (f64, f64) {
 pub fn py_quintile_spread(scores: Vec<f64>, returns: Vec<f64>, n_quantiles: usize) -> f64 {
     stats::quintile_spread(&scores, &returns, n_quantiles)
 }
+
+/// Build dollar-neutral long/short weights from a cross-sectional signal.
+///
+/// Ranks symbols by signal, longs the top quantile and shorts the bottom
+/// (equal weight within each leg, +0.5 / -0.5 gross per leg). Ties are
+/// ordered by symbol.
+///
+/// Args:
+///     signals: List of (symbol, signal) tuples.
+///     n_quantiles: Number of groups (default 5).
+///     long_top: Long the highest signals (default True); False swaps legs.
+///
+/// Returns:
+///     List of (symbol, weight) tuples in rank order; symbols outside both
+///     legs are omitted. Empty if there are fewer symbols than quantiles.
+///
+/// Example::
+///
+///     weights = nanobook.py_quantile_portfolio([("AAPL", 0.3), ...], 5)
+///
+#[pyfunction]
+#[pyo3(signature = (signals, n_quantiles=5, long_top=true))]
+pub fn py_quantile_portfolio(
+    signals: Vec<(String, f64)>,
+    n_quantiles: usize,
+    long_top: bool,
+) -> PyResult<Vec<(String, f64)>> {
+    let signals = signals
+        .iter()
+        .map(|(symbol, signal)| Ok((parse_symbol(symbol)?, *signal)))
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(stats::quantile_portfolio(&signals, n_quantiles, long_top)
+        .into_iter()
+        .map(|(symbol, weight)| (symbol.as_str().to_string(), weight))
+        .collect())
+}
+
+/// Compute the information coefficient (rank IC) of a signal.
+///
+/// Spearman rank correlation between ``signal`` and the forward return that
+/// followed each observation. Non-finite pairs are dropped.
+///
+/// Args:
+///     signal: Signal values (list of floats).
+///     forward_return: Next-period returns (list of floats, same length).
+///
+/// Returns:
+///     IC in [-1, 1], or None with fewer than 2 valid pairs, mismatched
+///     lengths, or a constant input.
+///
+/// Example::
+///
+///     ic = nanobook.py_information_coefficient(scores, next_returns)
+///
+#[pyfunction]
+pub fn py_information_coefficient(signal: Vec<f64>, forward_return: Vec<f64>) -> Option<f64> {
+    stats::information_coefficient(&signal, &forward_return)
+}
+
+/// Compute the cross-sectional rank IC for each period.
+///
+/// Args:
+///     signals: One list of per-asset signals per period.
+///     returns: One list of per-asset forward returns per period.
+///
+/// Returns:
+///     List of ICs, one per period; NaN where the IC is undefined.
+///
+/// Example::
+///
+///     ics = nanobook.py_rolling_ic(signal_matrix, forward_return_matrix)
+///
+#[pyfunction]
+pub fn py_rolling_ic(signals: Vec<Vec<f64>>, returns: Vec<Vec<f64>>) -> Vec<f64> {
+    stats::rolling_ic(&signals, &returns)
+}
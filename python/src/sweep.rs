@@ -1,7 +1,10 @@
+use nanobook::backtest_bridge::sweep_stop_configs;
 use nanobook::portfolio::sweep::sweep_strategy;
 use nanobook::portfolio::{CostModel, EqualWeight};
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 
+use crate::backtest_bridge::{parse_schedule, parse_stop_cfg};
 use crate::metrics::PyMetrics;
 use crate::types::parse_symbol;
 
@@ -71,3 +74,68 @@ pub fn py_sweep_equal_weight(
         .map(|r| r.metrics.map(PyMetrics::from))
         .collect())
 }
+
+/// Run ``backtest_weights`` once per stop configuration, in parallel.
+///
+/// Releases the GIL while Rayon runs the backtests. Results are in the
+/// order of ``configs`` regardless of thread scheduling.
+///
+/// Args:
+///     weight_schedule: Per-period list of (symbol, weight).
+///     price_schedule: Per-period list of (symbol, price_cents).
+///     initial_cash: Starting cash in cents
+///     cost_bps: Transaction cost in basis points
+///     configs: Stop config dicts, same keys as ``backtest_weights``'s
+///         ``stop_cfg`` (``fixed_stop_pct``, ``trailing_stop_pct``,
+///         ``atr_multiple``, ``atr_period``)
+///     periods_per_year: Annualization factor
+///     risk_free: Risk-free rate per period
+///
+/// Returns:
+///     List of (config, Metrics or None), one per config
+///
+/// Example::
+///
+///     grid = [{"fixed_stop_pct": p} for p in (0.05, 0.10, 0.15)]
+///     results = sweep_stop_configs(weights, prices, 1_000_000_00, 10, grid)
+///     best_cfg, best = max(results, key=lambda r: r[1].sharpe)
+///
+#[pyfunction]
+#[pyo3(name = "sweep_stop_configs")]
+#[pyo3(signature = (weight_schedule, price_schedule, initial_cash, cost_bps, configs, periods_per_year=252.0, risk_free=0.0))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_sweep_stop_configs<'py>(
+    py: Python<'py>,
+    weight_schedule: Vec<Vec<(String, f64)>>,
+    price_schedule: Vec<Vec<(String, i64)>>,
+    initial_cash: i64,
+    cost_bps: u32,
+    configs: Vec<Bound<'py, PyDict>>,
+    periods_per_year: f64,
+    risk_free: f64,
+) -> PyResult<Vec<(Bound<'py, PyDict>, Option<PyMetrics>)>> {
+    let weights = parse_schedule(&weight_schedule)?;
+    let prices = parse_schedule(&price_schedule)?;
+    let stop_configs = configs
+        .iter()
+        .map(|cfg| Ok(parse_stop_cfg(Some(cfg.clone()))?.unwrap_or_default()))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let results = py.allow_threads(|| {
+        sweep_stop_configs(
+            &weights,
+            &prices,
+            initial_cash,
+            cost_bps,
+            periods_per_year,
+            risk_free,
+            &stop_configs,
+        )
+    });
+
+    Ok(configs
+        .into_iter()
+        .zip(results)
+        .map(|(cfg, (_, metrics))| (cfg, metrics.map(PyMetrics::from)))
+        .collect())
+}
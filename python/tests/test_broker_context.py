@@ -0,0 +1,68 @@
+"""Context-manager and close semantics of the broker wrappers (no live connection)."""
+
+import pytest
+
+import nanobook
+
+
+class RecordingIbkr(nanobook.IbkrBroker):
+    """Skips the network connect and records lifecycle calls."""
+
+    def __init__(self, *args):
+        self.calls = []
+
+    def connect(self):
+        self.calls.append("connect")
+
+    def disconnect(self):
+        self.calls.append("disconnect")
+        super().disconnect()
+
+
+def test_with_block_connects_and_disconnects():
+    broker = RecordingIbkr("127.0.0.1", 4002, 1)
+    with broker as b:
+        assert b is broker
+        assert broker.calls == ["connect"]
+    assert broker.calls == ["connect", "disconnect"]
+
+
+def test_with_block_disconnects_on_error():
+    broker = RecordingIbkr("127.0.0.1", 4002, 1)
+    with pytest.raises(ValueError, match="boom"):
+        with broker:
+            raise ValueError("boom")
+    assert broker.calls == ["connect", "disconnect"]
+
+
+def test_use_after_exit_raises_not_connected():
+    broker = RecordingIbkr("127.0.0.1", 4002, 1)
+    with broker:
+        pass
+    with pytest.raises(RuntimeError, match="not connected"):
+        broker.positions()
+
+
+def test_close_is_idempotent():
+    broker = nanobook.IbkrBroker("127.0.0.1", 4002, 1)
+    broker.close()
+    broker.close()
+    broker.disconnect()
+    with pytest.raises(RuntimeError, match="not connected"):
+        broker.quote("AAPL")
+
+
+def test_failed_connect_propagates_from_with():
+    # Nothing listens on port 1, so entering the block fails.
+    with pytest.raises(RuntimeError):
+        with nanobook.IbkrBroker("127.0.0.1", 1, 1):
+            pass
+
+
+@pytest.mark.skipif(not hasattr(nanobook, "BinanceBroker"), reason="built without binance")
+def test_binance_close_is_idempotent():
+    broker = nanobook.BinanceBroker("key", "secret", testnet=True)
+    broker.close()
+    broker.close()
+    with pytest.raises(RuntimeError, match="not connected"):
+        broker.positions()
@@ -0,0 +1,68 @@
+"""Broker records and exceptions through the Python boundary, via MockBroker."""
+
+import pytest
+
+import nanobook
+
+
+def test_mock_positions_round_trip():
+    # Large and negative values must survive the i64 boundary unchanged.
+    held = [("AAPL", 100, 185_00), ("TSLA", -25, 250_50), ("BRK.A", 3, 650_000_00_00)]
+    with nanobook.MockBroker(positions=held) as broker:
+        positions = broker.positions()
+
+    assert [(p.symbol, p.quantity, p.avg_cost_cents) for p in positions] == held
+    assert [p.market_value_cents for p in positions] == [q * c for _, q, c in held]
+    assert all(p.unrealized_pnl_cents == 0 for p in positions)
+    assert positions[1] == nanobook.BrokerPosition("TSLA", -25, 250_50, -25 * 250_50)
+
+
+def test_records_keep_dict_access():
+    with nanobook.MockBroker(positions=[("AAPL", 10, 100_00)]) as broker:
+        pos = broker.positions()[0]
+        account = broker.account()
+        status = broker.order_status(broker.submit_order("AAPL", "buy", 5))
+
+    assert pos["symbol"] == "AAPL"
+    assert pos.to_dict()["quantity"] == 10
+    with pytest.raises(KeyError):
+        pos["missing"]
+    assert account["gross_position_value_cents"] == 1_000_00
+    assert status.status == "Filled"
+    assert status["id"] == status.id
+    assert status["commission_cents"] == status.commission_cents == 0
+
+
+def test_mock_account_and_orders():
+    with nanobook.MockBroker(equity_cents=500_000_00, cash_cents=200_000_00) as broker:
+        account = broker.account()
+        broker.submit_order("AAPL", "sell", 7, order_type="limit", limit_price_cents=190_00)
+        broker.cancel_order(1)
+
+    assert account == nanobook.BrokerAccount(500_000_00, 200_000_00, 200_000_00)
+    assert broker.submitted_orders() == [("AAPL", "sell", 7)]
+
+
+def test_errors_map_to_specific_types():
+    broker = nanobook.MockBroker(reject_orders=True)
+    with pytest.raises(nanobook.NotConnectedError, match="not connected"):
+        broker.positions()
+
+    with broker:
+        with pytest.raises(nanobook.OrderError):
+            broker.submit_order("AAPL", "buy", 1)
+        with pytest.raises(nanobook.InvalidSymbolError, match="MSFT"):
+            broker.quote("MSFT")
+
+    for exc in (nanobook.NotConnectedError, nanobook.OrderError, nanobook.InvalidSymbolError):
+        assert issubclass(exc, nanobook.BrokerError)
+    assert issubclass(nanobook.BrokerError, RuntimeError)
+
+
+def test_reduce_only_orders():
+    with nanobook.MockBroker(positions=[("AAPL", 150, 100_00)]) as broker:
+        broker.submit_order("AAPL", "sell", 200, reduce_only=True)
+        with pytest.raises(nanobook.OrderError, match="increase exposure"):
+            broker.submit_order("MSFT", "buy", 10, reduce_only=True)
+
+    assert broker.submitted_orders() == [("AAPL", "sell", 150)]
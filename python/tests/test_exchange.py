@@ -59,6 +59,47 @@ def test_submit_market():
     assert result.status == "Filled"
 
 
+def test_execution_quality():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 100, "gtc")
+    ex.submit_limit("buy", 10100, 60, "gtc")
+    ex.submit_limit("buy", 10000, 40, "gtc")
+
+    stats = ex.execution_quality()
+    assert stats["limit_trades"] == 2
+    assert stats["improved_trades"] == 1
+    assert stats["improvement_cents"] == 60 * 100
+    assert stats["trade_throughs"] == 0
+    assert stats["improvement_per_unit"] == 60.0
+
+
+def test_submit_market_fok():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 50, "gtc")
+    rejected = ex.submit_market_fok("buy", 100)
+    assert rejected.status == "Cancelled"
+    assert rejected.cancelled_quantity == 100
+    assert len(rejected.trades) == 0
+    assert ex.best_ask() == 10000
+
+    filled = ex.submit_market_fok("buy", 50)
+    assert filled.status == "Filled"
+    assert filled.filled_quantity == 50
+
+
+def test_delayed_orders_match_in_arrival_order():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 100, "gtc")
+    ex.submit_limit_delayed("buy", 10000, 100, 500)
+    ex.submit_limit_delayed("buy", 10100, 100, 100)
+    assert ex.pending_delayed_count() == 2
+    assert ex.advance_to(50) == []
+    results = ex.advance_to(1000)
+    assert [r.filled_quantity for r in results] == [100, 0]
+    assert ex.best_bid() == 10000
+    assert ex.sim_time() == 1000
+
+
 def test_cancel():
     ex = nanobook.Exchange()
     submit = ex.submit_limit("buy", 10000, 100, "gtc")
@@ -74,6 +115,37 @@ def test_cancel_nonexistent():
     assert result.error is not None
 
 
+
+def test_cancel_partial():
+    ex = nanobook.Exchange()
+    first = ex.submit_limit("buy", 10000, 100, "gtc")
+    ex.submit_limit("buy", 10000, 50, "gtc")
+    result = ex.cancel_partial(first.order_id, 40)
+    assert result.success
+    assert result.cancelled_quantity == 40
+    fill = ex.submit_market("sell", 60)
+    assert fill.trades[0].passive_order_id == first.order_id
+    assert fill.trades[0].quantity == 60
+
+
+def test_cancel_stale():
+    ex = nanobook.Exchange()
+    ids = [ex.submit_limit("buy", price, 100, "gtc").order_id for price in (9900, 9800, 9700)]
+    assert ex.cancel_stale(3) == ids[:2]
+    assert ex.best_bid() == 9700
+    assert ex.cancel_stale(3) == []
+
+
+def test_id_offsets():
+    ex = nanobook.Exchange(order_offset=1_000_000, trade_offset=500)
+    ex.submit_limit("sell", 10000, 100, "gtc")
+    result = ex.submit_limit("buy", 10000, 40, "gtc")
+    assert result.order_id == 1_000_002
+    assert result.trades[0].trade_id == 501
+    replayed = nanobook.Exchange.replay(ex.events())
+    assert replayed.submit_limit("buy", 9900, 10, "gtc").order_id == 1_000_003
+
+
 def test_modify():
     ex = nanobook.Exchange()
     submit = ex.submit_limit("buy", 10000, 100, "gtc")
@@ -81,6 +153,35 @@ def test_modify():
     assert result.success
     assert result.new_order_id is not None
     assert result.cancelled_quantity == 100
+    assert not result.priority_kept
+
+
+def test_modify_preserving_priority():
+    ex = nanobook.Exchange()
+    first = ex.submit_limit("buy", 10000, 100, "gtc")
+    ex.submit_limit("buy", 10000, 50, "gtc")
+    result = ex.modify(first.order_id, 10000, 40, preserve_priority=True)
+    assert result.success
+    assert result.priority_kept
+    assert result.new_order_id == first.order_id
+    fill = ex.submit_market("sell", 40)
+    assert fill.trades[0].passive_order_id == first.order_id
+    assert not ex.modify(first.order_id, 9900, 10, preserve_priority=True).priority_kept
+
+
+def test_trade_price_rule():
+    ex = nanobook.Exchange(trade_price_rule="aggressor")
+    ex.submit_limit("sell", 10000, 100, "gtc")
+    result = ex.submit_limit("buy", 10500, 100, "gtc")
+    assert result.trades[0].price == 10500
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 100, "gtc")
+    assert ex.submit_limit("buy", 10500, 100, "gtc").trades[0].price == 10000
+    try:
+        nanobook.Exchange(trade_price_rule="vwap")
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
 
 
 def test_best_bid_ask():
@@ -109,6 +210,90 @@ def test_depth():
     assert len(snap.asks) == 1
 
 
+def test_depth_level_timestamps():
+    ex = nanobook.Exchange()
+    first = ex.submit_limit("buy", 10000, 100, "gtc")
+    second = ex.submit_limit("buy", 10000, 50, "gtc")
+    level = ex.depth(1).bids[0]
+    assert level.oldest_timestamp == ex.get_order(first.order_id).timestamp
+    assert level.newest_timestamp == ex.get_order(second.order_id).timestamp
+    ex.submit_market("sell", 100)
+    assert ex.depth(1).bids[0].oldest_timestamp == ex.get_order(second.order_id).timestamp
+    assert ex.full_book().aggregate_levels(5).bids[0].oldest_timestamp is None
+
+
+def test_depth_aggregate_levels():
+    ex = nanobook.Exchange()
+    for price in (10000, 10001, 10002):
+        ex.submit_limit("buy", price, 100, "gtc")
+    ex.submit_limit("sell", 10003, 50, "gtc")
+    binned = ex.full_book().aggregate_levels(5)
+    assert [(l.price, l.quantity, l.order_count) for l in binned.bids] == [(10000, 300, 3)]
+    assert [l.price for l in binned.asks] == [10005]
+
+
+def test_book_checksum():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10100, 75, "gtc")
+    ex.submit_limit("buy", 10000, 150, "gtc")
+    # zlib.crc32(b"10100:75;|10000:150;")
+    assert ex.book_checksum() == 0x704A8BFE
+    assert ex.depth(10).checksum(10) == ex.book_checksum(10)
+    ex.submit_limit("buy", 10000, 1, "gtc")
+    assert ex.book_checksum() != 0x704A8BFE
+
+
+def test_level_orders():
+    ex = nanobook.Exchange()
+    ids = [ex.submit_limit("buy", 10000, qty, "gtc").order_id for qty in (100, 200, 300)]
+    ex.submit_limit("sell", 10000, 40, "gtc")
+    assert [(i, q) for i, q, _ in ex.level_orders("buy", 10000)] == [
+        (ids[0], 60),
+        (ids[1], 200),
+        (ids[2], 300),
+    ]
+    ex.cancel(ids[1])
+    assert [i for i, _, _ in ex.level_orders("buy", 10000)] == [ids[0], ids[2]]
+    assert ex.level_orders("buy", 9900) == []
+
+
+def test_order_executions():
+    ex = nanobook.Exchange()
+    resting = ex.submit_limit("sell", 10100, 100, "gtc").order_id
+    for qty in (30, 50, 20):
+        ex.submit_market("buy", qty)
+    executions = ex.order_executions(resting)
+    assert [(price, qty) for _, price, qty, _ in executions] == [(10100, 30), (10100, 50), (10100, 20)]
+    assert ex.get_order(resting).executions == executions
+    assert ex.order_executions(999) is None
+
+
+def test_queue_ahead_and_fill_probability():
+    ex = nanobook.Exchange()
+    front = ex.submit_limit("sell", 10100, 100, "gtc").order_id
+    back = ex.submit_limit("sell", 10100, 500, "gtc").order_id
+    assert ex.queue_ahead(front) == 0
+    assert ex.queue_ahead(back) == 100
+    assert ex.fill_probability(front, 10.0, 20.0) == 1.0
+    assert ex.fill_probability(back, 10.0, 20.0) == 0.0
+    assert ex.fill_probability(999, 10.0, 20.0) is None
+
+
+def test_trade_observer():
+    ex = nanobook.Exchange()
+    seen = []
+    ex.set_trade_observer(lambda t: seen.append((t.price, t.quantity)))
+    ex.submit_limit("sell", 10000, 30, "gtc")
+    ex.submit_limit("sell", 10100, 50, "gtc")
+    ex.submit_stop_market("buy", 10100, 20)
+    ex.submit_limit("buy", 10100, 40, "gtc")
+    assert seen == [(10000, 30), (10100, 10), (10100, 20)]
+
+    ex.set_trade_observer(None)
+    ex.submit_limit("buy", 10100, 5, "gtc")
+    assert len(seen) == 3
+
+
 def test_trades():
     ex = nanobook.Exchange()
     ex.submit_limit("sell", 10000, 100, "gtc")
@@ -118,6 +303,18 @@ def test_trades():
     assert trades[0].quantity == 100
 
 
+def test_trades_to_csv():
+    ex = nanobook.Exchange()
+    assert ex.trades_to_csv() == (
+        "trade_id,timestamp,price,quantity,aggressor_side,maker_order_id,taker_order_id\n"
+    )
+    ex.submit_limit("sell", 10150, 100, "gtc")
+    ex.submit_limit("buy", 10150, 40, "gtc")
+    lines = ex.trades_to_csv().splitlines()
+    assert lines[1] == "1,3,$101.50,40,BUY,1,2"
+    assert ex.events_to_csv().splitlines()[1] == "0,SubmitLimit,SELL,$101.50,,100,GTC,,"
+
+
 def test_trade_repr():
     ex = nanobook.Exchange()
     ex.submit_limit("sell", 10050, 100, "gtc")
@@ -134,6 +331,45 @@ def test_stop_market():
     assert ex.pending_stop_count() == 1
 
 
+def test_stop_cascade_depth_limit():
+    ex = nanobook.Exchange()
+    ex.set_max_cascade_depth(3)
+    for i in range(11):
+        ex.submit_limit("sell", 10000 + i * 100, 10, "gtc")
+    for i in range(10):
+        ex.submit_stop_market("buy", 10000 + i * 100, 10)
+
+    result = ex.submit_limit("buy", 10000, 10, "gtc")
+    assert result.cascade_truncated
+    assert ex.truncated_cascades() == 1
+    assert ex.pending_stop_count() == 7
+    assert ex.max_cascade_depth() == 3
+
+
+def test_stop_market_slippage_cap():
+    ex = nanobook.Exchange()
+    for price, qty in [(10000, 50), (9900, 30), (9700, 30), (9400, 60)]:
+        ex.submit_limit("buy", price, qty, "gtc")
+    ex.submit_stop_market("sell", 10000, 100, max_slippage=300)
+    ex.submit_limit("sell", 10000, 50, "gtc")
+    assert [t.price for t in ex.trades()[1:]] == [9900, 9700]
+    assert ex.best_bid_ask() == (9400, None)
+
+
+def test_market_if_touched():
+    ex = nanobook.Exchange()
+    ex.submit_limit("buy", 9800, 50, "gtc")
+    ex.submit_limit("sell", 9850, 100, "gtc")
+    mit = ex.submit_mit("buy", 9800, 60)
+    assert mit.status == "Pending"
+    assert ex.get_stop_order(mit.order_id)["kind"] == "if_touched"
+    ex.submit_limit("sell", 9800, 50, "gtc")
+    assert ex.pending_stop_count() == 0
+    fill = ex.trades()[-1]
+    assert fill.price == 9850
+    assert fill.quantity == 60
+
+
 def test_cancel_stop():
     ex = nanobook.Exchange()
     stop = ex.submit_stop_market("buy", 10500, 100)
@@ -167,6 +403,19 @@ def test_trailing_stop_atr():
     assert result.status == "Pending"
 
 
+def test_trailing_stop_limit_offset():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 50, "gtc")
+    result = ex.submit_trailing_stop_limit_offset(
+        "sell", 9500, 100, 50, "fixed", 300
+    )
+    assert ex.get_stop_order(result.order_id)["limit_price"] == 9400
+    ex.submit_limit("buy", 10000, 50, "gtc")
+    stop = ex.get_stop_order(result.order_id)
+    assert stop["stop_price"] == 9700
+    assert stop["limit_price"] == 9600
+
+
 def test_clear_trades():
     ex = nanobook.Exchange()
     ex.submit_limit("sell", 10000, 100, "gtc")
@@ -174,6 +423,50 @@ def test_clear_trades():
     assert len(ex.trades()) == 1
     ex.clear_trades()
     assert len(ex.trades()) == 0
+    assert ex.trade_count() == 1
+    assert ex.total_matched_quantity() == 100
+    assert ex.total_matched_notional() == 1_000_000
+
+
+
+def test_auction_uncross():
+    ex = nanobook.Exchange()
+    ex.begin_auction()
+    assert ex.in_auction()
+    ex.submit_limit("buy", 10100, 200, "gtc")
+    ex.submit_limit("sell", 10000, 150, "gtc")
+    assert ex.trades() == []
+
+    result = ex.run_auction()
+    assert not ex.in_auction()
+    assert result.clearing_price in (10000, 10100)
+    assert result.matched_volume == 150
+    assert result.imbalance == 50
+    assert sum(t.quantity for t in result.trades) == 150
+    assert any(e.kind == "run_auction" for e in ex.events())
+
+
+def test_imbalance_history():
+    ex = nanobook.Exchange()
+    ex.enable_imbalance_recording(capacity=2)
+    ex.submit_limit("buy", 10000, 300, "gtc")
+    ex.submit_limit("sell", 10100, 100, "gtc")
+    ex.submit_limit("sell", 10100, 100, "gtc")
+    history = ex.imbalance_history()
+    assert [imb for _, imb in history] == [0.5, 0.2]
+    assert history[0][0] < history[1][0]
+
+def test_submit_batch():
+    orders = [("sell", 10100, 50, "gtc"), ("sell", 10200, 50, "gtc"), ("buy", 10150, 80, "ioc")]
+    batched = nanobook.Exchange()
+    results = batched.submit_batch(orders)
+    single = nanobook.Exchange()
+    expected = [single.submit_limit(*order) for order in orders]
+
+    assert [r.filled_quantity for r in results] == [e.filled_quantity for e in expected]
+    assert results[2].filled_quantity == 50
+    assert batched.best_bid_ask() == single.best_bid_ask()
+    assert [e.kind for e in batched.events()] == ["submit_batch"]
 
 
 def test_invalid_side():
@@ -148,3 +148,25 @@ def test_parse_itch_zero_length():
             nanobook.parse_itch(path)
     finally:
         os.unlink(path)
+
+def _add_order(ref, price):
+    payload = b'A' + struct.pack(">HH6sQcI8sI", 1, 0, b'\x00' * 6, ref, b'B', 100, b'AAPL    ', price)
+    return struct.pack(">H", len(payload)) + payload
+
+def _mold_packet(seq, messages):
+    # MoldUDP64 downstream: session(10), sequence(u64), count(u16), messages
+    return b'SESSION001' + struct.pack(">QH", seq, len(messages)) + b''.join(messages)
+
+def test_parse_moldudp64_reports_gap():
+    first = _mold_packet(1, [_add_order(1, 990000), _add_order(2, 980000)])
+    second = _mold_packet(4, [_add_order(4, 970000)])  # sequence 3 missing
+
+    events, gaps = nanobook.parse_moldudp64(first + second)
+    assert [symbol for symbol, _ in events] == ["AAPL"] * 3
+    assert all(event.kind == "submit_limit" for _, event in events)
+    assert gaps == [(3, 4)]
+
+def test_parse_moldudp64_truncated_packet():
+    packet = _mold_packet(1, [_add_order(1, 990000)])
+    with pytest.raises(ValueError, match="truncated"):
+        nanobook.parse_moldudp64(packet[:-1])
@@ -0,0 +1,40 @@
+import os
+import tempfile
+
+import nanobook
+import pytest
+
+
+def write_csv(text):
+    fd, path = tempfile.mkstemp(suffix=".csv")
+    with os.fdopen(fd, "w") as f:
+        f.write(text)
+    return path
+
+
+def test_load_csv_replays():
+    path = write_csv(
+        "action,side,price,quantity,order_id\n"
+        "limit,sell,101.50,100,\n"
+        "limit,buy,101.00,50,\n"
+        "cancel,,,,2\n"
+        "market,buy,,30,\n"
+    )
+    try:
+        events = nanobook.load_csv(path, 0, 1, 2, 3, order_id=4, price_scale=100.0)
+    finally:
+        os.remove(path)
+
+    assert [e.kind for e in events] == ["submit_limit", "submit_limit", "cancel", "submit_market"]
+    ex = nanobook.Exchange.replay(events)
+    assert ex.best_bid() is None
+    assert ex.best_ask() == 101_50
+
+
+def test_load_csv_reports_bad_line():
+    path = write_csv("limit\tbuy\t10100\t10\nlimit\tbuy\tx\t10\n")
+    try:
+        with pytest.raises(OSError, match="line 2"):
+            nanobook.load_csv(path, 0, 1, 2, 3, delimiter="\t", has_header=False)
+    finally:
+        os.remove(path)
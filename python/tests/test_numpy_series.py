@@ -0,0 +1,88 @@
+"""numpy in/out for indicator and rolling-metric functions.
+
+Array inputs are read zero-copy and results come back as numpy arrays; the
+list path must stay bit-for-bit identical.
+"""
+
+import time
+
+import nanobook
+import pytest
+
+np = pytest.importorskip("numpy")
+
+N = 100_000
+
+
+@pytest.fixture(scope="module")
+def series():
+    rng = np.random.default_rng(7)
+    close = 100.0 + np.cumsum(rng.normal(0.0, 1.0, N))
+    high = close + rng.uniform(0.0, 1.0, N)
+    low = close - rng.uniform(0.0, 1.0, N)
+    returns = rng.normal(0.0005, 0.01, N)
+    return close, high, low, returns
+
+
+def _bits(values):
+    return np.asarray(values, dtype=np.float64).view(np.uint64)
+
+
+def _assert_same(from_array, from_list):
+    assert isinstance(from_array, np.ndarray)
+    assert from_array.dtype == np.float64
+    assert isinstance(from_list, list)
+    np.testing.assert_array_equal(_bits(from_array), _bits(from_list))
+
+
+CASES = [
+    ("rsi", lambda c, h, l, r: nanobook.py_rsi(c, 14)),
+    ("macd", lambda c, h, l, r: nanobook.py_macd(c, 12, 26, 9)),
+    ("bbands", lambda c, h, l, r: nanobook.py_bbands(c, 20, 2.0, 2.0)),
+    ("bbands_ext", lambda c, h, l, r: nanobook.py_bbands_ext(c, 20, 2.0, 2.0)),
+    ("atr", lambda c, h, l, r: nanobook.py_atr(h, l, c, 14)),
+    ("roc", lambda c, h, l, r: nanobook.py_roc(c, 10)),
+    ("momentum", lambda c, h, l, r: nanobook.py_momentum(c, 10)),
+    ("cci", lambda c, h, l, r: nanobook.py_cci(h, l, c, 14)),
+    ("keltner", lambda c, h, l, r: nanobook.py_keltner(h, l, c, 20, 2.0)),
+    ("donchian", lambda c, h, l, r: nanobook.py_donchian(h, l, 20)),
+    ("rolling_sharpe", lambda c, h, l, r: nanobook.py_rolling_sharpe(r, 63, 252)),
+    ("rolling_volatility", lambda c, h, l, r: nanobook.py_rolling_volatility(r, 63, 252)),
+]
+
+
+@pytest.mark.parametrize("name,call", CASES, ids=[c[0] for c in CASES])
+def test_array_matches_list_bit_for_bit(series, name, call):
+    as_lists = [s.tolist() for s in series]
+
+    start = time.perf_counter()
+    from_array = call(*series)
+    array_time = time.perf_counter() - start
+
+    start = time.perf_counter()
+    from_list = call(*as_lists)
+    list_time = time.perf_counter() - start
+
+    if isinstance(from_array, tuple):
+        assert len(from_array) == len(from_list) == 3
+        for a, b in zip(from_array, from_list):
+            _assert_same(a, b)
+    else:
+        _assert_same(from_array, from_list)
+
+    print(f"{name}: array {array_time * 1e3:.2f} ms, list {list_time * 1e3:.2f} ms")
+
+
+def test_output_array_is_writable_and_owned():
+    out = nanobook.py_rsi(np.linspace(1.0, 2.0, 50), 14)
+    assert out.shape == (50,)
+    out[0] = 1.0  # memory owned by the Rust buffer, not shared with the input
+    assert out[0] == 1.0
+
+
+def test_non_float64_arrays_are_converted():
+    out = nanobook.py_rolling_volatility(np.arange(1, 200, dtype=np.int64), 20, 252)
+    assert isinstance(out, np.ndarray)
+
+    strided = np.linspace(1.0, 2.0, 200)[::2]  # non-contiguous view
+    _assert_same(nanobook.py_rsi(strided, 14), nanobook.py_rsi(strided.tolist(), 14))
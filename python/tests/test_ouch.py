@@ -0,0 +1,61 @@
+import struct
+
+import nanobook
+import pytest
+
+
+def soup(packet, msg):
+    # SoupBinTCP: u16 length (type byte + payload), packet type, payload
+    return struct.pack(">H", len(msg) + 1) + packet + msg
+
+
+def token(t):
+    return t.encode().ljust(14)
+
+
+def enter(tok, side, shares, price, tif=99_999):
+    # OUCH 4.2 Enter Order (O), 49 bytes
+    msg = (
+        b"O" + token(tok) + side + struct.pack(">I", shares) + b"AAPL    "
+        + struct.pack(">II", price, tif) + b"FIRM" + b"YPN"
+        + struct.pack(">I", 0) + b"NR"
+    )
+    return soup(b"U", msg)
+
+
+def replace(existing, replacement, shares, price):
+    # OUCH 4.2 Replace Order (U), 47 bytes
+    msg = (
+        b"U" + token(existing) + token(replacement)
+        + struct.pack(">III", shares, price, 99_999) + b"YN" + struct.pack(">I", 0)
+    )
+    return soup(b"U", msg)
+
+
+def cancel(tok, shares=0):
+    # OUCH 4.2 Cancel Order (X), 19 bytes
+    return soup(b"U", b"X" + token(tok) + struct.pack(">I", shares))
+
+
+def test_parse_ouch_submit_and_cancel():
+    data = enter("B1", b"B", 100, 990_000) + cancel("B1")
+    events = nanobook.parse_ouch(data)
+    assert [e.kind for e in events] == ["submit_limit", "cancel"]
+    # OUCH price 990,000 has 4 implied decimals -> 9,900 cents ($99.00)
+    assert "price: Price(9900)" in repr(events[0])
+    assert "order_id: OrderId(1)" in repr(events[1])
+
+
+def test_parse_ouch_replace_replays():
+    data = enter("B1", b"B", 100, 990_000) + replace("B1", "B2", 80, 995_000)
+    events = nanobook.parse_ouch(data)
+    assert events[1].kind == "modify"
+
+    ex = nanobook.Exchange.replay(events)
+    assert ex.best_bid() == 9950
+
+
+def test_parse_ouch_rejects_truncated_data():
+    data = enter("B1", b"B", 100, 990_000)[:-3]
+    with pytest.raises(ValueError):
+        nanobook.parse_ouch(data)
@@ -1,6 +1,7 @@
 """Tests for the Portfolio Python bindings."""
 
 import nanobook
+import pytest
 import tempfile
 import os
 
@@ -67,6 +68,14 @@ def test_compute_metrics():
     assert "Metrics" in repr(m)
 
 
+def test_compute_metrics_mar():
+    returns = [0.02, 0.03, 0.01, -0.005, 0.015, 0.001]
+    base = nanobook.py_compute_metrics(returns, 252.0, 0.0)
+    strict = nanobook.py_compute_metrics(returns, 252.0, 0.0, mar=0.012)
+    assert strict.sortino < base.sortino
+    assert strict.sharpe == base.sharpe
+
+
 def test_compute_metrics_empty():
     m = nanobook.py_compute_metrics([], 252.0, 0.0)
     assert m is None
@@ -102,3 +111,32 @@ def test_portfolio_save_load():
 def test_portfolio_repr():
     p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
     assert "Portfolio" in repr(p)
+
+
+def test_portfolio_total_equity_fx():
+    p = nanobook.Portfolio(
+        1_000_000_00, nanobook.CostModel.zero(), currencies=[("SAP", "EUR")]
+    )
+    prices = [("AAPL", 150_00), ("SAP", 120_00)]
+    p.rebalance_simple([("AAPL", 0.4), ("SAP", 0.4)], prices)
+    assert p.position("SAP").currency == "EUR"
+    assert p.position("AAPL").currency == "USD"
+
+    rates = [("EUR", "USD", 1.10)]
+    usd, missing_usd = p.total_equity_fx(prices, rates, "USD")
+    eur, missing_eur = p.total_equity_fx(prices, rates, "EUR")
+    assert missing_usd == [] and missing_eur == []
+    assert abs(usd / eur - 1.10) < 1e-6
+
+    partial, missing = p.total_equity_fx(prices, [], "USD")
+    assert missing == ["EUR"]
+    assert partial < usd
+
+
+def test_cost_model_fee_tiers():
+    model = nanobook.CostModel(maker_bps=-2, taker_bps=5)
+    assert model.compute_cost_with_liquidity(1_000_000, 100, maker=True) == -200
+    assert model.compute_cost_with_liquidity(1_000_000, 100) == 500
+
+    with pytest.raises(ValueError):
+        nanobook.CostModel(maker_bps=-2)
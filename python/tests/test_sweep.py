@@ -49,3 +49,20 @@ def test_sweep_multi_stock():
     )
     assert len(results) == 10
     assert all(r is not None for r in results)
+
+
+def test_sweep_stop_configs_matches_serial_backtests():
+    closes = [100_00, 104_00, 97_00, 92_00, 99_00, 106_00, 101_00]
+    prices = [[("AAPL", p)] for p in closes]
+    weights = [[("AAPL", 1.0)] for _ in closes]
+    grid = [{"fixed_stop_pct": pct} for pct in (0.03, 0.08, 0.20)]
+
+    results = nanobook.sweep_stop_configs(weights, prices, 100_000_00, 10, grid)
+
+    assert len(results) == 3
+    assert [cfg for cfg, _ in results] == grid
+    serial = [
+        nanobook.backtest_weights(weights, prices, 100_000_00, 10, stop_cfg=cfg)["metrics"]
+        for cfg in grid
+    ]
+    assert [m.sharpe for _, m in results] == [m.sharpe for m in serial]
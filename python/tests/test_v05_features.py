@@ -65,6 +65,17 @@ def test_exchange_events_and_replay():
     assert ex2.best_bid_ask() == ex.best_bid_ask()
     assert len(ex2.trades()) == len(ex.trades())
 
+def test_exchange_replay_checked():
+    ex = nanobook.Exchange()
+    ex.submit_limit("buy", 10000, 100)
+    ex.submit_limit("sell", 10000, 50)
+    replayed = nanobook.Exchange.replay_checked(ex.events())
+    assert replayed.best_bid_ask() == ex.best_bid_ask()
+
+    ex.cancel(999)
+    with pytest.raises(ValueError, match="event 2"):
+        nanobook.Exchange.replay_checked(ex.events())
+
 def test_event_serialization():
     ex = nanobook.Exchange()
     ex.submit_limit("buy", 10000, 100)
@@ -141,6 +152,13 @@ def test_position_pnl():
     assert pos.realized_pnl == 50_000_00 # profit on 5000 shares
     assert pos.quantity == 5000
 
+def test_position_liquidation_and_break_even():
+    portfolio = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
+    portfolio.rebalance_simple([("AAPL", 1.0)], [("AAPL", 100_00)])
+    pos = portfolio.position("AAPL")
+    assert pos.liquidation_price(0.005, 10.0) == 90_50
+    assert pos.break_even_price(10.0) == 100_21
+
 def test_position_shorting():
     portfolio = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
     # Target negative weight = short
@@ -171,6 +189,39 @@ def test_multiexchange_forwarding():
     assert price_dict["AAPL"] == (10000, None)
     assert price_dict["MSFT"] == (None, 20000)
 
+def test_multiexchange_contract_multiplier():
+    multi = nanobook.MultiExchange()
+    multi.register_symbol("ES", tick_size=25, multiplier=50)
+    assert multi.notional("ES", 4000_00, 2) == 400_000_00
+    assert multi.notional("AAPL", 4000_00, 2) == 8_000_00
+
+def test_multiexchange_remove_and_retain():
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("AAPL", "buy", 10000, 100)
+    multi.submit_limit("MSFT", "buy", 20000, 50)
+    multi.submit_limit("TSLA", "buy", 30000, 10)
+    multi.cancel("MSFT", 1)
+    assert multi.total_order_count() == 3
+
+    removed = multi.remove("TSLA")
+    assert removed.best_bid() == 30000
+    assert multi.remove("TSLA") is None
+    assert multi.retain_active() == ["MSFT"]
+    assert multi.symbols() == ["AAPL"]
+    assert multi.symbol_count() == 1
+
+def test_multiexchange_cross_venue_arbitrage():
+    multi = nanobook.MultiExchange()
+    multi.submit_limit_on("A", "AAPL", "buy", 101, 300)
+    assert multi.find_arbitrage("AAPL") is None
+    multi.submit_limit_on("B", "AAPL", "sell", 100, 200)
+    assert multi.find_arbitrage("AAPL") == {
+        "buy_venue": "B",
+        "sell_venue": "A",
+        "quantity": 200,
+        "spread": 1,
+    }
+
 def test_multiexchange_complex_forwarding():
     multi = nanobook.MultiExchange()
     multi.submit_limit("AAPL", "buy", 10000, 100)
@@ -241,6 +292,22 @@ def test_portfolio_rebalance_lob():
     assert portfolio.position("AAPL").quantity > 0
     assert multi.get_or_create("AAPL").best_ask() is None # Swept the book
 
+def test_portfolio_consolidated_position():
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("AAPL", "sell", 100_00, 100_000)
+    multi.submit_limit("AAPL", "buy", 99_00, 100_000)
+
+    portfolio = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
+    assert portfolio.consolidated_position("AAPL") is None
+    portfolio.rebalance_lob([("AAPL", 0.5)], multi)
+    portfolio.rebalance_lob([("AAPL", 0.1)], multi)
+
+    c = portfolio.consolidated_position("AAPL")
+    assert c["bought"] > c["sold"] > 0
+    assert c["net"] == c["bought"] - c["sold"] == portfolio.position("AAPL").quantity
+    assert portfolio.gross_traded_quantity("AAPL") == c["bought"] + c["sold"]
+    assert portfolio.gross_traded_quantity("MSFT") == 0
+
 def test_clear_order_history():
     ex = nanobook.Exchange()
     ex.submit_limit("buy", 10000, 100)
@@ -1,5 +1,7 @@
 import math
 
+import pytest
+
 import nanobook
 
 
@@ -25,11 +27,16 @@ def test_capabilities_surface():
     expected = {
         "backtest_stops",
         "garch_forecast",
+        "garch_gjr_forecast",
         "optimize_min_variance",
         "optimize_max_sharpe",
         "optimize_risk_parity",
         "optimize_cvar",
         "optimize_cdar",
+        "optimize_mean_variance",
+        "optimize_max_diversification",
+        "optimize_cvar_scenarios",
+        "optimize_min_variance_tc",
         "backtest_holdings",
     }
     assert expected.issubset(caps)
@@ -42,6 +49,77 @@ def test_garch_forecast_finite():
     assert v >= 0.0
 
 
+def test_garch_gjr_forecast_horizon():
+    v = nanobook.py_garch_gjr_forecast([0.01, -0.03, 0.007, -0.02, 0.004], horizon=3)
+    assert len(v) == 3
+    assert all(math.isfinite(x) and x > 0.0 for x in v)
+    assert nanobook.garch_gjr_forecast([0.01, -0.03, 0.007], horizon=2) == nanobook.py_garch_gjr_forecast(
+        [0.01, -0.03, 0.007], horizon=2
+    )
+
+
+def test_garch_fit_parameters():
+    returns = [0.01, -0.03, 0.007, -0.02, 0.004, 0.015, -0.011, 0.002, -0.006, 0.009] * 20
+    fit = nanobook.py_garch_fit(returns)
+    assert fit["omega"] > 0.0
+    assert fit["alpha"] >= 0.0 and fit["beta"] >= 0.0
+    assert abs(fit["persistence"] - (fit["alpha"] + fit["beta"])) < 1e-12
+    assert fit["stationary"] == (fit["persistence"] < 1.0)
+    assert math.isfinite(fit["log_likelihood"])
+    assert nanobook.py_garch_fit([0.01]) is None
+
+
+def test_garch_term_structure():
+    returns = [0.01, -0.03, 0.007, -0.02, 0.004, 0.015, -0.011, 0.002, -0.006, 0.009] * 20
+    path = nanobook.py_garch_term_structure(returns, horizon=5, periods_per_year=252.0)
+    assert len(path) == 5
+    for step in path:
+        assert step["variance"] > 0.0
+        assert abs(step["annualized_vol"] - math.sqrt(step["variance"] * 252.0)) < 1e-12
+
+
+def test_ewma_volatility_path():
+    returns = [0.01, -0.02, 0.005, 0.03, -0.015]
+    path = nanobook.py_ewma_volatility(returns, lambda_=0.94, periods_per_year=252.0)
+    assert len(path) == len(returns)
+    assert all(math.isfinite(v) and v >= 0.0 for v in path)
+
+    with pytest.raises(ValueError, match="lambda_"):
+        nanobook.py_ewma_volatility(returns, lambda_=1.0)
+
+
+def test_quantile_portfolio_quintiles():
+    signals = [(name, float(i)) for i, name in enumerate("ABCDEFGHIJ")]
+    weights = nanobook.py_quantile_portfolio(signals, 5)
+    assert weights == [("J", 0.25), ("I", 0.25), ("B", -0.25), ("A", -0.25)]
+    assert nanobook.py_quantile_portfolio(signals, 5, long_top=False)[0] == ("J", -0.25)
+
+    with pytest.raises(ValueError):
+        nanobook.py_quantile_portfolio([("TOOLONGNAME", 1.0)], 2)
+
+
+def test_information_coefficient():
+    signal = [0.3, -0.1, 0.8, 0.0, 0.5]
+    forward = [0.02, -0.01, 0.05, 0.001, 0.03]
+    assert nanobook.py_information_coefficient(signal, forward) == 1.0
+    assert nanobook.py_information_coefficient([-s for s in signal], forward) == -1.0
+    assert nanobook.py_information_coefficient([1.0], [0.1]) is None
+
+    ics = nanobook.py_rolling_ic([[1.0, 2.0, 3.0]] * 3, [[0.1, 0.2, 0.3], [0.3, 0.2, 0.1], [0.1]])
+    assert ics[:2] == [1.0, -1.0]
+    assert math.isnan(ics[2])
+
+
+def test_position_sizing_helpers():
+    assert abs(nanobook.py_kelly_fraction(0.5, 2.0) - 0.25) < 1e-12
+    base = nanobook.py_vol_target_weight(1.0, 0.20, 0.10)
+    assert abs(base - 0.5) < 1e-12
+    assert abs(nanobook.py_vol_target_weight(1.0, 0.40, 0.10) - base / 2) < 1e-12
+    weights = nanobook.py_equal_risk_weights([0.10, 0.20, 0.40])
+    assert abs(sum(weights) - 1.0) < 1e-12
+    assert weights[0] > weights[1] > weights[2]
+
+
 def test_optimizers_return_valid_weights():
     symbols = ["AAPL", "MSFT", "NVDA"]
     r = _sample_returns_matrix()
@@ -59,6 +137,103 @@ def test_optimizers_return_valid_weights():
     _assert_long_only_weights(cdar, symbols)
 
 
+def test_optimize_mean_variance_bounds_and_target():
+    symbols = ["LOW", "HIGH"]
+    mu = [0.05, 0.10]
+    cov = [[0.04, 0.006], [0.006, 0.09]]
+
+    capped = nanobook.py_optimize_mean_variance(mu, cov, symbols, max_weights=[0.4, 1.0])
+    _assert_long_only_weights(capped, symbols)
+    assert abs(capped["LOW"] - 0.4) < 1e-9
+
+    tilted = nanobook.py_optimize_mean_variance(mu, cov, symbols, target_return=0.09)
+    assert tilted["HIGH"] > 0.5
+    assert nanobook.py_optimize_mean_variance(mu, cov, symbols, target_return=0.2) == {}
+
+
+def test_optimize_cvar_scenarios_avoids_crash_tail():
+    symbols = ["A", "B", "CRASH"]
+    scenarios = [
+        [0.01 * ((i * 7) % 5 - 2), 0.01 * ((i * 3) % 5 - 2), -0.08 if i % 50 == 7 else 0.002]
+        for i in range(100)
+    ]
+    w = nanobook.py_optimize_cvar_scenarios(scenarios, symbols, alpha=0.95)
+    _assert_long_only_weights(w, symbols)
+    minvar = nanobook.py_optimize_min_variance(scenarios, symbols)
+    assert w["CRASH"] < minvar["CRASH"]
+
+    boxed = nanobook.optimize_cvar_scenarios(
+        scenarios, symbols, min_weights=[0.0, 0.0, 0.5], max_weights=[1.0, 1.0, 1.0]
+    )
+    assert abs(boxed["CRASH"] - 0.5) < 1e-9
+    assert nanobook.py_optimize_cvar_scenarios(scenarios, symbols, max_weights=[0.2] * 3) == {}
+
+
+def test_optimize_min_variance_tc_limits_turnover():
+    symbols = ["A", "B", "C"]
+    cov = [[0.04, 0.006, 0.002], [0.006, 0.09, 0.01], [0.002, 0.01, 0.0625]]
+    current = [0.2, 0.5, 0.3]
+
+    def turnover(w):
+        return sum(abs(w[s] - c) for s, c in zip(symbols, current))
+
+    free = nanobook.py_optimize_min_variance_tc(cov, current, symbols, 0.0)
+    _assert_long_only_weights(free, symbols)
+    plain = nanobook.py_optimize_mean_variance([0.0] * 3, cov, symbols)
+    assert all(abs(free[s] - plain[s]) < 1e-9 for s in symbols)
+
+    costly = nanobook.optimize_min_variance_tc(cov, current, symbols, 200.0)
+    _assert_long_only_weights(costly, symbols)
+    assert turnover(costly) < turnover(free)
+    assert nanobook.py_optimize_min_variance_tc(cov, current, symbols, -1.0) == {}
+
+
+def test_risk_contributions_sum_to_variance():
+    cov = [[0.04, 0.006], [0.006, 0.09]]
+    w = [0.7, 0.3]
+    rc = nanobook.py_risk_contributions(w, cov)
+    variance = sum(w[i] * cov[i][j] * w[j] for i in range(2) for j in range(2))
+    assert abs(sum(rc) - variance) < 1e-15
+
+    marginal = nanobook.py_marginal_risk(w, cov)
+    assert abs(sum(wi * m for wi, m in zip(w, marginal)) - math.sqrt(variance)) < 1e-12
+    assert nanobook.py_risk_contributions([1.0], cov) == []
+
+
+def test_iterative_optimizers_are_deterministic_and_report_convergence():
+    symbols = ["A", "B", "C"]
+    rows = [
+        [0.010, 0.004, -0.002],
+        [-0.003, 0.006, 0.001],
+        [0.007, -0.001, 0.002],
+        [0.004, 0.003, -0.004],
+        [-0.002, 0.005, 0.003],
+        [0.006, -0.002, 0.001],
+    ]
+    first = nanobook.py_optimize_risk_parity(rows, symbols)
+    for _ in range(100):
+        assert nanobook.py_optimize_risk_parity(rows, symbols) == first
+
+    capped = nanobook.py_optimize_min_variance_ext(rows, symbols, max_iter=2, tolerance=0.0)
+    assert capped["iterations"] == 2
+    assert capped["converged"] is False
+    _assert_long_only_weights(capped["weights"], symbols)
+
+    loose = nanobook.py_optimize_risk_parity_ext(rows, symbols, tolerance=1e-3)
+    default = nanobook.py_optimize_risk_parity_ext(rows, symbols)
+    assert loose["converged"] is True
+    assert loose["iterations"] < default["iterations"]
+    assert default["weights"] == first
+
+
+def test_optimize_max_diversification_equal_correlation():
+    symbols = ["A", "B", "C"]
+    cov = [[0.04, 0.012, 0.012], [0.012, 0.04, 0.012], [0.012, 0.012, 0.04]]
+    w = nanobook.py_optimize_max_diversification(cov, [0.2, 0.2, 0.2], symbols)
+    _assert_long_only_weights(w, symbols)
+    assert all(abs(v - 1.0 / 3.0) < 1e-9 for v in w.values())
+
+
 def test_backtest_weights_v09_payload():
     result = nanobook.py_backtest_weights(
         weight_schedule=[[('AAPL', 1.0)], [('AAPL', 1.0)]],
@@ -130,6 +305,22 @@ def test_backtest_reports_tightest_stop_reason():
     assert event["trigger_price"] == 104_50
 
 
+def test_backtest_weights_dividends():
+    kwargs = dict(
+        weight_schedule=[[("AAPL", 1.0)], [("AAPL", 1.0)]],
+        price_schedule=[[("AAPL", 100_00)], [("AAPL", 100_00)]],
+        initial_cash=10_000_00,
+        cost_bps=0,
+    )
+    base = nanobook.py_backtest_weights(**kwargs)
+    paid = nanobook.py_backtest_weights(
+        **kwargs, dividends=[[], [("AAPL", 1_00), ("MSFT", 5_00)]]
+    )
+
+    assert paid["final_cash"] - base["final_cash"] == 100_00
+    assert paid["returns"][1] > base["returns"][1]
+
+
 def test_clean_aliases_equivalent():
     symbols = ["AAPL", "MSFT", "NVDA"]
     r = _sample_returns_matrix()
@@ -153,3 +344,142 @@ def test_clean_aliases_equivalent():
     assert nanobook.optimize_cdar(r, symbols, alpha=0.95) == nanobook.py_optimize_cdar(
         r, symbols, alpha=0.95
     )
+
+
+def test_backtest_weights_to_records_columns():
+    weight_schedule = [
+        [("AAPL", 0.5), ("MSFT", 0.5)],
+        [("AAPL", 1.0)],
+        [("AAPL", 1.0)],
+    ]
+    result = nanobook.py_backtest_weights(
+        weight_schedule=weight_schedule,
+        price_schedule=[
+            [("AAPL", 100_00), ("MSFT", 50_00)],
+            [("AAPL", 85_00), ("MSFT", 51_00)],
+            [("AAPL", 86_00), ("MSFT", 52_00)],
+        ],
+        initial_cash=100_000_00,
+        cost_bps=0,
+        stop_cfg={"fixed_stop_pct": 0.10},
+    )
+    assert isinstance(result, dict)
+
+    records = result.to_records()
+    assert list(records) == ["period", "return", "equity", "AAPL", "MSFT"]
+    assert all(len(col) == len(weight_schedule) for col in records.values())
+    assert records["period"] == [0, 1, 2]
+    assert records["return"] == result["returns"]
+    assert records["equity"] == result["equity_curve"][1:]
+
+    # Weight columns follow the holdings schedule, 0.0 when not held.
+    for i, period in enumerate(result["holdings"]):
+        held = dict(period)
+        assert records["AAPL"][i] == held.get("AAPL", 0.0)
+        assert records["MSFT"][i] == held.get("MSFT", 0.0)
+    assert records["MSFT"][1:] == [0.0, 0.0]
+
+    stops = result.stop_events_records()
+    assert stops == {
+        "period_index": [1],
+        "symbol": ["AAPL"],
+        "trigger_price": [90_00],
+        "exit_price": [85_00],
+        "reason": ["fixed"],
+    }
+
+
+def test_backtest_weights_stop_records_empty_without_stops():
+    result = nanobook.backtest_weights(
+        weight_schedule=[[("AAPL", 1.0)]],
+        price_schedule=[[("AAPL", 100_00)]],
+        initial_cash=100_000_00,
+        cost_bps=0,
+    )
+    assert result.stop_events_records()["symbol"] == []
+    assert len(result.to_records()["period"]) == 1
+
+
+def test_backtest_weights_rebalance_calendar():
+    prices = [[("AAPL", 100_00 + 10 * t), ("MSFT", 100_00 - 5 * t)] for t in range(252)]
+    weights = [[("AAPL", 0.5), ("MSFT", 0.5)]] * len(prices)
+    kwargs = dict(
+        weight_schedule=weights,
+        price_schedule=prices,
+        initial_cash=1_000_000_00,
+        cost_bps=10,
+    )
+    daily = nanobook.backtest_weights(**kwargs)
+    monthly = nanobook.backtest_weights(
+        **kwargs, rebalance_on=[t % 21 == 0 for t in range(len(prices))]
+    )
+
+    assert sum(monthly["turnover"]) < sum(daily["turnover"])
+    assert monthly["turnover"][1] == 0.0
+    assert monthly["equity_curve"][-1] != daily["equity_curve"][-1]
+    assert dict(monthly["holdings"][20])["AAPL"] > 0.5
+
+
+def test_backtest_weights_warmup():
+    prices = [[("AAPL", 100_00 + (300 if t % 2 else -200) + 20 * t)] for t in range(40)]
+    weights = [[("AAPL", 0.8)]] * len(prices)
+    result = nanobook.backtest_weights(weights, prices, 100_000_00, 10, warmup=10)
+    truncated = nanobook.backtest_weights(weights[10:], prices[10:], 100_000_00, 10)
+
+    assert all(h == [] for h in result["holdings"][:10])
+    assert result["returns"][:10] == [0.0] * 10
+    assert result["returns"][10:] == truncated["returns"]
+    assert result["metrics"].sharpe == truncated["metrics"].sharpe
+
+
+def test_backtest_weights_record_trades():
+    weights = [[("AAPL", 0.4), ("MSFT", 0.4)], [("AAPL", 0.6)]]
+    prices = [[("AAPL", 100_00), ("MSFT", 200_00)], [("AAPL", 110_00), ("MSFT", 190_00)]]
+
+    assert nanobook.backtest_weights(weights, prices, 100_000_00, 10)["trades"] == []
+
+    result = nanobook.backtest_weights(weights, prices, 100_000_00, 10, record_trades=True)
+    trades = result["trades"]
+    assert len(trades) == 4
+    assert [t["side"] for t in trades[:2]] == ["buy", "buy"]
+    sign = {"buy": 1, "sell": -1}
+    spent = sum(sign[t["side"]] * t["quantity"] * t["price"] + t["cost"] for t in trades)
+    assert 100_000_00 - result["final_cash"] == spent
+
+
+def test_backtest_weights_short_stop_and_gross_bound():
+    prices = [[("AAPL", px)] for px in (100_00, 95_00, 90_00, 96_00)]
+    weights = [[("AAPL", -0.5)]] * len(prices)
+    result = nanobook.backtest_weights(
+        weights, prices, 100_000_00, 0, stop_cfg={"trailing_stop_pct": 0.05}
+    )
+    assert [(e["period_index"], e["reason"]) for e in result["stop_events"]] == [(3, "trailing")]
+
+    bounded = nanobook.backtest_weights(
+        [[("AAPL", 1.2), ("MSFT", -0.8)]],
+        [[("AAPL", 100_00), ("MSFT", 100_00)]],
+        100_000_00,
+        0,
+        max_gross_weight=1.0,
+    )
+    assert bounded["holdings"][0] == [("AAPL", 0.6), ("MSFT", -0.4)]
+
+
+def test_backtest_weights_benchmark_relative_metrics():
+    prices = [[("AAPL", px)] for px in (100_00, 103_00, 99_00, 104_00, 102_00, 107_00)]
+    weights = [[("AAPL", 1.0)]] * len(prices)
+    strategy = nanobook.backtest_weights(weights, prices, 100_000_00, 0)
+    assert strategy["relative_metrics"] is None
+
+    result = nanobook.backtest_weights(
+        weights, prices, 100_000_00, 0, benchmark_returns=strategy["returns"]
+    )
+    relative = result["relative_metrics"]
+    assert abs(relative.beta - 1.0) < 1e-12
+    assert relative.tracking_error == 0.0
+    assert relative.information_ratio == 0.0
+
+    short = nanobook.backtest_weights(
+        weights, prices, 100_000_00, 0, benchmark_returns=strategy["returns"][1:]
+    )
+    assert short["relative_metrics"] is None
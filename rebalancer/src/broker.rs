@@ -8,7 +8,7 @@ use nanobook_broker::ibkr::orders;
 use nanobook_broker::{
     BrokerSide,
     error::BrokerError,
-    types::{Account, Position},
+    types::{Account, Position, Quote},
 };
 
 use crate::config::Config;
@@ -25,6 +25,7 @@ pub trait BrokerGateway {
     fn account_summary(&self) -> BrokerResult<Account>;
     fn positions(&self) -> BrokerResult<Vec<Position>>;
     fn prices(&self, symbols: &[Symbol]) -> BrokerResult<Vec<(Symbol, i64)>>;
+    fn quotes(&self, symbols: &[Symbol]) -> BrokerResult<Vec<Quote>>;
     fn execute_limit_order(
         &self,
         symbol: Symbol,
@@ -48,6 +49,10 @@ impl BrokerGateway for IbkrClient {
         self.prices(symbols)
     }
 
+    fn quotes(&self, symbols: &[Symbol]) -> BrokerResult<Vec<Quote>> {
+        symbols.iter().map(|sym| self.quote(sym)).collect()
+    }
+
     fn execute_limit_order(
         &self,
         symbol: Symbol,
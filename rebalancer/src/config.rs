@@ -5,6 +5,8 @@ use std::path::Path;
 use serde::Deserialize;
 
 use crate::error::{Error, Result};
+use crate::execution::PricingStrategy;
+use crate::reconcile::ReconcileTolerance;
 
 /// Top-level configuration.
 #[derive(Debug, Clone, Deserialize)]
@@ -15,6 +17,8 @@ pub struct Config {
     pub risk: RiskConfig,
     pub cost: CostConfig,
     pub logging: LoggingConfig,
+    #[serde(default)]
+    pub reconcile: ReconcileConfig,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -54,6 +58,17 @@ pub struct ExecutionConfig {
     pub order_timeout_secs: u64,
     #[serde(default = "default_max_orders")]
     pub max_orders_per_run: usize,
+    /// Limit-order pricing; defaults to `OffsetBps(limit_offset_bps)`.
+    #[serde(default)]
+    pub pricing: Option<PricingStrategy>,
+}
+
+impl ExecutionConfig {
+    /// The configured pricing strategy, or a midpoint offset by `limit_offset_bps`.
+    pub fn pricing_strategy(&self) -> PricingStrategy {
+        self.pricing
+            .unwrap_or(PricingStrategy::OffsetBps(i64::from(self.limit_offset_bps)))
+    }
 }
 
 fn default_interval() -> u64 {
@@ -137,6 +152,41 @@ fn default_slippage() -> u32 {
     5
 }
 
+/// Drift tolerated by `reconcile` before a symbol needs a corrective trade.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReconcileConfig {
+    #[serde(default = "default_share_tolerance")]
+    pub share_tolerance: i64,
+    #[serde(default = "default_notional_tolerance")]
+    pub notional_tolerance_usd: f64,
+}
+
+impl Default for ReconcileConfig {
+    fn default() -> Self {
+        Self {
+            share_tolerance: default_share_tolerance(),
+            notional_tolerance_usd: default_notional_tolerance(),
+        }
+    }
+}
+
+impl ReconcileConfig {
+    /// Tolerance in the units `reconcile` works with.
+    pub fn tolerance(&self) -> ReconcileTolerance {
+        ReconcileTolerance {
+            shares: self.share_tolerance,
+            notional_cents: (self.notional_tolerance_usd * 100.0) as i64,
+        }
+    }
+}
+
+fn default_share_tolerance() -> i64 {
+    1
+}
+fn default_notional_tolerance() -> f64 {
+    100.0
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct LoggingConfig {
     #[serde(default = "default_log_dir")]
@@ -189,6 +239,14 @@ impl Config {
         if self.risk.max_short_pct < 0.0 || self.risk.max_short_pct > 1.0 {
             return Err(Error::Config("max_short_pct must be in [0.0, 1.0]".into()));
         }
+        if self.reconcile.share_tolerance < 0 {
+            return Err(Error::Config("share_tolerance must be >= 0".into()));
+        }
+        if !self.reconcile.notional_tolerance_usd.is_finite()
+            || self.reconcile.notional_tolerance_usd < 0.0
+        {
+            return Err(Error::Config("notional_tolerance_usd must be >= 0".into()));
+        }
         if self.execution.max_orders_per_run == 0 {
             return Err(Error::Config("max_orders_per_run must be > 0".into()));
         }
@@ -300,6 +358,50 @@ audit_file = "audit.jsonl"
         );
     }
 
+    #[test]
+    fn pricing_strategy_from_toml() {
+        let config: Config = toml::from_str(example_toml()).unwrap();
+        assert_eq!(
+            config.execution.pricing_strategy(),
+            PricingStrategy::OffsetBps(5)
+        );
+
+        let toml = example_toml().replace(
+            "max_orders_per_run = 50",
+            "max_orders_per_run = 50\npricing = \"join_bbo\"",
+        );
+        let config: Config = toml::from_str(&toml).unwrap();
+        assert_eq!(
+            config.execution.pricing_strategy(),
+            PricingStrategy::JoinBbo
+        );
+
+        let toml = example_toml().replace(
+            "max_orders_per_run = 50",
+            "max_orders_per_run = 50\npricing = { offset_bps = -3 }",
+        );
+        let config: Config = toml::from_str(&toml).unwrap();
+        assert_eq!(
+            config.execution.pricing_strategy(),
+            PricingStrategy::OffsetBps(-3)
+        );
+    }
+
+    #[test]
+    fn reconcile_section_is_optional() {
+        let config: Config = toml::from_str(example_toml()).unwrap();
+        assert_eq!(config.reconcile.share_tolerance, 1);
+        assert_eq!(config.reconcile.tolerance().notional_cents, 10_000);
+
+        let toml = format!(
+            "{}\n[reconcile]\nshare_tolerance = 5\nnotional_tolerance_usd = 250.0\n",
+            example_toml()
+        );
+        let config: Config = toml::from_str(&toml).unwrap();
+        assert_eq!(config.reconcile.tolerance().shares, 5);
+        assert_eq!(config.reconcile.tolerance().notional_cents, 25_000);
+    }
+
     #[test]
     fn cash_account_type() {
         let toml = example_toml().replace("\"margin\"", "\"cash\"");
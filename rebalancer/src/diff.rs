@@ -7,7 +7,7 @@
 
 use nanobook::Symbol;
 use rustc_hash::FxHashMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// A single rebalance order (computed diff).
 #[derive(Debug, Clone, Serialize)]
@@ -21,7 +21,7 @@ pub struct RebalanceOrder {
 }
 
 /// Trade direction.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Action {
     Buy,
     Sell,
@@ -147,7 +147,7 @@ pub fn compute_diff(
 }
 
 /// Classify a trade based on current position and desired change.
-fn classify_trade(current_qty: i64, diff_qty: i64) -> (Action, &'static str) {
+pub(crate) fn classify_trade(current_qty: i64, diff_qty: i64) -> (Action, &'static str) {
     match (current_qty, diff_qty) {
         // No position → buying long
         (0, d) if d > 0 => (Action::Buy, "open"),
@@ -171,7 +171,7 @@ fn classify_trade(current_qty: i64, diff_qty: i64) -> (Action, &'static str) {
 
 /// Compute limit price with offset from mid.
 /// Buys get mid + offset, sells get mid - offset.
-fn compute_limit_price(mid_cents: i64, is_sell: bool, offset_bps: u32) -> i64 {
+pub(crate) fn compute_limit_price(mid_cents: i64, is_sell: bool, offset_bps: u32) -> i64 {
     let offset = mid_cents * offset_bps as i64 / 10_000;
     if is_sell {
         mid_cents - offset
@@ -216,6 +216,66 @@ impl CostEstimate {
     }
 }
 
+/// Machine-readable order plan (`rebalancer run --dry-run --json`).
+///
+/// All amounts are in cents. An empty plan serializes as `{"orders": []}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Plan {
+    pub orders: Vec<PlanOrder>,
+    /// Sum of order notionals.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub turnover: i64,
+    /// Estimated commission plus slippage.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub est_cost: i64,
+}
+
+/// One order in a [`Plan`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanOrder {
+    pub symbol: Symbol,
+    pub side: Action,
+    pub quantity: i64,
+    pub limit_price: i64,
+    pub est_notional: i64,
+}
+
+fn is_zero(value: &i64) -> bool {
+    *value == 0
+}
+
+impl Plan {
+    /// Build a plan from computed orders and their estimated cost.
+    pub fn new(orders: &[RebalanceOrder], cost: &CostEstimate) -> Self {
+        if orders.is_empty() {
+            return Self {
+                orders: Vec::new(),
+                turnover: 0,
+                est_cost: 0,
+            };
+        }
+        Self {
+            orders: orders
+                .iter()
+                .map(|o| PlanOrder {
+                    symbol: o.symbol,
+                    side: o.action,
+                    quantity: o.shares,
+                    limit_price: o.limit_price_cents,
+                    est_notional: o.notional_cents,
+                })
+                .collect(),
+            turnover: orders.iter().map(|o| o.notional_cents).sum(),
+            est_cost: cost.total_cents(),
+        }
+    }
+
+    /// Serialize as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("plan contains only plain data")
+    }
+}
+
 impl std::fmt::Display for CostEstimate {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -230,6 +290,8 @@ impl std::fmt::Display for CostEstimate {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::inconsistent_digit_grouping)]
+
     use super::*;
 
     fn aapl() -> Symbol {
@@ -404,4 +466,41 @@ mod tests {
         assert_eq!(orders[0].shares, 100);
         assert_eq!(orders[0].description, "close short");
     }
+
+    #[test]
+    fn plan_json_round_trips() {
+        let orders = compute_diff(
+            100_000_00,
+            &[CurrentPosition {
+                symbol: msft(),
+                quantity: 10,
+                avg_cost_cents: 400_00,
+            }],
+            &[(aapl(), 0.5)],
+            &[(aapl(), 200_00), (msft(), 400_00)],
+            0,
+            0,
+        );
+        let cost = estimate_cost(&orders, 0.0035, 0.35, 5);
+        let plan = Plan::new(&orders, &cost);
+
+        let json = plan.to_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["orders"][0]["symbol"], "MSFT");
+        assert_eq!(value["orders"][0]["side"], "Sell");
+        assert_eq!(value["orders"][1]["quantity"], 250);
+        assert_eq!(value["orders"][1]["limit_price"], 200_00);
+        assert_eq!(value["turnover"], 54_000_00);
+
+        let back: Plan = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, plan);
+        assert_eq!(back.est_cost, cost.total_cents());
+    }
+
+    #[test]
+    fn empty_plan_json() {
+        let plan = Plan::new(&[], &estimate_cost(&[], 0.0035, 0.35, 5));
+        let value: serde_json::Value = serde_json::from_str(&plan.to_json()).unwrap();
+        assert_eq!(value, serde_json::json!({"orders": []}));
+    }
 }
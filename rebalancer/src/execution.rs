@@ -5,11 +5,12 @@
 use std::time::Duration;
 
 use log::{error, info, warn};
-use nanobook::Symbol;
-use nanobook_broker::BrokerSide;
+use nanobook::{Price, Symbol};
 use nanobook_broker::ibkr::orders::{self, OrderOutcome};
-use nanobook_broker::types::Position;
+use nanobook_broker::types::{Position, Quote};
+use nanobook_broker::{BrokerOrder, BrokerOrderType, BrokerSide};
 use rustc_hash::FxHashMap;
+use serde::Deserialize;
 
 use crate::audit::{self, AuditLog};
 use crate::broker::{as_connection_error, connect_ibkr};
@@ -24,9 +25,88 @@ use crate::target::TargetSpec;
 pub struct RunOptions {
     pub dry_run: bool,
     pub force: bool,
+    /// Print the dry-run plan as JSON on stdout; human output goes to stderr.
+    pub json: bool,
     pub target_file: String,
 }
 
+/// How limit prices are set from the current quote.
+///
+/// Configured as `pricing` under `[execution]`, e.g. `pricing = "join_bbo"` or
+/// `pricing = { offset_bps = 5 }`. Without it, orders are priced at
+/// `OffsetBps(limit_offset_bps)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PricingStrategy {
+    /// Bid/ask midpoint.
+    Midpoint,
+    /// The opposite touch (buy at the ask, sell at the bid) for urgency.
+    CrossSpread,
+    /// Our side's touch (buy at the bid, sell at the ask); passive.
+    JoinBbo,
+    /// Midpoint skewed by this many basis points toward the other side
+    /// (buys higher, sells lower); negative values skew passively.
+    OffsetBps(i64),
+}
+
+impl PricingStrategy {
+    /// Limit price in cents for `side`, given the current `quote`.
+    ///
+    /// A one-sided quote uses the side that is present as the midpoint and
+    /// for either touch; a quote with neither side falls back to the last
+    /// trade. With a zero spread, `Midpoint`, `CrossSpread`, and `JoinBbo`
+    /// all return the touch.
+    pub fn limit_price(self, quote: &Quote, side: BrokerSide) -> i64 {
+        let (bid, ask) = match (quote.bid_cents > 0, quote.ask_cents > 0) {
+            (true, true) => (quote.bid_cents, quote.ask_cents),
+            (true, false) => (quote.bid_cents, quote.bid_cents),
+            (false, true) => (quote.ask_cents, quote.ask_cents),
+            (false, false) => (quote.last_cents, quote.last_cents),
+        };
+        let mid = bid + (ask - bid) / 2;
+        match (self, side) {
+            (PricingStrategy::Midpoint, _) => mid,
+            (PricingStrategy::CrossSpread, BrokerSide::Buy)
+            | (PricingStrategy::JoinBbo, BrokerSide::Sell) => ask,
+            (PricingStrategy::CrossSpread, BrokerSide::Sell)
+            | (PricingStrategy::JoinBbo, BrokerSide::Buy) => bid,
+            (PricingStrategy::OffsetBps(bps), side) => {
+                let offset = mid.saturating_mul(bps) / 10_000;
+                match side {
+                    BrokerSide::Buy => mid + offset,
+                    BrokerSide::Sell => mid - offset,
+                }
+            }
+        }
+    }
+}
+
+/// Reprice `orders` from live `quotes` using `strategy`.
+///
+/// Orders without a quote keep the limit price from the diff.
+pub fn price_orders(orders: &mut [RebalanceOrder], quotes: &[Quote], strategy: PricingStrategy) {
+    let quote_map: FxHashMap<Symbol, &Quote> = quotes.iter().map(|q| (q.symbol, q)).collect();
+    for order in orders {
+        if let Some(quote) = quote_map.get(&order.symbol) {
+            order.limit_price_cents = strategy.limit_price(quote, action_to_side(order.action));
+        }
+    }
+}
+
+/// Turn a priced rebalance order into a generic limit `BrokerOrder`.
+pub fn to_broker_order(order: &RebalanceOrder) -> Result<BrokerOrder> {
+    let quantity = u64::try_from(order.shares)
+        .map_err(|_| Error::Order(format!("invalid share quantity for order {order:?}")))?;
+    Ok(BrokerOrder {
+        symbol: order.symbol,
+        side: action_to_side(order.action),
+        quantity,
+        order_type: BrokerOrderType::Limit(Price(order.limit_price_cents)),
+        reduce_only: false,
+        quantity_scaled: None,
+    })
+}
+
 /// Convert broker positions to rebalancer CurrentPosition type.
 fn to_current_positions(broker_positions: &[Position]) -> Vec<CurrentPosition> {
     broker_positions
@@ -71,20 +151,27 @@ pub fn run(config: &Config, target: &TargetSpec, opts: &RunOptions) -> Result<()
 
     // 3. Fetch account summary
     let summary = as_connection_error(client.account_summary())?;
-    println!(
+    let account_line = format!(
         "Account {} ({}): ${:.2} equity, ${:.2} cash",
         config.account.id,
         format!("{:?}", config.account.account_type).to_lowercase(),
         summary.equity_cents as f64 / 100.0,
         summary.cash_cents as f64 / 100.0,
     );
+    if opts.json {
+        eprintln!("{account_line}");
+    } else {
+        println!("{account_line}");
+    }
 
     // 4. Fetch current positions (convert from broker types to rebalancer types)
     let broker_positions = as_connection_error(client.positions())?;
     let positions = to_current_positions(&broker_positions);
     audit::log_positions(&mut audit, &positions, summary.equity_cents)?;
 
-    display_current_positions(&positions, summary.equity_cents);
+    if !opts.json {
+        display_current_positions(&positions, summary.equity_cents);
+    }
 
     // 5. Fetch live prices for all symbols (current + target)
     let all_symbols = collect_all_symbols(&positions, target);
@@ -94,7 +181,7 @@ pub fn run(config: &Config, target: &TargetSpec, opts: &RunOptions) -> Result<()
     let targets = target.as_target_pairs();
     let min_trade_cents = (config.risk.min_trade_usd * 100.0) as i64;
 
-    let orders = diff::compute_diff(
+    let mut orders = diff::compute_diff(
         summary.equity_cents,
         &positions,
         &targets,
@@ -105,8 +192,27 @@ pub fn run(config: &Config, target: &TargetSpec, opts: &RunOptions) -> Result<()
 
     enforce_max_orders_per_run(orders.len(), config.execution.max_orders_per_run)?;
 
+    // 6b. Set limit prices from live quotes
+    if !orders.is_empty() {
+        let order_symbols: Vec<Symbol> = orders.iter().map(|o| o.symbol).collect();
+        let quotes = as_connection_error(client.quotes(&order_symbols))?;
+        price_orders(&mut orders, &quotes, config.execution.pricing_strategy());
+    }
+
+    if opts.json {
+        let cost = diff::estimate_cost(
+            &orders,
+            config.cost.commission_per_share,
+            config.cost.commission_min,
+            config.cost.slippage_bps,
+        );
+        println!("{}", diff::Plan::new(&orders, &cost).to_json());
+    }
+
     if orders.is_empty() {
-        println!("\nNo rebalancing needed — portfolio matches target.");
+        if !opts.json {
+            println!("\nNo rebalancing needed — portfolio matches target.");
+        }
         audit.log_simple("no_rebalance_needed")?;
         return Ok(());
     }
@@ -114,8 +220,10 @@ pub fn run(config: &Config, target: &TargetSpec, opts: &RunOptions) -> Result<()
     audit::log_diff(&mut audit, &orders)?;
 
     // 7. Display the plan
-    display_plan(&orders, &config.cost);
-    println!();
+    if !opts.json {
+        display_plan(&orders, &config.cost);
+        println!();
+    }
 
     // 8. Run risk checks
     let current_qty: FxHashMap<Symbol, i64> =
@@ -131,7 +239,11 @@ pub fn run(config: &Config, target: &TargetSpec, opts: &RunOptions) -> Result<()
         &risk_config,
     );
 
-    print!("{risk_report}");
+    if opts.json {
+        eprint!("{risk_report}");
+    } else {
+        print!("{risk_report}");
+    }
     audit::log_risk_check(&mut audit, &risk_report)?;
 
     if risk_report.has_failures() {
@@ -142,7 +254,9 @@ pub fn run(config: &Config, target: &TargetSpec, opts: &RunOptions) -> Result<()
 
     // 9. Dry run stops here
     if opts.dry_run {
-        println!("\n[DRY RUN] No orders submitted.");
+        if !opts.json {
+            println!("\n[DRY RUN] No orders submitted.");
+        }
         return Ok(());
     }
 
@@ -182,14 +296,11 @@ pub fn run(config: &Config, target: &TargetSpec, opts: &RunOptions) -> Result<()
 
         submitted += 1;
 
-        let side = action_to_side(order.action);
-        let shares = u64::try_from(order.shares)
-            .map_err(|_| Error::Order(format!("invalid share quantity for order {order:?}")))?;
-
+        let broker_order = to_broker_order(order)?;
         match client.execute_limit_order(
-            order.symbol,
-            side,
-            shares,
+            broker_order.symbol,
+            broker_order.side,
+            broker_order.quantity,
             order.limit_price_cents,
             timeout,
         ) {
@@ -253,11 +364,13 @@ pub fn run(config: &Config, target: &TargetSpec, opts: &RunOptions) -> Result<()
     let final_prices = as_connection_error(client.prices(&all_symbols))?;
     let final_summary = as_connection_error(client.account_summary())?;
 
-    let report = reconcile::reconcile(
+    let report = reconcile::reconcile_with_tolerance(
         &final_positions,
         &targets,
         &final_prices,
         final_summary.equity_cents,
+        &config.reconcile.tolerance(),
+        config.execution.limit_offset_bps,
     );
     print!("\n{report}");
 
@@ -314,8 +427,23 @@ pub fn run_reconcile(config: &Config, target: &TargetSpec) -> Result<()> {
     let prices = as_connection_error(client.prices(&all_symbols))?;
     let targets = target.as_target_pairs();
 
-    let report = reconcile::reconcile(&positions, &targets, &prices, summary.equity_cents);
+    let report = reconcile::reconcile_with_tolerance(
+        &positions,
+        &targets,
+        &prices,
+        summary.equity_cents,
+        &config.reconcile.tolerance(),
+        config.execution.limit_offset_bps,
+    );
     print!("{report}");
+    if report.needs_trade() {
+        println!(
+            "\n{} corrective order(s) needed; run `rebalancer run` to apply them.",
+            report.corrections.len()
+        );
+    } else {
+        println!("\nAll drift within tolerance; nothing to do.");
+    }
 
     Ok(())
 }
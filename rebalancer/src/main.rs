@@ -37,6 +37,10 @@ enum Command {
         /// Skip confirmation prompt (for automation/cron)
         #[arg(long)]
         force: bool,
+
+        /// With --dry-run, print the order plan as JSON on stdout
+        #[arg(long, requires = "dry_run")]
+        json: bool,
     },
 
     /// Show current IBKR positions
@@ -72,6 +76,7 @@ fn main() {
             target,
             dry_run,
             force,
+            json,
         } => {
             let spec = match TargetSpec::load(&target) {
                 Ok(s) => s,
@@ -83,6 +88,7 @@ fn main() {
             let opts = RunOptions {
                 dry_run,
                 force,
+                json,
                 target_file: target.display().to_string(),
             };
             execution::run(&config, &spec, &opts)
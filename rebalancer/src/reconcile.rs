@@ -4,13 +4,47 @@ use nanobook::Symbol;
 use rustc_hash::FxHashMap;
 use serde::Serialize;
 
-use crate::diff::CurrentPosition;
+use crate::diff::{self, CurrentPosition, RebalanceOrder};
 
 /// Reconciliation report comparing actual vs target.
 #[derive(Debug, Clone, Serialize)]
 pub struct ReconcileReport {
     pub entries: Vec<ReconcileEntry>,
     pub tracking_error_pct: f64,
+    /// Orders that would bring every `NeedsTrade` symbol back to target.
+    pub corrections: Vec<RebalanceOrder>,
+}
+
+/// How far a symbol has drifted from its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ReconcileStatus {
+    /// Actual shares equal target shares.
+    Matched,
+    /// Off target, but within both the share and notional tolerance.
+    DriftWithinBand,
+    /// Off target by more than the tolerance; a corrective order is listed.
+    NeedsTrade,
+}
+
+impl std::fmt::Display for ReconcileStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReconcileStatus::Matched => write!(f, "MATCHED"),
+            ReconcileStatus::DriftWithinBand => write!(f, "IN BAND"),
+            ReconcileStatus::NeedsTrade => write!(f, "TRADE"),
+        }
+    }
+}
+
+/// Drift tolerated before a symbol needs a corrective trade.
+///
+/// A symbol is within band only if its share drift is at most `shares` and
+/// its drift notional is at most `notional_cents`. The default (all zero)
+/// flags any drift.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReconcileTolerance {
+    pub shares: i64,
+    pub notional_cents: i64,
 }
 
 /// One symbol's reconciliation entry.
@@ -23,16 +57,41 @@ pub struct ReconcileEntry {
     pub target_shares: i64,
     pub actual_shares: i64,
     pub diff_shares: i64,
+    pub status: ReconcileStatus,
 }
 
 /// Compare actual positions against targets.
 ///
 /// Returns a report with per-symbol comparison and overall tracking error.
+/// Any drift counts as `NeedsTrade`; see [`reconcile_with_tolerance`].
 pub fn reconcile(
     actual_positions: &[CurrentPosition],
     targets: &[(Symbol, f64)],
     prices: &[(Symbol, i64)],
     equity_cents: i64,
+) -> ReconcileReport {
+    reconcile_with_tolerance(
+        actual_positions,
+        targets,
+        prices,
+        equity_cents,
+        &ReconcileTolerance::default(),
+        0,
+    )
+}
+
+/// Compare actual positions against targets, classifying drift by `tolerance`.
+///
+/// Symbols that need a trade get a corrective order priced at the current
+/// price offset by `limit_offset_bps` (as in [`diff::compute_diff`]).
+/// Symbols without a price get no corrective order, even if flagged.
+pub fn reconcile_with_tolerance(
+    actual_positions: &[CurrentPosition],
+    targets: &[(Symbol, f64)],
+    prices: &[(Symbol, i64)],
+    equity_cents: i64,
+    tolerance: &ReconcileTolerance,
+    limit_offset_bps: u32,
 ) -> ReconcileReport {
     let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
     let target_map: FxHashMap<Symbol, f64> = targets.iter().copied().collect();
@@ -52,6 +111,7 @@ pub fn reconcile(
     all_symbols.dedup();
 
     let mut entries = Vec::new();
+    let mut corrections = Vec::new();
     let mut sum_sq_diff = 0.0_f64;
 
     for sym in &all_symbols {
@@ -74,6 +134,35 @@ pub fn reconcile(
         let diff_weight = actual_weight - target_weight;
         sum_sq_diff += diff_weight * diff_weight;
 
+        let diff_shares = actual_qty - target_shares;
+        let drift_notional = diff_shares.abs().saturating_mul(price);
+        let status = if diff_shares == 0 {
+            ReconcileStatus::Matched
+        } else if diff_shares.abs() <= tolerance.shares
+            && drift_notional <= tolerance.notional_cents
+        {
+            ReconcileStatus::DriftWithinBand
+        } else {
+            ReconcileStatus::NeedsTrade
+        };
+
+        if status == ReconcileStatus::NeedsTrade && price > 0 {
+            let trade_qty = -diff_shares;
+            let (action, description) = diff::classify_trade(actual_qty, trade_qty);
+            corrections.push(RebalanceOrder {
+                symbol: *sym,
+                action,
+                shares: trade_qty.abs(),
+                limit_price_cents: diff::compute_limit_price(
+                    price,
+                    trade_qty < 0,
+                    limit_offset_bps,
+                ),
+                notional_cents: drift_notional,
+                description,
+            });
+        }
+
         entries.push(ReconcileEntry {
             symbol: sym.as_str().to_string(),
             target_weight,
@@ -81,7 +170,8 @@ pub fn reconcile(
             diff_weight,
             target_shares,
             actual_shares: actual_qty,
-            diff_shares: actual_qty - target_shares,
+            diff_shares,
+            status,
         });
     }
 
@@ -90,6 +180,16 @@ pub fn reconcile(
     ReconcileReport {
         entries,
         tracking_error_pct,
+        corrections,
+    }
+}
+
+impl ReconcileReport {
+    /// True if any symbol drifted beyond tolerance.
+    pub fn needs_trade(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|e| e.status == ReconcileStatus::NeedsTrade)
     }
 }
 
@@ -98,29 +198,49 @@ impl std::fmt::Display for ReconcileReport {
         writeln!(f, "RECONCILIATION:")?;
         writeln!(
             f,
-            "  {:8} {:>10} {:>10} {:>10} {:>10} {:>10}",
-            "Symbol", "Target%", "Actual%", "Diff%", "TargetQty", "ActualQty"
+            "  {:8} {:>10} {:>10} {:>10} {:>10} {:>10} {:>8}",
+            "Symbol", "Target%", "Actual%", "Diff%", "TargetQty", "ActualQty", "Status"
         )?;
         for e in &self.entries {
             writeln!(
                 f,
-                "  {:8} {:>9.2}% {:>9.2}% {:>+9.2}% {:>10} {:>10}",
+                "  {:8} {:>9.2}% {:>9.2}% {:>+9.2}% {:>10} {:>10} {:>8}",
                 e.symbol,
                 e.target_weight * 100.0,
                 e.actual_weight * 100.0,
                 e.diff_weight * 100.0,
                 e.target_shares,
                 e.actual_shares,
+                e.status.to_string(),
             )?;
         }
         writeln!(f, "\n  Tracking error: {:.3}%", self.tracking_error_pct)?;
+        if self.corrections.is_empty() {
+            writeln!(f, "  No corrective trades needed.")?;
+        } else {
+            writeln!(f, "\n  CORRECTIVE ORDERS:")?;
+            for order in &self.corrections {
+                writeln!(
+                    f,
+                    "  {:10} {:8} {:>8} @ ${:.2} ({})",
+                    order.action.to_string(),
+                    order.symbol,
+                    order.shares,
+                    order.limit_price_cents as f64 / 100.0,
+                    order.description,
+                )?;
+            }
+        }
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::inconsistent_digit_grouping)]
+
     use super::*;
+    use crate::diff::Action;
 
     fn aapl() -> Symbol {
         Symbol::new("AAPL")
@@ -183,6 +303,85 @@ mod tests {
         assert!(msft_entry.actual_shares > 0);
     }
 
+    #[test]
+    fn classifies_drift_against_tolerance() {
+        let positions = vec![
+            CurrentPosition {
+                symbol: aapl(),
+                quantity: 999, // one share short of target
+                avg_cost_cents: 100_00,
+            },
+            CurrentPosition {
+                symbol: msft(),
+                quantity: 1_500, // 500 shares over target
+                avg_cost_cents: 100_00,
+            },
+            CurrentPosition {
+                symbol: Symbol::new("SPY"),
+                quantity: 500,
+                avg_cost_cents: 100_00,
+            },
+        ];
+        let targets = vec![(aapl(), 0.25), (msft(), 0.25), (Symbol::new("SPY"), 0.125)];
+        let prices = vec![
+            (aapl(), 100_00),
+            (msft(), 100_00),
+            (Symbol::new("SPY"), 100_00),
+        ];
+        let tolerance = ReconcileTolerance {
+            shares: 2,
+            notional_cents: 500_00,
+        };
+
+        let report =
+            reconcile_with_tolerance(&positions, &targets, &prices, 400_000_00, &tolerance, 0);
+
+        let status = |sym: &str| {
+            report
+                .entries
+                .iter()
+                .find(|e| e.symbol == sym)
+                .unwrap()
+                .status
+        };
+        assert_eq!(status("AAPL"), ReconcileStatus::DriftWithinBand);
+        assert_eq!(status("MSFT"), ReconcileStatus::NeedsTrade);
+        assert_eq!(status("SPY"), ReconcileStatus::Matched);
+        assert!(report.needs_trade());
+
+        assert_eq!(report.corrections.len(), 1);
+        let fix = &report.corrections[0];
+        assert_eq!(fix.symbol, msft());
+        assert_eq!(fix.action, Action::Sell);
+        assert_eq!(fix.shares, 500);
+        assert_eq!(fix.notional_cents, 50_000_00);
+    }
+
+    #[test]
+    fn notional_tolerance_flags_expensive_single_share() {
+        let positions = vec![CurrentPosition {
+            symbol: aapl(),
+            quantity: 9,
+            avg_cost_cents: 1_000_00,
+        }];
+        let tolerance = ReconcileTolerance {
+            shares: 1,
+            notional_cents: 500_00,
+        };
+        // Off by one $1,000 share: within the share band, outside notional.
+        let report = reconcile_with_tolerance(
+            &positions,
+            &[(aapl(), 1.0)],
+            &[(aapl(), 1_000_00)],
+            10_000_00,
+            &tolerance,
+            10,
+        );
+        assert_eq!(report.entries[0].status, ReconcileStatus::NeedsTrade);
+        assert_eq!(report.corrections[0].action, Action::Buy);
+        assert_eq!(report.corrections[0].limit_price_cents, 1_001_00);
+    }
+
     #[test]
     fn display_format() {
         let report = ReconcileReport {
@@ -194,11 +393,14 @@ mod tests {
                 target_shares: 2702,
                 actual_shares: 2648,
                 diff_shares: -54,
+                status: ReconcileStatus::NeedsTrade,
             }],
             tracking_error_pct: 1.0,
+            corrections: Vec::new(),
         };
         let s = format!("{report}");
         assert!(s.contains("AAPL"));
         assert!(s.contains("Tracking error"));
+        assert!(s.contains("TRADE"));
     }
 }
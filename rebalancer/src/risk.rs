@@ -23,17 +23,18 @@ fn action_to_side(action: Action) -> BrokerSide {
 
 /// Convert rebalancer risk config into nanobook-risk config.
 fn adapt_config(config: &RiskConfig) -> SharedRiskConfig {
-    let mut shared = SharedRiskConfig::default();
-    shared.max_position_pct = config.max_position_pct;
-    shared.max_leverage = config.max_leverage;
-    shared.min_trade_usd = config.min_trade_usd;
-    shared.max_trade_usd = config.max_trade_usd;
-    shared.allow_short = config.allow_short;
-    shared.max_short_pct = config.max_short_pct;
-    // Rebalancer config doesn't expose these yet; 0 = disabled.
-    shared.max_order_value_cents = 0;
-    shared.max_batch_value_cents = 0;
-    shared
+    SharedRiskConfig {
+        max_position_pct: config.max_position_pct,
+        max_leverage: config.max_leverage,
+        min_trade_usd: config.min_trade_usd,
+        max_trade_usd: config.max_trade_usd,
+        allow_short: config.allow_short,
+        max_short_pct: config.max_short_pct,
+        // Rebalancer config doesn't expose these yet; 0 = disabled.
+        max_order_value_cents: 0,
+        max_batch_value_cents: 0,
+        ..SharedRiskConfig::default()
+    }
 }
 
 fn validation_failure(detail: impl Into<String>) -> RiskReport {
@@ -116,6 +117,8 @@ pub fn check_risk(
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::inconsistent_digit_grouping)]
+
     use super::*;
     use crate::diff::{Action, RebalanceOrder};
 
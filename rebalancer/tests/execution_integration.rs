@@ -4,11 +4,12 @@
 //! Integration tests for rebalancer execution helpers.
 
 use nanobook::Symbol;
-use nanobook_broker::BrokerSide;
-use nanobook_rebalancer::diff::{Action, CurrentPosition};
+use nanobook_broker::{BrokerOrderType, BrokerSide, Quote};
+use nanobook_rebalancer::diff::{Action, CurrentPosition, RebalanceOrder};
 use nanobook_rebalancer::error::Error;
 use nanobook_rebalancer::execution::{
-    action_to_side, apply_constraint_overrides, collect_all_symbols, enforce_max_orders_per_run,
+    PricingStrategy, action_to_side, apply_constraint_overrides, collect_all_symbols,
+    enforce_max_orders_per_run, price_orders, to_broker_order,
 };
 use nanobook_rebalancer::target::TargetSpec;
 
@@ -205,3 +206,102 @@ fn enforce_max_orders_per_run_rejects_over_limit() {
         _ => panic!("expected RiskFailed"),
     }
 }
+
+// ============================================================================
+// PricingStrategy
+// ============================================================================
+
+fn quote(bid: i64, ask: i64) -> Quote {
+    Quote {
+        symbol: aapl(),
+        bid_cents: bid,
+        ask_cents: ask,
+        last_cents: 0,
+        volume: 0,
+    }
+}
+
+#[test]
+fn pricing_strategies_from_known_bbo() {
+    let q = quote(100_00, 100_10);
+    let cases = [
+        (PricingStrategy::Midpoint, 100_05, 100_05),
+        (PricingStrategy::CrossSpread, 100_10, 100_00),
+        (PricingStrategy::JoinBbo, 100_00, 100_10),
+        // 10 bps of $100.05 is 10 cents (rounded down).
+        (PricingStrategy::OffsetBps(10), 100_15, 99_95),
+        (PricingStrategy::OffsetBps(-10), 99_95, 100_15),
+    ];
+    for (strategy, buy, sell) in cases {
+        assert_eq!(
+            strategy.limit_price(&q, BrokerSide::Buy),
+            buy,
+            "{strategy:?} buy"
+        );
+        assert_eq!(
+            strategy.limit_price(&q, BrokerSide::Sell),
+            sell,
+            "{strategy:?} sell"
+        );
+    }
+}
+
+#[test]
+fn zero_spread_quote_collapses_to_touch() {
+    let q = quote(50_00, 50_00);
+    for strategy in [
+        PricingStrategy::Midpoint,
+        PricingStrategy::CrossSpread,
+        PricingStrategy::JoinBbo,
+        PricingStrategy::OffsetBps(0),
+    ] {
+        assert_eq!(strategy.limit_price(&q, BrokerSide::Buy), 50_00);
+        assert_eq!(strategy.limit_price(&q, BrokerSide::Sell), 50_00);
+    }
+    assert_eq!(
+        PricingStrategy::OffsetBps(20).limit_price(&q, BrokerSide::Buy),
+        50_10
+    );
+}
+
+#[test]
+fn one_sided_quote_uses_available_side() {
+    let q = quote(0, 20_00);
+    assert_eq!(
+        PricingStrategy::JoinBbo.limit_price(&q, BrokerSide::Buy),
+        20_00
+    );
+    assert_eq!(
+        PricingStrategy::Midpoint.limit_price(&q, BrokerSide::Sell),
+        20_00
+    );
+}
+
+#[test]
+fn price_orders_reprices_quoted_symbols_only() {
+    let order = |symbol, action| RebalanceOrder {
+        symbol,
+        action,
+        shares: 10,
+        limit_price_cents: 1,
+        notional_cents: 0,
+        description: "test",
+    };
+    let mut orders = vec![order(aapl(), Action::Buy), order(msft(), Action::Sell)];
+    price_orders(
+        &mut orders,
+        &[quote(100_00, 100_10)],
+        PricingStrategy::CrossSpread,
+    );
+
+    assert_eq!(orders[0].limit_price_cents, 100_10);
+    assert_eq!(orders[1].limit_price_cents, 1, "no MSFT quote: unchanged");
+
+    let broker_order = to_broker_order(&orders[0]).unwrap();
+    assert_eq!(broker_order.side, BrokerSide::Buy);
+    assert_eq!(broker_order.quantity, 10);
+    assert!(matches!(
+        broker_order.order_type,
+        BrokerOrderType::Limit(p) if p.0 == 100_10
+    ));
+}
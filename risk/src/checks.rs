@@ -1,5 +1,7 @@
 //! Individual risk check implementations.
 
+use std::collections::BTreeMap;
+
 use nanobook::Symbol;
 use nanobook_broker::{Account, BrokerSide};
 use rustc_hash::FxHashMap;
@@ -7,6 +9,9 @@ use rustc_hash::FxHashMap;
 use crate::config::RiskConfig;
 use crate::report::{RiskCheck, RiskReport, RiskStatus};
 
+/// Sector bucket for symbols missing from [`RiskConfig::sector_map`].
+pub const UNCLASSIFIED_SECTOR: &str = "Unclassified";
+
 /// Returns `"<="` if the check passed, `">"` if it failed or warned.
 fn cmp_symbol(status: RiskStatus) -> &'static str {
     if status == RiskStatus::Pass {
@@ -144,7 +149,35 @@ pub fn check_batch(
         });
     }
 
-    // 4. Max order value in cents
+    // 4. Sector concentration — gross post-trade exposure per sector
+    if !config.sector_map.is_empty() {
+        let mut sector_exposure: BTreeMap<&str, i64> = BTreeMap::new();
+        for (sym, qty) in &post_qty {
+            let price = price_map.get(sym).copied().unwrap_or(0).saturating_abs();
+            let sector = config
+                .sector_map
+                .get(sym)
+                .map_or(UNCLASSIFIED_SECTOR, String::as_str);
+            let value = sector_exposure.entry(sector).or_insert(0);
+            *value = value.saturating_add(qty.saturating_abs().saturating_mul(price));
+        }
+        for (sector, value) in sector_exposure {
+            let sector_pct = ratio_or_inf(value, equity);
+            if value > 0 && sector_pct > config.max_sector_pct {
+                checks.push(RiskCheck {
+                    name: "Max sector",
+                    status: RiskStatus::Fail,
+                    detail: format!(
+                        "{sector}: {:.1}% > {:.1}% limit",
+                        sector_pct * 100.0,
+                        config.max_sector_pct * 100.0,
+                    ),
+                });
+            }
+        }
+    }
+
+    // 5. Max order value in cents
     let mut batch_value = 0_i64;
     for &(sym, _side, qty, price) in orders {
         let qty_i64 = i64::try_from(qty).unwrap_or(i64::MAX);
@@ -166,7 +199,7 @@ pub fn check_batch(
         }
     }
 
-    // 5. Max rebalance batch value in cents
+    // 6. Max rebalance batch value in cents
     let max_batch = config.max_batch_value_cents;
     if max_batch > 0 && batch_value > max_batch {
         checks.push(RiskCheck {
@@ -180,7 +213,7 @@ pub fn check_batch(
         });
     }
 
-    // 6. Max trade size — warn if any trade > max_trade_usd
+    // 7. Max trade size — warn if any trade > max_trade_usd
     let max_cents = (config.max_trade_usd * 100.0) as i64;
     for &(sym, _side, qty, price) in orders {
         let qty_i64 = i64::try_from(qty).unwrap_or(i64::MAX);
@@ -199,14 +232,14 @@ pub fn check_batch(
         }
     }
 
-    // 7. Order count
+    // 8. Order count
     checks.push(RiskCheck {
         name: "Order count",
         status: RiskStatus::Pass,
         detail: format!("{} orders", orders.len()),
     });
 
-    // 8. Target weights sanity
+    // 9. Target weights sanity
     let long_sum: f64 = target_map.values().filter(|w| **w > 0.0).sum();
     let short_sum: f64 = target_map
         .values()
@@ -227,8 +260,72 @@ pub fn check_batch(
     RiskReport { checks }
 }
 
+/// Run account-level circuit breakers: daily realized loss and drawdown.
+///
+/// `high_water_mark_cents` is the caller's running equity peak; if current
+/// equity is above it, equity is used as the peak. `realized_pnl_today_cents`
+/// is negative for a losing day.
+pub fn check_account(
+    config: &RiskConfig,
+    account: &Account,
+    high_water_mark_cents: i64,
+    realized_pnl_today_cents: i64,
+) -> RiskReport {
+    let mut checks = Vec::new();
+
+    // 1. Daily realized loss
+    let max_loss = config.max_daily_loss_cents;
+    if max_loss > 0 {
+        let loss = realized_pnl_today_cents.saturating_neg().max(0);
+        let loss_status = if loss > max_loss {
+            RiskStatus::Fail
+        } else {
+            RiskStatus::Pass
+        };
+        checks.push(RiskCheck {
+            name: "Daily loss",
+            status: loss_status,
+            detail: format!(
+                "${:.0} {} ${:.0} max_daily_loss_cents",
+                loss as f64 / 100.0,
+                cmp_symbol(loss_status),
+                max_loss as f64 / 100.0,
+            ),
+        });
+    }
+
+    // 2. Drawdown from the high-water mark
+    let equity = account.equity_cents;
+    let hwm = high_water_mark_cents.max(equity);
+    let drawdown = if hwm > 0 {
+        hwm.saturating_sub(equity) as f64 / hwm as f64
+    } else {
+        0.0
+    };
+    let dd_status = if drawdown > config.max_drawdown_pct {
+        RiskStatus::Fail
+    } else {
+        RiskStatus::Pass
+    };
+    checks.push(RiskCheck {
+        name: "Max drawdown",
+        status: dd_status,
+        detail: format!(
+            "{:.1}% below ${:.0} peak {} {:.1}% limit",
+            drawdown * 100.0,
+            hwm as f64 / 100.0,
+            cmp_symbol(dd_status),
+            config.max_drawdown_pct * 100.0,
+        ),
+    });
+
+    RiskReport { checks }
+}
+
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::inconsistent_digit_grouping)]
+
     use super::*;
 
     fn aapl() -> Symbol {
@@ -412,4 +509,66 @@ mod tests {
                 .contains("$250 > $100 max_batch_value_cents")
         );
     }
+
+    #[test]
+    fn fails_sector_concentration() {
+        let msft = Symbol::new("MSFT");
+        let mut config = default_config();
+        config.max_sector_pct = 0.35;
+        config.sector_map = [(aapl(), "Tech".to_string()), (msft, "Tech".to_string())]
+            .into_iter()
+            .collect();
+
+        // $20K of each on $100K equity: 20% per name, 40% combined tech.
+        let orders = vec![
+            (aapl(), BrokerSide::Buy, 100, 200_00),
+            (msft, BrokerSide::Buy, 50, 400_00),
+            (spy(), BrokerSide::Buy, 20, 500_00),
+        ];
+        let targets = vec![(aapl(), 0.20), (msft, 0.20), (spy(), 0.10)];
+        let report = check_batch(&config, &orders, &account(100_000_00), &[], &targets);
+
+        let position = report
+            .checks
+            .iter()
+            .find(|c| c.name == "Max position")
+            .unwrap();
+        assert_eq!(position.status, RiskStatus::Pass);
+
+        let sectors: Vec<_> = report
+            .checks
+            .iter()
+            .filter(|c| c.name == "Max sector")
+            .collect();
+        assert_eq!(
+            sectors.len(),
+            1,
+            "only Tech breaches; SPY is Unclassified at 10%"
+        );
+        assert_eq!(sectors[0].status, RiskStatus::Fail);
+        assert!(sectors[0].detail.contains("Tech: 40.0% > 35.0% limit"));
+    }
+
+    #[test]
+    fn unmapped_symbols_share_unclassified_bucket() {
+        let mut config = default_config();
+        config.max_sector_pct = 0.35;
+        config.sector_map = [(Symbol::new("XOM"), "Energy".to_string())]
+            .into_iter()
+            .collect();
+
+        let orders = vec![
+            (aapl(), BrokerSide::Buy, 100, 200_00),
+            (spy(), BrokerSide::Buy, 40, 500_00),
+        ];
+        let targets = vec![(aapl(), 0.20), (spy(), 0.20)];
+        let report = check_batch(&config, &orders, &account(100_000_00), &[], &targets);
+
+        let sector = report
+            .checks
+            .iter()
+            .find(|c| c.name == "Max sector")
+            .unwrap();
+        assert!(sector.detail.starts_with("Unclassified: 40.0%"));
+    }
 }
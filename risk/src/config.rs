@@ -1,5 +1,9 @@
 //! Risk configuration.
 
+use std::collections::HashMap;
+
+use nanobook::Symbol;
+
 /// Configuration for the risk engine.
 #[derive(Debug, Clone)]
 pub struct RiskConfig {
@@ -11,8 +15,10 @@ pub struct RiskConfig {
     pub max_batch_value_cents: i64,
     /// Max gross leverage (1.0 for long-only).
     pub max_leverage: f64,
-    /// Max drawdown fraction (circuit breaker).
+    /// Max drawdown fraction below the high-water mark (circuit breaker).
     pub max_drawdown_pct: f64,
+    /// Max realized loss per day in USD cents (0 = disabled).
+    pub max_daily_loss_cents: i64,
     /// Allow short selling.
     pub allow_short: bool,
     /// Max short exposure as fraction of equity.
@@ -21,6 +27,15 @@ pub struct RiskConfig {
     pub min_trade_usd: f64,
     /// Max trade size in USD.
     pub max_trade_usd: f64,
+    /// Sector (or any grouping) of each symbol; unmapped symbols count as
+    /// "Unclassified". The sector check is skipped while the map is empty.
+    pub sector_map: HashMap<Symbol, String>,
+    /// Max gross post-trade exposure per sector as fraction of equity.
+    pub max_sector_pct: f64,
+    /// Max order submissions per sliding window (0 = disabled).
+    pub max_orders_per_window: u32,
+    /// Length of the order-rate window in seconds.
+    pub window_secs: u64,
 }
 
 impl RiskConfig {
@@ -62,6 +77,12 @@ impl RiskConfig {
                 self.min_trade_usd
             ));
         }
+        if self.max_daily_loss_cents < 0 {
+            return Err(format!(
+                "max_daily_loss_cents must be >= 0, got {}",
+                self.max_daily_loss_cents
+            ));
+        }
         if self.max_order_value_cents < 0 {
             return Err(format!(
                 "max_order_value_cents must be >= 0, got {}",
@@ -80,6 +101,15 @@ impl RiskConfig {
                 self.max_trade_usd
             ));
         }
+        if !self.max_sector_pct.is_finite() || self.max_sector_pct <= 0.0 {
+            return Err(format!(
+                "max_sector_pct must be > 0 and finite, got {}",
+                self.max_sector_pct
+            ));
+        }
+        if self.max_orders_per_window > 0 && self.window_secs == 0 {
+            return Err("window_secs must be > 0 when max_orders_per_window is set".into());
+        }
         Ok(())
     }
 }
@@ -92,10 +122,15 @@ impl Default for RiskConfig {
             max_batch_value_cents: 100_000_000, // $1M
             max_leverage: 1.5,
             max_drawdown_pct: 0.20,
+            max_daily_loss_cents: 0,
             allow_short: true,
             max_short_pct: 0.30,
             min_trade_usd: 100.0,
             max_trade_usd: 100_000.0,
+            sector_map: HashMap::new(),
+            max_sector_pct: 1.0,
+            max_orders_per_window: 0,
+            window_secs: 1,
         }
     }
 }
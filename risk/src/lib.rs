@@ -5,6 +5,7 @@
 
 pub mod checks;
 pub mod config;
+mod rate;
 pub mod report;
 
 pub use config::RiskConfig;
@@ -12,11 +13,16 @@ pub use report::{RiskCheck, RiskReport, RiskStatus};
 
 use nanobook::Symbol;
 use nanobook_broker::{Account, BrokerSide};
+use rate::RateWindow;
 
 /// Pre-trade risk engine.
+///
+/// Stateless apart from the order-rate window used by
+/// [`check_rate`](Self::check_rate); cloning an engine copies that window.
 #[derive(Debug, Clone)]
 pub struct RiskEngine {
     config: RiskConfig,
+    rate: RateWindow,
 }
 
 impl RiskEngine {
@@ -31,7 +37,10 @@ impl RiskEngine {
         if let Err(msg) = config.validate() {
             panic!("invalid RiskConfig: {msg}");
         }
-        Self { config }
+        Self {
+            config,
+            rate: RateWindow::default(),
+        }
     }
 
     /// Access the current config.
@@ -41,8 +50,9 @@ impl RiskEngine {
 
     /// Check a single order against risk limits.
     ///
-    /// A lightweight check for one order — validates position concentration
-    /// and order size.
+    /// A lightweight check for one order — validates position concentration,
+    /// order size, buying power for buys, and short availability for sells
+    /// that open or extend a short.
     pub fn check_order(
         &self,
         symbol: &Symbol,
@@ -140,18 +150,143 @@ impl RiskEngine {
             ),
         });
 
-        // Short check
-        if side == BrokerSide::Sell && post_qty < 0 && !self.config.allow_short {
+        // Buying power — only the part of a buy that opens or adds to a long
+        // needs funding; buying to cover a short never does.
+        if side == BrokerSide::Buy {
+            let opening_qty = post_qty.max(0) - current_qty.max(0);
+            let opening = opening_qty.saturating_mul(price_cents);
+            let available = account.buying_power_cents;
+            let bp_status = if opening > available {
+                RiskStatus::Fail
+            } else {
+                RiskStatus::Pass
+            };
             checks.push(RiskCheck {
-                name: "Short selling",
-                status: RiskStatus::Fail,
-                detail: "short selling not allowed".into(),
+                name: "Buying power",
+                status: bp_status,
+                detail: if bp_status == RiskStatus::Pass {
+                    format!(
+                        "${:.0} <= ${:.0} available",
+                        opening as f64 / 100.0,
+                        available as f64 / 100.0,
+                    )
+                } else {
+                    format!(
+                        "${:.0} > ${:.0} available (short ${:.0})",
+                        opening as f64 / 100.0,
+                        available as f64 / 100.0,
+                        opening.saturating_sub(available) as f64 / 100.0,
+                    )
+                },
             });
         }
 
+        // Short check
+        if side == BrokerSide::Sell && post_qty < 0 {
+            if !self.config.allow_short {
+                checks.push(RiskCheck {
+                    name: "Short selling",
+                    status: RiskStatus::Fail,
+                    detail: "short selling not allowed".into(),
+                });
+            } else {
+                // Only the part beyond an existing long opens a short; a
+                // covered sell is never blocked here.
+                let opening_qty = (-post_qty) - (-current_qty).max(0);
+                let opening = opening_qty.saturating_mul(price_cents);
+                let limit = (self.config.max_short_pct * equity.max(0) as f64) as i64;
+                let short_status = if opening > limit {
+                    RiskStatus::Fail
+                } else {
+                    RiskStatus::Pass
+                };
+                checks.push(RiskCheck {
+                    name: "Short availability",
+                    status: short_status,
+                    detail: format!(
+                        "${:.0} {} ${:.0} limit ({:.1}% of equity)",
+                        opening as f64 / 100.0,
+                        if short_status == RiskStatus::Pass {
+                            "<="
+                        } else {
+                            ">"
+                        },
+                        limit as f64 / 100.0,
+                        self.config.max_short_pct * 100.0,
+                    ),
+                });
+            }
+        }
+
         RiskReport { checks }
     }
 
+    /// Check account-level circuit breakers.
+    ///
+    /// Fails when today's realized loss exceeds `max_daily_loss_cents` or when
+    /// equity sits more than `max_drawdown_pct` below `high_water_mark_cents`.
+    /// The engine does not track equity, so the caller tracks the running
+    /// high-water mark and today's realized PnL.
+    pub fn check_account(
+        &self,
+        account: &Account,
+        high_water_mark_cents: i64,
+        realized_pnl_today_cents: i64,
+    ) -> RiskReport {
+        checks::check_account(
+            &self.config,
+            account,
+            high_water_mark_cents,
+            realized_pnl_today_cents,
+        )
+    }
+
+    /// Throttle order submissions to `max_orders_per_window` per `window_secs`.
+    ///
+    /// Call once per order right before submitting it, with the current time
+    /// in nanoseconds; submit only if the report has no failures. Accepted
+    /// calls are recorded in a sliding window, rejected ones are not, so a
+    /// runaway loop is held to the cap until it stops. The window lives behind
+    /// a mutex, so one engine can be shared across threads.
+    ///
+    /// ```
+    /// use nanobook_risk::{RiskConfig, RiskEngine};
+    ///
+    /// let engine = RiskEngine::new(RiskConfig {
+    ///     max_orders_per_window: 2,
+    ///     window_secs: 1,
+    ///     ..RiskConfig::default()
+    /// });
+    /// assert!(!engine.check_rate(0).has_failures());
+    /// assert!(!engine.check_rate(1).has_failures());
+    /// assert!(engine.check_rate(2).has_failures());
+    /// assert!(!engine.check_rate(1_000_000_000).has_failures());
+    /// ```
+    pub fn check_rate(&self, now_ts: u64) -> RiskReport {
+        let cap = self.config.max_orders_per_window;
+        if cap == 0 {
+            return RiskReport { checks: Vec::new() };
+        }
+        let window_ns = self.config.window_secs.saturating_mul(1_000_000_000);
+        let (count, accepted) = self.rate.try_record(now_ts, window_ns, cap);
+        let status = if accepted {
+            RiskStatus::Pass
+        } else {
+            RiskStatus::Fail
+        };
+        RiskReport {
+            checks: vec![RiskCheck {
+                name: "Order rate",
+                status,
+                detail: format!(
+                    "{count} orders in {}s {} {cap} limit",
+                    self.config.window_secs,
+                    if accepted { "<=" } else { ">" },
+                ),
+            }],
+        }
+    }
+
     /// Check a batch of orders (e.g., a full rebalance).
     ///
     /// Validates all risk limits including leverage, short exposure, and
@@ -0,0 +1,41 @@
+//! Sliding-window order-rate tracking.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Timestamps (ns) of recently accepted submissions, oldest first.
+#[derive(Debug, Default)]
+pub(crate) struct RateWindow {
+    submissions: Mutex<VecDeque<u64>>,
+}
+
+impl RateWindow {
+    /// Drop submissions older than `window_ns` before `now_ts`, then record
+    /// `now_ts` if fewer than `cap` remain. Returns the count in the window
+    /// including this submission, and whether it was accepted.
+    pub(crate) fn try_record(&self, now_ts: u64, window_ns: u64, cap: u32) -> (usize, bool) {
+        let mut submissions = self.submissions.lock().unwrap_or_else(|e| e.into_inner());
+        while submissions
+            .front()
+            .is_some_and(|&ts| now_ts.saturating_sub(ts) >= window_ns)
+        {
+            submissions.pop_front();
+        }
+        if submissions.len() < cap as usize {
+            submissions.push_back(now_ts);
+            (submissions.len(), true)
+        } else {
+            (submissions.len() + 1, false)
+        }
+    }
+}
+
+impl Clone for RateWindow {
+    /// Clones get an independent copy of the current window.
+    fn clone(&self) -> Self {
+        let submissions = self.submissions.lock().unwrap_or_else(|e| e.into_inner());
+        Self {
+            submissions: Mutex::new(submissions.clone()),
+        }
+    }
+}
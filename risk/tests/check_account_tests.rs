@@ -0,0 +1,86 @@
+// Allow our dollar.cents digit grouping convention (e.g., 100_00 = $100.00)
+#![allow(clippy::inconsistent_digit_grouping)]
+
+//! Tests for RiskEngine::check_account — daily loss and drawdown breakers.
+
+use nanobook_broker::Account;
+use nanobook_risk::{RiskConfig, RiskEngine, RiskStatus};
+
+fn account(equity: i64) -> Account {
+    Account {
+        equity_cents: equity,
+        buying_power_cents: equity,
+        cash_cents: equity,
+        gross_position_value_cents: 0,
+    }
+}
+
+fn engine() -> RiskEngine {
+    RiskEngine::new(RiskConfig {
+        max_daily_loss_cents: 5_000_00, // 5% of $100K
+        max_drawdown_pct: 0.10,
+        ..RiskConfig::default()
+    })
+}
+
+fn status(report: &nanobook_risk::RiskReport, name: &str) -> RiskStatus {
+    report
+        .checks
+        .iter()
+        .find(|c| c.name == name)
+        .unwrap_or_else(|| panic!("missing check {name}"))
+        .status
+}
+
+#[test]
+fn six_percent_loss_day_breaches_five_percent_limit() {
+    let report = engine().check_account(&account(94_000_00), 100_000_00, -6_000_00);
+
+    assert!(report.has_failures());
+    assert_eq!(status(&report, "Daily loss"), RiskStatus::Fail);
+    // 6% below peak is still inside the 10% drawdown limit.
+    assert_eq!(status(&report, "Max drawdown"), RiskStatus::Pass);
+}
+
+#[test]
+fn loss_at_limit_and_gains_pass() {
+    let at_limit = engine().check_account(&account(95_000_00), 100_000_00, -5_000_00);
+    assert_eq!(status(&at_limit, "Daily loss"), RiskStatus::Pass);
+
+    let gain = engine().check_account(&account(101_000_00), 100_000_00, 1_000_00);
+    assert!(!gain.has_failures());
+}
+
+#[test]
+fn drawdown_below_high_water_mark_fails() {
+    // Small realized loss today, but equity is 12% below the peak.
+    let report = engine().check_account(&account(88_000_00), 100_000_00, -100_00);
+
+    assert_eq!(status(&report, "Daily loss"), RiskStatus::Pass);
+    assert_eq!(status(&report, "Max drawdown"), RiskStatus::Fail);
+}
+
+#[test]
+fn equity_above_stale_high_water_mark_is_new_peak() {
+    let report = engine().check_account(&account(120_000_00), 100_000_00, 0);
+    assert_eq!(status(&report, "Max drawdown"), RiskStatus::Pass);
+}
+
+#[test]
+fn daily_loss_check_disabled_by_default() {
+    let report = RiskEngine::new(RiskConfig::default()).check_account(
+        &account(50_000_00),
+        50_000_00,
+        -1_000_000_00,
+    );
+    assert!(!report.checks.iter().any(|c| c.name == "Daily loss"));
+}
+
+#[test]
+fn negative_daily_loss_limit_fails_validation() {
+    let config = RiskConfig {
+        max_daily_loss_cents: -1,
+        ..RiskConfig::default()
+    };
+    assert!(config.validate().is_err());
+}
@@ -1,5 +1,7 @@
 // Allow our dollar.cents digit grouping convention (e.g., 100_00 = $100.00)
 #![allow(clippy::inconsistent_digit_grouping)]
+// Tests tweak one field of a default config at a time
+#![allow(clippy::field_reassign_with_default)]
 
 //! Tests for RiskEngine::check_order — currently has ZERO tests.
 
@@ -165,7 +167,19 @@ fn short_not_allowed_fails() {
 fn zero_equity_does_not_panic() {
     let report = engine().check_order(&aapl(), BrokerSide::Buy, 10, 150_00, &account(0), &[]);
     // Should not panic; position check defaults to 0%
-    assert!(!report.has_failures());
+    let position = report
+        .checks
+        .iter()
+        .find(|c| c.name == "Max position")
+        .unwrap();
+    assert_eq!(position.status, RiskStatus::Pass);
+    // ...but there is no buying power to fund the buy.
+    let bp = report
+        .checks
+        .iter()
+        .find(|c| c.name == "Buying power")
+        .unwrap();
+    assert_eq!(bp.status, RiskStatus::Fail);
 }
 
 #[test]
@@ -360,3 +374,108 @@ fn risk_engine_panics_on_invalid_config() {
     config.max_position_pct = f64::NAN;
     RiskEngine::new(config);
 }
+
+// ============================================================================
+// Buying power and short availability
+// ============================================================================
+
+fn account_with_bp(equity: i64, buying_power: i64) -> Account {
+    Account {
+        equity_cents: equity,
+        buying_power_cents: buying_power,
+        cash_cents: buying_power,
+        gross_position_value_cents: equity - buying_power,
+    }
+}
+
+fn loose_engine() -> RiskEngine {
+    RiskEngine::new(RiskConfig {
+        max_position_pct: 1.0,
+        max_order_value_cents: 0,
+        max_trade_usd: 10_000_000.0,
+        ..RiskConfig::default()
+    })
+}
+
+#[test]
+fn buy_beyond_buying_power_fails_with_shortfall() {
+    // $1M buy against $500K buying power.
+    let report = loose_engine().check_order(
+        &aapl(),
+        BrokerSide::Buy,
+        5_000,
+        200_00,
+        &account_with_bp(2_000_000_00, 500_000_00),
+        &[],
+    );
+    let bp = report
+        .checks
+        .iter()
+        .find(|c| c.name == "Buying power")
+        .unwrap();
+    assert_eq!(bp.status, RiskStatus::Fail);
+    assert!(
+        bp.detail
+            .contains("$1000000 > $500000 available (short $500000)")
+    );
+}
+
+#[test]
+fn covered_sell_is_never_blocked_by_buying_power() {
+    for buying_power in [0, 1_00, 500_000_00] {
+        let report = loose_engine().check_order(
+            &aapl(),
+            BrokerSide::Sell,
+            5_000,
+            200_00,
+            &account_with_bp(2_000_000_00, buying_power),
+            &[(aapl(), 5_000)],
+        );
+        assert!(!report.has_failures(), "bp={buying_power}: {report}");
+        assert!(!report.checks.iter().any(|c| c.name == "Buying power"));
+        assert!(!report.checks.iter().any(|c| c.name == "Short availability"));
+    }
+}
+
+#[test]
+fn buy_to_cover_needs_no_buying_power() {
+    let report = loose_engine().check_order(
+        &aapl(),
+        BrokerSide::Buy,
+        100,
+        200_00,
+        &account_with_bp(100_000_00, 0),
+        &[(aapl(), -100)],
+    );
+    assert!(!report.has_failures(), "{report}");
+}
+
+#[test]
+fn short_beyond_availability_fails() {
+    // Selling 200 with 50 long opens a 150-share ($30K) short; the default
+    // 30% short limit on $100K equity allows $30K.
+    let at_limit = loose_engine().check_order(
+        &aapl(),
+        BrokerSide::Sell,
+        200,
+        200_00,
+        &account_with_bp(100_000_00, 100_000_00),
+        &[(aapl(), 50)],
+    );
+    assert!(!at_limit.has_failures(), "{at_limit}");
+
+    let over = loose_engine().check_order(
+        &aapl(),
+        BrokerSide::Sell,
+        201,
+        200_00,
+        &account_with_bp(100_000_00, 100_000_00),
+        &[(aapl(), 50)],
+    );
+    let short = over
+        .checks
+        .iter()
+        .find(|c| c.name == "Short availability")
+        .unwrap();
+    assert_eq!(short.status, RiskStatus::Fail);
+}
@@ -0,0 +1,79 @@
+//! Tests for RiskEngine::check_rate — sliding-window order throttle.
+
+use nanobook_risk::{RiskConfig, RiskEngine, RiskStatus};
+
+const SECOND: u64 = 1_000_000_000;
+
+fn engine(cap: u32) -> RiskEngine {
+    RiskEngine::new(RiskConfig {
+        max_orders_per_window: cap,
+        window_secs: 1,
+        ..RiskConfig::default()
+    })
+}
+
+#[test]
+fn eleventh_order_in_one_second_trips_cap_of_ten() {
+    let engine = engine(10);
+    for i in 0..10 {
+        let report = engine.check_rate(i * SECOND / 20);
+        assert!(!report.has_failures(), "order {i} should pass");
+    }
+
+    let report = engine.check_rate(SECOND / 2);
+    assert!(report.has_failures());
+    assert_eq!(report.checks[0].name, "Order rate");
+    assert_eq!(report.checks[0].status, RiskStatus::Fail);
+    assert!(
+        report.checks[0]
+            .detail
+            .contains("11 orders in 1s > 10 limit")
+    );
+}
+
+#[test]
+fn window_resets_after_it_passes() {
+    let engine = engine(10);
+    for _ in 0..11 {
+        engine.check_rate(0);
+    }
+    assert!(engine.check_rate(SECOND - 1).has_failures());
+    assert!(!engine.check_rate(SECOND).has_failures());
+}
+
+#[test]
+fn window_slides_instead_of_resetting_in_buckets() {
+    let engine = engine(2);
+    assert!(!engine.check_rate(0).has_failures());
+    assert!(!engine.check_rate(SECOND * 9 / 10).has_failures());
+    // 0.0s expired, 0.9s still inside the window.
+    assert!(!engine.check_rate(SECOND).has_failures());
+    assert!(engine.check_rate(SECOND * 3 / 2).has_failures());
+}
+
+#[test]
+fn disabled_by_default() {
+    let engine = RiskEngine::new(RiskConfig::default());
+    for _ in 0..1_000 {
+        assert!(engine.check_rate(0).checks.is_empty());
+    }
+}
+
+#[test]
+fn clones_track_their_own_window() {
+    let engine = engine(1);
+    assert!(!engine.check_rate(0).has_failures());
+    let clone = engine.clone();
+    assert!(clone.check_rate(1).has_failures());
+    assert!(engine.check_rate(2).has_failures());
+}
+
+#[test]
+fn zero_window_with_cap_fails_validation() {
+    let config = RiskConfig {
+        max_orders_per_window: 5,
+        window_secs: 0,
+        ..RiskConfig::default()
+    };
+    assert!(config.validate().is_err());
+}
@@ -0,0 +1,361 @@
+//! Single-price auctions (opening/closing uncross).
+//!
+//! Between [`Exchange::begin_auction`] and [`Exchange::run_auction`] the
+//! exchange is in a call phase: GTC limit orders rest without matching, so
+//! the book may cross. The auction then clears all crossed interest at one
+//! price:
+//!
+//! 1. The clearing price maximizes executed volume
+//!    `min(bids at or above p, asks at or below p)` over the crossed levels.
+//! 2. Ties go to the price closest to the reference — the last trade price,
+//!    or the middle of the tied range if nothing has traded — and then to
+//!    the lower price.
+//!
+//! Every fill executes at the clearing price, allocated in price-time (FIFO)
+//! priority on both sides. An auction trade has no true aggressor; the later
+//! of the two orders is reported as the aggressor.
+
+#[cfg(feature = "event-log")]
+use crate::event::Event;
+use crate::{Exchange, OrderBook, OrderId, Price, Quantity, Side, Trade};
+
+/// Outcome of [`Exchange::run_auction`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuctionResult {
+    /// Clearing price (`None` if the book was not crossed)
+    pub clearing_price: Option<Price>,
+    /// Total quantity executed at the clearing price
+    pub matched_volume: Quantity,
+    /// Unmatched interest at the clearing price: bids at or above it minus
+    /// asks at or below it (positive = buy surplus)
+    pub imbalance: i64,
+    /// Trades executed by the uncross
+    pub trades: Vec<Trade>,
+    /// The stop cascade the uncross set off was cut off at the exchange's
+    /// [maximum depth](crate::Exchange::set_max_cascade_depth)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cascade_truncated: bool,
+}
+
+/// Clearing price, executable volume and imbalance of a crossed book.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuctionQuote {
+    pub price: Price,
+    pub volume: Quantity,
+    pub imbalance: i64,
+}
+
+impl OrderBook {
+    /// Indicative auction price for the current book, without executing.
+    ///
+    /// Returns `None` unless the best bid is at or above the best ask. See the
+    /// [module docs](crate::auction) for the price and tie-break rules.
+    pub fn auction_quote(&self, reference: Option<Price>) -> Option<AuctionQuote> {
+        let (best_bid, best_ask) = (self.best_bid()?, self.best_ask()?);
+        if best_bid < best_ask {
+            return None;
+        }
+
+        let mut candidates: Vec<Price> = self
+            .bids()
+            .iter_best_to_worst()
+            .chain(self.asks().iter_best_to_worst())
+            .map(|(price, _)| *price)
+            .filter(|price| (best_ask..=best_bid).contains(price))
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let quotes: Vec<AuctionQuote> = candidates
+            .into_iter()
+            .map(|price| {
+                let demand = self.bids().quantity_at_or_better(price);
+                let supply = self.asks().quantity_at_or_better(price);
+                AuctionQuote {
+                    price,
+                    volume: demand.min(supply),
+                    imbalance: demand as i64 - supply as i64,
+                }
+            })
+            .collect();
+
+        let max_volume = quotes.iter().map(|q| q.volume).max()?;
+        let tied: Vec<AuctionQuote> = quotes
+            .into_iter()
+            .filter(|q| q.volume == max_volume)
+            .collect();
+        let reference = reference.unwrap_or_else(|| {
+            let (low, high) = (tied[0].price.0, tied[tied.len() - 1].price.0);
+            Price(low + (high - low) / 2)
+        });
+        // `tied` is in ascending price order, so `min_by_key` keeps the lower
+        // price on equal distance.
+        tied.into_iter()
+            .min_by_key(|q| (q.price.0 - reference.0).unsigned_abs())
+    }
+
+    /// Execute `volume` at `price`, best bids against best asks in FIFO order.
+    fn uncross(&mut self, price: Price, mut volume: Quantity) -> Vec<Trade> {
+        let mut trades = Vec::new();
+        while volume > 0 {
+            let (Some(bid_id), Some(ask_id)) =
+                (self.front_order(Side::Buy), self.front_order(Side::Sell))
+            else {
+                break;
+            };
+            let bid_remaining = self.get_order(bid_id).map_or(0, |o| o.remaining_quantity);
+            let ask_remaining = self.get_order(ask_id).map_or(0, |o| o.remaining_quantity);
+            let quantity = volume.min(bid_remaining).min(ask_remaining);
+            if quantity == 0 {
+                break;
+            }
+
+            let (aggressor, passive, aggressor_side) = if bid_id.0 > ask_id.0 {
+                (bid_id, ask_id, Side::Buy)
+            } else {
+                (ask_id, bid_id, Side::Sell)
+            };
+            let trade = Trade::new(
+                self.next_trade_id(),
+                price,
+                quantity,
+                aggressor,
+                passive,
+                aggressor_side,
+                self.next_timestamp(),
+            );
+
+            self.fill_front(Side::Buy, bid_id, &trade);
+            self.fill_front(Side::Sell, ask_id, &trade);
+            trades.push(trade);
+            volume -= quantity;
+        }
+        trades
+    }
+
+    /// The first live order at the best level of `side`.
+    fn front_order(&mut self, side: Side) -> Option<OrderId> {
+        let levels = self.side_mut(side);
+        let price = levels.best_price()?;
+        levels.get_level_mut(price)?.front()
+    }
+
+    /// Fill the front order at the best level of `side` with `trade`.
+    fn fill_front(&mut self, side: Side, order_id: OrderId, trade: &Trade) {
+        let quantity = trade.quantity;
+        let filled = {
+            let order = self
+                .get_order_mut(order_id)
+                .expect("invariant: front order exists in book");
+            order.fill_trade(trade);
+            order.remaining_quantity == 0
+        };
+
+        let levels = self.side_mut(side);
+        let Some(price) = levels.best_price() else {
+            return;
+        };
+        if let Some(level) = levels.get_level_mut(price) {
+            if filled {
+                level.pop_front(quantity);
+                if level.is_empty() {
+                    levels.remove_level(price);
+                }
+            } else {
+                level.decrease_quantity(quantity);
+            }
+        }
+    }
+}
+
+impl Exchange {
+    /// Start an auction call phase.
+    ///
+    /// Until [`run_auction`](Self::run_auction), GTC limit orders (including
+    /// modify replacements) rest without matching, so the book may cross.
+    /// Market, IOC and FOK orders submitted during the call are cancelled
+    /// without trading. Calling this during a call phase has no effect.
+    pub fn begin_auction(&mut self) {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::BeginAuction);
+
+        self.in_auction = true;
+    }
+
+    /// Whether the exchange is in an auction call phase.
+    pub fn in_auction(&self) -> bool {
+        self.in_auction
+    }
+
+    /// Uncross the book at a single clearing price and resume continuous trading.
+    ///
+    /// The reference price for tie-breaks is the last trade price. Stop
+    /// orders trigger off the auction trades as they would after any other
+    /// trade. Returns an empty result if the book is not crossed.
+    pub fn run_auction(&mut self) -> AuctionResult {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::RunAuction);
+
+        let mut result = self.run_auction_internal();
+        if let Some(trade) = result.trades.last() {
+            self.last_trade_price = Some(trade.price);
+            result.cascade_truncated = self.process_trade_triggers();
+        }
+        result
+    }
+
+    /// Internal: uncross without recording an event or processing stops.
+    pub(crate) fn run_auction_internal(&mut self) -> AuctionResult {
+        self.in_auction = false;
+        let Some(quote) = self.book.auction_quote(self.last_trade_price) else {
+            return AuctionResult::default();
+        };
+
+        let trades = self.book.uncross(quote.price, quote.volume);
+        self.record_trades(&trades);
+        self.record_imbalance();
+        AuctionResult {
+            clearing_price: Some(quote.price),
+            matched_volume: quote.volume,
+            imbalance: quote.imbalance,
+            trades,
+            cascade_truncated: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OrderStatus, TimeInForce};
+
+    fn limit(exchange: &mut Exchange, side: Side, price: i64, quantity: Quantity) -> OrderId {
+        exchange
+            .submit_limit(side, Price(price), quantity, TimeInForce::GTC)
+            .order_id
+    }
+
+    #[test]
+    fn call_phase_rests_crossing_orders() {
+        let mut exchange = Exchange::new();
+        exchange.begin_auction();
+        limit(&mut exchange, Side::Sell, 99_00, 100);
+        let result = exchange.submit_limit(Side::Buy, Price(101_00), 100, TimeInForce::GTC);
+        assert!(result.trades.is_empty());
+        assert_eq!(result.status, OrderStatus::New);
+        assert!(exchange.book().is_crossed());
+
+        let ioc = exchange.submit_limit(Side::Buy, Price(101_00), 10, TimeInForce::IOC);
+        assert_eq!(ioc.cancelled_quantity, 10);
+        assert!(exchange.submit_market(Side::Sell, 10).trades.is_empty());
+    }
+
+    #[test]
+    fn textbook_uncross() {
+        let mut exchange = Exchange::new();
+        exchange.begin_auction();
+        let b1 = limit(&mut exchange, Side::Buy, 101_00, 200);
+        let b2 = limit(&mut exchange, Side::Buy, 100_00, 300);
+        limit(&mut exchange, Side::Buy, 99_00, 100);
+        limit(&mut exchange, Side::Sell, 98_00, 100);
+        limit(&mut exchange, Side::Sell, 99_00, 200);
+        let s3 = limit(&mut exchange, Side::Sell, 100_00, 300);
+        limit(&mut exchange, Side::Sell, 102_00, 200);
+
+        // Executable volume: 98 → 100, 99 → 300, 100 → 500, 101 → 200.
+        let quote = exchange.book().auction_quote(None).unwrap();
+        assert_eq!(quote.price, Price(100_00));
+
+        let result = exchange.run_auction();
+        assert_eq!(result.clearing_price, Some(Price(100_00)));
+        assert_eq!(result.matched_volume, 500);
+        assert_eq!(result.imbalance, -100); // 500 demanded vs 600 offered
+        assert!(result.trades.iter().all(|t| t.price == Price(100_00)));
+        assert_eq!(result.trades.iter().map(|t| t.quantity).sum::<u64>(), 500);
+
+        assert!(!exchange.in_auction());
+        assert!(!exchange.book().is_crossed());
+        assert_eq!(
+            exchange.best_bid_ask(),
+            (Some(Price(99_00)), Some(Price(100_00)))
+        );
+        assert_eq!(exchange.get_order(b1).unwrap().status, OrderStatus::Filled);
+        assert_eq!(exchange.get_order(b2).unwrap().status, OrderStatus::Filled);
+        assert_eq!(exchange.get_order(s3).unwrap().remaining_quantity, 100);
+        assert_eq!(exchange.last_trade_price(), Some(Price(100_00)));
+
+        // Back to continuous matching.
+        let result = exchange.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+        assert_eq!(result.filled_quantity, 50);
+    }
+
+    #[test]
+    fn tie_goes_to_price_nearest_reference() {
+        let mut exchange = Exchange::new();
+        // A prior trade at 100.80 sets the reference price.
+        limit(&mut exchange, Side::Sell, 100_80, 10);
+        limit(&mut exchange, Side::Buy, 100_80, 10);
+
+        exchange.begin_auction();
+        limit(&mut exchange, Side::Buy, 101_00, 100);
+        limit(&mut exchange, Side::Sell, 99_00, 100);
+        assert_eq!(
+            exchange.book().auction_quote(None).unwrap().price,
+            Price(99_00)
+        );
+
+        let result = exchange.run_auction();
+        assert_eq!(result.clearing_price, Some(Price(101_00)));
+        assert_eq!(result.matched_volume, 100);
+        assert_eq!(result.imbalance, 0);
+    }
+
+    #[test]
+    fn uncrossed_book_is_a_no_op() {
+        let mut exchange = Exchange::new();
+        exchange.begin_auction();
+        limit(&mut exchange, Side::Buy, 99_00, 100);
+        limit(&mut exchange, Side::Sell, 101_00, 100);
+
+        assert_eq!(exchange.run_auction(), AuctionResult::default());
+        assert!(!exchange.in_auction());
+        assert_eq!(exchange.book().active_order_count(), 2);
+    }
+
+    #[test]
+    fn auction_reports_truncated_stop_cascade() {
+        let mut exchange = Exchange::new();
+        exchange.set_max_cascade_depth(3);
+        exchange.begin_auction();
+        for i in 0..=10 {
+            limit(&mut exchange, Side::Sell, 100_00 + i * 100, 10);
+        }
+        for i in 0..10 {
+            exchange.submit_stop_market(Side::Buy, Price(100_00 + i * 100), 10);
+        }
+        limit(&mut exchange, Side::Buy, 100_00, 10);
+
+        let result = exchange.run_auction();
+
+        assert_eq!(result.clearing_price, Some(Price(100_00)));
+        assert!(result.cascade_truncated);
+        assert_eq!(exchange.truncated_cascades(), 1);
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn replay_reproduces_auction() {
+        let mut exchange = Exchange::new();
+        exchange.begin_auction();
+        limit(&mut exchange, Side::Buy, 101_00, 200);
+        limit(&mut exchange, Side::Sell, 100_00, 150);
+        exchange.run_auction();
+        limit(&mut exchange, Side::Sell, 101_00, 30);
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.trades(), exchange.trades());
+        assert_eq!(replayed.best_bid_ask(), exchange.best_bid_ask());
+        assert!(!replayed.in_auction());
+    }
+}
@@ -3,9 +3,13 @@
 //! Python computes the weight schedule (factor models, signals, etc.),
 //! Rust handles the inner simulation loop (rebalance, track positions, compute returns).
 
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 
-use crate::portfolio::metrics::{Metrics, compute_metrics};
+use crate::Side;
+use crate::portfolio::metrics::{
+    Metrics, RelativeMetrics, compute_metrics, compute_relative_metrics,
+};
 use crate::portfolio::{CostModel, Portfolio};
 use crate::types::Symbol;
 
@@ -47,6 +51,54 @@ impl BacktestStopConfig {
 pub struct BacktestBridgeOptions {
     /// Optional stop simulation configuration.
     pub stop_cfg: Option<BacktestStopConfig>,
+    /// Optional per-period cash dividends as `(symbol, per_share_cents)`,
+    /// parallel with the weight schedule. Paid on positions held after the
+    /// period's rebalance and stops, before the period return is recorded.
+    /// Periods beyond the schedule's length pay nothing.
+    pub dividends: Option<Vec<Vec<(Symbol, i64)>>>,
+    /// Per-symbol cost models. Symbols not listed pay `cost_bps` commission.
+    pub symbol_costs: HashMap<Symbol, CostModel>,
+    /// Optional rebalance calendar, parallel with the weight schedule. Target
+    /// weights are only traded on `true` periods; positions drift in between,
+    /// while stops, dividends and returns still apply every period. Periods
+    /// beyond the calendar's length do not rebalance. `None` rebalances every
+    /// period.
+    pub rebalance_on: Option<Vec<bool>>,
+    /// Number of leading periods during which weights are ignored and the
+    /// portfolio stays in cash. Those periods still appear in `returns` (as
+    /// 0.0) and the other per-period outputs, but metrics cover only the
+    /// periods after the warmup.
+    pub warmup: usize,
+    /// Bound on the gross target weight (`Σ |weight|`) of each period.
+    /// Negative weights open shorts; periods whose gross weight exceeds the
+    /// bound are scaled down proportionally. `None` leaves weights as given.
+    pub max_gross_weight: Option<f64>,
+    /// Optional benchmark returns, one per period. When the length matches
+    /// the number of periods, [`BacktestBridgeResult::relative_metrics`] is
+    /// computed over the same post-warmup window as `metrics`; otherwise it
+    /// is left `None`.
+    pub benchmark_returns: Option<Vec<f64>>,
+    /// Record every fill in [`BacktestBridgeResult::trades`]. Off by default
+    /// to keep large sweeps allocation-free.
+    pub record_trades: bool,
+}
+
+/// A fill executed during the backtest, recorded when
+/// [`BacktestBridgeOptions::record_trades`] is set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BacktestTrade {
+    /// Period index where the fill happened.
+    pub period_index: usize,
+    /// Symbol traded.
+    pub symbol: Symbol,
+    /// Buy or sell.
+    pub side: Side,
+    /// Shares traded (unsigned).
+    pub quantity: u64,
+    /// Fill price (cents).
+    pub price: i64,
+    /// Transaction cost charged (cents).
+    pub cost: i64,
 }
 
 /// Stop event emitted by stop-aware backtest simulation.
@@ -75,12 +127,19 @@ pub struct BacktestBridgeResult {
     pub final_cash: i64,
     /// Computed metrics (None if no returns).
     pub metrics: Option<Metrics>,
+    /// Beta, alpha, tracking error and information ratio against
+    /// `benchmark_returns` (None without a length-matching benchmark).
+    pub relative_metrics: Option<RelativeMetrics>,
     /// Per-period holdings as (symbol, weight).
     pub holdings: Vec<Vec<(Symbol, f64)>>,
     /// Per-period per-symbol close-to-close returns.
     pub symbol_returns: Vec<Vec<(Symbol, f64)>>,
     /// Stop-trigger events (empty when stop simulation disabled or no triggers).
     pub stop_events: Vec<BacktestStopEvent>,
+    /// Per-period one-sided turnover of the rebalance (0.0 when not rebalanced).
+    pub turnover: Vec<f64>,
+    /// Every fill, in execution order (empty unless `record_trades` is set).
+    pub trades: Vec<BacktestTrade>,
 }
 
 /// Simulate portfolio returns from a pre-computed weight schedule.
@@ -109,7 +168,7 @@ pub fn backtest_weights(
 ///
 /// Returns an empty result (no returns, no metrics) for invalid inputs:
 /// mismatched schedule lengths, non-positive cash, NaN/Inf weights,
-/// negative prices, or cost > 100%.
+/// negative prices, cost > 100%, or a non-positive `max_gross_weight`.
 pub fn backtest_weights_with_options(
     weight_schedule: &[Vec<(Symbol, f64)>],
     price_schedule: &[Vec<(Symbol, i64)>],
@@ -124,7 +183,11 @@ pub fn backtest_weights_with_options(
         price_schedule,
         initial_cash_cents,
         cost_bps,
-    ) {
+    ) || !options.symbol_costs.values().all(valid_cost_model)
+        || options
+            .max_gross_weight
+            .is_some_and(|bound| !(bound.is_finite() && bound > 0.0))
+    {
         return empty_result(initial_cash_cents);
     }
 
@@ -137,15 +200,30 @@ pub fn backtest_weights_with_options(
         commission_bps: cost_bps,
         slippage_bps: 0,
         min_trade_fee: 0,
+        size_impact: None,
+        fee_tiers: None,
     };
 
-    let mut portfolio = Portfolio::new(initial_cash_cents, cost_model);
+    let mut portfolio = Portfolio::new(initial_cash_cents, cost_model).with_symbol_costs(
+        options
+            .symbol_costs
+            .iter()
+            .map(|(&sym, &model)| (sym, model)),
+    );
+    if let Some(bound) = options.max_gross_weight {
+        portfolio = portfolio.with_max_gross_weight(bound);
+    }
+    if options.record_trades {
+        portfolio = portfolio.with_fill_log();
+    }
     let mut equity_curve = Vec::with_capacity(weight_schedule.len() + 1);
     equity_curve.push(initial_cash_cents);
 
     let mut holdings = Vec::with_capacity(weight_schedule.len());
     let mut symbol_returns = Vec::with_capacity(weight_schedule.len());
     let mut stop_events = Vec::new();
+    let mut turnover = Vec::with_capacity(weight_schedule.len());
+    let mut trades = Vec::new();
 
     let mut prev_prices: HashMap<Symbol, i64> = HashMap::new();
     let mut stop_trackers: HashMap<Symbol, StopTracker> = HashMap::new();
@@ -175,8 +253,19 @@ pub fn backtest_weights_with_options(
         period_symbol_returns.sort_by_key(|(sym, _)| *sym);
         symbol_returns.push(period_symbol_returns);
 
-        // Rebalance to target weights first.
-        portfolio.rebalance_simple(weights, prices);
+        // Rebalance to target weights first, on calendar periods after the warmup.
+        let rebalance = period_index >= options.warmup
+            && options
+                .rebalance_on
+                .as_ref()
+                .is_none_or(|calendar| calendar.get(period_index).copied().unwrap_or(false));
+        if rebalance {
+            let prior_weights = portfolio.current_weights(prices);
+            portfolio.rebalance_simple(weights, prices);
+            turnover.push(portfolio.turnover(&prior_weights));
+        } else {
+            turnover.push(0.0);
+        }
 
         // Optional stop simulation runs after target rebalance on each bar.
         if let Some(cfg) = stop_cfg.as_ref() {
@@ -190,6 +279,34 @@ pub fn backtest_weights_with_options(
             );
         }
 
+        trades.extend(
+            portfolio
+                .take_fills()
+                .into_iter()
+                .map(|fill| BacktestTrade {
+                    period_index,
+                    symbol: fill.symbol,
+                    side: if fill.quantity > 0 {
+                        Side::Buy
+                    } else {
+                        Side::Sell
+                    },
+                    quantity: fill.quantity.unsigned_abs(),
+                    price: fill.price,
+                    cost: fill.cost,
+                }),
+        );
+
+        // Credit dividends on positions held going into the close.
+        if let Some(period_dividends) = options.dividends.as_ref().and_then(|d| d.get(period_index))
+        {
+            for &(sym, per_share) in period_dividends {
+                if per_share > 0 {
+                    portfolio.apply_dividend(sym, per_share);
+                }
+            }
+        }
+
         // Record return for this period.
         portfolio.record_return(prices);
 
@@ -205,19 +322,78 @@ pub fn backtest_weights_with_options(
     }
 
     let returns = portfolio.returns().to_vec();
-    let metrics = compute_metrics(&returns, periods_per_year, risk_free);
+    let warmup = options.warmup.min(returns.len());
+    let metrics = compute_metrics(&returns[warmup..], periods_per_year, risk_free);
+    let relative_metrics = options
+        .benchmark_returns
+        .as_deref()
+        .filter(|benchmark| benchmark.len() == returns.len())
+        .and_then(|benchmark| {
+            compute_relative_metrics(
+                &returns[warmup..],
+                &benchmark[warmup..],
+                periods_per_year,
+                risk_free,
+            )
+        });
 
     BacktestBridgeResult {
         returns,
         equity_curve,
         final_cash: portfolio.cash(),
         metrics,
+        relative_metrics,
         holdings,
         symbol_returns,
         stop_events,
+        turnover,
+        trades,
     }
 }
 
+/// Run [`backtest_weights_with_options`] once per stop configuration, in parallel.
+///
+/// Results are returned in the order of `configs`, independent of thread
+/// scheduling. Metrics are `None` where the backtest produced no returns
+/// (e.g. invalid inputs). No dividends are applied.
+#[cfg(feature = "parallel")]
+pub fn sweep_stop_configs(
+    weight_schedule: &[Vec<(Symbol, f64)>],
+    price_schedule: &[Vec<(Symbol, i64)>],
+    initial_cash_cents: i64,
+    cost_bps: u32,
+    periods_per_year: f64,
+    risk_free: f64,
+    configs: &[BacktestStopConfig],
+) -> Vec<(BacktestStopConfig, Option<Metrics>)> {
+    use rayon::prelude::*;
+
+    configs
+        .par_iter()
+        .map(|cfg| {
+            let options = BacktestBridgeOptions {
+                stop_cfg: Some(cfg.clone()),
+                ..Default::default()
+            };
+            let result = backtest_weights_with_options(
+                weight_schedule,
+                price_schedule,
+                initial_cash_cents,
+                cost_bps,
+                periods_per_year,
+                risk_free,
+                options,
+            );
+            (cfg.clone(), result.metrics)
+        })
+        .collect()
+}
+
+/// Costs above 100% of notional or negative minimum fees are rejected.
+fn valid_cost_model(model: &CostModel) -> bool {
+    model.commission_bps as u64 + model.slippage_bps as u64 <= 10_000 && model.min_trade_fee >= 0
+}
+
 fn valid_inputs(
     weight_schedule: &[Vec<(Symbol, f64)>],
     price_schedule: &[Vec<(Symbol, i64)>],
@@ -256,9 +432,12 @@ fn empty_result(initial_cash_cents: i64) -> BacktestBridgeResult {
         equity_curve: vec![initial_cash_cents],
         final_cash: initial_cash_cents,
         metrics: None,
+        relative_metrics: None,
         holdings: Vec::new(),
         symbol_returns: Vec::new(),
         stop_events: Vec::new(),
+        turnover: Vec::new(),
+        trades: Vec::new(),
     }
 }
 
@@ -343,15 +522,20 @@ fn apply_stop_cfg(
     for (sym, qty, price) in open_positions {
         let side = if qty >= 0 { 1 } else { -1 };
 
-        let tracker = trackers
-            .entry(sym)
-            .or_insert_with(|| StopTracker::new(price, side));
-
-        if tracker.side != side {
-            *tracker = StopTracker::new(price, side);
-        } else {
-            tracker.update(price, cfg.atr_period);
-        }
+        // A position opened (or flipped through flat) this bar starts a fresh
+        // tracker at the current price; only held positions accumulate moves.
+        let tracker = match trackers.entry(sym) {
+            Entry::Occupied(entry) => {
+                let tracker = entry.into_mut();
+                if tracker.side == side {
+                    tracker.update(price, cfg.atr_period);
+                } else {
+                    *tracker = StopTracker::new(price, side);
+                }
+                tracker
+            }
+            Entry::Vacant(entry) => entry.insert(StopTracker::new(price, side)),
+        };
 
         let Some((stop_level, reason)) = effective_stop_level(cfg, tracker) else {
             continue;
@@ -406,7 +590,7 @@ fn effective_stop_level(
     }
 
     if let Some(mult) = cfg.atr_multiple
-        && let Some(atr) = tracker.atr(cfg.atr_period)
+        && let Some(atr) = tracker.atr(cfg.atr_period).filter(|atr| *atr > 0.0)
     {
         let level = if tracker.side > 0 {
             (tracker.reference_price as f64 - mult * atr).round() as i64
@@ -504,6 +688,7 @@ mod tests {
                 atr_multiple: None,
                 atr_period: 14,
             }),
+            ..Default::default()
         };
 
         let result =
@@ -537,6 +722,7 @@ mod tests {
                 atr_multiple: None,
                 atr_period: 14,
             }),
+            ..Default::default()
         };
 
         let result =
@@ -546,6 +732,127 @@ mod tests {
         assert_eq!(result.stop_events[0].reason, "trailing");
     }
 
+    fn stop_options(cfg: BacktestStopConfig) -> BacktestBridgeOptions {
+        BacktestBridgeOptions {
+            stop_cfg: Some(cfg),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn short_trailing_stop_triggers_on_rally() {
+        let weights = vec![vec![(aapl(), -0.5)]; 4];
+        let prices: Vec<_> = [100_00, 95_00, 90_00, 96_00]
+            .iter()
+            .map(|&px| vec![(aapl(), px)])
+            .collect();
+        let options = stop_options(BacktestStopConfig {
+            fixed_stop_pct: None,
+            trailing_stop_pct: Some(0.05),
+            atr_multiple: None,
+            atr_period: 14,
+        });
+
+        let result =
+            backtest_weights_with_options(&weights, &prices, 100_000_00, 0, 252.0, 0.0, options);
+
+        assert!(result.holdings[0][0].1 < 0.0);
+        assert_eq!(result.stop_events.len(), 1);
+        let event = &result.stop_events[0];
+        assert_eq!(event.reason, "trailing");
+        assert_eq!(event.period_index, 3);
+        // Trails the low of 90.00 by 5%.
+        assert_eq!(event.trigger_price, 94_50);
+        assert_eq!(event.exit_price, 96_00);
+        assert!(result.holdings[3].is_empty());
+    }
+
+    #[test]
+    fn short_atr_stop_sits_above_the_low() {
+        let weights = vec![vec![(aapl(), -0.5)]; 5];
+        let prices: Vec<_> = [100_00, 99_00, 98_00, 97_00, 100_00]
+            .iter()
+            .map(|&px| vec![(aapl(), px)])
+            .collect();
+        let options = stop_options(BacktestStopConfig {
+            fixed_stop_pct: None,
+            trailing_stop_pct: None,
+            atr_multiple: Some(2.0),
+            atr_period: 4,
+        });
+
+        let result =
+            backtest_weights_with_options(&weights, &prices, 100_000_00, 0, 252.0, 0.0, options);
+
+        assert_eq!(result.stop_events.len(), 1);
+        let event = &result.stop_events[0];
+        assert_eq!(event.reason, "atr");
+        assert_eq!(event.period_index, 4);
+        // ATR over the last 4 moves is 1.50, so the stop is 97.00 + 2 × 1.50.
+        assert_eq!(event.trigger_price, 100_00);
+    }
+
+    #[test]
+    fn flipping_long_to_short_restarts_the_stop_from_the_flip() {
+        let weights = vec![
+            vec![(aapl(), 0.5)],
+            vec![(aapl(), 0.5)],
+            vec![(aapl(), -0.5)],
+            vec![(aapl(), -0.5)],
+            vec![(aapl(), -0.5)],
+        ];
+        let prices: Vec<_> = [100_00, 100_00, 100_00, 108_00, 111_00]
+            .iter()
+            .map(|&px| vec![(aapl(), px)])
+            .collect();
+        let options = BacktestBridgeOptions {
+            record_trades: true,
+            ..stop_options(BacktestStopConfig {
+                fixed_stop_pct: Some(0.10),
+                trailing_stop_pct: None,
+                atr_multiple: None,
+                atr_period: 14,
+            })
+        };
+
+        let result =
+            backtest_weights_with_options(&weights, &prices, 100_000_00, 0, 252.0, 0.0, options);
+
+        // One fill crosses through flat.
+        let flip = &result.trades[1];
+        assert_eq!((flip.period_index, flip.side), (2, Side::Sell));
+        assert_eq!(flip.quantity, 1_000);
+
+        assert_eq!(result.stop_events.len(), 1);
+        let event = &result.stop_events[0];
+        assert_eq!(event.reason, "fixed");
+        assert_eq!(event.period_index, 4);
+        assert_eq!(event.trigger_price, 110_00);
+    }
+
+    #[test]
+    fn gross_weight_bound_scales_long_short_targets() {
+        let weights = vec![vec![(aapl(), 1.2), (msft(), -0.8)]];
+        let prices = vec![vec![(aapl(), 100_00), (msft(), 100_00)]];
+        let options = BacktestBridgeOptions {
+            max_gross_weight: Some(1.0),
+            ..Default::default()
+        };
+
+        let result =
+            backtest_weights_with_options(&weights, &prices, 100_000_00, 0, 252.0, 0.0, options);
+
+        assert_eq!(result.holdings[0], vec![(aapl(), 0.6), (msft(), -0.4)]);
+
+        let invalid = BacktestBridgeOptions {
+            max_gross_weight: Some(0.0),
+            ..Default::default()
+        };
+        let result =
+            backtest_weights_with_options(&weights, &prices, 100_000_00, 0, 252.0, 0.0, invalid);
+        assert!(result.returns.is_empty());
+    }
+
     #[test]
     fn first_breach_triggers_once_per_position_lifecycle() {
         let weights = vec![
@@ -566,6 +873,7 @@ mod tests {
                 atr_multiple: None,
                 atr_period: 14,
             }),
+            ..Default::default()
         };
 
         let result =
@@ -596,6 +904,7 @@ mod tests {
                 atr_multiple: None,
                 atr_period: 14,
             }),
+            ..Default::default()
         };
 
         let result =
@@ -605,4 +914,253 @@ mod tests {
         assert_eq!(result.stop_events[0].reason, "trailing");
         assert_eq!(result.stop_events[0].trigger_price, 104_50);
     }
+
+    #[test]
+    fn dividends_raise_cash_and_period_return() {
+        let weights = vec![vec![(aapl(), 1.0)], vec![(aapl(), 1.0)]];
+        let prices = vec![vec![(aapl(), 100_00)], vec![(aapl(), 100_00)]];
+
+        let base = backtest_weights(&weights, &prices, 10_000_00, 0, 252.0, 0.0);
+        let options = BacktestBridgeOptions {
+            dividends: Some(vec![vec![], vec![(aapl(), 1_00), (msft(), 5_00)]]),
+            ..Default::default()
+        };
+        let with_div =
+            backtest_weights_with_options(&weights, &prices, 10_000_00, 0, 252.0, 0.0, options);
+
+        // 100 shares x $1 = $100; MSFT is not held so pays nothing.
+        assert_eq!(with_div.final_cash - base.final_cash, 100_00);
+        assert!(with_div.returns[1] > base.returns[1]);
+        assert!(with_div.holdings[1].iter().all(|(s, _)| *s != msft()));
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn stop_config_sweep_matches_serial_runs() {
+        let prices: Vec<_> = [100_00, 104_00, 97_00, 92_00, 99_00, 106_00, 101_00]
+            .iter()
+            .map(|&p| vec![(aapl(), p)])
+            .collect();
+        let weights = vec![vec![(aapl(), 1.0)]; prices.len()];
+        let configs: Vec<_> = [0.03, 0.08, 0.20]
+            .iter()
+            .map(|&pct| BacktestStopConfig {
+                fixed_stop_pct: Some(pct),
+                atr_period: 14,
+                ..Default::default()
+            })
+            .collect();
+
+        let swept = sweep_stop_configs(&weights, &prices, 100_000_00, 10, 252.0, 0.0, &configs);
+        assert_eq!(swept.len(), 3);
+
+        let serial: Vec<f64> = configs
+            .iter()
+            .map(|cfg| {
+                let options = BacktestBridgeOptions {
+                    stop_cfg: Some(cfg.clone()),
+                    ..Default::default()
+                };
+                backtest_weights_with_options(
+                    &weights, &prices, 100_000_00, 10, 252.0, 0.0, options,
+                )
+                .metrics
+                .unwrap()
+                .sharpe
+            })
+            .collect();
+
+        for ((cfg, metrics), (input, sharpe)) in swept.iter().zip(configs.iter().zip(&serial)) {
+            assert_eq!(cfg.fixed_stop_pct, input.fixed_stop_pct);
+            assert_eq!(metrics.as_ref().unwrap().sharpe.to_bits(), sharpe.to_bits());
+        }
+
+        let best = |sharpes: Vec<f64>| {
+            (0..sharpes.len())
+                .max_by(|&a, &b| sharpes[a].total_cmp(&sharpes[b]))
+                .unwrap()
+        };
+        let swept_sharpes = swept
+            .iter()
+            .map(|(_, m)| m.as_ref().unwrap().sharpe)
+            .collect();
+        assert_eq!(best(swept_sharpes), best(serial));
+    }
+
+    #[test]
+    fn symbol_costs_override_default_cost() {
+        let weights = vec![vec![(aapl(), 0.4), (msft(), 0.4)]];
+        let prices = vec![vec![(aapl(), 100_00), (msft(), 100_00)]];
+        let high_fee = CostModel {
+            commission_bps: 200,
+            slippage_bps: 0,
+            min_trade_fee: 0,
+            size_impact: None,
+            fee_tiers: None,
+        };
+        let options = BacktestBridgeOptions {
+            symbol_costs: HashMap::from([(msft(), high_fee)]),
+            ..Default::default()
+        };
+
+        let result =
+            backtest_weights_with_options(&weights, &prices, 100_000_00, 10, 252.0, 0.0, options);
+
+        // $40K per leg: AAPL pays the 10 bps default ($40), MSFT 200 bps ($800).
+        assert_eq!(result.final_cash, 100_000_00 - 80_000_00 - 40_00 - 800_00);
+
+        let invalid = BacktestBridgeOptions {
+            symbol_costs: HashMap::from([(
+                msft(),
+                CostModel {
+                    commission_bps: 10_001,
+                    ..high_fee
+                },
+            )]),
+            ..Default::default()
+        };
+        let result =
+            backtest_weights_with_options(&weights, &prices, 100_000_00, 10, 252.0, 0.0, invalid);
+        assert!(result.returns.is_empty());
+    }
+
+    #[test]
+    fn monthly_calendar_lowers_turnover_and_lets_weights_drift() {
+        // Two assets trending apart over a year of daily bars.
+        let prices: Vec<_> = (0..252)
+            .map(|t| {
+                vec![
+                    (aapl(), 100_00 + 10 * t as i64),
+                    (msft(), 100_00 - 5 * t as i64),
+                ]
+            })
+            .collect();
+        let weights = vec![vec![(aapl(), 0.5), (msft(), 0.5)]; prices.len()];
+        let monthly: Vec<bool> = (0..prices.len()).map(|t| t % 21 == 0).collect();
+
+        let daily = backtest_weights(&weights, &prices, 1_000_000_00, 10, 252.0, 0.0);
+        let options = BacktestBridgeOptions {
+            rebalance_on: Some(monthly),
+            ..Default::default()
+        };
+        let monthly =
+            backtest_weights_with_options(&weights, &prices, 1_000_000_00, 10, 252.0, 0.0, options);
+
+        let total = |r: &BacktestBridgeResult| r.turnover.iter().sum::<f64>();
+        assert!(total(&monthly) < total(&daily));
+        assert_eq!(monthly.turnover[1], 0.0);
+        assert_ne!(monthly.equity_curve.last(), daily.equity_curve.last());
+        assert_eq!(monthly.returns.len(), 252);
+
+        // Between rebalances, AAPL's weight drifts up instead of resetting to 0.5.
+        let aapl_weight = |r: &BacktestBridgeResult, t: usize| r.holdings[t][0].1;
+        assert!(aapl_weight(&monthly, 20) > aapl_weight(&monthly, 1));
+        assert!((aapl_weight(&daily, 20) - aapl_weight(&daily, 1)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn warmup_holds_cash_and_excludes_periods_from_metrics() {
+        let prices: Vec<_> = (0..40)
+            .map(|t| vec![(aapl(), 100_00 + [0, 300, -200, 500][t % 4] + 20 * t as i64)])
+            .collect();
+        let weights = vec![vec![(aapl(), 0.8)]; prices.len()];
+        let options = BacktestBridgeOptions {
+            warmup: 10,
+            ..Default::default()
+        };
+
+        let result =
+            backtest_weights_with_options(&weights, &prices, 100_000_00, 10, 252.0, 0.0, options);
+
+        assert!(result.holdings[..10].iter().all(Vec::is_empty));
+        assert!(!result.holdings[10].is_empty());
+        assert_eq!(result.returns.len(), 40);
+        assert!(result.returns[..10].iter().all(|r| *r == 0.0));
+
+        let metrics = result.metrics.unwrap();
+        let on_tail = compute_metrics(&result.returns[10..], 252.0, 0.0).unwrap();
+        assert_eq!(metrics.sharpe, on_tail.sharpe);
+        assert_eq!(metrics.num_periods, 30);
+
+        // Equivalent to starting the backtest at the first post-warmup bar.
+        let truncated = backtest_weights(&weights[10..], &prices[10..], 100_000_00, 10, 252.0, 0.0);
+        assert_eq!(truncated.returns, result.returns[10..]);
+        assert_eq!(truncated.metrics.unwrap().sharpe, metrics.sharpe);
+    }
+
+    #[test]
+    fn benchmark_equal_to_strategy_has_unit_beta() {
+        let prices: Vec<_> = [100_00, 103_00, 99_00, 104_00, 102_00, 107_00]
+            .iter()
+            .map(|&px| vec![(aapl(), px)])
+            .collect();
+        let weights = vec![vec![(aapl(), 1.0)]; prices.len()];
+        let strategy = backtest_weights(&weights, &prices, 100_000_00, 0, 252.0, 0.0);
+        assert!(strategy.relative_metrics.is_none());
+
+        let options = BacktestBridgeOptions {
+            benchmark_returns: Some(strategy.returns.clone()),
+            ..Default::default()
+        };
+        let result =
+            backtest_weights_with_options(&weights, &prices, 100_000_00, 0, 252.0, 0.0, options);
+        let relative = result.relative_metrics.unwrap();
+        assert!((relative.beta - 1.0).abs() < 1e-12);
+        assert_eq!(relative.tracking_error, 0.0);
+        assert_eq!(relative.information_ratio, 0.0);
+
+        // A benchmark of the wrong length is ignored rather than rejected.
+        let options = BacktestBridgeOptions {
+            benchmark_returns: Some(strategy.returns[1..].to_vec()),
+            ..Default::default()
+        };
+        let result =
+            backtest_weights_with_options(&weights, &prices, 100_000_00, 0, 252.0, 0.0, options);
+        assert!(result.relative_metrics.is_none());
+        assert!(result.metrics.is_some());
+    }
+
+    #[test]
+    fn trade_log_records_every_fill_and_reconciles_cash() {
+        let weights = vec![vec![(aapl(), 0.4), (msft(), 0.4)], vec![(aapl(), 0.6)]];
+        let prices = vec![
+            vec![(aapl(), 100_00), (msft(), 200_00)],
+            vec![(aapl(), 110_00), (msft(), 190_00)],
+        ];
+
+        let silent = backtest_weights(&weights, &prices, 100_000_00, 10, 252.0, 0.0);
+        assert!(silent.trades.is_empty());
+
+        let options = BacktestBridgeOptions {
+            record_trades: true,
+            ..Default::default()
+        };
+        let result =
+            backtest_weights_with_options(&weights, &prices, 100_000_00, 10, 252.0, 0.0, options);
+
+        // Two opening buys, then MSFT is closed and AAPL topped up.
+        assert_eq!(result.trades.len(), 4);
+        assert!(result.trades[..2].iter().all(|t| t.period_index == 0));
+        assert!(result.trades[..2].iter().all(|t| t.side == Side::Buy));
+        let msft_exit = result.trades.iter().find(|t| t.side == Side::Sell).unwrap();
+        assert_eq!(
+            (msft_exit.symbol, msft_exit.period_index, msft_exit.price),
+            (msft(), 1, 190_00)
+        );
+        assert_eq!(result.final_cash, silent.final_cash);
+
+        let cash_spent: i64 = result
+            .trades
+            .iter()
+            .map(|t| {
+                let notional = t.quantity as i64 * t.price;
+                match t.side {
+                    Side::Buy => notional + t.cost,
+                    Side::Sell => -notional + t.cost,
+                }
+            })
+            .sum();
+        assert_eq!(100_000_00 - result.final_cash, cash_spent);
+        assert!(result.trades.iter().all(|t| t.cost > 0));
+    }
 }
@@ -7,7 +7,10 @@
 
 use rustc_hash::FxHashMap;
 
-use crate::{Order, OrderId, Price, PriceLevels, Quantity, Side, TimeInForce, Timestamp, TradeId};
+use crate::{
+    BookInvariantError, Order, OrderId, Price, PriceLevels, Quantity, Side, TimeInForce, Timestamp,
+    TradeId, TradePriceRule,
+};
 
 // Re-import for tests only
 #[cfg(test)]
@@ -18,6 +21,7 @@ use crate::OrderStatus;
 /// Maintains both sides of the book plus a central index of all orders
 /// (active and historical) for O(1) lookup.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrderBook {
     /// Buy orders, sorted by price descending (best = highest)
     bids: PriceLevels,
@@ -31,6 +35,9 @@ pub struct OrderBook {
     next_trade_id: u64,
     /// Next timestamp to assign (monotonic counter)
     next_timestamp: u64,
+    /// Price convention for trades produced by matching
+    #[cfg_attr(feature = "serde", serde(default))]
+    trade_price_rule: TradePriceRule,
 }
 
 impl OrderBook {
@@ -43,9 +50,41 @@ impl OrderBook {
             next_order_id: 1,
             next_trade_id: 1,
             next_timestamp: 1,
+            trade_price_rule: TradePriceRule::default(),
         }
     }
 
+    /// Use `rule` to price trades produced by matching.
+    pub fn with_trade_price_rule(mut self, rule: TradePriceRule) -> Self {
+        self.trade_price_rule = rule;
+        self
+    }
+
+    /// The price convention for trades produced by matching.
+    pub fn trade_price_rule(&self) -> TradePriceRule {
+        self.trade_price_rule
+    }
+
+    /// Start order IDs, trade IDs and timestamps just above the given
+    /// offsets, so books seeded with disjoint offsets never share an ID.
+    pub fn with_id_offsets(mut self, order_offset: u64, trade_offset: u64, ts_offset: u64) -> Self {
+        self.raise_id_offsets(order_offset, trade_offset, ts_offset);
+        self
+    }
+
+    /// Move each counter past its offset; counters already beyond it are
+    /// left alone so IDs stay monotonic.
+    pub(crate) fn raise_id_offsets(
+        &mut self,
+        order_offset: u64,
+        trade_offset: u64,
+        ts_offset: u64,
+    ) {
+        self.next_order_id = self.next_order_id.max(order_offset + 1);
+        self.next_trade_id = self.next_trade_id.max(trade_offset + 1);
+        self.next_timestamp = self.next_timestamp.max(ts_offset + 1);
+    }
+
     // === ID and timestamp generation ===
 
     /// Generate the next order ID (monotonically increasing).
@@ -74,6 +113,16 @@ impl OrderBook {
         OrderId(self.next_order_id)
     }
 
+    /// Peek at what the next trade ID would be (without consuming it).
+    pub fn peek_next_trade_id(&self) -> TradeId {
+        TradeId(self.next_trade_id)
+    }
+
+    /// Peek at what the next timestamp would be (without consuming it).
+    pub fn peek_next_timestamp(&self) -> Timestamp {
+        self.next_timestamp
+    }
+
     // === Order access ===
 
     /// Get an order by ID (includes historical filled/cancelled orders).
@@ -175,7 +224,7 @@ impl OrderBook {
     }
 
     /// Check if the book is crossed (best bid >= best ask).
-    /// This should never happen after matching is complete.
+    /// This only happens during an auction call phase, never after matching.
     pub fn is_crossed(&self) -> bool {
         match (self.best_bid(), self.best_ask()) {
             (Some(bid), Some(ask)) => bid >= ask,
@@ -183,6 +232,69 @@ impl OrderBook {
         }
     }
 
+    // === Integrity ===
+
+    /// Check the book's internal invariants, returning the first violation.
+    ///
+    /// Verifies that each side's cached best price matches its best level,
+    /// that every queued order ID is an active order in the central index
+    /// resting on that side and price, that each level's quantity equals the
+    /// sum of its orders' remaining quantities, and that the book is not
+    /// crossed. Walks the whole book, so it is O(n) in resting orders.
+    pub fn validate(&self) -> Result<(), BookInvariantError> {
+        self.validate_levels()?;
+        if let (Some(bid), Some(ask)) = self.best_bid_ask()
+            && bid >= ask
+        {
+            return Err(BookInvariantError::CrossedBook { bid, ask });
+        }
+        Ok(())
+    }
+
+    /// [`validate`](Self::validate) minus the crossed-book check.
+    pub(crate) fn validate_levels(&self) -> Result<(), BookInvariantError> {
+        for levels in [&self.bids, &self.asks] {
+            levels.validate_best_price()?;
+            let side = levels.side();
+            for (&price, level) in levels.iter_best_to_worst() {
+                let mut actual: Quantity = 0;
+                for order_id in level.iter() {
+                    let Some(order) = self.orders.get(&order_id) else {
+                        return Err(BookInvariantError::UnknownOrder {
+                            side,
+                            price,
+                            order_id,
+                        });
+                    };
+                    if !order.is_active() {
+                        return Err(BookInvariantError::InactiveOrder {
+                            side,
+                            price,
+                            order_id,
+                        });
+                    }
+                    if order.side != side || order.price != price {
+                        return Err(BookInvariantError::MisplacedOrder {
+                            side,
+                            price,
+                            order_id,
+                        });
+                    }
+                    actual += order.remaining_quantity;
+                }
+                if actual != level.total_quantity() {
+                    return Err(BookInvariantError::LevelQuantity {
+                        side,
+                        price,
+                        cached: level.total_quantity(),
+                        actual,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
     // === Order management ===
 
     /// Add a new order to the book.
@@ -234,11 +346,32 @@ impl OrderBook {
         order.cancel();
 
         // Mark as tombstone in price level (O(1))
-        self.side_mut(side).mark_tombstone(price, index, remaining);
+        self.side_mut(side)
+            .tombstone_order(price, index, order_id, remaining);
 
         Some(remaining)
     }
 
+    /// Shrink a resting order's remaining quantity to `new_remaining`,
+    /// keeping its place in the queue.
+    ///
+    /// Returns the quantity removed, or `None` if the order is not active or
+    /// `new_remaining` is zero or not below its remaining quantity.
+    pub fn reduce_order(&mut self, order_id: OrderId, new_remaining: Quantity) -> Option<Quantity> {
+        let order = self.orders.get_mut(&order_id)?;
+        if !order.is_active() || new_remaining == 0 || new_remaining >= order.remaining_quantity {
+            return None;
+        }
+
+        let reduced = order.remaining_quantity - new_remaining;
+        order.remaining_quantity = new_remaining;
+        let (side, price) = (order.side, order.price);
+        self.side_mut(side)
+            .get_level_mut(price)?
+            .decrease_quantity(reduced);
+        Some(reduced)
+    }
+
     /// Create a new order with auto-generated ID and timestamp.
     ///
     /// This is a convenience method that:
@@ -273,9 +406,24 @@ impl OrderBook {
     }
 
     /// Remove all tombstones from the book.
+    ///
+    /// Resting orders' queue positions are rebased so cancels keep their
+    /// O(1) path.
     pub fn compact(&mut self) {
         self.bids.compact();
         self.asks.compact();
+
+        let levels = self
+            .bids
+            .iter_best_to_worst()
+            .chain(self.asks.iter_best_to_worst());
+        for (_, level) in levels {
+            for (slot, id) in level.orders.iter().enumerate() {
+                if let Some(order) = self.orders.get_mut(id) {
+                    order.position_in_level = level.head + slot;
+                }
+            }
+        }
     }
 }
 
@@ -567,4 +715,152 @@ mod tests {
         assert!(book.get_order(o2_id).is_some());
         assert!(book.get_order(o3_id).is_some());
     }
+
+    #[test]
+    fn order_index_lookup_at_scale() {
+        let mut book = OrderBook::new();
+        for i in 0..10_000u64 {
+            let side = if i % 2 == 0 { Side::Buy } else { Side::Sell };
+            let price = match side {
+                Side::Buy => Price(99_00 - (i % 100) as i64),
+                Side::Sell => Price(101_00 + (i % 100) as i64),
+            };
+            let order = book.create_order(side, price, 1 + i % 7, TimeInForce::GTC);
+            book.add_order(order);
+        }
+        for id in (3..=10_000).step_by(3) {
+            book.cancel_order(OrderId(id));
+        }
+
+        for id in 1..=10_000u64 {
+            let order = book.get_order(OrderId(id)).unwrap();
+            assert_eq!(order.id, OrderId(id));
+            assert_eq!(order.original_quantity, 1 + (id - 1) % 7);
+            assert_eq!(order.is_active(), id % 3 != 0);
+        }
+        assert!(book.get_order(OrderId(10_001)).is_none());
+        assert_eq!(book.active_order_count(), 10_000 - 3_333);
+        assert_eq!(book.validate(), Ok(()));
+    }
+
+    fn two_sided_book() -> OrderBook {
+        let mut book = OrderBook::new();
+        for (side, price) in [
+            (Side::Buy, Price(99_00)),
+            (Side::Buy, Price(98_00)),
+            (Side::Buy, Price(99_00)),
+            (Side::Sell, Price(101_00)),
+        ] {
+            let order = book.create_order(side, price, 100, TimeInForce::GTC);
+            book.add_order(order);
+        }
+        book
+    }
+
+    #[test]
+    fn validate_passes_healthy_book() {
+        let mut book = two_sided_book();
+        assert_eq!(book.validate(), Ok(()));
+
+        book.cancel_order(OrderId(1));
+        book.reduce_order(OrderId(3), 40);
+        assert_eq!(book.validate(), Ok(()));
+        book.compact();
+        assert_eq!(book.validate(), Ok(()));
+    }
+
+    #[test]
+    fn compact_rebases_queue_positions() {
+        let mut book = two_sided_book();
+        let order = book.create_order(Side::Buy, Price(99_00), 100, TimeInForce::GTC);
+        book.add_order(order);
+
+        book.cancel_order(OrderId(1));
+        book.compact();
+
+        let level = book.bids().get_level(Price(99_00)).unwrap();
+        for id in [OrderId(3), OrderId(5)] {
+            let position = book.get_order(id).unwrap().position_in_level;
+            assert_eq!(level.orders.get(position - level.head), Some(&id));
+        }
+
+        book.cancel_order(OrderId(5));
+        let level = book.bids().get_level(Price(99_00)).unwrap();
+        assert_eq!(level.iter().collect::<Vec<_>>(), vec![OrderId(3)]);
+        assert_eq!(book.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_detects_stale_best_price() {
+        let mut book = two_sided_book();
+        book.bids_mut().set_cached_best_price(Some(Price(98_00)));
+
+        let err = book.validate().unwrap_err();
+        assert_eq!(
+            err,
+            BookInvariantError::StaleBestPrice {
+                side: Side::Buy,
+                cached: Some(Price(98_00)),
+                actual: Some(Price(99_00)),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "BUY best price cached as $98.00 but best level is $99.00"
+        );
+    }
+
+    #[test]
+    fn validate_detects_corrupted_levels() {
+        let mut book = two_sided_book();
+        book.bids_mut()
+            .get_level_mut(Price(98_00))
+            .unwrap()
+            .push_back(OrderId(42), 10);
+        assert_eq!(
+            book.validate(),
+            Err(BookInvariantError::UnknownOrder {
+                side: Side::Buy,
+                price: Price(98_00),
+                order_id: OrderId(42),
+            })
+        );
+
+        let mut book = two_sided_book();
+        book.get_order_mut(OrderId(4)).unwrap().remaining_quantity = 60;
+        assert_eq!(
+            book.validate(),
+            Err(BookInvariantError::LevelQuantity {
+                side: Side::Sell,
+                price: Price(101_00),
+                cached: 100,
+                actual: 60,
+            })
+        );
+
+        let mut book = two_sided_book();
+        book.get_order_mut(OrderId(2)).unwrap().status = OrderStatus::Cancelled;
+        assert!(matches!(
+            book.validate(),
+            Err(BookInvariantError::InactiveOrder {
+                order_id: OrderId(2),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_detects_crossed_book() {
+        let mut book = two_sided_book();
+        let order = book.create_order(Side::Sell, Price(99_00), 10, TimeInForce::GTC);
+        book.add_order(order);
+        assert_eq!(
+            book.validate(),
+            Err(BookInvariantError::CrossedBook {
+                bid: Price(99_00),
+                ask: Price(99_00),
+            })
+        );
+        assert_eq!(book.validate_levels(), Ok(()));
+    }
 }
@@ -0,0 +1,281 @@
+//! Checkpoints: snapshot-plus-delta compaction for event logs.
+//!
+//! Replaying from genesis gets slow for books with millions of events.
+//! A [`Checkpoint`] captures the full exchange state (order book, stop book,
+//! trade history, cumulative volume stats, delayed orders, imbalance
+//! samples, and ID/timestamp counters) so a restore only has to apply the
+//! events recorded after it. Requires the `event-log` feature.
+//!
+//! ```
+//! use nanobook::{Exchange, Price, Side, TimeInForce};
+//!
+//! let mut exchange = Exchange::new();
+//! exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+//! let checkpoint = exchange.checkpoint();
+//!
+//! exchange.submit_limit(Side::Buy, Price(101_00), 40, TimeInForce::GTC);
+//! let tail = &exchange.events()[checkpoint.event_count()..];
+//!
+//! let restored = Exchange::restore(checkpoint, tail);
+//! assert_eq!(restored.best_bid_ask(), exchange.best_bid_ask());
+//! assert_eq!(restored.trades(), exchange.trades());
+//! ```
+
+use std::cmp::Reverse;
+
+use crate::exchange::ImbalanceRecorder;
+use crate::latency::DelayedOrder;
+use crate::{
+    Exchange, ExecutionQualityStats, InstrumentSpec, OrderBook, OrderId, Price, Quantity, StopBook,
+    Timestamp, Trade, TradeId,
+};
+
+/// Full exchange state at a point in its event history.
+///
+/// Serializable with the `serde` feature, so a periodic checkpoint plus a
+/// short event tail can replace a full log on disk.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint {
+    book: OrderBook,
+    stop_book: StopBook,
+    trades: Vec<Trade>,
+    last_trade_price: Option<Price>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    in_auction: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    matched_quantity: Quantity,
+    #[cfg_attr(feature = "serde", serde(default))]
+    matched_notional: i128,
+    #[cfg_attr(feature = "serde", serde(default))]
+    trade_count: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    instrument: InstrumentSpec,
+    #[cfg_attr(feature = "serde", serde(default))]
+    delayed: Vec<DelayedOrder>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    delayed_sequence: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    sim_time: Timestamp,
+    #[cfg_attr(feature = "serde", serde(default))]
+    execution_quality: ExecutionQualityStats,
+    #[cfg_attr(feature = "serde", serde(default = "default_max_cascade_depth"))]
+    max_cascade_depth: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    truncated_cascades: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    imbalance: Option<ImbalanceRecorder>,
+    event_count: usize,
+}
+
+/// Cascade limit of checkpoints saved before it was recorded.
+#[cfg(feature = "serde")]
+fn default_max_cascade_depth() -> usize {
+    Exchange::DEFAULT_MAX_CASCADE_DEPTH
+}
+
+impl Checkpoint {
+    /// Number of events the exchange had recorded when the checkpoint was taken.
+    ///
+    /// Slice the full log with `&events[checkpoint.event_count()..]` to get
+    /// the tail to pass to [`Exchange::restore`].
+    pub fn event_count(&self) -> usize {
+        self.event_count
+    }
+
+    /// Next order ID the restored exchange will assign.
+    pub fn next_order_id(&self) -> OrderId {
+        self.book.peek_next_order_id()
+    }
+
+    /// Next trade ID the restored exchange will assign.
+    pub fn next_trade_id(&self) -> TradeId {
+        self.book.peek_next_trade_id()
+    }
+
+    /// Next timestamp the restored exchange will assign.
+    pub fn next_timestamp(&self) -> Timestamp {
+        self.book.peek_next_timestamp()
+    }
+}
+
+impl Exchange {
+    /// Capture the exchange state as a [`Checkpoint`].
+    ///
+    /// The event log itself is not included; only its length is recorded.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            book: self.book.clone(),
+            stop_book: self.stop_book.clone(),
+            trades: self.trades.clone(),
+            last_trade_price: self.last_trade_price,
+            in_auction: self.in_auction,
+            matched_quantity: self.matched_quantity,
+            matched_notional: self.matched_notional,
+            trade_count: self.trade_count,
+            instrument: self.instrument,
+            delayed: self.delayed.iter().map(|order| order.0.clone()).collect(),
+            delayed_sequence: self.delayed_sequence,
+            sim_time: self.sim_time,
+            execution_quality: self.execution_quality,
+            max_cascade_depth: self.max_cascade_depth,
+            truncated_cascades: self.truncated_cascades,
+            imbalance: self.imbalance.clone(),
+            event_count: self.events.len(),
+        }
+    }
+
+    /// Rebuild an exchange from a checkpoint and the events recorded after it.
+    ///
+    /// Produces the same book, stops, trades, and counters as a full replay.
+    /// The restored event log contains only `subsequent_events`.
+    pub fn restore(checkpoint: Checkpoint, subsequent_events: &[crate::Event]) -> Self {
+        let mut exchange = Self {
+            book: checkpoint.book,
+            trades: checkpoint.trades,
+            stop_book: checkpoint.stop_book,
+            last_trade_price: checkpoint.last_trade_price,
+            matched_quantity: checkpoint.matched_quantity,
+            matched_notional: checkpoint.matched_notional,
+            trade_count: checkpoint.trade_count,
+            in_auction: checkpoint.in_auction,
+            instrument: checkpoint.instrument,
+            imbalance: checkpoint.imbalance,
+            delayed: checkpoint.delayed.into_iter().map(Reverse).collect(),
+            delayed_sequence: checkpoint.delayed_sequence,
+            sim_time: checkpoint.sim_time,
+            trade_observer: Default::default(),
+            scratch_trades: Vec::new(),
+            execution_quality: checkpoint.execution_quality,
+            max_cascade_depth: checkpoint.max_cascade_depth,
+            truncated_cascades: checkpoint.truncated_cascades,
+            events: Vec::new(),
+        };
+        exchange.apply_all(subsequent_events);
+        exchange
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, Side, TimeInForce};
+
+    fn session_events() -> Vec<Event> {
+        let mut exchange = Exchange::new();
+        for i in 0..40u64 {
+            let offset = (i % 5) as i64 * 10;
+            match i % 6 {
+                0 | 1 => {
+                    exchange.submit_limit(Side::Sell, Price(100_10 + offset), 20, TimeInForce::GTC);
+                }
+                2 | 3 => {
+                    exchange.submit_limit(Side::Buy, Price(99_90 - offset), 20, TimeInForce::GTC);
+                }
+                4 => {
+                    exchange.submit_stop_market(Side::Buy, Price(100_20), 15);
+                    exchange.submit_market(Side::Buy, 25);
+                }
+                _ => {
+                    exchange.cancel(OrderId(i));
+                }
+            }
+        }
+        exchange.events().to_vec()
+    }
+
+    #[test]
+    fn restore_matches_full_replay() {
+        let events = session_events();
+        let full = Exchange::replay(&events);
+
+        for k in [0, 1, 7, events.len() / 2, events.len() - 1, events.len()] {
+            let checkpoint = Exchange::replay(&events[..k]).checkpoint();
+            assert_eq!(checkpoint.event_count(), k);
+
+            let restored = Exchange::restore(checkpoint, &events[k..]);
+            assert_eq!(restored.best_bid_ask(), full.best_bid_ask(), "k={k}");
+            assert_eq!(restored.trades(), full.trades(), "k={k}");
+            assert_eq!(restored.pending_stop_count(), full.pending_stop_count());
+            assert_eq!(restored.last_trade_price(), full.last_trade_price());
+            assert_eq!(restored.trade_count(), full.trade_count());
+            assert_eq!(
+                restored.total_matched_notional(),
+                full.total_matched_notional()
+            );
+            assert_eq!(restored.execution_quality(), full.execution_quality());
+            assert_eq!(
+                restored.full_book().total_bid_quantity(),
+                full.full_book().total_bid_quantity()
+            );
+        }
+    }
+
+    #[test]
+    fn restore_continues_ids_monotonically() {
+        let events = session_events();
+        let mut full = Exchange::replay(&events);
+        let checkpoint = full.checkpoint();
+        assert_eq!(checkpoint.next_order_id(), full.book().peek_next_order_id());
+
+        let mut restored = Exchange::restore(checkpoint, &[]);
+        let a = full.submit_limit(Side::Buy, Price(100_50), 10, TimeInForce::GTC);
+        let b = restored.submit_limit(Side::Buy, Price(100_50), 10, TimeInForce::GTC);
+
+        assert_eq!(a.order_id, b.order_id);
+        assert_eq!(full.trades(), restored.trades());
+        assert_eq!(
+            full.get_order(a.order_id).unwrap().timestamp,
+            restored.get_order(b.order_id).unwrap().timestamp
+        );
+    }
+
+    #[test]
+    fn restore_keeps_delayed_orders_queued() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit_delayed(Side::Buy, Price(100_00), 60, TimeInForce::GTC, 30);
+        exchange.submit_limit_delayed(Side::Buy, Price(100_00), 60, TimeInForce::GTC, 10);
+        exchange.advance_to(5);
+        let checkpoint = exchange.checkpoint();
+        exchange.advance_to(50);
+
+        let tail = &exchange.events()[checkpoint.event_count()..];
+        let restored = Exchange::restore(checkpoint, tail);
+        assert_eq!(restored.trades(), exchange.trades());
+        assert_eq!(restored.full_book(), exchange.full_book());
+        assert_eq!(restored.sim_time(), 50);
+    }
+
+    #[test]
+    fn restore_keeps_imbalance_recording() {
+        let events = session_events();
+        let k = events.len() / 2;
+        let mut exchange = Exchange::new();
+        exchange.enable_imbalance_recording(8);
+        exchange.apply_all(&events[..k]);
+
+        let checkpoint = exchange.checkpoint();
+        exchange.apply_all(&events[k..]);
+        let restored = Exchange::restore(checkpoint, &events[k..]);
+
+        let history: Vec<_> = exchange.imbalance_history().collect();
+        assert_eq!(history.len(), 8);
+        assert_eq!(restored.imbalance_history().collect::<Vec<_>>(), history);
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn checkpoint_serde_round_trip() {
+        let events = session_events();
+        let exchange = Exchange::replay(&events);
+
+        let json = serde_json::to_string(&exchange.checkpoint()).unwrap();
+        let checkpoint: Checkpoint = serde_json::from_str(&json).unwrap();
+        let restored = Exchange::restore(checkpoint, &[]);
+
+        assert_eq!(restored.best_bid_ask(), exchange.best_bid_ask());
+        assert_eq!(restored.trades(), exchange.trades());
+        assert_eq!(restored.pending_stop_count(), exchange.pending_stop_count());
+    }
+}
@@ -1,7 +1,9 @@
-//! Validation errors for order submission.
+//! Validation errors for order submission, checked replay, and book integrity.
 
 use std::fmt;
 
+use crate::{OrderId, Price, Quantity, Side};
+
 /// Errors returned by validated order submission methods.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -10,6 +12,10 @@ pub enum ValidationError {
     ZeroQuantity,
     /// Price must be greater than zero for limit orders.
     ZeroPrice,
+    /// Price is not a multiple of the instrument's tick size.
+    OffTick,
+    /// Quantity is not a multiple of the instrument's lot size.
+    OddLot,
 }
 
 impl fmt::Display for ValidationError {
@@ -17,12 +23,187 @@ impl fmt::Display for ValidationError {
         match self {
             ValidationError::ZeroQuantity => write!(f, "quantity must be greater than zero"),
             ValidationError::ZeroPrice => write!(f, "price must be greater than zero"),
+            ValidationError::OffTick => write!(f, "price is not a multiple of the tick size"),
+            ValidationError::OddLot => write!(f, "quantity is not a multiple of the lot size"),
         }
     }
 }
 
 impl std::error::Error for ValidationError {}
 
+/// Invariant broken while replaying an event log with
+/// [`Exchange::replay_checked`](crate::Exchange::replay_checked).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReplayError {
+    /// Index of the event after which the violation was detected
+    pub event_index: usize,
+    /// What went wrong
+    pub reason: ReplayViolation,
+}
+
+/// Invariants checked by [`Exchange::replay_checked`](crate::Exchange::replay_checked).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReplayViolation {
+    /// The event refers to an order ID the log never assigned.
+    UnknownOrder(OrderId),
+    /// Best bid at or above best ask outside an auction call phase.
+    CrossedBook { bid: Price, ask: Price },
+    /// A level's cached quantity differs from the sum of its orders.
+    LevelQuantity {
+        side: Side,
+        price: Price,
+        cached: Quantity,
+        actual: Quantity,
+    },
+    /// The event's trades executed more than the order's quantity.
+    Overfill { ordered: Quantity, filled: Quantity },
+    /// Trade volume recorded in history differs from the matched-volume
+    /// counter.
+    VolumeMismatch { trades: Quantity, counted: Quantity },
+    /// Order or trade IDs failed to increase.
+    NonMonotonicIds,
+}
+
+impl fmt::Display for ReplayViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayViolation::UnknownOrder(id) => write!(f, "unknown order {}", id.0),
+            ReplayViolation::CrossedBook { bid, ask } => {
+                write!(f, "book crossed: bid {bid} >= ask {ask}")
+            }
+            ReplayViolation::LevelQuantity {
+                side,
+                price,
+                cached,
+                actual,
+            } => write!(
+                f,
+                "{side} level {price} holds {cached} but its orders sum to {actual}"
+            ),
+            ReplayViolation::Overfill { ordered, filled } => {
+                write!(f, "filled {filled} of an order for {ordered}")
+            }
+            ReplayViolation::VolumeMismatch { trades, counted } => {
+                write!(
+                    f,
+                    "trades total {trades} but matched volume grew by {counted}"
+                )
+            }
+            ReplayViolation::NonMonotonicIds => write!(f, "order or trade IDs went backwards"),
+        }
+    }
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "replay failed at event {}: {}",
+            self.event_index, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Broken order book invariant found by
+/// [`OrderBook::validate`](crate::OrderBook::validate).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BookInvariantError {
+    /// The cached best price differs from the best price level actually held.
+    StaleBestPrice {
+        side: Side,
+        cached: Option<Price>,
+        actual: Option<Price>,
+    },
+    /// A level queues an order ID missing from the central order index.
+    UnknownOrder {
+        side: Side,
+        price: Price,
+        order_id: OrderId,
+    },
+    /// A level queues an order that is filled or cancelled.
+    InactiveOrder {
+        side: Side,
+        price: Price,
+        order_id: OrderId,
+    },
+    /// A level queues an order whose own side or price is different.
+    MisplacedOrder {
+        side: Side,
+        price: Price,
+        order_id: OrderId,
+    },
+    /// A level's cached quantity differs from the sum of its orders'
+    /// remaining quantities.
+    LevelQuantity {
+        side: Side,
+        price: Price,
+        cached: Quantity,
+        actual: Quantity,
+    },
+    /// Best bid at or above best ask.
+    CrossedBook { bid: Price, ask: Price },
+}
+
+impl fmt::Display for BookInvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let price_or_none = |p: &Option<Price>| p.map_or("none".to_string(), |p| p.to_string());
+        match self {
+            BookInvariantError::StaleBestPrice {
+                side,
+                cached,
+                actual,
+            } => write!(
+                f,
+                "{side} best price cached as {} but best level is {}",
+                price_or_none(cached),
+                price_or_none(actual)
+            ),
+            BookInvariantError::UnknownOrder {
+                side,
+                price,
+                order_id,
+            } => write!(f, "{side} level {price} holds unknown order {}", order_id.0),
+            BookInvariantError::InactiveOrder {
+                side,
+                price,
+                order_id,
+            } => write!(
+                f,
+                "{side} level {price} holds inactive order {}",
+                order_id.0
+            ),
+            BookInvariantError::MisplacedOrder {
+                side,
+                price,
+                order_id,
+            } => write!(
+                f,
+                "{side} level {price} holds order {} resting elsewhere",
+                order_id.0
+            ),
+            BookInvariantError::LevelQuantity {
+                side,
+                price,
+                cached,
+                actual,
+            } => write!(
+                f,
+                "{side} level {price} holds {cached} but its orders sum to {actual}"
+            ),
+            BookInvariantError::CrossedBook { bid, ask } => {
+                write!(f, "book crossed: bid {bid} >= ask {ask}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BookInvariantError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,6 +218,10 @@ mod tests {
             format!("{}", ValidationError::ZeroPrice),
             "price must be greater than zero"
         );
+        assert_eq!(
+            format!("{}", ValidationError::OddLot),
+            "quantity is not a multiple of the lot size"
+        );
     }
 
     #[test]
@@ -6,10 +6,10 @@
 //! - Debugging and audit trails
 //! - Serialization/persistence of exchange state
 
-#[cfg(feature = "event-log")]
-use crate::Exchange;
 use crate::stop::TrailMethod;
-use crate::{OrderId, Price, Quantity, Side, TimeInForce, Trade};
+#[cfg(feature = "event-log")]
+use crate::{Exchange, ReplayError, ReplayViolation};
+use crate::{OrderId, Price, Quantity, Side, TimeInForce, Timestamp, Trade};
 
 /// An event that can be applied to an exchange.
 ///
@@ -65,6 +65,66 @@ pub enum Event {
         time_in_force: TimeInForce,
         trail_method: TrailMethod,
     },
+    /// Start an auction call phase
+    BeginAuction,
+    /// Uncross the book at a single price and resume continuous trading
+    RunAuction,
+    /// Submit a stop-market order whose fills are capped at
+    /// `max_slippage` cents beyond the stop price
+    SubmitStopMarketCapped {
+        side: Side,
+        stop_price: Price,
+        quantity: Quantity,
+        max_slippage: i64,
+    },
+    /// Reduce a resting order's size in place, keeping priority
+    ReduceQuantity {
+        order_id: OrderId,
+        new_quantity: Quantity,
+    },
+    /// Cancel part of a resting order, keeping priority on the rest
+    CancelPartial {
+        order_id: OrderId,
+        quantity: Quantity,
+    },
+    /// Submit a market-if-touched order
+    SubmitMit {
+        side: Side,
+        trigger_price: Price,
+        quantity: Quantity,
+    },
+    /// Queue a limit order arriving at simulated time `arrival`
+    SubmitLimitDelayed {
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        time_in_force: TimeInForce,
+        arrival: Timestamp,
+    },
+    /// Advance the simulation clock, processing arrived delayed orders
+    AdvanceTo { timestamp: Timestamp },
+    /// Submit a trailing stop-limit order whose limit trails
+    /// `limit_offset` cents beyond the stop
+    SubmitTrailingStopLimitOffset {
+        side: Side,
+        stop_price: Price,
+        limit_offset: i64,
+        quantity: Quantity,
+        time_in_force: TimeInForce,
+        trail_method: TrailMethod,
+    },
+    /// Move the order ID, trade ID and timestamp counters past the offsets
+    SetIdOffsets {
+        order_offset: u64,
+        trade_offset: u64,
+        ts_offset: Timestamp,
+    },
+    /// Submit limit orders `(side, price, quantity, time_in_force)` in order
+    SubmitBatch {
+        orders: Vec<(Side, Price, Quantity, TimeInForce)>,
+    },
+    /// Submit a fill-or-kill market order
+    SubmitMarketFok { side: Side, quantity: Quantity },
 }
 
 impl Event {
@@ -102,6 +162,19 @@ impl Event {
         }
     }
 
+    /// Create a ReduceQuantity event.
+    pub fn reduce_quantity(order_id: OrderId, new_quantity: Quantity) -> Self {
+        Event::ReduceQuantity {
+            order_id,
+            new_quantity,
+        }
+    }
+
+    /// Create a CancelPartial event.
+    pub fn cancel_partial(order_id: OrderId, quantity: Quantity) -> Self {
+        Event::CancelPartial { order_id, quantity }
+    }
+
     /// Create a SubmitStopMarket event.
     pub fn submit_stop_market(side: Side, stop_price: Price, quantity: Quantity) -> Self {
         Event::SubmitStopMarket {
@@ -111,6 +184,90 @@ impl Event {
         }
     }
 
+    /// Create a SubmitStopMarketCapped event.
+    pub fn submit_stop_market_capped(
+        side: Side,
+        stop_price: Price,
+        quantity: Quantity,
+        max_slippage: i64,
+    ) -> Self {
+        Event::SubmitStopMarketCapped {
+            side,
+            stop_price,
+            quantity,
+            max_slippage,
+        }
+    }
+
+    /// Create a SubmitMit event.
+    pub fn submit_mit(side: Side, trigger_price: Price, quantity: Quantity) -> Self {
+        Event::SubmitMit {
+            side,
+            trigger_price,
+            quantity,
+        }
+    }
+
+    /// Create a SubmitLimitDelayed event.
+    pub fn submit_limit_delayed(
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        time_in_force: TimeInForce,
+        arrival: Timestamp,
+    ) -> Self {
+        Event::SubmitLimitDelayed {
+            side,
+            price,
+            quantity,
+            time_in_force,
+            arrival,
+        }
+    }
+
+    /// Create an AdvanceTo event.
+    pub fn advance_to(timestamp: Timestamp) -> Self {
+        Event::AdvanceTo { timestamp }
+    }
+
+    /// Create a SubmitTrailingStopLimitOffset event.
+    pub fn submit_trailing_stop_limit_offset(
+        side: Side,
+        stop_price: Price,
+        limit_offset: i64,
+        quantity: Quantity,
+        time_in_force: TimeInForce,
+        trail_method: TrailMethod,
+    ) -> Self {
+        Event::SubmitTrailingStopLimitOffset {
+            side,
+            stop_price,
+            limit_offset,
+            quantity,
+            time_in_force,
+            trail_method,
+        }
+    }
+
+    /// Create a SetIdOffsets event.
+    pub fn set_id_offsets(order_offset: u64, trade_offset: u64, ts_offset: Timestamp) -> Self {
+        Event::SetIdOffsets {
+            order_offset,
+            trade_offset,
+            ts_offset,
+        }
+    }
+
+    /// Create a SubmitBatch event.
+    pub fn submit_batch(orders: Vec<(Side, Price, Quantity, TimeInForce)>) -> Self {
+        Event::SubmitBatch { orders }
+    }
+
+    /// Create a SubmitMarketFok event.
+    pub fn submit_market_fok(side: Side, quantity: Quantity) -> Self {
+        Event::SubmitMarketFok { side, quantity }
+    }
+
     /// Create a SubmitStopLimit event.
     pub fn submit_stop_limit(
         side: Side,
@@ -226,6 +383,17 @@ impl Exchange {
                 }
                 result.trades
             }
+            Event::ReduceQuantity {
+                order_id,
+                new_quantity,
+            } => {
+                self.reduce_quantity_internal(*order_id, *new_quantity);
+                Vec::new()
+            }
+            Event::CancelPartial { order_id, quantity } => {
+                self.cancel_partial_internal(*order_id, *quantity);
+                Vec::new()
+            }
             Event::SubmitStopMarket {
                 side,
                 stop_price,
@@ -234,6 +402,88 @@ impl Exchange {
                 self.submit_stop_internal(*side, *stop_price, None, *quantity, TimeInForce::GTC);
                 Vec::new()
             }
+            Event::SubmitStopMarketCapped {
+                side,
+                stop_price,
+                quantity,
+                max_slippage,
+            } => {
+                self.submit_stop_capped_internal(*side, *stop_price, *quantity, *max_slippage);
+                Vec::new()
+            }
+            Event::SubmitMit {
+                side,
+                trigger_price,
+                quantity,
+            } => {
+                self.submit_mit_internal(*side, *trigger_price, *quantity);
+                Vec::new()
+            }
+            Event::SubmitLimitDelayed {
+                side,
+                price,
+                quantity,
+                time_in_force,
+                arrival,
+            } => {
+                self.submit_limit_delayed_internal(
+                    *side,
+                    *price,
+                    *quantity,
+                    *time_in_force,
+                    *arrival,
+                );
+                Vec::new()
+            }
+            Event::AdvanceTo { timestamp } => self
+                .advance_to_internal(*timestamp)
+                .into_iter()
+                .flat_map(|result| result.trades)
+                .collect(),
+            Event::SubmitTrailingStopLimitOffset {
+                side,
+                stop_price,
+                limit_offset,
+                quantity,
+                time_in_force,
+                trail_method,
+            } => {
+                self.submit_trailing_stop_limit_offset_internal(
+                    *side,
+                    *stop_price,
+                    *limit_offset,
+                    *quantity,
+                    *time_in_force,
+                    trail_method.clone(),
+                );
+                Vec::new()
+            }
+            Event::SetIdOffsets {
+                order_offset,
+                trade_offset,
+                ts_offset,
+            } => {
+                self.book
+                    .raise_id_offsets(*order_offset, *trade_offset, *ts_offset);
+                Vec::new()
+            }
+            Event::SubmitBatch { orders } => self
+                .submit_batch_internal(orders)
+                .into_iter()
+                .flat_map(|result| result.trades)
+                .collect(),
+            Event::SubmitMarketFok { side, quantity } => {
+                let price = match side {
+                    Side::Buy => Price::MAX,
+                    Side::Sell => Price::MIN,
+                };
+                let result = self.submit_limit_internal(*side, price, *quantity, TimeInForce::FOK);
+                if !result.trades.is_empty() {
+                    self.last_trade_price = Some(result.trades.last().unwrap().price);
+                    self.process_trade_triggers();
+                }
+                result.trades
+            }
             Event::SubmitStopLimit {
                 side,
                 stop_price,
@@ -284,6 +534,18 @@ impl Exchange {
                 );
                 Vec::new()
             }
+            Event::BeginAuction => {
+                self.in_auction = true;
+                Vec::new()
+            }
+            Event::RunAuction => {
+                let result = self.run_auction_internal();
+                if let Some(trade) = result.trades.last() {
+                    self.last_trade_price = Some(trade.price);
+                    self.process_trade_triggers();
+                }
+                result.trades
+            }
         };
 
         ApplyResult { trades }
@@ -309,6 +571,140 @@ impl Exchange {
         exchange
     }
 
+    /// Replay events like [`replay`](Self::replay), verifying invariants
+    /// after each one, and fail on the first violation.
+    ///
+    /// Checks that every cancel/modify/reduce refers to an order ID the log
+    /// has already assigned, that the book is not crossed outside an auction,
+    /// that each level's cached quantity matches its orders, that a submit's
+    /// trades do not exceed its quantity, that the new trades add up to the
+    /// growth in matched volume, and that order and trade IDs only increase.
+    /// Much slower than `replay`; meant for diagnosing suspect logs.
+    pub fn replay_checked(events: &[Event]) -> Result<Self, ReplayError> {
+        let mut exchange = Self::new();
+        for (event_index, event) in events.iter().enumerate() {
+            exchange
+                .apply_checked(event)
+                .map_err(|reason| ReplayError {
+                    event_index,
+                    reason,
+                })?;
+        }
+        Ok(exchange)
+    }
+
+    /// Apply one event and check the replay invariants around it.
+    fn apply_checked(&mut self, event: &Event) -> Result<(), ReplayViolation> {
+        let next_order_id = self.book.peek_next_order_id();
+        let next_trade_id = self.book.peek_next_trade_id();
+        let first_trade = self.trades.len();
+        let matched_before = self.matched_quantity;
+
+        if let Event::Cancel { order_id }
+        | Event::Modify { order_id, .. }
+        | Event::ReduceQuantity { order_id, .. }
+        | Event::CancelPartial { order_id, .. } = event
+            && (order_id.0 == 0 || order_id.0 >= next_order_id.0)
+        {
+            return Err(ReplayViolation::UnknownOrder(*order_id));
+        }
+
+        let result = self.apply(event);
+
+        let ordered = match event {
+            Event::SubmitLimit { quantity, .. }
+            | Event::SubmitMarket { quantity, .. }
+            | Event::SubmitMarketFok { quantity, .. } => Some(*quantity),
+            Event::Modify { new_quantity, .. } => Some(*new_quantity),
+            _ => None,
+        };
+        let filled: Quantity = result.trades.iter().map(|t| t.quantity).sum();
+        if let Some(ordered) = ordered
+            && filled > ordered
+        {
+            return Err(ReplayViolation::Overfill { ordered, filled });
+        }
+
+        let new_trades = &self.trades[first_trade..];
+        let traded: Quantity = new_trades.iter().map(|t| t.quantity).sum();
+        let counted = self.matched_quantity - matched_before;
+        if traded != counted {
+            return Err(ReplayViolation::VolumeMismatch {
+                trades: traded,
+                counted,
+            });
+        }
+
+        let mut expected_trade_id = next_trade_id.0;
+        for trade in new_trades {
+            if trade.id.0 < expected_trade_id {
+                return Err(ReplayViolation::NonMonotonicIds);
+            }
+            expected_trade_id = trade.id.0 + 1;
+        }
+        if self.book.peek_next_order_id().0 < next_order_id.0
+            || self.book.peek_next_trade_id().0 < expected_trade_id
+        {
+            return Err(ReplayViolation::NonMonotonicIds);
+        }
+
+        if !self.in_auction
+            && let (Some(bid), Some(ask)) = self.book.best_bid_ask()
+            && bid >= ask
+        {
+            return Err(ReplayViolation::CrossedBook { bid, ask });
+        }
+
+        for (side, levels) in [
+            (Side::Buy, self.book.bids()),
+            (Side::Sell, self.book.asks()),
+        ] {
+            for (price, level) in levels.iter_best_to_worst() {
+                let actual: Quantity = level
+                    .iter()
+                    .filter_map(|id| self.book.get_order(id))
+                    .map(|o| o.remaining_quantity)
+                    .sum();
+                if actual != level.total_quantity() {
+                    return Err(ReplayViolation::LevelQuantity {
+                        side,
+                        price: *price,
+                        cached: level.total_quantity(),
+                        actual,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replay the first `index` events to reconstruct the book as of event `index`.
+    ///
+    /// Events are applied one at a time through [`apply`](Self::apply), so each
+    /// event's stop cascade completes before stopping. An `index` past the end
+    /// replays everything.
+    pub fn replay_until(events: &[Event], index: usize) -> Self {
+        Self::replay(&events[..index.min(events.len())])
+    }
+
+    /// Replay events until the exchange clock passes `timestamp`.
+    ///
+    /// Timestamps are the book's logical clock (as stamped on orders and
+    /// trades). An event is applied only if the clock has not yet advanced
+    /// beyond `timestamp` when it starts, so the result contains every order
+    /// and trade stamped at or before `timestamp` (plus any trades the last
+    /// applied event produced).
+    pub fn replay_until_timestamp(events: &[Event], timestamp: Timestamp) -> Self {
+        let mut exchange = Self::new();
+        for event in events {
+            if exchange.book.peek_next_timestamp() > timestamp {
+                break;
+            }
+            exchange.apply(event);
+        }
+        exchange
+    }
+
     /// Get all recorded events.
     pub fn events(&self) -> &[Event] {
         &self.events
@@ -543,6 +939,20 @@ mod tests {
         assert_eq!(replayed.last_trade_price(), original.last_trade_price());
     }
 
+    #[test]
+    fn replay_with_mit_orders() {
+        let mut original = Exchange::new();
+        original.submit_limit(Side::Buy, Price(98_00), 50, TimeInForce::GTC);
+        original.submit_limit(Side::Sell, Price(98_50), 100, TimeInForce::GTC);
+        original.submit_mit(Side::Buy, Price(98_00), 60);
+        original.submit_limit(Side::Sell, Price(98_00), 50, TimeInForce::GTC);
+        assert_eq!(original.pending_stop_count(), 0);
+
+        let replayed = Exchange::replay(original.events());
+        assert_eq!(replayed.trades(), original.trades());
+        assert_eq!(replayed.full_book(), original.full_book());
+    }
+
     #[test]
     fn events_are_equal() {
         let e1 = Event::submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
@@ -552,4 +962,121 @@ mod tests {
         assert_eq!(e1, e2);
         assert_ne!(e1, e3);
     }
+    fn cascade_events() -> Vec<Event> {
+        vec![
+            Event::submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC),
+            Event::submit_limit(Side::Sell, Price(101_00), 50, TimeInForce::GTC),
+            Event::submit_limit(Side::Sell, Price(103_00), 100, TimeInForce::GTC),
+            Event::submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC),
+            // Trade at 100 fires the first stop; its fill at 101 fires the second.
+            Event::submit_stop_market(Side::Buy, Price(100_00), 50),
+            Event::submit_stop_market(Side::Buy, Price(101_00), 50),
+            Event::submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC),
+            Event::cancel(OrderId(4)),
+            Event::submit_market(Side::Sell, 10),
+        ]
+    }
+
+    #[test]
+    fn replay_checked_accepts_a_clean_log() {
+        let mut exchange = Exchange::new();
+        let mut flow = crate::sim::OrderFlowGenerator::new(11);
+        for step in 0..300u64 {
+            for (side, price, quantity, tif) in flow.next_batch() {
+                exchange.submit_limit(side, price, quantity, tif);
+            }
+            match step % 10 {
+                3 => {
+                    exchange.submit_stop_market(Side::Buy, Price(100_05), 100);
+                }
+                5 => {
+                    exchange.cancel(OrderId(step));
+                }
+                7 => {
+                    exchange.modify(OrderId(step), Price(99_95), 200);
+                }
+                8 => {
+                    exchange.submit_market(Side::Sell, 150);
+                }
+                _ => {}
+            }
+        }
+        exchange.begin_auction();
+        exchange.submit_limit(Side::Buy, Price(101_00), 500, TimeInForce::GTC);
+        exchange.run_auction();
+
+        let checked = Exchange::replay_checked(&cascade_events()).unwrap();
+        assert_eq!(
+            checked.trades(),
+            Exchange::replay(&cascade_events()).trades()
+        );
+        let checked = Exchange::replay_checked(exchange.events()).unwrap();
+        assert_eq!(checked.trades(), exchange.trades());
+        assert_eq!(checked.full_book(), exchange.full_book());
+    }
+
+    #[test]
+    fn replay_checked_reports_first_violation() {
+        let mut events = cascade_events();
+        events.insert(3, Event::cancel(OrderId(999)));
+        events.push(Event::modify(OrderId(0), Price(100_00), 10));
+
+        let err = Exchange::replay_checked(&events).unwrap_err();
+        assert_eq!(err.event_index, 3);
+        assert_eq!(err.reason, ReplayViolation::UnknownOrder(OrderId(999)));
+        assert_eq!(
+            err.to_string(),
+            "replay failed at event 3: unknown order 999"
+        );
+
+        // The plain replay ignores the bad cancel and carries on.
+        assert_eq!(Exchange::replay(&events).events().len(), events.len());
+    }
+
+    #[test]
+    fn replay_until_matches_incremental_apply() {
+        let events = cascade_events();
+
+        for k in 0..=events.len() {
+            let mut stepwise = Exchange::new();
+            for event in &events[..k] {
+                stepwise.apply(event);
+            }
+            let partial = Exchange::replay_until(&events, k);
+
+            assert_eq!(partial.events(), stepwise.events());
+            assert_eq!(partial.best_bid_ask(), stepwise.best_bid_ask());
+            assert_eq!(partial.trades(), stepwise.trades());
+            assert_eq!(partial.pending_stop_count(), stepwise.pending_stop_count());
+        }
+    }
+
+    #[test]
+    fn replay_until_clamps_and_leaves_book_uncrossed() {
+        let events = cascade_events();
+
+        let full = Exchange::replay_until(&events, usize::MAX);
+        assert_eq!(full.events().len(), events.len());
+
+        // Index 7 stops right after the trade that fires the stop cascade.
+        let mid = Exchange::replay_until(&events, 7);
+        assert_eq!(mid.pending_stop_count(), 0);
+        if let (Some(bid), Some(ask)) = mid.best_bid_ask() {
+            assert!(bid < ask);
+        }
+    }
+
+    #[test]
+    fn replay_until_timestamp_stops_at_clock() {
+        let events = cascade_events();
+        let full = Exchange::replay(&events);
+
+        // Each of the first four limits consumes one timestamp (1..=4).
+        let early = Exchange::replay_until_timestamp(&events, 2);
+        assert_eq!(early.events().len(), 2);
+        assert_eq!(early.best_ask(), Some(Price(100_00)));
+
+        let all = Exchange::replay_until_timestamp(&events, Timestamp::MAX);
+        assert_eq!(all.trades(), full.trades());
+    }
 }
@@ -4,18 +4,119 @@
 //! OrderBook and provides methods for submitting orders with proper
 //! time-in-force handling.
 
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
 #[cfg(feature = "event-log")]
 use crate::event::Event;
 use crate::{
-    Order, OrderBook, OrderId, OrderStatus, Price, Quantity, Side, TimeInForce, Trade,
+    BookInvariantError, Execution, InstrumentSpec, Order, OrderBook, OrderId, OrderStatus, Price,
+    Quantity, Side, TimeInForce, Timestamp, Trade, TradePriceRule,
     error::ValidationError,
+    latency::DelayedOrder,
     result::{
         CancelError, CancelResult, ModifyError, ModifyResult, StopSubmitResult, SubmitResult,
     },
-    snapshot::BookSnapshot,
-    stop::{StopBook, StopOrder, StopStatus, TrailMethod},
+    snapshot::{BookSnapshot, LevelSnapshot},
+    stop::{ConditionalKind, StopBook, StopOrder, StopStatus, TrailMethod, capped_limit},
 };
 
+/// How [`Exchange::modify_with_policy`] applies a modification.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModifyPolicy {
+    /// Always cancel and replace: the order gets a new ID and goes to the
+    /// back of the queue (what [`Exchange::modify`] does).
+    #[default]
+    AlwaysCancelReplace,
+    /// Keep the order in place when only its size goes down at the same
+    /// price; otherwise cancel and replace.
+    PreservePriorityOnReduce,
+}
+
+/// Price improvement received by aggressors, accumulated by
+/// [`Exchange::execution_quality`].
+///
+/// Only priced aggressors in continuous trading are measured: a trade
+/// improves on its aggressor's limit when it prints at a better price (below
+/// a buy limit, above a sell limit). Market orders have no limit and are
+/// only counted; auction uncrosses are not included.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionQualityStats {
+    /// Trades whose aggressor had a limit price
+    pub limit_trades: u64,
+    /// Quantity of those trades
+    pub limit_quantity: Quantity,
+    /// Trades that printed better than the aggressor's limit
+    pub improved_trades: u64,
+    /// Quantity of the improved trades
+    pub improved_quantity: Quantity,
+    /// Σ |limit − price| × quantity over improved trades (cents)
+    pub improvement_cents: i128,
+    /// Trades that printed worse than the aggressor's limit (should stay 0)
+    pub trade_throughs: u64,
+    /// Trades whose aggressor was a market order
+    pub market_trades: u64,
+}
+
+impl ExecutionQualityStats {
+    /// Account for `trades` made by an aggressor on `side` with limit `limit`.
+    fn record(&mut self, side: Side, limit: Price, trades: &[Trade]) {
+        if limit == Price::MAX || limit == Price::MIN {
+            self.market_trades += trades.len() as u64;
+            return;
+        }
+        for trade in trades {
+            let improvement = match side {
+                Side::Buy => limit.0 - trade.price.0,
+                Side::Sell => trade.price.0 - limit.0,
+            };
+            self.limit_trades += 1;
+            self.limit_quantity += trade.quantity;
+            if improvement > 0 {
+                self.improved_trades += 1;
+                self.improved_quantity += trade.quantity;
+                self.improvement_cents += improvement as i128 * trade.quantity as i128;
+            } else if improvement < 0 {
+                self.trade_throughs += 1;
+            }
+        }
+    }
+
+    /// Average improvement per unit across all limit-aggressor quantity
+    /// (cents), or `None` before any such trade.
+    pub fn improvement_per_unit(&self) -> Option<f64> {
+        (self.limit_quantity > 0)
+            .then(|| self.improvement_cents as f64 / self.limit_quantity as f64)
+    }
+}
+
+/// Callback invoked with each trade as it is produced; see
+/// [`Exchange::set_trade_observer`].
+pub type TradeObserver = Box<dyn FnMut(&Trade) + Send + Sync>;
+
+/// Holds the optional trade observer. Clones start without one, since a
+/// boxed closure cannot be cloned.
+#[derive(Default)]
+pub(crate) struct ObserverSlot(Option<TradeObserver>);
+
+impl Clone for ObserverSlot {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl std::fmt::Debug for ObserverSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.0.is_some() {
+            "Some(<observer>)"
+        } else {
+            "None"
+        })
+    }
+}
+
 /// The exchange: processes orders and maintains the order book.
 ///
 /// This is the main interface for interacting with the limit order book.
@@ -35,6 +136,34 @@ pub struct Exchange {
     pub(crate) stop_book: StopBook,
     /// Last trade price (for stop order triggers)
     pub(crate) last_trade_price: Option<Price>,
+    /// Cumulative matched quantity (survives `clear_trades`)
+    pub(crate) matched_quantity: Quantity,
+    /// Cumulative matched notional in cents (survives `clear_trades`)
+    pub(crate) matched_notional: i128,
+    /// Cumulative number of trades (survives `clear_trades`)
+    pub(crate) trade_count: u64,
+    /// Auction call phase: limit orders rest without matching
+    pub(crate) in_auction: bool,
+    /// Tick/lot grid enforced by the `try_submit_*` methods
+    pub(crate) instrument: InstrumentSpec,
+    /// Top-of-book imbalance samples (None = recording disabled)
+    pub(crate) imbalance: Option<ImbalanceRecorder>,
+    /// Delayed orders not yet arrived, earliest arrival on top
+    pub(crate) delayed: BinaryHeap<Reverse<DelayedOrder>>,
+    /// Next delayed-order sequence number
+    pub(crate) delayed_sequence: u64,
+    /// Simulation clock advanced by `advance_to`
+    pub(crate) sim_time: Timestamp,
+    /// Called with every trade as it is recorded
+    pub(crate) trade_observer: ObserverSlot,
+    /// Reused match buffer; always empty between calls
+    pub(crate) scratch_trades: Vec<Trade>,
+    /// Cumulative price improvement (survives `clear_trades`)
+    pub(crate) execution_quality: ExecutionQualityStats,
+    /// Stop-trigger rounds allowed per trade
+    pub(crate) max_cascade_depth: usize,
+    /// Cascades cut off at `max_cascade_depth`
+    pub(crate) truncated_cascades: u64,
     /// Event log for replay (only with "event-log" feature)
     #[cfg(feature = "event-log")]
     pub(crate) events: Vec<crate::event::Event>,
@@ -48,11 +177,84 @@ impl Exchange {
             trades: Vec::new(),
             stop_book: StopBook::new(),
             last_trade_price: None,
+            matched_quantity: 0,
+            matched_notional: 0,
+            trade_count: 0,
+            in_auction: false,
+            instrument: InstrumentSpec::default(),
+            imbalance: None,
+            delayed: BinaryHeap::new(),
+            delayed_sequence: 0,
+            sim_time: 0,
+            trade_observer: ObserverSlot::default(),
+            scratch_trades: Vec::new(),
+            execution_quality: ExecutionQualityStats::default(),
+            max_cascade_depth: Self::DEFAULT_MAX_CASCADE_DEPTH,
+            truncated_cascades: 0,
             #[cfg(feature = "event-log")]
             events: Vec::new(),
         }
     }
 
+    /// Create an exchange with room for `trades_cap` trades and `events_cap`
+    /// log events reserved up front.
+    ///
+    /// Behaves exactly like [`new`](Self::new). The trade history and event
+    /// log then grow without reallocating until they pass the hints, which
+    /// saves the ~log2(n) grow-and-copy steps each would otherwise take on
+    /// the hot path. `events_cap` is ignored without the `event-log` feature.
+    pub fn with_capacity(trades_cap: usize, events_cap: usize) -> Self {
+        let mut exchange = Self::new();
+        exchange.trades.reserve_exact(trades_cap);
+        #[cfg(feature = "event-log")]
+        exchange.events.reserve_exact(events_cap);
+        #[cfg(not(feature = "event-log"))]
+        let _ = events_cap;
+        exchange
+    }
+
+    /// Create an exchange whose trades print under `rule` instead of at the
+    /// resting order's price.
+    ///
+    /// The rule is not part of the event log: [`replay`](Self::replay)
+    /// starts from [`new`](Self::new), so replay a log recorded under another
+    /// rule with `Exchange::with_trade_price_rule(rule).apply_all(events)`.
+    pub fn with_trade_price_rule(rule: TradePriceRule) -> Self {
+        let mut exchange = Self::new();
+        exchange.book = OrderBook::new().with_trade_price_rule(rule);
+        exchange
+    }
+
+    /// Create an exchange whose order IDs, trade IDs and timestamps start
+    /// just above the given offsets (`OrderId(order_offset + 1)` first).
+    ///
+    /// Give each shard of a sharded simulation a disjoint range so their
+    /// trade tapes can be merged without ID collisions. The offsets are
+    /// recorded as the first event, so [`replay`](Self::replay) reproduces
+    /// the same ID range.
+    pub fn with_id_offsets(order_offset: u64, trade_offset: u64, ts_offset: Timestamp) -> Self {
+        let mut exchange = Self::new();
+        exchange.set_id_offsets(order_offset, trade_offset, ts_offset);
+        exchange
+    }
+
+    /// Move the ID and timestamp counters just above the given offsets.
+    ///
+    /// Counters that are already past an offset are left unchanged, so IDs
+    /// never repeat. Use this to seed an exchange built with another
+    /// constructor, such as [`with_trade_price_rule`](Self::with_trade_price_rule).
+    pub fn set_id_offsets(&mut self, order_offset: u64, trade_offset: u64, ts_offset: Timestamp) {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SetIdOffsets {
+            order_offset,
+            trade_offset,
+            ts_offset,
+        });
+
+        self.book
+            .raise_id_offsets(order_offset, trade_offset, ts_offset);
+    }
+
     // === Order Submission ===
 
     /// Submit a limit order.
@@ -77,15 +279,51 @@ impl Exchange {
             time_in_force: tif,
         });
 
-        let result = self.submit_limit_internal(side, price, quantity, tif);
+        let mut result = self.submit_limit_internal(side, price, quantity, tif);
         if !result.trades.is_empty() {
             let last_price = result.trades.last().unwrap().price;
             self.last_trade_price = Some(last_price);
-            self.process_trade_triggers();
+            result.cascade_truncated = self.process_trade_triggers();
         }
         result
     }
 
+    /// Submit limit orders `(side, price, quantity, time_in_force)` in order.
+    ///
+    /// Equivalent to calling [`submit_limit`](Self::submit_limit) for each
+    /// entry (stops triggered by one order fire before the next is
+    /// submitted), but the whole batch is logged as a single
+    /// [`Event::SubmitBatch`](crate::Event::SubmitBatch) and the result
+    /// vector is allocated once. Returns one [`SubmitResult`] per order.
+    pub fn submit_batch(
+        &mut self,
+        orders: &[(Side, Price, Quantity, TimeInForce)],
+    ) -> Vec<SubmitResult> {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SubmitBatch {
+            orders: orders.to_vec(),
+        });
+
+        self.submit_batch_internal(orders)
+    }
+
+    /// Internal: submit a batch without recording an event.
+    pub(crate) fn submit_batch_internal(
+        &mut self,
+        orders: &[(Side, Price, Quantity, TimeInForce)],
+    ) -> Vec<SubmitResult> {
+        let mut results = Vec::with_capacity(orders.len());
+        for &(side, price, quantity, tif) in orders {
+            let mut result = self.submit_limit_internal(side, price, quantity, tif);
+            if let Some(trade) = result.trades.last() {
+                self.last_trade_price = Some(trade.price);
+                result.cascade_truncated = self.process_trade_triggers();
+            }
+            results.push(result);
+        }
+        results
+    }
+
     /// Submit a market order.
     ///
     /// Market orders execute immediately at the best available prices.
@@ -102,11 +340,34 @@ impl Exchange {
             Side::Buy => Price::MAX,
             Side::Sell => Price::MIN,
         };
-        let result = self.submit_limit_internal(side, price, quantity, TimeInForce::IOC);
+        let mut result = self.submit_limit_internal(side, price, quantity, TimeInForce::IOC);
+        if !result.trades.is_empty() {
+            let last_price = result.trades.last().unwrap().price;
+            self.last_trade_price = Some(last_price);
+            result.cascade_truncated = self.process_trade_triggers();
+        }
+        result
+    }
+
+    /// Submit a fill-or-kill market order.
+    ///
+    /// Like [`submit_market`](Self::submit_market), but if the opposite
+    /// side cannot fill the whole quantity the order is rejected without
+    /// trading: status `Cancelled`, no trades, `cancelled_quantity ==
+    /// quantity`. Mirrors a FOK limit at the worst possible price.
+    pub fn submit_market_fok(&mut self, side: Side, quantity: Quantity) -> SubmitResult {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SubmitMarketFok { side, quantity });
+
+        let price = match side {
+            Side::Buy => Price::MAX,
+            Side::Sell => Price::MIN,
+        };
+        let mut result = self.submit_limit_internal(side, price, quantity, TimeInForce::FOK);
         if !result.trades.is_empty() {
             let last_price = result.trades.last().unwrap().price;
             self.last_trade_price = Some(last_price);
-            self.process_trade_triggers();
+            result.cascade_truncated = self.process_trade_triggers();
         }
         result
     }
@@ -114,7 +375,9 @@ impl Exchange {
     /// Submit a limit order with input validation.
     ///
     /// Returns `Err(ValidationError::ZeroQuantity)` if quantity is 0,
-    /// or `Err(ValidationError::ZeroPrice)` if price is <= 0.
+    /// `Err(ValidationError::ZeroPrice)` if price is <= 0, and
+    /// `OffTick`/`OddLot` if price or quantity is off the
+    /// [instrument](Self::instrument)'s tick or lot grid.
     pub fn try_submit_limit(
         &mut self,
         side: Side,
@@ -128,12 +391,19 @@ impl Exchange {
         if price.0 <= 0 {
             return Err(ValidationError::ZeroPrice);
         }
+        if !self.instrument.is_valid_price(price) {
+            return Err(ValidationError::OffTick);
+        }
+        if !self.instrument.is_valid_quantity(quantity) {
+            return Err(ValidationError::OddLot);
+        }
         Ok(self.submit_limit(side, price, quantity, tif))
     }
 
     /// Submit a market order with input validation.
     ///
-    /// Returns `Err(ValidationError::ZeroQuantity)` if quantity is 0.
+    /// Returns `Err(ValidationError::ZeroQuantity)` if quantity is 0, or
+    /// `Err(ValidationError::OddLot)` if it is not a whole number of lots.
     pub fn try_submit_market(
         &mut self,
         side: Side,
@@ -142,6 +412,9 @@ impl Exchange {
         if quantity == 0 {
             return Err(ValidationError::ZeroQuantity);
         }
+        if !self.instrument.is_valid_quantity(quantity) {
+            return Err(ValidationError::OddLot);
+        }
         Ok(self.submit_market(side, quantity))
     }
 
@@ -152,6 +425,23 @@ impl Exchange {
         price: Price,
         quantity: Quantity,
         tif: TimeInForce,
+    ) -> SubmitResult {
+        let result = if self.in_auction {
+            self.submit_to_auction(side, price, quantity, tif)
+        } else {
+            self.submit_to_book(side, price, quantity, tif)
+        };
+        self.record_imbalance();
+        result
+    }
+
+    /// Internal: match a limit order and handle the remainder per its TIF.
+    fn submit_to_book(
+        &mut self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        tif: TimeInForce,
     ) -> SubmitResult {
         // FOK: Check feasibility before doing anything
         if tif == TimeInForce::FOK && !self.book.can_fully_fill(side, price, quantity) {
@@ -167,6 +457,7 @@ impl Exchange {
                 filled_quantity: 0,
                 resting_quantity: 0,
                 cancelled_quantity: quantity,
+                cascade_truncated: false,
             };
         }
 
@@ -174,11 +465,18 @@ impl Exchange {
         let mut order = self.book.create_order(side, price, quantity, tif);
         let order_id = order.id;
 
-        // Match against the book
-        let match_result = self.book.match_order(&mut order);
+        // Match into the reused buffer, then hand the caller an exact-size
+        // copy: one allocation per trading submit (none without trades)
+        // instead of growing a fresh vector trade by trade.
+        let mut scratch = std::mem::take(&mut self.scratch_trades);
+        self.book.match_order_into(&mut order, &mut scratch);
 
         // Record trades
-        self.trades.extend(match_result.trades.iter().cloned());
+        self.record_trades(&scratch);
+        self.execution_quality.record(side, price, &scratch);
+        let trades = scratch.to_vec();
+        scratch.clear();
+        self.scratch_trades = scratch;
 
         let filled = order.filled_quantity;
         let remaining = order.remaining_quantity;
@@ -214,10 +512,42 @@ impl Exchange {
         SubmitResult {
             order_id,
             status,
-            trades: match_result.trades,
+            trades,
             filled_quantity: filled,
             resting_quantity: resting,
             cancelled_quantity: cancelled,
+            cascade_truncated: false,
+        }
+    }
+
+    /// Internal: during an auction call, rest GTC orders unmatched and
+    /// cancel everything else.
+    fn submit_to_auction(
+        &mut self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        tif: TimeInForce,
+    ) -> SubmitResult {
+        let mut order = self.book.create_order(side, price, quantity, tif);
+        let order_id = order.id;
+        let (status, resting, cancelled) = if tif == TimeInForce::GTC {
+            order.status = OrderStatus::New;
+            self.book.add_order(order);
+            (OrderStatus::New, quantity, 0)
+        } else {
+            order.cancel();
+            self.book.orders.insert(order_id, order);
+            (OrderStatus::Cancelled, 0, quantity)
+        };
+        SubmitResult {
+            order_id,
+            status,
+            trades: Vec::new(),
+            filled_quantity: 0,
+            resting_quantity: resting,
+            cancelled_quantity: cancelled,
+            cascade_truncated: false,
         }
     }
 
@@ -256,9 +586,81 @@ impl Exchange {
         }
 
         // Cancel it
-        match self.book.cancel_order(order_id) {
+        let result = match self.book.cancel_order(order_id) {
             Some(qty) => CancelResult::success(qty),
             None => CancelResult::failure(CancelError::OrderNotActive),
+        };
+        self.record_imbalance();
+        result
+    }
+
+    /// Cancel every resting order submitted before `older_than`.
+    ///
+    /// An order is stale when its timestamp is strictly less than the
+    /// cutoff. Each cancellation goes through [`cancel`](Self::cancel), so it
+    /// is recorded in the event log. Pending stop orders are not resting and
+    /// are left alone. Returns the cancelled IDs in submission order.
+    pub fn cancel_stale(&mut self, older_than: Timestamp) -> Vec<OrderId> {
+        let mut stale: Vec<OrderId> = self
+            .book
+            .orders
+            .values()
+            .filter(|order| order.is_active() && order.timestamp < older_than)
+            .map(|order| order.id)
+            .collect();
+        stale.sort_unstable_by_key(|id| id.0);
+        stale.retain(|&id| self.cancel(id).success);
+        stale
+    }
+
+    /// Cancel `quantity` of an order, leaving the rest resting with its ID
+    /// and queue position.
+    ///
+    /// If `quantity` covers the remaining quantity this is a full
+    /// [`cancel`](Self::cancel). A pending stop order has nothing resting
+    /// yet, so it can only be cancelled in full; a smaller `quantity` fails
+    /// with [`CancelError::OrderNotActive`]. A `quantity` of 0 cancels nothing.
+    pub fn cancel_partial(&mut self, order_id: OrderId, quantity: Quantity) -> CancelResult {
+        #[cfg(feature = "event-log")]
+        self.events
+            .push(Event::CancelPartial { order_id, quantity });
+
+        self.cancel_partial_internal(order_id, quantity)
+    }
+
+    /// Internal: partial cancel without recording event.
+    pub(crate) fn cancel_partial_internal(
+        &mut self,
+        order_id: OrderId,
+        quantity: Quantity,
+    ) -> CancelResult {
+        if let Some(stop) = self.stop_book.get(order_id)
+            && self.stop_book.contains_pending(order_id)
+        {
+            if quantity >= stop.quantity {
+                return self.cancel_internal(order_id);
+            }
+            return CancelResult::failure(CancelError::OrderNotActive);
+        }
+
+        let remaining = match self.book.get_order(order_id) {
+            Some(o) if o.is_active() => o.remaining_quantity,
+            Some(_) => return CancelResult::failure(CancelError::OrderNotActive),
+            None => return CancelResult::failure(CancelError::OrderNotFound),
+        };
+        if quantity >= remaining {
+            return self.cancel_internal(order_id);
+        }
+        if quantity == 0 {
+            return CancelResult::success(0);
+        }
+
+        match self.book.reduce_order(order_id, remaining - quantity) {
+            Some(cancelled) => {
+                self.record_imbalance();
+                CancelResult::success(cancelled)
+            }
+            None => CancelResult::failure(CancelError::OrderNotActive),
         }
     }
 
@@ -316,10 +718,99 @@ impl Exchange {
         ModifyResult::success(order_id, result.order_id, cancelled, result.trades)
     }
 
+    /// Reduce a resting order's remaining quantity to `new_quantity` in
+    /// place. The order keeps its ID and its queue position.
+    ///
+    /// Fails with [`ModifyError::InvalidQuantity`] unless
+    /// `0 < new_quantity < remaining`.
+    pub fn reduce_quantity(&mut self, order_id: OrderId, new_quantity: Quantity) -> ModifyResult {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::ReduceQuantity {
+            order_id,
+            new_quantity,
+        });
+
+        self.reduce_quantity_internal(order_id, new_quantity)
+    }
+
+    /// Internal: reduce without recording event.
+    pub(crate) fn reduce_quantity_internal(
+        &mut self,
+        order_id: OrderId,
+        new_quantity: Quantity,
+    ) -> ModifyResult {
+        match self.book.get_order(order_id) {
+            Some(o) if o.is_active() => {}
+            Some(_) => return ModifyResult::failure(order_id, ModifyError::OrderNotActive),
+            None => return ModifyResult::failure(order_id, ModifyError::OrderNotFound),
+        }
+        match self.book.reduce_order(order_id, new_quantity) {
+            Some(reduced) => {
+                self.record_imbalance();
+                ModifyResult::reduced(order_id, reduced)
+            }
+            None => ModifyResult::failure(order_id, ModifyError::InvalidQuantity),
+        }
+    }
+
+    /// Modify an order under an explicit [`ModifyPolicy`].
+    ///
+    /// With [`ModifyPolicy::PreservePriorityOnReduce`], a change that only
+    /// lowers the size at the same price is applied in place via
+    /// [`reduce_quantity`](Self::reduce_quantity) and keeps queue priority;
+    /// anything else (a price change or a size increase) is a
+    /// cancel-replace like [`modify`](Self::modify). Check
+    /// [`ModifyResult::priority_kept`] to see which happened.
+    pub fn modify_with_policy(
+        &mut self,
+        order_id: OrderId,
+        new_price: Price,
+        new_quantity: Quantity,
+        policy: ModifyPolicy,
+    ) -> ModifyResult {
+        let reducible = policy == ModifyPolicy::PreservePriorityOnReduce
+            && new_quantity > 0
+            && self.book.get_order(order_id).is_some_and(|o| {
+                o.is_active() && o.price == new_price && new_quantity < o.remaining_quantity
+            });
+        if reducible {
+            self.reduce_quantity(order_id, new_quantity)
+        } else {
+            self.modify(order_id, new_price, new_quantity)
+        }
+    }
+
     // === Stop Orders ===
 
-    /// Maximum cascade depth to prevent infinite stop-trigger loops.
-    const MAX_CASCADE_DEPTH: usize = 100;
+    /// Default limit on stop-trigger rounds per trade; see
+    /// [`set_max_cascade_depth`](Self::set_max_cascade_depth).
+    pub const DEFAULT_MAX_CASCADE_DEPTH: usize = 100;
+
+    /// Limit how many rounds of stop triggers one trade can set off
+    /// (default [`DEFAULT_MAX_CASCADE_DEPTH`](Self::DEFAULT_MAX_CASCADE_DEPTH),
+    /// at least 1).
+    ///
+    /// A cascade cut off with stops still triggerable at the last trade
+    /// price leaves them pending until the next trade; the submit, stop
+    /// trigger, delayed arrival or auction that set it off reports
+    /// `cascade_truncated`, and
+    /// [`truncated_cascades`](Self::truncated_cascades) counts every
+    /// occurrence. Like the trade price rule, the limit is not part of the
+    /// event log.
+    pub fn set_max_cascade_depth(&mut self, depth: usize) {
+        self.max_cascade_depth = depth.max(1);
+    }
+
+    /// Current limit on stop-trigger rounds per trade.
+    pub fn max_cascade_depth(&self) -> usize {
+        self.max_cascade_depth
+    }
+
+    /// Number of stop cascades cut off at the depth limit since the
+    /// exchange was created.
+    pub fn truncated_cascades(&self) -> u64 {
+        self.truncated_cascades
+    }
 
     /// Submit a stop-market order.
     ///
@@ -342,6 +833,59 @@ impl Exchange {
         self.submit_stop_internal(side, stop_price, None, quantity, TimeInForce::GTC)
     }
 
+    /// Submit a stop-market order with a slippage cap (a non-guaranteed stop
+    /// with bounded fills).
+    ///
+    /// Triggers like [`submit_stop_market`](Self::submit_stop_market), but
+    /// the resulting order is a marketable IOC limit at `stop_price +
+    /// max_slippage` (buy) or `stop_price - max_slippage` (sell): in a gap it
+    /// fills only up to that price and the rest is cancelled.
+    pub fn submit_stop_market_capped(
+        &mut self,
+        side: Side,
+        stop_price: Price,
+        quantity: Quantity,
+        max_slippage: i64,
+    ) -> StopSubmitResult {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SubmitStopMarketCapped {
+            side,
+            stop_price,
+            quantity,
+            max_slippage,
+        });
+
+        self.submit_stop_capped_internal(side, stop_price, quantity, max_slippage)
+    }
+
+    /// Submit a market-if-touched (MIT) order.
+    ///
+    /// The order becomes a market order when `last_trade_price` touches
+    /// `trigger_price` — in the **opposite** direction to a stop:
+    /// - Buy MIT: triggers when `last_trade_price <= trigger_price` (buy the dip)
+    /// - Sell MIT: triggers when `last_trade_price >= trigger_price`
+    ///
+    /// It lives in the stop book: it counts towards
+    /// [`pending_stop_count`](Self::pending_stop_count), is cancelled with
+    /// [`cancel`](Self::cancel), and shows up in
+    /// [`get_stop_order`](Self::get_stop_order) with
+    /// [`ConditionalKind::IfTouched`].
+    pub fn submit_mit(
+        &mut self,
+        side: Side,
+        trigger_price: Price,
+        quantity: Quantity,
+    ) -> StopSubmitResult {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SubmitMit {
+            side,
+            trigger_price,
+            quantity,
+        });
+
+        self.submit_mit_internal(side, trigger_price, quantity)
+    }
+
     /// Submit a stop-limit order.
     ///
     /// The order becomes a limit order at `limit_price` when `last_trade_price`
@@ -431,6 +975,69 @@ impl Exchange {
         )
     }
 
+    /// Submit a trailing stop-limit order whose limit price trails with the
+    /// stop.
+    ///
+    /// The limit sits `limit_offset` cents beyond the stop price (above for
+    /// buys, below for sells). Each time the watermark moves the stop, the
+    /// limit moves by the same amount, so the triggered order is a limit at
+    /// `stop_price ± limit_offset` of the trailed stop rather than a price
+    /// fixed at submission.
+    pub fn submit_trailing_stop_limit_offset(
+        &mut self,
+        side: Side,
+        initial_stop_price: Price,
+        limit_offset: i64,
+        quantity: Quantity,
+        tif: TimeInForce,
+        trail_method: TrailMethod,
+    ) -> StopSubmitResult {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SubmitTrailingStopLimitOffset {
+            side,
+            stop_price: initial_stop_price,
+            limit_offset,
+            quantity,
+            time_in_force: tif,
+            trail_method: trail_method.clone(),
+        });
+
+        self.submit_trailing_stop_limit_offset_internal(
+            side,
+            initial_stop_price,
+            limit_offset,
+            quantity,
+            tif,
+            trail_method,
+        )
+    }
+
+    /// Internal: submit trailing stop-limit with a trailing limit offset
+    /// without recording event.
+    pub(crate) fn submit_trailing_stop_limit_offset_internal(
+        &mut self,
+        side: Side,
+        stop_price: Price,
+        limit_offset: i64,
+        quantity: Quantity,
+        tif: TimeInForce,
+        trail_method: TrailMethod,
+    ) -> StopSubmitResult {
+        let result = self.submit_trailing_stop_internal(
+            side,
+            stop_price,
+            Some(capped_limit(side, stop_price, limit_offset)),
+            quantity,
+            tif,
+            trail_method,
+        );
+        // Trailing stops never trigger on insert, so the order is still pending.
+        if let Some(order) = self.stop_book.get_mut(result.order_id) {
+            order.limit_offset = Some(limit_offset);
+        }
+        result
+    }
+
     /// Internal: submit trailing stop order.
     pub(crate) fn submit_trailing_stop_internal(
         &mut self,
@@ -448,6 +1055,8 @@ impl Exchange {
             quantity,
             tif,
             Some(trail_method),
+            None,
+            ConditionalKind::Stop,
         )
     }
 
@@ -460,35 +1069,92 @@ impl Exchange {
         quantity: Quantity,
         tif: TimeInForce,
     ) -> StopSubmitResult {
-        self.insert_stop_order(side, stop_price, limit_price, quantity, tif, None)
+        self.insert_stop_order(
+            side,
+            stop_price,
+            limit_price,
+            quantity,
+            tif,
+            None,
+            None,
+            ConditionalKind::Stop,
+        )
     }
 
-    /// Shared logic for inserting stop/trailing-stop orders.
-    fn insert_stop_order(
+    /// Internal: submit market-if-touched order without recording event.
+    pub(crate) fn submit_mit_internal(
         &mut self,
         side: Side,
-        stop_price: Price,
-        limit_price: Option<Price>,
+        trigger_price: Price,
         quantity: Quantity,
-        tif: TimeInForce,
-        trail_method: Option<TrailMethod>,
     ) -> StopSubmitResult {
-        let id = self.book.next_order_id();
-        let timestamp = self.book.next_timestamp();
-        let is_trailing = trail_method.is_some();
-
-        let order = StopOrder {
-            id,
+        self.insert_stop_order(
             side,
-            stop_price,
-            limit_price,
+            trigger_price,
+            None,
+            quantity,
+            TimeInForce::GTC,
+            None,
+            None,
+            ConditionalKind::IfTouched,
+        )
+    }
+
+    /// Internal: submit slippage-capped stop-market order without recording event.
+    pub(crate) fn submit_stop_capped_internal(
+        &mut self,
+        side: Side,
+        stop_price: Price,
+        quantity: Quantity,
+        max_slippage: i64,
+    ) -> StopSubmitResult {
+        self.insert_stop_order(
+            side,
+            stop_price,
+            None,
+            quantity,
+            TimeInForce::GTC,
+            None,
+            Some(max_slippage),
+            ConditionalKind::Stop,
+        )
+    }
+
+    /// Shared logic for inserting stop/trailing-stop orders.
+    #[allow(clippy::too_many_arguments)]
+    fn insert_stop_order(
+        &mut self,
+        side: Side,
+        stop_price: Price,
+        limit_price: Option<Price>,
+        quantity: Quantity,
+        tif: TimeInForce,
+        trail_method: Option<TrailMethod>,
+        max_slippage: Option<i64>,
+        kind: ConditionalKind,
+    ) -> StopSubmitResult {
+        let id = self.book.next_order_id();
+        let timestamp = self.book.next_timestamp();
+        let is_trailing = trail_method.is_some();
+
+        let order = StopOrder {
+            id,
+            side,
+            stop_price,
+            limit_price,
             quantity,
             time_in_force: tif,
             timestamp,
             status: StopStatus::Pending,
             trail_method,
             watermark: None,
+            max_slippage,
+            kind,
+            limit_offset: None,
         };
+        let triggers_now = self
+            .last_trade_price
+            .is_some_and(|last_price| order.is_triggered_by(last_price));
 
         self.stop_book.insert(order);
 
@@ -496,30 +1162,24 @@ impl Exchange {
         // establish the watermark first. update_trailing_stops() will adjust the
         // stop price relative to the watermark, so the raw stop_price check would
         // be misleading.
-        if !is_trailing {
-            if let Some(last_price) = self.last_trade_price {
-                let should_trigger = match side {
-                    Side::Buy => last_price >= stop_price,
-                    Side::Sell => last_price <= stop_price,
-                };
-                if should_trigger {
-                    self.process_trade_triggers();
-                    let status = self
-                        .stop_book
-                        .get(id)
-                        .map(|o| o.status)
-                        .unwrap_or(StopStatus::Triggered);
-                    return StopSubmitResult {
-                        order_id: id,
-                        status,
-                    };
-                }
-            }
+        if !is_trailing && triggers_now {
+            let cascade_truncated = self.process_trade_triggers();
+            let status = self
+                .stop_book
+                .get(id)
+                .map(|o| o.status)
+                .unwrap_or(StopStatus::Triggered);
+            return StopSubmitResult {
+                order_id: id,
+                status,
+                cascade_truncated,
+            };
         }
 
         StopSubmitResult {
             order_id: id,
             status: StopStatus::Pending,
+            cascade_truncated: false,
         }
     }
 
@@ -529,12 +1189,13 @@ impl Exchange {
     /// stop prices reflect the latest market move.
     ///
     /// Triggered stops may produce trades that trigger more stops (cascade).
-    /// Limited to `MAX_CASCADE_DEPTH` iterations to prevent infinite loops.
-    pub(crate) fn process_trade_triggers(&mut self) {
-        for _ in 0..Self::MAX_CASCADE_DEPTH {
+    /// Limited to `max_cascade_depth` iterations to prevent infinite loops;
+    /// returns whether that limit cut the cascade short.
+    pub(crate) fn process_trade_triggers(&mut self) -> bool {
+        for _ in 0..self.max_cascade_depth {
             let trade_price = match self.last_trade_price {
                 Some(p) => p,
-                None => return,
+                None => return false,
             };
 
             // Update trailing stops before checking triggers
@@ -542,7 +1203,7 @@ impl Exchange {
 
             let triggered = self.stop_book.collect_triggered(trade_price);
             if triggered.is_empty() {
-                return;
+                return false;
             }
 
             let mut new_last_price = None;
@@ -556,9 +1217,10 @@ impl Exchange {
                         stop.time_in_force,
                     ),
                     None => {
-                        let price = match stop.side {
-                            Side::Buy => Price::MAX,
-                            Side::Sell => Price::MIN,
+                        let price = match (stop.side, stop.max_slippage) {
+                            (side, Some(cap)) => capped_limit(side, stop.stop_price, cap),
+                            (Side::Buy, None) => Price::MAX,
+                            (Side::Sell, None) => Price::MIN,
                         };
                         self.submit_limit_internal(
                             stop.side,
@@ -577,9 +1239,20 @@ impl Exchange {
 
             match new_last_price {
                 Some(p) => self.last_trade_price = Some(p),
-                None => return, // No new trades, no more triggers possible
+                None => return false, // No new trades, no more triggers possible
             }
         }
+
+        // Depth exhausted: truncated only if more stops would still fire,
+        // counting trailing stops the last trade would ratchet into range
+        let truncated = self.last_trade_price.is_some_and(|price| {
+            self.stop_book.update_trailing_stops(price);
+            self.stop_book.any_triggered_by(price)
+        });
+        if truncated {
+            self.truncated_cascades += 1;
+        }
+        truncated
     }
 
     // === Queries ===
@@ -589,6 +1262,18 @@ impl Exchange {
         self.book.get_order(order_id)
     }
 
+    /// Fills of an order as `(trade id, price, quantity, timestamp)`, in
+    /// execution order.
+    ///
+    /// Available while the order is active or in history; `None` for an
+    /// unknown order or one removed by
+    /// [`clear_order_history`](Self::clear_order_history).
+    pub fn order_executions(&self, order_id: OrderId) -> Option<&[Execution]> {
+        self.book
+            .get_order(order_id)
+            .map(|order| order.executions.as_slice())
+    }
+
     /// Get the best bid and ask prices.
     pub fn best_bid_ask(&self) -> (Option<Price>, Option<Price>) {
         self.book.best_bid_ask()
@@ -619,11 +1304,179 @@ impl Exchange {
         self.book.full_snapshot()
     }
 
+    /// Stream one side's levels best-to-worst, one [`LevelSnapshot`] at a time.
+    ///
+    /// Unlike [`full_book`](Self::full_book) nothing is collected, so deep
+    /// books can be processed with bounded memory. See
+    /// [`OrderBook::iter_levels`].
+    pub fn iter_levels(&self, side: Side) -> impl Iterator<Item = LevelSnapshot> + '_ {
+        self.book.iter_levels(side)
+    }
+
+    /// Check the order book's internal invariants.
+    ///
+    /// See [`OrderBook::validate`]. A crossed book is allowed during an
+    /// auction call phase, where it is expected.
+    pub fn validate(&self) -> Result<(), BookInvariantError> {
+        if self.in_auction {
+            self.book.validate_levels()
+        } else {
+            self.book.validate()
+        }
+    }
+
+    /// Live orders resting at `price` on `side`, in queue priority order.
+    ///
+    /// Each entry is `(id, remaining quantity, timestamp)`; cancelled orders
+    /// still tombstoned in the queue are skipped. Empty if no level exists
+    /// at that price.
+    pub fn level_orders(&self, side: Side, price: Price) -> Vec<(OrderId, Quantity, Timestamp)> {
+        let Some(level) = self.book.side(side).get_level(price) else {
+            return Vec::new();
+        };
+        level
+            .iter()
+            .filter_map(|id| self.book.get_order(id))
+            .map(|order| (order.id, order.remaining_quantity, order.timestamp))
+            .collect()
+    }
+
+    /// Quantity resting ahead of `order_id` at its price level: the
+    /// remaining size of every live order queued before it.
+    ///
+    /// Returns `None` if the order is not resting on the book.
+    pub fn queue_ahead(&self, order_id: OrderId) -> Option<Quantity> {
+        let order = self.book.get_order(order_id).filter(|o| o.is_active())?;
+        let level = self.book.side(order.side).get_level(order.price)?;
+        let mut ahead = 0;
+        for id in level.iter() {
+            if id == order_id {
+                return Some(ahead);
+            }
+            ahead += self
+                .book
+                .get_order(id)
+                .map_or(0, |queued| queued.remaining_quantity);
+        }
+        None
+    }
+
+    /// Probability that resting order `order_id` fully fills within
+    /// `horizon`, given `volume_rate` shares per unit time trading against
+    /// its side at its price.
+    ///
+    /// Deterministic v1 model: the queue ahead must trade first, so the order
+    /// fills (1.0) when `volume_rate × horizon ≥ queue_ahead + remaining`, and
+    /// otherwise does not (0.0). Returns `None` for orders that are not
+    /// resting on the book.
+    pub fn fill_probability(
+        &self,
+        order_id: OrderId,
+        volume_rate: f64,
+        horizon: f64,
+    ) -> Option<f64> {
+        let ahead = self.queue_ahead(order_id)?;
+        let remaining = self.book.get_order(order_id)?.remaining_quantity;
+        let expected_volume = volume_rate.max(0.0) * horizon.max(0.0);
+        let needed = (ahead + remaining) as f64;
+        Some(if expected_volume >= needed { 1.0 } else { 0.0 })
+    }
+
+    /// CRC-32 of the top `depth` levels per side; see
+    /// [`BookSnapshot::checksum`] for the canonical encoding.
+    pub fn book_checksum(&self, depth: usize) -> u32 {
+        self.book.snapshot(depth).checksum(depth)
+    }
+
     /// Get all trades that have occurred.
     pub fn trades(&self) -> &[Trade] {
         &self.trades
     }
 
+    /// Total quantity matched since the exchange was created.
+    ///
+    /// Unlike [`trades`](Self::trades), this is not reset by
+    /// [`clear_trades`](Self::clear_trades).
+    pub fn total_matched_quantity(&self) -> Quantity {
+        self.matched_quantity
+    }
+
+    /// Total matched notional (Σ price × quantity) in cents since the
+    /// exchange was created. Not reset by [`clear_trades`](Self::clear_trades).
+    pub fn total_matched_notional(&self) -> i128 {
+        self.matched_notional
+    }
+
+    /// Number of trades since the exchange was created. Not reset by
+    /// [`clear_trades`](Self::clear_trades).
+    pub fn trade_count(&self) -> u64 {
+        self.trade_count
+    }
+
+    /// Price improvement and trade-through counts since the exchange was
+    /// created. Not reset by [`clear_trades`](Self::clear_trades).
+    pub fn execution_quality(&self) -> ExecutionQualityStats {
+        self.execution_quality
+    }
+
+    /// Internal: append trades to the history and the cumulative stats, and
+    /// notify the trade observer.
+    pub(crate) fn record_trades(&mut self, trades: &[Trade]) {
+        for trade in trades {
+            self.matched_quantity += trade.quantity;
+            self.matched_notional += trade.price.0 as i128 * trade.quantity as i128;
+        }
+        self.trade_count += trades.len() as u64;
+        self.trades.extend(trades.iter().cloned());
+        if let Some(observer) = self.trade_observer.0.as_mut() {
+            trades.iter().for_each(observer);
+        }
+    }
+
+    /// Call `observer` with every trade as it happens, including trades from
+    /// triggered stops, delayed arrivals and auctions.
+    ///
+    /// Replaces any previous observer. The observer runs while the exchange
+    /// is mid-update, so it must not call back into the exchange; hand the
+    /// trade off (e.g. over a channel) instead. Clones of the exchange and
+    /// replays start without an observer. Without one, recording a trade
+    /// costs a single branch.
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use nanobook::{Exchange, Price, Side, TimeInForce};
+    ///
+    /// let fills = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = Arc::clone(&fills);
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_trade_observer(Box::new(move |trade| {
+    ///     sink.lock().unwrap().push((trade.price, trade.quantity));
+    /// }));
+    ///
+    /// exchange.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+    /// exchange.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+    /// assert_eq!(*fills.lock().unwrap(), vec![(Price(100_00), 50)]);
+    /// ```
+    pub fn set_trade_observer(&mut self, observer: TradeObserver) {
+        self.trade_observer.0 = Some(observer);
+    }
+
+    /// Remove and return the trade observer, if any.
+    pub fn take_trade_observer(&mut self) -> Option<TradeObserver> {
+        self.trade_observer.0.take()
+    }
+
+    /// The instrument this exchange trades (default: a plain equity).
+    pub fn instrument(&self) -> &InstrumentSpec {
+        &self.instrument
+    }
+
+    /// Set the instrument metadata. Its tick and lot sizes are enforced by
+    /// the `try_submit_*` methods; the unchecked `submit_*` methods ignore them.
+    pub fn set_instrument(&mut self, spec: InstrumentSpec) {
+        self.instrument = spec;
+    }
+
     /// Get the underlying order book (for advanced queries).
     pub fn book(&self) -> &OrderBook {
         &self.book
@@ -654,11 +1507,48 @@ impl Exchange {
         &self.stop_book
     }
 
+    // === Imbalance Recording ===
+
+    /// Start recording top-of-book imbalance, keeping the latest `capacity`
+    /// samples (at least one).
+    ///
+    /// Whenever an order, cancel, modify or auction changes the best price
+    /// or quantity on either side, a sample `(timestamp, imbalance)` is
+    /// appended, where `imbalance = (bid_qty - ask_qty) / (bid_qty + ask_qty)`
+    /// over the best level of each side (+1 = bids only, -1 = asks only) and
+    /// `timestamp` is the latest exchange timestamp. An empty book records
+    /// nothing. Re-enabling discards previous samples.
+    pub fn enable_imbalance_recording(&mut self, capacity: usize) {
+        let mut recorder = ImbalanceRecorder::new(capacity);
+        recorder.record(&self.book);
+        self.imbalance = Some(recorder);
+    }
+
+    /// Stop recording imbalance and drop the recorded samples.
+    pub fn disable_imbalance_recording(&mut self) {
+        self.imbalance = None;
+    }
+
+    /// Recorded imbalance samples, oldest first (empty if recording is off).
+    pub fn imbalance_history(&self) -> impl Iterator<Item = (Timestamp, f64)> + '_ {
+        self.imbalance
+            .iter()
+            .flat_map(|recorder| recorder.samples.iter().copied())
+    }
+
+    /// Internal: sample the imbalance if recording is on.
+    pub(crate) fn record_imbalance(&mut self) {
+        if let Some(recorder) = &mut self.imbalance {
+            recorder.record(&self.book);
+        }
+    }
+
     // === Memory Management ===
 
     /// Clear trade history to free memory.
     ///
-    /// Use periodically for long-running instances.
+    /// Use periodically for long-running instances. The cumulative
+    /// [`trade_count`](Self::trade_count) and matched volume stats are kept.
     pub fn clear_trades(&mut self) {
         self.trades.clear();
     }
@@ -687,9 +1577,59 @@ impl Default for Exchange {
     }
 }
 
+/// Best price and quantity on each side.
+type TopOfBook = ((Option<Price>, Quantity), (Option<Price>, Quantity));
+
+/// Bounded history of top-of-book imbalance samples.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct ImbalanceRecorder {
+    capacity: usize,
+    samples: VecDeque<(Timestamp, f64)>,
+    last_top: Option<TopOfBook>,
+}
+
+impl ImbalanceRecorder {
+    fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+            last_top: None,
+        }
+    }
+
+    /// Append a sample if the top of book changed since the last call.
+    fn record(&mut self, book: &OrderBook) {
+        let top_of = |side: Side| {
+            book.side(side).best_level().map_or((None, 0), |level| {
+                (Some(level.price()), level.total_quantity())
+            })
+        };
+        let top = (top_of(Side::Buy), top_of(Side::Sell));
+        if self.last_top == Some(top) {
+            return;
+        }
+        self.last_top = Some(top);
+
+        let ((_, bid_qty), (_, ask_qty)) = top;
+        let total = bid_qty + ask_qty;
+        if total == 0 {
+            return;
+        }
+        let imbalance = (bid_qty as f64 - ask_qty as f64) / total as f64;
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        let timestamp = book.peek_next_timestamp().saturating_sub(1);
+        self.samples.push_back((timestamp, imbalance));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TradeId;
 
     // === Basic submission ===
 
@@ -874,6 +1814,59 @@ mod tests {
         assert_eq!(result.filled_quantity, 0);
     }
 
+    #[test]
+    fn submit_market_fok_rejects_without_touching_book() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 20, TimeInForce::GTC);
+        let before = exchange.depth(10).asks;
+
+        let result = exchange.submit_market_fok(Side::Buy, 100);
+
+        assert_eq!(result.status, OrderStatus::Cancelled);
+        assert_eq!(result.filled_quantity, 0);
+        assert_eq!(result.cancelled_quantity, 100);
+        assert!(result.trades.is_empty());
+        assert_eq!(exchange.depth(10).asks, before);
+        assert!(exchange.trades().is_empty());
+    }
+
+    #[test]
+    fn submit_market_fok_fills_when_liquidity_suffices() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 20, TimeInForce::GTC);
+
+        let result = exchange.submit_market_fok(Side::Buy, 50);
+
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.filled_quantity, 50);
+        assert_eq!(result.cancelled_quantity, 0);
+        assert_eq!(result.trades.len(), 2);
+        assert_eq!(exchange.best_ask(), None);
+        assert_eq!(exchange.last_trade_price(), Some(Price(101_00)));
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn submit_market_fok_replays() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+        exchange.submit_market_fok(Side::Buy, 100);
+        exchange.submit_market_fok(Side::Buy, 50);
+
+        assert!(matches!(
+            exchange.events()[1],
+            Event::SubmitMarketFok {
+                side: Side::Buy,
+                quantity: 100
+            }
+        ));
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.trades(), exchange.trades());
+        assert_eq!(replayed.best_ask(), None);
+    }
+
     // === Cancel ===
 
     #[test]
@@ -912,6 +1905,68 @@ mod tests {
         assert_eq!(result.error, Some(CancelError::OrderNotActive));
     }
 
+    #[test]
+    fn cancel_after_front_fill_removes_correct_order() {
+        let mut exchange = Exchange::new();
+
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        let third = exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+
+        // Fill the first order so the level's queue shifts.
+        exchange.submit_market(Side::Buy, 10);
+        assert!(exchange.cancel(third.order_id).success);
+        assert_eq!(exchange.depth(1).asks[0].quantity, 10);
+
+        // Must fill only the remaining live order (and terminate).
+        let result = exchange.submit_market(Side::Buy, 20);
+        assert_eq!(result.filled_quantity, 10);
+        assert_eq!(exchange.best_ask(), None);
+    }
+
+    #[test]
+    fn cancel_stale_sweeps_orders_before_cutoff() {
+        let mut exchange = Exchange::new();
+        let ids: Vec<OrderId> = [99_00, 98_00, 97_00]
+            .into_iter()
+            .map(|price| {
+                exchange
+                    .submit_limit(Side::Buy, Price(price), 100, TimeInForce::GTC)
+                    .order_id
+            })
+            .collect();
+        let timestamps: Vec<Timestamp> = ids
+            .iter()
+            .map(|&id| exchange.get_order(id).unwrap().timestamp)
+            .collect();
+        assert_eq!(timestamps, vec![1, 2, 3]);
+
+        assert_eq!(exchange.cancel_stale(3), vec![ids[0], ids[1]]);
+        assert_eq!(
+            exchange.get_order(ids[0]).unwrap().status,
+            OrderStatus::Cancelled
+        );
+        assert_eq!(exchange.best_bid(), Some(Price(97_00)));
+        assert!(exchange.get_order(ids[2]).unwrap().is_active());
+
+        // Already-cancelled orders are not reported twice.
+        assert!(exchange.cancel_stale(3).is_empty());
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn cancel_stale_is_replayable() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(98_00), 100, TimeInForce::GTC);
+        exchange.cancel_stale(3);
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.full_book(), exchange.full_book());
+        assert_eq!(replayed.best_ask(), None);
+    }
+
     // === Modify ===
 
     #[test]
@@ -972,6 +2027,305 @@ mod tests {
         assert_eq!(result.error, Some(ModifyError::InvalidQuantity));
     }
 
+    #[test]
+    fn size_down_at_same_price_keeps_priority() {
+        let mut exchange = Exchange::new();
+        let first = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let second = exchange.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+
+        let result = exchange.modify_with_policy(
+            first.order_id,
+            Price(100_00),
+            40,
+            ModifyPolicy::PreservePriorityOnReduce,
+        );
+        assert!(result.success);
+        assert!(result.priority_kept);
+        assert_eq!(result.new_order_id, Some(first.order_id));
+        assert_eq!(result.cancelled_quantity, 60);
+        assert_eq!(
+            exchange
+                .get_order(first.order_id)
+                .unwrap()
+                .remaining_quantity,
+            40
+        );
+        assert_eq!(
+            exchange.book().bids().quantity_at_or_better(Price(100_00)),
+            90
+        );
+
+        // Still ahead of the later order in the queue.
+        let fill = exchange.submit_market(Side::Sell, 40);
+        assert_eq!(fill.trades[0].passive_order_id, first.order_id);
+        assert!(exchange.get_order(second.order_id).unwrap().is_active());
+    }
+
+    #[test]
+    fn price_change_or_size_up_cancel_replaces() {
+        let mut exchange = Exchange::new();
+        let submit = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let policy = ModifyPolicy::PreservePriorityOnReduce;
+
+        let repriced = exchange.modify_with_policy(submit.order_id, Price(99_00), 50, policy);
+        assert!(repriced.success);
+        assert!(!repriced.priority_kept);
+        let id = repriced.new_order_id.unwrap();
+        assert_ne!(id, submit.order_id);
+
+        let upsized = exchange.modify_with_policy(id, Price(99_00), 80, policy);
+        assert!(!upsized.priority_kept);
+        assert_ne!(upsized.new_order_id, Some(id));
+
+        let always = exchange.modify_with_policy(
+            upsized.new_order_id.unwrap(),
+            Price(99_00),
+            10,
+            ModifyPolicy::AlwaysCancelReplace,
+        );
+        assert!(always.success);
+        assert!(!always.priority_kept);
+    }
+
+    #[test]
+    fn reduce_quantity_validates() {
+        let mut exchange = Exchange::new();
+        let submit = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+
+        for qty in [0, 100, 150] {
+            let result = exchange.reduce_quantity(submit.order_id, qty);
+            assert_eq!(result.error, Some(ModifyError::InvalidQuantity));
+        }
+        assert_eq!(
+            exchange.reduce_quantity(OrderId(999), 10).error,
+            Some(ModifyError::OrderNotFound)
+        );
+        exchange.cancel(submit.order_id);
+        assert_eq!(
+            exchange.reduce_quantity(submit.order_id, 10).error,
+            Some(ModifyError::OrderNotActive)
+        );
+    }
+
+    #[test]
+    fn cancel_partial_keeps_queue_position() {
+        let mut exchange = Exchange::new();
+        let first = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let second = exchange.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+
+        let result = exchange.cancel_partial(first.order_id, 40);
+        assert!(result.success);
+        assert_eq!(result.cancelled_quantity, 40);
+        let order = exchange.get_order(first.order_id).unwrap();
+        assert_eq!(order.remaining_quantity, 60);
+        assert!(order.is_active());
+        assert_eq!(
+            exchange.book().bids().quantity_at_or_better(Price(100_00)),
+            110
+        );
+
+        let fill = exchange.submit_market(Side::Sell, 60);
+        assert_eq!(fill.trades.len(), 1);
+        assert_eq!(fill.trades[0].passive_order_id, first.order_id);
+        assert!(exchange.get_order(second.order_id).unwrap().is_active());
+    }
+
+    #[test]
+    fn cancel_partial_of_everything_is_a_full_cancel() {
+        let mut exchange = Exchange::new();
+        let submit = exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+
+        assert_eq!(
+            exchange
+                .cancel_partial(submit.order_id, 0)
+                .cancelled_quantity,
+            0
+        );
+        let result = exchange.cancel_partial(submit.order_id, 250);
+        assert!(result.success);
+        assert_eq!(result.cancelled_quantity, 100);
+        assert_eq!(
+            exchange.get_order(submit.order_id).unwrap().status,
+            OrderStatus::Cancelled
+        );
+        assert_eq!(exchange.best_ask(), None);
+        assert_eq!(
+            exchange.cancel_partial(submit.order_id, 10).error,
+            Some(CancelError::OrderNotActive)
+        );
+        assert_eq!(
+            exchange.cancel_partial(OrderId(999), 10).error,
+            Some(CancelError::OrderNotFound)
+        );
+
+        let stop = exchange.submit_stop_market(Side::Buy, Price(105_00), 100);
+        assert_eq!(
+            exchange.cancel_partial(stop.order_id, 40).error,
+            Some(CancelError::OrderNotActive)
+        );
+        assert_eq!(
+            exchange
+                .cancel_partial(stop.order_id, 100)
+                .cancelled_quantity,
+            100
+        );
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn replay_reproduces_partial_cancel() {
+        let mut exchange = Exchange::new();
+        let first = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+        exchange.cancel_partial(first.order_id, 40);
+        exchange.submit_market(Side::Sell, 80);
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.trades(), exchange.trades());
+        assert_eq!(replayed.full_book(), exchange.full_book());
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn replay_reproduces_in_place_reduction() {
+        let mut exchange = Exchange::new();
+        let first = exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+        exchange.modify_with_policy(
+            first.order_id,
+            Price(101_00),
+            30,
+            ModifyPolicy::PreservePriorityOnReduce,
+        );
+        exchange.submit_market(Side::Buy, 50);
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.trades(), exchange.trades());
+        assert_eq!(
+            replayed.get_order(first.order_id),
+            exchange.get_order(first.order_id)
+        );
+    }
+
+    // === Trade price rule ===
+
+    #[test]
+    fn aggressor_price_rule_keeps_book_uncrossed() {
+        let mut exchange = Exchange::with_trade_price_rule(TradePriceRule::AggressorPrice);
+        exchange.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(103_00), 50, TimeInForce::GTC);
+
+        let result = exchange.submit_limit(Side::Buy, Price(105_00), 80, TimeInForce::GTC);
+        assert!(result.trades.iter().all(|t| t.price == Price(105_00)));
+        assert_eq!(result.filled_quantity, 80);
+        assert_eq!(exchange.last_trade_price(), Some(Price(105_00)));
+        assert_eq!(exchange.best_bid_ask(), (None, Some(Price(103_00))));
+        assert!(!exchange.book().is_crossed());
+        assert_eq!(
+            Exchange::new().book().trade_price_rule(),
+            TradePriceRule::RestingPrice
+        );
+    }
+
+    // === Matched volume ===
+
+    #[test]
+    fn matched_volume_survives_clear_trades() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+
+        assert_eq!(exchange.trade_count(), 1);
+        assert_eq!(exchange.total_matched_quantity(), 100);
+        assert_eq!(exchange.total_matched_notional(), 1_000_000);
+
+        exchange.clear_trades();
+        assert!(exchange.trades().is_empty());
+        assert_eq!(exchange.trade_count(), 1);
+        assert_eq!(exchange.total_matched_notional(), 1_000_000);
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn replay_rebuilds_matched_volume() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 60, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 60, TimeInForce::GTC);
+        exchange.submit_market(Side::Buy, 100);
+
+        assert_eq!(exchange.trade_count(), 2);
+        assert_eq!(exchange.total_matched_notional(), 60 * 100_00 + 40 * 101_00);
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.trade_count(), exchange.trade_count());
+        assert_eq!(replayed.total_matched_quantity(), 100);
+        assert_eq!(
+            replayed.total_matched_notional(),
+            exchange.total_matched_notional()
+        );
+    }
+
+    // === Execution quality ===
+
+    #[test]
+    fn execution_quality_measures_price_improvement() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(100_50), 100, TimeInForce::GTC);
+
+        // Buy 150 limit $101: 100 @ $100.00 (+100¢) and 50 @ $100.50 (+50¢)
+        exchange.submit_limit(Side::Buy, Price(101_00), 150, TimeInForce::GTC);
+
+        let stats = exchange.execution_quality();
+        assert_eq!(stats.limit_trades, 2);
+        assert_eq!(stats.improved_trades, 2);
+        assert_eq!(stats.improved_quantity, 150);
+        assert_eq!(stats.improvement_cents, 100 * 100 + 50 * 50);
+        assert_eq!(stats.trade_throughs, 0);
+        assert_eq!(stats.improvement_per_unit(), Some(12_500.0 / 150.0));
+
+        // A trade exactly at the limit earns nothing
+        exchange.submit_limit(Side::Buy, Price(100_50), 50, TimeInForce::GTC);
+        let stats = exchange.execution_quality();
+        assert_eq!(stats.limit_trades, 3);
+        assert_eq!(stats.limit_quantity, 200);
+        assert_eq!(stats.improved_trades, 2);
+        assert_eq!(stats.improvement_cents, 12_500);
+
+        exchange.clear_trades();
+        assert_eq!(exchange.execution_quality(), stats);
+    }
+
+    #[test]
+    fn execution_quality_sell_side_and_market_orders() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(98_00), 40, TimeInForce::IOC);
+        exchange.submit_market(Side::Sell, 60);
+
+        let stats = exchange.execution_quality();
+        assert_eq!(stats.limit_trades, 1);
+        assert_eq!(stats.improvement_cents, 40 * 100);
+        assert_eq!(stats.market_trades, 1);
+        assert_eq!(stats.trade_throughs, 0);
+        assert_eq!(
+            Exchange::new().execution_quality().improvement_per_unit(),
+            None
+        );
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn replay_rebuilds_execution_quality() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(100_25), 60, TimeInForce::GTC);
+        exchange.submit_market(Side::Buy, 40);
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.execution_quality(), exchange.execution_quality());
+    }
+
     // === Validation ===
 
     #[test]
@@ -1010,6 +2364,26 @@ mod tests {
         assert_eq!(result.unwrap_err(), ValidationError::ZeroQuantity);
     }
 
+    #[test]
+    fn try_submit_enforces_instrument_grid() {
+        let mut exchange = Exchange::new();
+        exchange.set_instrument(InstrumentSpec {
+            tick_size: 5,
+            lot_size: 100,
+            ..InstrumentSpec::default()
+        });
+
+        let off_tick = exchange.try_submit_limit(Side::Buy, Price(100_03), 100, TimeInForce::GTC);
+        assert_eq!(off_tick.unwrap_err(), ValidationError::OffTick);
+        let odd_lot = exchange.try_submit_market(Side::Buy, 150);
+        assert_eq!(odd_lot.unwrap_err(), ValidationError::OddLot);
+        assert!(
+            exchange
+                .try_submit_limit(Side::Buy, Price(100_05), 200, TimeInForce::GTC)
+                .is_ok()
+        );
+    }
+
     #[test]
     fn try_submit_market_valid() {
         let mut exchange = Exchange::new();
@@ -1107,6 +2481,114 @@ mod tests {
         assert_eq!(exchange.pending_stop_count(), 0);
     }
 
+    #[test]
+    fn buy_mit_triggers_on_a_drop() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(98_00), 50, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(98_50), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 10, TimeInForce::GTC);
+
+        let mit = exchange.submit_mit(Side::Buy, Price(98_00), 60);
+        let stop = exchange.submit_stop_market(Side::Buy, Price(98_00), 10);
+        assert_eq!(mit.status, StopStatus::Pending);
+        assert_eq!(
+            exchange.get_stop_order(mit.order_id).unwrap().kind,
+            ConditionalKind::IfTouched
+        );
+
+        // A print at 101 fires the buy stop but not the buy MIT.
+        exchange.submit_limit(Side::Buy, Price(101_00), 10, TimeInForce::GTC);
+        assert_eq!(
+            exchange.get_stop_order(stop.order_id).unwrap().status,
+            StopStatus::Triggered
+        );
+        assert_eq!(
+            exchange.get_stop_order(mit.order_id).unwrap().status,
+            StopStatus::Pending
+        );
+
+        // The drop to 98 fires the MIT as a market buy.
+        let trades_before = exchange.trades().len();
+        exchange.submit_limit(Side::Sell, Price(98_00), 50, TimeInForce::GTC);
+        assert_eq!(
+            exchange.get_stop_order(mit.order_id).unwrap().status,
+            StopStatus::Triggered
+        );
+        let fills = &exchange.trades()[trades_before + 1..];
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].aggressor_side, Side::Buy);
+        assert_eq!(fills[0].price, Price(98_50));
+        assert_eq!(fills[0].quantity, 60);
+        assert_eq!(exchange.pending_stop_count(), 0);
+    }
+
+    #[test]
+    fn mit_already_touched_triggers_on_submit() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 50, TimeInForce::GTC);
+
+        // Last trade 100 is at or above a sell MIT at 100, not a sell MIT at 101.
+        let above = exchange.submit_mit(Side::Sell, Price(101_00), 50);
+        assert_eq!(above.status, StopStatus::Pending);
+        let touched = exchange.submit_mit(Side::Sell, Price(100_00), 50);
+        assert_eq!(touched.status, StopStatus::Triggered);
+        assert_eq!(exchange.best_bid(), None);
+
+        assert!(exchange.cancel(above.order_id).success);
+        assert_eq!(exchange.pending_stop_count(), 0);
+    }
+
+    /// Bids gapping down below a sell stop at 100; returns the stop's fills.
+    fn gapped_sell_stop(max_slippage: Option<i64>) -> (Exchange, Vec<Trade>) {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 30, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(97_00), 30, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(94_00), 60, TimeInForce::GTC);
+        match max_slippage {
+            Some(cap) => exchange.submit_stop_market_capped(Side::Sell, Price(100_00), 100, cap),
+            None => exchange.submit_stop_market(Side::Sell, Price(100_00), 100),
+        };
+        // The print at 100 triggers the stop.
+        exchange.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+        assert_eq!(exchange.pending_stop_count(), 0);
+        let fills = exchange.trades()[1..].to_vec();
+        (exchange, fills)
+    }
+
+    #[test]
+    fn uncapped_stop_sweeps_the_gap() {
+        let (exchange, fills) = gapped_sell_stop(None);
+        let prices: Vec<_> = fills.iter().map(|t| t.price).collect();
+        assert_eq!(prices, vec![Price(99_00), Price(97_00), Price(94_00)]);
+        assert_eq!(fills.iter().map(|t| t.quantity).sum::<u64>(), 100);
+        assert_eq!(exchange.best_bid(), Some(Price(94_00)));
+    }
+
+    #[test]
+    fn capped_stop_fills_only_to_its_limit() {
+        let (exchange, fills) = gapped_sell_stop(Some(3_00));
+        let prices: Vec<_> = fills.iter().map(|t| t.price).collect();
+        assert_eq!(prices, vec![Price(99_00), Price(97_00)]);
+        assert_eq!(fills.iter().map(|t| t.quantity).sum::<u64>(), 60);
+
+        // The residual 40 is cancelled (IOC) rather than resting at 97.
+        let order = exchange.get_order(fills[0].aggressor_order_id).unwrap();
+        assert_eq!(order.time_in_force, TimeInForce::IOC);
+        assert_eq!(order.remaining_quantity, 40);
+        assert_eq!(exchange.best_ask(), None);
+        assert_eq!(exchange.best_bid(), Some(Price(94_00)));
+        assert_eq!(exchange.last_trade_price(), Some(Price(97_00)));
+
+        #[cfg(feature = "event-log")]
+        assert_eq!(
+            Exchange::replay(exchange.events()).trades(),
+            exchange.trades()
+        );
+    }
+
     #[test]
     fn immediate_trigger_if_price_already_past() {
         let mut exchange = Exchange::new();
@@ -1145,6 +2627,107 @@ mod tests {
         assert_eq!(exchange.pending_stop_count(), 0);
     }
 
+    /// Asks one dollar apart from $100 and a buy stop at each, so every
+    /// triggered stop lifts the next ask and fires the next stop.
+    fn stop_chain(exchange: &mut Exchange, len: i64) {
+        for i in 0..=len {
+            exchange.submit_limit(Side::Sell, Price(100_00 + i * 100), 10, TimeInForce::GTC);
+        }
+        for i in 0..len {
+            exchange.submit_stop_market(Side::Buy, Price(100_00 + i * 100), 10);
+        }
+    }
+
+    #[test]
+    fn stop_cascade_truncated_at_max_depth() {
+        let mut exchange = Exchange::new();
+        exchange.set_max_cascade_depth(5);
+        stop_chain(&mut exchange, 20);
+
+        let result = exchange.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+
+        assert!(result.cascade_truncated);
+        assert_eq!(exchange.truncated_cascades(), 1);
+        assert_eq!(exchange.pending_stop_count(), 15);
+        assert_eq!(exchange.last_trade_price(), Some(Price(105_00)));
+        assert_eq!(exchange.best_ask(), Some(Price(106_00)));
+        assert!(exchange.validate().is_ok());
+    }
+
+    #[test]
+    fn stop_cascade_ending_at_max_depth_is_not_truncated() {
+        let mut exchange = Exchange::new();
+        exchange.set_max_cascade_depth(5);
+        stop_chain(&mut exchange, 5);
+
+        let result = exchange.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+
+        assert!(!result.cascade_truncated);
+        assert_eq!(exchange.truncated_cascades(), 0);
+        assert_eq!(exchange.pending_stop_count(), 0);
+    }
+
+    #[test]
+    fn immediately_triggered_stop_reports_truncation() {
+        let mut exchange = Exchange::new();
+        exchange.set_max_cascade_depth(5);
+        exchange.submit_limit(Side::Sell, Price(99_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC);
+        stop_chain(&mut exchange, 20);
+
+        let result = exchange.submit_stop_market(Side::Buy, Price(99_00), 10);
+
+        assert_eq!(result.status, StopStatus::Triggered);
+        assert!(result.cascade_truncated);
+        assert_eq!(exchange.truncated_cascades(), 1);
+    }
+
+    #[test]
+    fn trailing_stop_ratcheted_by_last_trade_counts_as_truncation() {
+        let mut exchange = Exchange::new();
+        exchange.set_max_cascade_depth(1);
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(95_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(50_00), 10, TimeInForce::GTC);
+        exchange.submit_stop_market(Side::Sell, Price(100_00), 10);
+        // ATR trail: no offset until a price change is seen, then 5.00
+        // below the 100.00 high once the stop's sale prints at 95.00.
+        exchange.submit_trailing_stop_market(
+            Side::Sell,
+            Price(1_00),
+            10,
+            TrailMethod::Atr {
+                multiplier: 1.0,
+                period: 14,
+            },
+        );
+
+        let result = exchange.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+
+        assert_eq!(exchange.last_trade_price(), Some(Price(95_00)));
+        assert!(result.cascade_truncated);
+        assert_eq!(exchange.pending_stop_count(), 1);
+    }
+
+    #[test]
+    fn default_max_cascade_depth_runs_long_chains() {
+        let mut exchange = Exchange::new();
+        assert_eq!(
+            exchange.max_cascade_depth(),
+            Exchange::DEFAULT_MAX_CASCADE_DEPTH
+        );
+        stop_chain(&mut exchange, 20);
+
+        let result = exchange.submit_market(Side::Buy, 10);
+
+        assert!(!result.cascade_truncated);
+        assert_eq!(exchange.pending_stop_count(), 0);
+        assert_eq!(exchange.best_ask(), None);
+
+        exchange.set_max_cascade_depth(0);
+        assert_eq!(exchange.max_cascade_depth(), 1);
+    }
+
     // === Queries ===
 
     #[test]
@@ -1174,6 +2757,109 @@ mod tests {
         assert_eq!(snap.best_ask(), Some(Price(101_00)));
     }
 
+    #[test]
+    fn level_orders_in_queue_order() {
+        let mut exchange = Exchange::new();
+        let ids: Vec<OrderId> = [100, 200, 300]
+            .into_iter()
+            .map(|qty| {
+                exchange
+                    .submit_limit(Side::Buy, Price(100_00), qty, TimeInForce::GTC)
+                    .order_id
+            })
+            .collect();
+        // Partially fill the front order.
+        exchange.submit_limit(Side::Sell, Price(100_00), 40, TimeInForce::GTC);
+
+        let orders = exchange.level_orders(Side::Buy, Price(100_00));
+        let queue: Vec<(OrderId, Quantity)> = orders.iter().map(|&(id, q, _)| (id, q)).collect();
+        assert_eq!(queue, vec![(ids[0], 60), (ids[1], 200), (ids[2], 300)]);
+        assert!(orders.windows(2).all(|w| w[0].2 < w[1].2));
+
+        exchange.cancel(ids[1]);
+        let ids_left: Vec<OrderId> = exchange
+            .level_orders(Side::Buy, Price(100_00))
+            .iter()
+            .map(|&(id, _, _)| id)
+            .collect();
+        assert_eq!(ids_left, vec![ids[0], ids[2]]);
+
+        assert!(exchange.level_orders(Side::Buy, Price(99_00)).is_empty());
+        assert!(exchange.level_orders(Side::Sell, Price(100_00)).is_empty());
+    }
+
+    #[test]
+    fn queue_ahead_and_fill_probability() {
+        let mut exchange = Exchange::new();
+        let front = exchange
+            .submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC)
+            .order_id;
+        let middle = exchange
+            .submit_limit(Side::Sell, Price(101_00), 500, TimeInForce::GTC)
+            .order_id;
+        let back = exchange
+            .submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC)
+            .order_id;
+
+        assert_eq!(exchange.queue_ahead(front), Some(0));
+        assert_eq!(exchange.queue_ahead(back), Some(600));
+
+        // 10 shares per unit time for 20 units: 200 shares trade.
+        assert_eq!(exchange.fill_probability(front, 10.0, 20.0), Some(1.0));
+        assert_eq!(exchange.fill_probability(back, 10.0, 20.0), Some(0.0));
+        assert_eq!(exchange.fill_probability(back, 10.0, 70.0), Some(1.0));
+
+        // Cancelling the middle order moves the back of the queue up.
+        exchange.cancel(middle);
+        assert_eq!(exchange.queue_ahead(back), Some(100));
+        assert_eq!(exchange.fill_probability(back, 10.0, 20.0), Some(1.0));
+
+        // Not resting: cancelled, filled, or unknown.
+        assert_eq!(exchange.fill_probability(middle, 10.0, 20.0), None);
+        exchange.submit_market(Side::Buy, 100);
+        assert_eq!(exchange.queue_ahead(front), None);
+        assert_eq!(exchange.queue_ahead(back), Some(0));
+        assert_eq!(exchange.fill_probability(OrderId(999), 10.0, 20.0), None);
+    }
+
+    #[test]
+    fn trade_observer_sees_every_trade_including_stops() {
+        use std::sync::{Arc, Mutex};
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let mut exchange = Exchange::new();
+        exchange.set_trade_observer(Box::new(move |trade| {
+            sink.lock().unwrap().push((trade.price, trade.quantity));
+        }));
+
+        exchange.submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 50, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(102_00), 40, TimeInForce::GTC);
+        exchange.submit_stop_market(Side::Buy, Price(101_00), 40);
+
+        // Sweeps two levels; the print at 101 triggers the buy stop, which
+        // takes the rest of 101 and part of 102.
+        exchange.submit_limit(Side::Buy, Price(101_00), 60, TimeInForce::GTC);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                (Price(100_00), 30),
+                (Price(101_00), 30),
+                (Price(101_00), 20),
+                (Price(102_00), 20),
+            ]
+        );
+        assert_eq!(seen.lock().unwrap().len(), exchange.trades().len());
+
+        // Clones don't inherit the observer; taking it stops notifications.
+        let mut clone = exchange.clone();
+        clone.submit_limit(Side::Buy, Price(102_00), 5, TimeInForce::GTC);
+        assert!(exchange.take_trade_observer().is_some());
+        exchange.submit_limit(Side::Buy, Price(102_00), 5, TimeInForce::GTC);
+        assert_eq!(seen.lock().unwrap().len(), 4);
+    }
+
     // === Trailing Stop Orders ===
 
     #[test]
@@ -1294,4 +2980,300 @@ mod tests {
         assert!(cancel.success);
         assert_eq!(exchange.pending_stop_count(), 0);
     }
+
+    #[test]
+    fn trailing_stop_limit_offset_moves_limit_with_stop() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(105_00), 50, TimeInForce::GTC);
+
+        // Trailing sell stop at 95, trail $3, limit $1 below the stop
+        let result = exchange.submit_trailing_stop_limit_offset(
+            Side::Sell,
+            Price(95_00),
+            1_00,
+            50,
+            TimeInForce::GTC,
+            TrailMethod::Fixed(3_00),
+        );
+        let stop = exchange.get_stop_order(result.order_id).unwrap();
+        assert_eq!(stop.limit_price, Some(Price(94_00)));
+
+        // Favorable run to 105: stop 95 -> 102 and limit 94 -> 101
+        exchange.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(105_00), 50, TimeInForce::GTC);
+        let stop = exchange.get_stop_order(result.order_id).unwrap();
+        assert_eq!(stop.stop_price, Price(102_00));
+        assert_eq!(stop.limit_price, Some(Price(101_00)));
+
+        // Trade at 101.50 triggers; the sell rests at the trailed limit
+        // instead of hitting the 100 bid.
+        exchange.submit_limit(Side::Buy, Price(101_50), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_50), 10, TimeInForce::GTC);
+        assert_eq!(exchange.pending_stop_count(), 0);
+        assert_eq!(exchange.best_ask(), Some(Price(101_00)));
+        assert_eq!(exchange.best_bid(), Some(Price(100_00)));
+        assert_eq!(exchange.trades().len(), 3);
+    }
+
+    // === Imbalance recording ===
+
+    #[test]
+    fn imbalance_moves_toward_the_heavier_side() {
+        let mut exchange = Exchange::new();
+        exchange.enable_imbalance_recording(100);
+        exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+        for _ in 0..3 {
+            exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        }
+        // Deeper bids leave the top of book unchanged: no sample.
+        exchange.submit_limit(Side::Buy, Price(99_00), 500, TimeInForce::GTC);
+
+        let history: Vec<(u64, f64)> = exchange.imbalance_history().collect();
+        let values: Vec<f64> = history.iter().map(|&(_, imb)| imb).collect();
+        assert_eq!(values, vec![1.0, 0.0, 1.0 / 3.0, 0.5, 0.6]);
+        assert_eq!(history[0].0, 1);
+        assert!(history.windows(2).all(|w| w[0].0 < w[1].0));
+
+        // Asks stacking up swing it back toward -1.
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+        exchange.enable_imbalance_recording(100);
+        for _ in 0..4 {
+            exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+        }
+        let last = exchange.imbalance_history().last().unwrap().1;
+        assert!((last - (10.0 - 400.0) / 410.0).abs() < 1e-12);
+        assert_eq!(exchange.imbalance_history().next().unwrap().1, 1.0);
+    }
+
+    #[test]
+    fn imbalance_history_respects_capacity() {
+        let mut exchange = Exchange::new();
+        assert_eq!(exchange.imbalance_history().count(), 0);
+
+        exchange.enable_imbalance_recording(3);
+        for i in 0..10 {
+            exchange.submit_limit(Side::Buy, Price(100_00 + i), 10, TimeInForce::GTC);
+        }
+        let id = exchange
+            .submit_limit(Side::Sell, Price(120_00), 10, TimeInForce::GTC)
+            .order_id;
+        exchange.cancel(id);
+
+        let values: Vec<f64> = exchange.imbalance_history().map(|(_, imb)| imb).collect();
+        assert_eq!(values, vec![1.0, 0.0, 1.0]);
+
+        exchange.disable_imbalance_recording();
+        assert_eq!(exchange.imbalance_history().count(), 0);
+    }
+
+    #[test]
+    fn id_offsets_keep_shards_disjoint() {
+        let mut shards = [
+            Exchange::new(),
+            Exchange::with_id_offsets(1_000_000, 1_000_000, 0),
+        ];
+        let mut order_ids = std::collections::HashSet::new();
+        for shard in &mut shards {
+            for i in 0..200 {
+                let side = if i % 2 == 0 { Side::Buy } else { Side::Sell };
+                let result = shard.submit_limit(side, Price(100_00), 10, TimeInForce::GTC);
+                assert!(order_ids.insert(result.order_id));
+            }
+        }
+        assert_eq!(order_ids.len(), 400);
+        assert_eq!(shards[1].trades()[0].id, TradeId(1_000_001));
+        assert!(shards[0].trades().iter().all(|t| t.id.0 <= 1_000_000));
+    }
+
+    #[test]
+    fn id_offsets_only_move_counters_forward() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+        exchange.set_id_offsets(0, 0, 0);
+        let id = exchange
+            .submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC)
+            .order_id;
+        assert_eq!(id, OrderId(2));
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn replay_preserves_id_offsets() {
+        let mut exchange = Exchange::with_id_offsets(1_000_000, 500, 7_000);
+        exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(100_00), 40, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 40, TimeInForce::GTC);
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.trades(), exchange.trades());
+        assert_eq!(replayed.trades()[0].id, TradeId(501));
+        assert_eq!(replayed.trades()[0].timestamp, 7_003);
+        assert!(replayed.get_order(OrderId(1_000_003)).is_some());
+        assert!(Exchange::replay_checked(exchange.events()).is_ok());
+    }
+
+    #[test]
+    fn order_executions_track_each_fill() {
+        let mut exchange = Exchange::new();
+        let resting = exchange
+            .submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC)
+            .order_id;
+        exchange.submit_limit(Side::Sell, Price(102_00), 100, TimeInForce::GTC);
+        for quantity in [30, 50] {
+            exchange.submit_market(Side::Buy, quantity);
+        }
+        let sweep = exchange.submit_limit(Side::Buy, Price(102_00), 60, TimeInForce::GTC);
+
+        let executions = exchange.order_executions(resting).unwrap();
+        let prices: Vec<Price> = executions.iter().map(|e| e.1).collect();
+        assert_eq!(prices, vec![Price(101_00); 3]);
+        let quantities: Vec<Quantity> = executions.iter().map(|e| e.2).collect();
+        assert_eq!(quantities, vec![30, 50, 20]);
+        assert_eq!(
+            quantities.iter().sum::<Quantity>(),
+            exchange.get_order(resting).unwrap().filled_quantity
+        );
+        assert_eq!(executions[2].0, sweep.trades[0].id);
+
+        // The aggressor sees its own fills across both levels.
+        let aggressor = exchange.order_executions(sweep.order_id).unwrap();
+        assert_eq!(
+            aggressor.iter().map(|e| (e.1, e.2)).collect::<Vec<_>>(),
+            vec![(Price(101_00), 20), (Price(102_00), 40)]
+        );
+
+        exchange.clear_order_history();
+        assert!(exchange.order_executions(resting).is_none());
+        assert!(exchange.order_executions(OrderId(999)).is_none());
+    }
+
+    #[test]
+    fn validate_allows_crossed_book_only_in_auction() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(100_00), 40, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+        exchange.cancel(OrderId(3));
+        assert_eq!(exchange.validate(), Ok(()));
+
+        exchange.begin_auction();
+        exchange.submit_limit(Side::Sell, Price(99_00), 10, TimeInForce::GTC);
+        assert!(exchange.book().is_crossed());
+        assert_eq!(exchange.validate(), Ok(()));
+        exchange.run_auction();
+        assert_eq!(exchange.validate(), Ok(()));
+    }
+
+    #[test]
+    fn with_capacity_avoids_reallocation_and_matches_new() {
+        let run = |exchange: &mut Exchange| {
+            let trades_ptr = exchange.trades.as_ptr();
+            let trades_cap = exchange.trades.capacity();
+            #[cfg(feature = "event-log")]
+            let (events_ptr, events_cap) = (exchange.events.as_ptr(), exchange.events.capacity());
+
+            let mut results = Vec::new();
+            for i in 0..500 {
+                let price = Price(100_00 + (i % 7));
+                for (side, tif) in [
+                    (Side::Sell, TimeInForce::GTC),
+                    (Side::Buy, TimeInForce::IOC),
+                ] {
+                    let r = exchange.submit_limit(side, price, 10, tif);
+                    results.push((r.order_id, r.status, r.trades, r.filled_quantity));
+                }
+            }
+            assert_eq!(exchange.trades().len(), 500);
+
+            let stable =
+                exchange.trades.as_ptr() == trades_ptr && exchange.trades.capacity() == trades_cap;
+            #[cfg(feature = "event-log")]
+            let stable = stable
+                && exchange.events.as_ptr() == events_ptr
+                && exchange.events.capacity() == events_cap;
+            (results, stable)
+        };
+
+        let mut reserved = Exchange::with_capacity(1000, 1000);
+        assert!(reserved.trades.capacity() >= 1000);
+        let (reserved_results, stable) = run(&mut reserved);
+        assert!(stable, "history reallocated within the capacity hints");
+
+        let mut plain = Exchange::new();
+        let (plain_results, _) = run(&mut plain);
+        assert_eq!(reserved_results, plain_results);
+        assert_eq!(reserved.trades(), plain.trades());
+        assert_eq!(reserved.full_book(), plain.full_book());
+        assert!(reserved.scratch_trades.is_empty());
+    }
+
+    fn mixed_batch() -> Vec<(Side, Price, Quantity, TimeInForce)> {
+        let tifs = [
+            TimeInForce::GTC,
+            TimeInForce::GTC,
+            TimeInForce::IOC,
+            TimeInForce::FOK,
+        ];
+        (0..50)
+            .map(|i: i64| {
+                let side = if i % 2 == 0 { Side::Buy } else { Side::Sell };
+                let price = Price(100_00 + (i * 37 % 11) - 5);
+                (side, price, 10 + (i as u64 * 13 % 40), tifs[i as usize % 4])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn submit_batch_matches_individual_submits() {
+        let orders = mixed_batch();
+        let setup = |exchange: &mut Exchange| {
+            exchange.submit_limit(Side::Sell, Price(100_03), 30, TimeInForce::GTC);
+            exchange.submit_stop_market(Side::Buy, Price(100_03), 15);
+        };
+
+        let mut batched = Exchange::new();
+        setup(&mut batched);
+        let batch_results = batched.submit_batch(&orders);
+
+        let mut single = Exchange::new();
+        setup(&mut single);
+        let single_results: Vec<SubmitResult> = orders
+            .iter()
+            .map(|&(side, price, quantity, tif)| single.submit_limit(side, price, quantity, tif))
+            .collect();
+
+        assert_eq!(batch_results.len(), 50);
+        for (b, s) in batch_results.iter().zip(&single_results) {
+            assert_eq!(
+                (b.order_id, b.status, &b.trades, b.resting_quantity),
+                (s.order_id, s.status, &s.trades, s.resting_quantity)
+            );
+        }
+        assert!(!batched.trades().is_empty());
+        assert_eq!(batched.trades(), single.trades());
+        assert_eq!(batched.full_book(), single.full_book());
+        assert_eq!(batched.pending_stop_count(), single.pending_stop_count());
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn submit_batch_replays_from_one_event() {
+        let mut exchange = Exchange::new();
+        exchange.submit_stop_market(Side::Buy, Price(100_03), 15);
+        exchange.submit_batch(&mixed_batch());
+        assert_eq!(exchange.events().len(), 2);
+        assert!(matches!(
+            &exchange.events()[1],
+            Event::SubmitBatch { orders } if orders.len() == 50
+        ));
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.trades(), exchange.trades());
+        assert_eq!(replayed.full_book(), exchange.full_book());
+        assert!(Exchange::replay_checked(exchange.events()).is_ok());
+    }
 }
@@ -0,0 +1,226 @@
+//! Execution algorithms: slicing a parent order into child orders.
+
+use crate::{Exchange, Quantity, Side, SubmitResult};
+
+/// Time-weighted average price (TWAP) schedule for a parent order.
+///
+/// The parent quantity is split into equal child market orders, one per
+/// slice; the final slice also takes the rounding remainder. The caller
+/// decides when each slice runs (e.g. once per bar) by calling
+/// [`execute_slice`](Self::execute_slice). Fills are accumulated so the
+/// achieved average price can be compared with a single market order for
+/// the whole quantity, estimated from the book when the first slice runs.
+///
+/// ```
+/// use nanobook::{Exchange, Price, Side, TimeInForce};
+/// use nanobook::execution::Twap;
+///
+/// let mut exchange = Exchange::new();
+/// exchange.submit_limit(Side::Sell, Price(100_00), 1_000, TimeInForce::GTC);
+///
+/// let mut twap = Twap::new(Side::Buy, 1_000, 3);
+/// assert_eq!(twap.schedule(), &[333, 333, 334]);
+///
+/// for slice in 0..twap.slice_count() {
+///     twap.execute_slice(&mut exchange, slice);
+/// }
+/// assert_eq!(twap.filled_quantity(), 1_000);
+/// assert_eq!(twap.average_price(), Some(100_00.0));
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Twap {
+    side: Side,
+    quantity: Quantity,
+    schedule: Vec<Quantity>,
+    executed: Vec<bool>,
+    filled_quantity: Quantity,
+    /// Σ price × quantity over child fills, in cents.
+    filled_notional: i128,
+    single_shot_price: Option<f64>,
+}
+
+impl Twap {
+    /// Schedule `quantity` on `side` in `slices` child orders.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slices` is zero.
+    pub fn new(side: Side, quantity: Quantity, slices: usize) -> Self {
+        assert!(slices > 0, "TWAP needs at least one slice");
+        let base = quantity / slices as u64;
+        let mut schedule = vec![base; slices];
+        schedule[slices - 1] += quantity % slices as u64;
+
+        Self {
+            side,
+            quantity,
+            schedule,
+            executed: vec![false; slices],
+            filled_quantity: 0,
+            filled_notional: 0,
+            single_shot_price: None,
+        }
+    }
+
+    /// Side of the parent order.
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    /// Parent order quantity.
+    pub fn quantity(&self) -> Quantity {
+        self.quantity
+    }
+
+    /// Child order size for each slice.
+    pub fn schedule(&self) -> &[Quantity] {
+        &self.schedule
+    }
+
+    /// Number of slices.
+    pub fn slice_count(&self) -> usize {
+        self.schedule.len()
+    }
+
+    /// Whether every slice has been submitted.
+    pub fn is_complete(&self) -> bool {
+        self.executed.iter().all(|&done| done)
+    }
+
+    /// Submit slice `slice_index` as a market order on `exchange`.
+    ///
+    /// Returns `None` (and submits nothing) if the index is out of range,
+    /// the slice already ran, or its size is zero. The first slice to run
+    /// also records the [`single_shot_price`](Self::single_shot_price).
+    pub fn execute_slice(
+        &mut self,
+        exchange: &mut Exchange,
+        slice_index: usize,
+    ) -> Option<SubmitResult> {
+        let quantity = *self.schedule.get(slice_index)?;
+        if self.executed[slice_index] || quantity == 0 {
+            return None;
+        }
+        if !self.executed.contains(&true) {
+            self.single_shot_price = sweep_price(exchange, self.side, self.quantity);
+        }
+
+        let result = exchange.submit_market(self.side, quantity);
+        self.executed[slice_index] = true;
+        for trade in &result.trades {
+            self.filled_quantity += trade.quantity;
+            self.filled_notional += trade.price.0 as i128 * trade.quantity as i128;
+        }
+        Some(result)
+    }
+
+    /// Total quantity filled across executed slices.
+    pub fn filled_quantity(&self) -> Quantity {
+        self.filled_quantity
+    }
+
+    /// Achieved volume-weighted average fill price in cents, or `None`
+    /// before any fill.
+    pub fn average_price(&self) -> Option<f64> {
+        if self.filled_quantity == 0 {
+            return None;
+        }
+        Some(self.filled_notional as f64 / self.filled_quantity as f64)
+    }
+
+    /// Average price in cents a single market order for the full parent
+    /// quantity would have achieved against the book as it stood when the
+    /// first slice ran (over the fillable part only), or `None` if the
+    /// book was empty on that side or no slice has run.
+    pub fn single_shot_price(&self) -> Option<f64> {
+        self.single_shot_price
+    }
+
+    /// Price improvement of the TWAP over the single-shot benchmark, in
+    /// basis points of the benchmark. Positive means the slices traded
+    /// better (lower for buys, higher for sells).
+    pub fn improvement_bps(&self) -> Option<f64> {
+        let (achieved, benchmark) = (self.average_price()?, self.single_shot_price?);
+        let diff = match self.side {
+            Side::Buy => benchmark - achieved,
+            Side::Sell => achieved - benchmark,
+        };
+        Some(diff / benchmark * 10_000.0)
+    }
+}
+
+/// Average price of sweeping `quantity` from the side opposite `side`.
+fn sweep_price(exchange: &Exchange, side: Side, quantity: Quantity) -> Option<f64> {
+    let mut remaining = quantity;
+    let mut filled = 0u64;
+    let mut notional = 0i128;
+    for (price, level) in exchange.book().opposite_side(side).iter_best_to_worst() {
+        if remaining == 0 {
+            break;
+        }
+        let take = remaining.min(level.total_quantity());
+        filled += take;
+        notional += price.0 as i128 * take as i128;
+        remaining -= take;
+    }
+    (filled > 0).then(|| notional as f64 / filled as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Price, TimeInForce};
+
+    #[test]
+    fn final_slice_takes_remainder() {
+        assert_eq!(Twap::new(Side::Sell, 10, 4).schedule(), &[2, 2, 2, 4]);
+        assert_eq!(Twap::new(Side::Sell, 2, 3).schedule(), &[0, 0, 2]);
+    }
+
+    #[test]
+    fn five_slices_of_1000_shares() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 400, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 600, TimeInForce::GTC);
+
+        let mut twap = Twap::new(Side::Buy, 1_000, 5);
+        assert_eq!(twap.schedule(), &[200; 5]);
+
+        for slice in 0..5 {
+            // Liquidity replenishes at the touch between slices.
+            if slice > 0 {
+                exchange.submit_limit(Side::Sell, Price(100_00), 200, TimeInForce::GTC);
+            }
+            let result = twap.execute_slice(&mut exchange, slice).unwrap();
+            assert_eq!(result.filled_quantity, 200);
+        }
+        assert!(twap.is_complete());
+        assert!(twap.execute_slice(&mut exchange, 0).is_none());
+        assert!(twap.execute_slice(&mut exchange, 5).is_none());
+
+        assert_eq!(twap.filled_quantity(), 1_000);
+        assert_eq!(twap.average_price(), Some(100_00.0));
+        // One order for 1000 would have swept 400 @ 100 and 600 @ 101.
+        assert_eq!(twap.single_shot_price(), Some(100_60.0));
+        let improvement = twap.improvement_bps().unwrap();
+        assert!((improvement - 60.0 / 100_60.0 * 10_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn partial_fills_track_average_price() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(50_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(49_00), 100, TimeInForce::GTC);
+
+        let mut twap = Twap::new(Side::Sell, 300, 2);
+        twap.execute_slice(&mut exchange, 0);
+        let last = twap.execute_slice(&mut exchange, 1).unwrap();
+
+        assert_eq!(last.filled_quantity, 50); // book ran dry
+        assert_eq!(twap.filled_quantity(), 200);
+        assert_eq!(twap.average_price(), Some(49_50.0));
+        assert_eq!(twap.single_shot_price(), Some(49_50.0));
+        assert_eq!(twap.improvement_bps(), Some(0.0));
+    }
+}
@@ -0,0 +1,270 @@
+//! CSV export of trade history and the event log.
+//!
+//! Prices are rendered with the `Price` display convention (`$101.50`),
+//! sides as `BUY`/`SELL`, and IDs as plain integers, so the output loads
+//! directly into a spreadsheet.
+
+use std::io::{self, Write};
+
+use crate::Exchange;
+#[cfg(feature = "event-log")]
+use crate::stop::capped_limit;
+#[cfg(feature = "event-log")]
+use crate::{Event, TrailMethod};
+
+/// Header row written by [`Exchange::trades_to_csv`].
+pub const TRADES_CSV_HEADER: &str =
+    "trade_id,timestamp,price,quantity,aggressor_side,maker_order_id,taker_order_id";
+
+/// Header row written by [`Exchange::events_to_csv`].
+#[cfg(feature = "event-log")]
+pub const EVENTS_CSV_HEADER: &str =
+    "index,event,side,price,stop_price,quantity,time_in_force,order_id,trail";
+
+impl Exchange {
+    /// Write the trade history as CSV.
+    ///
+    /// Columns: `trade_id,timestamp,price,quantity,aggressor_side,maker_order_id,taker_order_id`.
+    /// An exchange with no trades emits only the header line.
+    pub fn trades_to_csv<W: Write>(&self, w: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(w);
+        writeln!(w, "{}", TRADES_CSV_HEADER)?;
+        for t in self.trades() {
+            writeln!(
+                w,
+                "{},{},{},{},{},{},{}",
+                t.id.0,
+                t.timestamp,
+                t.price,
+                t.quantity,
+                t.aggressor_side,
+                t.passive_order_id.0,
+                t.aggressor_order_id.0
+            )?;
+        }
+        w.flush()
+    }
+
+    /// Write the event log as CSV, one row per event (one `SubmitLimit` row
+    /// per order of a `SubmitBatch`, all sharing the batch's index).
+    ///
+    /// Columns: `index,event,side,price,stop_price,quantity,time_in_force,order_id,trail`.
+    /// Fields that don't apply to an event type are left empty; `price` is the
+    /// limit price (the new price for `Modify`, the slippage-capped limit for
+    /// `SubmitStopMarketCapped`, the initial trailed limit for
+    /// `SubmitTrailingStopLimitOffset`); `stop_price` also holds the trigger of a
+    /// `SubmitMit`.
+    /// Simulation timestamps (`SubmitLimitDelayed` arrivals, `AdvanceTo`) and
+    /// `SetIdOffsets` counters have no column and are omitted.
+    #[cfg(feature = "event-log")]
+    pub fn events_to_csv<W: Write>(&self, w: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(w);
+        writeln!(w, "{}", EVENTS_CSV_HEADER)?;
+        for (index, event) in self.events().iter().enumerate() {
+            let row = match event {
+                Event::SubmitLimit {
+                    side,
+                    price,
+                    quantity,
+                    time_in_force,
+                } => format!("SubmitLimit,{side},{price},,{quantity},{time_in_force},,"),
+                Event::SubmitMarket { side, quantity } => {
+                    format!("SubmitMarket,{side},,,{quantity},,,")
+                }
+                Event::Cancel { order_id } => format!("Cancel,,,,,,{},", order_id.0),
+                Event::Modify {
+                    order_id,
+                    new_price,
+                    new_quantity,
+                } => format!("Modify,,{new_price},,{new_quantity},,{},", order_id.0),
+                Event::ReduceQuantity {
+                    order_id,
+                    new_quantity,
+                } => format!("ReduceQuantity,,,,{new_quantity},,{},", order_id.0),
+                Event::CancelPartial { order_id, quantity } => {
+                    format!("CancelPartial,,,,{quantity},,{},", order_id.0)
+                }
+                Event::SubmitStopMarket {
+                    side,
+                    stop_price,
+                    quantity,
+                } => format!("SubmitStopMarket,{side},,{stop_price},{quantity},,,"),
+                Event::SubmitStopLimit {
+                    side,
+                    stop_price,
+                    limit_price,
+                    quantity,
+                    time_in_force,
+                } => format!(
+                    "SubmitStopLimit,{side},{limit_price},{stop_price},{quantity},{time_in_force},,"
+                ),
+                Event::SubmitTrailingStopMarket {
+                    side,
+                    stop_price,
+                    quantity,
+                    trail_method,
+                } => format!(
+                    "SubmitTrailingStopMarket,{side},,{stop_price},{quantity},,,{}",
+                    trail_label(trail_method)
+                ),
+                Event::SubmitTrailingStopLimit {
+                    side,
+                    stop_price,
+                    limit_price,
+                    quantity,
+                    time_in_force,
+                    trail_method,
+                } => format!(
+                    "SubmitTrailingStopLimit,{side},{limit_price},{stop_price},{quantity},{time_in_force},,{}",
+                    trail_label(trail_method)
+                ),
+                Event::BeginAuction => "BeginAuction,,,,,,,".to_string(),
+                Event::RunAuction => "RunAuction,,,,,,,".to_string(),
+                Event::SubmitStopMarketCapped {
+                    side,
+                    stop_price,
+                    quantity,
+                    max_slippage,
+                } => format!(
+                    "SubmitStopMarketCapped,{side},{},{stop_price},{quantity},,,",
+                    capped_limit(*side, *stop_price, *max_slippage)
+                ),
+                Event::SubmitMit {
+                    side,
+                    trigger_price,
+                    quantity,
+                } => format!("SubmitMit,{side},,{trigger_price},{quantity},,,"),
+                Event::SubmitLimitDelayed {
+                    side,
+                    price,
+                    quantity,
+                    time_in_force,
+                    ..
+                } => format!("SubmitLimitDelayed,{side},{price},,{quantity},{time_in_force},,"),
+                Event::AdvanceTo { .. } => "AdvanceTo,,,,,,,".to_string(),
+                Event::SubmitTrailingStopLimitOffset {
+                    side,
+                    stop_price,
+                    limit_offset,
+                    quantity,
+                    time_in_force,
+                    trail_method,
+                } => format!(
+                    "SubmitTrailingStopLimitOffset,{side},{},{stop_price},{quantity},{time_in_force},,{}",
+                    capped_limit(*side, *stop_price, *limit_offset),
+                    trail_label(trail_method)
+                ),
+                Event::SetIdOffsets { .. } => "SetIdOffsets,,,,,,,".to_string(),
+                Event::SubmitBatch { orders } if orders.is_empty() => {
+                    "SubmitBatch,,,,,,,".to_string()
+                }
+                Event::SubmitMarketFok { side, quantity } => {
+                    format!("SubmitMarketFok,{side},,,{quantity},,,")
+                }
+                Event::SubmitBatch { orders } => orders
+                    .iter()
+                    .map(|(side, price, quantity, tif)| {
+                        format!("SubmitLimit,{side},{price},,{quantity},{tif},,")
+                    })
+                    .collect::<Vec<_>>()
+                    .join(&format!("\n{index},")),
+            };
+            writeln!(w, "{index},{row}")?;
+        }
+        w.flush()
+    }
+}
+
+/// Compact, comma-free rendering of a trail method (`fixed:200`, `pct:0.05`, `atr:2x14`).
+#[cfg(feature = "event-log")]
+fn trail_label(method: &TrailMethod) -> String {
+    match method {
+        TrailMethod::Fixed(offset) => format!("fixed:{offset}"),
+        TrailMethod::Percentage(pct) => format!("pct:{pct}"),
+        TrailMethod::Atr { multiplier, period } => format!("atr:{multiplier}x{period}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Exchange, Price, Side, TimeInForce};
+
+    fn csv_lines(exchange: &Exchange) -> Vec<String> {
+        let mut buf = Vec::new();
+        exchange.trades_to_csv(&mut buf).unwrap();
+        String::from_utf8(buf)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn empty_exchange_emits_header_only() {
+        let lines = csv_lines(&Exchange::new());
+        assert_eq!(
+            lines,
+            vec!["trade_id,timestamp,price,quantity,aggressor_side,maker_order_id,taker_order_id"]
+        );
+    }
+
+    #[test]
+    fn trade_rows_render_exactly() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(101_50), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(102_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(102_00), 150, TimeInForce::GTC);
+
+        let lines = csv_lines(&exchange);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], "1,4,$101.50,100,BUY,1,3");
+        assert_eq!(lines[2], "2,5,$102.00,50,BUY,2,3");
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn event_rows_render_exactly() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(101_50), 100, TimeInForce::GTC);
+        exchange.submit_stop_market(Side::Sell, Price(99_00), 10);
+        exchange.cancel(crate::OrderId(1));
+
+        let mut buf = Vec::new();
+        exchange.events_to_csv(&mut buf).unwrap();
+        let csv = String::from_utf8(buf).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "index,event,side,price,stop_price,quantity,time_in_force,order_id,trail"
+        );
+        assert_eq!(lines[1], "0,SubmitLimit,SELL,$101.50,,100,GTC,,");
+        assert_eq!(lines[2], "1,SubmitStopMarket,SELL,,$99.00,10,,,");
+        assert_eq!(lines[3], "2,Cancel,,,,,,1,");
+        assert!(lines.iter().all(|l| l.split(',').count() == 9));
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn batch_events_render_one_row_per_order() {
+        let mut exchange = Exchange::new();
+        exchange.submit_batch(&[
+            (Side::Sell, Price(101_00), 10, TimeInForce::GTC),
+            (Side::Buy, Price(99_00), 20, TimeInForce::IOC),
+        ]);
+        exchange.submit_batch(&[]);
+
+        let mut buf = Vec::new();
+        exchange.events_to_csv(&mut buf).unwrap();
+        let csv = String::from_utf8(buf).unwrap();
+        let lines: Vec<_> = csv.lines().skip(1).collect();
+        assert_eq!(
+            lines,
+            [
+                "0,SubmitLimit,SELL,$101.00,,10,GTC,,",
+                "0,SubmitLimit,BUY,$99.00,,20,IOC,,",
+                "1,SubmitBatch,,,,,,,",
+            ]
+        );
+    }
+}
@@ -3,7 +3,19 @@
 //! This module intentionally prioritizes stability and predictable behavior
 //! over parameter-rich model fitting. It provides a robust one-step-ahead
 //! volatility estimate for qtrade integration, with deterministic fallbacks
-//! on sparse or degenerate inputs.
+//! on sparse or degenerate inputs. A RiskMetrics-style EWMA estimator is
+//! provided as a cheaper, parameter-free alternative, and [`garch_fit`]
+//! exposes fitted GARCH(1,1) parameters for inspection and multi-step
+//! forecasts via [`garch_term_structure`].
+
+/// Number of leading observations used to seed the EWMA variance.
+const EWMA_SEED_WINDOW: usize = 10;
+
+/// Upper bound on `alpha + beta` explored by [`garch_fit`]'s refinement.
+const GARCH_FIT_MAX_PERSISTENCE: f64 = 1.2;
+
+/// Iteration cap for [`garch_fit`]'s pattern search.
+const GARCH_FIT_MAX_ITERATIONS: usize = 2_000;
 
 /// One-step-ahead volatility forecast from a GARCH(p, q)-style recursion.
 ///
@@ -94,6 +106,337 @@ pub fn garch_forecast(returns: &[f64], p: usize, q: usize, mean: &str) -> f64 {
     }
 }
 
+/// Fitted GARCH(1,1) parameters: `h_t = omega + alpha * eps_{t-1}^2 + beta * h_{t-1}`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GarchFit {
+    pub omega: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    /// Gaussian log-likelihood of the fit, including the `ln(2π)` constant
+    pub log_likelihood: f64,
+    /// `alpha + beta`; the fit is covariance-stationary only below 1
+    pub persistence: f64,
+    /// Conditional variance at the final observation
+    pub last_variance: f64,
+}
+
+impl GarchFit {
+    /// Whether `persistence < 1`. Non-stationary fits are returned as-is
+    /// so they can be inspected; their variance forecasts do not revert.
+    pub fn is_stationary(&self) -> bool {
+        self.persistence < 1.0
+    }
+
+    /// Long-run variance `omega / (1 - persistence)`, or `None` for a
+    /// non-stationary fit.
+    pub fn unconditional_variance(&self) -> Option<f64> {
+        self.is_stationary()
+            .then(|| self.omega / (1.0 - self.persistence))
+    }
+}
+
+/// One step of a [`garch_term_structure`] forecast.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GarchForecastStep {
+    /// Per-period conditional variance expected at this step
+    pub variance: f64,
+    /// `sqrt(variance * periods_per_year)`
+    pub annualized_vol: f64,
+}
+
+/// Fit GARCH(1,1) to zero-mean returns by Gaussian maximum likelihood.
+///
+/// Behavior:
+/// - A deterministic grid with variance targeting picks the starting point,
+///   then a pattern search refines `omega`, `alpha` and `beta` freely, so
+///   the result may be non-stationary (see [`GarchFit::is_stationary`]).
+/// - The variance recursion is seeded with the sample variance.
+/// - Returns `None` for fewer than 2 returns, non-finite inputs, or a
+///   series with no variance.
+pub fn garch_fit(returns: &[f64]) -> Option<GarchFit> {
+    if returns.len() < 2 || returns.iter().any(|r| !r.is_finite()) {
+        return None;
+    }
+    let var0 = sample_variance(returns)?;
+    if var0 <= 0.0 {
+        return None;
+    }
+
+    // Negative log-likelihood over (ln omega, alpha, beta).
+    let cost = |x: &[f64; 3]| -> f64 {
+        let [ln_omega, alpha, beta] = *x;
+        if alpha < 0.0 || beta < 0.0 || alpha + beta > GARCH_FIT_MAX_PERSISTENCE {
+            return f64::INFINITY;
+        }
+        gjr_log_likelihood(returns, var0, ln_omega.exp(), alpha, 0.0, beta)
+            .map_or(f64::INFINITY, |(ll, _)| -ll)
+    };
+
+    let mut best = [var0.ln(), 0.0, 0.0];
+    let mut best_cost = f64::INFINITY;
+    for ai in 0..=15 {
+        let alpha = ai as f64 * 0.02;
+        for bi in 0..=24 {
+            let beta = 0.50 + bi as f64 * 0.02;
+            let persistence = alpha + beta;
+            if persistence >= 0.999 {
+                continue;
+            }
+            let x = [((1.0 - persistence) * var0).ln(), alpha, beta];
+            let c = cost(&x);
+            if c < best_cost {
+                best = x;
+                best_cost = c;
+            }
+        }
+    }
+    if !best_cost.is_finite() {
+        return None;
+    }
+
+    // Hooke-Jeeves style coordinate search with step halving.
+    let mut steps = [0.5, 0.01, 0.01];
+    for _ in 0..GARCH_FIT_MAX_ITERATIONS {
+        let mut improved = false;
+        for i in 0..3 {
+            for direction in [1.0, -1.0] {
+                let mut candidate = best;
+                candidate[i] += direction * steps[i];
+                let c = cost(&candidate);
+                if c < best_cost {
+                    best = candidate;
+                    best_cost = c;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+        if !improved {
+            if steps[1] < 1e-6 {
+                break;
+            }
+            steps.iter_mut().for_each(|s| *s *= 0.5);
+        }
+    }
+
+    let [ln_omega, alpha, beta] = best;
+    let omega = ln_omega.exp();
+    let (_, last_variance) = gjr_log_likelihood(returns, var0, omega, alpha, 0.0, beta)?;
+    let n = returns.len() as f64;
+    Some(GarchFit {
+        omega,
+        alpha,
+        beta,
+        log_likelihood: -best_cost - 0.5 * n * (2.0 * std::f64::consts::PI).ln(),
+        persistence: alpha + beta,
+        last_variance,
+    })
+}
+
+/// Conditional variance term structure for steps `1..=horizon` from a
+/// [`garch_fit`].
+///
+/// Step 1 is `omega + alpha * eps_T^2 + beta * h_T`; later steps follow
+/// `h_{k+1} = omega + (alpha + beta) * h_k`, which reverts geometrically
+/// toward [`GarchFit::unconditional_variance`] for a stationary fit (and
+/// drifts away from it otherwise).
+///
+/// Behavior:
+/// - If the fit fails (see [`garch_fit`]), every step falls back to the
+///   sample variance, like [`garch_gjr_forecast`].
+/// - Empty for `horizon == 0`.
+pub fn garch_term_structure(
+    returns: &[f64],
+    horizon: usize,
+    periods_per_year: f64,
+) -> Vec<GarchForecastStep> {
+    let step = |variance: f64| GarchForecastStep {
+        variance,
+        annualized_vol: (variance * periods_per_year).sqrt(),
+    };
+    if horizon == 0 {
+        return Vec::new();
+    }
+
+    let Some(fit) = garch_fit(returns) else {
+        let fallback = sample_variance(returns).unwrap_or(0.0).max(0.0);
+        return vec![step(fallback); horizon];
+    };
+
+    let last_eps = returns[returns.len() - 1];
+    let mut h =
+        (fit.omega + fit.alpha * last_eps * last_eps + fit.beta * fit.last_variance).max(1e-12);
+    let mut out = Vec::with_capacity(horizon);
+    out.push(step(h));
+    for _ in 1..horizon {
+        h = (fit.omega + fit.persistence * h).max(1e-12);
+        out.push(step(h));
+    }
+    out
+}
+
+/// Multi-step conditional variance forecast from a GJR-GARCH(1,1,1) fit.
+///
+/// The GJR term adds `gamma * eps^2` whenever the previous shock was
+/// negative, so downside moves raise next-period variance more than upside
+/// moves of the same size (the leverage effect).
+///
+/// Returns `horizon` per-period variances (not volatilities, not annualized),
+/// starting with the one-step-ahead forecast.
+///
+/// Behavior:
+/// - Parameters are fit by Gaussian maximum likelihood over a fixed,
+///   deterministic grid with variance targeting for `omega`.
+/// - Invalid/non-finite inputs, or a fit that finds no admissible
+///   parameters, fall back to the sample variance repeated `horizon` times.
+pub fn garch_gjr_forecast(returns: &[f64], horizon: usize) -> Vec<f64> {
+    if horizon == 0 {
+        return Vec::new();
+    }
+
+    let fallback = vec![sample_variance(returns).unwrap_or(0.0).max(0.0); horizon];
+
+    if returns.len() < 2 || returns.iter().any(|r| !r.is_finite()) {
+        return fallback;
+    }
+
+    let var0 = sample_variance(returns).unwrap_or(0.0).max(1e-12);
+    let Some(fit) = fit_gjr(returns, var0) else {
+        return fallback;
+    };
+
+    let last_eps = returns[returns.len() - 1];
+    let shock = if last_eps < 0.0 {
+        fit.alpha + fit.gamma
+    } else {
+        fit.alpha
+    };
+    let persistence = fit.alpha + 0.5 * fit.gamma + fit.beta;
+
+    let mut out = Vec::with_capacity(horizon);
+    let mut h = (fit.omega + shock * last_eps * last_eps + fit.beta * fit.last_h).max(1e-12);
+    out.push(h);
+    for _ in 1..horizon {
+        // E[I(eps < 0) * eps^2] = h / 2 under a symmetric innovation density.
+        h = (fit.omega + persistence * h).max(1e-12);
+        out.push(h);
+    }
+
+    if out.iter().all(|v| v.is_finite()) {
+        out
+    } else {
+        fallback
+    }
+}
+
+struct GjrFit {
+    omega: f64,
+    alpha: f64,
+    gamma: f64,
+    beta: f64,
+    /// Conditional variance at the final observation.
+    last_h: f64,
+}
+
+/// Coarse-grid Gaussian MLE for GJR-GARCH(1,1,1) on zero-mean residuals.
+///
+/// Returns `None` when no stationary candidate yields a finite likelihood.
+fn fit_gjr(eps: &[f64], var0: f64) -> Option<GjrFit> {
+    let mut best: Option<(f64, GjrFit)> = None;
+
+    for ai in 0..=10 {
+        let alpha = ai as f64 * 0.02;
+        for gi in 0..=10 {
+            let gamma = gi as f64 * 0.04;
+            for bi in 0..=24 {
+                let beta = 0.50 + bi as f64 * 0.02;
+                let persistence = alpha + 0.5 * gamma + beta;
+                if persistence >= 0.999 {
+                    continue;
+                }
+
+                let omega = (1.0 - persistence) * var0;
+                let Some((ll, last_h)) = gjr_log_likelihood(eps, var0, omega, alpha, gamma, beta)
+                else {
+                    continue;
+                };
+
+                if best.as_ref().is_none_or(|(b, _)| ll > *b) {
+                    best = Some((
+                        ll,
+                        GjrFit {
+                            omega,
+                            alpha,
+                            gamma,
+                            beta,
+                            last_h,
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    best.map(|(_, fit)| fit)
+}
+
+/// Gaussian log-likelihood (up to a constant) and the final conditional variance.
+fn gjr_log_likelihood(
+    eps: &[f64],
+    var0: f64,
+    omega: f64,
+    alpha: f64,
+    gamma: f64,
+    beta: f64,
+) -> Option<(f64, f64)> {
+    let mut h = var0;
+    let mut ll = 0.0;
+
+    for (t, e) in eps.iter().enumerate() {
+        if t > 0 {
+            let prev = eps[t - 1];
+            let shock = if prev < 0.0 { alpha + gamma } else { alpha };
+            h = (omega + shock * prev * prev + beta * h).max(1e-12);
+        }
+        ll -= 0.5 * (h.ln() + e * e / h);
+    }
+
+    if ll.is_finite() { Some((ll, h)) } else { None }
+}
+
+/// RiskMetrics-style EWMA conditional volatility path.
+///
+/// Uses `var_t = lambda * var_{t-1} + (1 - lambda) * r_{t-1}^2`, seeded with the
+/// sample variance of the first few observations. Returns one annualized
+/// volatility per input return (`sqrt(var_t * periods_per_year)`), where
+/// element `t` only uses information up to `t - 1`.
+///
+/// Returns an empty vector when `lambda` is outside `(0, 1)`,
+/// `periods_per_year` is not positive, or any return is non-finite.
+pub fn ewma_volatility(returns: &[f64], lambda: f64, periods_per_year: f64) -> Vec<f64> {
+    let valid_params = lambda > 0.0 && lambda < 1.0 && periods_per_year.is_finite();
+    if !valid_params || periods_per_year <= 0.0 || returns.iter().any(|r| !r.is_finite()) {
+        return Vec::new();
+    }
+
+    let Some(first) = returns.first() else {
+        return Vec::new();
+    };
+
+    let seed_len = returns.len().min(EWMA_SEED_WINDOW);
+    let mut var = sample_variance(&returns[..seed_len]).unwrap_or(first * first);
+
+    let mut out = Vec::with_capacity(returns.len());
+    out.push((var * periods_per_year).sqrt());
+    for r in &returns[..returns.len() - 1] {
+        var = lambda * var + (1.0 - lambda) * r * r;
+        out.push((var * periods_per_year).sqrt());
+    }
+    out
+}
+
 fn sample_volatility(returns: &[f64]) -> f64 {
     sample_variance(returns).unwrap_or(0.0).max(0.0).sqrt()
 }
@@ -185,4 +528,192 @@ mod tests {
             "constant={constant}"
         );
     }
+
+    #[test]
+    fn gjr_forecast_has_requested_horizon() {
+        let returns = vec![0.01, -0.004, 0.008, -0.002, 0.005, -0.003, 0.004];
+        let v = garch_gjr_forecast(&returns, 5);
+        assert_eq!(v.len(), 5);
+        assert!(v.iter().all(|x| x.is_finite() && *x > 0.0));
+        assert!(garch_gjr_forecast(&returns, 0).is_empty());
+    }
+
+    #[test]
+    fn gjr_invalid_input_falls_back_to_sample_variance() {
+        let returns = vec![0.01, f64::NAN, 0.02];
+        let v = garch_gjr_forecast(&returns, 3);
+        assert_eq!(v.len(), 3);
+        assert!(v.iter().all(|x| *x == v[0]));
+
+        let short = garch_gjr_forecast(&[0.01], 2);
+        assert_eq!(short, vec![0.0, 0.0]);
+    }
+
+    /// Deterministic GJR-GARCH path with a strong leverage term, driven by a
+    /// fixed LCG + Box-Muller stream so the fixture is reproducible.
+    fn simulated_gjr_returns(n: usize) -> Vec<f64> {
+        let (omega, alpha, gamma, beta) = (2e-6, 0.02, 0.30, 0.80);
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut uniform = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        };
+
+        let mut h = omega / (1.0 - alpha - 0.5 * gamma - beta);
+        let mut prev = 0.0_f64;
+        let mut out = Vec::with_capacity(n);
+        for _ in 0..n {
+            let shock = if prev < 0.0 { alpha + gamma } else { alpha };
+            h = omega + shock * prev * prev + beta * h;
+            let z = (-2.0 * uniform().ln()).sqrt() * (2.0 * std::f64::consts::PI * uniform()).cos();
+            prev = h.sqrt() * z;
+            out.push(prev);
+        }
+        out
+    }
+
+    #[test]
+    fn gjr_exceeds_symmetric_after_negative_shocks() {
+        let mut returns = simulated_gjr_returns(500);
+        returns.extend_from_slice(&[-0.03, -0.04, -0.05]);
+
+        let gjr = garch_gjr_forecast(&returns, 1);
+        let symmetric = garch_forecast(&returns, 1, 1, "zero");
+        assert!(
+            gjr[0].sqrt() > symmetric,
+            "gjr={} symmetric={symmetric}",
+            gjr[0].sqrt()
+        );
+    }
+
+    #[test]
+    fn gjr_multi_step_reverts_toward_unconditional() {
+        let mut returns = simulated_gjr_returns(500);
+        returns.push(-0.05);
+
+        let v = garch_gjr_forecast(&returns, 50);
+        let long_run = sample_variance(&returns).unwrap();
+        assert!(v[0] > v[49]);
+        assert!((v[49] - long_run).abs() < (v[0] - long_run).abs());
+    }
+
+    /// GARCH(1,1) path from the same LCG + Box-Muller stream as the GJR fixture.
+    fn simulated_garch_returns(n: usize, omega: f64, alpha: f64, beta: f64) -> Vec<f64> {
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut uniform = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        };
+
+        let mut h = omega / (1.0 - alpha - beta);
+        let mut prev = 0.0_f64;
+        let mut out = Vec::with_capacity(n);
+        for _ in 0..n {
+            h = omega + alpha * prev * prev + beta * h;
+            let z = (-2.0 * uniform().ln()).sqrt() * (2.0 * std::f64::consts::PI * uniform()).cos();
+            prev = h.sqrt() * z;
+            out.push(prev);
+        }
+        out
+    }
+
+    #[test]
+    fn garch_fit_recovers_persistence() {
+        let returns = simulated_garch_returns(3_000, 2e-6, 0.08, 0.90);
+        let fit = garch_fit(&returns).unwrap();
+        assert!(
+            (fit.persistence - 0.98).abs() < 0.03,
+            "persistence={} alpha={} beta={}",
+            fit.persistence,
+            fit.alpha,
+            fit.beta
+        );
+        assert!((fit.alpha - 0.08).abs() < 0.04, "alpha={}", fit.alpha);
+        assert!(fit.is_stationary());
+        assert!(fit.omega > 0.0);
+        assert_eq!(fit.persistence, fit.alpha + fit.beta);
+        assert!(fit.log_likelihood.is_finite());
+    }
+
+    #[test]
+    fn term_structure_reverts_to_unconditional_variance() {
+        let mut returns = simulated_garch_returns(2_000, 2e-6, 0.08, 0.90);
+        returns.push(-0.05);
+        let fit = garch_fit(&returns).unwrap();
+        let long_run = fit.unconditional_variance().unwrap();
+
+        let path = garch_term_structure(&returns, 250, 252.0);
+        assert_eq!(path.len(), 250);
+        let one_step = fit.omega + fit.alpha * 0.05 * 0.05 + fit.beta * fit.last_variance;
+        assert!((path[0].variance - one_step).abs() < 1e-15);
+        assert!((path[0].annualized_vol - (one_step * 252.0).sqrt()).abs() < 1e-12);
+
+        // Shocked above the long-run level, so every step moves closer to it.
+        assert!(path[0].variance > long_run);
+        for w in path.windows(2) {
+            assert!(w[1].variance < w[0].variance);
+            assert!(w[1].variance > long_run);
+        }
+        assert!((path[249].variance - long_run) < 0.05 * (path[0].variance - long_run));
+    }
+
+    #[test]
+    fn term_structure_falls_back_without_a_fit() {
+        let path = garch_term_structure(&[0.01, f64::NAN, 0.02], 3, 252.0);
+        assert_eq!(path.len(), 3);
+        assert!(path.iter().all(|s| *s == path[0]));
+        assert!(garch_term_structure(&[0.01, 0.02], 0, 252.0).is_empty());
+    }
+
+    #[test]
+    fn garch_fit_rejects_degenerate_input() {
+        assert!(garch_fit(&[0.01]).is_none());
+        assert!(garch_fit(&[0.01, f64::NAN, 0.02]).is_none());
+        assert!(garch_fit(&[0.0; 10]).is_none());
+    }
+
+    #[test]
+    fn ewma_rejects_invalid_lambda() {
+        let returns = vec![0.01, -0.02, 0.005];
+        assert!(ewma_volatility(&returns, 0.0, 252.0).is_empty());
+        assert!(ewma_volatility(&returns, 1.0, 252.0).is_empty());
+        assert!(ewma_volatility(&returns, f64::NAN, 252.0).is_empty());
+        assert!(ewma_volatility(&[], 0.94, 252.0).is_empty());
+    }
+
+    #[test]
+    fn ewma_higher_lambda_is_smoother() {
+        let returns: Vec<f64> = (0..100)
+            .map(|i| {
+                if i % 7 == 0 {
+                    -0.03
+                } else {
+                    0.004 * ((i % 5) as f64 - 2.0)
+                }
+            })
+            .collect();
+
+        let roughness =
+            |path: &[f64]| -> f64 { path.windows(2).map(|w| (w[1] - w[0]).abs()).sum() };
+
+        let smooth = ewma_volatility(&returns, 0.97, 252.0);
+        let rough = ewma_volatility(&returns, 0.80, 252.0);
+        assert_eq!(smooth.len(), returns.len());
+        assert!(roughness(&smooth) < roughness(&rough));
+    }
+
+    #[test]
+    fn ewma_small_lambda_tracks_squared_returns() {
+        let returns = vec![0.01, -0.02, 0.005, 0.03, -0.015, 0.002];
+        let path = ewma_volatility(&returns, 1e-6, 1.0);
+        for t in 1..returns.len() {
+            let var = path[t] * path[t];
+            let sq = returns[t - 1] * returns[t - 1];
+            assert!((var - sq).abs() < 1e-8, "t={t} var={var} sq={sq}");
+        }
+    }
 }
@@ -1,12 +1,15 @@
 //! Technical analysis indicators.
 //!
-//! Drop-in replacements for TA-Lib's RSI, MACD, Bollinger Bands, and ATR.
+//! Drop-in replacements for TA-Lib's RSI, MACD, Bollinger Bands, ATR, ROC,
+//! MOM, and CCI.
 //! All functions use the same algorithms and conventions as TA-Lib so that
 //! outputs are numerically identical (within floating-point tolerance).
+//! Keltner and Donchian Channels, which TA-Lib lacks, follow the same
+//! conventions.
 //!
 //! # Conventions
 //!
-//! - Input slices are `&[f64]` (closing prices, or OHLC for ATR).
+//! - Input slices are `&[f64]` (closing prices, or OHLC for ATR and CCI).
 //! - Output `Vec<f64>` has the same length as input; elements within the
 //!   lookback period are filled with `f64::NAN`.
 //! - **Wilder's smoothing** (RSI, ATR): `alpha = 1/period`, NOT `2/(period+1)`.
@@ -14,7 +17,8 @@
 //!
 //! # References
 //!
-//! - TA-Lib source: `ta_RSI.c`, `ta_MACD.c`, `ta_BBANDS.c`, `ta_ATR.c`
+//! - TA-Lib source: `ta_RSI.c`, `ta_MACD.c`, `ta_BBANDS.c`, `ta_ATR.c`,
+//!   `ta_ROC.c`, `ta_MOM.c`, `ta_CCI.c`
 //!   <https://github.com/TA-Lib/ta-lib/tree/main/src/ta_func>
 
 // ---------------------------------------------------------------------------
@@ -284,6 +288,54 @@ pub fn bbands(
     (upper, middle, lower)
 }
 
+/// Bollinger Bands with the derived %B and bandwidth series.
+///
+/// Every series has the input's length, NaN within the lookback period.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BBandsResult {
+    pub upper: Vec<f64>,
+    pub middle: Vec<f64>,
+    pub lower: Vec<f64>,
+    /// `(close - lower) / (upper - lower)`: 0.5 at the middle band, above
+    /// 1.0 over the upper band, negative under the lower. NaN when the
+    /// bands collapse (zero deviation).
+    pub percent_b: Vec<f64>,
+    /// `(upper - lower) / middle`. NaN when the middle band is zero.
+    pub bandwidth: Vec<f64>,
+}
+
+/// [`bbands`] plus %B and bandwidth.
+///
+/// Arguments are the same as for [`bbands`].
+pub fn bbands_ext(close: &[f64], period: usize, num_std_up: f64, num_std_dn: f64) -> BBandsResult {
+    let (upper, middle, lower) = bbands(close, period, num_std_up, num_std_dn);
+    let n = close.len();
+
+    let mut percent_b = vec![f64::NAN; n];
+    let mut bandwidth = vec![f64::NAN; n];
+
+    for i in 0..n {
+        if middle[i].is_nan() {
+            continue;
+        }
+        let width = upper[i] - lower[i];
+        if width > 0.0 {
+            percent_b[i] = (close[i] - lower[i]) / width;
+        }
+        if middle[i] != 0.0 {
+            bandwidth[i] = width / middle[i];
+        }
+    }
+
+    BBandsResult {
+        upper,
+        middle,
+        lower,
+        percent_b,
+        bandwidth,
+    }
+}
+
 /// Average True Range (Wilder's smoothing of True Range).
 ///
 /// Matches TA-Lib `ta_ATR.c` behavior:
@@ -332,6 +384,157 @@ pub fn atr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Vec<f64>
     out
 }
 
+/// Rate of change: percentage change over `period` bars.
+///
+/// Matches TA-Lib `ta_ROC.c` behavior:
+/// - `((value / value[i - period]) - 1) * 100`.
+/// - First `period` elements are NaN.
+/// - A zero base value yields 0.0.
+pub fn roc(values: &[f64], period: usize) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if n <= period || period == 0 {
+        return out;
+    }
+
+    for i in period..n {
+        let base = values[i - period];
+        out[i] = if base != 0.0 {
+            (values[i] / base - 1.0) * 100.0
+        } else {
+            0.0
+        };
+    }
+    out
+}
+
+/// Momentum: absolute change over `period` bars.
+///
+/// Matches TA-Lib `ta_MOM.c` behavior:
+/// - `value - value[i - period]`.
+/// - First `period` elements are NaN.
+pub fn momentum(values: &[f64], period: usize) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if n <= period || period == 0 {
+        return out;
+    }
+
+    for i in period..n {
+        out[i] = values[i] - values[i - period];
+    }
+    out
+}
+
+/// Commodity Channel Index.
+///
+/// Matches TA-Lib `ta_CCI.c` behavior:
+/// - Typical price = (H + L + C) / 3.
+/// - CCI = (TP - SMA(TP)) / (0.015 * mean absolute deviation of TP).
+/// - First `period - 1` elements are NaN.
+/// - Zero mean deviation (flat typical price) yields 0.0.
+/// - All NaN if `high`, `low`, and `close` lengths differ.
+///
+/// # Arguments
+///
+/// * `high` — High prices.
+/// * `low` — Low prices.
+/// * `close` — Closing prices.
+/// * `period` — Lookback period (typically 14 or 20).
+pub fn cci(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Vec<f64> {
+    let n = high.len();
+    let mut out = vec![f64::NAN; n];
+    if n != low.len() || n != close.len() || n < period || period == 0 {
+        return out;
+    }
+
+    let typical: Vec<f64> = (0..n)
+        .map(|i| (high[i] + low[i] + close[i]) / 3.0)
+        .collect();
+    let mean = sma(&typical, period);
+
+    for i in (period - 1)..n {
+        let window = &typical[i + 1 - period..=i];
+        let mean_dev = window.iter().map(|tp| (tp - mean[i]).abs()).sum::<f64>() / period as f64;
+        out[i] = if mean_dev > 0.0 {
+            (typical[i] - mean[i]) / (0.015 * mean_dev)
+        } else {
+            0.0
+        };
+    }
+    out
+}
+
+/// Keltner Channels: EMA of close ± a multiple of ATR.
+///
+/// - Middle line = EMA(close, `period`), seeded with the SMA like MACD.
+/// - Upper/lower = middle ± `atr_mult` * ATR(`period`) (Wilder, see [`atr`]).
+/// - NaN until both the EMA and ATR are defined (first `period` elements).
+/// - All NaN if `high`, `low`, and `close` lengths differ.
+///
+/// Returns `(upper, middle, lower)`.
+pub fn keltner(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: usize,
+    atr_mult: f64,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let n = high.len();
+    let mut upper = vec![f64::NAN; n];
+    let mut middle = vec![f64::NAN; n];
+    let mut lower = vec![f64::NAN; n];
+    if n != low.len() || n != close.len() {
+        return (upper, middle, lower);
+    }
+
+    let center = ema(close, period);
+    let range = atr(high, low, close, period);
+    for i in 0..n {
+        if !center[i].is_nan() && !range[i].is_nan() {
+            middle[i] = center[i];
+            upper[i] = center[i] + atr_mult * range[i];
+            lower[i] = center[i] - atr_mult * range[i];
+        }
+    }
+
+    (upper, middle, lower)
+}
+
+/// Donchian Channels: rolling highest high and lowest low.
+///
+/// - Upper = max of `high` over the last `period` bars (including the current one).
+/// - Lower = min of `low` over the same window; middle = (upper + lower) / 2.
+/// - First `period - 1` elements are NaN.
+/// - All NaN if `high` and `low` lengths differ.
+///
+/// A close above the previous bar's upper channel is a breakout.
+///
+/// Returns `(upper, middle, lower)`.
+pub fn donchian(high: &[f64], low: &[f64], period: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let n = high.len();
+    let mut upper = vec![f64::NAN; n];
+    let mut middle = vec![f64::NAN; n];
+    let mut lower = vec![f64::NAN; n];
+    if n != low.len() || n < period || period == 0 {
+        return (upper, middle, lower);
+    }
+
+    for i in (period - 1)..n {
+        let start = i + 1 - period;
+        let hi = high[start..=i]
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let lo = low[start..=i].iter().copied().fold(f64::INFINITY, f64::min);
+        upper[i] = hi;
+        lower[i] = lo;
+        middle[i] = (hi + lo) / 2.0;
+    }
+
+    (upper, middle, lower)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -437,6 +640,47 @@ mod tests {
         assert!((lower[last] - 100.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn bbands_ext_percent_b_and_bandwidth() {
+        // Symmetric swings around 100 end on the middle band.
+        let swings = |amplitude: f64| -> Vec<f64> {
+            (0..18)
+                .map(|i| {
+                    if i % 2 == 0 {
+                        100.0 - amplitude
+                    } else {
+                        100.0 + amplitude
+                    }
+                })
+                .chain([100.0, 100.0])
+                .collect()
+        };
+        let calm = swings(1.0);
+        let last = calm.len() - 1;
+        let result = bbands_ext(&calm, 20, 2.0, 2.0);
+        assert!(result.percent_b[last - 1].is_nan());
+        assert!((result.middle[last] - 100.0).abs() < 1e-10);
+        assert!((result.percent_b[last] - 0.5).abs() < 1e-10);
+
+        let wide = bbands_ext(&swings(5.0), 20, 2.0, 2.0);
+        assert!(wide.bandwidth[last] > result.bandwidth[last]);
+
+        // A breakout closes above the upper band.
+        let mut breakout = calm.clone();
+        breakout[last] = 110.0;
+        let broken = bbands_ext(&breakout, 20, 2.0, 2.0);
+        assert!(broken.percent_b[last] > 1.0);
+        breakout[last] = 90.0;
+        assert!(bbands_ext(&breakout, 20, 2.0, 2.0).percent_b[last] < 0.0);
+    }
+
+    #[test]
+    fn bbands_ext_flat_bands_have_no_percent_b() {
+        let result = bbands_ext(&[100.0; 25], 20, 2.0, 2.0);
+        assert!(result.percent_b[24].is_nan());
+        assert_eq!(result.bandwidth[24], 0.0);
+    }
+
     #[test]
     fn atr_basic() {
         // Simple case: constant range
@@ -480,4 +724,78 @@ mod tests {
         let result = rsi(&short, 14);
         assert!(result.iter().all(|v| v.is_nan()));
     }
+
+    #[test]
+    fn keltner_wraps_ema_with_atr() {
+        let high = vec![102.0; 30];
+        let low = vec![98.0; 30];
+        let close = vec![100.0; 30];
+        let (upper, middle, lower) = keltner(&high, &low, &close, 10, 2.0);
+        assert!(upper[9].is_nan());
+        assert!((middle[29] - 100.0).abs() < 1e-10);
+        assert!((upper[29] - 108.0).abs() < 1e-10);
+        assert!((lower[29] - 92.0).abs() < 1e-10);
+
+        let (upper, _, _) = keltner(&high, &low[..29], &close, 10, 2.0);
+        assert!(upper.iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn donchian_tracks_rolling_extremes() {
+        let high = [10.0, 12.0, 11.0, 9.0, 13.0, 8.0, 16.0];
+        let low = [5.0, 6.0, 4.0, 7.0, 6.0, 5.0, 9.0];
+        let (upper, middle, lower) = donchian(&high, &low, 3);
+        assert!(upper[1].is_nan());
+        for i in 2..high.len() {
+            let max = high[i - 2..=i].iter().copied().fold(f64::MIN, f64::max);
+            assert_eq!(upper[i], max);
+        }
+        assert_eq!(lower[4], 4.0);
+        assert_eq!(middle[4], 8.5);
+
+        // Bars 4 and 6 clear the previous window's high.
+        let breakouts: Vec<usize> = (3..high.len())
+            .filter(|&i| high[i] > upper[i - 1])
+            .collect();
+        assert_eq!(breakouts, vec![4, 6]);
+
+        let (upper, _, _) = donchian(&high, &low[..6], 3);
+        assert!(upper.iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn roc_and_momentum_over_a_doubling() {
+        let values = [10.0, 20.0, 40.0, 80.0];
+        let rate = roc(&values, 1);
+        assert!(rate[0].is_nan());
+        assert!(rate[1..].iter().all(|&r| (r - 100.0).abs() < 1e-10));
+
+        let mom = momentum(&values, 2);
+        assert!(mom[1].is_nan());
+        assert_eq!(&mom[2..], &[30.0, 60.0]);
+
+        assert_eq!(roc(&[0.0, 5.0], 1)[1], 0.0);
+    }
+
+    #[test]
+    fn cci_swings_around_typical_mean() {
+        let close: Vec<f64> = (0..40)
+            .map(|i| 100.0 + 10.0 * (i as f64 * 0.4).sin())
+            .collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+        let result = cci(&high, &low, &close, 10);
+        assert!(result[8].is_nan());
+        assert!(!result[9].is_nan());
+        assert!(result[9..].iter().any(|&v| v > 100.0));
+        assert!(result[9..].iter().any(|&v| v < -100.0));
+
+        let flat = cci(&[101.0; 12], &[99.0; 12], &[100.0; 12], 10);
+        assert_eq!(flat[11], 0.0);
+        assert!(
+            cci(&high, &low[..39], &close, 10)
+                .iter()
+                .all(|v| v.is_nan())
+        );
+    }
 }
@@ -0,0 +1,90 @@
+//! Instrument metadata: tick size, lot size, currency and contract multiplier.
+
+use crate::{Price, Quantity, Symbol};
+
+/// Static description of a tradable instrument.
+///
+/// The default is a plain equity: one-cent ticks, single-share lots, USD,
+/// and a multiplier of 1. Derivatives set [`multiplier`](Self::multiplier)
+/// so that [`notional`](Self::notional) reflects contract value.
+///
+/// ```
+/// use nanobook::{InstrumentSpec, Price, Symbol};
+///
+/// // E-mini S&P 500: quarter-point ticks, $50 per index point.
+/// let es = InstrumentSpec {
+///     tick_size: 25,
+///     multiplier: 50,
+///     ..InstrumentSpec::default()
+/// };
+/// assert_eq!(es.notional(Price(4000_00), 2), 400_000_00);
+/// assert_eq!(es.currency, Symbol::new("USD"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InstrumentSpec {
+    /// Minimum price increment, in cents
+    pub tick_size: i64,
+    /// Minimum quantity increment
+    pub lot_size: Quantity,
+    /// Quote currency
+    pub currency: Symbol,
+    /// Currency value of one unit of price per contract (1 for equities)
+    pub multiplier: i64,
+}
+
+impl Default for InstrumentSpec {
+    fn default() -> Self {
+        Self {
+            tick_size: 1,
+            lot_size: 1,
+            currency: Symbol::new("USD"),
+            multiplier: 1,
+        }
+    }
+}
+
+impl InstrumentSpec {
+    /// Value of `quantity` contracts at `price`, in cents:
+    /// `price × quantity × multiplier`.
+    pub fn notional(&self, price: Price, quantity: Quantity) -> i64 {
+        price.0 * quantity as i64 * self.multiplier
+    }
+
+    /// Whether `price` lies on the tick grid.
+    pub fn is_valid_price(&self, price: Price) -> bool {
+        self.tick_size <= 1 || price.0 % self.tick_size == 0
+    }
+
+    /// Whether `quantity` is a whole number of lots.
+    pub fn is_valid_quantity(&self, quantity: Quantity) -> bool {
+        self.lot_size <= 1 || quantity % self.lot_size == 0
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::inconsistent_digit_grouping)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_a_plain_equity() {
+        let spec = InstrumentSpec::default();
+        assert_eq!(spec.notional(Price(150_00), 10), 1500_00);
+        assert!(spec.is_valid_price(Price(150_01)));
+        assert!(spec.is_valid_quantity(7));
+    }
+
+    #[test]
+    fn tick_and_lot_grid() {
+        let spec = InstrumentSpec {
+            tick_size: 5,
+            lot_size: 100,
+            ..InstrumentSpec::default()
+        };
+        assert!(spec.is_valid_price(Price(100_05)));
+        assert!(!spec.is_valid_price(Price(100_03)));
+        assert!(spec.is_valid_quantity(300));
+        assert!(!spec.is_valid_quantity(150));
+    }
+}
@@ -1,6 +1,10 @@
 //! NASDAQ ITCH 5.0 parser and nanobook Event conversion.
+//!
+//! [`write_itch`] goes the other way: it replays an event log through a fresh
+//! [`Exchange`] and writes the resulting market-data feed as ITCH frames.
 
-use crate::{Event, OrderId, Price, Side, TimeInForce};
+use crate::{Event, Exchange, OrderId, Price, Quantity, Side, TimeInForce, Trade};
+use std::collections::HashMap;
 use std::io::{Read, Result};
 
 /// ITCH 5.0 Message Types
@@ -289,3 +293,455 @@ pub fn itch_to_event(msg: ItchMessage) -> Option<(String, Event)> {
         _ => None,
     }
 }
+
+/// MoldUDP64 downstream header: session (10), sequence number (8), count (2).
+const MOLD_HEADER_LEN: usize = 20;
+
+/// Message count marking a MoldUDP64 end-of-session packet.
+const MOLD_END_OF_SESSION: u16 = 0xFFFF;
+
+/// A MoldUDP64 packet whose sequence number was not the one expected.
+///
+/// `received > expected` means messages `expected..received` were lost;
+/// `received < expected` means the packet arrived out of order or was
+/// retransmitted, and its already-covered messages were skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceGap {
+    pub expected: u64,
+    pub received: u64,
+}
+
+/// Unwraps MoldUDP64 downstream packets into ITCH messages.
+///
+/// Tracks the next expected sequence number across calls, so packets can be
+/// fed one UDP payload at a time. Sequence problems are recorded in
+/// [`gaps`](Self::gaps) instead of failing the decode.
+#[derive(Debug, Default)]
+pub struct MoldUdp64Decoder {
+    next_sequence: Option<u64>,
+    gaps: Vec<SequenceGap>,
+}
+
+impl MoldUdp64Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode one or more concatenated downstream packets.
+    ///
+    /// Heartbeats (count 0) and end-of-session packets carry no messages but
+    /// still advance gap detection.
+    pub fn decode(&mut self, payload: &[u8]) -> Result<Vec<ItchMessage>> {
+        let mut messages = Vec::new();
+        let mut rest = payload;
+        while !rest.is_empty() {
+            let used = self.decode_packet(rest, &mut messages)?;
+            rest = &rest[used..];
+        }
+        Ok(messages)
+    }
+
+    /// Sequence gaps and out-of-order packets seen so far.
+    pub fn gaps(&self) -> &[SequenceGap] {
+        &self.gaps
+    }
+
+    /// Sequence number of the next message expected, once a packet was seen.
+    pub fn next_sequence(&self) -> Option<u64> {
+        self.next_sequence
+    }
+
+    /// Decode the packet at the start of `data`; returns the bytes consumed.
+    fn decode_packet(&mut self, data: &[u8], messages: &mut Vec<ItchMessage>) -> Result<usize> {
+        if data.len() < MOLD_HEADER_LEN {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("MoldUDP64 header too short: {} bytes", data.len()),
+            ));
+        }
+        let sequence = u64::from_be_bytes(data[10..18].try_into().unwrap());
+        let count = u16::from_be_bytes(data[18..20].try_into().unwrap());
+        let count = if count == MOLD_END_OF_SESSION {
+            0
+        } else {
+            count
+        };
+
+        // Find the end of the message block before handing it to the parser.
+        let mut end = MOLD_HEADER_LEN;
+        for _ in 0..count {
+            let len = data
+                .get(end..end + 2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]) as usize);
+            match len {
+                Some(len) if end + 2 + len <= data.len() => end += 2 + len,
+                _ => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("MoldUDP64 packet {sequence} truncated ({count} messages)"),
+                    ));
+                }
+            }
+        }
+
+        let expected = self.next_sequence.unwrap_or(sequence);
+        if sequence != expected {
+            self.gaps.push(SequenceGap {
+                expected,
+                received: sequence,
+            });
+        }
+        let skip = expected.saturating_sub(sequence);
+        self.next_sequence = Some(expected.max(sequence + u64::from(count)));
+
+        let mut parser = ItchParser::new(&data[MOLD_HEADER_LEN..end]);
+        let mut index = 0;
+        while let Some(msg) = parser.next_message()? {
+            if index >= skip {
+                messages.push(msg);
+            }
+            index += 1;
+        }
+        Ok(end)
+    }
+}
+
+/// Decode MoldUDP64 downstream packets into nanobook events.
+///
+/// `payload` holds one or more concatenated packets (UDP payloads). Parsing
+/// stops at the first malformed packet. Use [`MoldUdp64Decoder`] directly to
+/// see sequence gaps, errors, and stock symbols.
+pub fn parse_moldudp64(payload: &[u8]) -> Vec<Event> {
+    let mut decoder = MoldUdp64Decoder::new();
+    let mut messages = Vec::new();
+    let mut rest = payload;
+    while !rest.is_empty() {
+        match decoder.decode_packet(rest, &mut messages) {
+            Ok(used) => rest = &rest[used..],
+            Err(_) => break,
+        }
+    }
+    messages
+        .into_iter()
+        .filter_map(itch_to_event)
+        .map(|(_, event)| event)
+        .collect()
+}
+
+/// Stock name used by [`write_itch`].
+pub const DEFAULT_STOCK: &str = "NANOBOOK";
+
+/// Encode one message as a length-prefixed ITCH 5.0 frame.
+///
+/// Covers the book messages the parser understands: Add Order (`A`), Order
+/// Executed (`E`), Order Executed With Price (`C`), Order Cancel (`X`), Order
+/// Delete (`D`), Order Replace (`U`) and Trade (`P`). Stock locate is written
+/// as 1 and tracking number as 0. Returns `None` for `StockDirectory` and
+/// `Other`, which the writer does not produce.
+pub fn encode_message(msg: &ItchMessage) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(52);
+    out.extend_from_slice(&[0, 0]); // length, patched below
+    match msg {
+        ItchMessage::AddOrder {
+            timestamp,
+            order_ref,
+            side,
+            shares,
+            stock,
+            price,
+        } => {
+            header(&mut out, b'A', *timestamp);
+            out.extend_from_slice(&order_ref.to_be_bytes());
+            out.push(side_byte(*side));
+            out.extend_from_slice(&shares.to_be_bytes());
+            out.extend_from_slice(&stock_bytes(stock));
+            out.extend_from_slice(&price.to_be_bytes());
+        }
+        ItchMessage::OrderExecuted {
+            timestamp,
+            order_ref,
+            shares,
+            match_number,
+        } => {
+            header(&mut out, b'E', *timestamp);
+            out.extend_from_slice(&order_ref.to_be_bytes());
+            out.extend_from_slice(&shares.to_be_bytes());
+            out.extend_from_slice(&match_number.to_be_bytes());
+        }
+        ItchMessage::OrderExecutedWithPrice {
+            timestamp,
+            order_ref,
+            shares,
+            match_number,
+            printable,
+            price,
+        } => {
+            header(&mut out, b'C', *timestamp);
+            out.extend_from_slice(&order_ref.to_be_bytes());
+            out.extend_from_slice(&shares.to_be_bytes());
+            out.extend_from_slice(&match_number.to_be_bytes());
+            out.push(if *printable { b'Y' } else { b'N' });
+            out.extend_from_slice(&price.to_be_bytes());
+        }
+        ItchMessage::OrderCancel {
+            timestamp,
+            order_ref,
+            shares,
+        } => {
+            header(&mut out, b'X', *timestamp);
+            out.extend_from_slice(&order_ref.to_be_bytes());
+            out.extend_from_slice(&shares.to_be_bytes());
+        }
+        ItchMessage::OrderDelete {
+            timestamp,
+            order_ref,
+        } => {
+            header(&mut out, b'D', *timestamp);
+            out.extend_from_slice(&order_ref.to_be_bytes());
+        }
+        ItchMessage::OrderReplace {
+            timestamp,
+            old_order_ref,
+            new_order_ref,
+            shares,
+            price,
+        } => {
+            header(&mut out, b'U', *timestamp);
+            out.extend_from_slice(&old_order_ref.to_be_bytes());
+            out.extend_from_slice(&new_order_ref.to_be_bytes());
+            out.extend_from_slice(&shares.to_be_bytes());
+            out.extend_from_slice(&price.to_be_bytes());
+        }
+        ItchMessage::Trade {
+            timestamp,
+            side,
+            shares,
+            stock,
+            price,
+            match_number,
+        } => {
+            header(&mut out, b'P', *timestamp);
+            out.extend_from_slice(&0u64.to_be_bytes()); // order reference (always 0)
+            out.push(side_byte(*side));
+            out.extend_from_slice(&shares.to_be_bytes());
+            out.extend_from_slice(&stock_bytes(stock));
+            out.extend_from_slice(&price.to_be_bytes());
+            out.extend_from_slice(&match_number.to_be_bytes());
+        }
+        ItchMessage::StockDirectory { .. } | ItchMessage::Other(_) => return None,
+    }
+    let len = (out.len() - 2) as u16;
+    out[..2].copy_from_slice(&len.to_be_bytes());
+    Some(out)
+}
+
+/// Message type, stock locate, tracking number, and 48-bit timestamp.
+fn header(out: &mut Vec<u8>, msg_type: u8, timestamp: u64) {
+    out.push(msg_type);
+    out.extend_from_slice(&1u16.to_be_bytes());
+    out.extend_from_slice(&0u16.to_be_bytes());
+    out.extend_from_slice(&timestamp.to_be_bytes()[2..8]);
+}
+
+fn side_byte(side: Side) -> u8 {
+    match side {
+        Side::Buy => b'B',
+        Side::Sell => b'S',
+    }
+}
+
+fn stock_bytes(stock: &str) -> [u8; 8] {
+    let mut out = [b' '; 8];
+    for (dst, src) in out.iter_mut().zip(stock.bytes()) {
+        *dst = src;
+    }
+    out
+}
+
+/// Nanobook cents to ITCH price (4 implied decimals), saturating at the
+/// `u32` range.
+fn itch_price(price: Price) -> u32 {
+    u32::try_from(price.0.saturating_mul(100).max(0)).unwrap_or(u32::MAX)
+}
+
+fn itch_shares(quantity: Quantity) -> u32 {
+    u32::try_from(quantity).unwrap_or(u32::MAX)
+}
+
+/// Serialize an event log as an ITCH 5.0 feed for [`DEFAULT_STOCK`].
+///
+/// See [`write_itch_for`].
+pub fn write_itch(events: &[Event]) -> Vec<u8> {
+    write_itch_for(DEFAULT_STOCK, events)
+}
+
+/// Serialize an event log as the ITCH 5.0 feed a venue would publish for it.
+///
+/// The events are replayed through a fresh [`Exchange`]; order references
+/// are nanobook order ids and match numbers are trade ids. After each event:
+///
+/// - every fill against a displayed order becomes Order Executed (`E`) (for
+///   both orders of an auction fill), and a fill against an order that never
+///   rested visibly (e.g. a triggered stop hit within the same cascade)
+///   becomes a Trade (`P`);
+/// - an order that ends up resting becomes Add Order (`A`) for its resting
+///   quantity (IOC, FOK and market remainders never display);
+/// - a cancelled displayed order becomes Order Cancel (`X`) for its full
+///   remaining size;
+/// - a modify that rests without trading becomes Order Replace (`U`); one that
+///   trades becomes a cancel of the old order followed by the steps above.
+///
+/// For limit orders that rest without crossing, cancels, and non-crossing
+/// modifies, [`ItchParser`] and [`itch_to_event`] reproduce the input events.
+/// Prices and sizes outside the ITCH `u32` fields saturate.
+pub fn write_itch_for(stock: &str, events: &[Event]) -> Vec<u8> {
+    let mut exchange = Exchange::new();
+    // Displayed orders and the size the feed currently shows for them.
+    let mut displayed: HashMap<OrderId, Quantity> = HashMap::new();
+    let mut messages = Vec::new();
+
+    for event in events {
+        let first_new_id = exchange.book().peek_next_order_id().0;
+        let first_trade = exchange.trades().len();
+        exchange.apply(event);
+        let timestamp = exchange.book().peek_next_timestamp();
+        let next_id = exchange.book().peek_next_order_id().0;
+
+        // A resting, untouched replacement is a plain Order Replace.
+        if let Event::Modify {
+            order_id,
+            new_price,
+            new_quantity,
+        } = event
+            && displayed.contains_key(order_id)
+            && first_new_id + 1 == next_id
+            && exchange.trades().len() == first_trade
+            && exchange
+                .get_order(OrderId(first_new_id))
+                .is_some_and(|o| o.is_active() && o.remaining_quantity == *new_quantity)
+        {
+            displayed.remove(order_id);
+            displayed.insert(OrderId(first_new_id), *new_quantity);
+            messages.push(ItchMessage::OrderReplace {
+                timestamp,
+                old_order_ref: order_id.0,
+                new_order_ref: first_new_id,
+                shares: itch_shares(*new_quantity),
+                price: itch_price(*new_price),
+            });
+            continue;
+        }
+
+        // An in-place size reduction is a partial Order Cancel.
+        if let Event::ReduceQuantity {
+            order_id,
+            new_quantity,
+        } = event
+            && let Some(shown) = displayed.get_mut(order_id)
+            && exchange
+                .get_order(*order_id)
+                .is_some_and(|o| o.remaining_quantity == *new_quantity && *shown > *new_quantity)
+        {
+            messages.push(ItchMessage::OrderCancel {
+                timestamp,
+                order_ref: order_id.0,
+                shares: itch_shares(*shown - *new_quantity),
+            });
+            *shown = *new_quantity;
+        }
+
+        // A partial cancel that leaves the order resting shrinks it the same way.
+        if let Event::CancelPartial { order_id, .. } = event
+            && let Some(shown) = displayed.get_mut(order_id)
+            && let Some(order) = exchange.get_order(*order_id).filter(|o| o.is_active())
+            && *shown > order.remaining_quantity
+        {
+            messages.push(ItchMessage::OrderCancel {
+                timestamp,
+                order_ref: order_id.0,
+                shares: itch_shares(*shown - order.remaining_quantity),
+            });
+            *shown = order.remaining_quantity;
+        }
+
+        // Displayed orders taken off the book by a cancel or modify.
+        if let Event::Cancel { order_id }
+        | Event::CancelPartial { order_id, .. }
+        | Event::Modify { order_id, .. } = event
+            && !exchange.get_order(*order_id).is_some_and(|o| o.is_active())
+            && let Some(shares) = displayed.remove(order_id)
+        {
+            messages.push(ItchMessage::OrderCancel {
+                timestamp,
+                order_ref: order_id.0,
+                shares: itch_shares(shares),
+            });
+        }
+
+        for trade in &exchange.trades()[first_trade..] {
+            messages.push(execution(stock, trade, &mut displayed));
+            // In an auction uncross both sides were resting and displayed.
+            if let Some(shown) = displayed.get_mut(&trade.aggressor_order_id) {
+                *shown = shown.saturating_sub(trade.quantity);
+                if *shown == 0 {
+                    displayed.remove(&trade.aggressor_order_id);
+                }
+                messages.push(ItchMessage::OrderExecuted {
+                    timestamp: trade.timestamp,
+                    order_ref: trade.aggressor_order_id.0,
+                    shares: itch_shares(trade.quantity),
+                    match_number: trade.id.0,
+                });
+            }
+        }
+
+        for id in first_new_id..next_id {
+            if let Some(order) = exchange.get_order(OrderId(id))
+                && order.is_active()
+            {
+                displayed.insert(order.id, order.remaining_quantity);
+                messages.push(ItchMessage::AddOrder {
+                    timestamp: order.timestamp,
+                    order_ref: id,
+                    side: order.side,
+                    shares: itch_shares(order.remaining_quantity),
+                    stock: stock.to_string(),
+                    price: itch_price(order.price),
+                });
+            }
+        }
+    }
+
+    messages
+        .iter()
+        .filter_map(encode_message)
+        .flatten()
+        .collect()
+}
+
+fn execution(
+    stock: &str,
+    trade: &Trade,
+    displayed: &mut HashMap<OrderId, Quantity>,
+) -> ItchMessage {
+    let Some(shown) = displayed.get_mut(&trade.passive_order_id) else {
+        return ItchMessage::Trade {
+            timestamp: trade.timestamp,
+            side: trade.passive_side(),
+            shares: itch_shares(trade.quantity),
+            stock: stock.to_string(),
+            price: itch_price(trade.price),
+            match_number: trade.id.0,
+        };
+    };
+    *shown = shown.saturating_sub(trade.quantity);
+    if *shown == 0 {
+        displayed.remove(&trade.passive_order_id);
+    }
+    ItchMessage::OrderExecuted {
+        timestamp: trade.timestamp,
+        order_ref: trade.passive_order_id.0,
+        shares: itch_shares(trade.quantity),
+        match_number: trade.id.0,
+    }
+}
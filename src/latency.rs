@@ -0,0 +1,267 @@
+//! Arrival-time sequencing for latency-aware simulation.
+//!
+//! [`Exchange::submit_limit_delayed`] queues a limit order with the
+//! simulated time at which it reaches the matching engine instead of
+//! matching it immediately. [`Exchange::advance_to`] moves the simulation
+//! clock forward and processes every queued order that has arrived, earliest
+//! arrival first (ties in submission order). Agents can therefore submit in
+//! any wall-clock order and still be matched deterministically in arrival
+//! order.
+//!
+//! ```
+//! use nanobook::{Exchange, Price, Side, TimeInForce};
+//!
+//! let mut exchange = Exchange::new();
+//! exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+//!
+//! // The slow agent calls first, but the fast agent's order arrives first.
+//! exchange.submit_limit_delayed(Side::Buy, Price(100_00), 100, TimeInForce::IOC, 250);
+//! exchange.submit_limit_delayed(Side::Buy, Price(100_00), 100, TimeInForce::IOC, 120);
+//!
+//! let results = exchange.advance_to(300);
+//! assert_eq!(results[0].filled_quantity, 100); // arrived at 120
+//! assert_eq!(results[1].filled_quantity, 0); // arrived at 250, book empty
+//! ```
+//!
+//! The simulation clock is separate from the book's logical timestamps,
+//! which keep counting submissions as usual.
+
+use std::cmp::{Ordering, Reverse};
+
+#[cfg(feature = "event-log")]
+use crate::event::Event;
+use crate::{Exchange, Price, Quantity, Side, SubmitResult, TimeInForce, Timestamp};
+
+/// A limit order waiting to reach the matching engine.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DelayedOrder {
+    /// Simulated time at which the order arrives
+    pub arrival: Timestamp,
+    /// Submission sequence number (breaks arrival-time ties)
+    pub sequence: u64,
+    pub side: Side,
+    pub price: Price,
+    pub quantity: Quantity,
+    pub time_in_force: TimeInForce,
+}
+
+/// Ordered by `(arrival, sequence)`: the earliest arrival is the least.
+impl Ord for DelayedOrder {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.arrival, self.sequence).cmp(&(other.arrival, other.sequence))
+    }
+}
+
+impl PartialOrd for DelayedOrder {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Exchange {
+    /// Queue a limit order that reaches the matching engine at simulated
+    /// time `arrival`.
+    ///
+    /// Nothing matches until [`advance_to`](Self::advance_to) reaches
+    /// `arrival`. An order whose arrival is already in the past is processed
+    /// by the next `advance_to` call, in arrival order with the others.
+    pub fn submit_limit_delayed(
+        &mut self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        tif: TimeInForce,
+        arrival: Timestamp,
+    ) {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SubmitLimitDelayed {
+            side,
+            price,
+            quantity,
+            time_in_force: tif,
+            arrival,
+        });
+
+        self.submit_limit_delayed_internal(side, price, quantity, tif, arrival);
+    }
+
+    /// Internal: queue a delayed order without recording an event.
+    pub(crate) fn submit_limit_delayed_internal(
+        &mut self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        tif: TimeInForce,
+        arrival: Timestamp,
+    ) {
+        let sequence = self.delayed_sequence;
+        self.delayed_sequence += 1;
+        self.delayed.push(Reverse(DelayedOrder {
+            arrival,
+            sequence,
+            side,
+            price,
+            quantity,
+            time_in_force: tif,
+        }));
+    }
+
+    /// Advance the simulation clock to `timestamp` and process every queued
+    /// order with `arrival <= timestamp`, earliest arrival first.
+    ///
+    /// Returns one [`SubmitResult`] per processed order, in processing
+    /// order. Stop orders trigger as they would after an immediate submit.
+    /// The clock never moves backwards.
+    pub fn advance_to(&mut self, timestamp: Timestamp) -> Vec<SubmitResult> {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::AdvanceTo { timestamp });
+
+        self.advance_to_internal(timestamp)
+    }
+
+    /// Internal: advance the clock without recording an event.
+    pub(crate) fn advance_to_internal(&mut self, timestamp: Timestamp) -> Vec<SubmitResult> {
+        self.sim_time = self.sim_time.max(timestamp);
+        let mut results = Vec::new();
+        while self
+            .delayed
+            .peek()
+            .is_some_and(|next| next.0.arrival <= self.sim_time)
+        {
+            let order = self.delayed.pop().expect("invariant: peeked order").0;
+            let mut result = self.submit_limit_internal(
+                order.side,
+                order.price,
+                order.quantity,
+                order.time_in_force,
+            );
+            if let Some(trade) = result.trades.last() {
+                self.last_trade_price = Some(trade.price);
+                result.cascade_truncated = self.process_trade_triggers();
+            }
+            results.push(result);
+        }
+        results
+    }
+
+    /// Current simulation clock (the latest [`advance_to`](Self::advance_to)
+    /// timestamp, 0 initially).
+    pub fn sim_time(&self) -> Timestamp {
+        self.sim_time
+    }
+
+    /// Number of delayed orders that have not arrived yet.
+    pub fn pending_delayed_count(&self) -> usize {
+        self.delayed.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swapped_arrivals_match_in_arrival_order() {
+        let run = |first_arrival, second_arrival| {
+            let mut exchange = Exchange::new();
+            exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+            exchange.submit_limit_delayed(
+                Side::Buy,
+                Price(100_00),
+                100,
+                TimeInForce::GTC,
+                first_arrival,
+            );
+            exchange.submit_limit_delayed(
+                Side::Buy,
+                Price(101_00),
+                100,
+                TimeInForce::GTC,
+                second_arrival,
+            );
+            assert_eq!(exchange.pending_delayed_count(), 2);
+            assert!(exchange.trades().is_empty());
+
+            let results = exchange.advance_to(1_000);
+            assert_eq!(exchange.pending_delayed_count(), 0);
+            assert_eq!(exchange.trades().len(), 1);
+            (results, exchange)
+        };
+
+        // Second call arrives first: it takes the liquidity.
+        let (results, exchange) = run(500, 100);
+        assert_eq!(results[0].filled_quantity, 100);
+        assert_eq!(exchange.trades()[0].aggressor_order_id, results[0].order_id);
+        assert_eq!(exchange.best_bid(), Some(Price(100_00)));
+
+        // Same orders, arrivals swapped back: the first call wins instead.
+        let (results, exchange) = run(100, 500);
+        assert_eq!(results[0].filled_quantity, 100);
+        assert_eq!(exchange.best_bid(), Some(Price(101_00)));
+    }
+
+    #[test]
+    fn orders_wait_for_the_clock() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit_delayed(Side::Buy, Price(99_00), 10, TimeInForce::GTC, 50);
+        exchange.submit_limit_delayed(Side::Buy, Price(98_00), 10, TimeInForce::GTC, 50);
+        exchange.submit_limit_delayed(Side::Buy, Price(97_00), 10, TimeInForce::GTC, 80);
+
+        assert!(exchange.advance_to(49).is_empty());
+        assert_eq!(exchange.best_bid(), None);
+
+        // Equal arrivals keep submission order.
+        let results = exchange.advance_to(50);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].order_id.0 < results[1].order_id.0);
+        assert_eq!(
+            exchange.get_order(results[0].order_id).unwrap().price,
+            Price(99_00)
+        );
+
+        // The clock never rewinds; a late arrival goes out with the next advance.
+        exchange.advance_to(10);
+        assert_eq!(exchange.sim_time(), 50);
+        exchange.submit_limit_delayed(Side::Buy, Price(96_00), 10, TimeInForce::GTC, 20);
+        assert_eq!(exchange.advance_to(50).len(), 1);
+        assert_eq!(exchange.pending_delayed_count(), 1);
+    }
+
+    #[test]
+    fn arrival_reports_truncated_stop_cascade() {
+        let mut exchange = Exchange::new();
+        exchange.set_max_cascade_depth(3);
+        for i in 0..=10 {
+            exchange.submit_limit(Side::Sell, Price(100_00 + i * 100), 10, TimeInForce::GTC);
+        }
+        for i in 0..10 {
+            exchange.submit_stop_market(Side::Buy, Price(100_00 + i * 100), 10);
+        }
+        exchange.submit_limit_delayed(Side::Buy, Price(100_00), 10, TimeInForce::GTC, 5);
+
+        let results = exchange.advance_to(5);
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].cascade_truncated);
+        assert_eq!(exchange.truncated_cascades(), 1);
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn replay_reproduces_delayed_matching() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_stop_market(Side::Buy, Price(100_00), 20);
+        exchange.submit_limit(Side::Sell, Price(102_00), 50, TimeInForce::GTC);
+        exchange.submit_limit_delayed(Side::Buy, Price(100_00), 60, TimeInForce::GTC, 30);
+        exchange.submit_limit_delayed(Side::Buy, Price(100_00), 60, TimeInForce::GTC, 10);
+        exchange.advance_to(20);
+        exchange.advance_to(40);
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.trades(), exchange.trades());
+        assert_eq!(replayed.full_book(), exchange.full_book());
+        assert_eq!(replayed.sim_time(), 40);
+    }
+}
@@ -12,6 +12,7 @@ use crate::{OrderId, Price, Quantity};
 /// Orders are processed FIFO (first-in-first-out) for time priority.
 /// The level tracks total quantity for efficient depth queries.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Level {
     /// The price for all orders in this level
     price: Price,
@@ -21,6 +22,10 @@ pub struct Level {
     total_quantity: Quantity,
     /// Number of tombstones (cancelled orders still in the queue)
     tombstone_count: usize,
+    /// Entries dequeued from the front since creation, so positions handed
+    /// out at insert time stay valid after fills pop the queue.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) head: usize,
 }
 
 impl Level {
@@ -31,6 +36,7 @@ impl Level {
             orders: VecDeque::new(),
             total_quantity: 0,
             tombstone_count: 0,
+            head: 0,
         }
     }
 
@@ -71,6 +77,7 @@ impl Level {
             if id.0 == 0 {
                 // It's a tombstone
                 self.orders.pop_front();
+                self.head += 1;
                 self.tombstone_count -= 1;
             } else {
                 return Some(id);
@@ -96,6 +103,7 @@ impl Level {
     /// Returns `None` if the level is empty.
     pub fn pop_front(&mut self, quantity: Quantity) -> Option<OrderId> {
         while let Some(id) = self.orders.pop_front() {
+            self.head += 1;
             if id.0 == 0 {
                 self.tombstone_count -= 1;
                 continue;
@@ -108,10 +116,31 @@ impl Level {
 
     /// Mark an order as a tombstone (O(1) cancellation).
     ///
-    /// The caller provides the index into the VecDeque (tracked in OrderBook's
-    /// HashMap). The order's quantity is subtracted from the level total.
+    /// The caller provides the position returned when the order was inserted
+    /// (tracked in OrderBook's HashMap); entries already dequeued from the
+    /// front are accounted for. The order's quantity is subtracted from the
+    /// level total.
     pub fn mark_tombstone(&mut self, index: usize, quantity: Quantity) {
-        if let Some(id_ref) = self.orders.get_mut(index) {
+        if let Some(slot) = index.checked_sub(self.head) {
+            self.tombstone_slot(slot, quantity);
+        }
+    }
+
+    /// Tombstone `order_id`, using `index` as a hint.
+    ///
+    /// Falls back to a linear scan when the hint is stale (a position kept
+    /// across [`compact`](Self::compact) without being rebased).
+    pub(crate) fn tombstone_order(&mut self, index: usize, order_id: OrderId, quantity: Quantity) {
+        let hinted = index
+            .checked_sub(self.head)
+            .filter(|&slot| self.orders.get(slot) == Some(&order_id));
+        if let Some(slot) = hinted.or_else(|| self.orders.iter().position(|&id| id == order_id)) {
+            self.tombstone_slot(slot, quantity);
+        }
+    }
+
+    fn tombstone_slot(&mut self, slot: usize, quantity: Quantity) {
+        if let Some(id_ref) = self.orders.get_mut(slot) {
             if id_ref.0 != 0 {
                 id_ref.0 = 0; // Set to tombstone ID
                 self.total_quantity = self.total_quantity.saturating_sub(quantity);
@@ -138,6 +167,9 @@ impl Level {
     }
 
     /// Remove all tombstones from the queue.
+    ///
+    /// Live orders move to new positions (`head + slot`), so callers holding
+    /// insert-time positions must rebase them, as `OrderBook::compact` does.
     pub fn compact(&mut self) {
         if self.tombstone_count == 0 {
             return;
@@ -153,6 +185,16 @@ impl Level {
         self.total_quantity = self.total_quantity.saturating_sub(amount);
     }
 
+    /// Returns the live order at the front of the queue (the oldest).
+    pub fn oldest(&self) -> Option<OrderId> {
+        self.orders.iter().copied().find(|id| id.0 != 0)
+    }
+
+    /// Returns the live order at the back of the queue (the newest).
+    pub fn newest(&self) -> Option<OrderId> {
+        self.orders.iter().rev().copied().find(|id| id.0 != 0)
+    }
+
     /// Returns an iterator over the active order IDs in FIFO order.
     pub fn iter(&self) -> impl Iterator<Item = OrderId> + '_ {
         self.orders.iter().copied().filter(|id| id.0 != 0)
@@ -337,6 +379,22 @@ mod tests {
         assert_eq!(level.tombstone_count(), 0);
     }
 
+    #[test]
+    fn tombstone_position_survives_front_pops() {
+        let mut level = Level::new(Price(100_00));
+        level.push_back(OrderId(1), 100);
+        level.push_back(OrderId(2), 200);
+        level.push_back(OrderId(3), 150);
+
+        // Fill the head, then cancel order 3 by its insert-time position.
+        assert_eq!(level.pop_front(100), Some(OrderId(1)));
+        level.tombstone_order(2, OrderId(3), 150);
+
+        assert_eq!(level.total_quantity(), 200);
+        let ids: Vec<_> = level.iter().collect();
+        assert_eq!(ids, vec![OrderId(2)]);
+    }
+
     #[test]
     fn quantity_saturates_on_underflow() {
         let mut level = Level::new(Price(100_00));
@@ -148,22 +148,32 @@
 //! assert_eq!(snap.spread(), Some(100));  // $1.00
 //! ```
 
+pub mod auction;
 #[cfg(feature = "portfolio")]
 pub mod backtest_bridge;
 mod book;
+#[cfg(feature = "event-log")]
+mod checkpoint;
 pub mod cv;
 mod error;
 mod event;
 mod exchange;
+pub mod execution;
+pub mod export;
 pub mod garch;
 pub mod indicators;
+mod instrument;
 #[cfg(feature = "itch")]
 pub mod itch;
+pub mod latency;
 mod level;
+pub mod loaders;
 mod matching;
 pub mod multi_exchange;
 pub mod optimize;
 mod order;
+#[cfg(feature = "ouch")]
+pub mod ouch;
 #[cfg(feature = "persistence")]
 pub mod persistence;
 #[cfg(feature = "portfolio")]
@@ -171,6 +181,7 @@ pub mod portfolio;
 mod price_levels;
 mod result;
 mod side;
+pub mod sim;
 mod snapshot;
 pub mod stats;
 pub mod stop;
@@ -179,21 +190,25 @@ mod trade;
 mod types;
 
 // Re-export public API
+pub use auction::{AuctionQuote, AuctionResult};
 pub use book::OrderBook;
-pub use error::ValidationError;
+#[cfg(feature = "event-log")]
+pub use checkpoint::Checkpoint;
+pub use error::{BookInvariantError, ReplayError, ReplayViolation, ValidationError};
 pub use event::{ApplyResult, Event};
-pub use exchange::Exchange;
+pub use exchange::{Exchange, ExecutionQualityStats, ModifyPolicy, TradeObserver};
+pub use instrument::InstrumentSpec;
 pub use level::Level;
-pub use matching::MatchResult;
-pub use multi_exchange::MultiExchange;
-pub use order::{Order, OrderStatus};
+pub use matching::{MatchResult, TradePriceRule};
+pub use multi_exchange::{ArbOpportunity, ConsolidatedBbo, MultiExchange, VenueQuote};
+pub use order::{Execution, Order, OrderStatus};
 pub use price_levels::PriceLevels;
 pub use result::{
     CancelError, CancelResult, ModifyError, ModifyResult, StopSubmitResult, SubmitResult,
 };
 pub use side::Side;
-pub use snapshot::{BookSnapshot, LevelSnapshot};
-pub use stop::{StopBook, StopOrder, StopStatus, TrailMethod};
+pub use snapshot::{BookDelta, BookSnapshot, LevelSnapshot, SideDelta, snapshot_diff};
+pub use stop::{ConditionalKind, StopBook, StopOrder, StopStatus, TrailMethod};
 pub use tif::TimeInForce;
-pub use trade::Trade;
+pub use trade::{OhlcvBar, Trade};
 pub use types::{OrderId, Price, Quantity, Symbol, Timestamp, TradeId};
@@ -0,0 +1,312 @@
+//! Event loaders for external market data.
+//!
+//! [`load_csv`] turns a delimited text file of order actions into an event
+//! sequence that [`Exchange::replay`](crate::Exchange::replay) can drive.
+//! Which column holds which field is described by a [`CsvSchema`].
+//!
+//! # Usage
+//!
+//! ```ignore
+//! use nanobook::loaders::{CsvSchema, load_csv};
+//! use std::path::Path;
+//!
+//! // action,side,price,quantity,order_id  with prices in dollars
+//! let schema = CsvSchema::new(0, 1, 2, 3).with_order_id(4).with_price_scale(100.0);
+//! let events = load_csv(Path::new("ticks.csv"), &schema).unwrap();
+//! let exchange = Exchange::replay(&events);
+//! ```
+
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use crate::{Event, OrderId, Price, Quantity, Side, TimeInForce};
+
+/// Column layout of an order-action CSV/TSV file.
+///
+/// Columns are zero-based. Recognized actions (case-insensitive):
+/// `limit`/`add`/`submit_limit`, `market`/`submit_market`, and `cancel`.
+/// A `cancel` row needs an [order ID column](Self::with_order_id) holding
+/// the exchange-assigned ID, which replay hands out sequentially from 1 in
+/// submission order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvSchema {
+    /// Field separator (`,` by default)
+    pub delimiter: char,
+    /// Whether the first non-empty line is a header to skip
+    pub has_header: bool,
+    /// Action column
+    pub action: usize,
+    /// Side column (`buy`/`b`/`bid` or `sell`/`s`/`ask`)
+    pub side: usize,
+    /// Price column; unused by market and cancel rows
+    pub price: usize,
+    /// Quantity column
+    pub quantity: usize,
+    /// Time-in-force column (`gtc`/`ioc`/`fok`); GTC when absent or empty
+    pub tif: Option<usize>,
+    /// Order ID column, required by cancel rows
+    pub order_id: Option<usize>,
+    /// Factor turning a price field into cents: 1 for cents, 100 for dollars
+    pub price_scale: f64,
+}
+
+impl CsvSchema {
+    /// Comma-separated file with a header row and prices in cents.
+    pub fn new(action: usize, side: usize, price: usize, quantity: usize) -> Self {
+        Self {
+            delimiter: ',',
+            has_header: true,
+            action,
+            side,
+            price,
+            quantity,
+            tif: None,
+            order_id: None,
+            price_scale: 1.0,
+        }
+    }
+
+    /// Split fields on `delimiter` instead (e.g. `'\t'` for TSV).
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Treat the first line as data.
+    pub fn without_header(mut self) -> Self {
+        self.has_header = false;
+        self
+    }
+
+    /// Read the time-in-force from column `tif`.
+    pub fn with_tif(mut self, tif: usize) -> Self {
+        self.tif = Some(tif);
+        self
+    }
+
+    /// Read cancel targets from column `order_id`.
+    pub fn with_order_id(mut self, order_id: usize) -> Self {
+        self.order_id = Some(order_id);
+        self
+    }
+
+    /// Multiply prices by `scale` to get cents (100 for dollar prices).
+    pub fn with_price_scale(mut self, scale: f64) -> Self {
+        self.price_scale = scale;
+        self
+    }
+}
+
+/// Load events from a CSV/TSV file laid out as `schema` describes.
+///
+/// Empty lines are skipped. A malformed row fails the whole load with an
+/// `InvalidData` error naming its 1-based line number.
+pub fn load_csv(path: &Path, schema: &CsvSchema) -> io::Result<Vec<Event>> {
+    let file = std::fs::File::open(path)?;
+    read_csv(io::BufReader::new(file), schema)
+}
+
+/// Parse events from CSV/TSV text; see [`load_csv`].
+pub fn read_csv<R: BufRead>(reader: R, schema: &CsvSchema) -> io::Result<Vec<Event>> {
+    let mut events = Vec::new();
+    let mut header_pending = schema.has_header;
+
+    for (line_num, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if header_pending {
+            header_pending = false;
+            continue;
+        }
+        let fields: Vec<&str> = line.split(schema.delimiter).map(str::trim).collect();
+        let event = parse_row(&fields, schema).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}", line_num + 1, e),
+            )
+        })?;
+        events.push(event);
+    }
+
+    Ok(events)
+}
+
+/// Build the event for one row.
+fn parse_row(fields: &[&str], schema: &CsvSchema) -> Result<Event, String> {
+    let field = |col: usize, name: &str| {
+        fields
+            .get(col)
+            .copied()
+            .filter(|f| !f.is_empty())
+            .ok_or_else(|| format!("missing {name} (column {col})"))
+    };
+
+    let action = field(schema.action, "action")?;
+    match action.to_ascii_lowercase().as_str() {
+        "limit" | "add" | "submit_limit" => {
+            let tif = match schema.tif.and_then(|col| fields.get(col)) {
+                Some(tif) if !tif.is_empty() => parse_tif(tif)?,
+                _ => TimeInForce::GTC,
+            };
+            Ok(Event::submit_limit(
+                parse_side(field(schema.side, "side")?)?,
+                parse_price(field(schema.price, "price")?, schema.price_scale)?,
+                parse_quantity(field(schema.quantity, "quantity")?)?,
+                tif,
+            ))
+        }
+        "market" | "submit_market" => Ok(Event::submit_market(
+            parse_side(field(schema.side, "side")?)?,
+            parse_quantity(field(schema.quantity, "quantity")?)?,
+        )),
+        "cancel" => {
+            let col = schema
+                .order_id
+                .ok_or("cancel row but the schema has no order_id column")?;
+            let id = field(col, "order_id")?;
+            let id = id
+                .parse::<u64>()
+                .map_err(|_| format!("invalid order_id {id:?}"))?;
+            Ok(Event::cancel(OrderId(id)))
+        }
+        _ => Err(format!("unknown action {action:?}")),
+    }
+}
+
+fn parse_side(s: &str) -> Result<Side, String> {
+    match s.to_ascii_lowercase().as_str() {
+        "buy" | "b" | "bid" => Ok(Side::Buy),
+        "sell" | "s" | "ask" => Ok(Side::Sell),
+        _ => Err(format!("invalid side {s:?}")),
+    }
+}
+
+fn parse_tif(s: &str) -> Result<TimeInForce, String> {
+    match s.to_ascii_lowercase().as_str() {
+        "gtc" => Ok(TimeInForce::GTC),
+        "ioc" => Ok(TimeInForce::IOC),
+        "fok" => Ok(TimeInForce::FOK),
+        _ => Err(format!("invalid time in force {s:?}")),
+    }
+}
+
+/// Parse a price field and scale it to whole cents (rounded).
+fn parse_price(s: &str, scale: f64) -> Result<Price, String> {
+    let value = s
+        .parse::<f64>()
+        .ok()
+        .map(|v| (v * scale).round())
+        .filter(|cents| cents.is_finite() && *cents > 0.0 && *cents < i64::MAX as f64)
+        .ok_or_else(|| format!("invalid price {s:?}"))?;
+    Ok(Price(value as i64))
+}
+
+fn parse_quantity(s: &str) -> Result<Quantity, String> {
+    s.parse::<Quantity>()
+        .ok()
+        .filter(|&q| q > 0)
+        .ok_or_else(|| format!("invalid quantity {s:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TICKS: &str = "\
+action,side,price,quantity,tif,order_id
+limit,sell,101.50,100,,
+limit,buy,101.00,50,gtc,
+
+LIMIT,BUY,101.50,30,ioc,
+cancel,,,,,2
+market,sell,,40,,
+";
+
+    fn schema() -> CsvSchema {
+        CsvSchema::new(0, 1, 2, 3)
+            .with_tif(4)
+            .with_order_id(5)
+            .with_price_scale(100.0)
+    }
+
+    #[test]
+    fn parses_submits_and_cancels() {
+        let events = read_csv(TICKS.as_bytes(), &schema()).unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                Event::submit_limit(Side::Sell, Price(101_50), 100, TimeInForce::GTC),
+                Event::submit_limit(Side::Buy, Price(101_00), 50, TimeInForce::GTC),
+                Event::submit_limit(Side::Buy, Price(101_50), 30, TimeInForce::IOC),
+                Event::cancel(OrderId(2)),
+                Event::submit_market(Side::Sell, 40),
+            ]
+        );
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn parsed_events_replay() {
+        let events = read_csv(TICKS.as_bytes(), &schema()).unwrap();
+
+        let exchange = crate::Exchange::replay(&events);
+        assert_eq!(exchange.trades().len(), 1);
+        assert_eq!(exchange.best_bid(), None);
+        assert_eq!(exchange.best_ask(), Some(Price(101_50)));
+    }
+
+    #[test]
+    fn tsv_with_cent_prices_and_no_header() {
+        let tsv = "add\tb\t10150\t10\nadd\tS\t10200\t5\n";
+        let schema = CsvSchema::new(0, 1, 2, 3)
+            .with_delimiter('\t')
+            .without_header();
+
+        let events = read_csv(tsv.as_bytes(), &schema).unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                Event::submit_limit(Side::Buy, Price(101_50), 10, TimeInForce::GTC),
+                Event::submit_limit(Side::Sell, Price(102_00), 5, TimeInForce::GTC),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_rows_report_line_numbers() {
+        let cases = [
+            ("h\nlimit,buy,abc,10\n", "line 2: invalid price \"abc\""),
+            ("h\nlimit,buy,1.00,0\n", "line 2: invalid quantity \"0\""),
+            ("h\n\nlimit,hold,1.00,1\n", "line 3: invalid side \"hold\""),
+            ("h\nlimit,buy,1.00\n", "line 2: missing quantity (column 3)"),
+            ("h\nreplace,buy,1,1\n", "line 2: unknown action \"replace\""),
+            (
+                "h\ncancel,,,\n",
+                "line 2: cancel row but the schema has no order_id column",
+            ),
+        ];
+        let schema = CsvSchema::new(0, 1, 2, 3).with_price_scale(100.0);
+
+        for (csv, expected) in cases {
+            let err = read_csv(csv.as_bytes(), &schema).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn load_csv_reads_file() {
+        let path = std::env::temp_dir().join("nanobook_test_loaders.csv");
+        std::fs::write(&path, TICKS).unwrap();
+
+        let events = load_csv(&path, &schema()).unwrap();
+        assert_eq!(events.len(), 5);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
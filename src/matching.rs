@@ -3,10 +3,48 @@
 //! The matching engine implements price-time priority:
 //! 1. Better prices match first (higher bids, lower asks)
 //! 2. At the same price, earlier orders match first (FIFO)
-//! 3. Trades execute at the resting order's price (price improvement for aggressor)
+//! 3. Trades execute at the resting order's price (price improvement for aggressor),
+//!    unless the book uses a different [`TradePriceRule`]
 
 use crate::{Order, OrderBook, Price, Quantity, Side, Trade};
 
+/// Which price a trade prints at.
+///
+/// Every rule prints within `[resting price, aggressor limit]`, so no order
+/// ever trades through its own limit. Market orders have no meaningful limit
+/// and always print at the resting price.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TradePriceRule {
+    /// The resting order's price (price improvement goes to the aggressor).
+    #[default]
+    RestingPrice,
+    /// The aggressor's limit price.
+    AggressorPrice,
+    /// Midway between the aggressor's limit and the resting price, rounded
+    /// toward the resting price.
+    Midpoint,
+}
+
+impl TradePriceRule {
+    /// Trade price for an aggressor on `side` with limit `aggressor` hitting
+    /// a resting order at `resting`.
+    pub fn trade_price(self, side: Side, aggressor: Price, resting: Price) -> Price {
+        if aggressor == Price::MAX || aggressor == Price::MIN {
+            return resting;
+        }
+        match self {
+            TradePriceRule::RestingPrice => resting,
+            TradePriceRule::AggressorPrice => aggressor,
+            // Halving the (non-negative) gap truncates toward `resting`.
+            TradePriceRule::Midpoint => match side {
+                Side::Buy => Price(resting.0 + (aggressor.0 - resting.0) / 2),
+                Side::Sell => Price(resting.0 - (resting.0 - aggressor.0) / 2),
+            },
+        }
+    }
+}
+
 /// Result of matching an incoming order against the book.
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -59,11 +97,17 @@ impl OrderBook {
     /// resting orders in the book. The incoming order is NOT added to
     /// the book — the caller decides whether to add it based on TIF.
     pub fn match_order(&mut self, incoming: &mut Order) -> MatchResult {
-        let mut result = MatchResult {
-            trades: Vec::new(),
+        let mut trades = Vec::new();
+        self.match_order_into(incoming, &mut trades);
+        MatchResult {
+            trades,
             remaining_quantity: incoming.remaining_quantity,
-        };
+        }
+    }
 
+    /// Like [`match_order`](Self::match_order), but appends the trades to a
+    /// caller-owned buffer so its allocation can be reused across calls.
+    pub fn match_order_into(&mut self, incoming: &mut Order, trades: &mut Vec<Trade>) {
         // Match until no more crosses or order is filled
         while incoming.remaining_quantity > 0 {
             // Get the best price on the opposite side
@@ -79,15 +123,12 @@ impl OrderBook {
             }
 
             // Match against orders at the best price level
-            self.match_at_price(incoming, best_price, &mut result);
+            self.match_at_price(incoming, best_price, trades);
         }
-
-        result.remaining_quantity = incoming.remaining_quantity;
-        result
     }
 
     /// Match an incoming order against all orders at a specific price level.
-    fn match_at_price(&mut self, incoming: &mut Order, price: Price, result: &mut MatchResult) {
+    fn match_at_price(&mut self, incoming: &mut Order, price: Price, trades: &mut Vec<Trade>) {
         // Process orders at this price level until exhausted or incoming filled
         while incoming.remaining_quantity > 0 {
             // Get the front order at this price (skips tombstones)
@@ -113,28 +154,31 @@ impl OrderBook {
             let fill_qty = incoming.remaining_quantity.min(resting_remaining);
 
             // Create the trade
+            let trade_price =
+                self.trade_price_rule()
+                    .trade_price(incoming.side, incoming.price, price);
             let trade = Trade::new(
                 self.next_trade_id(),
-                price, // Trade at resting order's price
+                trade_price,
                 fill_qty,
                 incoming.id,
                 resting_id,
                 incoming.side,
                 self.next_timestamp(),
             );
-            result.trades.push(trade);
 
             // Update the incoming order
-            incoming.fill(fill_qty);
+            incoming.fill_trade(&trade);
 
             // Update the resting order
             let resting_fully_filled = {
                 let resting = self
                     .get_order_mut(resting_id)
                     .expect("invariant: resting order exists in book");
-                resting.fill(fill_qty);
+                resting.fill_trade(&trade);
                 resting.remaining_quantity == 0
             };
+            trades.push(trade);
 
             // Update the price level
             let opposite = self.opposite_side_mut(incoming.side);
@@ -385,6 +429,52 @@ mod tests {
         assert_eq!(result.trades[0].price, Price(105_00));
     }
 
+    // === Trade price rules ===
+
+    fn print_price(rule: TradePriceRule, side: Side, limit: Price) -> Price {
+        let mut book = OrderBook::new().with_trade_price_rule(rule);
+        let resting = match side {
+            Side::Buy => book.create_order(Side::Sell, Price(100_00), 100, TimeInForce::GTC),
+            Side::Sell => book.create_order(Side::Buy, Price(105_00), 100, TimeInForce::GTC),
+        };
+        book.add_order(resting);
+        let mut order = book.create_order(side, limit, 100, TimeInForce::IOC);
+        book.match_order(&mut order).trades[0].price
+    }
+
+    #[test]
+    fn aggressor_price_rule_prints_at_incoming_limit() {
+        let buy = Price(105_00);
+        assert_eq!(
+            print_price(TradePriceRule::AggressorPrice, Side::Buy, buy),
+            buy
+        );
+        assert_eq!(
+            print_price(TradePriceRule::RestingPrice, Side::Buy, buy),
+            Price(100_00)
+        );
+        assert_eq!(
+            print_price(TradePriceRule::AggressorPrice, Side::Sell, Price(100_00)),
+            Price(100_00)
+        );
+    }
+
+    #[test]
+    fn midpoint_rule_rounds_toward_resting_price() {
+        let rule = TradePriceRule::Midpoint;
+        assert_eq!(print_price(rule, Side::Buy, Price(105_00)), Price(102_50));
+        assert_eq!(print_price(rule, Side::Buy, Price(100_01)), Price(100_00));
+        assert_eq!(print_price(rule, Side::Sell, Price(104_99)), Price(105_00));
+    }
+
+    #[test]
+    fn market_orders_always_print_at_resting_price() {
+        for rule in [TradePriceRule::AggressorPrice, TradePriceRule::Midpoint] {
+            assert_eq!(print_price(rule, Side::Buy, Price::MAX), Price(100_00));
+            assert_eq!(print_price(rule, Side::Sell, Price::MIN), Price(105_00));
+        }
+    }
+
     // === Order state after matching ===
 
     #[test]
@@ -1,6 +1,7 @@
-//! Multi-symbol exchange: one LOB per symbol.
+//! Multi-symbol exchange: one LOB per symbol, plus optional per-venue books
+//! for symbols listed on several venues.
 
-use crate::{Exchange, Price, Symbol};
+use crate::{Exchange, InstrumentSpec, Price, Quantity, Symbol};
 use rustc_hash::FxHashMap;
 
 /// A collection of per-symbol `Exchange` instances.
@@ -26,6 +27,38 @@ use rustc_hash::FxHashMap;
 #[derive(Clone, Debug, Default)]
 pub struct MultiExchange {
     exchanges: FxHashMap<Symbol, Exchange>,
+    specs: FxHashMap<Symbol, InstrumentSpec>,
+    /// symbol → venue → book, for symbols listed on several venues
+    venues: FxHashMap<Symbol, FxHashMap<Symbol, Exchange>>,
+}
+
+/// One side of the touch on a specific venue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VenueQuote {
+    pub venue: Symbol,
+    pub price: Price,
+    /// Quantity resting at `price` on `venue`
+    pub quantity: Quantity,
+}
+
+/// Best bid and best ask for a symbol across all of its venues.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConsolidatedBbo {
+    pub bid: Option<VenueQuote>,
+    pub ask: Option<VenueQuote>,
+}
+
+/// A crossed cross-venue market: buy on one venue, sell on another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArbOpportunity {
+    /// Venue with the lowest ask (where to buy)
+    pub buy_venue: Symbol,
+    /// Venue with the highest bid (where to sell)
+    pub sell_venue: Symbol,
+    /// Smaller of the two touch quantities
+    pub quantity: Quantity,
+    /// Bid minus ask, in cents per unit
+    pub spread: i64,
 }
 
 impl MultiExchange {
@@ -35,8 +68,45 @@ impl MultiExchange {
     }
 
     /// Get or create the exchange for a symbol.
+    ///
+    /// A newly created exchange gets the symbol's registered
+    /// [`InstrumentSpec`], if any.
     pub fn get_or_create(&mut self, symbol: &Symbol) -> &mut Exchange {
-        self.exchanges.entry(*symbol).or_default()
+        self.exchanges.entry(*symbol).or_insert_with(|| {
+            let mut exchange = Exchange::new();
+            if let Some(spec) = self.specs.get(symbol) {
+                exchange.set_instrument(*spec);
+            }
+            exchange
+        })
+    }
+
+    /// Register instrument metadata for a symbol.
+    ///
+    /// Applies to the symbol's exchange whether it already exists or is
+    /// created later by [`get_or_create`](Self::get_or_create).
+    pub fn register_symbol(&mut self, symbol: Symbol, spec: InstrumentSpec) {
+        self.specs.insert(symbol, spec);
+        if let Some(exchange) = self.exchanges.get_mut(&symbol) {
+            exchange.set_instrument(spec);
+        }
+    }
+
+    /// Instrument metadata registered for a symbol.
+    pub fn spec(&self, symbol: &Symbol) -> Option<&InstrumentSpec> {
+        self.specs.get(symbol)
+    }
+
+    /// Value of `quantity` contracts of `symbol` at `price`, in cents.
+    ///
+    /// Applies the registered contract multiplier; unregistered symbols use
+    /// the equity default of 1.
+    pub fn notional(&self, symbol: &Symbol, price: Price, quantity: Quantity) -> i64 {
+        self.specs
+            .get(symbol)
+            .copied()
+            .unwrap_or_default()
+            .notional(price, quantity)
     }
 
     /// Get a reference to the exchange for a symbol, if it exists.
@@ -49,6 +119,133 @@ impl MultiExchange {
         self.exchanges.get_mut(symbol)
     }
 
+    /// Remove a symbol's exchange, returning it (e.g. to archive its events).
+    ///
+    /// Also drops the symbol's [venue books](Self::get_or_create_on). The
+    /// registered [`InstrumentSpec`] is kept, so a later
+    /// [`get_or_create`](Self::get_or_create) starts a fresh book with it.
+    pub fn remove(&mut self, symbol: &Symbol) -> Option<Exchange> {
+        self.venues.remove(symbol);
+        self.exchanges.remove(symbol)
+    }
+
+    /// Keep only the exchanges for which `keep` returns `true`, dropping the rest.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Symbol, &Exchange) -> bool) {
+        self.exchanges
+            .retain(|symbol, exchange| keep(symbol, exchange));
+    }
+
+    /// Number of symbols with a live exchange.
+    pub fn symbol_count(&self) -> usize {
+        self.exchanges.len()
+    }
+
+    /// Orders stored across all books (venue books included), including
+    /// filled and cancelled history.
+    pub fn total_order_count(&self) -> usize {
+        self.exchanges
+            .values()
+            .chain(self.venues.values().flat_map(|books| books.values()))
+            .map(|exchange| exchange.book().order_count())
+            .sum()
+    }
+
+    // === Venues ===
+
+    /// Get or create the book for `symbol` on `venue`.
+    ///
+    /// Venue books are independent of the single book returned by
+    /// [`get_or_create`](Self::get_or_create); they feed
+    /// [`consolidated_bbo`](Self::consolidated_bbo) and
+    /// [`find_arbitrage`](Self::find_arbitrage). A new venue book gets the
+    /// symbol's registered [`InstrumentSpec`], if any.
+    pub fn get_or_create_on(&mut self, venue: &Symbol, symbol: &Symbol) -> &mut Exchange {
+        let spec = self.specs.get(symbol).copied();
+        self.venues
+            .entry(*symbol)
+            .or_default()
+            .entry(*venue)
+            .or_insert_with(|| {
+                let mut exchange = Exchange::new();
+                if let Some(spec) = spec {
+                    exchange.set_instrument(spec);
+                }
+                exchange
+            })
+    }
+
+    /// The book for `symbol` on `venue`, if it exists.
+    pub fn get_on(&self, venue: &Symbol, symbol: &Symbol) -> Option<&Exchange> {
+        self.venues.get(symbol)?.get(venue)
+    }
+
+    /// Venues listing `symbol`, in name order.
+    pub fn venues(&self, symbol: &Symbol) -> Vec<Symbol> {
+        let mut venues: Vec<Symbol> = self
+            .venues
+            .get(symbol)
+            .map(|books| books.keys().copied().collect())
+            .unwrap_or_default();
+        venues.sort_unstable();
+        venues
+    }
+
+    /// Best bid and ask for `symbol` across its venue books.
+    ///
+    /// Ties between venues at the same price go to the venue that sorts
+    /// first by name. Returns `None` if the symbol has no venue books.
+    pub fn consolidated_bbo(&self, symbol: &Symbol) -> Option<ConsolidatedBbo> {
+        let mut bbo = ConsolidatedBbo {
+            bid: None,
+            ask: None,
+        };
+        for venue in self.venues(symbol) {
+            let book = self.get_on(&venue, symbol)?.book();
+            if let Some(level) = book.bids().best_level() {
+                let quote = VenueQuote {
+                    venue,
+                    price: level.price(),
+                    quantity: level.total_quantity(),
+                };
+                if bbo.bid.is_none_or(|best| quote.price > best.price) {
+                    bbo.bid = Some(quote);
+                }
+            }
+            if let Some(level) = book.asks().best_level() {
+                let quote = VenueQuote {
+                    venue,
+                    price: level.price(),
+                    quantity: level.total_quantity(),
+                };
+                if bbo.ask.is_none_or(|best| quote.price < best.price) {
+                    bbo.ask = Some(quote);
+                }
+            }
+        }
+        self.venues.contains_key(symbol).then_some(bbo)
+    }
+
+    /// A cross-venue opportunity for `symbol`: one venue's best bid above
+    /// another venue's best ask.
+    ///
+    /// Returns `None` unless the consolidated market is strictly crossed
+    /// across two different venues (a locked market has nothing to capture).
+    pub fn find_arbitrage(&self, symbol: &Symbol) -> Option<ArbOpportunity> {
+        let ConsolidatedBbo {
+            bid: Some(bid),
+            ask: Some(ask),
+        } = self.consolidated_bbo(symbol)?
+        else {
+            return None;
+        };
+        (bid.price > ask.price && bid.venue != ask.venue).then(|| ArbOpportunity {
+            buy_venue: ask.venue,
+            sell_venue: bid.venue,
+            quantity: bid.quantity.min(ask.quantity),
+            spread: bid.price.0 - ask.price.0,
+        })
+    }
+
     /// Iterator over all symbols that have exchanges.
     pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
         self.exchanges.keys()
@@ -125,6 +322,156 @@ mod tests {
         assert_eq!(syms.len(), 2);
     }
 
+    #[test]
+    fn multiplier_scales_notional() {
+        let es = Symbol::new("ES");
+        let mut multi = MultiExchange::new();
+        multi.register_symbol(
+            es,
+            InstrumentSpec {
+                tick_size: 25,
+                multiplier: 50,
+                ..InstrumentSpec::default()
+            },
+        );
+
+        // 2 contracts at $4000 = $400,000, not $8,000.
+        assert_eq!(multi.notional(&es, Price(4000_00), 2), 400_000_00);
+        assert_eq!(multi.notional(&aapl(), Price(4000_00), 2), 8_000_00);
+    }
+
+    #[test]
+    fn registered_spec_applies_to_exchange() {
+        let es = Symbol::new("ES");
+        let spec = InstrumentSpec {
+            tick_size: 25,
+            multiplier: 50,
+            ..InstrumentSpec::default()
+        };
+        let mut multi = MultiExchange::new();
+        multi.get_or_create(&aapl());
+        multi.register_symbol(es, spec);
+        multi.register_symbol(aapl(), InstrumentSpec::default());
+
+        let exchange = multi.get_or_create(&es);
+        assert_eq!(exchange.instrument(), &spec);
+        let err = exchange.try_submit_limit(Side::Buy, Price(4000_10), 1, TimeInForce::GTC);
+        assert_eq!(err.unwrap_err(), crate::ValidationError::OffTick);
+        assert!(
+            exchange
+                .try_submit_limit(Side::Buy, Price(4000_25), 1, TimeInForce::GTC)
+                .is_ok()
+        );
+        assert_eq!(multi.get(&aapl()).unwrap().instrument().multiplier, 1);
+    }
+
+    #[test]
+    fn remove_drops_symbol_from_iteration() {
+        let mut multi = MultiExchange::new();
+        multi
+            .get_or_create(&aapl())
+            .submit_limit(Side::Sell, Price(150_00), 100, TimeInForce::GTC);
+        multi.get_or_create(&msft());
+
+        let removed = multi.remove(&aapl()).unwrap();
+        assert_eq!(removed.best_ask(), Some(Price(150_00)));
+        assert!(multi.remove(&aapl()).is_none());
+        assert_eq!(multi.symbol_count(), 1);
+        assert_eq!(multi.symbols().collect::<Vec<_>>(), vec![&msft()]);
+        assert!(multi.best_prices().iter().all(|(sym, _, _)| *sym != aapl()));
+    }
+
+    #[test]
+    fn retain_keeps_only_active_books() {
+        let mut multi = MultiExchange::new();
+        multi
+            .get_or_create(&aapl())
+            .submit_limit(Side::Buy, Price(150_00), 100, TimeInForce::GTC);
+        let dead = multi.get_or_create(&msft());
+        let id = dead
+            .submit_limit(Side::Buy, Price(300_00), 100, TimeInForce::GTC)
+            .order_id;
+        dead.cancel(id);
+        assert_eq!(multi.total_order_count(), 2);
+
+        multi.retain(|_, exchange| exchange.book().active_order_count() > 0);
+        assert_eq!(multi.symbol_count(), 1);
+        assert!(multi.get(&msft()).is_none());
+        assert_eq!(multi.total_order_count(), 1);
+    }
+
+    #[test]
+    fn crossed_venues_are_an_opportunity() {
+        let (a, b) = (Symbol::new("VENUE_A"), Symbol::new("VENUE_B"));
+        let mut multi = MultiExchange::new();
+        multi.get_or_create_on(&a, &aapl()).submit_limit(
+            Side::Buy,
+            Price(101),
+            300,
+            TimeInForce::GTC,
+        );
+        multi.get_or_create_on(&b, &aapl()).submit_limit(
+            Side::Sell,
+            Price(100),
+            200,
+            TimeInForce::GTC,
+        );
+
+        let bbo = multi.consolidated_bbo(&aapl()).unwrap();
+        assert_eq!(bbo.bid.unwrap().venue, a);
+        assert_eq!(bbo.ask.unwrap().venue, b);
+
+        assert_eq!(
+            multi.find_arbitrage(&aapl()),
+            Some(ArbOpportunity {
+                buy_venue: b,
+                sell_venue: a,
+                quantity: 200,
+                spread: 1,
+            })
+        );
+        // The single-venue book is untouched.
+        assert!(multi.get(&aapl()).is_none());
+    }
+
+    #[test]
+    fn no_opportunity_without_a_cross() {
+        let (a, b) = (Symbol::new("VENUE_A"), Symbol::new("VENUE_B"));
+        let mut multi = MultiExchange::new();
+        assert!(multi.find_arbitrage(&aapl()).is_none());
+
+        // One venue only.
+        multi.get_or_create_on(&a, &aapl()).submit_limit(
+            Side::Buy,
+            Price(100_00),
+            100,
+            TimeInForce::GTC,
+        );
+        multi.get_or_create_on(&a, &aapl()).submit_limit(
+            Side::Sell,
+            Price(100_05),
+            100,
+            TimeInForce::GTC,
+        );
+        assert!(multi.find_arbitrage(&aapl()).is_none());
+
+        // Locked, not crossed.
+        multi.get_or_create_on(&b, &aapl()).submit_limit(
+            Side::Sell,
+            Price(100_00),
+            100,
+            TimeInForce::GTC,
+        );
+        assert_eq!(
+            multi.consolidated_bbo(&aapl()).unwrap().ask.unwrap().venue,
+            b
+        );
+        assert!(multi.find_arbitrage(&aapl()).is_none());
+
+        multi.remove(&aapl());
+        assert!(multi.venues(&aapl()).is_empty());
+    }
+
     #[test]
     fn empty() {
         let multi = MultiExchange::new();
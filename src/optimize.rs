@@ -3,29 +3,73 @@
 //! The implementations here are deterministic and safety-first:
 //! - invalid inputs return empty weights,
 //! - valid outputs are finite, non-negative, and sum to ~1.
+//!
+//! [`optimize_mean_variance`], [`optimize_min_variance_tc`], and
+//! [`optimize_cvar_scenarios`] are the exceptions: they honor caller-supplied
+//! box bounds, which may be negative.
+
+/// Default iteration cap of [`optimize_min_variance`].
+pub const MIN_VARIANCE_MAX_ITER: usize = 350;
+
+/// Default iteration cap of [`optimize_risk_parity`].
+pub const RISK_PARITY_MAX_ITER: usize = 600;
+
+/// Default convergence tolerance: the Euclidean length of the last step.
+pub const OPTIMIZER_TOLERANCE: f64 = 1e-8;
+
+/// Weights from an iterative optimizer together with how it stopped.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptimizeResult {
+    pub weights: Vec<f64>,
+    /// Iterations actually run (0 for invalid or single-asset input)
+    pub iterations: usize,
+    /// `false` if `max_iter` ran out first; `weights` is then the last iterate
+    pub converged: bool,
+}
 
 /// Long-only minimum-variance optimization on the unit simplex.
 pub fn optimize_min_variance(returns: &[Vec<f64>]) -> Vec<f64> {
+    optimize_min_variance_ext(returns, MIN_VARIANCE_MAX_ITER, OPTIMIZER_TOLERANCE).weights
+}
+
+/// [`optimize_min_variance`] with an explicit iteration cap and tolerance.
+///
+/// Iteration stops once a projected-gradient step moves the weights by less
+/// than `tolerance` (Euclidean norm). If `max_iter` is reached first, the
+/// last iterate is returned with `converged == false`. The iteration is a
+/// fixed sequence of floating-point operations, so identical inputs always
+/// produce bit-identical weights.
+pub fn optimize_min_variance_ext(
+    returns: &[Vec<f64>],
+    max_iter: usize,
+    tolerance: f64,
+) -> OptimizeResult {
     let Some((_rows, cols)) = matrix_shape(returns) else {
-        return Vec::new();
+        return OptimizeResult::invalid();
     };
 
     if cols == 1 {
-        return vec![1.0];
+        return OptimizeResult::single();
     }
 
+    let threshold = tolerance.max(0.0).powi(2);
     let cov = covariance_matrix(returns);
     let mut w = equal_weights(cols);
     let mut lr = 0.20_f64;
+    let mut iterations = 0;
+    let mut converged = false;
 
-    for _ in 0..350 {
+    while iterations < max_iter {
+        iterations += 1;
         let sigma_w = mat_vec_mul(&cov, &w);
         let grad: Vec<f64> = sigma_w.iter().map(|g| 2.0 * g).collect();
         let candidate: Vec<f64> = w.iter().zip(&grad).map(|(wi, gi)| wi - lr * gi).collect();
         let projected = project_simplex(&candidate);
 
-        if squared_distance(&projected, &w) < 1e-16 {
+        if squared_distance(&projected, &w) < threshold {
             w = projected;
+            converged = true;
             break;
         }
 
@@ -33,7 +77,11 @@ pub fn optimize_min_variance(returns: &[Vec<f64>]) -> Vec<f64> {
         lr *= 0.995;
     }
 
-    normalize_long_only(w)
+    OptimizeResult {
+        weights: normalize_long_only(w),
+        iterations,
+        converged,
+    }
 }
 
 /// Long-only maximum-Sharpe optimization on the unit simplex.
@@ -87,18 +135,34 @@ pub fn optimize_max_sharpe(returns: &[Vec<f64>], risk_free: f64) -> Vec<f64> {
 
 /// Long-only risk parity approximation.
 pub fn optimize_risk_parity(returns: &[Vec<f64>]) -> Vec<f64> {
+    optimize_risk_parity_ext(returns, RISK_PARITY_MAX_ITER, OPTIMIZER_TOLERANCE).weights
+}
+
+/// [`optimize_risk_parity`] with an explicit iteration cap and tolerance.
+///
+/// Same stopping rule and determinism guarantee as
+/// [`optimize_min_variance_ext`].
+pub fn optimize_risk_parity_ext(
+    returns: &[Vec<f64>],
+    max_iter: usize,
+    tolerance: f64,
+) -> OptimizeResult {
     let Some((_rows, cols)) = matrix_shape(returns) else {
-        return Vec::new();
+        return OptimizeResult::invalid();
     };
 
     if cols == 1 {
-        return vec![1.0];
+        return OptimizeResult::single();
     }
 
+    let threshold = tolerance.max(0.0).powi(2);
     let cov = covariance_matrix(returns);
     let mut w = equal_weights(cols);
+    let mut iterations = 0;
+    let mut converged = false;
 
-    for _ in 0..600 {
+    while iterations < max_iter {
+        iterations += 1;
         let sigma_w = mat_vec_mul(&cov, &w);
         let port_var = dot(&w, &sigma_w).max(1e-12);
         let target = port_var / cols as f64;
@@ -124,15 +188,38 @@ pub fn optimize_risk_parity(returns: &[Vec<f64>]) -> Vec<f64> {
             .collect();
         let damped = normalize_long_only(damped);
 
-        if squared_distance(&damped, &w) < 1e-16 {
+        if squared_distance(&damped, &w) < threshold {
             w = damped;
+            converged = true;
             break;
         }
 
         w = damped;
     }
 
-    normalize_long_only(w)
+    OptimizeResult {
+        weights: normalize_long_only(w),
+        iterations,
+        converged,
+    }
+}
+
+impl OptimizeResult {
+    fn invalid() -> Self {
+        Self {
+            weights: Vec::new(),
+            iterations: 0,
+            converged: false,
+        }
+    }
+
+    fn single() -> Self {
+        Self {
+            weights: vec![1.0],
+            iterations: 0,
+            converged: true,
+        }
+    }
 }
 
 /// Long-only CVaR-minimization proxy using inverse tail-loss weighting.
@@ -177,6 +264,618 @@ pub fn optimize_cdar(returns: &[Vec<f64>], alpha: f64) -> Vec<f64> {
     inverse_risk_weights(&risks)
 }
 
+/// Minimum-CVaR weights over an explicit scenario matrix.
+///
+/// Rows of `scenario_returns` are scenarios and columns are assets. Solves
+/// the Rockafellar-Uryasev linear program
+///
+/// ```text
+/// min  zeta + 1 / ((1 - alpha) * S) * sum_s u_s
+/// s.t. u_s >= -r_s' * w - zeta,  u_s >= 0
+///      sum(w) == 1,  min_weights[i] <= w[i] <= max_weights[i]
+/// ```
+///
+/// with a dense two-phase simplex, so the result is the exact LP optimum
+/// rather than the inverse-tail proxy of [`optimize_cvar`]. At the optimum
+/// `zeta` is the portfolio's `alpha`-VaR. `alpha` is clamped to
+/// `[0.5, 0.999]`.
+///
+/// Returns an empty vector on invalid input or infeasible bounds.
+pub fn optimize_cvar_scenarios(
+    scenario_returns: &[Vec<f64>],
+    alpha: f64,
+    min_weights: &[f64],
+    max_weights: &[f64],
+) -> Vec<f64> {
+    let Some((scenarios, n)) = matrix_shape(scenario_returns) else {
+        return Vec::new();
+    };
+    if min_weights.len() != n || max_weights.len() != n {
+        return Vec::new();
+    }
+    for (lo, hi) in min_weights.iter().zip(max_weights) {
+        if !lo.is_finite() || !hi.is_finite() || lo > hi {
+            return Vec::new();
+        }
+    }
+    let budget = 1.0 - min_weights.iter().sum::<f64>();
+    if budget < -1e-12 || max_weights.iter().sum::<f64>() < 1.0 - 1e-12 {
+        return Vec::new();
+    }
+    let budget = budget.max(0.0);
+    let alpha = alpha.clamp(0.5, 0.999);
+
+    // Columns: x (w - lo), zeta+, zeta-, u, surplus t, bound slack v.
+    // Bounds wider than the free budget can never bind and get no row.
+    let bounded: Vec<usize> = (0..n)
+        .filter(|&i| max_weights[i] - min_weights[i] < budget)
+        .collect();
+    let (zeta_pos, zeta_neg) = (n, n + 1);
+    let u0 = n + 2;
+    let t0 = u0 + scenarios;
+    let v0 = t0 + scenarios;
+    let vars = v0 + bounded.len();
+
+    let mut cost = vec![0.0; vars];
+    cost[zeta_pos] = 1.0;
+    cost[zeta_neg] = -1.0;
+    let tail_weight = 1.0 / ((1.0 - alpha) * scenarios as f64);
+    cost[u0..t0].iter_mut().for_each(|c| *c = tail_weight);
+
+    let mut rows = Vec::with_capacity(1 + scenarios + bounded.len());
+    let mut rhs = Vec::with_capacity(rows.capacity());
+
+    let mut row = vec![0.0; vars];
+    row[..n].iter_mut().for_each(|a| *a = 1.0);
+    rows.push(row);
+    rhs.push(budget);
+
+    // u_s + zeta + r_s' * (lo + x) - t_s == 0
+    for (s, scenario) in scenario_returns.iter().enumerate() {
+        let mut row = vec![0.0; vars];
+        row[..n].copy_from_slice(scenario);
+        row[zeta_pos] = 1.0;
+        row[zeta_neg] = -1.0;
+        row[u0 + s] = 1.0;
+        row[t0 + s] = -1.0;
+        rows.push(row);
+        rhs.push(-dot(scenario, min_weights));
+    }
+
+    for (k, &i) in bounded.iter().enumerate() {
+        let mut row = vec![0.0; vars];
+        row[i] = 1.0;
+        row[v0 + k] = 1.0;
+        rows.push(row);
+        rhs.push(max_weights[i] - min_weights[i]);
+    }
+
+    let Some(solution) = lp_minimize(&cost, rows, rhs) else {
+        return Vec::new();
+    };
+    solution[..n]
+        .iter()
+        .zip(min_weights.iter().zip(max_weights))
+        .map(|(x, (lo, hi))| (lo + x).clamp(*lo, *hi))
+        .collect()
+}
+
+/// Long-only maximum-diversification portfolio.
+///
+/// Maximizes the diversification ratio `w' * vols / sqrt(w' * cov * w)`.
+/// The problem is solved as minimum variance over the vol-scaled covariance
+/// (`cov[i][j] / (vols[i] * vols[j])`) and mapped back via `w[i] ∝ z[i] / vols[i]`,
+/// so equal correlations yield inverse-vol weights (equal weight when vols match).
+///
+/// Assets with zero volatility receive zero weight; if every asset has zero
+/// volatility the result is equal weight. Invalid input returns empty weights.
+pub fn optimize_max_diversification(cov: &[Vec<f64>], vols: &[f64]) -> Vec<f64> {
+    let n = vols.len();
+    if n == 0 || !is_square_finite(cov, n) || vols.iter().any(|v| !v.is_finite() || *v < 0.0) {
+        return Vec::new();
+    }
+
+    if n == 1 {
+        return vec![1.0];
+    }
+
+    let active: Vec<usize> = (0..n).filter(|&i| vols[i] > 1e-12).collect();
+    if active.is_empty() {
+        return equal_weights(n);
+    }
+
+    let k = active.len();
+    let mut scaled = vec![vec![0.0; k]; k];
+    for (a, &i) in active.iter().enumerate() {
+        for (b, &j) in active.iter().enumerate() {
+            scaled[a][b] = cov[i][j] / (vols[i] * vols[j]);
+        }
+    }
+
+    let z = box_mean_variance(&vec![0.0; k], &scaled, &vec![0.0; k], &vec![1.0; k], 0.0);
+
+    let mut w = vec![0.0; n];
+    for (a, &i) in active.iter().enumerate() {
+        w[i] = z[a] / vols[i];
+    }
+    normalize_long_only(w)
+}
+
+/// Minimum-variance optimization with box bounds and an optional return floor.
+///
+/// Minimizes `w' * cov * w` subject to `sum(w) == 1`,
+/// `min_weights[i] <= w[i] <= max_weights[i]`, and, when `target_return` is
+/// given, `expected_returns' * w >= target_return`.
+///
+/// The return floor is handled by bisecting on the Lagrange multiplier of the
+/// mean-variance objective `w' * cov * w - lambda * mu' * w`; each inner problem
+/// is solved by projected gradient onto the bounded simplex.
+///
+/// Returns an empty vector on invalid input or infeasible constraints.
+pub fn optimize_mean_variance(
+    expected_returns: &[f64],
+    cov: &[Vec<f64>],
+    min_weights: &[f64],
+    max_weights: &[f64],
+    target_return: Option<f64>,
+) -> Vec<f64> {
+    let n = expected_returns.len();
+    if n == 0
+        || min_weights.len() != n
+        || max_weights.len() != n
+        || !is_square_finite(cov, n)
+        || expected_returns.iter().any(|x| !x.is_finite())
+    {
+        return Vec::new();
+    }
+
+    for (lo, hi) in min_weights.iter().zip(max_weights) {
+        if !lo.is_finite() || !hi.is_finite() || lo > hi {
+            return Vec::new();
+        }
+    }
+
+    let lo_sum = min_weights.iter().sum::<f64>();
+    let hi_sum = max_weights.iter().sum::<f64>();
+    if lo_sum > 1.0 + 1e-12 || hi_sum < 1.0 - 1e-12 {
+        return Vec::new();
+    }
+
+    let min_var = box_mean_variance(expected_returns, cov, min_weights, max_weights, 0.0);
+    let Some(target) = target_return else {
+        return min_var;
+    };
+    if !target.is_finite() {
+        return Vec::new();
+    }
+    if dot(expected_returns, &min_var) >= target - 1e-12 {
+        return min_var;
+    }
+
+    let best = max_return_weights(expected_returns, min_weights, max_weights);
+    if dot(expected_returns, &best) < target - 1e-12 {
+        return Vec::new();
+    }
+
+    // Grow the multiplier until the return floor is met, then bisect down to
+    // the smallest multiplier (i.e. lowest variance) that still satisfies it.
+    let mut lambda_lo = 0.0_f64;
+    let mut lambda_hi = 1e-6_f64;
+    let mut w_hi = box_mean_variance(expected_returns, cov, min_weights, max_weights, lambda_hi);
+    let mut doublings = 0;
+    while dot(expected_returns, &w_hi) < target - 1e-12 {
+        doublings += 1;
+        if doublings > 80 {
+            return best;
+        }
+        lambda_lo = lambda_hi;
+        lambda_hi *= 2.0;
+        w_hi = box_mean_variance(expected_returns, cov, min_weights, max_weights, lambda_hi);
+    }
+
+    for _ in 0..60 {
+        let mid = 0.5 * (lambda_lo + lambda_hi);
+        let w = box_mean_variance(expected_returns, cov, min_weights, max_weights, mid);
+        if dot(expected_returns, &w) >= target - 1e-12 {
+            lambda_hi = mid;
+            w_hi = w;
+        } else {
+            lambda_lo = mid;
+        }
+    }
+
+    w_hi
+}
+
+/// Minimum-variance rebalance that pays for turnover.
+///
+/// Minimizes `w' * cov * w + cost * sum(|w[i] - current_weights[i]|)` subject
+/// to `sum(w) == 1` and `min_weights[i] <= w[i] <= max_weights[i]`, where
+/// `cost = cost_bps / 10_000`. The penalty is in the same units as `cov`, so
+/// scale `cov` to the rebalance horizon. Variance improvements smaller than
+/// the cost of trading for them are skipped, and a large enough `cost_bps`
+/// leaves a feasible `current_weights` untouched.
+///
+/// Solved by proximal gradient: each step projects onto the bounded simplex
+/// with soft-thresholding around `current_weights`. With `cost_bps == 0` this
+/// is the same problem as [`optimize_mean_variance`] without a return floor.
+///
+/// Returns an empty vector on invalid input or infeasible bounds.
+pub fn optimize_min_variance_tc(
+    cov: &[Vec<f64>],
+    current_weights: &[f64],
+    cost_bps: f64,
+    min_weights: &[f64],
+    max_weights: &[f64],
+) -> Vec<f64> {
+    let n = current_weights.len();
+    if n == 0
+        || !cost_bps.is_finite()
+        || cost_bps < 0.0
+        || current_weights.iter().any(|x| !x.is_finite())
+    {
+        return Vec::new();
+    }
+
+    let zero_mu = vec![0.0; n];
+    let base = optimize_mean_variance(&zero_mu, cov, min_weights, max_weights, None);
+    if base.is_empty() || cost_bps == 0.0 {
+        return base;
+    }
+
+    let lipschitz = cov
+        .iter()
+        .map(|row| 2.0 * row.iter().map(|x| x.abs()).sum::<f64>())
+        .fold(0.0_f64, f64::max)
+        .max(1e-12);
+    let step = 1.0 / lipschitz;
+    let threshold = step * cost_bps / 10_000.0;
+
+    let mut w = base;
+    for _ in 0..2000 {
+        let sigma_w = mat_vec_mul(cov, &w);
+        let candidate: Vec<f64> = w
+            .iter()
+            .zip(&sigma_w)
+            .map(|(wi, sw)| wi - step * 2.0 * sw)
+            .collect();
+        let next = project_box_simplex_shrink(
+            &candidate,
+            min_weights,
+            max_weights,
+            current_weights,
+            threshold,
+        );
+
+        if squared_distance(&next, &w) < 1e-20 {
+            return next;
+        }
+        w = next;
+    }
+
+    w
+}
+
+/// Each asset's contribution to portfolio variance, `w[i] * (cov * w)[i]`.
+///
+/// The contributions sum to the portfolio variance `w' * cov * w`; divide by
+/// that total for percentage shares. A risk-parity portfolio has equal
+/// contributions. Returns an empty vector on invalid input.
+pub fn risk_contributions(weights: &[f64], cov: &[Vec<f64>]) -> Vec<f64> {
+    let n = weights.len();
+    if n == 0 || !is_square_finite(cov, n) || weights.iter().any(|w| !w.is_finite()) {
+        return Vec::new();
+    }
+
+    let sigma_w = mat_vec_mul(cov, weights);
+    weights.iter().zip(&sigma_w).map(|(w, sw)| w * sw).collect()
+}
+
+/// Marginal contribution to portfolio volatility, `(cov * w)[i] / sqrt(w' * cov * w)`.
+///
+/// This is the gradient of portfolio volatility with respect to each weight,
+/// so `sum(w[i] * marginal[i])` equals the volatility. A zero-variance
+/// portfolio has zero marginal risk everywhere. Returns an empty vector on
+/// invalid input.
+pub fn marginal_risk(weights: &[f64], cov: &[Vec<f64>]) -> Vec<f64> {
+    let n = weights.len();
+    if n == 0 || !is_square_finite(cov, n) || weights.iter().any(|w| !w.is_finite()) {
+        return Vec::new();
+    }
+
+    let sigma_w = mat_vec_mul(cov, weights);
+    let vol = dot(weights, &sigma_w).max(0.0).sqrt();
+    if vol <= 1e-300 {
+        return vec![0.0; n];
+    }
+    sigma_w.iter().map(|sw| sw / vol).collect()
+}
+
+fn is_square_finite(matrix: &[Vec<f64>], n: usize) -> bool {
+    matrix.len() == n
+        && matrix
+            .iter()
+            .all(|row| row.len() == n && row.iter().all(|x| x.is_finite()))
+}
+
+/// Projected-gradient solve of `min w' * cov * w - lambda * mu' * w` on the bounded simplex.
+fn box_mean_variance(
+    mu: &[f64],
+    cov: &[Vec<f64>],
+    lo: &[f64],
+    hi: &[f64],
+    lambda: f64,
+) -> Vec<f64> {
+    // Gershgorin bound on the Hessian's largest eigenvalue gives a safe step size.
+    let lipschitz = cov
+        .iter()
+        .map(|row| 2.0 * row.iter().map(|x| x.abs()).sum::<f64>())
+        .fold(0.0_f64, f64::max)
+        .max(1e-12);
+    let step = 1.0 / lipschitz;
+
+    let mut w = project_box_simplex(&equal_weights(mu.len()), lo, hi);
+    for _ in 0..2000 {
+        let sigma_w = mat_vec_mul(cov, &w);
+        let candidate: Vec<f64> = w
+            .iter()
+            .zip(&sigma_w)
+            .zip(mu)
+            .map(|((wi, sw), m)| wi - step * (2.0 * sw - lambda * m))
+            .collect();
+        let projected = project_box_simplex(&candidate, lo, hi);
+
+        if squared_distance(&projected, &w) < 1e-20 {
+            return projected;
+        }
+        w = projected;
+    }
+
+    w
+}
+
+/// Euclidean projection onto `{w : sum(w) == 1, lo <= w <= hi}`.
+///
+/// Bisects on the shift `tau` such that `sum(clamp(v - tau, lo, hi)) == 1`.
+/// Callers must ensure `sum(lo) <= 1 <= sum(hi)`.
+fn project_box_simplex(v: &[f64], lo: &[f64], hi: &[f64]) -> Vec<f64> {
+    let clamped_sum = |tau: f64| -> f64 {
+        v.iter()
+            .zip(lo.iter().zip(hi))
+            .map(|(x, (l, h))| (x - tau).clamp(*l, *h))
+            .sum()
+    };
+
+    let mut tau_lo = v
+        .iter()
+        .zip(hi)
+        .map(|(x, h)| x - h)
+        .fold(f64::INFINITY, f64::min);
+    let mut tau_hi = v
+        .iter()
+        .zip(lo)
+        .map(|(x, l)| x - l)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    for _ in 0..100 {
+        let mid = 0.5 * (tau_lo + tau_hi);
+        if clamped_sum(mid) > 1.0 {
+            tau_lo = mid;
+        } else {
+            tau_hi = mid;
+        }
+    }
+
+    let tau = 0.5 * (tau_lo + tau_hi);
+    v.iter()
+        .zip(lo.iter().zip(hi))
+        .map(|(x, (l, h))| (x - tau).clamp(*l, *h))
+        .collect()
+}
+
+/// Proximal step for `threshold * sum(|w - center|)` on the bounded simplex.
+///
+/// For a fixed shift `tau` each coordinate is soft-thresholded around
+/// `center` and clamped to its box; bisects on `tau` so the result sums to
+/// one. Callers must ensure `sum(lo) <= 1 <= sum(hi)`.
+fn project_box_simplex_shrink(
+    v: &[f64],
+    lo: &[f64],
+    hi: &[f64],
+    center: &[f64],
+    threshold: f64,
+) -> Vec<f64> {
+    let coordinate = |i: usize, tau: f64| -> f64 {
+        let d = v[i] - tau - center[i];
+        let shrunk = d.signum() * (d.abs() - threshold).max(0.0);
+        (center[i] + shrunk).clamp(lo[i], hi[i])
+    };
+    let n = v.len();
+
+    let mut tau_lo = (0..n)
+        .map(|i| v[i] - hi[i] - threshold)
+        .fold(f64::INFINITY, f64::min);
+    let mut tau_hi = (0..n)
+        .map(|i| v[i] - lo[i] + threshold)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    for _ in 0..100 {
+        let mid = 0.5 * (tau_lo + tau_hi);
+        if (0..n).map(|i| coordinate(i, mid)).sum::<f64>() > 1.0 {
+            tau_lo = mid;
+        } else {
+            tau_hi = mid;
+        }
+    }
+
+    let tau = 0.5 * (tau_lo + tau_hi);
+    (0..n).map(|i| coordinate(i, tau)).collect()
+}
+
+/// Highest-return allocation on the bounded simplex (greedy fill by `mu`).
+fn max_return_weights(mu: &[f64], lo: &[f64], hi: &[f64]) -> Vec<f64> {
+    let mut w = lo.to_vec();
+    let mut budget = 1.0 - lo.iter().sum::<f64>();
+
+    let mut order: Vec<usize> = (0..mu.len()).collect();
+    order.sort_by(|&a, &b| {
+        mu[b]
+            .partial_cmp(&mu[a])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    for i in order {
+        if budget <= 0.0 {
+            break;
+        }
+        let add = (hi[i] - lo[i]).min(budget);
+        w[i] += add;
+        budget -= add;
+    }
+
+    w
+}
+
+/// Pivots allowed per simplex phase before giving up.
+const LP_MAX_PIVOTS: usize = 50_000;
+
+/// Consecutive degenerate pivots before switching to Bland's rule.
+const LP_DEGENERATE_STREAK: usize = 50;
+
+const LP_EPS: f64 = 1e-9;
+
+/// Dense two-phase simplex for `min c' * x` subject to `A * x == b`, `x >= 0`.
+///
+/// Uses Dantzig's entering rule, falling back to Bland's rule during long
+/// runs of degenerate pivots so it cannot cycle. Returns `None` if the
+/// problem is infeasible, unbounded, or exceeds the pivot budget.
+fn lp_minimize(cost: &[f64], mut rows: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Option<Vec<f64>> {
+    let m = rows.len();
+    let vars = cost.len();
+    let width = vars + m;
+
+    // Tableau rows hold [A | I_artificial | b] with b >= 0.
+    let mut tableau: Vec<Vec<f64>> = Vec::with_capacity(m + 1);
+    for (r, (mut row, mut b)) in rows.drain(..).zip(rhs.drain(..)).enumerate() {
+        if b < 0.0 {
+            row.iter_mut().for_each(|a| *a = -*a);
+            b = -b;
+        }
+        row.resize(width + 1, 0.0);
+        row[vars + r] = 1.0;
+        row[width] = b;
+        tableau.push(row);
+    }
+    let mut basis: Vec<usize> = (vars..width).collect();
+
+    // Phase 1: minimize the sum of artificials.
+    let mut objective = vec![0.0; width + 1];
+    for row in &tableau {
+        for (o, a) in objective[..vars].iter_mut().zip(row) {
+            *o -= a;
+        }
+        objective[width] -= row[width];
+    }
+    tableau.push(objective);
+    lp_iterate(&mut tableau, &mut basis, width)?;
+    if -tableau[m][width] > 1e-7 {
+        return None;
+    }
+
+    // Pivot leftover (zero-valued) artificials out where possible.
+    for r in 0..m {
+        if basis[r] >= vars
+            && let Some(col) = (0..vars).find(|&j| tableau[r][j].abs() > LP_EPS)
+        {
+            lp_pivot(&mut tableau, &mut basis, r, col);
+        }
+    }
+
+    // Phase 2: the real objective, with artificials barred from entering.
+    let mut objective = vec![0.0; width + 1];
+    objective[..vars].copy_from_slice(cost);
+    for r in 0..m {
+        let c = if basis[r] < vars { cost[basis[r]] } else { 0.0 };
+        if c != 0.0 {
+            for (o, a) in objective.iter_mut().zip(&tableau[r]) {
+                *o -= c * a;
+            }
+        }
+    }
+    tableau[m] = objective;
+    lp_iterate(&mut tableau, &mut basis, vars)?;
+
+    let mut x = vec![0.0; vars];
+    for (r, &j) in basis.iter().enumerate() {
+        if j < vars {
+            x[j] = tableau[r][width];
+        }
+    }
+    Some(x)
+}
+
+/// Run simplex pivots until optimal, considering columns `0..entering_limit`.
+fn lp_iterate(tableau: &mut [Vec<f64>], basis: &mut [usize], entering_limit: usize) -> Option<()> {
+    let m = basis.len();
+    let rhs = tableau[0].len() - 1;
+    let mut degenerate = 0;
+
+    for _ in 0..LP_MAX_PIVOTS {
+        let reduced = &tableau[m][..entering_limit];
+        let entering = if degenerate < LP_DEGENERATE_STREAK {
+            reduced
+                .iter()
+                .enumerate()
+                .filter(|(_, d)| **d < -LP_EPS)
+                .min_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(j, _)| j)
+        } else {
+            reduced.iter().position(|d| *d < -LP_EPS)
+        };
+        let Some(col) = entering else {
+            return Some(());
+        };
+
+        let mut leaving: Option<(usize, f64)> = None;
+        for r in 0..m {
+            let a = tableau[r][col];
+            if a > LP_EPS {
+                let ratio = tableau[r][rhs] / a;
+                let better = leaving.is_none_or(|(best, best_ratio)| {
+                    ratio < best_ratio - LP_EPS
+                        || (ratio <= best_ratio + LP_EPS && basis[r] < basis[best])
+                });
+                if better {
+                    leaving = Some((r, ratio));
+                }
+            }
+        }
+        let (row, ratio) = leaving?;
+
+        degenerate = if ratio <= LP_EPS { degenerate + 1 } else { 0 };
+        lp_pivot(tableau, basis, row, col);
+    }
+    None
+}
+
+fn lp_pivot(tableau: &mut [Vec<f64>], basis: &mut [usize], row: usize, col: usize) {
+    let pivot = tableau[row][col];
+    tableau[row].iter_mut().for_each(|a| *a /= pivot);
+    let pivot_row = tableau[row].clone();
+    for (r, other) in tableau.iter_mut().enumerate() {
+        if r == row {
+            continue;
+        }
+        let factor = other[col];
+        if factor != 0.0 {
+            for (a, p) in other.iter_mut().zip(&pivot_row) {
+                *a -= factor * p;
+            }
+        }
+    }
+    basis[row] = col;
+}
+
 fn matrix_shape(matrix: &[Vec<f64>]) -> Option<(usize, usize)> {
     let rows = matrix.len();
     if rows < 2 {
@@ -431,6 +1130,42 @@ mod tests {
         assert_valid_weights(&w, 3);
     }
 
+    #[test]
+    fn iterative_optimizers_are_bit_identical_across_calls() {
+        let r = qtrade_reference_returns();
+        let bits = |w: &[f64]| w.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+        let mv = bits(&optimize_min_variance(&r));
+        let rp = bits(&optimize_risk_parity(&r));
+        for _ in 0..100 {
+            assert_eq!(bits(&optimize_min_variance(&r)), mv);
+            assert_eq!(bits(&optimize_risk_parity(&r)), rp);
+        }
+    }
+
+    #[test]
+    fn tolerance_and_max_iter_control_convergence() {
+        let r = factor_returns();
+        let loose = optimize_risk_parity_ext(&r, RISK_PARITY_MAX_ITER, 1e-3);
+        let default = optimize_risk_parity_ext(&r, RISK_PARITY_MAX_ITER, OPTIMIZER_TOLERANCE);
+        let tight = optimize_risk_parity_ext(&r, 10_000, 1e-14);
+        assert!(loose.converged && default.converged && tight.converged);
+        assert!(loose.iterations < default.iterations);
+        assert!(default.iterations < tight.iterations);
+        assert_eq!(default.weights, optimize_risk_parity(&r));
+        // Tighter tolerances land closer to the fixed point.
+        let gap = |w: &[f64]| squared_distance(w, &tight.weights);
+        assert!(gap(&loose.weights) > gap(&default.weights));
+
+        let capped = optimize_min_variance_ext(&r, 3, 0.0);
+        assert!(!capped.converged);
+        assert_eq!(capped.iterations, 3);
+        assert_valid_weights(&capped.weights, 3);
+
+        let single = optimize_min_variance_ext(&[vec![0.01], vec![0.02]], 0, 0.0);
+        assert_eq!((single.weights, single.converged), (vec![1.0], true));
+        assert!(optimize_risk_parity_ext(&[], 10, 1e-8).weights.is_empty());
+    }
+
     #[test]
     fn max_sharpe_weights_are_valid() {
         let r = sample_returns();
@@ -515,4 +1250,264 @@ mod tests {
         assert_close(&cvar, &[0.1875, 0.3750, 0.1875, 0.2500], 1e-15);
         assert_close(&cdar, &[0.1875, 0.3750, 0.1875, 0.2500], 1e-12);
     }
+    fn two_asset_cov() -> Vec<Vec<f64>> {
+        vec![vec![0.04, 0.006], vec![0.006, 0.09]]
+    }
+
+    #[test]
+    fn mean_variance_respects_box_and_budget() {
+        let cov = two_asset_cov();
+        let w = optimize_mean_variance(&[0.05, 0.10], &cov, &[0.0, 0.0], &[1.0, 1.0], None);
+        assert_valid_weights(&w, 2);
+        // Unconstrained min-variance puts ~70% in the low-vol asset.
+        assert!(w[0] > 0.6);
+
+        let capped = optimize_mean_variance(&[0.05, 0.10], &cov, &[0.0, 0.0], &[0.4, 1.0], None);
+        assert_valid_weights(&capped, 2);
+        assert!((capped[0] - 0.4).abs() < 1e-9, "capped={capped:?}");
+    }
+
+    #[test]
+    fn mean_variance_target_return_shifts_weight() {
+        let cov = two_asset_cov();
+        let mu = [0.05, 0.10];
+        let base = optimize_mean_variance(&mu, &cov, &[0.0, 0.0], &[1.0, 1.0], None);
+        let tilted = optimize_mean_variance(&mu, &cov, &[0.0, 0.0], &[1.0, 1.0], Some(0.09));
+        assert_valid_weights(&tilted, 2);
+        assert!(tilted[1] > base[1]);
+        assert!(dot(&mu, &tilted) >= 0.09 - 1e-9);
+        // The floor should bind: no need to overshoot it.
+        assert!(dot(&mu, &tilted) < 0.09 + 1e-6);
+    }
+
+    #[test]
+    fn mean_variance_infeasible_returns_empty() {
+        let cov = two_asset_cov();
+        let mu = [0.05, 0.10];
+        // Bounds cannot sum to one.
+        assert!(optimize_mean_variance(&mu, &cov, &[0.0, 0.0], &[0.3, 0.3], None).is_empty());
+        assert!(optimize_mean_variance(&mu, &cov, &[0.6, 0.6], &[1.0, 1.0], None).is_empty());
+        // Target above the best achievable return.
+        assert!(optimize_mean_variance(&mu, &cov, &[0.0, 0.0], &[1.0, 1.0], Some(0.2)).is_empty());
+        // Shape mismatch.
+        assert!(optimize_mean_variance(&mu, &cov, &[0.0], &[1.0, 1.0], None).is_empty());
+    }
+
+    fn turnover(w: &[f64], current: &[f64]) -> f64 {
+        w.iter().zip(current).map(|(a, b)| (a - b).abs()).sum()
+    }
+
+    #[test]
+    fn min_variance_tc_zero_cost_matches_min_variance() {
+        let r = sample_returns();
+        let cov = covariance_matrix(&r);
+        let (lo, hi) = ([0.0; 3], [1.0; 3]);
+        let w = optimize_min_variance_tc(&cov, &[0.2, 0.3, 0.5], 0.0, &lo, &hi);
+        assert_valid_weights(&w, 3);
+        assert_close(
+            &w,
+            &optimize_mean_variance(&[0.0; 3], &cov, &lo, &hi, None),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn min_variance_tc_cost_reduces_turnover() {
+        let cov = vec![
+            vec![0.04, 0.006, 0.002],
+            vec![0.006, 0.09, 0.01],
+            vec![0.002, 0.01, 0.0625],
+        ];
+        let current = [0.2, 0.5, 0.3];
+        let (lo, hi) = ([0.0; 3], [1.0; 3]);
+
+        let mut last = f64::INFINITY;
+        for cost_bps in [0.0, 50.0, 200.0, 500.0] {
+            let w = optimize_min_variance_tc(&cov, &current, cost_bps, &lo, &hi);
+            assert_valid_weights(&w, 3);
+            let t = turnover(&w, &current);
+            assert!(t < last, "cost_bps={cost_bps} turnover={t} previous={last}");
+            last = t;
+        }
+
+        // Trading is never worth it once the cost dwarfs the variance gain.
+        let frozen = optimize_min_variance_tc(&cov, &current, 10_000.0, &lo, &hi);
+        assert_close(&frozen, &current, 1e-9);
+    }
+
+    #[test]
+    fn min_variance_tc_invalid_inputs_return_empty() {
+        let cov = two_asset_cov();
+        let current = [0.5, 0.5];
+        assert!(optimize_min_variance_tc(&cov, &current, -1.0, &[0.0; 2], &[1.0; 2]).is_empty());
+        assert!(optimize_min_variance_tc(&cov, &current, 10.0, &[0.0; 2], &[0.3; 2]).is_empty());
+        assert!(optimize_min_variance_tc(&cov, &[0.5], 10.0, &[0.0; 2], &[1.0; 2]).is_empty());
+    }
+
+    #[test]
+    fn risk_parity_contributions_are_equal() {
+        let r = factor_returns();
+        let cov = covariance_matrix(&r);
+        let w = optimize_risk_parity(&r);
+
+        let rc = risk_contributions(&w, &cov);
+        let total: f64 = rc.iter().sum();
+        assert!((total - dot(&w, &mat_vec_mul(&cov, &w))).abs() < 1e-15);
+        for c in &rc {
+            assert!(
+                (c / total - 1.0 / 3.0).abs() < 1e-3,
+                "shares={rc:?} total={total}"
+            );
+        }
+    }
+
+    #[test]
+    fn marginal_risk_recovers_volatility() {
+        let cov = vec![vec![0.04, 0.006], vec![0.006, 0.09]];
+        let w = [0.7, 0.3];
+        let marginal = marginal_risk(&w, &cov);
+        let vol = dot(&w, &mat_vec_mul(&cov, &w)).sqrt();
+        assert!((dot(&w, &marginal) - vol).abs() < 1e-12);
+        assert_close(
+            &risk_contributions(&w, &cov),
+            &[
+                0.7 * (0.7 * 0.04 + 0.3 * 0.006),
+                0.3 * (0.7 * 0.006 + 0.3 * 0.09),
+            ],
+            1e-15,
+        );
+
+        assert_eq!(marginal_risk(&[0.0, 0.0], &cov), vec![0.0, 0.0]);
+        assert!(risk_contributions(&[1.0], &cov).is_empty());
+        assert!(marginal_risk(&[f64::NAN, 1.0], &cov).is_empty());
+    }
+
+    fn diversification_ratio(cov: &[Vec<f64>], vols: &[f64], w: &[f64]) -> f64 {
+        dot(w, vols) / dot(w, &mat_vec_mul(cov, w)).sqrt()
+    }
+
+    #[test]
+    fn max_diversification_equal_correlation_is_equal_weight() {
+        let vol = 0.2;
+        let rho = 0.3;
+        let cov: Vec<Vec<f64>> = (0..4)
+            .map(|i| {
+                (0..4)
+                    .map(|j| if i == j { vol * vol } else { rho * vol * vol })
+                    .collect()
+            })
+            .collect();
+        let w = optimize_max_diversification(&cov, &[vol; 4]);
+        assert_close(&w, &[0.25; 4], 1e-9);
+    }
+
+    #[test]
+    fn max_diversification_beats_equal_weight() {
+        let vols = [0.10, 0.20, 0.30];
+        let corr = [[1.0, 0.8, 0.1], [0.8, 1.0, 0.2], [0.1, 0.2, 1.0]];
+        let cov: Vec<Vec<f64>> = (0..3)
+            .map(|i| (0..3).map(|j| corr[i][j] * vols[i] * vols[j]).collect())
+            .collect();
+
+        let w = optimize_max_diversification(&cov, &vols);
+        assert_valid_weights(&w, 3);
+        let eq = equal_weights(3);
+        assert!(diversification_ratio(&cov, &vols, &w) >= diversification_ratio(&cov, &vols, &eq));
+    }
+
+    #[test]
+    fn max_diversification_degenerate_inputs() {
+        assert_eq!(
+            optimize_max_diversification(&[vec![0.04]], &[0.2]),
+            vec![1.0]
+        );
+
+        let cov = vec![vec![0.04, 0.0], vec![0.0, 0.0]];
+        let w = optimize_max_diversification(&cov, &[0.2, 0.0]);
+        assert_close(&w, &[1.0, 0.0], 1e-12);
+
+        let zero = vec![vec![0.0, 0.0], vec![0.0, 0.0]];
+        assert_close(
+            &optimize_max_diversification(&zero, &[0.0, 0.0]),
+            &[0.5, 0.5],
+            1e-12,
+        );
+
+        assert!(optimize_max_diversification(&cov, &[0.2]).is_empty());
+    }
+
+    /// Two ordinary assets and a third with low variance but rare crashes.
+    fn crash_scenarios() -> Vec<Vec<f64>> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut uniform = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+        };
+        (0..200)
+            .map(|s| {
+                let a = 0.02 * uniform();
+                let b = 0.02 * uniform();
+                let c = if s % 70 == 13 {
+                    -0.08
+                } else {
+                    0.004 * uniform()
+                };
+                vec![a, b, c]
+            })
+            .collect()
+    }
+
+    /// One common factor plus idiosyncratic noise of increasing size.
+    fn factor_returns() -> Vec<Vec<f64>> {
+        crash_scenarios()
+            .iter()
+            .map(|row| {
+                let (market, a, b) = (row[0], row[1], row[2]);
+                vec![market + 0.5 * a, market + 1.5 * b, 2.0 * market + a - b]
+            })
+            .collect()
+    }
+
+    fn scenario_cvar(scenarios: &[Vec<f64>], w: &[f64], alpha: f64) -> f64 {
+        let mut losses: Vec<f64> = scenarios.iter().map(|r| -dot(r, w)).collect();
+        losses.sort_by(|a, b| b.total_cmp(a));
+        let k = ((1.0 - alpha) * losses.len() as f64).round() as usize;
+        losses[..k].iter().sum::<f64>() / k as f64
+    }
+
+    #[test]
+    fn cvar_scenarios_underweights_crash_asset() {
+        let r = crash_scenarios();
+        let w = optimize_cvar_scenarios(&r, 0.95, &[0.0; 3], &[1.0; 3]);
+        assert_valid_weights(&w, 3);
+
+        let mv = optimize_min_variance(&r);
+        assert!(w[2] < mv[2], "cvar={w:?} min_var={mv:?}");
+        assert!(scenario_cvar(&r, &w, 0.95) <= scenario_cvar(&r, &mv, 0.95) + 1e-12);
+        assert!(scenario_cvar(&r, &w, 0.95) <= scenario_cvar(&r, &equal_weights(3), 0.95));
+    }
+
+    #[test]
+    fn cvar_scenarios_respects_box() {
+        let r = crash_scenarios();
+        let lo = [0.1, 0.1, 0.3];
+        let hi = [0.5, 0.5, 0.6];
+        let w = optimize_cvar_scenarios(&r, 0.95, &lo, &hi);
+        assert_valid_weights(&w, 3);
+        for i in 0..3 {
+            assert!(w[i] >= lo[i] - 1e-9 && w[i] <= hi[i] + 1e-9, "w={w:?}");
+        }
+        assert!((w[2] - 0.3).abs() < 1e-9, "w={w:?}");
+    }
+
+    #[test]
+    fn cvar_scenarios_infeasible_returns_empty() {
+        let r = crash_scenarios();
+        assert!(optimize_cvar_scenarios(&r, 0.95, &[0.0; 3], &[0.3; 3]).is_empty());
+        assert!(optimize_cvar_scenarios(&r, 0.95, &[0.5; 3], &[1.0; 3]).is_empty());
+        assert!(optimize_cvar_scenarios(&r, 0.95, &[0.0; 2], &[1.0; 2]).is_empty());
+        assert!(optimize_cvar_scenarios(&[], 0.95, &[], &[]).is_empty());
+    }
 }
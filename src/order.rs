@@ -1,6 +1,9 @@
 //! Order representation and lifecycle
 
-use crate::{OrderId, Price, Quantity, Side, TimeInForce, Timestamp};
+use crate::{OrderId, Price, Quantity, Side, TimeInForce, Timestamp, Trade, TradeId};
+
+/// One fill of an order: `(trade id, price, quantity, timestamp)`.
+pub type Execution = (TradeId, Price, Quantity, Timestamp);
 
 /// Status of an order in its lifecycle.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
@@ -53,6 +56,12 @@ pub struct Order {
     pub time_in_force: TimeInForce,
     /// Current lifecycle status
     pub status: OrderStatus,
+    /// Fills in execution order, recorded by the matching engine
+    ///
+    /// Kept while the order is in history; dropped with it by
+    /// `Exchange::clear_order_history`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub executions: Vec<Execution>,
     /// Position index within the price level queue (for O(1) cancel)
     pub(crate) position_in_level: usize,
 }
@@ -80,6 +89,7 @@ impl Order {
             timestamp,
             time_in_force,
             status: OrderStatus::New,
+            executions: Vec::new(),
             position_in_level: 0,
         }
     }
@@ -115,6 +125,18 @@ impl Order {
         };
     }
 
+    /// Fill the order by `trade`'s quantity and append the trade to
+    /// [`executions`](Self::executions).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `trade.quantity > remaining_quantity`.
+    pub fn fill_trade(&mut self, trade: &Trade) {
+        self.fill(trade.quantity);
+        self.executions
+            .push((trade.id, trade.price, trade.quantity, trade.timestamp));
+    }
+
     /// Cancel the order, setting status to Cancelled.
     ///
     /// Returns the quantity that was cancelled (remaining at time of cancel).
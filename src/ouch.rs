@@ -0,0 +1,355 @@
+//! NASDAQ OUCH 4.2 order-entry parser and nanobook Event conversion.
+//!
+//! Input is a captured SoupBinTCP session: each packet is a 2-byte big-endian
+//! length followed by a packet type and payload. Unsequenced data packets
+//! (`'U'`) carry the messages we sent (Enter Order, Replace, Cancel);
+//! sequenced data packets (`'S'`) carry the exchange's replies (Accepted,
+//! Executed). Other SoupBinTCP packets (login, heartbeats) are skipped.
+//!
+//! [`OuchReplay`] turns the session into [`Event`]s for [`Exchange::replay`],
+//! mapping OUCH order tokens to the [`OrderId`]s a fresh exchange assigns.
+//!
+//! [`Exchange::replay`]: crate::Exchange::replay
+
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Result};
+
+use crate::{Event, OrderId, Price, Quantity, Side, TimeInForce};
+
+/// OUCH 4.2 message (the subset needed to replay an order-entry session).
+#[derive(Debug, Clone, PartialEq)]
+pub enum OuchMessage {
+    /// Inbound `O`: a new order.
+    EnterOrder {
+        token: String,
+        side: Side,
+        shares: u32,
+        stock: String,
+        price: u32,
+        time_in_force: u32,
+    },
+    /// Inbound `U`: cancel/replace an existing order under a new token.
+    ReplaceOrder {
+        existing_token: String,
+        replacement_token: String,
+        shares: u32,
+        price: u32,
+    },
+    /// Inbound `X`: reduce an order to `shares` (0 cancels it).
+    CancelOrder { token: String, shares: u32 },
+    /// Outbound `A`: the exchange accepted an order.
+    Accepted {
+        timestamp: u64,
+        token: String,
+        side: Side,
+        shares: u32,
+        stock: String,
+        price: u32,
+        time_in_force: u32,
+        order_ref: u64,
+    },
+    /// Outbound `E`: some of an order executed.
+    Executed {
+        timestamp: u64,
+        token: String,
+        shares: u32,
+        price: u32,
+        match_number: u64,
+    },
+    /// Any other message, by direction (`'U'` or `'S'`) and type.
+    Other { packet: char, msg_type: char },
+}
+
+/// OUCH time-in-force value for immediate-or-cancel.
+const TIF_IOC: u32 = 0;
+
+/// Parser for SoupBinTCP-framed OUCH 4.2 sessions.
+pub struct OuchParser<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> OuchParser<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Read the next OUCH message, skipping non-data SoupBinTCP packets.
+    ///
+    /// Returns `Ok(None)` at the end of the capture.
+    pub fn next_message(&mut self) -> Result<Option<OuchMessage>> {
+        loop {
+            if self.data.is_empty() {
+                return Ok(None);
+            }
+            if self.data.len() < 2 {
+                return Err(invalid("truncated SoupBinTCP length".into()));
+            }
+            let len = u16::from_be_bytes([self.data[0], self.data[1]]) as usize;
+            if len == 0 || self.data.len() < 2 + len {
+                return Err(invalid(format!(
+                    "SoupBinTCP packet of {len} bytes exceeds remaining {}",
+                    self.data.len() - 2
+                )));
+            }
+            let packet = &self.data[2..2 + len];
+            self.data = &self.data[2 + len..];
+
+            let packet_type = packet[0] as char;
+            let msg = &packet[1..];
+            match packet_type {
+                'U' | 'S' if !msg.is_empty() => {
+                    return parse_message(packet_type, msg).map(Some);
+                }
+                _ => continue,
+            }
+        }
+    }
+}
+
+/// Minimum message lengths (including the type byte) per OUCH 4.2.
+fn min_len(packet: char, msg_type: char) -> usize {
+    match (packet, msg_type) {
+        ('U', 'O') => 49,
+        ('U', 'U') => 47,
+        ('U', 'X') => 19,
+        ('S', 'A') => 66,
+        ('S', 'E') => 40,
+        _ => 1,
+    }
+}
+
+fn parse_message(packet: char, msg: &[u8]) -> Result<OuchMessage> {
+    let msg_type = msg[0] as char;
+    let need = min_len(packet, msg_type);
+    if msg.len() < need {
+        return Err(invalid(format!(
+            "OUCH '{msg_type}' message too short: {} bytes, need {need}",
+            msg.len()
+        )));
+    }
+
+    Ok(match (packet, msg_type) {
+        ('U', 'O') => OuchMessage::EnterOrder {
+            token: alpha(&msg[1..15]),
+            side: side(msg[15]),
+            shares: be_u32(&msg[16..20]),
+            stock: alpha(&msg[20..28]),
+            price: be_u32(&msg[28..32]),
+            time_in_force: be_u32(&msg[32..36]),
+        },
+        ('U', 'U') => OuchMessage::ReplaceOrder {
+            existing_token: alpha(&msg[1..15]),
+            replacement_token: alpha(&msg[15..29]),
+            shares: be_u32(&msg[29..33]),
+            price: be_u32(&msg[33..37]),
+        },
+        ('U', 'X') => OuchMessage::CancelOrder {
+            token: alpha(&msg[1..15]),
+            shares: be_u32(&msg[15..19]),
+        },
+        ('S', 'A') => OuchMessage::Accepted {
+            timestamp: be_u64(&msg[1..9]),
+            token: alpha(&msg[9..23]),
+            side: side(msg[23]),
+            shares: be_u32(&msg[24..28]),
+            stock: alpha(&msg[28..36]),
+            price: be_u32(&msg[36..40]),
+            time_in_force: be_u32(&msg[40..44]),
+            order_ref: be_u64(&msg[49..57]),
+        },
+        ('S', 'E') => OuchMessage::Executed {
+            timestamp: be_u64(&msg[1..9]),
+            token: alpha(&msg[9..23]),
+            shares: be_u32(&msg[23..27]),
+            price: be_u32(&msg[27..31]),
+            match_number: be_u64(&msg[32..40]),
+        },
+        _ => OuchMessage::Other { packet, msg_type },
+    })
+}
+
+/// `B` is buy; `S`, `T` (short) and `E` (short exempt) are sells.
+fn side(byte: u8) -> Side {
+    if byte == b'B' { Side::Buy } else { Side::Sell }
+}
+
+fn alpha(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).trim_end().to_string()
+}
+
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes(bytes.try_into().unwrap())
+}
+
+fn be_u64(bytes: &[u8]) -> u64 {
+    u64::from_be_bytes(bytes.try_into().unwrap())
+}
+
+fn invalid(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+/// OUCH price (4 implied decimals) to nanobook cents.
+fn to_price(ouch_price: u32) -> Price {
+    Price(i64::from(ouch_price / 100))
+}
+
+/// Live state of one of our orders during replay.
+#[derive(Debug, Clone, Copy)]
+struct LiveOrder {
+    id: OrderId,
+    price: Price,
+    remaining: Quantity,
+}
+
+/// Converts OUCH messages into replayable [`Event`]s.
+///
+/// Order ids follow a fresh [`Exchange`](crate::Exchange): every submit and
+/// every successful modify takes the next id, starting at 1. An order is
+/// submitted from its Enter Order message, or from its Accepted message if
+/// the capture only holds the outbound side. Executions only track the
+/// remaining quantity (the exchange reproduces the fills itself), so cancels
+/// and replaces of fully executed orders are dropped.
+#[derive(Debug)]
+pub struct OuchReplay {
+    next_id: u64,
+    orders: HashMap<String, LiveOrder>,
+}
+
+impl Default for OuchReplay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OuchReplay {
+    pub fn new() -> Self {
+        Self {
+            next_id: 1,
+            orders: HashMap::new(),
+        }
+    }
+
+    /// Convert one message; `None` if it does not change the book.
+    pub fn to_event(&mut self, msg: &OuchMessage) -> Option<Event> {
+        match msg {
+            OuchMessage::EnterOrder {
+                token,
+                side,
+                shares,
+                price,
+                time_in_force,
+                ..
+            }
+            | OuchMessage::Accepted {
+                token,
+                side,
+                shares,
+                price,
+                time_in_force,
+                ..
+            } => {
+                if self.orders.contains_key(token) || *shares == 0 {
+                    return None;
+                }
+                let price = to_price(*price);
+                let quantity = Quantity::from(*shares);
+                let id = self.take_id();
+                self.orders.insert(
+                    token.clone(),
+                    LiveOrder {
+                        id,
+                        price,
+                        remaining: quantity,
+                    },
+                );
+                let time_in_force = if *time_in_force == TIF_IOC {
+                    TimeInForce::IOC
+                } else {
+                    TimeInForce::GTC
+                };
+                Some(Event::submit_limit(*side, price, quantity, time_in_force))
+            }
+            OuchMessage::ReplaceOrder {
+                existing_token,
+                replacement_token,
+                shares,
+                price,
+            } => {
+                let old = self.live(existing_token)?;
+                self.orders.remove(existing_token);
+                let new_quantity = Quantity::from(*shares);
+                if new_quantity == 0 {
+                    return Some(Event::cancel(old.id));
+                }
+                let new_price = to_price(*price);
+                let id = self.take_id();
+                self.orders.insert(
+                    replacement_token.clone(),
+                    LiveOrder {
+                        id,
+                        price: new_price,
+                        remaining: new_quantity,
+                    },
+                );
+                Some(Event::modify(old.id, new_price, new_quantity))
+            }
+            OuchMessage::CancelOrder { token, shares } => {
+                let old = self.live(token)?;
+                let target = Quantity::from(*shares);
+                if target == 0 {
+                    self.orders.remove(token);
+                    return Some(Event::cancel(old.id));
+                }
+                if target >= old.remaining {
+                    return None;
+                }
+                // Partial cancel: re-enter the smaller size at the same price.
+                let id = self.take_id();
+                self.orders.insert(
+                    token.clone(),
+                    LiveOrder {
+                        id,
+                        price: old.price,
+                        remaining: target,
+                    },
+                );
+                Some(Event::modify(old.id, old.price, target))
+            }
+            OuchMessage::Executed { token, shares, .. } => {
+                if let Some(order) = self.orders.get_mut(token) {
+                    order.remaining = order.remaining.saturating_sub(Quantity::from(*shares));
+                }
+                None
+            }
+            OuchMessage::Other { .. } => None,
+        }
+    }
+
+    fn take_id(&mut self) -> OrderId {
+        let id = OrderId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    fn live(&self, token: &str) -> Option<LiveOrder> {
+        self.orders
+            .get(token)
+            .copied()
+            .filter(|order| order.remaining > 0)
+    }
+}
+
+/// Parse a SoupBinTCP-framed OUCH 4.2 capture into replayable events.
+///
+/// Parsing stops at the first malformed packet; use [`OuchParser`] directly
+/// to see the error.
+pub fn parse_ouch(bytes: &[u8]) -> Vec<Event> {
+    let mut parser = OuchParser::new(bytes);
+    let mut replay = OuchReplay::new();
+    let mut events = Vec::new();
+    while let Ok(Some(msg)) = parser.next_message() {
+        events.extend(replay.to_event(&msg));
+    }
+    events
+}
@@ -3,6 +3,9 @@
 //! Events are stored as one JSON object per line (`.jsonl` format).
 //! This is simple, streamable, and human-readable.
 //!
+//! For large logs, [`Exchange::save_binary`] writes a compact postcard
+//! encoding behind a magic-byte header and format version.
+//!
 //! # Usage
 //!
 //! ```ignore
@@ -14,9 +17,17 @@
 //!
 //! // Load from file
 //! let exchange = Exchange::load(Path::new("orders.jsonl")).unwrap();
+//!
+//! // Incremental: append only the events produced since the last sync
+//! let mut log = exchange.open_append_log(Path::new("orders.jsonl")).unwrap();
+//! exchange.submit_market(Side::Buy, 100);
+//! log.sync(&exchange).unwrap();
+//! log.flush().unwrap();
+//! let exchange = Exchange::replay_from_log(Path::new("orders.jsonl")).unwrap();
 //! ```
 
-use std::io::{self, BufRead, Write};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, Read, Write};
 use std::path::Path;
 
 use crate::Exchange;
@@ -65,6 +76,117 @@ pub fn load_events(path: &Path) -> io::Result<Vec<Event>> {
     Ok(events)
 }
 
+/// Magic bytes at the start of every binary event log.
+pub const BINARY_MAGIC: [u8; 4] = *b"NBEV";
+
+/// Current binary event log format version.
+pub const BINARY_VERSION: u16 = 1;
+
+/// Save events to a file in the binary (postcard) format.
+///
+/// The file starts with [`BINARY_MAGIC`] and a little-endian
+/// [`BINARY_VERSION`], followed by the postcard-encoded event vector.
+pub fn save_events_binary(events: &[Event], path: &Path) -> io::Result<()> {
+    let file = File::create(path)?;
+    let mut writer = io::BufWriter::new(file);
+
+    writer.write_all(&BINARY_MAGIC)?;
+    writer.write_all(&BINARY_VERSION.to_le_bytes())?;
+    postcard::to_io(events, &mut writer).map_err(io::Error::other)?;
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Load events from a binary (postcard) file.
+///
+/// Returns `InvalidData` if the magic bytes or format version don't match.
+pub fn load_events_binary(path: &Path) -> io::Result<Vec<Event>> {
+    let file = File::open(path)?;
+    let mut reader = io::BufReader::new(file);
+
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if magic != BINARY_MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a nanobook binary event log (bad magic bytes)",
+        ));
+    }
+
+    let mut version = [0u8; 2];
+    reader.read_exact(&mut version)?;
+    let version = u16::from_le_bytes(version);
+    if version != BINARY_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "unsupported binary event log version {} (expected {})",
+                version, BINARY_VERSION
+            ),
+        ));
+    }
+
+    let mut body = Vec::new();
+    reader.read_to_end(&mut body)?;
+    postcard::from_bytes(&body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Append-only JSON Lines event log.
+///
+/// Obtained from [`Exchange::open_append_log`]. Each call to [`sync`](Self::sync)
+/// writes only the events the exchange produced since the previous sync, so
+/// checkpointing costs O(new events) instead of rewriting the whole file.
+///
+/// Writes are buffered; call [`flush`](Self::flush) to push them to the OS.
+/// Every event is serialized to a complete line before it is handed to the
+/// writer, and [`Exchange::replay_from_log`] ignores an unterminated final
+/// line, so a crash mid-write loses at most the last event.
+#[derive(Debug)]
+pub struct AppendLog {
+    writer: io::BufWriter<File>,
+    /// Number of the exchange's events already written to the file.
+    cursor: usize,
+}
+
+impl AppendLog {
+    /// Append a single event as one JSON line.
+    pub fn append(&mut self, event: &Event) -> io::Result<()> {
+        let mut line = serde_json::to_string(event).map_err(io::Error::other)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())
+    }
+
+    /// Append every event `exchange` recorded since the last sync.
+    ///
+    /// Returns the number of events written.
+    pub fn sync(&mut self, exchange: &Exchange) -> io::Result<usize> {
+        let pending = exchange.events().get(self.cursor..).unwrap_or(&[]);
+        for event in pending {
+            self.append(event)?;
+        }
+        self.cursor += pending.len();
+        Ok(pending.len())
+    }
+
+    /// Sync, flush, then clear the exchange's in-memory event log.
+    ///
+    /// Keeps memory bounded in long-running simulations: the file becomes
+    /// the only copy of the history.
+    pub fn sync_and_clear(&mut self, exchange: &mut Exchange) -> io::Result<usize> {
+        let written = self.sync(exchange)?;
+        self.flush()?;
+        exchange.clear_events();
+        self.cursor = 0;
+        Ok(written)
+    }
+
+    /// Flush buffered lines to the underlying file.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
 impl Exchange {
     /// Save the exchange's event log to a file.
     ///
@@ -82,6 +204,95 @@ impl Exchange {
         let events = load_events(path)?;
         Ok(Self::replay(&events))
     }
+
+    /// Save the exchange's event log in the compact binary format.
+    ///
+    /// Requires the `persistence` and `event-log` features.
+    pub fn save_binary(&self, path: &Path) -> io::Result<()> {
+        save_events_binary(self.events(), path)
+    }
+
+    /// Load an exchange from a binary event log written by
+    /// [`save_binary`](Self::save_binary).
+    pub fn load_binary(path: &Path) -> io::Result<Self> {
+        let events = load_events_binary(path)?;
+        Ok(Self::replay(&events))
+    }
+
+    /// Open (or create) an append-only event log at `path`.
+    ///
+    /// Events already present in the file are assumed to be this exchange's
+    /// first events (as after [`replay_from_log`](Self::replay_from_log)), so
+    /// the first [`AppendLog::sync`] writes only what is missing. For a new
+    /// file that means the whole in-memory log.
+    ///
+    /// A torn final line left by a crash mid-write is truncated away, so new
+    /// events start on a fresh line.
+    pub fn open_append_log(&self, path: &Path) -> io::Result<AppendLog> {
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)?;
+
+        let mut reader = io::BufReader::new(&file);
+        let mut line = Vec::new();
+        let mut complete_len = 0u64;
+        let mut cursor = 0;
+        loop {
+            line.clear();
+            let n = reader.read_until(b'\n', &mut line)?;
+            if n == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            complete_len += n as u64;
+            if serde_json::from_slice::<Event>(&line).is_ok() {
+                cursor += 1;
+            }
+        }
+        if !line.is_empty() {
+            file.set_len(complete_len)?;
+        }
+
+        Ok(AppendLog {
+            writer: io::BufWriter::new(file),
+            cursor,
+        })
+    }
+
+    /// Rebuild an exchange from an append-only event log.
+    ///
+    /// Like [`load`](Self::load), but tolerates a torn final line (one not
+    /// terminated by a newline that fails to parse), which is dropped.
+    pub fn replay_from_log(path: &Path) -> io::Result<Self> {
+        let mut contents = String::new();
+        File::open(path)?.read_to_string(&mut contents)?;
+
+        let mut exchange = Self::new();
+        let torn_tail = !contents.is_empty() && !contents.ends_with('\n');
+        let line_count = contents.lines().count();
+
+        for (line_num, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Event>(line) {
+                Ok(event) => {
+                    exchange.apply(&event);
+                }
+                Err(_) if torn_tail && line_num + 1 == line_count => break,
+                Err(e) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {}: {}", line_num + 1, e),
+                    ));
+                }
+            }
+        }
+
+        Ok(exchange)
+    }
 }
 
 #[cfg(test)]
@@ -181,4 +392,206 @@ mod tests {
         // Cleanup
         let _ = std::fs::remove_file(&path);
     }
+
+    #[test]
+    fn append_log_replay_matches_in_memory() {
+        let path = test_path("append_log");
+        let _ = std::fs::remove_file(&path);
+
+        let mut exchange = Exchange::new();
+        let mut log = exchange.open_append_log(&path).unwrap();
+
+        for i in 0..1000u64 {
+            let offset = (i % 7) as i64 * 25;
+            match i % 5 {
+                0 => {
+                    exchange.submit_limit(Side::Sell, Price(100_50 + offset), 10, TimeInForce::GTC);
+                }
+                1 => {
+                    exchange.submit_limit(Side::Buy, Price(99_50 - offset), 10, TimeInForce::GTC);
+                }
+                2 => {
+                    exchange.submit_market(Side::Buy, 5);
+                }
+                3 => {
+                    exchange.submit_stop_market(Side::Sell, Price(95_00 - offset), 5);
+                }
+                _ => {
+                    exchange.cancel(crate::OrderId(i / 2 + 1));
+                }
+            }
+            log.sync(&exchange).unwrap();
+        }
+        log.flush().unwrap();
+        assert_eq!(exchange.events().len(), 1000);
+
+        let replayed = Exchange::replay_from_log(&path).unwrap();
+        assert_eq!(exchange.best_bid_ask(), replayed.best_bid_ask());
+        assert_eq!(exchange.trades(), replayed.trades());
+        assert_eq!(exchange.pending_stop_count(), replayed.pending_stop_count());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn append_log_resumes_and_drops_torn_tail() {
+        let path = test_path("append_resume");
+        let _ = std::fs::remove_file(&path);
+
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+        let mut log = exchange.open_append_log(&path).unwrap();
+        assert_eq!(log.sync(&exchange).unwrap(), 1);
+        log.flush().unwrap();
+        drop(log);
+
+        // Reopening after replay only appends new events.
+        let mut resumed = Exchange::replay_from_log(&path).unwrap();
+        let mut log = resumed.open_append_log(&path).unwrap();
+        resumed.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+        assert_eq!(log.sync_and_clear(&mut resumed).unwrap(), 1);
+        assert!(resumed.events().is_empty());
+        drop(log);
+
+        // Simulate a crash mid-write.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"SubmitMarket\":{\"side\"").unwrap();
+        drop(file);
+
+        let replayed = Exchange::replay_from_log(&path).unwrap();
+        assert_eq!(replayed.events().len(), 2);
+        assert_eq!(
+            replayed.best_bid_ask(),
+            (Some(Price(100_00)), Some(Price(101_00)))
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn append_log_reopen_truncates_torn_tail() {
+        let path = test_path("append_torn");
+        let _ = std::fs::remove_file(&path);
+
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+        let mut log = exchange.open_append_log(&path).unwrap();
+        log.sync(&exchange).unwrap();
+        drop(log);
+
+        // Simulate a crash mid-write, then resume and keep logging.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"SubmitMarket\":{\"side\"").unwrap();
+        drop(file);
+
+        let mut resumed = Exchange::replay_from_log(&path).unwrap();
+        let mut log = resumed.open_append_log(&path).unwrap();
+        resumed.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+        assert_eq!(log.sync(&resumed).unwrap(), 1);
+        drop(log);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+        let replayed = Exchange::replay_from_log(&path).unwrap();
+        assert_eq!(replayed.events(), resumed.events());
+        assert_eq!(
+            replayed.best_bid_ask(),
+            (Some(Price(100_00)), Some(Price(101_00)))
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// A deterministic mixed order flow, like a compressed trading day.
+    fn simulated_session(n: u64) -> Exchange {
+        let mut exchange = Exchange::new();
+        let mut seed = 42u64;
+        for _ in 0..n {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let r = seed >> 33;
+            let offset = (r % 20) as i64 * 5;
+            let qty = 1 + r % 200;
+            match r % 10 {
+                0..=3 => {
+                    exchange.submit_limit(Side::Buy, Price(99_90 - offset), qty, TimeInForce::GTC);
+                }
+                4..=7 => {
+                    exchange.submit_limit(
+                        Side::Sell,
+                        Price(100_10 + offset),
+                        qty,
+                        TimeInForce::GTC,
+                    );
+                }
+                8 => {
+                    let side = if r % 2 == 0 { Side::Buy } else { Side::Sell };
+                    exchange.submit_market(side, qty);
+                }
+                _ => {
+                    exchange.cancel(crate::OrderId(1 + r % 500));
+                }
+            }
+        }
+        exchange
+    }
+
+    #[test]
+    fn binary_round_trip_is_deterministic() {
+        let json_path = test_path("binary_cmp");
+        let bin_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_binary_rt.nbev");
+
+        let exchange = simulated_session(5_000);
+        exchange.save(&json_path).unwrap();
+        exchange.save_binary(&bin_path).unwrap();
+
+        let loaded = Exchange::load_binary(&bin_path).unwrap();
+        assert_eq!(exchange.events(), loaded.events());
+        assert_eq!(exchange.best_bid_ask(), loaded.best_bid_ask());
+        assert_eq!(exchange.trades(), loaded.trades());
+        assert_eq!(
+            exchange.full_book().total_bid_quantity(),
+            loaded.full_book().total_bid_quantity()
+        );
+        assert_eq!(
+            exchange.full_book().total_ask_quantity(),
+            loaded.full_book().total_ask_quantity()
+        );
+
+        let json_size = std::fs::metadata(&json_path).unwrap().len();
+        let bin_size = std::fs::metadata(&bin_path).unwrap().len();
+        println!(
+            "{} events: jsonl {} bytes, binary {} bytes ({:.1}x smaller)",
+            exchange.events().len(),
+            json_size,
+            bin_size,
+            json_size as f64 / bin_size as f64
+        );
+        assert!(bin_size < json_size);
+
+        let _ = std::fs::remove_file(&json_path);
+        let _ = std::fs::remove_file(&bin_path);
+    }
+
+    #[test]
+    fn binary_rejects_bad_header() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_binary_hdr.nbev");
+
+        // JSONL is not a binary log.
+        std::fs::write(&path, b"{\"Cancel\":{\"order_id\":1}}\n").unwrap();
+        let err = Exchange::load_binary(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("magic"));
+
+        // Right magic, future version.
+        let mut bytes = BINARY_MAGIC.to_vec();
+        bytes.extend_from_slice(&(BINARY_VERSION + 1).to_le_bytes());
+        std::fs::write(&path, bytes).unwrap();
+        let err = Exchange::load_binary(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("version"));
+
+        let _ = std::fs::remove_file(&path);
+    }
 }
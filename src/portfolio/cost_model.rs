@@ -8,7 +8,7 @@
 /// ```ignore
 /// use nanobook::portfolio::CostModel;
 ///
-/// let model = CostModel { commission_bps: 10, slippage_bps: 5, min_trade_fee: 1_00 };
+/// let model = CostModel { commission_bps: 10, slippage_bps: 5, min_trade_fee: 1_00, size_impact: None, fee_tiers: None };
 /// // 15 bps on $10,000 notional = $1.50, but min fee is $1.00, so result = $1.50
 /// assert_eq!(model.compute_cost(1_000_000), 1500);
 /// ```
@@ -21,6 +21,76 @@ pub struct CostModel {
     pub slippage_bps: u32,
     /// Minimum fee per trade (cents)
     pub min_trade_fee: i64,
+    /// Size-dependent slippage; replaces `slippage_bps` when set
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub size_impact: Option<SizeAwareSlippage>,
+    /// Maker/taker commission tiers; replace `commission_bps` when set
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fee_tiers: Option<FeeTiers>,
+}
+
+/// Whether a fill added liquidity (resting order) or removed it (aggressor).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LiquidityFlag {
+    /// Resting order filled by an incoming one
+    Maker,
+    /// Incoming order that crossed the spread
+    #[default]
+    Taker,
+}
+
+impl LiquidityFlag {
+    /// Flag of `order_id`'s side of `trade`: maker if it was the resting order.
+    pub fn of(trade: &crate::Trade, order_id: crate::OrderId) -> Self {
+        if trade.passive_order_id == order_id {
+            LiquidityFlag::Maker
+        } else {
+            LiquidityFlag::Taker
+        }
+    }
+}
+
+/// Exchange-style maker/taker commission schedule, in bps.
+///
+/// A negative rate is a rebate: maker fills on many venues are paid to add
+/// liquidity, so their cost comes out negative and credits cash.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeTiers {
+    /// Commission on maker fills (negative = rebate)
+    pub maker_bps: i32,
+    /// Commission on taker fills
+    pub taker_bps: i32,
+}
+
+/// Slippage that grows linearly with order size relative to average daily volume.
+///
+/// Effective slippage in bps is `base_bps + impact_coeff * quantity / adv`,
+/// so an order for the full ADV pays `base_bps + impact_coeff`. A
+/// square-root impact law (`impact_coeff * sqrt(quantity / adv)`), closer to
+/// empirical market impact for large orders, is a possible follow-up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SizeAwareSlippage {
+    /// Slippage of an infinitesimally small order, in bps
+    pub base_bps: u32,
+    /// Additional bps per 100% of ADV traded
+    pub impact_coeff: f64,
+    /// Average daily volume, in shares; 0 disables the size term
+    pub adv: u64,
+}
+
+impl SizeAwareSlippage {
+    /// Effective slippage in bps for an order of `quantity` shares.
+    pub fn slippage_bps(&self, quantity: u64) -> f64 {
+        let participation = if self.adv == 0 {
+            0.0
+        } else {
+            quantity as f64 / self.adv as f64
+        };
+        f64::from(self.base_bps) + self.impact_coeff * participation
+    }
 }
 
 impl CostModel {
@@ -30,14 +100,38 @@ impl CostModel {
             commission_bps: 0,
             slippage_bps: 0,
             min_trade_fee: 0,
+            size_impact: None,
+            fee_tiers: None,
         }
     }
 
+    /// Charge `maker_bps` / `taker_bps` by fill liquidity instead of the flat
+    /// `commission_bps`.
+    pub fn with_fee_tiers(mut self, maker_bps: i32, taker_bps: i32) -> Self {
+        self.fee_tiers = Some(FeeTiers {
+            maker_bps,
+            taker_bps,
+        });
+        self
+    }
+
+    /// Use `impact` instead of the flat `slippage_bps`.
+    pub fn with_size_impact(mut self, impact: SizeAwareSlippage) -> Self {
+        self.size_impact = Some(impact);
+        self
+    }
+
     /// Compute the total cost for a trade with the given absolute notional value (cents).
     ///
     /// The notional should be `|quantity * price|`. Returns the cost in cents,
     /// which is always non-negative.
+    ///
+    /// With `size_impact` set this charges only its base slippage; use
+    /// [`compute_cost_sized`](Self::compute_cost_sized) to include the size term.
     pub fn compute_cost(&self, notional: i64) -> i64 {
+        if self.size_impact.is_some() {
+            return self.compute_cost_sized(notional, 0);
+        }
         let notional = notional.unsigned_abs() as u128;
         let total_bps = self.commission_bps as u128 + self.slippage_bps as u128;
         // notional * bps / 10_000 — use u128 to prevent overflow
@@ -45,6 +139,55 @@ impl CostModel {
         let bps_cost = i64::try_from(raw).unwrap_or(i64::MAX);
         bps_cost.max(self.min_trade_fee)
     }
+
+    /// Compute the total cost for a trade of `quantity` shares with the given
+    /// notional value (cents).
+    ///
+    /// Identical to [`compute_cost`](Self::compute_cost) unless `size_impact`
+    /// is set, in which case slippage scales with `quantity`.
+    pub fn compute_cost_sized(&self, notional: i64, quantity: u64) -> i64 {
+        let Some(impact) = self.size_impact else {
+            return self.compute_cost(notional);
+        };
+        let total_bps = f64::from(self.commission_bps) + impact.slippage_bps(quantity);
+        // Float-to-int `as` saturates, so huge costs clamp to i64::MAX.
+        let bps_cost = (notional.unsigned_abs() as f64 * total_bps / 10_000.0) as i64;
+        bps_cost.max(self.min_trade_fee)
+    }
+
+    /// Compute the cost of a fill of `quantity` shares, given whether it
+    /// added or removed liquidity.
+    ///
+    /// Without `fee_tiers` this is [`compute_cost_sized`](Self::compute_cost_sized)
+    /// for both flags. With tiers, the tier rate replaces `commission_bps`;
+    /// maker fills trade at their own price and pay no slippage. A negative
+    /// total (a net rebate) is returned as is, with no minimum fee.
+    pub fn compute_cost_with_liquidity(
+        &self,
+        notional: i64,
+        quantity: u64,
+        liquidity: LiquidityFlag,
+    ) -> i64 {
+        let Some(tiers) = self.fee_tiers else {
+            return self.compute_cost_sized(notional, quantity);
+        };
+        let total_bps = match liquidity {
+            LiquidityFlag::Maker => f64::from(tiers.maker_bps),
+            LiquidityFlag::Taker => {
+                let slippage = match self.size_impact {
+                    Some(impact) => impact.slippage_bps(quantity),
+                    None => f64::from(self.slippage_bps),
+                };
+                f64::from(tiers.taker_bps) + slippage
+            }
+        };
+        let bps_cost = (notional.unsigned_abs() as f64 * total_bps / 10_000.0) as i64;
+        if total_bps < 0.0 {
+            bps_cost
+        } else {
+            bps_cost.max(self.min_trade_fee)
+        }
+    }
 }
 
 impl Default for CostModel {
@@ -57,6 +200,41 @@ impl Default for CostModel {
 mod tests {
     use super::*;
 
+    #[test]
+    fn fee_tiers_charge_by_liquidity() {
+        let model = CostModel {
+            commission_bps: 10,
+            slippage_bps: 5,
+            min_trade_fee: 1_00,
+            size_impact: None,
+            fee_tiers: None,
+        };
+        // No tiers: both flags fall back to commission + slippage.
+        for flag in [LiquidityFlag::Maker, LiquidityFlag::Taker] {
+            assert_eq!(
+                model.compute_cost_with_liquidity(1_000_000, 100, flag),
+                1500
+            );
+        }
+
+        let tiered = model.with_fee_tiers(-2, 7);
+        // Maker: 2 bps rebate, no slippage, no minimum fee.
+        assert_eq!(
+            tiered.compute_cost_with_liquidity(1_000_000, 100, LiquidityFlag::Maker),
+            -200
+        );
+        // Taker: 7 bps + 5 bps slippage.
+        assert_eq!(
+            tiered.compute_cost_with_liquidity(1_000_000, 100, LiquidityFlag::Taker),
+            1200
+        );
+        // Positive costs still honor the minimum fee.
+        assert_eq!(
+            tiered.compute_cost_with_liquidity(10_000, 1, LiquidityFlag::Taker),
+            1_00
+        );
+    }
+
     #[test]
     fn zero_cost() {
         let model = CostModel::zero();
@@ -69,6 +247,8 @@ mod tests {
             commission_bps: 10,
             slippage_bps: 5,
             min_trade_fee: 0,
+            size_impact: None,
+            fee_tiers: None,
         };
         // 15 bps on 1_000_000 cents ($10,000) = 1500 cents ($15)
         assert_eq!(model.compute_cost(1_000_000), 1500);
@@ -80,6 +260,8 @@ mod tests {
             commission_bps: 1,
             slippage_bps: 0,
             min_trade_fee: 1_00, // $1 minimum
+            size_impact: None,
+            fee_tiers: None,
         };
         // 1 bps on 10_000 cents ($100) = 1 cent, but min is $1.00
         assert_eq!(model.compute_cost(10_000), 1_00);
@@ -91,6 +273,8 @@ mod tests {
             commission_bps: 10,
             slippage_bps: 0,
             min_trade_fee: 0,
+            size_impact: None,
+            fee_tiers: None,
         };
         assert_eq!(
             model.compute_cost(-1_000_000),
@@ -104,4 +288,35 @@ mod tests {
         assert!(model.compute_cost(0) >= 0);
         assert!(model.compute_cost(-100) >= 0);
     }
+
+    #[test]
+    fn size_aware_slippage_grows_with_quantity() {
+        let flat = CostModel {
+            commission_bps: 5,
+            slippage_bps: 10,
+            min_trade_fee: 0,
+            size_impact: None,
+            fee_tiers: None,
+        };
+        let sized = flat.with_size_impact(SizeAwareSlippage {
+            base_bps: 10,
+            impact_coeff: 100.0,
+            adv: 1_000_000,
+        });
+        let price = 50_00;
+        let per_share = |model: &CostModel, qty: u64| {
+            model.compute_cost_sized(qty as i64 * price, qty) as f64 / qty as f64
+        };
+
+        // Flat: 15 bps of $50 regardless of size.
+        assert_eq!(per_share(&flat, 100_000), per_share(&flat, 200_000));
+        assert_eq!(per_share(&flat, 100_000), 7.5);
+
+        // Sized: 10% of ADV adds 10 bps, 20% adds 20 bps.
+        assert_eq!(per_share(&sized, 100_000), 12.5);
+        assert_eq!(per_share(&sized, 200_000), 17.5);
+
+        // Without a quantity only the base slippage applies.
+        assert_eq!(sized.compute_cost(1_000_000), flat.compute_cost(1_000_000));
+    }
 }
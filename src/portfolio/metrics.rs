@@ -1,5 +1,7 @@
 //! Financial performance metrics.
 
+use super::sizing::kelly_fraction;
+
 /// Computed performance metrics for a return series.
 ///
 /// All return-based metrics assume simple (not log) returns.
@@ -16,7 +18,8 @@ pub struct Metrics {
     pub volatility: f64,
     /// Annualized Sharpe ratio: (mean return - risk_free) / volatility
     pub sharpe: f64,
-    /// Annualized Sortino ratio: (mean return - risk_free) / downside_deviation
+    /// Annualized Sortino ratio: (mean return - MAR) / downside_deviation,
+    /// where MAR is `risk_free` unless set via [`compute_metrics_ext`]
     pub sortino: f64,
     /// Maximum drawdown (as positive fraction, e.g., 0.20 = 20% peak-to-trough)
     pub max_drawdown: f64,
@@ -75,6 +78,20 @@ impl std::fmt::Display for Metrics {
 ///
 /// Returns `None` if `returns` is empty.
 pub fn compute_metrics(returns: &[f64], periods_per_year: f64, risk_free: f64) -> Option<Metrics> {
+    compute_metrics_ext(returns, periods_per_year, risk_free, risk_free)
+}
+
+/// [`compute_metrics`] with a separate minimum acceptable return for Sortino.
+///
+/// `mar` is a per-period return: downside deviation counts only returns
+/// below it, and the Sortino numerator is the mean excess over it. Sharpe
+/// still uses `risk_free`. `compute_metrics` is this with `mar == risk_free`.
+pub fn compute_metrics_ext(
+    returns: &[f64],
+    periods_per_year: f64,
+    risk_free: f64,
+    mar: f64,
+) -> Option<Metrics> {
     if returns.is_empty() {
         return None;
     }
@@ -105,7 +122,7 @@ pub fn compute_metrics(returns: &[f64], periods_per_year: f64, risk_free: f64) -
     };
     let volatility = variance.sqrt() * periods_per_year.sqrt();
 
-    // Excess returns for Sharpe/Sortino
+    // Excess returns for Sharpe
     let excess_mean = mean - risk_free;
 
     // Sharpe ratio (annualized)
@@ -115,12 +132,12 @@ pub fn compute_metrics(returns: &[f64], periods_per_year: f64, risk_free: f64) -
         0.0
     };
 
-    // Downside deviation (only negative excess returns)
+    // Downside deviation (only returns below MAR)
     let downside_variance = if n > 1 {
         returns
             .iter()
             .map(|&r| {
-                let excess = r - risk_free;
+                let excess = r - mar;
                 if excess < 0.0 { excess.powi(2) } else { 0.0 }
             })
             .sum::<f64>()
@@ -132,7 +149,7 @@ pub fn compute_metrics(returns: &[f64], periods_per_year: f64, risk_free: f64) -
 
     // Sortino ratio (annualized)
     let sortino = if downside_dev > 0.0 {
-        excess_mean * periods_per_year.sqrt() / downside_dev
+        (mean - mar) * periods_per_year.sqrt() / downside_dev
     } else {
         0.0
     };
@@ -189,12 +206,7 @@ pub fn compute_metrics(returns: &[f64], periods_per_year: f64, risk_free: f64) -
         0.0
     };
 
-    // Kelly criterion: w - (1 - w) / b
-    let kelly = if payoff_ratio > 0.0 && payoff_ratio.is_finite() {
-        win_rate - (1.0 - win_rate) / payoff_ratio
-    } else {
-        0.0
-    };
+    let kelly = kelly_fraction(win_rate, payoff_ratio);
 
     Some(Metrics {
         total_return,
@@ -215,6 +227,17 @@ pub fn compute_metrics(returns: &[f64], periods_per_year: f64, risk_free: f64) -
     })
 }
 
+/// Drawdown of `equity` below the high-water mark `peak`, as a positive
+/// fraction (0.25 = 25% below peak).
+///
+/// Returns 0.0 at or above the peak, and when `peak` is not positive.
+pub fn drawdown(peak: f64, equity: f64) -> f64 {
+    if peak <= 0.0 || equity >= peak {
+        return 0.0;
+    }
+    (peak - equity) / peak
+}
+
 /// Compute maximum drawdown from a return series.
 fn compute_max_drawdown(returns: &[f64]) -> f64 {
     let mut peak = 1.0_f64;
@@ -226,7 +249,7 @@ fn compute_max_drawdown(returns: &[f64]) -> f64 {
         if equity > peak {
             peak = equity;
         }
-        let dd = (peak - equity) / peak;
+        let dd = drawdown(peak, equity);
         if dd > max_dd {
             max_dd = dd;
         }
@@ -371,6 +394,85 @@ fn rolling_window(
     out
 }
 
+/// Performance of a return series relative to a benchmark.
+///
+/// Annualized with the same `periods_per_year` convention as [`Metrics`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelativeMetrics {
+    /// Sensitivity to the benchmark: cov(strategy, benchmark) / var(benchmark)
+    pub beta: f64,
+    /// Annualized Jensen's alpha: excess return not explained by beta
+    pub alpha: f64,
+    /// Annualized standard deviation of active (strategy - benchmark) returns
+    pub tracking_error: f64,
+    /// Annualized mean active return / tracking error (0 when tracking error is 0)
+    pub information_ratio: f64,
+}
+
+/// Compute benchmark-relative metrics from parallel return series.
+///
+/// # Arguments
+///
+/// * `returns` — Strategy returns
+/// * `benchmark` — Benchmark returns for the same periods
+/// * `periods_per_year` — Annualization factor (252 for daily, 12 for monthly)
+/// * `risk_free` — Risk-free rate per period, used for alpha
+///
+/// Returns `None` if the series are empty or differ in length. Beta is 0 when
+/// the benchmark has no variance.
+pub fn compute_relative_metrics(
+    returns: &[f64],
+    benchmark: &[f64],
+    periods_per_year: f64,
+    risk_free: f64,
+) -> Option<RelativeMetrics> {
+    if returns.is_empty() || returns.len() != benchmark.len() {
+        return None;
+    }
+
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let bench_mean = benchmark.iter().sum::<f64>() / n;
+
+    // Sample covariance / variances (n - 1), zero for a single period
+    let ddof = (n - 1.0).max(1.0);
+    let (mut cov, mut bench_var, mut active_var) = (0.0, 0.0, 0.0);
+    for (&r, &b) in returns.iter().zip(benchmark) {
+        let (dr, db) = (r - mean, b - bench_mean);
+        cov += dr * db;
+        bench_var += db * db;
+        active_var += (dr - db).powi(2);
+    }
+    cov /= ddof;
+    bench_var /= ddof;
+    active_var /= ddof;
+
+    let beta = if bench_var > 0.0 {
+        cov / bench_var
+    } else {
+        0.0
+    };
+    let alpha = ((mean - risk_free) - beta * (bench_mean - risk_free)) * periods_per_year;
+
+    let active_mean = mean - bench_mean;
+    let active_std = active_var.sqrt();
+    let tracking_error = active_std * periods_per_year.sqrt();
+    // Treat float noise from identical series as zero tracking error
+    let information_ratio = if active_std > 1e-12 {
+        active_mean * periods_per_year.sqrt() / active_std
+    } else {
+        0.0
+    };
+
+    Some(RelativeMetrics {
+        beta,
+        alpha,
+        tracking_error,
+        information_ratio,
+    })
+}
+
 /// Rolling Sharpe ratio over a sliding window.
 ///
 /// Returns NaN for positions where the window is incomplete.
@@ -477,6 +579,22 @@ mod tests {
         assert!(m.sortino >= m.sharpe);
     }
 
+    #[test]
+    fn sortino_mar_above_risk_free_is_stricter() {
+        let returns = vec![0.02, 0.03, 0.01, -0.005, 0.015, 0.001];
+        let base = compute_metrics(&returns, 252.0, 0.0).unwrap();
+        let same = compute_metrics_ext(&returns, 252.0, 0.0, 0.0).unwrap();
+        assert_eq!(base.sortino, same.sortino);
+
+        let strict = compute_metrics_ext(&returns, 252.0, 0.0, 0.012).unwrap();
+        assert!(strict.sortino < base.sortino);
+        assert_eq!(strict.sharpe, base.sharpe);
+        // Downside deviation = (mean - MAR) * sqrt(252) / Sortino.
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let downside = |m: &Metrics, mar: f64| (mean - mar) * 252f64.sqrt() / m.sortino;
+        assert!(downside(&strict, 0.012) > downside(&base, 0.0));
+    }
+
     #[test]
     fn win_loss_count() {
         let returns = vec![0.01, -0.02, 0.0, 0.03, -0.01];
@@ -570,6 +688,26 @@ mod tests {
         assert!((m.cvar_95 - (-0.10)).abs() < 0.01);
     }
 
+    #[test]
+    fn relative_metrics_against_self_and_scaled_benchmark() {
+        let bench = [0.01, -0.02, 0.015, 0.005, -0.01, 0.02];
+        let same = compute_relative_metrics(&bench, &bench, 252.0, 0.0).unwrap();
+        assert!((same.beta - 1.0).abs() < 1e-12);
+        assert!(same.alpha.abs() < 1e-12);
+        assert_eq!(same.tracking_error, 0.0);
+        assert_eq!(same.information_ratio, 0.0);
+
+        // Twice the benchmark plus a constant 0.1% per period.
+        let levered: Vec<f64> = bench.iter().map(|b| 2.0 * b + 0.001).collect();
+        let m = compute_relative_metrics(&levered, &bench, 252.0, 0.0).unwrap();
+        assert!((m.beta - 2.0).abs() < 1e-12);
+        assert!((m.alpha - 0.252).abs() < 1e-12);
+        assert!(m.tracking_error > 0.0);
+
+        assert!(compute_relative_metrics(&bench, &bench[1..], 252.0, 0.0).is_none());
+        assert!(compute_relative_metrics(&[], &[], 252.0, 0.0).is_none());
+    }
+
     #[test]
     fn rolling_sharpe_basic() {
         let returns = vec![0.01; 100];
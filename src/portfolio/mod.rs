@@ -23,37 +23,43 @@
 pub mod cost_model;
 pub mod metrics;
 pub mod position;
+pub mod sizing;
 pub mod strategy;
 #[cfg(feature = "parallel")]
 pub mod sweep;
 
-pub use cost_model::CostModel;
-pub use metrics::{Metrics, compute_metrics};
-pub use position::Position;
+pub use cost_model::{CostModel, FeeTiers, LiquidityFlag, SizeAwareSlippage};
+pub use metrics::{
+    Metrics, RelativeMetrics, compute_metrics, compute_metrics_ext, compute_relative_metrics,
+    drawdown,
+};
+pub use position::{ClosedLot, CostBasisMethod, Lot, Position};
+
+use position::default_currency;
 pub use strategy::{BacktestResult, EqualWeight, Strategy, run_backtest};
 
 use crate::types::Symbol;
 use rustc_hash::FxHashMap;
 
-/// Serde helper for `FxHashMap<Symbol, Position>` — serializes as `Vec<(Symbol, Position)>`.
+/// Serde helper for `FxHashMap<Symbol, V>` — serializes as a sorted `Vec<(Symbol, V)>`.
 #[cfg(feature = "serde")]
-mod serde_positions {
-    use super::{FxHashMap, Position, Symbol};
+mod serde_symbol_map {
+    use super::{FxHashMap, Symbol};
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-    pub fn serialize<S: Serializer>(
-        map: &FxHashMap<Symbol, Position>,
+    pub fn serialize<S: Serializer, V: Serialize>(
+        map: &FxHashMap<Symbol, V>,
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
-        let mut vec: Vec<(&Symbol, &Position)> = map.iter().collect();
+        let mut vec: Vec<(&Symbol, &V)> = map.iter().collect();
         vec.sort_by_key(|(sym, _)| *sym);
         vec.serialize(serializer)
     }
 
-    pub fn deserialize<'de, D: Deserializer<'de>>(
+    pub fn deserialize<'de, D: Deserializer<'de>, V: Deserialize<'de>>(
         deserializer: D,
-    ) -> Result<FxHashMap<Symbol, Position>, D::Error> {
-        let vec: Vec<(Symbol, Position)> = Vec::deserialize(deserializer)?;
+    ) -> Result<FxHashMap<Symbol, V>, D::Error> {
+        let vec: Vec<(Symbol, V)> = Vec::deserialize(deserializer)?;
         Ok(vec.into_iter().collect())
     }
 }
@@ -70,19 +76,125 @@ pub struct Portfolio {
     #[cfg_attr(
         feature = "serde",
         serde(
-            serialize_with = "serde_positions::serialize",
-            deserialize_with = "serde_positions::deserialize"
+            serialize_with = "serde_symbol_map::serialize",
+            deserialize_with = "serde_symbol_map::deserialize"
         )
     )]
     positions: FxHashMap<Symbol, Position>,
-    /// Cost model applied to each trade
+    /// Cost model applied to trades in symbols without their own model
     cost_model: CostModel,
+    /// Per-symbol cost models, overriding `cost_model`
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            serialize_with = "serde_symbol_map::serialize",
+            deserialize_with = "serde_symbol_map::deserialize"
+        )
+    )]
+    symbol_costs: FxHashMap<Symbol, CostModel>,
     /// Series of periodic returns (for metrics computation)
     returns: Vec<f64>,
     /// Equity curve (total portfolio value at each snapshot)
     equity_curve: Vec<i64>,
     /// Previous equity for return calculation
     prev_equity: i64,
+    /// Post-trade weights from the most recent rebalance (for turnover)
+    #[cfg_attr(feature = "serde", serde(default))]
+    last_weights: Vec<(Symbol, f64)>,
+    /// Maximum gross exposure / equity; `None` = uncapped
+    #[cfg_attr(feature = "serde", serde(default))]
+    max_leverage: Option<f64>,
+    /// Bound on the sum of absolute target weights in `rebalance_simple` /
+    /// `rebalance_with_band`; `None` = unbounded
+    #[cfg_attr(feature = "serde", serde(default))]
+    max_gross_weight: Option<f64>,
+    /// Last known price per symbol, maintained only when leverage is capped
+    #[cfg_attr(feature = "serde", serde(skip))]
+    marks: FxHashMap<Symbol, i64>,
+    /// Cost basis method applied to newly opened positions
+    #[cfg_attr(feature = "serde", serde(default))]
+    cost_basis: CostBasisMethod,
+    /// Fills recorded since the last `take_fills`; `None` = not recording
+    #[cfg_attr(feature = "serde", serde(skip))]
+    fill_log: Option<Vec<PortfolioFill>>,
+    /// Shares the leverage cap skipped in the most recent trading call
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_skipped: Vec<(Symbol, i64)>,
+    /// Quote currency per symbol; unlisted symbols are USD
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            serialize_with = "serde_symbol_map::serialize",
+            deserialize_with = "serde_symbol_map::deserialize"
+        )
+    )]
+    symbol_currencies: FxHashMap<Symbol, Symbol>,
+    /// Currency the cash balance is held in
+    #[cfg_attr(feature = "serde", serde(default = "default_currency"))]
+    cash_currency: Symbol,
+}
+
+/// Equity converted to a base currency by [`Portfolio::total_equity_fx`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FxEquity {
+    /// Cash plus position values in the base currency (cents), excluding
+    /// anything whose currency has no rate.
+    pub equity: i64,
+    /// Currencies skipped for lack of an FX rate, sorted.
+    pub missing_rates: Vec<Symbol>,
+}
+
+impl FxEquity {
+    /// Whether every currency could be converted.
+    pub fn is_complete(&self) -> bool {
+        self.missing_rates.is_empty()
+    }
+}
+
+/// Value of one unit of `from` in `to`: 1.0 when equal, else a direct
+/// `(from, to)` quote or the inverse of a `(to, from)` quote.
+fn fx_rate(rates: &[(Symbol, Symbol, f64)], from: Symbol, to: Symbol) -> Option<f64> {
+    if from == to {
+        return Some(1.0);
+    }
+    rates.iter().find_map(|&(base, quote, rate)| {
+        if !(rate.is_finite() && rate > 0.0) {
+            None
+        } else if (base, quote) == (from, to) {
+            Some(rate)
+        } else if (base, quote) == (to, from) {
+            Some(1.0 / rate)
+        } else {
+            None
+        }
+    })
+}
+
+/// A fill executed by the portfolio, as recorded by [`Portfolio::with_fill_log`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PortfolioFill {
+    pub symbol: Symbol,
+    /// Signed quantity: positive = bought, negative = sold.
+    pub quantity: i64,
+    /// Fill price (cents).
+    pub price: i64,
+    /// Transaction cost charged (cents).
+    pub cost: i64,
+}
+
+/// Venue-agnostic trading summary for one symbol, from
+/// [`Portfolio::consolidated_position`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConsolidatedPosition {
+    pub symbol: Symbol,
+    /// Total quantity bought.
+    pub bought: i64,
+    /// Total quantity sold.
+    pub sold: i64,
+    /// Net quantity held: `bought - sold`.
+    pub net: i64,
 }
 
 impl Portfolio {
@@ -99,12 +211,111 @@ impl Portfolio {
             cash: initial_cash,
             positions: FxHashMap::default(),
             cost_model,
+            symbol_costs: FxHashMap::default(),
             returns: Vec::new(),
             equity_curve: vec![initial_cash],
             prev_equity: initial_cash,
+            last_weights: Vec::new(),
+            max_leverage: None,
+            max_gross_weight: None,
+            marks: FxHashMap::default(),
+            cost_basis: CostBasisMethod::AverageCost,
+            fill_log: None,
+            last_skipped: Vec::new(),
+            symbol_currencies: FxHashMap::default(),
+            cash_currency: default_currency(),
         }
     }
 
+    /// Use `method` to compute realized PnL for positions opened from now on.
+    ///
+    /// ```ignore
+    /// let portfolio = Portfolio::new(1_000_000_00, CostModel::zero())
+    ///     .with_cost_basis(CostBasisMethod::Fifo);
+    /// ```
+    pub fn with_cost_basis(mut self, method: CostBasisMethod) -> Self {
+        self.cost_basis = method;
+        self
+    }
+
+    /// Record every fill; retrieve them with [`take_fills`](Self::take_fills).
+    pub fn with_fill_log(mut self) -> Self {
+        self.fill_log = Some(Vec::new());
+        self
+    }
+
+    /// Charge trades in each listed symbol with its own cost model.
+    ///
+    /// Symbols not in `costs` keep using the portfolio's default model.
+    ///
+    /// ```ignore
+    /// let crypto = CostModel { commission_bps: 50, slippage_bps: 0, min_trade_fee: 0, size_impact: None, fee_tiers: None };
+    /// let portfolio = Portfolio::new(1_000_000_00, CostModel::zero())
+    ///     .with_symbol_costs([(Symbol::new("BTC"), crypto)]);
+    /// ```
+    pub fn with_symbol_costs(
+        mut self,
+        costs: impl IntoIterator<Item = (Symbol, CostModel)>,
+    ) -> Self {
+        self.symbol_costs.extend(costs);
+        self
+    }
+
+    /// Quote each listed symbol in its own currency (e.g. from
+    /// [`InstrumentSpec::currency`](crate::InstrumentSpec::currency)).
+    ///
+    /// Symbols not in `currencies` are USD. Only
+    /// [`total_equity_fx`](Self::total_equity_fx) converts between currencies;
+    /// every other method sums raw cents.
+    pub fn with_symbol_currencies(
+        mut self,
+        currencies: impl IntoIterator<Item = (Symbol, Symbol)>,
+    ) -> Self {
+        for (symbol, currency) in currencies {
+            if let Some(pos) = self.positions.get_mut(&symbol) {
+                pos.currency = currency;
+            }
+            self.symbol_currencies.insert(symbol, currency);
+        }
+        self
+    }
+
+    /// Hold the cash balance in `currency` instead of USD.
+    pub fn with_cash_currency(mut self, currency: Symbol) -> Self {
+        self.cash_currency = currency;
+        self
+    }
+
+    /// Create a portfolio whose gross exposure may not exceed `max_leverage × equity`.
+    ///
+    /// Fills that would breach the cap are scaled down (or skipped entirely);
+    /// fills that reduce exposure are always allowed. Short positions count
+    /// toward gross exposure, so they are only possible within the cap.
+    pub fn with_leverage(initial_cash: i64, cost_model: CostModel, max_leverage: f64) -> Self {
+        debug_assert!(
+            max_leverage.is_finite() && max_leverage > 0.0,
+            "max_leverage must be finite and positive, got {max_leverage}"
+        );
+        Self {
+            max_leverage: Some(max_leverage),
+            ..Self::new(initial_cash, cost_model)
+        }
+    }
+
+    /// Bound the gross target weight (`Σ |weight|`) of each rebalance.
+    ///
+    /// Target sets whose gross weight exceeds `max_gross_weight` are scaled
+    /// down proportionally before trading; a 130/30 book (gross 1.6) needs a
+    /// bound of at least 1.6 to trade in full.
+    pub fn with_max_gross_weight(mut self, max_gross_weight: f64) -> Self {
+        debug_assert!(
+            max_gross_weight.is_finite() && max_gross_weight > 0.0,
+            "max_gross_weight must be finite and positive, got {max_gross_weight}"
+        );
+        self.max_gross_weight = Some(max_gross_weight);
+        self
+    }
+
     // === Queries ===
 
     /// Current cash balance (cents).
@@ -123,6 +334,24 @@ impl Portfolio {
         self.positions.iter()
     }
 
+    /// Gross buys, gross sells and net quantity for `symbol`, whichever
+    /// venue filled them. `None` if the symbol was never traded.
+    pub fn consolidated_position(&self, symbol: &Symbol) -> Option<ConsolidatedPosition> {
+        self.positions.get(symbol).map(|pos| ConsolidatedPosition {
+            symbol: *symbol,
+            bought: pos.bought,
+            sold: pos.sold,
+            net: pos.quantity,
+        })
+    }
+
+    /// Total quantity traded in `symbol` (bought + sold), across all venues.
+    pub fn gross_traded_quantity(&self, symbol: &Symbol) -> i64 {
+        self.positions
+            .get(symbol)
+            .map_or(0, |pos| pos.bought.saturating_add(pos.sold))
+    }
+
     /// Total equity: cash + sum of all position market values.
     ///
     /// `prices` maps symbols to current prices (cents).
@@ -139,6 +368,75 @@ impl Portfolio {
         self.cash + position_value
     }
 
+    /// Total equity in `base_currency`, converting cash and each position's
+    /// market value with `fx_rates`.
+    ///
+    /// Each rate `(from, to, rate)` means one unit of `from` is worth `rate`
+    /// units of `to`; the inverse pair is derived when only one direction is
+    /// quoted. Amounts in a currency with no usable rate are left out of
+    /// [`FxEquity::equity`] and reported in [`FxEquity::missing_rates`].
+    ///
+    /// ```ignore
+    /// let eur_usd = [(Symbol::new("EUR"), Symbol::new("USD"), 1.10)];
+    /// let value = portfolio.total_equity_fx(&prices, &eur_usd, Symbol::new("USD"));
+    /// assert!(value.is_complete());
+    /// ```
+    pub fn total_equity_fx(
+        &self,
+        prices: &[(Symbol, i64)],
+        fx_rates: &[(Symbol, Symbol, f64)],
+        base_currency: Symbol,
+    ) -> FxEquity {
+        let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+        let mut total = 0.0;
+        let mut missing = Vec::new();
+        let amounts = std::iter::once((self.cash_currency, self.cash)).chain(
+            self.positions.iter().map(|(sym, pos)| {
+                let price = price_map.get(sym).copied().unwrap_or(0);
+                (pos.currency, pos.market_value(price))
+            }),
+        );
+        for (currency, amount) in amounts {
+            match fx_rate(fx_rates, currency, base_currency) {
+                Some(rate) => total += amount as f64 * rate,
+                None => missing.push(currency),
+            }
+        }
+        missing.sort();
+        missing.dedup();
+        FxEquity {
+            equity: total.round() as i64,
+            missing_rates: missing,
+        }
+    }
+
+    /// Gross exposure: sum of `|quantity| × price` across positions (cents).
+    pub fn gross_exposure(&self, prices: &[(Symbol, i64)]) -> i64 {
+        let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+        self.positions
+            .iter()
+            .map(|(sym, pos)| {
+                let price = price_map.get(sym).copied().unwrap_or(0);
+                pos.market_value(price).saturating_abs()
+            })
+            .sum()
+    }
+
+    /// Net exposure: sum of signed `quantity × price` across positions (cents).
+    pub fn net_exposure(&self, prices: &[(Symbol, i64)]) -> i64 {
+        self.total_equity(prices) - self.cash
+    }
+
+    /// Leverage cap (gross exposure / equity), if one is configured.
+    pub fn max_leverage(&self) -> Option<f64> {
+        self.max_leverage
+    }
+
+    /// Gross target weight bound, if one is configured.
+    pub fn max_gross_weight(&self) -> Option<f64> {
+        self.max_gross_weight
+    }
+
     /// Current portfolio weights as (symbol, weight) pairs.
     ///
     /// Weights are fractions of total equity. Cash is not included
@@ -170,11 +468,48 @@ impl Portfolio {
         &self.equity_curve
     }
 
-    /// The cost model in use.
+    /// The default cost model, used for symbols without their own.
     pub fn cost_model(&self) -> &CostModel {
         &self.cost_model
     }
 
+    /// The cost model applied to trades in `symbol`.
+    pub fn cost_model_for(&self, symbol: &Symbol) -> &CostModel {
+        self.symbol_costs.get(symbol).unwrap_or(&self.cost_model)
+    }
+
+    /// Fills recorded since the last call, oldest first.
+    ///
+    /// Always empty unless the portfolio was built [`with_fill_log`](Self::with_fill_log).
+    pub fn take_fills(&mut self) -> Vec<PortfolioFill> {
+        self.fill_log
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// The cost basis method applied to new positions.
+    pub fn cost_basis(&self) -> CostBasisMethod {
+        self.cost_basis
+    }
+
+    /// One-sided turnover of the most recent rebalance.
+    ///
+    /// Computed as the larger of total weight bought and total weight sold,
+    /// comparing the post-trade weights recorded by the last
+    /// `rebalance_simple`/`rebalance_with_band` call against `prior_weights`
+    /// (typically `current_weights` taken just before that rebalance). Taking
+    /// the larger side keeps the figure meaningful when cash absorbs the trades.
+    pub fn turnover(&self, prior_weights: &[(Symbol, f64)]) -> f64 {
+        let mut diffs: FxHashMap<Symbol, f64> = self.last_weights.iter().copied().collect();
+        for &(sym, w) in prior_weights {
+            *diffs.entry(sym).or_insert(0.0) -= w;
+        }
+        let bought: f64 = diffs.values().filter(|d| **d > 0.0).sum();
+        let sold: f64 = diffs.values().filter(|d| **d < 0.0).map(|d| -d).sum();
+        bought.max(sold)
+    }
+
     // === Execution ===
 
     /// Rebalance the portfolio to target weights using simple fill (instant execution).
@@ -182,18 +517,72 @@ impl Portfolio {
     /// This is the hot path for parameter sweeps. Orders execute at the provided
     /// bar prices with no market microstructure simulation.
     ///
-    /// `targets`: desired (symbol, weight) pairs. Weights should sum to ≤ 1.0.
+    /// `targets`: desired (symbol, weight) pairs. Negative weights are short
+    /// positions; the sum of absolute weights should stay ≤ 1.0 unless the
+    /// portfolio is meant to be levered (see [`with_max_gross_weight`](Self::with_max_gross_weight)).
     /// `prices`: current (symbol, price_in_cents) for each symbol.
     ///
     /// Positions not in `targets` are closed. Costs are deducted from cash.
     pub fn rebalance_simple(&mut self, targets: &[(Symbol, f64)], prices: &[(Symbol, i64)]) {
+        self.last_skipped.clear();
+        self.rebalance_banded(targets, prices, 0.0);
+    }
+
+    /// Rebalance with a no-trade band around each target weight.
+    ///
+    /// Behaves like [`rebalance_simple`](Self::rebalance_simple), except a symbol
+    /// is only traded when `|current_weight - target_weight| > no_trade_band`.
+    /// Positions inside the band are left untouched, which cuts turnover from
+    /// small drifts. Symbols absent from `targets` have a target weight of 0.
+    pub fn rebalance_with_band(
+        &mut self,
+        targets: &[(Symbol, f64)],
+        prices: &[(Symbol, i64)],
+        no_trade_band: f64,
+    ) {
+        let band = if no_trade_band.is_finite() {
+            no_trade_band.max(0.0)
+        } else {
+            0.0
+        };
+        self.last_skipped.clear();
+        self.rebalance_banded(targets, prices, band);
+    }
+
+    fn rebalance_banded(&mut self, targets: &[(Symbol, f64)], prices: &[(Symbol, i64)], band: f64) {
         let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
         let equity = self.total_equity(prices);
         if equity <= 0 {
             return;
         }
+        if self.max_leverage.is_some() {
+            self.marks.extend(prices.iter().copied());
+        }
+
+        let scaled: Vec<(Symbol, f64)>;
+        let targets = match self.max_gross_weight {
+            Some(limit) => {
+                let gross: f64 = targets.iter().map(|(_, w)| w.abs()).sum();
+                if gross > limit {
+                    let scale = limit / gross;
+                    scaled = targets.iter().map(|&(s, w)| (s, w * scale)).collect();
+                    &scaled[..]
+                } else {
+                    targets
+                }
+            }
+            None => targets,
+        };
 
         let target_map: FxHashMap<Symbol, f64> = targets.iter().copied().collect();
+        let current: FxHashMap<Symbol, f64> = if band > 0.0 {
+            self.current_weights(prices).into_iter().collect()
+        } else {
+            FxHashMap::default()
+        };
+        let outside_band = |sym: &Symbol, target: f64| -> bool {
+            band <= 0.0 || (current.get(sym).copied().unwrap_or(0.0) - target).abs() > band
+        };
 
         // Close positions not in targets
         let to_close: Vec<Symbol> = self
@@ -204,12 +593,15 @@ impl Portfolio {
             .collect();
 
         for sym in to_close {
+            if !outside_band(&sym, 0.0) {
+                continue;
+            }
             if let Some(price) = price_map.get(&sym).copied() {
                 let qty = match self.positions.get(&sym) {
                     Some(pos) if !pos.is_flat() => -pos.quantity,
                     _ => continue,
                 };
-                self.execute_fill(sym, qty, price);
+                self.execute_fill(sym, qty, price, LiquidityFlag::Taker);
             }
         }
 
@@ -219,6 +611,9 @@ impl Portfolio {
                 Some(p) if p > 0 => p,
                 _ => continue,
             };
+            if !outside_band(&sym, target_weight) {
+                continue;
+            }
 
             let current_value = self
                 .positions
@@ -232,15 +627,32 @@ impl Portfolio {
             // Convert value difference to shares
             let diff_qty = diff_value / price;
             if diff_qty != 0 {
-                self.execute_fill(sym, diff_qty, price);
+                self.execute_fill(sym, diff_qty, price, LiquidityFlag::Taker);
             }
         }
+
+        self.last_weights = self.current_weights(prices);
+    }
+
+    /// Credit a cash dividend of `per_share_cents` on the held position in `symbol`.
+    ///
+    /// Cash changes by `per_share_cents × quantity`, so short positions pay the
+    /// dividend. Symbols without an open position are ignored (no position is
+    /// created). Returns the cash amount credited (negative for shorts).
+    pub fn apply_dividend(&mut self, symbol: Symbol, per_share_cents: i64) -> i64 {
+        let amount = match self.positions.get(&symbol) {
+            Some(pos) if !pos.is_flat() => pos.quantity.saturating_mul(per_share_cents),
+            _ => return 0,
+        };
+        self.cash = self.cash.saturating_add(amount);
+        amount
     }
 
     /// Close a single symbol position at the provided price.
     ///
     /// Returns `true` if a non-flat position existed and was closed.
     pub fn close_position_at(&mut self, symbol: Symbol, price: i64) -> bool {
+        self.last_skipped.clear();
         if price <= 0 {
             return false;
         }
@@ -250,7 +662,7 @@ impl Portfolio {
             _ => return false,
         };
 
-        self.execute_fill(symbol, qty, price);
+        self.execute_fill(symbol, qty, price, LiquidityFlag::Taker);
         true
     }
 
@@ -266,6 +678,8 @@ impl Portfolio {
         targets: &[(Symbol, f64)],
         exchanges: &mut crate::multi_exchange::MultiExchange,
     ) {
+        self.last_skipped.clear();
+
         // Collect current prices from exchange BBO
         let prices: Vec<(Symbol, i64)> = exchanges
             .symbols()
@@ -314,14 +728,7 @@ impl Portfolio {
             };
             let exchange = exchanges.get_or_create(&sym);
             let result = exchange.submit_market(side, qty);
-            for trade in &result.trades {
-                let fill_qty = if side == crate::Side::Sell {
-                    -(trade.quantity as i64)
-                } else {
-                    trade.quantity as i64
-                };
-                self.execute_fill(sym, fill_qty, trade.price.0);
-            }
+            self.book_lob_fills(sym, side, &result);
         }
 
         // Rebalance each target
@@ -353,17 +760,49 @@ impl Portfolio {
 
             let exchange = exchanges.get_or_create(&sym);
             let result = exchange.submit_market(side, diff_qty);
-            for trade in &result.trades {
-                let fill_qty = if side == crate::Side::Buy {
-                    trade.quantity as i64
-                } else {
-                    -(trade.quantity as i64)
-                };
-                self.execute_fill(sym, fill_qty, trade.price.0);
-            }
+            self.book_lob_fills(sym, side, &result);
+        }
+    }
+
+    /// Book the trades of an order this portfolio submitted as `side`.
+    ///
+    /// Use this for orders routed outside [`rebalance_lob`](Self::rebalance_lob),
+    /// e.g. to a specific venue book of a `MultiExchange`. Each trade is
+    /// charged as maker or taker according to the order's role in it.
+    pub fn apply_lob_fills(
+        &mut self,
+        symbol: Symbol,
+        side: crate::Side,
+        result: &crate::SubmitResult,
+    ) {
+        self.last_skipped.clear();
+        self.book_lob_fills(symbol, side, result);
+    }
+
+    fn book_lob_fills(&mut self, symbol: Symbol, side: crate::Side, result: &crate::SubmitResult) {
+        for trade in &result.trades {
+            let fill_qty = match side {
+                crate::Side::Buy => trade.quantity as i64,
+                crate::Side::Sell => -(trade.quantity as i64),
+            };
+            self.execute_fill(
+                symbol,
+                fill_qty,
+                trade.price.0,
+                LiquidityFlag::of(trade, result.order_id),
+            );
         }
     }
 
+    /// Shares the leverage cap refused to trade in the most recent
+    /// rebalance, [`close_position_at`](Self::close_position_at) or
+    /// [`apply_lob_fills`](Self::apply_lob_fills) call, per symbol.
+    ///
+    /// Always empty unless the portfolio was built [`with_leverage`](Self::with_leverage).
+    pub fn last_skipped(&self) -> &[(Symbol, i64)] {
+        &self.last_skipped
+    }
+
     /// Record a return for the current period.
     ///
     /// Call this at the end of each period (day, month, etc.) after rebalancing.
@@ -412,25 +851,124 @@ impl Portfolio {
     // === Internal ===
 
     /// Execute a fill: update position, deduct cost, adjust cash.
-    fn execute_fill(&mut self, symbol: Symbol, qty: i64, price: i64) {
+    ///
+    /// `liquidity` selects the maker or taker tier of the symbol's cost
+    /// model; a maker rebate (negative cost) adds to cash.
+    ///
+    /// Returns the number of shares skipped by the leverage cap (0 when uncapped).
+    fn execute_fill(
+        &mut self,
+        symbol: Symbol,
+        qty: i64,
+        price: i64,
+        liquidity: LiquidityFlag,
+    ) -> i64 {
         if qty == 0 {
-            return;
+            return 0;
+        }
+
+        let requested = qty;
+        let qty = match self.max_leverage {
+            Some(max_leverage) => self.clamp_to_leverage(symbol, qty, price, max_leverage),
+            None => qty,
+        };
+        let skipped = (requested - qty).abs();
+        if skipped > 0 {
+            match self.last_skipped.iter_mut().find(|(s, _)| *s == symbol) {
+                Some((_, total)) => *total += skipped,
+                None => self.last_skipped.push((symbol, skipped)),
+            }
+        }
+        if qty == 0 {
+            return skipped;
         }
 
         let notional = qty.saturating_abs().saturating_mul(price);
-        let cost = self.cost_model.compute_cost(notional);
+        let cost = self.cost_model_for(&symbol).compute_cost_with_liquidity(
+            notional,
+            qty.unsigned_abs(),
+            liquidity,
+        );
 
         // Update position
-        let pos = self
-            .positions
-            .entry(symbol)
-            .or_insert_with(|| Position::new(symbol));
+        let cost_basis = self.cost_basis;
+        let currency = self
+            .symbol_currencies
+            .get(&symbol)
+            .copied()
+            .unwrap_or_else(default_currency);
+        let pos = self.positions.entry(symbol).or_insert_with(|| {
+            Position::with_cost_basis(symbol, cost_basis).with_currency(currency)
+        });
         pos.apply_fill(qty, price);
 
         // Adjust cash: buying decreases cash, selling increases it
         self.cash = self
             .cash
             .saturating_sub(qty.saturating_mul(price).saturating_add(cost));
+
+        if let Some(log) = self.fill_log.as_mut() {
+            log.push(PortfolioFill {
+                symbol,
+                quantity: qty,
+                price,
+                cost,
+            });
+        }
+
+        skipped
+    }
+
+    /// Shrink `qty` so post-fill gross exposure stays within `max_leverage × equity`.
+    ///
+    /// Exposure is marked at the last known price per symbol. Any part of the
+    /// fill that reduces the existing position is always kept.
+    fn clamp_to_leverage(
+        &mut self,
+        symbol: Symbol,
+        qty: i64,
+        price: i64,
+        max_leverage: f64,
+    ) -> i64 {
+        self.marks.insert(symbol, price);
+        if price <= 0 {
+            return qty;
+        }
+
+        let current = self.positions.get(&symbol).map_or(0, |p| p.quantity);
+        let target = current.saturating_add(qty);
+        if target.abs() <= current.abs() && target.signum() * current.signum() >= 0 {
+            return qty;
+        }
+
+        let mut other_gross = 0_i64;
+        let mut equity = self.cash;
+        for (sym, pos) in &self.positions {
+            let mark = self.marks.get(sym).copied().unwrap_or(pos.avg_entry_price);
+            equity = equity.saturating_add(pos.market_value(mark));
+            if *sym != symbol {
+                other_gross = other_gross.saturating_add(pos.market_value(mark).saturating_abs());
+            }
+        }
+        equity -= self.cost_model_for(&symbol).compute_cost_sized(
+            qty.saturating_abs().saturating_mul(price),
+            qty.unsigned_abs(),
+        );
+
+        let headroom = (equity as f64 * max_leverage) as i64 - other_gross;
+        let allowed_abs = (headroom / price).max(0);
+        if target.abs() <= allowed_abs {
+            return qty;
+        }
+
+        let clamped = if target.signum() == current.signum() {
+            // Growing the same side: stop at the cap, never below the current size
+            target.signum() * allowed_abs.max(current.abs())
+        } else {
+            // Flipping through flat: the closing part is free, the new side is capped
+            target.signum() * allowed_abs
+        };
+        clamped - current
     }
 }
 
@@ -504,6 +1042,8 @@ mod tests {
             commission_bps: 10,
             slippage_bps: 0,
             min_trade_fee: 0,
+            size_impact: None,
+            fee_tiers: None,
         };
         let mut portfolio = Portfolio::new(1_000_000_00, model);
         let prices = [(aapl(), 150_00)];
@@ -516,6 +1056,87 @@ mod tests {
         assert!(equity < 1_000_000_00);
     }
 
+    #[test]
+    fn per_symbol_cost_models() {
+        let high_fee = CostModel {
+            commission_bps: 100,
+            slippage_bps: 0,
+            min_trade_fee: 0,
+            size_impact: None,
+            fee_tiers: None,
+        };
+        let default = CostModel {
+            commission_bps: 10,
+            slippage_bps: 0,
+            min_trade_fee: 0,
+            size_impact: None,
+            fee_tiers: None,
+        };
+        let btc = Symbol::new("BTC");
+        let mut portfolio = Portfolio::new(1_000_000_00, default)
+            .with_symbol_costs([(btc, high_fee), (aapl(), CostModel::zero())]);
+        let prices = [(btc, 50_000_00), (aapl(), 100_00), (msft(), 100_00)];
+
+        portfolio.rebalance_simple(&[(btc, 0.3), (aapl(), 0.3), (msft(), 0.3)], &prices);
+
+        // Each leg buys $300K: BTC pays 1%, AAPL nothing, MSFT (absent) the 0.1% default.
+        let spent = 3 * 300_000_00;
+        let fees = 1_000_000_00 - spent - portfolio.cash();
+        assert_eq!(fees, 3_000_00 + 300_00);
+        assert_eq!(portfolio.cost_model_for(&msft()).commission_bps, 10);
+        assert_eq!(portfolio.cost_model_for(&btc).commission_bps, 100);
+    }
+
+    #[test]
+    fn fill_log_records_fills_when_enabled() {
+        let prices = [(aapl(), 150_00), (msft(), 300_00)];
+        let targets = [(aapl(), 0.3), (msft(), 0.3)];
+
+        let mut silent = Portfolio::new(1_000_000_00, CostModel::zero());
+        silent.rebalance_simple(&targets, &prices);
+        assert!(silent.take_fills().is_empty());
+
+        let mut logged = Portfolio::new(1_000_000_00, CostModel::zero()).with_fill_log();
+        logged.rebalance_simple(&targets, &prices);
+        logged.close_position_at(aapl(), 140_00);
+        let fills = logged.take_fills();
+        assert_eq!(fills.len(), 3);
+        assert_eq!(fills[2].symbol, aapl());
+        assert_eq!(fills[2].quantity, -2_000);
+        assert!(logged.take_fills().is_empty());
+    }
+
+    #[test]
+    fn negative_weights_open_shorts_within_gross_bound() {
+        let prices = [(aapl(), 100_00), (msft(), 200_00)];
+        let targets = [(aapl(), 1.3), (msft(), -0.7)];
+
+        let mut unbounded = Portfolio::new(1_000_000_00, CostModel::zero());
+        unbounded.rebalance_simple(&targets, &prices);
+        assert_eq!(unbounded.position(&aapl()).unwrap().quantity, 13_000);
+        assert_eq!(unbounded.position(&msft()).unwrap().quantity, -3_500);
+
+        let mut bounded =
+            Portfolio::new(1_000_000_00, CostModel::zero()).with_max_gross_weight(1.0);
+        bounded.rebalance_simple(&targets, &prices);
+        assert_eq!(bounded.position(&aapl()).unwrap().quantity, 6_500);
+        assert_eq!(bounded.position(&msft()).unwrap().quantity, -1_750);
+        let gross: f64 = bounded
+            .current_weights(&prices)
+            .iter()
+            .map(|(_, w)| w.abs())
+            .sum();
+        assert!((gross - 1.0).abs() < 1e-9);
+
+        // Flipping through flat in one rebalance leaves a fresh short.
+        bounded.rebalance_simple(&[(aapl(), -0.5)], &prices);
+        let pos = bounded.position(&aapl()).unwrap();
+        assert_eq!(pos.quantity, -5_000);
+        assert_eq!(pos.avg_entry_price, 100_00);
+        assert!(bounded.close_position_at(aapl(), 90_00));
+        assert!(bounded.position(&aapl()).unwrap().is_flat());
+    }
+
     #[test]
     fn rebalance_closes_unneeded_positions() {
         let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
@@ -582,6 +1203,276 @@ mod tests {
         // Weight should be approximately 0.5
         assert!((weights[0].1 - 0.5).abs() < 0.01);
     }
+
+    #[test]
+    fn band_skips_small_drift_and_trades_large() {
+        let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
+        let prices = [(aapl(), 100_00), (msft(), 100_00)];
+        portfolio.rebalance_simple(&[(aapl(), 0.40), (msft(), 0.40)], &prices);
+        let aapl_before = portfolio.position(&aapl()).unwrap().quantity;
+        let msft_before = portfolio.position(&msft()).unwrap().quantity;
+
+        // AAPL drifts 2% from target, MSFT 8%.
+        let targets = [(aapl(), 0.42), (msft(), 0.48)];
+        portfolio.rebalance_with_band(&targets, &prices, 0.05);
+
+        assert_eq!(portfolio.position(&aapl()).unwrap().quantity, aapl_before);
+        assert!(portfolio.position(&msft()).unwrap().quantity > msft_before);
+        let weights: FxHashMap<Symbol, f64> =
+            portfolio.current_weights(&prices).into_iter().collect();
+        assert!((weights[&msft()] - 0.48).abs() < 1e-3);
+    }
+
+    #[test]
+    fn band_reduces_turnover_vs_simple() {
+        let prices = [(aapl(), 100_00), (msft(), 100_00)];
+        let start = [(aapl(), 0.40), (msft(), 0.40)];
+        let targets = [(aapl(), 0.42), (msft(), 0.48)];
+
+        let mut simple = Portfolio::new(1_000_000_00, CostModel::zero());
+        simple.rebalance_simple(&start, &prices);
+        let prior = simple.current_weights(&prices);
+        simple.rebalance_simple(&targets, &prices);
+
+        let mut banded = Portfolio::new(1_000_000_00, CostModel::zero());
+        banded.rebalance_simple(&start, &prices);
+        banded.rebalance_with_band(&targets, &prices, 0.05);
+
+        let simple_turnover = simple.turnover(&prior);
+        let banded_turnover = banded.turnover(&prior);
+        assert!((simple_turnover - 0.10).abs() < 1e-3, "{simple_turnover}");
+        assert!((banded_turnover - 0.08).abs() < 1e-3, "{banded_turnover}");
+        assert!(banded_turnover < simple_turnover);
+    }
+
+    #[test]
+    fn band_leaves_small_untargeted_position() {
+        let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
+        let prices = [(aapl(), 100_00), (msft(), 100_00)];
+        portfolio.rebalance_simple(&[(aapl(), 0.5), (msft(), 0.03)], &prices);
+
+        portfolio.rebalance_with_band(&[(aapl(), 0.5)], &prices, 0.05);
+        assert!(!portfolio.position(&msft()).unwrap().is_flat());
+    }
+
+    #[test]
+    fn leverage_cap_allows_two_x() {
+        let mut portfolio = Portfolio::with_leverage(1_000_000_00, CostModel::zero(), 2.0);
+        let prices = [(aapl(), 100_00), (msft(), 100_00)];
+        portfolio.rebalance_simple(&[(aapl(), 1.0), (msft(), 1.0)], &prices);
+
+        let equity = portfolio.total_equity(&prices) as f64;
+        let gross = portfolio.gross_exposure(&prices) as f64;
+        assert!(
+            (gross / equity - 2.0).abs() < 1e-3,
+            "gross/equity={}",
+            gross / equity
+        );
+        assert!(portfolio.cash() < 0);
+    }
+
+    #[test]
+    fn leverage_cap_clamps_three_x() {
+        let mut portfolio = Portfolio::with_leverage(1_000_000_00, CostModel::zero(), 2.0);
+        let prices = [(aapl(), 100_00), (msft(), 100_00)];
+        portfolio.rebalance_simple(&[(aapl(), 1.5), (msft(), 1.5)], &prices);
+
+        let equity = portfolio.total_equity(&prices) as f64;
+        let gross = portfolio.gross_exposure(&prices) as f64;
+        assert!(
+            gross / equity <= 2.0 + 1e-9,
+            "gross/equity={}",
+            gross / equity
+        );
+        assert!(gross / equity > 1.99);
+        assert_eq!(portfolio.position(&aapl()).unwrap().quantity, 15_000);
+        assert_eq!(portfolio.position(&msft()).unwrap().quantity, 5_000);
+        assert_eq!(portfolio.last_skipped(), &[(msft(), 10_000)]);
+    }
+
+    #[test]
+    fn rebalance_reports_shares_skipped_by_leverage_cap() {
+        let mut portfolio = Portfolio::with_leverage(100_000_00, CostModel::zero(), 1.0);
+        let prices = [(aapl(), 100_00)];
+
+        portfolio.rebalance_simple(&[(aapl(), 3.0)], &prices);
+        assert_eq!(portfolio.position(&aapl()).unwrap().quantity, 1_000);
+        assert_eq!(portfolio.last_skipped(), &[(aapl(), 2_000)]);
+
+        portfolio.rebalance_simple(&[(aapl(), 0.5)], &prices);
+        assert_eq!(portfolio.position(&aapl()).unwrap().quantity, 500);
+        assert!(portfolio.last_skipped().is_empty());
+
+        assert!(portfolio.close_position_at(aapl(), 100_00));
+        assert!(portfolio.last_skipped().is_empty());
+    }
+
+    #[test]
+    fn leverage_cap_reports_skipped_and_allows_reductions() {
+        let mut portfolio = Portfolio::with_leverage(100_000_00, CostModel::zero(), 1.0);
+        let skipped = portfolio.execute_fill(aapl(), 1_500, 100_00, LiquidityFlag::Taker);
+        assert_eq!(skipped, 500);
+        assert_eq!(portfolio.position(&aapl()).unwrap().quantity, 1_000);
+
+        // Shorting is bounded by the same cap, but the closing leg is free.
+        let skipped = portfolio.execute_fill(aapl(), -2_500, 100_00, LiquidityFlag::Taker);
+        assert_eq!(skipped, 500);
+        assert_eq!(portfolio.position(&aapl()).unwrap().quantity, -1_000);
+
+        assert_eq!(
+            portfolio.execute_fill(aapl(), 400, 100_00, LiquidityFlag::Taker),
+            0
+        );
+        assert_eq!(portfolio.position(&aapl()).unwrap().quantity, -600);
+    }
+
+    #[test]
+    fn gross_and_net_exposure() {
+        let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
+        portfolio.execute_fill(aapl(), 100, 100_00, LiquidityFlag::Taker);
+        portfolio.execute_fill(msft(), -50, 200_00, LiquidityFlag::Taker);
+        let prices = [(aapl(), 100_00), (msft(), 200_00)];
+        assert_eq!(portfolio.gross_exposure(&prices), 20_000_00);
+        assert_eq!(portfolio.net_exposure(&prices), 0);
+        assert_eq!(portfolio.max_leverage(), None);
+    }
+
+    #[test]
+    fn portfolio_cost_basis_applies_to_positions() {
+        let mut portfolio =
+            Portfolio::new(1_000_000_00, CostModel::zero()).with_cost_basis(CostBasisMethod::Lifo);
+        portfolio.execute_fill(aapl(), 100, 50_00, LiquidityFlag::Taker);
+        portfolio.execute_fill(aapl(), 100, 60_00, LiquidityFlag::Taker);
+        portfolio.execute_fill(aapl(), -100, 70_00, LiquidityFlag::Taker);
+
+        let pos = portfolio.position(&aapl()).unwrap();
+        assert_eq!(pos.cost_basis(), CostBasisMethod::Lifo);
+        assert_eq!(pos.realized_pnl, 100 * 10_00);
+    }
+
+    #[test]
+    fn maker_rebate_increases_cash_over_taker() {
+        let tiered = CostModel::zero().with_fee_tiers(-2, 5);
+        let mut maker = Portfolio::new(1_000_000_00, tiered);
+        let mut taker = Portfolio::new(1_000_000_00, tiered);
+        maker.execute_fill(aapl(), 1_000, 100_00, LiquidityFlag::Maker);
+        taker.execute_fill(aapl(), 1_000, 100_00, LiquidityFlag::Taker);
+
+        // $100K notional: 2 bps rebate ($20) vs 5 bps fee ($50).
+        assert_eq!(maker.cash(), 1_000_000_00 - 100_000_00 + 20_00);
+        assert_eq!(taker.cash(), 1_000_000_00 - 100_000_00 - 50_00);
+        assert!(maker.cash() > taker.cash());
+    }
+
+    #[test]
+    fn rebalance_lob_charges_market_orders_as_taker() {
+        use crate::multi_exchange::MultiExchange;
+        use crate::{Price, Side, TimeInForce};
+
+        let mut exchanges = MultiExchange::new();
+        let ex = exchanges.get_or_create(&aapl());
+        ex.submit_limit(Side::Buy, Price(99_00), 10_000, TimeInForce::GTC);
+        ex.submit_limit(Side::Sell, Price(101_00), 10_000, TimeInForce::GTC);
+
+        let mut portfolio =
+            Portfolio::new(1_000_000_00, CostModel::zero().with_fee_tiers(-2, 10)).with_fill_log();
+        portfolio.rebalance_lob(&[(aapl(), 0.5)], &mut exchanges);
+
+        let fills = portfolio.take_fills();
+        assert_eq!(fills.len(), 1);
+        let notional = fills[0].quantity * fills[0].price;
+        assert_eq!(fills[0].cost, notional * 10 / 10_000);
+    }
+
+    #[test]
+    fn consolidated_position_nets_fills_across_venues() {
+        use crate::multi_exchange::MultiExchange;
+        use crate::{Price, Side, TimeInForce};
+
+        let venue_a = Symbol::new("XNAS");
+        let venue_b = Symbol::new("ARCX");
+        let mut exchanges = MultiExchange::new();
+        exchanges.get_or_create_on(&venue_a, &aapl()).submit_limit(
+            Side::Sell,
+            Price(100_00),
+            100,
+            TimeInForce::GTC,
+        );
+        exchanges.get_or_create_on(&venue_b, &aapl()).submit_limit(
+            Side::Buy,
+            Price(101_00),
+            40,
+            TimeInForce::GTC,
+        );
+
+        let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
+        let bought = exchanges
+            .get_or_create_on(&venue_a, &aapl())
+            .submit_market(Side::Buy, 100);
+        portfolio.apply_lob_fills(aapl(), Side::Buy, &bought);
+        let sold = exchanges
+            .get_or_create_on(&venue_b, &aapl())
+            .submit_market(Side::Sell, 40);
+        portfolio.apply_lob_fills(aapl(), Side::Sell, &sold);
+
+        let consolidated = portfolio.consolidated_position(&aapl()).unwrap();
+        assert_eq!(
+            consolidated,
+            ConsolidatedPosition {
+                symbol: aapl(),
+                bought: 100,
+                sold: 40,
+                net: 60,
+            }
+        );
+        assert_eq!(portfolio.gross_traded_quantity(&aapl()), 140);
+        assert_eq!(portfolio.position(&aapl()).unwrap().quantity, 60);
+        assert_eq!(portfolio.cash(), 1_000_000_00 - 100 * 100_00 + 40 * 101_00);
+
+        assert!(portfolio.consolidated_position(&msft()).is_none());
+        assert_eq!(portfolio.gross_traded_quantity(&msft()), 0);
+    }
+
+    #[test]
+    fn total_equity_fx_converts_each_currency() {
+        let (sap, usd, eur) = (Symbol::new("SAP"), Symbol::new("USD"), Symbol::new("EUR"));
+        let mut portfolio =
+            Portfolio::new(100_000_00, CostModel::zero()).with_symbol_currencies([(sap, eur)]);
+        portfolio.execute_fill(aapl(), 100, 150_00, LiquidityFlag::Taker);
+        portfolio.execute_fill(sap, 50, 120_00, LiquidityFlag::Taker);
+        assert_eq!(portfolio.position(&sap).unwrap().currency, eur);
+        assert_eq!(portfolio.position(&aapl()).unwrap().currency, usd);
+
+        let prices = [(aapl(), 160_00), (sap, 130_00)];
+        let eur_usd = [(eur, usd, 1.10)];
+        let in_usd = portfolio.total_equity_fx(&prices, &eur_usd, usd);
+        let in_eur = portfolio.total_equity_fx(&prices, &eur_usd, eur);
+        assert!(in_usd.is_complete() && in_eur.is_complete());
+        // 79_000 cash + 16_000 AAPL in USD, 6_500 SAP in EUR
+        assert_eq!(in_usd.equity, 79_000_00 + 16_000_00 + 7_150_00);
+        assert!((in_usd.equity as f64 / in_eur.equity as f64 - 1.10).abs() < 1e-6);
+
+        // No rates: EUR holdings are skipped and reported, not valued at zero.
+        let partial = portfolio.total_equity_fx(&prices, &[], usd);
+        assert_eq!(partial.equity, 79_000_00 + 16_000_00);
+        assert_eq!(partial.missing_rates, vec![eur]);
+        let gbp = portfolio.total_equity_fx(&prices, &eur_usd, Symbol::new("GBP"));
+        assert_eq!(gbp.equity, 0);
+        assert_eq!(gbp.missing_rates.len(), 2);
+    }
+
+    #[test]
+    fn dividend_credits_held_shares_only() {
+        let mut portfolio = Portfolio::new(100_000_00, CostModel::zero());
+        portfolio.execute_fill(aapl(), 100, 100_00, LiquidityFlag::Taker);
+        let cash_before = portfolio.cash();
+
+        assert_eq!(portfolio.apply_dividend(aapl(), 1_00), 100_00);
+        assert_eq!(portfolio.cash(), cash_before + 100_00);
+
+        assert_eq!(portfolio.apply_dividend(msft(), 1_00), 0);
+        assert!(portfolio.position(&msft()).is_none());
+    }
 }
 
 #[cfg(all(test, feature = "persistence"))]
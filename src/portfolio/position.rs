@@ -1,7 +1,46 @@
 //! Position tracking for a single symbol.
 
+use std::collections::VecDeque;
+
 use crate::types::Symbol;
 
+/// How realized PnL is computed when a position is reduced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CostBasisMethod {
+    /// Realize against the volume-weighted average entry price (no lot ledger).
+    #[default]
+    AverageCost,
+    /// Consume the oldest open lots first.
+    Fifo,
+    /// Consume the most recent open lots first.
+    Lifo,
+}
+
+/// An open tax lot: a signed quantity acquired at a single price.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lot {
+    /// Signed quantity still open (positive = long, negative = short)
+    pub quantity: i64,
+    /// Entry price (cents)
+    pub price: i64,
+}
+
+/// A (portion of a) lot closed by a reducing fill.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClosedLot {
+    /// Signed quantity closed (sign of the original lot)
+    pub quantity: i64,
+    /// Entry price of the lot (cents)
+    pub entry_price: i64,
+    /// Exit price of the closing fill (cents)
+    pub exit_price: i64,
+    /// Realized PnL for this slice (cents)
+    pub realized_pnl: i64,
+}
+
 /// A position in a single instrument.
 ///
 /// Tracks quantity (positive = long, negative = short), average entry price,
@@ -19,20 +58,78 @@ pub struct Position {
     pub realized_pnl: i64,
     /// Cumulative cost of entry (quantity * avg_entry_price), used for VWAP tracking
     pub total_cost: i64,
+    /// Cumulative quantity bought, across all venues
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bought: i64,
+    /// Cumulative quantity sold, across all venues
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sold: i64,
+    /// Cost basis method used when reducing the position
+    #[cfg_attr(feature = "serde", serde(default))]
+    cost_basis: CostBasisMethod,
+    /// Open lots (only maintained for `Fifo`/`Lifo`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    lots: VecDeque<Lot>,
+    /// Closed lot slices (only maintained for `Fifo`/`Lifo`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    closed_lots: Vec<ClosedLot>,
+    /// Currency the instrument is quoted in (USD by default)
+    #[cfg_attr(feature = "serde", serde(default = "default_currency"))]
+    pub currency: Symbol,
+}
+
+/// Currency assumed when none is configured.
+pub(crate) fn default_currency() -> Symbol {
+    Symbol::new("USD")
 }
 
 impl Position {
     /// Create a new flat position for the given symbol.
     pub fn new(symbol: Symbol) -> Self {
+        Self::with_cost_basis(symbol, CostBasisMethod::AverageCost)
+    }
+
+    /// Create a new flat position that realizes PnL using `method`.
+    pub fn with_cost_basis(symbol: Symbol, method: CostBasisMethod) -> Self {
         Self {
             symbol,
             quantity: 0,
             avg_entry_price: 0,
             realized_pnl: 0,
             total_cost: 0,
+            bought: 0,
+            sold: 0,
+            cost_basis: method,
+            lots: VecDeque::new(),
+            closed_lots: Vec::new(),
+            currency: default_currency(),
         }
     }
 
+    /// Quote this position in `currency` instead of USD.
+    pub fn with_currency(mut self, currency: Symbol) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    /// The cost basis method in use.
+    #[inline]
+    pub fn cost_basis(&self) -> CostBasisMethod {
+        self.cost_basis
+    }
+
+    /// Open lots, oldest first. Empty under `AverageCost`.
+    pub fn lots(&self) -> &VecDeque<Lot> {
+        &self.lots
+    }
+
+    /// Closed lot slices in the order they were realized. Empty under `AverageCost`.
+    ///
+    /// Their `realized_pnl` values sum to [`Position::realized_pnl`].
+    pub fn realized_pnl_by_lot(&self) -> &[ClosedLot] {
+        &self.closed_lots
+    }
+
     /// Apply a fill to this position.
     ///
     /// `qty` is signed: positive = buy, negative = sell.
@@ -41,10 +138,62 @@ impl Position {
     /// If the fill increases the position (same direction), the average entry
     /// price is updated via VWAP. If it reduces or flips the position,
     /// realized PnL is recorded for the closed portion.
+    ///
+    /// Equivalent to [`apply_fill_with_fee`](Self::apply_fill_with_fee) with
+    /// a zero fee.
     pub fn apply_fill(&mut self, qty: i64, price: i64) {
+        self.apply_fill_with_fee(qty, price, 0);
+    }
+
+    /// Apply a fill that paid `fee` cents of commission.
+    ///
+    /// The fee is split pro rata between the closed and opened parts of the
+    /// fill: the closing share is deducted from realized PnL, the opening
+    /// share is added to the cost basis (raising a long's entry price,
+    /// lowering a short's), so unrealized PnL is net of entry costs. Under
+    /// `Fifo`/`Lifo` the opening share is folded into the new lot's price,
+    /// rounded toward zero to whole cents per unit.
+    pub fn apply_fill_with_fee(&mut self, qty: i64, price: i64, fee: i64) {
         if qty == 0 {
             return;
         }
+        if qty > 0 {
+            self.bought += qty;
+        } else {
+            self.sold -= qty;
+        }
+
+        let closing = if self.quantity.signum() == -qty.signum() {
+            qty.abs().min(self.quantity.abs())
+        } else {
+            0
+        };
+        let close_fee = fee * closing / qty.abs();
+        let open_fee = fee - close_fee;
+
+        self.apply_fill_price(qty, price);
+        self.realized_pnl -= close_fee;
+        if open_fee == 0 || self.quantity == 0 {
+            return;
+        }
+
+        if self.cost_basis == CostBasisMethod::AverageCost {
+            self.total_cost += open_fee;
+            self.avg_entry_price = self.total_cost / self.quantity;
+        } else if let Some(lot) = self.lots.back_mut() {
+            // The remainder of this fill is always the newest lot.
+            lot.price += open_fee / lot.quantity;
+            self.total_cost = self.lots.iter().map(|l| l.quantity * l.price).sum();
+            self.avg_entry_price = self.total_cost / self.quantity;
+        }
+    }
+
+    /// Apply a fill at `price` without fees.
+    fn apply_fill_price(&mut self, qty: i64, price: i64) {
+        if self.cost_basis != CostBasisMethod::AverageCost {
+            self.apply_fill_lots(qty, price);
+            return;
+        }
 
         let same_direction = (self.quantity >= 0 && qty > 0) || (self.quantity <= 0 && qty < 0);
 
@@ -89,6 +238,62 @@ impl Position {
         }
     }
 
+    /// Lot-ledger variant of `apply_fill`: reducing fills consume lots in
+    /// FIFO or LIFO order and any remainder opens a new lot.
+    fn apply_fill_lots(&mut self, qty: i64, price: i64) {
+        let mut remaining = qty;
+
+        while remaining != 0 {
+            let lot = match self.cost_basis {
+                CostBasisMethod::Lifo => self.lots.back_mut(),
+                _ => self.lots.front_mut(),
+            };
+            let Some(lot) = lot else { break };
+            if lot.quantity.signum() == remaining.signum() {
+                break;
+            }
+
+            let close_qty = remaining.abs().min(lot.quantity.abs());
+            let pnl_per_unit = if lot.quantity > 0 {
+                price - lot.price
+            } else {
+                lot.price - price
+            };
+            let pnl = pnl_per_unit * close_qty;
+            self.realized_pnl += pnl;
+            self.closed_lots.push(ClosedLot {
+                quantity: lot.quantity.signum() * close_qty,
+                entry_price: lot.price,
+                exit_price: price,
+                realized_pnl: pnl,
+            });
+
+            lot.quantity -= lot.quantity.signum() * close_qty;
+            remaining -= remaining.signum() * close_qty;
+            if lot.quantity == 0 {
+                match self.cost_basis {
+                    CostBasisMethod::Lifo => self.lots.pop_back(),
+                    _ => self.lots.pop_front(),
+                };
+            }
+        }
+
+        if remaining != 0 {
+            self.lots.push_back(Lot {
+                quantity: remaining,
+                price,
+            });
+        }
+
+        self.quantity += qty;
+        self.total_cost = self.lots.iter().map(|l| l.quantity * l.price).sum();
+        self.avg_entry_price = if self.quantity == 0 {
+            0
+        } else {
+            self.total_cost / self.quantity
+        };
+    }
+
     /// Current market value at the given price (cents).
     #[inline]
     pub fn market_value(&self, price: i64) -> i64 {
@@ -109,6 +314,49 @@ impl Position {
     pub fn is_flat(&self) -> bool {
         self.quantity == 0
     }
+
+    /// Price (cents) at which an isolated-margin position opened at
+    /// `leverage` would hit its maintenance margin.
+    ///
+    /// With initial margin `1 / leverage` and `maintenance_margin_pct` as a
+    /// fraction (0.005 = 0.5%), a long is liquidated at
+    /// `entry × (1 − 1/leverage + mm)` and a short at
+    /// `entry × (1 + 1/leverage − mm)`, rounded toward the entry price.
+    /// Returns `None` for a flat position, a non-positive leverage, or a long
+    /// that cannot be liquidated at any positive price.
+    pub fn liquidation_price(&self, maintenance_margin_pct: f64, leverage: f64) -> Option<i64> {
+        if self.quantity == 0 || leverage.is_nan() || leverage <= 0.0 {
+            return None;
+        }
+        let entry = self.avg_entry_price as f64;
+        let buffer = 1.0 / leverage - maintenance_margin_pct;
+        if self.quantity > 0 {
+            let price = (entry * (1.0 - buffer)).ceil();
+            (price > 0.0).then_some(price as i64)
+        } else {
+            Some((entry * (1.0 + buffer)).floor() as i64)
+        }
+    }
+
+    /// Exit price (cents) at which closing the position breaks even after
+    /// paying `fee_bps` on both the entry and the exit notional.
+    ///
+    /// Assumes [`avg_entry_price`](Self::avg_entry_price) excludes fees. A
+    /// long needs `entry × (1 + f) / (1 − f)`, a short
+    /// `entry × (1 − f) / (1 + f)`, rounded away from the entry price.
+    /// Returns `None` for a flat position.
+    pub fn break_even_price(&self, fee_bps: f64) -> Option<i64> {
+        if self.quantity == 0 {
+            return None;
+        }
+        let fee = fee_bps / 10_000.0;
+        let entry = self.avg_entry_price as f64;
+        Some(if self.quantity > 0 {
+            (entry * (1.0 + fee) / (1.0 - fee)).ceil() as i64
+        } else {
+            (entry * (1.0 - fee) / (1.0 + fee)).floor() as i64
+        })
+    }
 }
 
 #[cfg(test)]
@@ -203,6 +451,32 @@ mod tests {
         assert_eq!(pos.realized_pnl, 100 * 10_00); // $10 * 100
     }
 
+    #[test]
+    fn buy_commission_raises_cost_basis() {
+        let mut pos = Position::new(sym());
+        pos.apply_fill_with_fee(100, 50_00, 5_00); // $5 commission
+        assert_eq!(pos.avg_entry_price, 50_05);
+        assert_eq!(pos.unrealized_pnl(50_00), -5_00);
+
+        // Half of a $2 exit commission comes out of realized PnL.
+        pos.apply_fill_with_fee(-150, 50_00, 3_00);
+        assert_eq!(pos.realized_pnl, -5_00 - 2_00);
+        assert_eq!(pos.quantity, -50);
+        assert_eq!(pos.unrealized_pnl(50_00), -1_00);
+    }
+
+    #[test]
+    fn commission_with_lots() {
+        let mut pos = Position::with_cost_basis(sym(), CostBasisMethod::Fifo);
+        pos.apply_fill_with_fee(100, 50_00, 5_00);
+        assert_eq!(pos.lots()[0].price, 50_05);
+        assert_eq!(pos.unrealized_pnl(50_00), -5_00);
+
+        pos.apply_fill_with_fee(-100, 51_00, 1_00);
+        assert!(pos.is_flat());
+        assert_eq!(pos.realized_pnl, 100 * 95 - 1_00);
+    }
+
     #[test]
     fn zero_fill_is_noop() {
         let mut pos = Position::new(sym());
@@ -211,4 +485,98 @@ mod tests {
         assert_eq!(pos.quantity, 100);
         assert_eq!(pos.avg_entry_price, 50_00);
     }
+
+    #[test]
+    fn fifo_and_lifo_realize_different_pnl() {
+        let mut fifo = Position::with_cost_basis(sym(), CostBasisMethod::Fifo);
+        let mut lifo = Position::with_cost_basis(sym(), CostBasisMethod::Lifo);
+        for pos in [&mut fifo, &mut lifo] {
+            pos.apply_fill(100, 50_00); // lot 1 @ $50
+            pos.apply_fill(100, 60_00); // lot 2 @ $60
+            pos.apply_fill(-100, 70_00); // sell half @ $70
+        }
+
+        assert_eq!(fifo.realized_pnl, 100 * 20_00);
+        assert_eq!(lifo.realized_pnl, 100 * 10_00);
+        assert_eq!(
+            fifo.lots().iter().copied().collect::<Vec<_>>(),
+            vec![Lot {
+                quantity: 100,
+                price: 60_00
+            }]
+        );
+        assert_eq!(
+            lifo.lots().iter().copied().collect::<Vec<_>>(),
+            vec![Lot {
+                quantity: 100,
+                price: 50_00
+            }]
+        );
+        assert_eq!(fifo.avg_entry_price, 60_00);
+        assert_eq!(lifo.avg_entry_price, 50_00);
+
+        // Average cost sits between the two.
+        let mut avg = Position::new(sym());
+        avg.apply_fill(100, 50_00);
+        avg.apply_fill(100, 60_00);
+        avg.apply_fill(-100, 70_00);
+        assert_eq!(avg.realized_pnl, 100 * 15_00);
+        assert!(avg.lots().is_empty());
+    }
+
+    #[test]
+    fn lot_sale_spanning_lots_and_flip() {
+        let mut pos = Position::with_cost_basis(sym(), CostBasisMethod::Fifo);
+        pos.apply_fill(50, 10_00);
+        pos.apply_fill(50, 20_00);
+        pos.apply_fill(-130, 30_00); // closes both lots, opens short 30
+
+        let closed = pos.realized_pnl_by_lot();
+        assert_eq!(closed.len(), 2);
+        assert_eq!(closed[0].realized_pnl, 50 * 20_00);
+        assert_eq!(closed[1].realized_pnl, 50 * 10_00);
+        assert_eq!(
+            closed.iter().map(|c| c.realized_pnl).sum::<i64>(),
+            pos.realized_pnl
+        );
+
+        assert_eq!(pos.quantity, -30);
+        assert_eq!(pos.avg_entry_price, 30_00);
+        assert_eq!(pos.lots().len(), 1);
+    }
+
+    #[test]
+    fn liquidation_price_sits_on_the_adverse_side() {
+        let mut long = Position::new(sym());
+        long.apply_fill(10, 100_00);
+        let mut short = Position::new(sym());
+        short.apply_fill(-10, 100_00);
+
+        // 10x with 0.5% maintenance: 9.5% adverse move.
+        assert_eq!(long.liquidation_price(0.005, 10.0), Some(90_50));
+        assert_eq!(short.liquidation_price(0.005, 10.0), Some(109_50));
+
+        // Higher leverage moves the liquidation price toward entry.
+        assert_eq!(long.liquidation_price(0.005, 20.0), Some(95_50));
+        assert_eq!(short.liquidation_price(0.005, 20.0), Some(104_50));
+
+        // An unleveraged long with no maintenance margin never liquidates.
+        assert_eq!(long.liquidation_price(0.0, 1.0), None);
+        assert_eq!(long.liquidation_price(0.005, 0.0), None);
+        assert_eq!(Position::new(sym()).liquidation_price(0.005, 10.0), None);
+    }
+
+    #[test]
+    fn break_even_covers_round_trip_fees() {
+        let mut long = Position::new(sym());
+        long.apply_fill(10, 100_00);
+        let mut short = Position::new(sym());
+        short.apply_fill(-10, 100_00);
+
+        // 10 bps each way: ~20 bps away from entry.
+        assert_eq!(long.break_even_price(10.0), Some(100_21));
+        assert_eq!(short.break_even_price(10.0), Some(99_80));
+        assert_eq!(long.break_even_price(0.0), Some(100_00));
+        assert_eq!(Position::new(sym()).break_even_price(10.0), None);
+    }
 }
@@ -0,0 +1,92 @@
+//! Position sizing: Kelly fraction, volatility targeting, inverse-vol weights.
+//!
+//! Volatilities are in the same (e.g. annualized) units throughout; only
+//! their ratios matter.
+
+/// Kelly-optimal fraction of capital for a bet won with probability
+/// `win_rate` that pays `payoff_ratio` times the amount lost on a loss:
+/// `win_rate - (1 - win_rate) / payoff_ratio`.
+///
+/// Negative values mean the edge is negative (don't bet, or take the other
+/// side). Returns 0 if `payoff_ratio` is not positive and finite.
+///
+/// ```
+/// use nanobook::portfolio::sizing::kelly_fraction;
+///
+/// assert_eq!(kelly_fraction(0.5, 2.0), 0.25);
+/// ```
+pub fn kelly_fraction(win_rate: f64, payoff_ratio: f64) -> f64 {
+    if payoff_ratio > 0.0 && payoff_ratio.is_finite() {
+        win_rate - (1.0 - win_rate) / payoff_ratio
+    } else {
+        0.0
+    }
+}
+
+/// Weight that scales an asset with volatility `asset_vol` to `target_vol`,
+/// times `signal` (e.g. conviction in `[-1, 1]`): `signal × target_vol / asset_vol`.
+///
+/// Returns 0 if `asset_vol` is not positive and finite.
+pub fn vol_target_weight(signal: f64, asset_vol: f64, target_vol: f64) -> f64 {
+    if asset_vol > 0.0 && asset_vol.is_finite() {
+        signal * target_vol / asset_vol
+    } else {
+        0.0
+    }
+}
+
+/// Inverse-volatility weights: each asset gets `1 / vol`, normalized to sum to 1.
+///
+/// Assets with a non-positive or non-finite volatility get weight 0. Returns
+/// all zeros if no asset has a usable volatility.
+pub fn equal_risk_weights(vols: &[f64]) -> Vec<f64> {
+    let inverse: Vec<f64> = vols
+        .iter()
+        .map(|&vol| {
+            if vol > 0.0 && vol.is_finite() {
+                1.0 / vol
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let total: f64 = inverse.iter().sum();
+    if total == 0.0 {
+        return inverse;
+    }
+    inverse.into_iter().map(|w| w / total).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kelly_matches_closed_form() {
+        assert!((kelly_fraction(0.6, 1.0) - 0.2).abs() < 1e-12);
+        assert!(kelly_fraction(0.3, 1.0) < 0.0);
+        assert_eq!(kelly_fraction(0.6, 0.0), 0.0);
+        assert_eq!(kelly_fraction(0.6, f64::INFINITY), 0.0);
+    }
+
+    #[test]
+    fn doubling_vol_halves_weight() {
+        let base = vol_target_weight(1.0, 0.20, 0.10);
+        assert!((base - 0.5).abs() < 1e-12);
+        assert!((vol_target_weight(1.0, 0.40, 0.10) - base / 2.0).abs() < 1e-12);
+        assert!((vol_target_weight(-0.5, 0.20, 0.10) + 0.25).abs() < 1e-12);
+        assert_eq!(vol_target_weight(1.0, 0.0, 0.10), 0.0);
+    }
+
+    #[test]
+    fn inverse_vol_weights_sum_to_one_and_favor_low_vol() {
+        let weights = equal_risk_weights(&[0.10, 0.20, 0.40]);
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(weights[0] > weights[1] && weights[1] > weights[2]);
+        assert!((weights[0] - 2.0 * weights[1]).abs() < 1e-12);
+
+        assert_eq!(equal_risk_weights(&[0.2, f64::NAN]), vec![1.0, 0.0]);
+        assert_eq!(equal_risk_weights(&[0.0, -1.0]), vec![0.0, 0.0]);
+        assert!(equal_risk_weights(&[]).is_empty());
+    }
+}
@@ -225,6 +225,8 @@ mod tests {
             commission_bps: 10,
             slippage_bps: 5,
             min_trade_fee: 0,
+            size_impact: None,
+            fee_tiers: None,
         };
 
         let prices = vec![
@@ -5,7 +5,7 @@
 
 use std::collections::BTreeMap;
 
-use crate::{Level, OrderId, Price, Quantity, Side};
+use crate::{BookInvariantError, Level, OrderId, Price, Quantity, Side};
 
 /// One side of the order book (all bids or all asks).
 ///
@@ -15,6 +15,7 @@ use crate::{Level, OrderId, Price, Quantity, Side};
 /// The `BTreeMap` provides O(log n) insert/remove with sorted iteration.
 /// Best price is cached for O(1) access.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PriceLevels {
     /// Price levels, sorted by price
     levels: BTreeMap<Price, Level>,
@@ -106,8 +107,9 @@ impl PriceLevels {
     /// Returns the position index within the level.
     pub fn insert_order(&mut self, price: Price, order_id: OrderId, quantity: Quantity) -> usize {
         let level = self.get_or_create_level(price);
-        // Actually, VecDeque index is just the length before push.
-        let actual_index = level.orders.len();
+        // Position counts entries already dequeued, so it stays stable as the
+        // front of the queue is consumed.
+        let actual_index = level.head + level.orders.len();
         level.push_back(order_id, quantity);
         actual_index
     }
@@ -122,6 +124,22 @@ impl PriceLevels {
         }
     }
 
+    /// Tombstone a specific order, using its insert-time position as a hint.
+    pub(crate) fn tombstone_order(
+        &mut self,
+        price: Price,
+        index: usize,
+        order_id: OrderId,
+        quantity: Quantity,
+    ) {
+        if let Some(level) = self.levels.get_mut(&price) {
+            level.tombstone_order(index, order_id, quantity);
+            if level.is_empty() {
+                self.remove_level(price);
+            }
+        }
+    }
+
     /// Remove all tombstones from all levels.
     pub fn compact(&mut self) {
         for level in self.levels.values_mut() {
@@ -209,6 +227,28 @@ impl PriceLevels {
         }
     }
 
+    /// Check the cached best price against the level map.
+    pub(crate) fn validate_best_price(&self) -> Result<(), BookInvariantError> {
+        let actual = match self.side {
+            Side::Buy => self.levels.keys().next_back().copied(),
+            Side::Sell => self.levels.keys().next().copied(),
+        };
+        if actual != self.best_price {
+            return Err(BookInvariantError::StaleBestPrice {
+                side: self.side,
+                cached: self.best_price,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    /// Overwrite the best-price cache, leaving the levels untouched.
+    #[cfg(test)]
+    pub(crate) fn set_cached_best_price(&mut self, price: Option<Price>) {
+        self.best_price = price;
+    }
+
     // === Private helpers ===
 
     /// Recompute best price from scratch (O(1) for BTreeMap).
@@ -19,6 +19,10 @@ pub struct SubmitResult {
     pub resting_quantity: Quantity,
     /// Quantity that was cancelled (IOC remainder, FOK rejection)
     pub cancelled_quantity: Quantity,
+    /// The stop cascade this order set off was cut off at the exchange's
+    /// [maximum depth](crate::Exchange::set_max_cascade_depth)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cascade_truncated: bool,
 }
 
 impl SubmitResult {
@@ -96,6 +100,10 @@ pub struct ModifyResult {
     pub trades: Vec<Trade>,
     /// Error if modification failed
     pub error: Option<ModifyError>,
+    /// Whether the order kept its queue position (an in-place size
+    /// reduction; `new_order_id` is then the same id)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub priority_kept: bool,
 }
 
 impl ModifyResult {
@@ -113,6 +121,20 @@ impl ModifyResult {
             cancelled_quantity,
             trades,
             error: None,
+            priority_kept: false,
+        }
+    }
+
+    /// Create a result for an in-place size reduction that kept priority.
+    pub fn reduced(order_id: OrderId, cancelled_quantity: Quantity) -> Self {
+        Self {
+            success: true,
+            old_order_id: order_id,
+            new_order_id: Some(order_id),
+            cancelled_quantity,
+            trades: Vec::new(),
+            error: None,
+            priority_kept: true,
         }
     }
 
@@ -125,6 +147,7 @@ impl ModifyResult {
             cancelled_quantity: 0,
             trades: Vec::new(),
             error: Some(error),
+            priority_kept: false,
         }
     }
 }
@@ -137,7 +160,8 @@ pub enum ModifyError {
     OrderNotFound,
     /// Order already filled or cancelled
     OrderNotActive,
-    /// New quantity is zero
+    /// New quantity is zero (or, for a reduction, not below the remaining
+    /// quantity)
     InvalidQuantity,
 }
 
@@ -149,4 +173,8 @@ pub struct StopSubmitResult {
     pub order_id: OrderId,
     /// Status of the stop order (Pending or Triggered if immediate).
     pub status: StopStatus,
+    /// The cascade an immediate trigger set off was cut off at the
+    /// exchange's [maximum depth](crate::Exchange::set_max_cascade_depth)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cascade_truncated: bool,
 }
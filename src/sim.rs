@@ -0,0 +1,242 @@
+//! Deterministic synthetic order flow for reproducible stress tests.
+//!
+//! [`OrderFlowGenerator`] draws limit orders around a mid price from a small
+//! seeded PRNG (SplitMix64), so the same seed always yields the same order
+//! sequence — and, fed into an [`Exchange`](crate::Exchange), the same book
+//! and trades.
+//!
+//! ```
+//! use nanobook::Exchange;
+//! use nanobook::sim::OrderFlowGenerator;
+//!
+//! let mut flow = OrderFlowGenerator::new(42);
+//! let mut exchange = Exchange::new();
+//! for _ in 0..100 {
+//!     for (side, price, quantity, tif) in flow.next_batch() {
+//!         exchange.submit_limit(side, price, quantity, tif);
+//!     }
+//! }
+//! ```
+
+use crate::{Price, Quantity, Side, TimeInForce};
+
+/// Shape of the generated order flow.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrderFlowConfig {
+    /// Price orders are placed around, in cents
+    pub mid: Price,
+    /// Price grid, in cents
+    pub tick_size: i64,
+    /// Orders land up to this many ticks either side of `mid`; buys above
+    /// and sells below the mid are marketable
+    pub depth_ticks: i64,
+    /// Mean orders per [`next_batch`](OrderFlowGenerator::next_batch)
+    /// (Poisson arrivals)
+    pub arrival_rate: f64,
+    /// Mean order size in lots (exponentially distributed, at least one lot)
+    pub mean_lots: f64,
+    /// Shares per lot
+    pub lot_size: Quantity,
+    /// Probability an order is IOC rather than GTC
+    pub ioc_probability: f64,
+}
+
+impl Default for OrderFlowConfig {
+    fn default() -> Self {
+        Self {
+            mid: Price(100_00),
+            tick_size: 1,
+            depth_ticks: 10,
+            arrival_rate: 5.0,
+            mean_lots: 2.0,
+            lot_size: 100,
+            ioc_probability: 0.1,
+        }
+    }
+}
+
+/// Seeded generator of `(Side, Price, Quantity, TimeInForce)` limit orders.
+///
+/// Also an infinite [`Iterator`] over single orders.
+#[derive(Clone, Debug)]
+pub struct OrderFlowGenerator {
+    config: OrderFlowConfig,
+    rng: SplitMix64,
+}
+
+impl OrderFlowGenerator {
+    /// Generator with the default [`OrderFlowConfig`].
+    pub fn new(seed: u64) -> Self {
+        Self::with_config(seed, OrderFlowConfig::default())
+    }
+
+    /// Generator with a custom configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tick_size` or `lot_size` is zero, or `arrival_rate` is
+    /// negative or not finite.
+    pub fn with_config(seed: u64, config: OrderFlowConfig) -> Self {
+        assert!(config.tick_size > 0, "tick_size must be positive");
+        assert!(config.lot_size > 0, "lot_size must be positive");
+        assert!(
+            config.arrival_rate.is_finite() && config.arrival_rate >= 0.0,
+            "arrival_rate must be finite and non-negative"
+        );
+        Self {
+            config,
+            rng: SplitMix64(seed),
+        }
+    }
+
+    /// The generator's configuration.
+    pub fn config(&self) -> &OrderFlowConfig {
+        &self.config
+    }
+
+    /// Draw one order.
+    pub fn next_order(&mut self) -> (Side, Price, Quantity, TimeInForce) {
+        let c = self.config;
+        let side = if self.rng.next_u64() & 1 == 0 {
+            Side::Buy
+        } else {
+            Side::Sell
+        };
+
+        // Uniform offset in [-depth, depth] ticks; positive = passive.
+        let span = 2 * c.depth_ticks.max(0) as u64 + 1;
+        let offset = (self.rng.next_u64() % span) as i64 - c.depth_ticks.max(0);
+        let price = match side {
+            Side::Buy => c.mid.0 - offset * c.tick_size,
+            Side::Sell => c.mid.0 + offset * c.tick_size,
+        };
+        let price = Price(price.max(c.tick_size));
+
+        let lots = 1 + (-c.mean_lots.max(0.0) * self.rng.next_open_f64().ln()) as u64;
+        let tif = if self.rng.next_f64() < c.ioc_probability {
+            TimeInForce::IOC
+        } else {
+            TimeInForce::GTC
+        };
+        (side, price, lots * c.lot_size, tif)
+    }
+
+    /// Orders arriving in one time step: a Poisson(`arrival_rate`) number of
+    /// draws from [`next_order`](Self::next_order).
+    pub fn next_batch(&mut self) -> Vec<(Side, Price, Quantity, TimeInForce)> {
+        let count = self.poisson(self.config.arrival_rate);
+        (0..count).map(|_| self.next_order()).collect()
+    }
+
+    /// Knuth's multiplication method, in chunks of at most 500 so that
+    /// `exp(-lambda)` never underflows (a sum of Poissons is Poisson).
+    fn poisson(&mut self, mut lambda: f64) -> usize {
+        let mut count = 0;
+        while lambda > 0.0 {
+            let chunk = lambda.min(500.0);
+            lambda -= chunk;
+            let limit = (-chunk).exp();
+            let mut product = self.rng.next_f64();
+            while product > limit {
+                count += 1;
+                product *= self.rng.next_f64();
+            }
+        }
+        count
+    }
+}
+
+impl Iterator for OrderFlowGenerator {
+    type Item = (Side, Price, Quantity, TimeInForce);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_order())
+    }
+}
+
+/// SplitMix64: tiny, fast, and fully determined by its seed.
+#[derive(Clone, Debug)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in `(0, 1]`, safe to take the logarithm of.
+    fn next_open_f64(&mut self) -> f64 {
+        1.0 - self.next_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Exchange;
+
+    fn run(seed: u64, steps: usize) -> Exchange {
+        let mut flow = OrderFlowGenerator::new(seed);
+        let mut exchange = Exchange::new();
+        for _ in 0..steps {
+            for (side, price, quantity, tif) in flow.next_batch() {
+                exchange.submit_limit(side, price, quantity, tif);
+            }
+        }
+        exchange
+    }
+
+    #[test]
+    fn same_seed_same_orders() {
+        let a: Vec<_> = OrderFlowGenerator::new(7).take(500).collect();
+        let b: Vec<_> = OrderFlowGenerator::new(7).take(500).collect();
+        assert_eq!(a, b);
+
+        let c: Vec<_> = OrderFlowGenerator::new(8).take(500).collect();
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn same_seed_same_exchange_state() {
+        let (a, b) = (run(42, 200), run(42, 200));
+        assert!(!a.trades().is_empty());
+        assert_eq!(a.trades(), b.trades());
+        assert_eq!(a.full_book(), b.full_book());
+        #[cfg(feature = "event-log")]
+        assert_eq!(a.events(), b.events());
+    }
+
+    #[test]
+    fn orders_respect_config() {
+        let config = OrderFlowConfig {
+            tick_size: 5,
+            depth_ticks: 4,
+            lot_size: 10,
+            ..OrderFlowConfig::default()
+        };
+        let flow = OrderFlowGenerator::with_config(1, config);
+        for (_, price, quantity, _) in flow.take(1_000) {
+            assert_eq!(price.0 % 5, 0);
+            assert!((price.0 - 100_00).abs() <= 20);
+            assert_eq!(quantity % 10, 0);
+            assert!(quantity >= 10);
+        }
+    }
+
+    #[test]
+    fn batch_sizes_average_to_arrival_rate() {
+        let mut flow = OrderFlowGenerator::new(3);
+        let total: usize = (0..2_000).map(|_| flow.next_batch().len()).sum();
+        let mean = total as f64 / 2_000.0;
+        assert!((mean - 5.0).abs() < 0.25, "mean batch size {mean}");
+    }
+}
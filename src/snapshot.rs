@@ -1,9 +1,11 @@
 //! Book snapshots for market data.
 
-use crate::{OrderBook, Price, Quantity, Timestamp};
+use std::collections::BTreeMap;
+
+use crate::{OrderBook, OrderId, Price, Quantity, Side, Timestamp};
 
 /// A snapshot of the order book at a point in time.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BookSnapshot {
     /// Bid levels (highest price first)
@@ -90,10 +92,211 @@ impl BookSnapshot {
                 / total as f64,
         )
     }
+
+    /// CRC-32 checksum of the top `depth` levels, for detecting a desynced
+    /// book against a reference feed.
+    ///
+    /// Canonical encoding, hashed as ASCII bytes:
+    ///
+    /// 1. Up to `depth` ask levels, best (lowest) first, then up to `depth`
+    ///    bid levels, best (highest) first.
+    /// 2. Each level is `<price>:<quantity>;` with the price in integer
+    ///    cents and the quantity as base-10 integers without padding (a
+    ///    negative price keeps its `-`).
+    /// 3. A single `|` separates the asks from the bids.
+    ///
+    /// An ask at $101.00 for 75 and a bid at $100.00 for 150 encode as
+    /// `10100:75;|10000:150;`. The checksum is CRC-32/ISO-HDLC (the zlib,
+    /// PNG and Ethernet CRC: reflected polynomial `0xEDB88320`, initial
+    /// value and final XOR `0xFFFFFFFF`). Order counts and the timestamp are
+    /// not covered.
+    ///
+    /// ```
+    /// use nanobook::{Exchange, Price, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.submit_limit(Side::Sell, Price(101_00), 75, TimeInForce::GTC);
+    /// exchange.submit_limit(Side::Buy, Price(100_00), 150, TimeInForce::GTC);
+    /// assert_eq!(exchange.book_checksum(10), 0x704A_8BFE);
+    /// ```
+    pub fn checksum(&self, depth: usize) -> u32 {
+        let encode = |levels: &[LevelSnapshot]| -> String {
+            levels
+                .iter()
+                .take(depth)
+                .map(|level| format!("{}:{};", level.price.0, level.quantity))
+                .collect()
+        };
+        let canonical = format!("{}|{}", encode(&self.asks), encode(&self.bids));
+        crc32(canonical.as_bytes())
+    }
+
+    /// Serialize the snapshot to a JSON string.
+    #[cfg(feature = "persistence")]
+    pub fn to_json(&self) -> std::io::Result<String> {
+        serde_json::to_string(self).map_err(std::io::Error::other)
+    }
+
+    /// Deserialize a snapshot written by [`to_json`](Self::to_json).
+    #[cfg(feature = "persistence")]
+    pub fn from_json(json: &str) -> std::io::Result<Self> {
+        serde_json::from_str(json).map_err(std::io::Error::other)
+    }
+
+    /// Rebuild the later snapshot from this one and a [`snapshot_diff`] delta.
+    pub fn apply_delta(&self, delta: &BookDelta) -> BookSnapshot {
+        let mut bids = delta.bids.apply(&self.bids);
+        bids.reverse(); // highest price first
+        BookSnapshot {
+            bids,
+            asks: delta.asks.apply(&self.asks),
+            timestamp: delta.timestamp,
+        }
+    }
+
+    /// Merge levels into fixed-width price buckets of `bin_width` cents.
+    ///
+    /// Bucket prices are multiples of `bin_width`: bids round down and asks
+    /// round up, so binning never crosses the spread. Quantities and order
+    /// counts are summed per bucket, and levels keep best-first order. A
+    /// `bin_width` of 1 or less returns the levels unchanged.
+    pub fn aggregate_levels(&self, bin_width: i64) -> BookSnapshot {
+        if bin_width <= 1 {
+            return self.clone();
+        }
+        let floor = |p: i64| p.div_euclid(bin_width) * bin_width;
+        let ceil = |p: i64| -(-p).div_euclid(bin_width) * bin_width;
+        BookSnapshot {
+            bids: bin_levels(&self.bids, floor),
+            asks: bin_levels(&self.asks, ceil),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// CRC-32/ISO-HDLC, bit by bit (checksums are computed rarely enough that a
+/// lookup table isn't worth it).
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Merge best-first `levels` whose prices map to the same `bucket`.
+///
+/// `bucket` is monotone in price, so equal buckets are always adjacent.
+fn bin_levels(levels: &[LevelSnapshot], bucket: impl Fn(i64) -> i64) -> Vec<LevelSnapshot> {
+    let mut binned: Vec<LevelSnapshot> = Vec::new();
+    for level in levels {
+        let price = Price(bucket(level.price.0));
+        match binned.last_mut() {
+            Some(last) if last.price == price => {
+                last.quantity += level.quantity;
+                last.order_count += level.order_count;
+            }
+            _ => binned.push(LevelSnapshot {
+                price,
+                quantity: level.quantity,
+                order_count: level.order_count,
+                oldest_timestamp: None,
+                newest_timestamp: None,
+            }),
+        }
+    }
+    binned
+}
+
+/// Level changes on one side of the book between two snapshots.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SideDelta {
+    /// Levels present only in the later snapshot
+    pub added: Vec<LevelSnapshot>,
+    /// Prices of levels present only in the earlier snapshot
+    pub removed: Vec<Price>,
+    /// Levels present in both whose quantity or order count changed (new state)
+    pub changed: Vec<LevelSnapshot>,
+}
+
+impl SideDelta {
+    /// Whether the side is unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    fn between(prev: &[LevelSnapshot], next: &[LevelSnapshot]) -> Self {
+        let before: BTreeMap<Price, &LevelSnapshot> = prev.iter().map(|l| (l.price, l)).collect();
+        let after: BTreeMap<Price, &LevelSnapshot> = next.iter().map(|l| (l.price, l)).collect();
+
+        let mut delta = SideDelta::default();
+        for level in next {
+            match before.get(&level.price) {
+                None => delta.added.push(level.clone()),
+                Some(old) if *old != level => delta.changed.push(level.clone()),
+                Some(_) => {}
+            }
+        }
+        delta.removed = prev
+            .iter()
+            .filter(|l| !after.contains_key(&l.price))
+            .map(|l| l.price)
+            .collect();
+        delta
+    }
+
+    /// Apply to `levels`, returning the result in ascending price order.
+    fn apply(&self, levels: &[LevelSnapshot]) -> Vec<LevelSnapshot> {
+        let mut by_price: BTreeMap<Price, LevelSnapshot> =
+            levels.iter().map(|l| (l.price, l.clone())).collect();
+        for price in &self.removed {
+            by_price.remove(price);
+        }
+        for level in self.added.iter().chain(&self.changed) {
+            by_price.insert(level.price, level.clone());
+        }
+        by_price.into_values().collect()
+    }
+}
+
+/// Compact difference between two book snapshots, from [`snapshot_diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BookDelta {
+    pub bids: SideDelta,
+    pub asks: SideDelta,
+    /// Timestamp of the later snapshot
+    pub timestamp: Timestamp,
+}
+
+impl BookDelta {
+    /// Whether no level changed on either side.
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+}
+
+/// Levels added, removed and changed per side going from `prev` to `next`.
+///
+/// `prev.apply_delta(&snapshot_diff(prev, next))` reproduces `next`.
+pub fn snapshot_diff(prev: &BookSnapshot, next: &BookSnapshot) -> BookDelta {
+    BookDelta {
+        bids: SideDelta::between(&prev.bids, &next.bids),
+        asks: SideDelta::between(&prev.asks, &next.asks),
+        timestamp: next.timestamp,
+    }
 }
 
 /// A snapshot of a single price level.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LevelSnapshot {
     /// Price at this level
@@ -102,30 +305,44 @@ pub struct LevelSnapshot {
     pub quantity: Quantity,
     /// Number of orders at this level
     pub order_count: usize,
+    /// Timestamp of the front order, next in line to fill (`None` for
+    /// aggregated bins)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub oldest_timestamp: Option<Timestamp>,
+    /// Timestamp of the back order, last to arrive (`None` for aggregated bins)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub newest_timestamp: Option<Timestamp>,
 }
 
 impl OrderBook {
     /// Take a snapshot of the top N levels on each side.
     pub fn snapshot(&self, depth: usize) -> BookSnapshot {
-        fn snapshot_levels(levels: &crate::PriceLevels, depth: usize) -> Vec<LevelSnapshot> {
-            levels
-                .iter_best_to_worst()
-                .take(depth)
-                .map(|(price, level)| LevelSnapshot {
-                    price: *price,
-                    quantity: level.total_quantity(),
-                    order_count: level.order_count(),
-                })
-                .collect()
-        }
-
         BookSnapshot {
-            bids: snapshot_levels(self.bids(), depth),
-            asks: snapshot_levels(self.asks(), depth),
+            bids: self.iter_levels(Side::Buy).take(depth).collect(),
+            asks: self.iter_levels(Side::Sell).take(depth).collect(),
             timestamp: self.peek_next_order_id().0,
         }
     }
 
+    /// Stream one side's levels best-to-worst without collecting them.
+    ///
+    /// Each [`LevelSnapshot`] is built on demand, so consuming only a prefix
+    /// never touches the deeper levels and memory stays bounded however deep
+    /// the book is. Yields the same sequence as the matching side of
+    /// [`full_snapshot`](Self::full_snapshot).
+    pub fn iter_levels(&self, side: Side) -> impl Iterator<Item = LevelSnapshot> + '_ {
+        let timestamp_of = |id: Option<OrderId>| Some(self.get_order(id?)?.timestamp);
+        self.side(side)
+            .iter_best_to_worst()
+            .map(move |(price, level)| LevelSnapshot {
+                price: *price,
+                quantity: level.total_quantity(),
+                order_count: level.order_count(),
+                oldest_timestamp: timestamp_of(level.oldest()),
+                newest_timestamp: timestamp_of(level.newest()),
+            })
+    }
+
     /// Take a full snapshot of all levels.
     pub fn full_snapshot(&self) -> BookSnapshot {
         self.snapshot(usize::MAX)
@@ -135,7 +352,7 @@ impl OrderBook {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Side, TimeInForce};
+    use crate::{OrderId, TimeInForce};
 
     #[test]
     fn empty_snapshot() {
@@ -227,6 +444,70 @@ mod tests {
         assert_eq!(snap.bids.len(), 10);
     }
 
+    #[test]
+    fn level_timestamps_track_queue_front_and_back() {
+        let mut exchange = crate::Exchange::new();
+        let first = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let second = exchange.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+        let third = exchange.submit_limit(Side::Buy, Price(100_00), 25, TimeInForce::GTC);
+        let ts = |id| exchange.get_order(id).unwrap().timestamp;
+        let (t1, t2, t3) = (ts(first.order_id), ts(second.order_id), ts(third.order_id));
+
+        let level = exchange.depth(1).bids[0].clone();
+        assert_eq!(level.oldest_timestamp, Some(t1));
+        assert_eq!(level.newest_timestamp, Some(t3));
+
+        // Consuming the front order moves `oldest` to the next in line.
+        exchange.submit_market(Side::Sell, 100);
+        let level = exchange.depth(1).bids[0].clone();
+        assert_eq!(level.oldest_timestamp, Some(t2));
+        assert_eq!(level.newest_timestamp, Some(t3));
+
+        // A cancelled back order (tombstone) is skipped too.
+        exchange.cancel(third.order_id);
+        let level = exchange.depth(1).bids[0].clone();
+        assert_eq!(level.oldest_timestamp, Some(t2));
+        assert_eq!(level.newest_timestamp, Some(t2));
+
+        let binned = exchange.full_book().aggregate_levels(5);
+        assert_eq!(binned.bids[0].oldest_timestamp, None);
+        assert_eq!(binned.bids[0].newest_timestamp, None);
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn checksum_depends_only_on_visible_levels() {
+        let mut direct = crate::Exchange::new();
+        direct.submit_limit(Side::Buy, Price(100_00), 150, TimeInForce::GTC);
+        direct.submit_limit(Side::Sell, Price(101_00), 75, TimeInForce::GTC);
+        direct.submit_limit(Side::Sell, Price(102_00), 10, TimeInForce::GTC);
+
+        // Same levels via a fill, a cancel and a split order.
+        let mut winding = crate::Exchange::new();
+        winding.submit_limit(Side::Sell, Price(102_00), 10, TimeInForce::GTC);
+        winding.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+        winding.submit_limit(Side::Buy, Price(101_00), 25, TimeInForce::GTC);
+        let stale = winding.submit_limit(Side::Buy, Price(99_00), 40, TimeInForce::GTC);
+        winding.cancel(stale.order_id);
+        winding.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        winding.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+
+        assert_eq!(direct.book_checksum(10), winding.book_checksum(10));
+        assert_eq!(direct.book_checksum(1), winding.book_checksum(1));
+
+        // Any quantity change shows up; levels beyond `depth` don't.
+        let before = direct.book_checksum(10);
+        let shallow = direct.book_checksum(1);
+        direct.submit_limit(Side::Sell, Price(102_00), 1, TimeInForce::GTC);
+        assert_ne!(direct.book_checksum(10), before);
+        assert_eq!(direct.book_checksum(1), shallow);
+    }
+
     // === Analytics tests ===
 
     #[test]
@@ -292,10 +573,123 @@ mod tests {
         assert!((wmid - 101_50.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn aggregate_levels_bins_without_crossing() {
+        let mut book = OrderBook::new();
+        for (price, qty) in [(100_00, 100), (100_01, 50), (100_02, 25), (99_97, 10)] {
+            let order = book.create_order(Side::Buy, Price(price), qty, TimeInForce::GTC);
+            book.add_order(order);
+        }
+        for (price, qty) in [(100_03, 40), (100_05, 60), (100_07, 80)] {
+            let order = book.create_order(Side::Sell, Price(price), qty, TimeInForce::GTC);
+            book.add_order(order);
+        }
+
+        let binned = book.full_snapshot().aggregate_levels(5);
+
+        let bids: Vec<_> = binned
+            .bids
+            .iter()
+            .map(|l| (l.price, l.quantity, l.order_count))
+            .collect();
+        assert_eq!(bids, vec![(Price(100_00), 175, 3), (Price(99_95), 10, 1)]);
+        let asks: Vec<_> = binned
+            .asks
+            .iter()
+            .map(|l| (l.price, l.quantity, l.order_count))
+            .collect();
+        assert_eq!(asks, vec![(Price(100_05), 100, 2), (Price(100_10), 80, 1)]);
+        assert!(binned.spread().unwrap() > 0);
+        assert_eq!(binned.total_bid_quantity(), 185);
+
+        let unchanged = book.full_snapshot().aggregate_levels(1);
+        assert_eq!(unchanged.bids.len(), 4);
+    }
+
+    fn sample_book() -> OrderBook {
+        let mut book = OrderBook::new();
+        for (side, price, qty) in [
+            (Side::Buy, 100_00, 100),
+            (Side::Buy, 100_00, 50),
+            (Side::Buy, 99_00, 200),
+            (Side::Sell, 101_00, 75),
+            (Side::Sell, 102_00, 150),
+        ] {
+            let order = book.create_order(side, Price(price), qty, TimeInForce::GTC);
+            book.add_order(order);
+        }
+        book
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn json_round_trip() {
+        let snap = sample_book().full_snapshot();
+        let restored = BookSnapshot::from_json(&snap.to_json().unwrap()).unwrap();
+        assert_eq!(restored, snap);
+        assert!(BookSnapshot::from_json("{").is_err());
+    }
+
+    #[test]
+    fn diff_of_snapshot_with_itself_is_empty() {
+        let snap = sample_book().full_snapshot();
+        let delta = snapshot_diff(&snap, &snap);
+        assert!(delta.is_empty());
+        assert_eq!(snap.apply_delta(&delta), snap);
+    }
+
+    #[test]
+    fn diff_reports_level_changes_and_reapplies() {
+        let mut book = sample_book();
+        let prev = book.full_snapshot();
+
+        let order = book.create_order(Side::Buy, Price(99_00), 25, TimeInForce::GTC);
+        book.add_order(order);
+        let order = book.create_order(Side::Buy, Price(98_00), 10, TimeInForce::GTC);
+        book.add_order(order);
+        book.cancel_order(OrderId(4));
+        let next = book.full_snapshot();
+
+        let delta = snapshot_diff(&prev, &next);
+        assert_eq!(delta.bids.added.len(), 1);
+        assert_eq!(delta.bids.added[0].price, Price(98_00));
+        assert_eq!(delta.bids.changed.len(), 1);
+        assert_eq!(delta.bids.changed[0].quantity, 225);
+        assert!(delta.bids.removed.is_empty());
+        assert_eq!(delta.asks.removed, vec![Price(101_00)]);
+        assert_eq!(prev.apply_delta(&delta), next);
+    }
+
     #[test]
     fn weighted_mid_empty() {
         let book = OrderBook::new();
         let snap = book.snapshot(10);
         assert!(snap.weighted_mid().is_none());
     }
+
+    #[test]
+    fn iter_levels_streams_full_snapshot() {
+        let mut book = OrderBook::new();
+        for i in 0..20 {
+            let bid = book.create_order(
+                Side::Buy,
+                Price(100_00 - i * 10),
+                10 + i as u64,
+                TimeInForce::GTC,
+            );
+            book.add_order(bid);
+            let ask = book.create_order(Side::Sell, Price(101_00 + i * 10), 5, TimeInForce::GTC);
+            book.add_order(ask);
+        }
+        let full = book.full_snapshot();
+        assert_eq!(book.iter_levels(Side::Buy).collect::<Vec<_>>(), full.bids);
+        assert_eq!(book.iter_levels(Side::Sell).collect::<Vec<_>>(), full.asks);
+
+        // Taking a prefix leaves the rest unvisited; resuming picks up where it stopped.
+        let mut bids = book.iter_levels(Side::Buy);
+        let top: Vec<_> = bids.by_ref().take(5).collect();
+        assert_eq!(top, book.snapshot(5).bids);
+        assert_eq!(bids.next().as_ref(), full.bids.get(5));
+        assert_eq!(bids.count(), 14);
+    }
 }
@@ -1,6 +1,7 @@
 //! Statistical functions for quantitative analysis.
 //!
-//! Provides Spearman rank correlation and quintile spread analysis,
+//! Provides Spearman rank correlation, information coefficients, quintile
+//! spread analysis, and quantile long/short portfolio construction,
 //! replacing direct scipy/numpy calls in qtrade.
 //!
 //! # References
@@ -8,6 +9,8 @@
 //! - SciPy `spearmanr`: <https://github.com/scipy/scipy/blob/main/scipy/stats/_correlation.py>
 //! - Average-rank tie-breaking follows the standard convention.
 
+use crate::Symbol;
+
 // ---------------------------------------------------------------------------
 // Ranking
 // ---------------------------------------------------------------------------
@@ -303,6 +306,97 @@ pub fn quintile_spread(scores: &[f64], returns: &[f64], n_quantiles: usize) -> f
     top_mean - bottom_mean
 }
 
+/// Dollar-neutral long/short weights from a cross-sectional signal.
+///
+/// Ranks symbols by signal (highest first), splits them into `n_quantiles`
+/// groups of `n / n_quantiles` symbols like [`quintile_spread`], and gives
+/// the top group `+0.5 / group_size` each and the bottom group
+/// `-0.5 / group_size` each. With `long_top == false` the legs swap (long
+/// the lowest signals). Symbols outside both legs are omitted.
+///
+/// Equal signals are ordered by symbol, so ties at a quantile boundary
+/// resolve the same way on every call. Non-finite signals are skipped.
+///
+/// # Returns
+///
+/// `(symbol, weight)` pairs in rank order, or an empty vector if there are
+/// fewer usable symbols than `n_quantiles` or `n_quantiles < 2`.
+pub fn quantile_portfolio(
+    signals: &[(Symbol, f64)],
+    n_quantiles: usize,
+    long_top: bool,
+) -> Vec<(Symbol, f64)> {
+    let mut ranked: Vec<(Symbol, f64)> = signals
+        .iter()
+        .copied()
+        .filter(|(_, signal)| signal.is_finite())
+        .collect();
+    if n_quantiles < 2 || ranked.len() < n_quantiles {
+        return Vec::new();
+    }
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let group_size = ranked.len() / n_quantiles;
+    let weight = 0.5 / group_size as f64;
+    let top_weight = if long_top { weight } else { -weight };
+
+    let bottom_start = ranked.len() - group_size;
+    let mut weights = Vec::with_capacity(2 * group_size);
+    weights.extend(
+        ranked[..group_size]
+            .iter()
+            .map(|&(sym, _)| (sym, top_weight)),
+    );
+    weights.extend(
+        ranked[bottom_start..]
+            .iter()
+            .map(|&(sym, _)| (sym, -top_weight)),
+    );
+    weights
+}
+
+/// Information coefficient: Spearman rank correlation between a signal and
+/// the return that followed it.
+///
+/// `forward_return[i]` must be the return realized after `signal[i]` was
+/// observed; aligning the two is the caller's job. Pairs where either value
+/// is non-finite are dropped.
+///
+/// Returns `None` if the lengths differ, fewer than 2 valid pairs remain, or
+/// either side is constant after filtering.
+pub fn information_coefficient(signal: &[f64], forward_return: &[f64]) -> Option<f64> {
+    if signal.len() != forward_return.len() {
+        return None;
+    }
+    let (x, y): (Vec<f64>, Vec<f64>) = signal
+        .iter()
+        .zip(forward_return)
+        .filter(|(s, r)| s.is_finite() && r.is_finite())
+        .map(|(&s, &r)| (s, r))
+        .unzip();
+    if x.len() < 2 {
+        return None;
+    }
+
+    let r = pearson(&rankdata(&x), &rankdata(&y));
+    (!r.is_nan()).then(|| r.clamp(-1.0, 1.0))
+}
+
+/// Cross-sectional information coefficient per period.
+///
+/// `signals[t]` and `returns[t]` hold one value per asset for period `t`
+/// (forward returns, as in [`information_coefficient`]). Periods are paired
+/// by index; extra periods in the longer input are ignored.
+///
+/// Returns one IC per period, NaN where [`information_coefficient`] is `None`.
+pub fn rolling_ic(signals: &[Vec<f64>], returns: &[Vec<f64>]) -> Vec<f64> {
+    signals
+        .iter()
+        .zip(returns)
+        .map(|(signal, forward)| information_coefficient(signal, forward).unwrap_or(f64::NAN))
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -406,4 +500,83 @@ mod tests {
         // Gamma(5) = 24, ln(24) ≈ 3.178
         assert!((ln_gamma(5.0) - 24.0_f64.ln()).abs() < 1e-8);
     }
+
+    #[test]
+    fn quantile_portfolio_quintiles() {
+        let names = ["A", "B", "C", "D", "E", "F", "G", "H", "I", "J"];
+        let signals: Vec<(Symbol, f64)> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (Symbol::new(name), i as f64))
+            .collect();
+
+        let weights = quantile_portfolio(&signals, 5, true);
+        assert_eq!(
+            weights,
+            vec![
+                (Symbol::new("J"), 0.25),
+                (Symbol::new("I"), 0.25),
+                (Symbol::new("B"), -0.25),
+                (Symbol::new("A"), -0.25),
+            ]
+        );
+        assert!(weights.iter().map(|(_, w)| w).sum::<f64>().abs() < 1e-12);
+
+        let flipped = quantile_portfolio(&signals, 5, false);
+        assert_eq!(flipped[0], (Symbol::new("J"), -0.25));
+        assert_eq!(flipped[3], (Symbol::new("A"), 0.25));
+    }
+
+    #[test]
+    fn quantile_portfolio_breaks_ties_by_symbol() {
+        let signals = [
+            (Symbol::new("C"), 1.0),
+            (Symbol::new("A"), 1.0),
+            (Symbol::new("B"), 1.0),
+            (Symbol::new("D"), f64::NAN),
+        ];
+        let weights = quantile_portfolio(&signals, 3, true);
+        assert_eq!(
+            weights,
+            vec![(Symbol::new("A"), 0.5), (Symbol::new("C"), -0.5)]
+        );
+        assert!(quantile_portfolio(&signals, 4, true).is_empty());
+        assert!(quantile_portfolio(&signals, 1, true).is_empty());
+    }
+
+    #[test]
+    fn information_coefficient_signs() {
+        let signal = [0.3, -0.1, 0.8, 0.0, 0.5];
+        let forward = [0.02, -0.01, 0.05, 0.001, 0.03];
+        assert_eq!(information_coefficient(&signal, &forward), Some(1.0));
+
+        let inverted: Vec<f64> = signal.iter().map(|s| -s).collect();
+        assert_eq!(information_coefficient(&inverted, &forward), Some(-1.0));
+
+        assert_eq!(information_coefficient(&[1.0, f64::NAN], &[0.1, 0.2]), None);
+        assert_eq!(information_coefficient(&[1.0, 2.0], &[0.1]), None);
+        assert_eq!(
+            information_coefficient(&[1.0, 1.0, 1.0], &[0.1, 0.2, 0.3]),
+            None
+        );
+    }
+
+    #[test]
+    fn rolling_ic_per_period() {
+        let signals = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![1.0, 2.0, 3.0],
+            vec![1.0, f64::NAN, 3.0],
+        ];
+        let returns = vec![
+            vec![0.01, 0.02, 0.03],
+            vec![0.03, 0.02, 0.01],
+            vec![0.01, 0.02, f64::NAN],
+        ];
+        let ic = rolling_ic(&signals, &returns);
+        assert_eq!(ic.len(), 3);
+        assert_eq!(ic[0], 1.0);
+        assert_eq!(ic[1], -1.0);
+        assert!(ic[2].is_nan());
+    }
 }
@@ -3,6 +3,11 @@
 //! Stop orders rest in a separate book and are triggered when the last
 //! trade price reaches the stop price. Once triggered, they become
 //! regular market or limit orders.
+//!
+//! Market-if-touched (MIT) orders share the book but trigger in the
+//! opposite direction: a stop fires when price moves *against* the order
+//! (a buy stop on a rise), an MIT when price moves *in its favor* (a buy
+//! MIT on a dip). See [`ConditionalKind`].
 
 use std::collections::BTreeMap;
 
@@ -25,6 +30,22 @@ pub enum TrailMethod {
     Atr { multiplier: f64, period: usize },
 }
 
+/// Which way a conditional order triggers.
+///
+/// | Kind | Buy triggers when | Sell triggers when |
+/// |------|-------------------|--------------------|
+/// | [`Stop`](Self::Stop) | last trade `>=` trigger | last trade `<=` trigger |
+/// | [`IfTouched`](Self::IfTouched) | last trade `<=` trigger | last trade `>=` trigger |
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConditionalKind {
+    /// Stop: triggers on an adverse move (breakout / stop-loss).
+    #[default]
+    Stop,
+    /// If-touched: triggers on a favorable move (buy the dip, sell the rip).
+    IfTouched,
+}
+
 /// Status of a stop order.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -61,21 +82,65 @@ pub struct StopOrder {
     pub trail_method: Option<TrailMethod>,
     /// Watermark: best price seen (high for sell trailing, low for buy trailing).
     pub watermark: Option<Price>,
+    /// For stop-market orders: when set, the triggered order is an IOC limit
+    /// at `stop_price ± max_slippage` cents instead of an unbounded sweep.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_slippage: Option<i64>,
+    /// Trigger direction: stop (default) or market-if-touched.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind: ConditionalKind,
+    /// For trailing stop-limit orders: when set, the limit price trails the
+    /// stop at `stop_price ± limit_offset` cents (beyond the stop, against
+    /// the order's side) instead of staying fixed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub limit_offset: Option<i64>,
+}
+
+impl StopOrder {
+    /// Whether a trade at `trade_price` triggers this order.
+    pub fn is_triggered_by(&self, trade_price: Price) -> bool {
+        match (self.kind, self.side) {
+            (ConditionalKind::Stop, Side::Buy) | (ConditionalKind::IfTouched, Side::Sell) => {
+                trade_price >= self.stop_price
+            }
+            (ConditionalKind::Stop, Side::Sell) | (ConditionalKind::IfTouched, Side::Buy) => {
+                trade_price <= self.stop_price
+            }
+        }
+    }
+}
+
+/// Limit price of a slippage-capped stop-market order once triggered:
+/// `max_slippage` cents beyond the stop price, against the order's side.
+pub(crate) fn capped_limit(side: Side, stop_price: Price, max_slippage: i64) -> Price {
+    match side {
+        Side::Buy => Price(stop_price.0.saturating_add(max_slippage)),
+        Side::Sell => Price(stop_price.0.saturating_sub(max_slippage)),
+    }
 }
 
 /// Book of pending stop orders.
 ///
-/// Maintains two price-indexed maps for efficient trigger lookups:
+/// Maintains price-indexed maps for efficient trigger lookups:
 /// - Buy stops: trigger when `last_trade_price >= stop_price`
 /// - Sell stops: trigger when `last_trade_price <= stop_price`
+/// - Buy MITs: trigger when `last_trade_price <= stop_price`
+/// - Sell MITs: trigger when `last_trade_price >= stop_price`
 ///
 /// Also maintains a rolling window of trade price changes for ATR computation.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StopBook {
     /// Buy stop orders indexed by stop price.
     buy_stops: BTreeMap<Price, Vec<OrderId>>,
     /// Sell stop orders indexed by stop price.
     sell_stops: BTreeMap<Price, Vec<OrderId>>,
+    /// Buy market-if-touched orders indexed by trigger price.
+    #[cfg_attr(feature = "serde", serde(default))]
+    buy_mits: BTreeMap<Price, Vec<OrderId>>,
+    /// Sell market-if-touched orders indexed by trigger price.
+    #[cfg_attr(feature = "serde", serde(default))]
+    sell_mits: BTreeMap<Price, Vec<OrderId>>,
     /// All stop orders by ID.
     orders: FxHashMap<OrderId, StopOrder>,
     /// IDs of trailing stop orders (for efficient update iteration).
@@ -100,14 +165,12 @@ impl StopBook {
     pub fn insert(&mut self, order: StopOrder) {
         let id = order.id;
         let price = order.stop_price;
-        let side = order.side;
         let is_trailing = order.trail_method.is_some();
 
-        let map = match side {
-            Side::Buy => &mut self.buy_stops,
-            Side::Sell => &mut self.sell_stops,
-        };
-        map.entry(price).or_default().push(id);
+        self.trigger_map(order.side, order.kind)
+            .entry(price)
+            .or_default()
+            .push(id);
 
         self.orders.insert(id, order);
 
@@ -124,13 +187,10 @@ impl StopBook {
         };
 
         let price = order.stop_price;
-        let side = order.side;
+        let (side, kind) = (order.side, order.kind);
         order.status = StopStatus::Cancelled;
 
-        let map = match side {
-            Side::Buy => &mut self.buy_stops,
-            Side::Sell => &mut self.sell_stops,
-        };
+        let map = self.trigger_map(side, kind);
         if let Some(ids) = map.get_mut(&price) {
             ids.retain(|id| *id != order_id);
             if ids.is_empty() {
@@ -151,44 +211,32 @@ impl StopBook {
     pub fn collect_triggered(&mut self, trade_price: Price) -> Vec<StopOrder> {
         let mut triggered = Vec::new();
 
-        // Buy stops trigger when trade_price >= stop_price
-        // Collect all buy stops with stop_price <= trade_price
-        let buy_keys: Vec<Price> = self
-            .buy_stops
-            .range(..=trade_price)
-            .map(|(k, _)| *k)
-            .collect();
-        for key in buy_keys {
-            if let Some(ids) = self.buy_stops.remove(&key) {
-                for id in ids {
-                    if let Some(order) = self.orders.get_mut(&id) {
-                        if order.status == StopStatus::Pending {
-                            order.status = StopStatus::Triggered;
-                            triggered.push(order.clone());
-                        }
-                    }
-                }
-            }
+        // Buy stops and sell MITs trigger when trade_price >= trigger:
+        // collect all triggers <= trade_price.
+        for (side, kind) in [
+            (Side::Buy, ConditionalKind::Stop),
+            (Side::Sell, ConditionalKind::IfTouched),
+        ] {
+            let keys: Vec<Price> = self
+                .trigger_map(side, kind)
+                .range(..=trade_price)
+                .map(|(k, _)| *k)
+                .collect();
+            self.trigger_keys(side, kind, keys, &mut triggered);
         }
 
-        // Sell stops trigger when trade_price <= stop_price
-        // Collect all sell stops with stop_price >= trade_price
-        let sell_keys: Vec<Price> = self
-            .sell_stops
-            .range(trade_price..)
-            .map(|(k, _)| *k)
-            .collect();
-        for key in sell_keys {
-            if let Some(ids) = self.sell_stops.remove(&key) {
-                for id in ids {
-                    if let Some(order) = self.orders.get_mut(&id) {
-                        if order.status == StopStatus::Pending {
-                            order.status = StopStatus::Triggered;
-                            triggered.push(order.clone());
-                        }
-                    }
-                }
-            }
+        // Sell stops and buy MITs trigger when trade_price <= trigger:
+        // collect all triggers >= trade_price.
+        for (side, kind) in [
+            (Side::Sell, ConditionalKind::Stop),
+            (Side::Buy, ConditionalKind::IfTouched),
+        ] {
+            let keys: Vec<Price> = self
+                .trigger_map(side, kind)
+                .range(trade_price..)
+                .map(|(k, _)| *k)
+                .collect();
+            self.trigger_keys(side, kind, keys, &mut triggered);
         }
 
         // Sort by timestamp for deterministic FIFO ordering
@@ -206,20 +254,82 @@ impl StopBook {
         triggered
     }
 
+    /// Whether a trade at `trade_price` would trigger any pending order,
+    /// without triggering it.
+    pub(crate) fn any_triggered_by(&self, trade_price: Price) -> bool {
+        self.buy_stops.range(..=trade_price).next().is_some()
+            || self.sell_mits.range(..=trade_price).next().is_some()
+            || self.sell_stops.range(trade_price..).next().is_some()
+            || self.buy_mits.range(trade_price..).next().is_some()
+    }
+
+    /// Mark the pending orders at `keys` triggered, removing the price
+    /// levels from the map.
+    fn trigger_keys(
+        &mut self,
+        side: Side,
+        kind: ConditionalKind,
+        keys: Vec<Price>,
+        triggered: &mut Vec<StopOrder>,
+    ) {
+        for key in keys {
+            if let Some(ids) = self.trigger_map(side, kind).remove(&key) {
+                for id in ids {
+                    if let Some(order) = self.orders.get_mut(&id) {
+                        if order.status == StopStatus::Pending {
+                            order.status = StopStatus::Triggered;
+                            triggered.push(order.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// The price index holding orders of this side and kind.
+    fn trigger_map(
+        &mut self,
+        side: Side,
+        kind: ConditionalKind,
+    ) -> &mut BTreeMap<Price, Vec<OrderId>> {
+        match (side, kind) {
+            (Side::Buy, ConditionalKind::Stop) => &mut self.buy_stops,
+            (Side::Sell, ConditionalKind::Stop) => &mut self.sell_stops,
+            (Side::Buy, ConditionalKind::IfTouched) => &mut self.buy_mits,
+            (Side::Sell, ConditionalKind::IfTouched) => &mut self.sell_mits,
+        }
+    }
+
     /// Get a stop order by ID.
     pub fn get(&self, order_id: OrderId) -> Option<&StopOrder> {
         self.orders.get(&order_id)
     }
 
+    /// Get a mutable reference to a stop order by ID.
+    pub(crate) fn get_mut(&mut self, order_id: OrderId) -> Option<&mut StopOrder> {
+        self.orders.get_mut(&order_id)
+    }
+
     /// Returns true if there are no pending stop orders.
     pub fn is_empty(&self) -> bool {
-        self.buy_stops.is_empty() && self.sell_stops.is_empty()
+        self.buy_stops.is_empty()
+            && self.sell_stops.is_empty()
+            && self.buy_mits.is_empty()
+            && self.sell_mits.is_empty()
     }
 
-    /// Returns the number of pending stop orders.
+    /// Returns the number of pending stop orders (MITs included).
     pub fn pending_count(&self) -> usize {
-        self.buy_stops.values().map(|v| v.len()).sum::<usize>()
-            + self.sell_stops.values().map(|v| v.len()).sum::<usize>()
+        [
+            &self.buy_stops,
+            &self.sell_stops,
+            &self.buy_mits,
+            &self.sell_mits,
+        ]
+        .iter()
+        .flat_map(|map| map.values())
+        .map(|v| v.len())
+        .sum()
     }
 
     /// Record a trade price for ATR computation and update trailing stops.
@@ -320,6 +430,9 @@ impl StopBook {
                 .expect("invariant: trailing order exists in book");
             if should_update {
                 order.stop_price = new_stop;
+                if let Some(limit_offset) = order.limit_offset {
+                    order.limit_price = Some(capped_limit(side, new_stop, limit_offset));
+                }
             }
             order.watermark = Some(new_watermark);
         }
@@ -370,6 +483,9 @@ mod tests {
             status: StopStatus::Pending,
             trail_method: None,
             watermark: None,
+            max_slippage: None,
+            limit_offset: None,
+            kind: ConditionalKind::Stop,
         }
     }
 
@@ -392,6 +508,9 @@ mod tests {
             status: StopStatus::Pending,
             trail_method: Some(method),
             watermark: None,
+            max_slippage: None,
+            limit_offset: None,
+            kind: ConditionalKind::Stop,
         }
     }
 
@@ -510,6 +629,34 @@ mod tests {
         assert_eq!(triggered[2].id, OrderId(3));
     }
 
+    #[test]
+    fn if_touched_triggers_opposite_to_stops() {
+        let mut book = StopBook::new();
+        let mit = |id, side, price, ts| StopOrder {
+            kind: ConditionalKind::IfTouched,
+            ..make_stop(id, side, price, 50, ts)
+        };
+        book.insert(mit(1, Side::Buy, 98_00, 1));
+        book.insert(mit(2, Side::Sell, 102_00, 2));
+        book.insert(make_stop(3, Side::Buy, 98_00, 50, 3));
+        assert_eq!(book.pending_count(), 3);
+
+        // A rise to 101 fires the buy stop at 98 but neither MIT.
+        let triggered = book.collect_triggered(Price(101_00));
+        assert_eq!(triggered.len(), 1);
+        assert_eq!(triggered[0].id, OrderId(3));
+
+        // Buy MIT fires at or below its trigger, sell MIT at or above.
+        let triggered = book.collect_triggered(Price(98_00));
+        assert_eq!(triggered.len(), 1);
+        assert_eq!(triggered[0].id, OrderId(1));
+        assert!(!book.get(OrderId(2)).unwrap().is_triggered_by(Price(101_99)));
+        assert!(book.get(OrderId(2)).unwrap().is_triggered_by(Price(102_00)));
+
+        assert!(book.cancel(OrderId(2)));
+        assert!(book.is_empty());
+    }
+
     #[test]
     fn clear_history() {
         let mut book = StopBook::new();
@@ -556,6 +703,9 @@ mod tests {
             status: StopStatus::Pending,
             trail_method: None,
             watermark: None,
+            max_slippage: None,
+            limit_offset: None,
+            kind: ConditionalKind::Stop,
         };
         book.insert(stop);
 
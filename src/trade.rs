@@ -1,6 +1,7 @@
 //! Trade representation
 
 use crate::{OrderId, Price, Quantity, Side, Timestamp, TradeId};
+use std::collections::BTreeMap;
 use std::fmt;
 
 /// A completed trade between two orders.
@@ -89,6 +90,144 @@ impl Trade {
         let total_notional: i64 = trades.iter().map(|t| t.price.0 * t.quantity as i64).sum();
         Some(Price(total_notional / total_qty as i64))
     }
+
+    /// VWAP of the trades with `start_ts <= timestamp < end_ts`.
+    ///
+    /// Returns `None` if no trade falls in the window.
+    pub fn vwap_in_window(
+        trades: &[Trade],
+        start_ts: Timestamp,
+        end_ts: Timestamp,
+    ) -> Option<Price> {
+        let window: Vec<Trade> = trades
+            .iter()
+            .filter(|t| (start_ts..end_ts).contains(&t.timestamp))
+            .cloned()
+            .collect();
+        Self::vwap(&window)
+    }
+
+    /// Implementation shortfall of an execution: the executed VWAP versus
+    /// the arrival (decision) price, in basis points.
+    ///
+    /// Signed as a cost for an order on `side`: positive when buys paid more
+    /// or sells received less than `arrival_price`, negative when the fills
+    /// beat it. The VWAP is not rounded to cents. Returns `None` for an
+    /// empty execution or a non-positive arrival price.
+    ///
+    /// ```
+    /// use nanobook::{Trade, Price, TradeId, OrderId, Side};
+    ///
+    /// let fills = vec![
+    ///     Trade::new(TradeId(1), Price(100_00), 100, OrderId(1), OrderId(2), Side::Buy, 1),
+    ///     Trade::new(TradeId(2), Price(100_20), 100, OrderId(1), OrderId(3), Side::Buy, 2),
+    /// ];
+    /// // VWAP 100.10 against a 100.00 arrival: 10 bps paid.
+    /// let bps = Trade::slippage_vs_arrival(&fills, Side::Buy, Price(100_00)).unwrap();
+    /// assert!((bps - 10.0).abs() < 1e-9);
+    /// ```
+    pub fn slippage_vs_arrival(trades: &[Trade], side: Side, arrival_price: Price) -> Option<f64> {
+        let quantity: u64 = trades.iter().map(|t| t.quantity).sum();
+        if quantity == 0 || arrival_price.0 <= 0 {
+            return None;
+        }
+        let notional: i128 = trades
+            .iter()
+            .map(|t| t.price.0 as i128 * t.quantity as i128)
+            .sum();
+        let vwap = notional as f64 / quantity as f64;
+        let arrival = arrival_price.0 as f64;
+        let shortfall = match side {
+            Side::Buy => vwap - arrival,
+            Side::Sell => arrival - vwap,
+        };
+        Some(shortfall / arrival * 10_000.0)
+    }
+
+    /// Aggregate a trade series into OHLCV bars of `bar_size_ts` timestamp
+    /// ticks.
+    ///
+    /// Each bar covers `[start, start + bar_size_ts)` with `start` a multiple
+    /// of `bar_size_ts`. Windows without trades produce no bar (no
+    /// forward-fill), and bars come back in time order. Open and close are
+    /// the first and last trades of the window in slice order. Returns an
+    /// empty vector for an empty slice or a zero bar size.
+    ///
+    /// ```
+    /// use nanobook::{Trade, Price, TradeId, OrderId, Side};
+    ///
+    /// let trade = |id, price, ts| {
+    ///     Trade::new(TradeId(id), Price(price), 10, OrderId(1), OrderId(2), Side::Buy, ts)
+    /// };
+    /// let trades = vec![trade(1, 100_00, 3), trade(2, 101_00, 7), trade(3, 99_00, 12)];
+    ///
+    /// let bars = Trade::to_ohlcv_bars(&trades, 10);
+    /// assert_eq!(bars.len(), 2);
+    /// assert_eq!((bars[0].open, bars[0].close), (Price(100_00), Price(101_00)));
+    /// assert_eq!(bars[1].start, 10);
+    /// ```
+    pub fn to_ohlcv_bars(trades: &[Trade], bar_size_ts: u64) -> Vec<OhlcvBar> {
+        if bar_size_ts == 0 {
+            return Vec::new();
+        }
+
+        let mut bars: BTreeMap<Timestamp, (OhlcvBar, i64)> = BTreeMap::new();
+        for trade in trades {
+            let start = trade.timestamp - trade.timestamp % bar_size_ts;
+            let (bar, notional) = bars.entry(start).or_insert_with(|| {
+                (
+                    OhlcvBar {
+                        start,
+                        open: trade.price,
+                        high: trade.price,
+                        low: trade.price,
+                        close: trade.price,
+                        volume: 0,
+                        vwap: trade.price,
+                        trade_count: 0,
+                    },
+                    0,
+                )
+            });
+            bar.high = bar.high.max(trade.price);
+            bar.low = bar.low.min(trade.price);
+            bar.close = trade.price;
+            bar.volume += trade.quantity;
+            bar.trade_count += 1;
+            *notional += trade.notional();
+        }
+
+        bars.into_values()
+            .map(|(mut bar, notional)| {
+                if bar.volume > 0 {
+                    bar.vwap = Price(notional / bar.volume as i64);
+                }
+                bar
+            })
+            .collect()
+    }
+}
+
+/// One OHLCV bar built by [`Trade::to_ohlcv_bars`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OhlcvBar {
+    /// First timestamp of the window (a multiple of the bar size)
+    pub start: Timestamp,
+    /// Price of the first trade in the window
+    pub open: Price,
+    /// Highest trade price
+    pub high: Price,
+    /// Lowest trade price
+    pub low: Price,
+    /// Price of the last trade in the window
+    pub close: Price,
+    /// Total quantity traded
+    pub volume: Quantity,
+    /// Volume-weighted average price (truncated to whole cents)
+    pub vwap: Price,
+    /// Number of trades in the window
+    pub trade_count: usize,
 }
 
 impl fmt::Display for Trade {
@@ -212,4 +351,120 @@ mod tests {
     fn vwap_empty() {
         assert_eq!(Trade::vwap(&[]), None);
     }
+
+    fn fill(price: i64, quantity: Quantity, timestamp: Timestamp) -> Trade {
+        Trade::new(
+            TradeId(timestamp),
+            Price(price),
+            quantity,
+            OrderId(1),
+            OrderId(2),
+            Side::Buy,
+            timestamp,
+        )
+    }
+
+    #[test]
+    fn vwap_in_window_is_half_open() {
+        let trades = vec![
+            fill(100_00, 100, 1),
+            fill(102_00, 100, 5),
+            fill(110_00, 100, 10),
+        ];
+        assert_eq!(Trade::vwap_in_window(&trades, 1, 10), Some(Price(101_00)));
+        assert_eq!(Trade::vwap_in_window(&trades, 5, 11), Some(Price(106_00)));
+        assert_eq!(Trade::vwap_in_window(&trades, 2, 5), None);
+        assert_eq!(Trade::vwap_in_window(&[], 0, 100), None);
+    }
+
+    #[test]
+    fn slippage_vs_arrival_signs_cost_by_side() {
+        let at_arrival = vec![fill(100_00, 100, 1), fill(100_00, 50, 2)];
+        assert_eq!(
+            Trade::slippage_vs_arrival(&at_arrival, Side::Buy, Price(100_00)),
+            Some(0.0)
+        );
+
+        // Buying above arrival and selling below it both cost 50 bps.
+        let higher = vec![fill(100_50, 100, 1)];
+        let lower = vec![fill(99_50, 100, 1)];
+        let buy = Trade::slippage_vs_arrival(&higher, Side::Buy, Price(100_00)).unwrap();
+        let sell = Trade::slippage_vs_arrival(&lower, Side::Sell, Price(100_00)).unwrap();
+        assert!((buy - 50.0).abs() < 1e-9);
+        assert!((sell - 50.0).abs() < 1e-9);
+
+        // Favorable fills are negative cost.
+        let improved = Trade::slippage_vs_arrival(&lower, Side::Buy, Price(100_00)).unwrap();
+        assert!((improved + 50.0).abs() < 1e-9);
+
+        assert_eq!(
+            Trade::slippage_vs_arrival(&[], Side::Buy, Price(100_00)),
+            None
+        );
+    }
+
+    // === OHLCV tests ===
+
+    fn tape_trade(id: u64, price: i64, quantity: Quantity, timestamp: Timestamp) -> Trade {
+        Trade::new(
+            TradeId(id),
+            Price(price),
+            quantity,
+            OrderId(id * 2),
+            OrderId(id * 2 + 1),
+            Side::Buy,
+            timestamp,
+        )
+    }
+
+    #[test]
+    fn ohlcv_single_window() {
+        let trades = vec![
+            tape_trade(1, 100_00, 100, 100),
+            tape_trade(2, 102_00, 50, 105),
+            tape_trade(3, 99_00, 50, 109),
+        ];
+        let bars = Trade::to_ohlcv_bars(&trades, 10);
+
+        assert_eq!(
+            bars,
+            vec![OhlcvBar {
+                start: 100,
+                open: Price(100_00),
+                high: Price(102_00),
+                low: Price(99_00),
+                close: Price(99_00),
+                volume: 200,
+                vwap: Price(100_25), // (100*100 + 102*50 + 99*50) / 200
+                trade_count: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn ohlcv_splits_at_window_boundary_and_skips_gaps() {
+        let trades = vec![
+            tape_trade(1, 100_00, 10, 8),
+            tape_trade(2, 101_00, 10, 9),
+            tape_trade(3, 103_00, 10, 10), // first tick of the next window
+            tape_trade(4, 104_00, 10, 35), // window 20..30 has no trades
+        ];
+        let bars = Trade::to_ohlcv_bars(&trades, 10);
+
+        let starts: Vec<_> = bars.iter().map(|b| b.start).collect();
+        assert_eq!(starts, vec![0, 10, 30]);
+        assert_eq!(
+            (bars[0].open, bars[0].close),
+            (Price(100_00), Price(101_00))
+        );
+        assert_eq!(bars[0].volume, 20);
+        assert_eq!(bars[1].open, Price(103_00));
+        assert_eq!(bars[1].trade_count, 1);
+    }
+
+    #[test]
+    fn ohlcv_empty_input() {
+        assert!(Trade::to_ohlcv_bars(&[], 10).is_empty());
+        assert!(Trade::to_ohlcv_bars(&[make_trade()], 0).is_empty());
+    }
 }
@@ -0,0 +1,340 @@
+// Allow our dollar.cents digit grouping convention (e.g., 100_00 = $100.00)
+#![allow(clippy::inconsistent_digit_grouping)]
+
+//! ITCH 5.0 writer round trips and MoldUDP64 unwrapping.
+
+#![cfg(feature = "itch")]
+
+use nanobook::itch::{
+    DEFAULT_STOCK, ItchMessage, ItchParser, MoldUdp64Decoder, SequenceGap, encode_message,
+    itch_to_event, parse_moldudp64, write_itch, write_itch_for,
+};
+use nanobook::{Event, OrderId, Price, Side, TimeInForce};
+
+fn parse(bytes: &[u8]) -> Vec<ItchMessage> {
+    let mut parser = ItchParser::new(bytes);
+    let mut messages = Vec::new();
+    while let Some(msg) = parser.next_message().unwrap() {
+        messages.push(msg);
+    }
+    messages
+}
+
+fn sample_messages() -> Vec<ItchMessage> {
+    vec![
+        ItchMessage::AddOrder {
+            timestamp: 34_200_000_000_000,
+            order_ref: 7,
+            side: Side::Buy,
+            shares: 100,
+            stock: "AAPL".into(),
+            price: 150_2500,
+        },
+        ItchMessage::OrderExecuted {
+            timestamp: 2,
+            order_ref: 7,
+            shares: 40,
+            match_number: 11,
+        },
+        ItchMessage::OrderExecutedWithPrice {
+            timestamp: 3,
+            order_ref: 7,
+            shares: 10,
+            match_number: 12,
+            printable: true,
+            price: 150_2600,
+        },
+        ItchMessage::OrderCancel {
+            timestamp: 4,
+            order_ref: 7,
+            shares: 5,
+        },
+        ItchMessage::OrderReplace {
+            timestamp: 5,
+            old_order_ref: 7,
+            new_order_ref: 8,
+            shares: 45,
+            price: 150_1000,
+        },
+        ItchMessage::OrderDelete {
+            timestamp: 6,
+            order_ref: 8,
+        },
+        ItchMessage::Trade {
+            timestamp: 7,
+            side: Side::Sell,
+            shares: 300,
+            stock: "MSFT".into(),
+            price: 410_0000,
+            match_number: 13,
+        },
+    ]
+}
+
+#[test]
+fn frames_have_spec_lengths() {
+    // Message sizes from the ITCH 5.0 spec, plus the 2-byte length prefix.
+    let sizes: Vec<usize> = sample_messages()
+        .iter()
+        .map(|m| encode_message(m).unwrap().len() - 2)
+        .collect();
+    assert_eq!(sizes, vec![36, 31, 36, 23, 35, 19, 44]);
+
+    let add = encode_message(&sample_messages()[0]).unwrap();
+    assert_eq!(&add[..3], &[0, 36, b'A']);
+    assert_eq!(&add[3..5], &1u16.to_be_bytes()); // stock locate
+    assert_eq!(&add[7..13], &34_200_000_000_000u64.to_be_bytes()[2..]);
+    assert_eq!(&add[26..34], b"AAPL    ");
+}
+
+#[test]
+fn every_supported_message_round_trips() {
+    let messages = sample_messages();
+    let bytes: Vec<u8> = messages
+        .iter()
+        .flat_map(|m| encode_message(m).unwrap())
+        .collect();
+    assert_eq!(parse(&bytes), messages);
+}
+
+#[test]
+fn unsupported_messages_are_not_encoded() {
+    assert!(encode_message(&ItchMessage::Other('S')).is_none());
+}
+
+#[test]
+fn resting_events_round_trip_through_parser() {
+    let events = vec![
+        Event::submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC),
+        Event::submit_limit(Side::Sell, Price(101_00), 50, TimeInForce::GTC),
+        Event::submit_limit(Side::Buy, Price(98_50), 30, TimeInForce::GTC),
+        Event::modify(OrderId(1), Price(99_50), 80), // becomes order 4
+        Event::cancel(OrderId(2)),
+        Event::cancel(OrderId(4)),
+    ];
+
+    let parsed: Vec<(String, Event)> = parse(&write_itch(&events))
+        .into_iter()
+        .filter_map(itch_to_event)
+        .collect();
+
+    assert_eq!(
+        parsed.iter().map(|(_, e)| e.clone()).collect::<Vec<_>>(),
+        events
+    );
+    assert_eq!(parsed[0].0, DEFAULT_STOCK);
+}
+
+#[test]
+fn crossing_order_emits_executions_then_remainder() {
+    let events = vec![
+        Event::submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC),
+        Event::submit_limit(Side::Sell, Price(101_00), 30, TimeInForce::GTC),
+        Event::submit_limit(Side::Buy, Price(101_00), 80, TimeInForce::GTC),
+        Event::submit_market(Side::Sell, 5),
+    ];
+
+    let messages = parse(&write_itch_for("TEST", &events));
+    let summary: Vec<(char, u64, u32)> = messages
+        .iter()
+        .map(|m| match m {
+            ItchMessage::AddOrder {
+                order_ref, shares, ..
+            } => ('A', *order_ref, *shares),
+            ItchMessage::OrderExecuted {
+                order_ref, shares, ..
+            } => ('E', *order_ref, *shares),
+            other => panic!("unexpected {other:?}"),
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            ('A', 1, 30),
+            ('A', 2, 30),
+            ('E', 1, 30),
+            ('E', 2, 30),
+            ('A', 3, 20), // only the resting remainder is displayed
+            ('E', 3, 5),  // the market order itself never displays
+        ]
+    );
+}
+
+#[test]
+fn ioc_remainder_and_failed_cancel_emit_nothing() {
+    let events = vec![
+        Event::submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::IOC),
+        Event::cancel(OrderId(1)),
+    ];
+    assert!(write_itch(&events).is_empty());
+}
+
+#[test]
+fn crossing_modify_cancels_old_order() {
+    let events = vec![
+        Event::submit_limit(Side::Sell, Price(101_00), 10, TimeInForce::GTC),
+        Event::submit_limit(Side::Buy, Price(99_00), 25, TimeInForce::GTC),
+        Event::modify(OrderId(2), Price(101_00), 25), // crosses, becomes order 3
+    ];
+
+    let messages = parse(&write_itch(&events));
+    assert!(matches!(
+        messages[2],
+        ItchMessage::OrderCancel {
+            order_ref: 2,
+            shares: 25,
+            ..
+        }
+    ));
+    assert!(matches!(
+        messages[3],
+        ItchMessage::OrderExecuted {
+            order_ref: 1,
+            shares: 10,
+            ..
+        }
+    ));
+    assert!(matches!(
+        messages[4],
+        ItchMessage::AddOrder {
+            order_ref: 3,
+            shares: 15,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn in_place_reduction_is_a_partial_cancel() {
+    let events = vec![
+        Event::submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC),
+        Event::reduce_quantity(OrderId(1), 30),
+    ];
+
+    let messages = parse(&write_itch(&events));
+    assert_eq!(messages.len(), 2);
+    assert!(matches!(
+        messages[1],
+        ItchMessage::OrderCancel {
+            order_ref: 1,
+            shares: 70,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn auction_fills_execute_both_displayed_orders() {
+    let events = vec![
+        Event::BeginAuction,
+        Event::submit_limit(Side::Buy, Price(101_00), 100, TimeInForce::GTC),
+        Event::submit_limit(Side::Sell, Price(100_00), 60, TimeInForce::GTC),
+        Event::RunAuction,
+    ];
+
+    let messages = parse(&write_itch(&events));
+    let executed: Vec<(u64, u32)> = messages
+        .iter()
+        .filter_map(|m| match m {
+            ItchMessage::OrderExecuted {
+                order_ref, shares, ..
+            } => Some((*order_ref, *shares)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(executed, vec![(1, 60), (2, 60)]);
+}
+
+/// A MoldUDP64 downstream packet carrying `messages` starting at `sequence`.
+fn mold_packet(sequence: u64, messages: &[ItchMessage]) -> Vec<u8> {
+    let mut out = b"SESSION001".to_vec();
+    out.extend_from_slice(&sequence.to_be_bytes());
+    out.extend_from_slice(&(messages.len() as u16).to_be_bytes());
+    for msg in messages {
+        out.extend(encode_message(msg).unwrap());
+    }
+    out
+}
+
+fn add(order_ref: u64, price: u32) -> ItchMessage {
+    ItchMessage::AddOrder {
+        timestamp: order_ref,
+        order_ref,
+        side: Side::Buy,
+        shares: 100,
+        stock: "AAPL".into(),
+        price,
+    }
+}
+
+#[test]
+fn moldudp64_unwraps_packets_and_surfaces_gap() {
+    let first = mold_packet(1, &[add(1, 99_0000), add(2, 98_0000)]);
+    // Sequence 3 is missing: the next packet starts at 4.
+    let second = mold_packet(4, &[add(4, 97_0000)]);
+
+    let mut decoder = MoldUdp64Decoder::new();
+    let mut messages = decoder.decode(&first).unwrap();
+    messages.extend(decoder.decode(&second).unwrap());
+
+    assert_eq!(
+        messages,
+        vec![add(1, 99_0000), add(2, 98_0000), add(4, 97_0000)]
+    );
+    assert_eq!(
+        decoder.gaps(),
+        &[SequenceGap {
+            expected: 3,
+            received: 4
+        }]
+    );
+    assert_eq!(decoder.next_sequence(), Some(5));
+
+    let events = parse_moldudp64(&[first, second].concat());
+    assert_eq!(
+        events,
+        vec![
+            Event::submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC),
+            Event::submit_limit(Side::Buy, Price(98_00), 100, TimeInForce::GTC),
+            Event::submit_limit(Side::Buy, Price(97_00), 100, TimeInForce::GTC),
+        ]
+    );
+}
+
+#[test]
+fn moldudp64_skips_retransmitted_messages() {
+    let mut decoder = MoldUdp64Decoder::new();
+    decoder
+        .decode(&mold_packet(10, &[add(10, 1_0000), add(11, 1_0000)]))
+        .unwrap();
+    // Overlaps the previous packet by one message.
+    let messages = decoder
+        .decode(&mold_packet(11, &[add(11, 1_0000), add(12, 1_0000)]))
+        .unwrap();
+
+    assert_eq!(messages, vec![add(12, 1_0000)]);
+    assert_eq!(
+        decoder.gaps(),
+        &[SequenceGap {
+            expected: 12,
+            received: 11
+        }]
+    );
+}
+
+#[test]
+fn moldudp64_heartbeat_and_truncation() {
+    let mut decoder = MoldUdp64Decoder::new();
+    decoder.decode(&mold_packet(1, &[add(1, 1_0000)])).unwrap();
+    // A heartbeat announcing sequence 5 reveals messages 2..5 were lost.
+    assert!(decoder.decode(&mold_packet(5, &[])).unwrap().is_empty());
+    assert_eq!(decoder.gaps()[0].received, 5);
+
+    let mut truncated = mold_packet(5, &[add(5, 1_0000)]);
+    truncated.truncate(truncated.len() - 1);
+    assert!(decoder.decode(&truncated).is_err());
+
+    let good = mold_packet(1, &[add(1, 1_0000)]);
+    assert_eq!(parse_moldudp64(&[good, truncated].concat()).len(), 1);
+}
@@ -0,0 +1,217 @@
+// Allow our dollar.cents digit grouping convention (e.g., 100_00 = $100.00)
+#![allow(clippy::inconsistent_digit_grouping)]
+
+//! OUCH 4.2 parsing against hand-built SoupBinTCP frames.
+
+#![cfg(feature = "ouch")]
+
+use nanobook::ouch::{OuchMessage, OuchParser, parse_ouch};
+use nanobook::{Event, Exchange, OrderId, Price, Side, TimeInForce};
+
+/// Wrap an OUCH message in a SoupBinTCP packet of type `packet`.
+fn soup(packet: u8, msg: &[u8]) -> Vec<u8> {
+    let mut out = ((msg.len() + 1) as u16).to_be_bytes().to_vec();
+    out.push(packet);
+    out.extend_from_slice(msg);
+    out
+}
+
+fn token(t: &str) -> [u8; 14] {
+    let mut out = [b' '; 14];
+    out[..t.len()].copy_from_slice(t.as_bytes());
+    out
+}
+
+fn enter(tok: &str, side: u8, shares: u32, price: u32, tif: u32) -> Vec<u8> {
+    let mut msg = vec![b'O'];
+    msg.extend_from_slice(&token(tok));
+    msg.push(side);
+    msg.extend_from_slice(&shares.to_be_bytes());
+    msg.extend_from_slice(b"AAPL    ");
+    msg.extend_from_slice(&price.to_be_bytes());
+    msg.extend_from_slice(&tif.to_be_bytes());
+    msg.extend_from_slice(b"FIRM"); // firm
+    msg.extend_from_slice(b"YP"); // display, capacity
+    msg.push(b'N'); // intermarket sweep
+    msg.extend_from_slice(&0u32.to_be_bytes()); // minimum quantity
+    msg.extend_from_slice(b"NR"); // cross type, customer type
+    soup(b'U', &msg)
+}
+
+fn replace(existing: &str, replacement: &str, shares: u32, price: u32) -> Vec<u8> {
+    let mut msg = vec![b'U'];
+    msg.extend_from_slice(&token(existing));
+    msg.extend_from_slice(&token(replacement));
+    msg.extend_from_slice(&shares.to_be_bytes());
+    msg.extend_from_slice(&price.to_be_bytes());
+    msg.extend_from_slice(&99_999u32.to_be_bytes()); // time in force
+    msg.extend_from_slice(b"YN");
+    msg.extend_from_slice(&0u32.to_be_bytes());
+    soup(b'U', &msg)
+}
+
+fn cancel(tok: &str, shares: u32) -> Vec<u8> {
+    let mut msg = vec![b'X'];
+    msg.extend_from_slice(&token(tok));
+    msg.extend_from_slice(&shares.to_be_bytes());
+    soup(b'U', &msg)
+}
+
+fn accepted(tok: &str, side: u8, shares: u32, price: u32) -> Vec<u8> {
+    let mut msg = vec![b'A'];
+    msg.extend_from_slice(&1_000u64.to_be_bytes());
+    msg.extend_from_slice(&token(tok));
+    msg.push(side);
+    msg.extend_from_slice(&shares.to_be_bytes());
+    msg.extend_from_slice(b"AAPL    ");
+    msg.extend_from_slice(&price.to_be_bytes());
+    msg.extend_from_slice(&99_999u32.to_be_bytes());
+    msg.extend_from_slice(b"FIRM");
+    msg.push(b'Y');
+    msg.extend_from_slice(&77u64.to_be_bytes()); // order reference number
+    msg.extend_from_slice(b"AN");
+    msg.extend_from_slice(&0u32.to_be_bytes());
+    msg.extend_from_slice(b"NLN"); // cross type, order state, BBO weight
+    soup(b'S', &msg)
+}
+
+fn executed(tok: &str, shares: u32, price: u32) -> Vec<u8> {
+    let mut msg = vec![b'E'];
+    msg.extend_from_slice(&2_000u64.to_be_bytes());
+    msg.extend_from_slice(&token(tok));
+    msg.extend_from_slice(&shares.to_be_bytes());
+    msg.extend_from_slice(&price.to_be_bytes());
+    msg.push(b'A');
+    msg.extend_from_slice(&5u64.to_be_bytes());
+    soup(b'S', &msg)
+}
+
+fn session(frames: &[Vec<u8>]) -> Vec<u8> {
+    frames.concat()
+}
+
+#[test]
+fn message_sizes_match_spec() {
+    assert_eq!(enter("T1", b'B', 1, 1, 0).len(), 2 + 1 + 49);
+    assert_eq!(replace("T1", "T2", 1, 1).len(), 2 + 1 + 47);
+    assert_eq!(cancel("T1", 0).len(), 2 + 1 + 19);
+    assert_eq!(accepted("T1", b'B', 1, 1).len(), 2 + 1 + 66);
+    assert_eq!(executed("T1", 1, 1).len(), 2 + 1 + 40);
+}
+
+#[test]
+fn parses_enter_order_fields() {
+    let data = enter("ORD1", b'S', 300, 100_2500, 99_999);
+    let mut parser = OuchParser::new(&data);
+    assert_eq!(
+        parser.next_message().unwrap(),
+        Some(OuchMessage::EnterOrder {
+            token: "ORD1".into(),
+            side: Side::Sell,
+            shares: 300,
+            stock: "AAPL".into(),
+            price: 100_2500,
+            time_in_force: 99_999,
+        })
+    );
+    assert_eq!(parser.next_message().unwrap(), None);
+}
+
+#[test]
+fn enter_replace_cancel_become_submit_modify_cancel() {
+    let data = session(&[
+        soup(b'+', b"debug text"), // non-data packet, skipped
+        enter("B1", b'B', 100, 99_0000, 99_999),
+        accepted("B1", b'B', 100, 99_0000), // already submitted
+        enter("S1", b'S', 50, 101_0000, 99_999),
+        replace("B1", "B2", 80, 99_5000),
+        cancel("S1", 0),
+        cancel("B2", 30), // partial cancel down to 30
+    ]);
+
+    assert_eq!(
+        parse_ouch(&data),
+        vec![
+            Event::submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC),
+            Event::submit_limit(Side::Sell, Price(101_00), 50, TimeInForce::GTC),
+            Event::modify(OrderId(1), Price(99_50), 80),
+            Event::cancel(OrderId(2)),
+            Event::modify(OrderId(3), Price(99_50), 30),
+        ]
+    );
+}
+
+#[test]
+fn events_replay_into_exchange() {
+    let data = session(&[
+        enter("B1", b'B', 100, 99_0000, 99_999),
+        enter("B2", b'B', 40, 98_0000, 99_999),
+        replace("B1", "B3", 80, 99_5000),
+        cancel("B2", 0),
+    ]);
+
+    let exchange = Exchange::replay(&parse_ouch(&data));
+
+    assert_eq!(exchange.best_bid(), Some(Price(99_50)));
+    assert!(
+        exchange
+            .get_order(OrderId(3))
+            .is_some_and(|o| o.is_active())
+    );
+    assert!(!exchange.get_order(OrderId(2)).unwrap().is_active());
+}
+
+#[test]
+fn accepted_submits_when_inbound_side_is_missing() {
+    let data = session(&[accepted("A1", b'T', 25, 50_0000), cancel("A1", 0)]);
+    assert_eq!(
+        parse_ouch(&data),
+        vec![
+            Event::submit_limit(Side::Sell, Price(50_00), 25, TimeInForce::GTC),
+            Event::cancel(OrderId(1)),
+        ]
+    );
+}
+
+#[test]
+fn ioc_time_in_force_maps_to_ioc() {
+    let events = parse_ouch(&enter("I1", b'B', 10, 10_0000, 0));
+    assert_eq!(
+        events,
+        vec![Event::submit_limit(
+            Side::Buy,
+            Price(10_00),
+            10,
+            TimeInForce::IOC
+        )]
+    );
+}
+
+#[test]
+fn cancel_after_full_execution_is_dropped() {
+    let data = session(&[
+        enter("F1", b'B', 100, 99_0000, 99_999),
+        executed("F1", 100, 99_0000),
+        cancel("F1", 0),
+        cancel("UNKNOWN", 0),
+    ]);
+    assert_eq!(parse_ouch(&data).len(), 1);
+}
+
+#[test]
+fn truncated_frame_stops_parsing() {
+    let mut data = session(&[enter("B1", b'B', 100, 99_0000, 99_999), cancel("B1", 0)]);
+    data.truncate(data.len() - 3);
+
+    assert_eq!(parse_ouch(&data).len(), 1);
+
+    let mut parser = OuchParser::new(&data);
+    assert!(parser.next_message().unwrap().is_some());
+    assert!(parser.next_message().is_err());
+}
+
+#[test]
+fn short_message_is_an_error() {
+    let data = soup(b'U', b"X0123");
+    assert!(OuchParser::new(&data).next_message().is_err());
+}
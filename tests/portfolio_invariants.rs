@@ -41,6 +41,8 @@ fn equity_decreases_with_costs() {
         commission_bps: 10,
         slippage_bps: 5,
         min_trade_fee: 0,
+        size_impact: None,
+        fee_tiers: None,
     };
     let mut portfolio = Portfolio::new(1_000_000_00, model);
     let prices = [(aapl(), 150_00)];
@@ -192,6 +194,8 @@ fn cost_model_non_negative() {
         commission_bps: 100,
         slippage_bps: 50,
         min_trade_fee: 5_00,
+        size_impact: None,
+        fee_tiers: None,
     };
 
     for notional in &[0, 100, 1_000, 1_000_000, -500_000] {
@@ -208,6 +212,8 @@ fn cost_model_min_fee_floor() {
         commission_bps: 1,
         slippage_bps: 0,
         min_trade_fee: 10_00, // $10 minimum
+        size_impact: None,
+        fee_tiers: None,
     };
 
     // Small trade: bps cost < min fee → min fee wins
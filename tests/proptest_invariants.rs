@@ -606,6 +606,8 @@ mod portfolio_props {
                 commission_bps,
                 slippage_bps,
                 min_trade_fee: min_fee,
+                size_impact: None,
+                fee_tiers: None,
             };
             let cost = model.compute_cost(notional);
             prop_assert!(cost >= 0, "negative cost: {}", cost);